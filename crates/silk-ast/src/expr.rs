@@ -1,20 +1,65 @@
-use crate::{Pattern, Type};
+use crate::{ensure_sufficient_stack, NodeId, Pattern, Type};
 /// Expression AST nodes
 use silk_lexer::{FStringPart, Span};
+use std::fmt;
 
 /// Expression node with source location
-#[derive(Debug, Clone, PartialEq)]
+///
+/// `Clone`, `Debug` and `PartialEq` are written out rather than derived so
+/// that they grow the stack on long chains, like the passes over the tree
+/// (see [`crate::stack`]).
 pub struct Expression {
     pub kind: ExpressionKind,
     pub span: Span,
+    /// Assigned by the parser; `NodeId::DUMMY` for nodes built elsewhere
+    pub id: NodeId,
 }
 
 impl Expression {
     pub fn new(kind: ExpressionKind, span: Span) -> Self {
-        Self { kind, span }
+        Self::with_id(kind, span, NodeId::DUMMY)
+    }
+
+    pub fn with_id(kind: ExpressionKind, span: Span, id: NodeId) -> Self {
+        Self { kind, span, id }
     }
 }
 
+impl Clone for Expression {
+    fn clone(&self) -> Self {
+        ensure_sufficient_stack(|| Self {
+            kind: self.kind.clone(),
+            span: self.span,
+            id: self.id,
+        })
+    }
+}
+
+impl fmt::Debug for Expression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        ensure_sufficient_stack(|| {
+            f.debug_struct("Expression")
+                .field("kind", &self.kind)
+                .field("span", &self.span)
+                .field("id", &self.id)
+                .finish()
+        })
+    }
+}
+
+impl PartialEq for Expression {
+    fn eq(&self, other: &Self) -> bool {
+        ensure_sufficient_stack(|| {
+            self.kind == other.kind && self.span == other.span && self.id == other.id
+        })
+    }
+}
+
+// Expressions make up most of an AST; a variant with a large payload
+// should box it so this stays true
+#[cfg(target_pointer_width = "64")]
+const _: () = assert!(std::mem::size_of::<Expression>() <= 104);
+
 /// All expression kinds in Silk
 #[derive(Debug, Clone, PartialEq)]
 pub enum ExpressionKind {
@@ -180,6 +225,7 @@ pub enum BinaryOperator {
     FloorDiv, // //
     Mod,      // %
     Pow,      // **
+    MatMult,  // @
     BitOr,    // |
     BitXor,   // ^
     BitAnd,   // &
@@ -0,0 +1,154 @@
+/// Unstable feature gates
+///
+/// In-progress syntax and semantics ship behind named feature gates so they can
+/// be tried out without becoming part of the stable language. Both the parser
+/// and the semantic analyzer consult a `FeatureSet` before accepting a gated
+/// construct.
+///
+/// Features are enabled for a whole build (`--unstable-features`) or for one
+/// file, with a pragma comment before its first statement:
+///
+/// ```text
+/// # feature: comprehension-assignments
+/// ```
+use std::collections::BTreeSet;
+use std::fmt;
+use thiserror::Error;
+
+/// A named unstable language feature
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Feature {
+    /// Assignment expressions in comprehension iterables, and walrus targets
+    /// inside comprehensions binding in the enclosing scope (PEP 572)
+    ComprehensionAssignments,
+}
+
+impl Feature {
+    /// All known unstable features
+    pub const ALL: &'static [Feature] = &[Feature::ComprehensionAssignments];
+
+    /// The name used on the command line (e.g. `comprehension-assignments`)
+    pub fn name(self) -> &'static str {
+        match self {
+            Feature::ComprehensionAssignments => "comprehension-assignments",
+        }
+    }
+
+    /// One-line description of what the feature enables
+    pub fn description(self) -> &'static str {
+        match self {
+            Feature::ComprehensionAssignments => {
+                "assignment expressions in comprehension iterables and enclosing-scope walrus targets"
+            }
+        }
+    }
+
+    /// Look up a feature by its command-line name
+    pub fn from_name(name: &str) -> Option<Feature> {
+        Feature::ALL.iter().copied().find(|f| f.name() == name)
+    }
+}
+
+impl fmt::Display for Feature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Release channel of the running toolchain
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReleaseChannel {
+    /// Stable builds refuse unstable features entirely
+    Stable,
+    /// Development builds accept any known unstable feature
+    Nightly,
+}
+
+impl ReleaseChannel {
+    /// Channel selected at build time via `SILK_RELEASE_CHANNEL=stable`
+    pub fn current() -> Self {
+        match option_env!("SILK_RELEASE_CHANNEL") {
+            Some("stable") => ReleaseChannel::Stable,
+            _ => ReleaseChannel::Nightly,
+        }
+    }
+}
+
+/// Errors produced while parsing an `--unstable-features` list
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum FeatureError {
+    #[error("Unknown unstable feature '{0}'")]
+    UnknownFeature(String),
+
+    #[error("Unstable feature '{0}' cannot be enabled on the stable release channel")]
+    StableChannel(Feature),
+}
+
+/// Marker of a feature pragma comment, after the `#`
+pub const PRAGMA_PREFIX: &str = "feature:";
+
+/// Set of enabled unstable features (empty by default)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FeatureSet {
+    enabled: BTreeSet<Feature>,
+}
+
+impl FeatureSet {
+    /// Create an empty feature set (stable language only)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse a comma-separated feature list such as `comprehension-assignments,...`
+    pub fn from_flag(list: &str, channel: ReleaseChannel) -> Result<Self, FeatureError> {
+        let mut features = Self::new();
+
+        for name in list.split(',').map(str::trim).filter(|n| !n.is_empty()) {
+            let feature = Feature::from_name(name)
+                .ok_or_else(|| FeatureError::UnknownFeature(name.to_string()))?;
+            if channel == ReleaseChannel::Stable {
+                return Err(FeatureError::StableChannel(feature));
+            }
+            features.enable(feature);
+        }
+
+        Ok(features)
+    }
+
+    /// Parse a `# feature: a, b` pragma comment; `None` if the comment is
+    /// not a pragma
+    pub fn from_pragma(
+        comment: &str,
+        channel: ReleaseChannel,
+    ) -> Option<Result<Self, FeatureError>> {
+        let text = comment.strip_prefix('#')?.trim_start();
+        let list = text.strip_prefix(PRAGMA_PREFIX)?;
+        Some(Self::from_flag(list, channel))
+    }
+
+    /// Enable a feature
+    pub fn enable(&mut self, feature: Feature) {
+        self.enabled.insert(feature);
+    }
+
+    /// Check whether a feature is enabled
+    pub fn is_enabled(&self, feature: Feature) -> bool {
+        self.enabled.contains(&feature)
+    }
+
+    /// Iterate over enabled features in a stable order
+    pub fn iter(&self) -> impl Iterator<Item = Feature> + '_ {
+        self.enabled.iter().copied()
+    }
+
+    /// Check whether no unstable features are enabled
+    pub fn is_empty(&self) -> bool {
+        self.enabled.is_empty()
+    }
+}
+
+impl Extend<Feature> for FeatureSet {
+    fn extend<I: IntoIterator<Item = Feature>>(&mut self, features: I) {
+        self.enabled.extend(features);
+    }
+}
@@ -0,0 +1,38 @@
+/// Node identifiers
+///
+/// The parser numbers every statement and expression it creates, so later
+/// phases (typed AST, control-flow graph, symbol references, source maps)
+/// can attach information to a node in a side table keyed by its `NodeId`
+/// instead of cloning the subtree or keying by span, which nested nodes can
+/// share (`x` in the expression statement `x`).
+///
+/// Ids are unique within one parse and below `Program::node_count`, so a
+/// side table can also be a `Vec` indexed by `NodeId::index`.
+use std::fmt;
+
+/// Identifier of a statement or expression, unique within its program
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NodeId(u32);
+
+impl NodeId {
+    /// Id of nodes built outside of the parser
+    pub const DUMMY: NodeId = NodeId(u32::MAX);
+
+    pub const fn new(index: u32) -> Self {
+        Self(index)
+    }
+
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+
+    pub fn is_dummy(self) -> bool {
+        self == Self::DUMMY
+    }
+}
+
+impl fmt::Display for NodeId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{}", self.0)
+    }
+}
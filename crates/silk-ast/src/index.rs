@@ -0,0 +1,445 @@
+/// Lookup of AST nodes by source position
+///
+/// Editor features start from a cursor offset and need the syntax around
+/// it: the expression under the cursor, the statement it belongs to, the
+/// definition a span refers to. `NodeIndex` walks a program once and keeps
+/// every statement and expression in source order, so those questions do
+/// not each need their own traversal. Nodes can also be looked up by the
+/// `NodeId` the parser gave them.
+use crate::{
+    ensure_sufficient_stack, Comprehension, Expression, ExpressionKind, FunctionParams, NodeId,
+    Pattern, PatternKind, Program, Statement, StatementKind,
+};
+use silk_lexer::Span;
+use std::collections::HashMap;
+
+/// A statement or expression of the indexed program
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Node<'a> {
+    Statement(&'a Statement),
+    Expression(&'a Expression),
+}
+
+impl<'a> Node<'a> {
+    pub fn span(&self) -> Span {
+        match self {
+            Node::Statement(stmt) => stmt.span,
+            Node::Expression(expr) => expr.span,
+        }
+    }
+
+    pub fn id(&self) -> NodeId {
+        match self {
+            Node::Statement(stmt) => stmt.id,
+            Node::Expression(expr) => expr.id,
+        }
+    }
+
+    pub fn as_statement(&self) -> Option<&'a Statement> {
+        match self {
+            Node::Statement(stmt) => Some(stmt),
+            Node::Expression(_) => None,
+        }
+    }
+
+    pub fn as_expression(&self) -> Option<&'a Expression> {
+        match self {
+            Node::Expression(expr) => Some(expr),
+            Node::Statement(_) => None,
+        }
+    }
+}
+
+/// Every statement and expression of a program, ordered by start offset;
+/// a node comes before the nodes nested in it
+#[derive(Debug, Clone)]
+pub struct NodeIndex<'a> {
+    nodes: Vec<Node<'a>>,
+    /// Position in `nodes` of each node with an id
+    by_id: HashMap<NodeId, usize>,
+}
+
+impl<'a> NodeIndex<'a> {
+    pub fn new(program: &'a Program) -> Self {
+        let mut nodes = Vec::new();
+        for stmt in &program.statements {
+            collect_statement(stmt, &mut nodes);
+        }
+        // Stable, so a parent and a child with the same span stay in
+        // walk order (`x` in the expression statement `x`)
+        nodes.sort_by_key(|node| (node.span().start, std::cmp::Reverse(node.span().end)));
+        let by_id = nodes
+            .iter()
+            .enumerate()
+            .filter(|(_, node)| !node.id().is_dummy())
+            .map(|(position, node)| (node.id(), position))
+            .collect();
+        Self { nodes, by_id }
+    }
+
+    /// The node with this id
+    pub fn get(&self, id: NodeId) -> Option<Node<'a>> {
+        self.by_id.get(&id).map(|&position| self.nodes[position])
+    }
+
+    /// Nodes containing an offset, outermost first
+    pub fn nodes_at(&self, offset: usize) -> Vec<Node<'a>> {
+        let end = self
+            .nodes
+            .partition_point(|node| node.span().start <= offset);
+        self.nodes[..end]
+            .iter()
+            .filter(|node| offset < node.span().end)
+            .copied()
+            .collect()
+    }
+
+    /// Innermost node containing an offset
+    pub fn node_at(&self, offset: usize) -> Option<Node<'a>> {
+        self.nodes_at(offset).pop()
+    }
+
+    /// Innermost expression containing an offset
+    pub fn expression_at(&self, offset: usize) -> Option<&'a Expression> {
+        self.nodes_at(offset)
+            .into_iter()
+            .rev()
+            .find_map(|node| node.as_expression())
+    }
+
+    /// Innermost statement containing an offset
+    pub fn statement_at(&self, offset: usize) -> Option<&'a Statement> {
+        self.nodes_at(offset)
+            .into_iter()
+            .rev()
+            .find_map(|node| node.as_statement())
+    }
+
+    /// The statement with exactly this span
+    pub fn statement(&self, span: Span) -> Option<&'a Statement> {
+        self.with_span(span).find_map(|node| node.as_statement())
+    }
+
+    /// The outermost expression with exactly this span
+    pub fn expression(&self, span: Span) -> Option<&'a Expression> {
+        self.with_span(span).find_map(|node| node.as_expression())
+    }
+
+    /// Every node, in order
+    pub fn nodes(&self) -> impl Iterator<Item = Node<'a>> + '_ {
+        self.nodes.iter().copied()
+    }
+
+    /// Nodes inside a span, including any with exactly that span, in order
+    pub fn nodes_within(&self, span: Span) -> impl Iterator<Item = Node<'a>> + '_ {
+        let first = self
+            .nodes
+            .partition_point(|node| node.span().start < span.start);
+        self.nodes[first..]
+            .iter()
+            .take_while(move |node| node.span().start < span.end)
+            .filter(move |node| node.span().end <= span.end)
+            .copied()
+    }
+
+    fn with_span(&self, span: Span) -> impl Iterator<Item = Node<'a>> + '_ {
+        let first = self
+            .nodes
+            .partition_point(|node| node.span().start < span.start);
+        self.nodes[first..]
+            .iter()
+            .take_while(move |node| node.span().start == span.start)
+            .filter(move |node| node.span() == span)
+            .copied()
+    }
+
+    /// Number of indexed nodes
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+}
+
+fn collect_block<'a>(body: &'a [Statement], nodes: &mut Vec<Node<'a>>) {
+    for stmt in body {
+        collect_statement(stmt, nodes);
+    }
+}
+
+fn collect_statement<'a>(stmt: &'a Statement, nodes: &mut Vec<Node<'a>>) {
+    ensure_sufficient_stack(|| collect_statement_nodes(stmt, nodes))
+}
+
+fn collect_statement_nodes<'a>(stmt: &'a Statement, nodes: &mut Vec<Node<'a>>) {
+    nodes.push(Node::Statement(stmt));
+    match &stmt.kind {
+        StatementKind::Expr(expr) => collect_expression(expr, nodes),
+        StatementKind::Assign { targets, value, .. } => {
+            collect_expressions(targets, nodes);
+            collect_expression(value, nodes);
+        }
+        StatementKind::AugAssign { target, value, .. } => {
+            collect_expression(target, nodes);
+            collect_expression(value, nodes);
+        }
+        StatementKind::AnnAssign { target, value, .. } => {
+            collect_expression(target, nodes);
+            collect_optional(value.as_ref(), nodes);
+        }
+        StatementKind::Assert { test, msg } => {
+            collect_expression(test, nodes);
+            collect_optional(msg.as_ref(), nodes);
+        }
+        StatementKind::Delete { targets } => collect_expressions(targets, nodes),
+        StatementKind::Return { value } => collect_optional(value.as_ref(), nodes),
+        StatementKind::Raise { exc, cause } => {
+            collect_optional(exc.as_ref(), nodes);
+            collect_optional(cause.as_ref(), nodes);
+        }
+        StatementKind::If { test, body, orelse } | StatementKind::While { test, body, orelse } => {
+            collect_expression(test, nodes);
+            collect_block(body, nodes);
+            collect_block(orelse, nodes);
+        }
+        StatementKind::For {
+            target,
+            iter,
+            body,
+            orelse,
+            ..
+        } => {
+            collect_pattern(target, nodes);
+            collect_expression(iter, nodes);
+            collect_block(body, nodes);
+            collect_block(orelse, nodes);
+        }
+        StatementKind::With { items, body, .. } => {
+            for item in items {
+                collect_expression(&item.context_expr, nodes);
+                collect_optional(item.optional_vars.as_ref(), nodes);
+            }
+            collect_block(body, nodes);
+        }
+        StatementKind::Match { subject, cases } => {
+            collect_expression(subject, nodes);
+            for case in cases {
+                collect_pattern(&case.pattern, nodes);
+                collect_optional(case.guard.as_ref(), nodes);
+                collect_block(&case.body, nodes);
+            }
+        }
+        StatementKind::Try {
+            body,
+            handlers,
+            orelse,
+            finalbody,
+            ..
+        } => {
+            collect_block(body, nodes);
+            for handler in handlers {
+                collect_optional(handler.typ.as_ref(), nodes);
+                collect_block(&handler.body, nodes);
+            }
+            collect_block(orelse, nodes);
+            collect_block(finalbody, nodes);
+        }
+        StatementKind::FunctionDef {
+            params,
+            body,
+            decorator_list,
+            ..
+        } => {
+            collect_expressions(decorator_list, nodes);
+            collect_params(params, nodes);
+            collect_block(body, nodes);
+        }
+        StatementKind::ClassDef {
+            bases,
+            keywords,
+            body,
+            decorator_list,
+            ..
+        } => {
+            collect_expressions(decorator_list, nodes);
+            collect_expressions(bases, nodes);
+            for keyword in keywords {
+                collect_expression(&keyword.value, nodes);
+            }
+            collect_block(body, nodes);
+        }
+        StatementKind::Pass
+        | StatementKind::Break
+        | StatementKind::Continue
+        | StatementKind::Import { .. }
+        | StatementKind::ImportFrom { .. }
+        | StatementKind::Global { .. }
+        | StatementKind::Nonlocal { .. } => {}
+    }
+}
+
+fn collect_expressions<'a>(exprs: &'a [Expression], nodes: &mut Vec<Node<'a>>) {
+    for expr in exprs {
+        collect_expression(expr, nodes);
+    }
+}
+
+fn collect_params<'a>(params: &'a FunctionParams, nodes: &mut Vec<Node<'a>>) {
+    for param in params.all() {
+        collect_optional(param.default.as_ref(), nodes);
+    }
+}
+
+fn collect_optional<'a>(expr: Option<&'a Expression>, nodes: &mut Vec<Node<'a>>) {
+    if let Some(expr) = expr {
+        collect_expression(expr, nodes);
+    }
+}
+
+fn collect_expression<'a>(expr: &'a Expression, nodes: &mut Vec<Node<'a>>) {
+    ensure_sufficient_stack(|| collect_expression_nodes(expr, nodes))
+}
+
+fn collect_expression_nodes<'a>(expr: &'a Expression, nodes: &mut Vec<Node<'a>>) {
+    nodes.push(Node::Expression(expr));
+    match &expr.kind {
+        ExpressionKind::BinaryOp { left, right, .. }
+        | ExpressionKind::LogicalOp { left, right, .. } => {
+            collect_expression(left, nodes);
+            collect_expression(right, nodes);
+        }
+        ExpressionKind::UnaryOp { operand, .. } => collect_expression(operand, nodes),
+        ExpressionKind::Compare {
+            left, comparators, ..
+        } => {
+            collect_expression(left, nodes);
+            collect_expressions(comparators, nodes);
+        }
+        ExpressionKind::Call {
+            func,
+            args,
+            keywords,
+        } => {
+            collect_expression(func, nodes);
+            collect_expressions(args, nodes);
+            for keyword in keywords {
+                collect_expression(&keyword.value, nodes);
+            }
+        }
+        ExpressionKind::Attribute { value, .. }
+        | ExpressionKind::Await { value }
+        | ExpressionKind::YieldFrom { value } => collect_expression(value, nodes),
+        ExpressionKind::Subscript { value, index } => {
+            collect_expression(value, nodes);
+            collect_expression(index, nodes);
+        }
+        ExpressionKind::Slice { lower, upper, step } => {
+            for part in [lower, upper, step] {
+                collect_optional(part.as_deref(), nodes);
+            }
+        }
+        ExpressionKind::List { elements }
+        | ExpressionKind::Tuple { elements }
+        | ExpressionKind::Set { elements } => {
+            collect_expressions(elements, nodes);
+        }
+        ExpressionKind::Dict { keys, values } => {
+            for (key, value) in keys.iter().zip(values) {
+                collect_expression(key, nodes);
+                collect_expression(value, nodes);
+            }
+        }
+        ExpressionKind::ListComp {
+            element,
+            generators,
+        }
+        | ExpressionKind::SetComp {
+            element,
+            generators,
+        }
+        | ExpressionKind::GeneratorExp {
+            element,
+            generators,
+        } => {
+            collect_expression(element, nodes);
+            collect_generators(generators, nodes);
+        }
+        ExpressionKind::DictComp {
+            key,
+            value,
+            generators,
+        } => {
+            collect_expression(key, nodes);
+            collect_expression(value, nodes);
+            collect_generators(generators, nodes);
+        }
+        ExpressionKind::Lambda { params, body } => {
+            for param in params {
+                collect_optional(param.default.as_ref(), nodes);
+            }
+            collect_expression(body, nodes);
+        }
+        ExpressionKind::IfExp { test, body, orelse } => {
+            collect_expression(test, nodes);
+            collect_expression(body, nodes);
+            collect_expression(orelse, nodes);
+        }
+        ExpressionKind::NamedExpr { target, value } => {
+            collect_expression(target, nodes);
+            collect_expression(value, nodes);
+        }
+        ExpressionKind::Yield { value } => collect_optional(value.as_deref(), nodes),
+        ExpressionKind::Integer(_)
+        | ExpressionKind::Float(_)
+        | ExpressionKind::String(_)
+        | ExpressionKind::RawString(_)
+        | ExpressionKind::ByteString(_)
+        | ExpressionKind::ByteRawString(_)
+        | ExpressionKind::FString { .. }
+        | ExpressionKind::Boolean(_)
+        | ExpressionKind::None
+        | ExpressionKind::NotImplemented
+        | ExpressionKind::Ellipsis
+        | ExpressionKind::Identifier(_) => {}
+    }
+}
+
+fn collect_generators<'a>(generators: &'a [Comprehension], nodes: &mut Vec<Node<'a>>) {
+    for generator in generators {
+        collect_pattern(&generator.target, nodes);
+        collect_expression(&generator.iter, nodes);
+        collect_expressions(&generator.ifs, nodes);
+    }
+}
+
+/// Expressions inside a pattern: literals, mapping keys and class names
+fn collect_pattern<'a>(pattern: &'a Pattern, nodes: &mut Vec<Node<'a>>) {
+    match &pattern.kind {
+        PatternKind::Literal(expr) => collect_expression(expr, nodes),
+        PatternKind::Sequence { patterns } | PatternKind::Or { patterns } => {
+            for pattern in patterns {
+                collect_pattern(pattern, nodes);
+            }
+        }
+        PatternKind::Mapping { keys, patterns, .. } => {
+            collect_expressions(keys, nodes);
+            for pattern in patterns {
+                collect_pattern(pattern, nodes);
+            }
+        }
+        PatternKind::Class {
+            cls,
+            patterns,
+            kwd_patterns,
+        } => {
+            collect_expression(cls, nodes);
+            for pattern in patterns.iter().chain(kwd_patterns.iter().map(|(_, p)| p)) {
+                collect_pattern(pattern, nodes);
+            }
+        }
+        PatternKind::As { pattern, .. } => collect_pattern(pattern, nodes),
+        PatternKind::Name(_) | PatternKind::Wildcard | PatternKind::Star(_) => {}
+    }
+}
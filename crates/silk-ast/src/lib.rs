@@ -3,14 +3,24 @@
 /// Represents the parsed structure of Silk source code.
 /// Each node preserves source location information for error reporting.
 pub mod expr;
+pub mod features;
+pub mod id;
+pub mod index;
 pub mod pattern;
+pub mod stack;
 pub mod stmt;
 pub mod types;
+pub mod unparse;
 
 pub use expr::*;
+pub use features::{Feature, FeatureError, FeatureSet, ReleaseChannel};
+pub use id::NodeId;
+pub use index::{Node, NodeIndex};
 pub use pattern::*;
+pub use stack::ensure_sufficient_stack;
 pub use stmt::*;
 pub use types::*;
+pub use unparse::unparse;
 
 use silk_lexer::Span;
 
@@ -19,10 +29,19 @@ use silk_lexer::Span;
 pub struct Program {
     pub statements: Vec<Statement>,
     pub span: Span,
+    /// Unstable features enabled by `# feature:` pragmas in the source
+    pub features: FeatureSet,
+    /// Number of node ids the parser assigned; every id is below it
+    pub node_count: usize,
 }
 
 impl Program {
     pub fn new(statements: Vec<Statement>, span: Span) -> Self {
-        Self { statements, span }
+        Self {
+            statements,
+            span,
+            features: FeatureSet::new(),
+            node_count: 0,
+        }
     }
 }
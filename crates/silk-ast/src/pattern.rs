@@ -13,6 +13,103 @@ impl Pattern {
     pub fn new(kind: PatternKind, span: Span) -> Self {
         Self { kind, span }
     }
+
+    /// Names this pattern binds when it matches, in source order, with the
+    /// span of the pattern that binds each
+    pub fn bound_names(&self) -> Vec<(&str, Span)> {
+        let mut names = Vec::new();
+        self.collect_names(&mut names);
+        names
+    }
+
+    fn collect_names<'a>(&'a self, names: &mut Vec<(&'a str, Span)>) {
+        match &self.kind {
+            PatternKind::Name(name) | PatternKind::Star(Some(name)) => {
+                names.push((name, self.span))
+            }
+            PatternKind::Literal(_) | PatternKind::Wildcard | PatternKind::Star(None) => {}
+            PatternKind::Sequence { patterns } => {
+                for pattern in patterns {
+                    pattern.collect_names(names);
+                }
+            }
+            // Every alternative binds the same names
+            PatternKind::Or { patterns } => {
+                if let Some(first) = patterns.first() {
+                    first.collect_names(names);
+                }
+            }
+            PatternKind::Mapping { patterns, rest, .. } => {
+                for pattern in patterns {
+                    pattern.collect_names(names);
+                }
+                if let Some(rest) = rest {
+                    names.push((rest, self.span));
+                }
+            }
+            PatternKind::Class {
+                patterns,
+                kwd_patterns,
+                ..
+            } => {
+                for pattern in patterns.iter().chain(kwd_patterns.iter().map(|(_, p)| p)) {
+                    pattern.collect_names(names);
+                }
+            }
+            PatternKind::As { pattern, name } => {
+                pattern.collect_names(names);
+                names.push((name, self.span));
+            }
+        }
+    }
+
+    /// Expressions this pattern evaluates while matching: literal and
+    /// dotted values, class names, and mapping keys
+    pub fn expressions(&self) -> Vec<&Expression> {
+        let mut expressions = Vec::new();
+        self.collect_expressions(&mut expressions);
+        expressions
+    }
+
+    fn collect_expressions<'a>(&'a self, expressions: &mut Vec<&'a Expression>) {
+        match &self.kind {
+            PatternKind::Literal(value) => expressions.push(value),
+            PatternKind::Name(_) | PatternKind::Wildcard | PatternKind::Star(_) => {}
+            PatternKind::Sequence { patterns } | PatternKind::Or { patterns } => {
+                for pattern in patterns {
+                    pattern.collect_expressions(expressions);
+                }
+            }
+            PatternKind::Mapping { keys, patterns, .. } => {
+                for (key, pattern) in keys.iter().zip(patterns) {
+                    expressions.push(key);
+                    pattern.collect_expressions(expressions);
+                }
+            }
+            PatternKind::Class {
+                cls,
+                patterns,
+                kwd_patterns,
+            } => {
+                expressions.push(cls);
+                for pattern in patterns.iter().chain(kwd_patterns.iter().map(|(_, p)| p)) {
+                    pattern.collect_expressions(expressions);
+                }
+            }
+            PatternKind::As { pattern, .. } => pattern.collect_expressions(expressions),
+        }
+    }
+
+    /// Whether this pattern matches every value: a capture, `_`, or an
+    /// or-pattern or `as` pattern built on one
+    pub fn is_irrefutable(&self) -> bool {
+        match &self.kind {
+            PatternKind::Name(_) | PatternKind::Wildcard => true,
+            PatternKind::As { pattern, .. } => pattern.is_irrefutable(),
+            PatternKind::Or { patterns } => patterns.iter().any(Pattern::is_irrefutable),
+            _ => false,
+        }
+    }
 }
 
 /// All pattern kinds in Silk
@@ -27,6 +124,9 @@ pub enum PatternKind {
     // Wildcard pattern (_)
     Wildcard,
 
+    // Star pattern in a sequence (*rest, or *_ without a name)
+    Star(Option<String>),
+
     // Sequence pattern ([a, b, c] or (a, b, c))
     Sequence {
         patterns: Vec<Pattern>,
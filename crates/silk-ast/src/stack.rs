@@ -0,0 +1,24 @@
+//! Stack space for recursive passes over the AST
+//!
+//! The parser bounds real nesting (parentheses, blocks, unary operators)
+//! by `MAX_NESTING_DEPTH`, but not left-leaning chains: `1 + 1 + ...`,
+//! `a.b.c...`, `f()()...` and `elif` ladders are parsed in a loop and may
+//! be as long as the source allows, while each link is one more level of
+//! the tree. Passes that recurse over the tree call
+//! [`ensure_sufficient_stack`] at each level, which moves to a new stack
+//! segment when the current one is nearly used up, so a long chain costs
+//! memory instead of overflowing the stack.
+
+/// Stack left below which a new segment is allocated; larger than the
+/// deepest frame of a pass in a debug build
+const RED_ZONE: usize = 256 * 1024;
+
+/// Size of each new stack segment, that of a main thread
+const STACK_PER_SEGMENT: usize = 8 * 1024 * 1024;
+
+/// Run `f`, first moving to a new stack segment if the current one is
+/// nearly used up
+#[inline]
+pub fn ensure_sufficient_stack<R>(f: impl FnOnce() -> R) -> R {
+    stacker::maybe_grow(RED_ZONE, STACK_PER_SEGMENT, f)
+}
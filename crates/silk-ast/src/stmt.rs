@@ -1,20 +1,66 @@
-use crate::{Expression, Pattern, Type};
+use crate::{ensure_sufficient_stack, Expression, NodeId, Pattern, Type};
 /// Statement AST nodes
 use silk_lexer::Span;
+use std::fmt;
 
 /// Statement node with source location
-#[derive(Debug, Clone, PartialEq)]
+///
+/// `Clone`, `Debug` and `PartialEq` are written out rather than derived so
+/// that they grow the stack on long chains, like the passes over the tree
+/// (see [`crate::stack`]).
 pub struct Statement {
     pub kind: StatementKind,
     pub span: Span,
+    /// Assigned by the parser; `NodeId::DUMMY` for nodes built elsewhere
+    pub id: NodeId,
 }
 
 impl Statement {
     pub fn new(kind: StatementKind, span: Span) -> Self {
-        Self { kind, span }
+        Self::with_id(kind, span, NodeId::DUMMY)
+    }
+
+    pub fn with_id(kind: StatementKind, span: Span, id: NodeId) -> Self {
+        Self { kind, span, id }
+    }
+}
+
+impl Clone for Statement {
+    fn clone(&self) -> Self {
+        ensure_sufficient_stack(|| Self {
+            kind: self.kind.clone(),
+            span: self.span,
+            id: self.id,
+        })
+    }
+}
+
+impl fmt::Debug for Statement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        ensure_sufficient_stack(|| {
+            f.debug_struct("Statement")
+                .field("kind", &self.kind)
+                .field("span", &self.span)
+                .field("id", &self.id)
+                .finish()
+        })
+    }
+}
+
+impl PartialEq for Statement {
+    fn eq(&self, other: &Self) -> bool {
+        ensure_sufficient_stack(|| {
+            self.kind == other.kind && self.span == other.span && self.id == other.id
+        })
     }
 }
 
+// Every variant is as large as the largest, so bulky fields are boxed
+// (`FunctionDef::params`, `For::target`); box new ones too rather than
+// raising the limit
+#[cfg(target_pointer_width = "64")]
+const _: () = assert!(std::mem::size_of::<Statement>() <= 320);
+
 /// All statement kinds in Silk
 #[derive(Debug, Clone, PartialEq)]
 pub enum StatementKind {
@@ -111,7 +157,7 @@ pub enum StatementKind {
 
     // For loop
     For {
-        target: Pattern,
+        target: Box<Pattern>,
         iter: Expression,
         body: Vec<Statement>,
         orelse: Vec<Statement>,
@@ -131,18 +177,20 @@ pub enum StatementKind {
         cases: Vec<MatchCase>,
     },
 
-    // Try/except statement
+    // Try/except statement; `is_star` for `except*` handlers, which catch
+    // parts of an exception group
     Try {
         body: Vec<Statement>,
         handlers: Vec<ExceptHandler>,
         orelse: Vec<Statement>,
         finalbody: Vec<Statement>,
+        is_star: bool,
     },
 
     // Function definition
     FunctionDef {
         name: String,
-        params: FunctionParams,
+        params: Box<FunctionParams>,
         body: Vec<Statement>,
         decorator_list: Vec<Expression>,
         returns: Option<Type>,
@@ -169,6 +217,7 @@ pub enum AugAssignOperator {
     FloorDiv, // //=
     Mod,      // %=
     Pow,      // **=
+    MatMult,  // @=
     BitOr,    // |=
     BitXor,   // ^=
     BitAnd,   // &=
@@ -184,6 +233,22 @@ pub struct Alias {
     pub span: Span,
 }
 
+impl Alias {
+    /// The name this alias binds: `np` for `import numpy as np`,
+    /// `os` for `import os.path`
+    pub fn bound_name(&self) -> &str {
+        match &self.asname {
+            Some(asname) => asname,
+            None => self.name.split('.').next().unwrap_or(&self.name),
+        }
+    }
+
+    /// Whether the alias re-exports its name explicitly (`import x as x`)
+    pub fn is_reexport(&self) -> bool {
+        self.asname.as_deref() == Some(self.name.as_str())
+    }
+}
+
 /// With item
 #[derive(Debug, Clone, PartialEq)]
 pub struct WithItem {
@@ -213,12 +278,31 @@ pub struct ExceptHandler {
 /// Function parameters
 #[derive(Debug, Clone, PartialEq)]
 pub struct FunctionParams {
+    /// Positional-only parameters (before `/`)
+    pub posonlyargs: Vec<FunctionArg>,
+    /// Parameters that may be passed by position or keyword
     pub args: Vec<FunctionArg>,
     pub vararg: Option<FunctionArg>,
+    /// Keyword-only parameters (after `*` or `*args`)
     pub kwonlyargs: Vec<FunctionArg>,
     pub kwarg: Option<FunctionArg>,
 }
 
+impl FunctionParams {
+    /// Parameters that can be passed by position, in order
+    pub fn positional(&self) -> impl Iterator<Item = &FunctionArg> {
+        self.posonlyargs.iter().chain(&self.args)
+    }
+
+    /// Every parameter in declaration order
+    pub fn all(&self) -> impl Iterator<Item = &FunctionArg> {
+        self.positional()
+            .chain(&self.vararg)
+            .chain(&self.kwonlyargs)
+            .chain(&self.kwarg)
+    }
+}
+
 /// Function argument
 #[derive(Debug, Clone, PartialEq)]
 pub struct FunctionArg {
@@ -0,0 +1,917 @@
+/// Printing syntax trees back as source code
+///
+/// `Display` for programs, statements, expressions, types and patterns
+/// writes Silk source that parses back to the same tree. Parentheses are
+/// written only where precedence needs them, strings are re-escaped, and
+/// blocks are indented by four spaces. Comments, blank lines and the
+/// original layout are not part of the tree, so they are not reproduced:
+/// printing is a normal form, and printing a reparsed program gives the
+/// same text again.
+use crate::{
+    ensure_sufficient_stack, AugAssignOperator, BinaryOperator, CompareOperator, Comprehension,
+    Expression, ExpressionKind, FunctionArg, FunctionParams, LogicalOperator, Pattern, PatternKind,
+    Program, Statement, StatementKind, Type, TypeKind, UnaryOperator,
+};
+use silk_lexer::FStringPart;
+use std::fmt::{self, Write};
+
+/// Spaces per indentation level
+const INDENT: &str = "    ";
+
+/// Binding strength of printed expressions, loosest first; an operand is
+/// parenthesized when it binds more loosely than its position requires
+mod prec {
+    pub const YIELD: u8 = 0;
+    pub const WALRUS: u8 = 1;
+    pub const LAMBDA: u8 = 2;
+    pub const TERNARY: u8 = 3;
+    pub const OR: u8 = 4;
+    pub const AND: u8 = 5;
+    pub const NOT: u8 = 6;
+    pub const COMPARISON: u8 = 7;
+    pub const BIT_OR: u8 = 8;
+    pub const BIT_XOR: u8 = 9;
+    pub const BIT_AND: u8 = 10;
+    pub const SHIFT: u8 = 11;
+    pub const ADDITION: u8 = 12;
+    pub const MULTIPLICATION: u8 = 13;
+    pub const UNARY: u8 = 14;
+    pub const POWER: u8 = 15;
+    pub const AWAIT: u8 = 16;
+    pub const ATOM: u8 = 17;
+}
+
+/// Source of a whole program
+pub fn unparse(program: &Program) -> String {
+    program.to_string()
+}
+
+impl fmt::Display for Program {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut printer = Printer::default();
+        printer.block(&self.statements);
+        f.write_str(&printer.out)
+    }
+}
+
+/// A statement and its blocks, one line per statement, each ending with a
+/// newline
+impl fmt::Display for Statement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut printer = Printer::default();
+        printer.statement(self);
+        f.write_str(&printer.out)
+    }
+}
+
+impl fmt::Display for Expression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&expression(self, prec::LAMBDA))
+    }
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            TypeKind::Name(name) => f.write_str(name),
+            TypeKind::Generic { base, args } => write!(f, "{}[{}]", base, join(args)),
+            TypeKind::Tuple { elements } => write!(f, "tuple[{}]", join(elements)),
+            TypeKind::Union { types } => {
+                let types: Vec<String> = types.iter().map(ToString::to_string).collect();
+                f.write_str(&types.join(" | "))
+            }
+            TypeKind::Optional { inner } => write!(f, "Optional[{}]", inner),
+            TypeKind::Callable {
+                params,
+                return_type,
+            } => write!(f, "Callable[[{}], {}]", join(params), return_type),
+            TypeKind::Literal { values } => write!(f, "Literal[{}]", join(values)),
+            TypeKind::Any => f.write_str("Any"),
+            TypeKind::None => f.write_str("None"),
+        }
+    }
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            PatternKind::Name(name) => f.write_str(name),
+            PatternKind::Wildcard => f.write_str("_"),
+            PatternKind::Star(name) => write!(f, "*{}", name.as_deref().unwrap_or("_")),
+            PatternKind::Literal(value) => f.write_str(&expression(value, prec::OR)),
+            PatternKind::Sequence { patterns } => f.write_str(&tuple(patterns)),
+            PatternKind::Mapping {
+                keys,
+                patterns,
+                rest,
+            } => {
+                let mut items: Vec<String> = keys
+                    .iter()
+                    .zip(patterns)
+                    .map(|(key, pattern)| format!("{}: {}", expression(key, prec::OR), pattern))
+                    .collect();
+                if let Some(rest) = rest {
+                    items.push(format!("**{}", rest));
+                }
+                write!(f, "{{{}}}", items.join(", "))
+            }
+            PatternKind::Class {
+                cls,
+                patterns,
+                kwd_patterns,
+            } => {
+                let mut items: Vec<String> = patterns.iter().map(ToString::to_string).collect();
+                items.extend(
+                    kwd_patterns
+                        .iter()
+                        .map(|(name, pattern)| format!("{}={}", name, pattern)),
+                );
+                write!(f, "{}({})", expression(cls, prec::ATOM), items.join(", "))
+            }
+            PatternKind::Or { patterns } => {
+                let patterns: Vec<String> = patterns
+                    .iter()
+                    .map(|pattern| match pattern.kind {
+                        PatternKind::As { .. } => format!("({})", pattern),
+                        _ => pattern.to_string(),
+                    })
+                    .collect();
+                f.write_str(&patterns.join(" | "))
+            }
+            PatternKind::As { pattern, name } => match pattern.kind {
+                PatternKind::As { .. } => write!(f, "({}) as {}", pattern, name),
+                _ => write!(f, "{} as {}", pattern, name),
+            },
+        }
+    }
+}
+
+/// Writes statements line by line at the current indentation
+#[derive(Default)]
+struct Printer {
+    out: String,
+    indent: usize,
+}
+
+impl Printer {
+    fn line(&mut self, text: &str) {
+        for _ in 0..self.indent {
+            self.out.push_str(INDENT);
+        }
+        self.out.push_str(text);
+        self.out.push('\n');
+    }
+
+    /// `header` followed by an indented block; an empty block is `pass`
+    fn header(&mut self, header: &str, body: &[Statement]) {
+        self.line(header);
+        self.indent += 1;
+        if body.is_empty() {
+            self.line("pass");
+        } else {
+            self.block(body);
+        }
+        self.indent -= 1;
+    }
+
+    fn block(&mut self, statements: &[Statement]) {
+        for statement in statements {
+            self.statement(statement);
+        }
+    }
+
+    fn statement(&mut self, statement: &Statement) {
+        match &statement.kind {
+            StatementKind::Expr(value) => self.line(&expr(value)),
+            StatementKind::Assign {
+                targets,
+                value,
+                type_annotation,
+            } => {
+                let mut text = String::new();
+                for (index, target) in targets.iter().enumerate() {
+                    text.push_str(&expr(target));
+                    if index == 0 {
+                        if let Some(annotation) = type_annotation {
+                            let _ = write!(text, ": {}", annotation);
+                        }
+                    }
+                    text.push_str(" = ");
+                }
+                text.push_str(&expr(value));
+                self.line(&text);
+            }
+            StatementKind::AugAssign { target, op, value } => {
+                self.line(&format!(
+                    "{} {} {}",
+                    expr(target),
+                    aug_assign_operator(*op),
+                    expr(value)
+                ));
+            }
+            StatementKind::AnnAssign {
+                target,
+                annotation,
+                value,
+            } => {
+                let text = match value {
+                    Some(value) => format!("{}: {} = {}", expr(target), annotation, expr(value)),
+                    None => format!("{}: {}", expr(target), annotation),
+                };
+                self.line(&text);
+            }
+            StatementKind::Assert { test, msg } => {
+                let text = match msg {
+                    Some(msg) => format!("assert {}, {}", expr(test), expr(msg)),
+                    None => format!("assert {}", expr(test)),
+                };
+                self.line(&text);
+            }
+            StatementKind::Pass => self.line("pass"),
+            StatementKind::Delete { targets } => {
+                let targets: Vec<String> = targets.iter().map(expr).collect();
+                self.line(&format!("del {}", targets.join(", ")));
+            }
+            StatementKind::Return { value } => match value {
+                Some(value) => self.line(&format!("return {}", expr(value))),
+                None => self.line("return"),
+            },
+            StatementKind::Raise { exc, cause } => {
+                let text = match (exc, cause) {
+                    (Some(exc), Some(cause)) => {
+                        format!("raise {} from {}", expr(exc), expr(cause))
+                    }
+                    (Some(exc), None) => format!("raise {}", expr(exc)),
+                    (None, _) => "raise".to_string(),
+                };
+                self.line(&text);
+            }
+            StatementKind::Break => self.line("break"),
+            StatementKind::Continue => self.line("continue"),
+            StatementKind::Import { names } => {
+                let names: Vec<String> = names.iter().map(alias).collect();
+                self.line(&format!("import {}", names.join(", ")));
+            }
+            StatementKind::ImportFrom {
+                module,
+                names,
+                level,
+            } => {
+                // Three dots in a row lex as `...`
+                let dots = if *level >= 3 {
+                    vec!["."; *level].join(" ")
+                } else {
+                    ".".repeat(*level)
+                };
+                let separator = if *level >= 3 && module.is_some() {
+                    " "
+                } else {
+                    ""
+                };
+                let names: Vec<String> = names.iter().map(alias).collect();
+                self.line(&format!(
+                    "from {}{}{} import {}",
+                    dots,
+                    separator,
+                    module.as_deref().unwrap_or(""),
+                    names.join(", ")
+                ));
+            }
+            StatementKind::Global { names } => self.line(&format!("global {}", names.join(", "))),
+            StatementKind::Nonlocal { names } => {
+                self.line(&format!("nonlocal {}", names.join(", ")))
+            }
+            StatementKind::If { test, body, orelse } => self.if_chain("if", test, body, orelse),
+            StatementKind::While { test, body, orelse } => {
+                self.header(&format!("while {}:", expr(test)), body);
+                self.else_block(orelse);
+            }
+            StatementKind::For {
+                target,
+                iter,
+                body,
+                orelse,
+                is_async,
+            } => {
+                let target = match &target.kind {
+                    // `for x, y in ...` without parentheses
+                    PatternKind::Sequence { patterns } if patterns.len() > 1 => join(patterns),
+                    _ => target.to_string(),
+                };
+                let keyword = if *is_async { "async for" } else { "for" };
+                self.header(&format!("{} {} in {}:", keyword, target, expr(iter)), body);
+                self.else_block(orelse);
+            }
+            StatementKind::With {
+                items,
+                body,
+                is_async,
+            } => {
+                let items: Vec<String> = items
+                    .iter()
+                    .map(|item| {
+                        let context = expression(&item.context_expr, prec::TERNARY);
+                        match &item.optional_vars {
+                            Some(vars) => format!("{} as {}", context, expr(vars)),
+                            None => context,
+                        }
+                    })
+                    .collect();
+                let keyword = if *is_async { "async with" } else { "with" };
+                self.header(&format!("{} {}:", keyword, items.join(", ")), body);
+            }
+            StatementKind::Match { subject, cases } => {
+                self.line(&format!("match {}:", expr(subject)));
+                self.indent += 1;
+                for case in cases {
+                    let header = match &case.guard {
+                        Some(guard) => format!("case {} if {}:", case.pattern, expr(guard)),
+                        None => format!("case {}:", case.pattern),
+                    };
+                    self.header(&header, &case.body);
+                }
+                self.indent -= 1;
+            }
+            StatementKind::Try {
+                body,
+                handlers,
+                orelse,
+                finalbody,
+                is_star,
+            } => {
+                self.header("try:", body);
+                let except = if *is_star { "except*" } else { "except" };
+                for handler in handlers {
+                    let header = match (&handler.typ, &handler.name) {
+                        (Some(typ), Some(name)) => {
+                            format!("{} {} as {}:", except, expr(typ), name)
+                        }
+                        (Some(typ), None) => format!("{} {}:", except, expr(typ)),
+                        (None, _) => "except:".to_string(),
+                    };
+                    self.header(&header, &handler.body);
+                }
+                self.else_block(orelse);
+                if !finalbody.is_empty() {
+                    self.header("finally:", finalbody);
+                }
+            }
+            StatementKind::FunctionDef {
+                name,
+                params,
+                body,
+                decorator_list,
+                returns,
+                is_async,
+            } => {
+                self.decorators(decorator_list);
+                let keyword = if *is_async { "async def" } else { "def" };
+                let returns = returns
+                    .as_ref()
+                    .map_or(String::new(), |returns| format!(" -> {}", returns));
+                self.header(
+                    &format!("{} {}({}){}:", keyword, name, parameters(params), returns),
+                    body,
+                );
+            }
+            StatementKind::ClassDef {
+                name,
+                bases,
+                keywords,
+                body,
+                decorator_list,
+            } => {
+                self.decorators(decorator_list);
+                let mut arguments: Vec<String> = bases.iter().map(expr).collect();
+                arguments.extend(keywords.iter().map(|keyword| match &keyword.arg {
+                    Some(arg) => format!("{}={}", arg, expr(&keyword.value)),
+                    None => format!("**{}", expr(&keyword.value)),
+                }));
+                let header = if arguments.is_empty() {
+                    format!("class {}:", name)
+                } else {
+                    format!("class {}({}):", name, arguments.join(", "))
+                };
+                self.header(&header, body);
+            }
+        }
+    }
+
+    /// An `if` with its `elif` and `else` clauses; an `else` block that
+    /// holds only an `if` is written as `elif`
+    fn if_chain(
+        &mut self,
+        keyword: &str,
+        test: &Expression,
+        body: &[Statement],
+        orelse: &[Statement],
+    ) {
+        self.header(&format!("{} {}:", keyword, expr(test)), body);
+        match orelse {
+            [Statement {
+                kind: StatementKind::If { test, body, orelse },
+                ..
+            }] => self.if_chain("elif", test, body, orelse),
+            _ => self.else_block(orelse),
+        }
+    }
+
+    fn else_block(&mut self, orelse: &[Statement]) {
+        if !orelse.is_empty() {
+            self.header("else:", orelse);
+        }
+    }
+
+    fn decorators(&mut self, decorators: &[Expression]) {
+        for decorator in decorators {
+            self.line(&format!("@{}", expr(decorator)));
+        }
+    }
+}
+
+/// An expression where any expression but an unparenthesized walrus may
+/// appear
+fn expr(value: &Expression) -> String {
+    expression(value, prec::LAMBDA)
+}
+
+/// An expression in a position that needs at least precedence `min`,
+/// parenthesized if it binds more loosely
+fn expression(value: &Expression, min: u8) -> String {
+    let (text, precedence) = ensure_sufficient_stack(|| bare(value));
+    if precedence < min {
+        format!("({})", text)
+    } else {
+        text
+    }
+}
+
+/// An expression without surrounding parentheses, and its precedence
+fn bare(value: &Expression) -> (String, u8) {
+    match &value.kind {
+        ExpressionKind::Integer(value) if *value < 0 => (value.to_string(), prec::UNARY),
+        ExpressionKind::Integer(value) => (value.to_string(), prec::ATOM),
+        ExpressionKind::Float(value) => (float(*value), prec::ATOM),
+        ExpressionKind::String(value) => (format!("\"{}\"", escape(value, false)), prec::ATOM),
+        ExpressionKind::RawString(value) => {
+            let quote = if value.contains('"') { '\'' } else { '"' };
+            (format!("r{}{}{}", quote, value, quote), prec::ATOM)
+        }
+        ExpressionKind::ByteString(bytes) => (format!("b\"{}\"", escape_bytes(bytes)), prec::ATOM),
+        ExpressionKind::ByteRawString(bytes) => {
+            let text = String::from_utf8_lossy(bytes);
+            let quote = if text.contains('"') { '\'' } else { '"' };
+            (format!("br{}{}{}", quote, text, quote), prec::ATOM)
+        }
+        ExpressionKind::FString { parts } => (fstring(parts), prec::ATOM),
+        ExpressionKind::Boolean(true) => ("True".to_string(), prec::ATOM),
+        ExpressionKind::Boolean(false) => ("False".to_string(), prec::ATOM),
+        ExpressionKind::None => ("None".to_string(), prec::ATOM),
+        ExpressionKind::NotImplemented => ("NotImplemented".to_string(), prec::ATOM),
+        ExpressionKind::Ellipsis => ("...".to_string(), prec::ATOM),
+        ExpressionKind::Identifier(name) => (name.clone(), prec::ATOM),
+        ExpressionKind::BinaryOp { left, op, right } => {
+            let precedence = binary_precedence(*op);
+            // `**` is right-associative and binds tighter than a unary
+            // operator on its left but not on its right
+            let (left_min, right_min) = if *op == BinaryOperator::Pow {
+                (prec::AWAIT, prec::UNARY)
+            } else {
+                (precedence, precedence + 1)
+            };
+            let text = format!(
+                "{} {} {}",
+                expression(left, left_min),
+                binary_operator(*op),
+                expression(right, right_min)
+            );
+            (text, precedence)
+        }
+        ExpressionKind::UnaryOp { op, operand } => match op {
+            UnaryOperator::Not => (format!("not {}", expression(operand, prec::NOT)), prec::NOT),
+            op => {
+                let symbol = match op {
+                    UnaryOperator::UAdd => "+",
+                    UnaryOperator::USub => "-",
+                    _ => "~",
+                };
+                (
+                    format!("{}{}", symbol, expression(operand, prec::UNARY)),
+                    prec::UNARY,
+                )
+            }
+        },
+        ExpressionKind::Compare {
+            left,
+            ops,
+            comparators,
+        } => {
+            let mut text = expression(left, prec::COMPARISON);
+            for (op, comparator) in ops.iter().zip(comparators) {
+                let _ = write!(
+                    text,
+                    " {} {}",
+                    compare_operator(*op),
+                    expression(comparator, prec::COMPARISON + 1)
+                );
+            }
+            (text, prec::COMPARISON)
+        }
+        ExpressionKind::LogicalOp { left, op, right } => {
+            let (precedence, keyword) = match op {
+                LogicalOperator::And => (prec::AND, "and"),
+                LogicalOperator::Or => (prec::OR, "or"),
+            };
+            let text = format!(
+                "{} {} {}",
+                expression(left, precedence),
+                keyword,
+                expression(right, precedence + 1)
+            );
+            (text, precedence)
+        }
+        ExpressionKind::Call {
+            func,
+            args,
+            keywords,
+        } => {
+            let mut arguments: Vec<String> = args.iter().map(expr).collect();
+            arguments.extend(keywords.iter().map(|keyword| match &keyword.arg {
+                Some(arg) => format!("{}={}", arg, expr(&keyword.value)),
+                None => format!("**{}", expr(&keyword.value)),
+            }));
+            (
+                format!("{}({})", receiver(func), arguments.join(", ")),
+                prec::ATOM,
+            )
+        }
+        ExpressionKind::Attribute { value, attr } => {
+            (format!("{}.{}", receiver(value), attr), prec::ATOM)
+        }
+        ExpressionKind::Subscript { value, index } => {
+            let index = match &index.kind {
+                ExpressionKind::Slice { .. } => bare(index).0,
+                _ => expr(index),
+            };
+            (format!("{}[{}]", receiver(value), index), prec::ATOM)
+        }
+        ExpressionKind::Slice { lower, upper, step } => {
+            let part = |part: &Option<Box<Expression>>| {
+                part.as_ref()
+                    .map_or(String::new(), |part| expression(part, prec::TERNARY))
+            };
+            let mut text = format!("{}:{}", part(lower), part(upper));
+            if step.is_some() {
+                let _ = write!(text, ":{}", part(step));
+            }
+            (text, prec::ATOM)
+        }
+        ExpressionKind::List { elements } => {
+            let elements: Vec<String> = elements.iter().map(expr).collect();
+            (format!("[{}]", elements.join(", ")), prec::ATOM)
+        }
+        ExpressionKind::Tuple { elements } => {
+            let elements: Vec<String> = elements.iter().map(expr).collect();
+            (tuple(&elements), prec::ATOM)
+        }
+        ExpressionKind::Dict { keys, values } => {
+            let items: Vec<String> = keys
+                .iter()
+                .zip(values)
+                .map(|(key, value)| format!("{}: {}", expression(key, prec::TERNARY), expr(value)))
+                .collect();
+            (format!("{{{}}}", items.join(", ")), prec::ATOM)
+        }
+        // There is no literal for an empty set
+        ExpressionKind::Set { elements } if elements.is_empty() => {
+            ("set()".to_string(), prec::ATOM)
+        }
+        ExpressionKind::Set { elements } => {
+            let elements: Vec<String> = elements.iter().map(expr).collect();
+            (format!("{{{}}}", elements.join(", ")), prec::ATOM)
+        }
+        ExpressionKind::ListComp {
+            element,
+            generators,
+        } => (
+            format!("[{}{}]", expr(element), comprehensions(generators)),
+            prec::ATOM,
+        ),
+        ExpressionKind::DictComp {
+            key,
+            value,
+            generators,
+        } => (
+            format!(
+                "{{{}: {}{}}}",
+                expression(key, prec::TERNARY),
+                expr(value),
+                comprehensions(generators)
+            ),
+            prec::ATOM,
+        ),
+        ExpressionKind::SetComp {
+            element,
+            generators,
+        } => (
+            format!("{{{}{}}}", expr(element), comprehensions(generators)),
+            prec::ATOM,
+        ),
+        ExpressionKind::GeneratorExp {
+            element,
+            generators,
+        } => (
+            format!("({}{})", expr(element), comprehensions(generators)),
+            prec::ATOM,
+        ),
+        ExpressionKind::Lambda { params, body } => {
+            let params: Vec<String> = params
+                .iter()
+                .map(|param| match &param.default {
+                    Some(default) => {
+                        format!("{}={}", param.name, expression(default, prec::TERNARY))
+                    }
+                    None => param.name.clone(),
+                })
+                .collect();
+            let text = if params.is_empty() {
+                format!("lambda: {}", expr(body))
+            } else {
+                format!("lambda {}: {}", params.join(", "), expr(body))
+            };
+            (text, prec::LAMBDA)
+        }
+        ExpressionKind::IfExp { test, body, orelse } => (
+            format!(
+                "{} if {} else {}",
+                expression(body, prec::OR),
+                expression(test, prec::OR),
+                expression(orelse, prec::LAMBDA)
+            ),
+            prec::TERNARY,
+        ),
+        ExpressionKind::NamedExpr { target, value } => (
+            format!(
+                "{} := {}",
+                expression(target, prec::ATOM),
+                expression(value, prec::LAMBDA)
+            ),
+            prec::WALRUS,
+        ),
+        ExpressionKind::Await { value } => (
+            format!("await {}", expression(value, prec::ATOM)),
+            prec::AWAIT,
+        ),
+        ExpressionKind::Yield { value } => match value {
+            Some(value) => (format!("yield {}", expr(value)), prec::YIELD),
+            None => ("yield".to_string(), prec::YIELD),
+        },
+        ExpressionKind::YieldFrom { value } => (format!("yield from {}", expr(value)), prec::YIELD),
+    }
+}
+
+/// The value of a call, attribute access or subscript; a number needs
+/// parentheses so that `.` is not read as a decimal point
+fn receiver(value: &Expression) -> String {
+    match value.kind {
+        ExpressionKind::Integer(_) | ExpressionKind::Float(_) => format!("({})", bare(value).0),
+        _ => expression(value, prec::ATOM),
+    }
+}
+
+/// `for target in iter if condition` clauses, each with a leading space
+fn comprehensions(generators: &[Comprehension]) -> String {
+    let mut text = String::new();
+    for generator in generators {
+        let keyword = if generator.is_async {
+            "async for"
+        } else {
+            "for"
+        };
+        let _ = write!(
+            text,
+            " {} {} in {}",
+            keyword,
+            generator.target,
+            expression(&generator.iter, prec::OR)
+        );
+        for condition in &generator.ifs {
+            let _ = write!(text, " if {}", expression(condition, prec::OR));
+        }
+    }
+    text
+}
+
+/// Parameters of a function definition, without the parentheses
+fn parameters(params: &FunctionParams) -> String {
+    let mut items: Vec<String> = params.posonlyargs.iter().map(parameter).collect();
+    if !params.posonlyargs.is_empty() {
+        items.push("/".to_string());
+    }
+    items.extend(params.args.iter().map(parameter));
+    match &params.vararg {
+        Some(vararg) => items.push(format!("*{}", parameter(vararg))),
+        None if !params.kwonlyargs.is_empty() => items.push("*".to_string()),
+        None => {}
+    }
+    items.extend(params.kwonlyargs.iter().map(parameter));
+    if let Some(kwarg) = &params.kwarg {
+        items.push(format!("**{}", parameter(kwarg)));
+    }
+    items.join(", ")
+}
+
+fn parameter(param: &FunctionArg) -> String {
+    let mut text = param.name.clone();
+    if let Some(annotation) = &param.annotation {
+        let _ = write!(text, ": {}", annotation);
+    }
+    if let Some(default) = &param.default {
+        let separator = if param.annotation.is_some() {
+            " = "
+        } else {
+            "="
+        };
+        let _ = write!(text, "{}{}", separator, expr(default));
+    }
+    text
+}
+
+fn alias(alias: &crate::Alias) -> String {
+    match &alias.asname {
+        Some(asname) => format!("{} as {}", alias.name, asname),
+        None => alias.name.clone(),
+    }
+}
+
+/// `(a, b)`, with the trailing comma of a one-element tuple
+fn tuple(elements: &[impl ToString]) -> String {
+    match elements {
+        [element] => format!("({},)", element.to_string()),
+        elements => format!("({})", join(elements)),
+    }
+}
+
+fn join(items: &[impl ToString]) -> String {
+    items
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// A float literal that reads back as a float: always with a decimal point
+/// or an exponent
+fn float(value: f64) -> String {
+    let text = format!("{:?}", value);
+    if text.contains(['.', 'e', 'E']) || !value.is_finite() {
+        text
+    } else {
+        format!("{}.0", text)
+    }
+}
+
+/// The body of a double-quoted string; in an f-string, braces are doubled
+fn escape(text: &str, fstring: bool) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '"' => out.push_str("\\\""),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '{' if fstring => out.push_str("{{"),
+            '}' if fstring => out.push_str("}}"),
+            // Control characters are all below U+0100; `\x` always takes
+            // two digits, where `\0` would run into a following digit
+            c if c.is_control() => {
+                let _ = write!(out, "\\x{:02x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn escape_bytes(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len());
+    for &byte in bytes {
+        match byte {
+            b'\\' => out.push_str("\\\\"),
+            b'"' => out.push_str("\\\""),
+            b'\n' => out.push_str("\\n"),
+            b'\r' => out.push_str("\\r"),
+            b'\t' => out.push_str("\\t"),
+            b' '..=b'~' => out.push(byte as char),
+            byte => {
+                let _ = write!(out, "\\x{:02x}", byte);
+            }
+        }
+    }
+    out
+}
+
+fn fstring(parts: &[FStringPart]) -> String {
+    // Expressions are kept as written and may span lines, which only a
+    // triple-quoted f-string allows
+    let multiline = parts.iter().any(|part| match part {
+        FStringPart::Text(_) => false,
+        FStringPart::Expression {
+            code, format_spec, ..
+        } => code.contains('\n') || format_spec.as_ref().is_some_and(|spec| spec.contains('\n')),
+    });
+    let quote = if multiline { "\"\"\"" } else { "\"" };
+    let mut out = format!("f{}", quote);
+    for part in parts {
+        match part {
+            FStringPart::Text(text) => out.push_str(&escape(text, true)),
+            FStringPart::Expression {
+                code,
+                conversion,
+                format_spec,
+            } => {
+                out.push('{');
+                out.push_str(code);
+                if let Some(conversion) = conversion {
+                    out.push('!');
+                    out.push(*conversion);
+                }
+                if let Some(spec) = format_spec {
+                    out.push(':');
+                    out.push_str(spec);
+                }
+                out.push('}');
+            }
+        }
+    }
+    out.push_str(quote);
+    out
+}
+
+fn binary_precedence(op: BinaryOperator) -> u8 {
+    match op {
+        BinaryOperator::BitOr => prec::BIT_OR,
+        BinaryOperator::BitXor => prec::BIT_XOR,
+        BinaryOperator::BitAnd => prec::BIT_AND,
+        BinaryOperator::LShift | BinaryOperator::RShift => prec::SHIFT,
+        BinaryOperator::Add | BinaryOperator::Sub => prec::ADDITION,
+        BinaryOperator::Mult
+        | BinaryOperator::Div
+        | BinaryOperator::FloorDiv
+        | BinaryOperator::Mod
+        | BinaryOperator::MatMult => prec::MULTIPLICATION,
+        BinaryOperator::Pow => prec::POWER,
+    }
+}
+
+fn binary_operator(op: BinaryOperator) -> &'static str {
+    match op {
+        BinaryOperator::Add => "+",
+        BinaryOperator::Sub => "-",
+        BinaryOperator::Mult => "*",
+        BinaryOperator::Div => "/",
+        BinaryOperator::FloorDiv => "//",
+        BinaryOperator::Mod => "%",
+        BinaryOperator::Pow => "**",
+        BinaryOperator::MatMult => "@",
+        BinaryOperator::BitOr => "|",
+        BinaryOperator::BitXor => "^",
+        BinaryOperator::BitAnd => "&",
+        BinaryOperator::LShift => "<<",
+        BinaryOperator::RShift => ">>",
+    }
+}
+
+fn compare_operator(op: CompareOperator) -> &'static str {
+    match op {
+        CompareOperator::Eq => "==",
+        CompareOperator::NotEq => "!=",
+        CompareOperator::Lt => "<",
+        CompareOperator::LtE => "<=",
+        CompareOperator::Gt => ">",
+        CompareOperator::GtE => ">=",
+        CompareOperator::Is => "is",
+        CompareOperator::IsNot => "is not",
+        CompareOperator::In => "in",
+        CompareOperator::NotIn => "not in",
+    }
+}
+
+fn aug_assign_operator(op: AugAssignOperator) -> &'static str {
+    match op {
+        AugAssignOperator::Add => "+=",
+        AugAssignOperator::Sub => "-=",
+        AugAssignOperator::Mult => "*=",
+        AugAssignOperator::Div => "/=",
+        AugAssignOperator::FloorDiv => "//=",
+        AugAssignOperator::Mod => "%=",
+        AugAssignOperator::Pow => "**=",
+        AugAssignOperator::MatMult => "@=",
+        AugAssignOperator::BitOr => "|=",
+        AugAssignOperator::BitXor => "^=",
+        AugAssignOperator::BitAnd => "&=",
+        AugAssignOperator::LShift => "<<=",
+        AugAssignOperator::RShift => ">>=",
+    }
+}
@@ -0,0 +1,60 @@
+//! Lexer and parser benchmarks on generated inputs
+//!
+//! `cargo bench -p silk-bench` runs them all; pass a filter to run some,
+//! e.g. `cargo bench -p silk-bench -- parse/large_file`. Parsing is
+//! measured from an already lexed token stream, so the two groups add up
+//! to the cost of the front end.
+
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion, Throughput};
+use silk_bench::{huge_list, huge_string, large_file, nested_blocks, nested_expression};
+use silk_lexer::Lexer;
+use silk_parser::Parser;
+use std::hint::black_box;
+
+/// (name, source) of every benchmark input
+fn inputs() -> Vec<(&'static str, String)> {
+    vec![
+        ("large_file", large_file(10_000)),
+        ("nested_expression", nested_expression(90)),
+        ("nested_blocks", nested_blocks(90)),
+        ("huge_list", huge_list(100_000)),
+        ("huge_string", huge_string(1_000_000)),
+    ]
+}
+
+fn lex(c: &mut Criterion) {
+    let mut group = c.benchmark_group("lex");
+    // Several megabytes, where per-token allocation used to dominate; too
+    // slow to also parse on every run
+    let megabytes = ("megabytes", large_file(100_000));
+    for (name, source) in inputs().into_iter().chain([megabytes]) {
+        group.throughput(Throughput::Bytes(source.len() as u64));
+        group.bench_function(name, |b| {
+            b.iter(|| Lexer::new(black_box(&source)).tokenize().unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn parse(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse");
+    for (name, source) in inputs() {
+        let tokens = Lexer::new(&source).tokenize().unwrap();
+        group.throughput(Throughput::Bytes(source.len() as u64));
+        group.bench_function(name, |b| {
+            b.iter_batched(
+                || tokens.clone(),
+                |tokens| {
+                    Parser::from_tokens(tokens, Default::default())
+                        .parse_program()
+                        .unwrap()
+                },
+                BatchSize::LargeInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, lex, parse);
+criterion_main!(benches);
@@ -0,0 +1,112 @@
+//! Generated inputs for the lexer and parser benchmarks
+//!
+//! Each generator returns a valid Silk program of a given size, so a
+//! benchmark measures the normal path through the front end, not error
+//! handling. The inputs are deterministic: the same size always gives the
+//! same text, so timings from different runs compare.
+//!
+//! Run the benchmarks with `cargo bench -p silk-bench`; criterion keeps the
+//! previous run in `target/criterion` and reports the change against it.
+
+use silk_parser::MAX_NESTING_DEPTH;
+
+/// A program of about `lines` lines mixing the constructs of typical code:
+/// classes, functions with annotations and docstrings, loops, comprehensions,
+/// calls, string and numeric literals, and comments
+pub fn large_file(lines: usize) -> String {
+    let mut source = String::with_capacity(lines * 32);
+    let mut count = 0;
+    for index in 0.. {
+        if count >= lines {
+            break;
+        }
+        let section = format!(
+            r#"# Section {index}
+class Shape{index}:
+    """A shape with a name and a size."""
+
+    def __init__(self, name: str, size: float = 1.5):
+        self.name = name
+        self.size = size
+
+    def area(self) -> float:
+        return self.size * self.size * 3.14159
+
+def process_{index}(values: list[int], limit: int = 0x{index:x}) -> dict[str, int]:
+    """Count the values above the limit."""
+    counts = {{"above": 0, "below": 0}}
+    for value in values:
+        if value > limit and value % 2 == 0:
+            counts["above"] += 1
+        elif value < -limit or not value:
+            counts["below"] -= 1
+        else:
+            continue
+    squares = [v ** 2 for v in values if v != {index}]
+    shape = Shape{index}(f"shape {{len(squares)}}", size=2.5e-3)
+    while limit > 0:
+        limit = limit - 1 if limit > 10 else limit // 2
+    print('done', shape.area(), squares[0:2], (limit, 0b1010, 0o17))
+    return counts
+
+"#
+        );
+        count += section.lines().count();
+        source.push_str(&section);
+    }
+    source
+}
+
+/// An expression nested `depth` levels deep in parentheses, brackets and
+/// unary minus; `depth` must stay below `MAX_NESTING_DEPTH`
+pub fn nested_expression(depth: usize) -> String {
+    assert!(depth < MAX_NESTING_DEPTH - 2, "too deep for the parser");
+    let openers = ["(", "[", "-"];
+    let closers = [")", "]", ""];
+    let mut source = String::from("x = ");
+    for level in 0..depth {
+        source.push_str(openers[level % openers.len()]);
+    }
+    source.push('1');
+    for level in (0..depth).rev() {
+        source.push_str(closers[level % closers.len()]);
+    }
+    source.push('\n');
+    source
+}
+
+/// `if` statements nested `depth` levels deep, each with a few statements;
+/// `depth` must stay below `MAX_NESTING_DEPTH`
+pub fn nested_blocks(depth: usize) -> String {
+    assert!(depth < MAX_NESTING_DEPTH - 2, "too deep for the parser");
+    let mut source = String::from("x = 1\n");
+    for level in 0..depth {
+        let indent = "    ".repeat(level);
+        source.push_str(&format!("{indent}if x > {level}:\n"));
+        source.push_str(&format!("{indent}    y = x + {level}\n"));
+    }
+    source.push_str(&format!("{}pass\n", "    ".repeat(depth)));
+    source
+}
+
+/// A list literal of `elements` integers, ten to a line
+pub fn huge_list(elements: usize) -> String {
+    let mut source = String::from("values = [\n");
+    for row in 0..elements.div_ceil(10) {
+        let items: Vec<String> = (row * 10..((row + 1) * 10).min(elements))
+            .map(|n| (n * 7919 % 100_000).to_string())
+            .collect();
+        source.push_str(&format!("    {},\n", items.join(", ")));
+    }
+    source.push_str("]\n");
+    source
+}
+
+/// A string literal of `length` characters, with escapes
+pub fn huge_string(length: usize) -> String {
+    let text = "lorem ipsum dolor sit amet \\n\\t";
+    let body: String = text.chars().cycle().take(length).collect();
+    // Do not cut an escape in half
+    let body = body.strip_suffix('\\').unwrap_or(&body);
+    format!("text = \"{}\"\n", body)
+}
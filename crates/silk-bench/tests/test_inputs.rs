@@ -0,0 +1,44 @@
+//! Tests that the benchmark inputs are valid programs of the requested size
+
+use silk_bench::{huge_list, huge_string, large_file, nested_blocks, nested_expression};
+use silk_parser::Parser;
+
+/// Parse on a thread with the stack of a main thread, where the benchmarks
+/// run; the test harness runs tests on smaller stacks
+fn parse(source: &str) {
+    let source = source.to_string();
+    let result = std::thread::Builder::new()
+        .stack_size(8 << 20)
+        .spawn(move || Parser::parse(&source).map(drop))
+        .unwrap()
+        .join()
+        .expect("parser panicked");
+    if let Err(err) = result {
+        panic!("benchmark input does not parse: {}", err);
+    }
+}
+
+#[test]
+fn test_large_file() {
+    let source = large_file(1_000);
+    assert!((1_000..1_100).contains(&source.lines().count()));
+    parse(&source);
+    assert_eq!(large_file(1_000), source, "inputs must be deterministic");
+}
+
+#[test]
+fn test_nested_inputs() {
+    parse(&nested_expression(90));
+    parse(&nested_blocks(90));
+}
+
+#[test]
+fn test_huge_literals() {
+    let source = huge_list(1_005);
+    assert_eq!(source.matches(',').count(), 1_005);
+    parse(&source);
+
+    let source = huge_string(10_001);
+    parse(&source);
+    assert!(source.len() >= 10_000);
+}
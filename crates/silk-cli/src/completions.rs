@@ -0,0 +1,83 @@
+/// Shell completions (`silk completions <shell>`)
+///
+/// The script printed for a shell calls back into `silk` on every tab
+/// press (with `COMPLETE=<shell>` set), so subcommands, flags and values
+/// always match the installed version. File arguments complete to `.silk`
+/// files and to directories.
+use clap::ValueEnum;
+use clap_complete::env::{Bash, EnvCompleter, Fish, Powershell, Zsh};
+use clap_complete::{ArgValueCompleter, PathCompleter};
+use std::env;
+use std::io::{self, Write};
+use std::path::Path;
+
+/// Environment variable that asks `silk` for completions
+pub const COMPLETE_VAR: &str = "COMPLETE";
+
+/// Shells completions can be generated for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+    Powershell,
+}
+
+impl Shell {
+    fn completer(self) -> &'static dyn EnvCompleter {
+        match self {
+            Shell::Bash => &Bash,
+            Shell::Zsh => &Zsh,
+            Shell::Fish => &Fish,
+            Shell::Powershell => &Powershell,
+        }
+    }
+}
+
+/// Write the script that registers completions with `shell`
+pub fn write_registration(shell: Shell, out: &mut dyn Write) -> io::Result<()> {
+    // The script calls this binary back, so it works before `silk` is on PATH
+    let binary = env::current_exe()
+        .map(|path| path.to_string_lossy().into_owned())
+        .unwrap_or_else(|_| "silk".to_string());
+    shell
+        .completer()
+        .write_registration(COMPLETE_VAR, "silk", "silk", &binary, out)
+}
+
+/// Completion of file arguments: Silk sources and directories
+pub fn source_paths() -> ArgValueCompleter {
+    ArgValueCompleter::new(PathCompleter::any().filter(is_source_candidate))
+}
+
+/// Completion of `silk run`'s file: a Silk source, or `-` for stdin
+pub fn source_file_or_stdin() -> ArgValueCompleter {
+    ArgValueCompleter::new(PathCompleter::any().filter(is_source_candidate).stdio())
+}
+
+fn is_source_candidate(path: &Path) -> bool {
+    path.is_dir() || path.extension().is_some_and(|ext| ext == "silk")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_source_candidates() {
+        assert!(is_source_candidate(Path::new("src/main.silk")));
+        assert!(is_source_candidate(Path::new(".")));
+        assert!(!is_source_candidate(Path::new("notes.txt")));
+    }
+
+    #[test]
+    fn test_registration_calls_back_into_silk() {
+        for shell in Shell::value_variants() {
+            let mut script = Vec::new();
+            write_registration(*shell, &mut script).unwrap();
+            let script = String::from_utf8(script).unwrap();
+            assert!(script.contains(COMPLETE_VAR), "{:?}", shell);
+            assert!(script.contains("silk"), "{:?}", shell);
+        }
+    }
+}
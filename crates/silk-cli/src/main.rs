@@ -1,9 +1,32 @@
 use anyhow::Result;
 /// Silk CLI - Command-line interface for the Silk compiler
-use clap::{Parser, Subcommand};
-use silk_compiler::Compiler;
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
+use silk_compiler::manifest::{init_project, new_project};
+use silk_compiler::{
+    apply_fixes, ast_diff, call_graph, codes, collect_source_files, discover_tests, ice,
+    ColorChoice, Compiler, CompilerOptions, Config, Database, FeatureSet, FileDiagnostics,
+    LintLevel, Manifest, ManifestError, ReleaseChannel, Renderer, Severity, SourceFile, SourceMap,
+    Timings, CMDLINE_NAME, STDIN_NAME,
+};
+use std::env;
 use std::fs;
-use std::path::PathBuf;
+use std::io::{self, IsTerminal, Read};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::fmt::format::FmtSpan;
+
+mod completions;
+mod memory;
+mod output;
+mod stats;
+mod watch;
+
+use output::{fail, out, outln, status, usage_error};
+use stats::Stats;
+
+#[global_allocator]
+static ALLOCATOR: memory::CountingAllocator = memory::CountingAllocator;
 
 #[derive(Parser)]
 #[command(name = "silk")]
@@ -12,113 +35,926 @@ use std::path::PathBuf;
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+
+    /// Enable unstable language features (comma-separated, e.g. comprehension-assignments)
+    #[arg(long, global = true, value_name = "FEATURES")]
+    unstable_features: Option<String>,
+
+    /// Log compiler phases (with their durations) to stderr
+    #[arg(short, long, global = true)]
+    verbose: bool,
+
+    /// Print per-phase durations and memory statistics
+    #[arg(long, global = true)]
+    timings: bool,
+
+    /// Print token, AST node and symbol counts and the memory used by each phase
+    #[arg(long, global = true)]
+    stats: bool,
+
+    /// Color diagnostics: auto (terminals, unless NO_COLOR is set), always or never
+    #[arg(long, global = true, value_name = "WHEN", default_value_t = ColorChoice::Auto)]
+    color: ColorChoice,
+
+    /// Only print diagnostics and warnings, no progress or success messages
+    #[arg(short, long, global = true)]
+    quiet: bool,
+
+    /// On an internal compiler error, also write the AST of the program to a temporary file
+    #[arg(long, global = true)]
+    ice_dump_ast: bool,
+
+    /// Show at most N errors (warnings are always shown)
+    #[arg(long, global = true, value_name = "N", value_parser = parse_max_errors)]
+    max_errors: Option<usize>,
+
+    /// Do not report these lints (comma-separated names or codes; wins over silk.toml and .silkrc)
+    #[arg(
+        short = 'A',
+        long,
+        global = true,
+        value_name = "LINTS",
+        value_delimiter = ',',
+        value_parser = parse_lint
+    )]
+    allow: Vec<&'static str>,
+
+    /// Report these lints as warnings
+    #[arg(
+        short = 'W',
+        long,
+        global = true,
+        value_name = "LINTS",
+        value_delimiter = ',',
+        value_parser = parse_lint
+    )]
+    warn: Vec<&'static str>,
+
+    /// Report these lints as errors
+    #[arg(
+        short = 'D',
+        long,
+        global = true,
+        value_name = "LINTS",
+        value_delimiter = ',',
+        value_parser = parse_lint
+    )]
+    deny: Vec<&'static str>,
 }
 
 #[derive(Subcommand)]
 enum Commands {
-    /// Compile a Silk file
+    /// Compile Silk files
     Build {
-        /// Input file
-        #[arg(value_name = "FILE")]
-        file: PathBuf,
+        /// Input files, directories, or glob patterns (default: the project's source dirs)
+        #[arg(value_name = "PATH", add = completions::source_paths())]
+        paths: Vec<PathBuf>,
 
         /// Output file
         #[arg(short, long)]
         output: Option<PathBuf>,
 
         /// Optimization level (0-3)
-        #[arg(short = 'O', long, default_value = "0")]
+        #[arg(
+            short = 'O',
+            long,
+            default_value = "0",
+            value_parser = clap::value_parser!(u8).range(0..=3)
+        )]
         opt_level: u8,
     },
 
     /// Compile and run a Silk file
     Run {
-        /// Input file
-        #[arg(value_name = "FILE")]
-        file: PathBuf,
+        /// Input file, or `-` for standard input (default: the project's entry point)
+        #[arg(value_name = "FILE", add = completions::source_file_or_stdin())]
+        file: Option<PathBuf>,
+
+        /// Run a program given inline (e.g. `silk run -c "print(1 + 1)"`)
+        #[arg(short = 'c', long, value_name = "CODE", conflicts_with = "file")]
+        code: Option<String>,
+
+        /// Re-run whenever a source file changes
+        #[arg(long, conflicts_with = "code")]
+        watch: bool,
     },
 
-    /// Type-check a Silk file without compiling
+    /// Type-check Silk files without compiling
     Check {
-        /// Input file
-        #[arg(value_name = "FILE")]
-        file: PathBuf,
+        /// Input files, directories, or glob patterns (default: the project's source dirs)
+        #[arg(value_name = "PATH", add = completions::source_paths())]
+        paths: Vec<PathBuf>,
+
+        /// Emit diagnostics as JSON lines (code, severity, message, file, span)
+        #[arg(long)]
+        json: bool,
+
+        /// Re-check whenever a source file changes
+        #[arg(long)]
+        watch: bool,
+
+        /// Apply safe fixes first (remove unused imports, prefix unused variables with `_`)
+        #[arg(long)]
+        fix: bool,
+    },
+
+    /// Discover and run test functions (`test_*` or `@test`)
+    Test {
+        /// Input files, directories, or glob patterns (default: the project's source dirs)
+        #[arg(value_name = "PATH", add = completions::source_paths())]
+        paths: Vec<PathBuf>,
+
+        /// Only list the discovered tests
+        #[arg(long)]
+        list: bool,
+    },
+
+    /// Report on the structure of Silk programs
+    Analyze {
+        /// Input files, directories, or glob patterns (default: the project's source dirs)
+        #[arg(value_name = "PATH", add = completions::source_paths())]
+        paths: Vec<PathBuf>,
+
+        /// Print the call graph: caller → callee edges with call locations
+        #[arg(long, value_name = "FORMAT")]
+        call_graph: Option<GraphFormat>,
+
+        /// Print the complexity, nesting depth, parameter count and length
+        /// of each function, marking values above the `[metrics]` limits
+        #[arg(long)]
+        metrics: bool,
+    },
+
+    /// Show structural differences between two versions of a file
+    ///
+    /// Functions, classes and methods added, removed or with a changed
+    /// signature, and statements changed in each body. Both files are
+    /// compared in printed form, so formatting and comments are ignored.
+    Diff {
+        /// The old version
+        #[arg(value_name = "OLD", add = completions::source_paths())]
+        old: PathBuf,
+
+        /// The new version
+        #[arg(value_name = "NEW", add = completions::source_paths())]
+        new: PathBuf,
+    },
+
+    /// Create a new Silk project in a new directory
+    New {
+        /// Project name (also the directory name)
+        #[arg(value_name = "NAME")]
+        name: String,
+
+        /// Create the project even inside another project
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Create a Silk project in the current directory
+    Init {
+        /// Project name (default: the directory name)
+        #[arg(long)]
+        name: Option<String>,
+
+        /// Create the project even inside another project
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Explain an error code (e.g. `silk explain E0200`), or list all codes
+    Explain {
+        /// Error code or error name (e.g. E0200 or undefined-variable)
+        #[arg(value_name = "CODE")]
+        code: Option<String>,
     },
 
     /// Show tokens (lexer output) for debugging
     Lex {
         /// Input file
-        #[arg(value_name = "FILE")]
+        #[arg(value_name = "FILE", add = completions::source_paths())]
         file: PathBuf,
     },
+
+    /// Start the language server on stdin/stdout
+    Lsp,
+
+    /// Print a shell script that sets up tab completion for silk
+    ///
+    /// For example, add `source <(silk completions bash)` to ~/.bashrc, or
+    /// `silk completions fish | source` to the fish configuration.
+    Completions {
+        /// Shell to complete in
+        #[arg(value_enum)]
+        shell: completions::Shell,
+    },
 }
 
-fn main() -> Result<()> {
+/// Output format of `silk analyze` graphs
+#[derive(Clone, Copy, ValueEnum)]
+enum GraphFormat {
+    /// Graphviz DOT
+    Dot,
+    /// JSON document
+    Json,
+}
+
+fn main() {
+    // Answer tab completion requests from the script of `silk completions`
+    clap_complete::CompleteEnv::with_factory(Cli::command)
+        .var(completions::COMPLETE_VAR)
+        .complete();
+
+    // Invalid arguments exit with status 2
     let cli = Cli::parse();
-    let compiler = Compiler::new();
+    output::set_quiet(cli.quiet);
+    if let Err(e) = run(cli) {
+        eprintln!("✗ {:#}", e);
+        std::process::exit(output::INTERNAL);
+    }
+}
+
+/// Run a command; errors returned are failures of silk itself, errors
+/// of the input exit with their status directly
+fn run(cli: Cli) -> Result<()> {
+    let started = Instant::now();
+
+    if cli.verbose {
+        tracing_subscriber::fmt()
+            .with_max_level(LevelFilter::DEBUG)
+            .with_span_events(FmtSpan::CLOSE)
+            .with_writer(std::io::stderr)
+            .init();
+    }
+
+    let features = match &cli.unstable_features {
+        Some(list) => FeatureSet::from_flag(list, ReleaseChannel::current())
+            .unwrap_or_else(|e| usage_error(e)),
+        None => FeatureSet::new(),
+    };
+    let mut options = CompilerOptions::new()
+        .with_features(features)
+        .with_color(cli.color)
+        .with_ice_dump_ast(cli.ice_dump_ast);
+    if let Some(count) = cli.max_errors {
+        options = options.with_max_errors(count);
+    }
+    let lint_flags = [
+        (&cli.allow, LintLevel::Allow),
+        (&cli.warn, LintLevel::Warn),
+        (&cli.deny, LintLevel::Error),
+    ];
+    for (lints, level) in lint_flags {
+        for &lint in lints {
+            options = options.with_lint(lint, level);
+        }
+    }
+    if let Commands::Build { opt_level, .. } = &cli.command {
+        options = options.with_opt_level(*opt_level);
+    }
+    // Diagnostics go to stderr
+    let renderer = Renderer::new(options.color.use_color(std::io::stderr().is_terminal()));
+    let compiler = Compiler::with_options(options);
 
     match cli.command {
-        Commands::Build {
-            file,
-            output: _,
-            opt_level,
-        } => {
-            println!("Building {} (opt-level: {})...", file.display(), opt_level);
-            let source = fs::read_to_string(&file)?;
+        Commands::Build { paths, .. } => {
+            let (files, config) = resolve_sources(&paths).unwrap_or_else(|e| usage_error(e));
+            status!(
+                "Building {} file(s) (opt-level: {})...",
+                files.len(),
+                compiler.options().opt_level
+            );
 
-            // For now, just lex
-            match compiler.lex(&source) {
-                Ok(tokens) => {
-                    println!("✓ Lexing successful ({} tokens)", tokens.len());
-                    // TODO: Continue with parsing, semantic analysis, codegen
-                    println!("⚠ Full compilation not yet implemented");
+            let reports = check_files(&compiler, &files, config.as_ref());
+            print_reports(&reports, &renderer);
+            let failed = print_summary(&reports, &renderer);
+            if cli.timings {
+                print_timings(&reports, started);
+            }
+            if cli.stats {
+                eprintln!("{}", Stats::collect(&compiler, &files));
+            }
+            if failed {
+                std::process::exit(error_status(&reports));
+            }
+            // TODO: Continue with codegen
+            eprintln!("⚠ Full compilation not yet implemented");
+        }
+
+        Commands::Run { file, code, watch } => {
+            let inline = match (&code, &file) {
+                (Some(code), _) => Some((CMDLINE_NAME, code.clone())),
+                (None, Some(path)) if path.as_os_str() == "-" => {
+                    if watch {
+                        usage_error("Cannot watch standard input");
+                    }
+                    let mut source = String::new();
+                    io::stdin().read_to_string(&mut source)?;
+                    Some((STDIN_NAME, source))
+                }
+                _ => None,
+            };
+            if let Some((name, source)) = inline {
+                let status = run_program(&compiler, name, &source, &renderer);
+                if status != 0 {
+                    std::process::exit(status);
                 }
+                return Ok(());
+            }
+
+            let file = file.unwrap_or_else(|| load_manifest().entry_path());
+            let name = file.display().to_string();
+            // Exit status of one run
+            let run = || match fs::read_to_string(&file) {
+                Ok(source) => run_program(&compiler, &name, &source, &renderer),
                 Err(e) => {
-                    eprintln!("✗ Lexing failed: {}", e);
-                    std::process::exit(1);
+                    eprintln!("✗ Cannot read {}: {}", file.display(), e);
+                    output::USAGE
+                }
+            };
+
+            if watch {
+                // Watch the whole project, or the directory of a standalone file
+                let root = match Manifest::discover(&env::current_dir()?) {
+                    Ok(manifest) => manifest.root,
+                    Err(_) => file
+                        .parent()
+                        .filter(|dir| !dir.as_os_str().is_empty())
+                        .map(PathBuf::from)
+                        .unwrap_or_else(|| PathBuf::from(".")),
+                };
+                watch::watch(&[root], || {
+                    run();
+                })?;
+            } else {
+                let status = run();
+                if status != 0 {
+                    std::process::exit(status);
                 }
             }
         }
 
-        Commands::Run { file } => {
-            println!("Running {}...", file.display());
-            println!("⚠ Run command not yet implemented");
+        Commands::Check {
+            paths,
+            json,
+            watch,
+            fix,
+        } => {
+            let mut database = Database::new(compiler.clone());
+            let measure = Measure {
+                timings: cli.timings,
+                stats: cli.stats,
+            };
+            if watch {
+                let roots = if paths.is_empty() {
+                    vec![load_manifest().root]
+                } else {
+                    watch::watch_roots(&paths)
+                };
+                // Only files that changed since the last run are checked again
+                watch::watch(&roots, || {
+                    run_check(
+                        &compiler,
+                        &mut database,
+                        &paths,
+                        json,
+                        fix,
+                        measure,
+                        &renderer,
+                    );
+                })?;
+            } else {
+                let status = run_check(
+                    &compiler,
+                    &mut database,
+                    &paths,
+                    json,
+                    fix,
+                    measure,
+                    &renderer,
+                );
+                if status != 0 {
+                    std::process::exit(status);
+                }
+            }
         }
 
-        Commands::Check { file } => {
-            println!("Type-checking {}...", file.display());
-            let source = fs::read_to_string(&file)?;
+        Commands::Test { paths, list } => {
+            let (files, config) = resolve_sources(&paths).unwrap_or_else(|e| usage_error(e));
 
-            match compiler.lex(&source) {
-                Ok(_) => {
-                    println!("✓ Lexing successful");
-                    println!("⚠ Type checking not yet implemented");
+            // Tests only run on programs that pass the checker
+            let reports = check_files(&compiler, &files, config.as_ref());
+            if cli.timings {
+                print_timings(&reports, started);
+            }
+            if cli.stats {
+                eprintln!("{}", Stats::collect(&compiler, &files));
+            }
+            if reports.iter().any(|r| r.error_count() > 0) {
+                print_reports(&reports, &renderer);
+                print_summary(&reports, &renderer);
+                std::process::exit(error_status(&reports));
+            }
+
+            let mut total = 0;
+            let mut invalid = 0;
+            for file in &files {
+                let source = read_input(file);
+                let program = compiler.parse(&source)?;
+                for test in discover_tests(&program) {
+                    total += 1;
+                    if test.is_runnable() {
+                        outln!("  {}::{}", file.display(), test.name);
+                    } else {
+                        invalid += 1;
+                        eprintln!(
+                            "  ✗ {}::{} takes {} required parameter(s) (line {}); tests take none",
+                            file.display(),
+                            test.name,
+                            test.required_params,
+                            test.span.line
+                        );
+                    }
                 }
-                Err(e) => {
-                    eprintln!("✗ Lexing failed: {}", e);
-                    std::process::exit(1);
+            }
+            status!("Discovered {} test(s) in {} file(s)", total, files.len());
+
+            if invalid > 0 {
+                fail();
+            }
+            if !list {
+                eprintln!("⚠ Test execution not yet implemented (requires the interpreter)");
+                fail();
+            }
+        }
+
+        Commands::Analyze {
+            paths,
+            call_graph,
+            metrics,
+        } => {
+            if call_graph.is_none() && !metrics {
+                usage_error("Nothing to analyze (use --call-graph dot|json or --metrics)");
+            }
+            let (files, config) = resolve_sources(&paths).unwrap_or_else(|e| usage_error(e));
+            let compiler = configured(&compiler, config.as_ref());
+
+            let mut graphs = Vec::new();
+            let mut measured = Vec::new();
+            for file in files {
+                let source = read_input(&file);
+                let parse_error = |e: silk_compiler::ParseError| -> ! {
+                    eprintln!("✗ {}: {}", file.display(), e);
+                    fail();
+                };
+                if call_graph.is_some() {
+                    let graph = compiler.call_graph(file.clone(), &source);
+                    graphs.push(graph.unwrap_or_else(|e| parse_error(e)));
                 }
+                if metrics {
+                    let file_metrics = compiler.metrics(file.clone(), &source);
+                    measured.push(file_metrics.unwrap_or_else(|e| parse_error(e)));
+                }
+            }
+            match call_graph {
+                Some(GraphFormat::Dot) => out!("{}", call_graph::to_dot(&graphs)),
+                Some(GraphFormat::Json) => outln!("{:#}", call_graph::to_json(&graphs)),
+                None => {}
+            }
+            if metrics {
+                if call_graph.is_some() {
+                    outln!();
+                }
+                let limits = compiler.metric_limits();
+                out!("{}", silk_compiler::metrics::to_table(&measured, &limits));
+            }
+        }
+
+        Commands::Diff { old, new } => {
+            let mut programs = Vec::new();
+            for file in [&old, &new] {
+                let source = read_input(file);
+                match compiler.parse(&source) {
+                    Ok(program) => programs.push(program),
+                    Err(e) => {
+                        eprintln!("✗ {}: {}", file.display(), e);
+                        fail();
+                    }
+                }
+            }
+
+            let changes = ast_diff::diff(&programs[0], &programs[1]);
+            for change in &changes {
+                out!("{}", change);
+            }
+            if changes.is_empty() {
+                status!("✓ No structural differences");
+            }
+        }
+
+        Commands::New { name, force } => match new_project(&env::current_dir()?, &name, force) {
+            Ok(manifest) => status!(
+                "✓ Created project '{}' in {}",
+                name,
+                manifest.root.display()
+            ),
+            Err(e) => usage_error(e),
+        },
+
+        Commands::Init { name, force } => {
+            let dir = env::current_dir()?;
+            let name = name.unwrap_or_else(|| {
+                dir.file_name()
+                    .map(|n| n.to_string_lossy().into_owned())
+                    .unwrap_or_default()
+            });
+            match init_project(&dir, &name, force) {
+                Ok(_) => status!("✓ Initialized project '{}'", name),
+                Err(e) => usage_error(e),
             }
         }
 
+        Commands::Explain { code } => match code {
+            Some(code) => match codes::lookup(&code) {
+                Some(entry) => {
+                    outln!("{} ({})\n", entry.code, entry.name);
+                    out!("{}", entry.explanation);
+                }
+                None => usage_error(format!(
+                    "Unknown error code '{}' (codes look like E0200)",
+                    code
+                )),
+            },
+            None => {
+                let width = codes::ERROR_CODES
+                    .iter()
+                    .map(|entry| entry.name.len())
+                    .max();
+                for entry in codes::ERROR_CODES {
+                    outln!(
+                        "{}  {:<width$}  {}",
+                        entry.code,
+                        entry.name,
+                        entry.summary(),
+                        width = width.unwrap_or_default()
+                    );
+                }
+            }
+        },
+
         Commands::Lex { file } => {
-            let source = fs::read_to_string(&file)?;
+            let source = read_input(&file);
 
             match compiler.lex(&source) {
                 Ok(tokens) => {
-                    println!("Tokens for {}:\n", file.display());
+                    outln!("Tokens for {}:\n", file.display());
                     for (i, token) in tokens.iter().enumerate() {
-                        println!("{:4}: {:?}", i, token);
+                        outln!("{:4}: {:?}", i, token);
                     }
-                    println!("\nTotal: {} tokens", tokens.len());
+                    outln!("\nTotal: {} tokens", tokens.len());
                 }
                 Err(e) => {
                     eprintln!("✗ Lexing failed: {}", e);
-                    std::process::exit(1);
+                    fail();
                 }
             }
         }
+
+        Commands::Lsp => {
+            // Exit status 1 when the client exits without shutting down
+            if !silk_lsp::run_stdio(compiler)? {
+                fail();
+            }
+        }
+
+        Commands::Completions { shell } => {
+            completions::write_registration(shell, &mut io::stdout().lock())
+                .unwrap_or_else(|e| output::stdout_failed(e));
+        }
     }
 
     Ok(())
 }
+
+/// Parse `--max-errors`, which must allow at least one error
+fn parse_max_errors(value: &str) -> Result<usize, String> {
+    match value.parse::<usize>() {
+        Ok(0) => Err("must be at least 1".to_string()),
+        Ok(count) => Ok(count),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Parse a lint given to `-A`/`-W`/`-D` by name or code, to its name
+fn parse_lint(value: &str) -> Result<&'static str, String> {
+    match codes::lookup(value) {
+        Some(entry) if entry.lint => Ok(entry.name),
+        Some(entry) => Err(format!(
+            "{} ({}) is an error, not a lint",
+            entry.code, entry.name
+        )),
+        None => Err("no such lint (`silk explain` lists every code)".to_string()),
+    }
+}
+
+/// Find the project manifest for the current directory, exiting on failure
+fn load_manifest() -> Manifest {
+    let result = env::current_dir()
+        .map_err(ManifestError::from)
+        .and_then(|dir| Manifest::discover(&dir));
+    result.unwrap_or_else(|e| usage_error(e))
+}
+
+/// Read an input file, exiting with a usage error if it cannot be read
+fn read_input(path: &Path) -> String {
+    fs::read_to_string(path)
+        .unwrap_or_else(|e| usage_error(format!("Cannot read {}: {}", path.display(), e)))
+}
+
+/// Source files to process: the given paths, or the project's source
+/// directories when none are given, without the files the configuration
+/// ignores. The configuration (from silk.toml or .silkrc, if any) is
+/// returned for its lint levels.
+fn resolve_sources(paths: &[PathBuf]) -> Result<(Vec<PathBuf>, Option<Config>), String> {
+    let dir = env::current_dir().map_err(|e| e.to_string())?;
+    let (files, config) = if paths.is_empty() {
+        let manifest = Manifest::discover(&dir).map_err(|e| e.to_string())?;
+        (
+            collect_files(&manifest.source_paths())?,
+            Some(manifest.config()),
+        )
+    } else {
+        let config = Config::discover(&dir).map_err(|e| e.to_string())?;
+        (collect_files(paths)?, config)
+    };
+
+    let Some(config) = config else {
+        return Ok((files, None));
+    };
+    // Files named on the command line are checked even when ignored
+    let files: Vec<PathBuf> = files
+        .into_iter()
+        .filter(|file| paths.contains(file) || !config.is_ignored(file))
+        .collect();
+    if files.is_empty() {
+        return Err("No .silk files found (all are ignored)".to_string());
+    }
+    Ok((files, Some(config)))
+}
+
+/// Statistics to print after checking (`--timings`, `--stats`)
+#[derive(Debug, Clone, Copy)]
+struct Measure {
+    timings: bool,
+    stats: bool,
+}
+
+/// Run `silk check` once, reusing the results in `database` for files that
+/// did not change; returns its exit status
+fn run_check(
+    compiler: &Compiler,
+    database: &mut Database,
+    paths: &[PathBuf],
+    json: bool,
+    fix: bool,
+    measure: Measure,
+    renderer: &Renderer,
+) -> i32 {
+    let started = Instant::now();
+    let (files, config) = match resolve_sources(paths) {
+        Ok(sources) => sources,
+        Err(e) => {
+            eprintln!("✗ {}", e);
+            return output::USAGE;
+        }
+    };
+    if fix {
+        fix_files(compiler, &files, config.as_ref());
+    }
+    database.set_compiler(configured(compiler, config.as_ref()));
+    let reports = database.check_files(&files);
+
+    let failed = if json {
+        // One JSON object per line on stdout, nothing else
+        for diagnostic in reports.iter().flat_map(|r| &r.diagnostics) {
+            outln!("{}", diagnostic.to_json_line());
+        }
+        print_hidden_errors(&reports);
+        reports.iter().any(|r| r.error_count() > 0)
+    } else {
+        print_reports(&reports, renderer);
+        print_summary(&reports, renderer)
+    };
+
+    if measure.timings {
+        print_timings(&reports, started);
+    }
+    if measure.stats {
+        eprintln!("{}", Stats::collect(database.compiler(), &files));
+    }
+    if failed {
+        error_status(&reports)
+    } else {
+        0
+    }
+}
+
+/// Exit status for reports with errors: `INTERNAL` if the compiler
+/// failed on a file, `FAILURE` otherwise
+fn error_status(reports: &[FileDiagnostics]) -> i32 {
+    let internal = reports
+        .iter()
+        .flat_map(|report| &report.diagnostics)
+        .any(ice::is_ice);
+    if internal {
+        output::INTERNAL
+    } else {
+        output::FAILURE
+    }
+}
+
+/// Check files in parallel, applying the configured lint levels
+fn check_files(
+    compiler: &Compiler,
+    files: &[PathBuf],
+    config: Option<&Config>,
+) -> Vec<FileDiagnostics> {
+    configured(compiler, config).check_files(files)
+}
+
+/// The compiler with the lint levels of the configuration applied
+fn configured(compiler: &Compiler, config: Option<&Config>) -> Compiler {
+    let mut compiler = compiler.clone();
+    if let Some(config) = config {
+        compiler.options_mut().merge_config(config);
+    }
+    compiler
+}
+
+/// Apply the fixes of every diagnostic still reported under the configured
+/// lint levels, rewriting the files in place
+///
+/// Progress goes to stderr so `--json` output stays machine-readable. A
+/// file is left untouched if the fixed code would no longer parse.
+fn fix_files(compiler: &Compiler, files: &[PathBuf], config: Option<&Config>) {
+    for report in check_files(compiler, files, config) {
+        if report.diagnostics.iter().all(|d| d.fix.is_none()) {
+            continue;
+        }
+        let path = report.path.display();
+        let source = match fs::read_to_string(&report.path) {
+            Ok(source) => source,
+            Err(e) => {
+                eprintln!("✗ Cannot read {}: {}", path, e);
+                continue;
+            }
+        };
+
+        let (fixed, count) = apply_fixes(&source, &report.diagnostics);
+        if count == 0 {
+            continue;
+        }
+        if compiler.parse(&fixed).is_err() {
+            eprintln!("⚠ Not fixing {}: the fixed code would not parse", path);
+            continue;
+        }
+        match fs::write(&report.path, fixed) {
+            Ok(()) => status!("✓ Fixed {} problem(s) in {}", count, path),
+            Err(e) => eprintln!("✗ Cannot write {}: {}", path, e),
+        }
+    }
+}
+
+/// Expand command-line paths into source files
+fn collect_files(paths: &[PathBuf]) -> Result<Vec<PathBuf>, String> {
+    match collect_source_files(paths) {
+        Ok(files) if files.is_empty() => Err("No .silk files found".to_string()),
+        Ok(files) => Ok(files),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Check a program and run it if it has no errors; returns the exit
+/// status
+fn run_program(compiler: &Compiler, name: &str, source: &str, renderer: &Renderer) -> i32 {
+    let reports = [compiler.check_source(name, source)];
+    let failed = reports[0].error_count() > 0;
+    if !reports[0].diagnostics.is_empty() {
+        let mut sources = SourceMap::new();
+        sources.add(name, source);
+        print_reports_from(&reports, sources, renderer);
+    }
+    if failed {
+        return error_status(&reports);
+    }
+
+    status!("Running {}...", name);
+    eprintln!("⚠ Run command not yet implemented");
+    0
+}
+
+/// Print each file's diagnostics, grouped per file, with the source line
+/// each one points at
+fn print_reports(reports: &[FileDiagnostics], renderer: &Renderer) {
+    print_reports_from(reports, SourceMap::new(), renderer);
+}
+
+/// Print reports, reading the sources not already in `sources` from disk
+fn print_reports_from(reports: &[FileDiagnostics], mut sources: SourceMap, renderer: &Renderer) {
+    for report in reports {
+        if report.diagnostics.is_empty() && report.hidden_errors == 0 {
+            status!("✓ {}", report.path.display());
+            continue;
+        }
+
+        eprintln!("{}:", report.path.display());
+        let path = report.path.display().to_string();
+        for diagnostic in &report.diagnostics {
+            let source = diagnostic
+                .span
+                .and_then(|_| load_source(&mut sources, &path));
+            eprint!("{}", renderer.render(diagnostic, source));
+        }
+    }
+    print_hidden_errors(reports);
+}
+
+/// Say how many errors `--max-errors` left out, if any
+fn print_hidden_errors(reports: &[FileDiagnostics]) {
+    let hidden: usize = reports.iter().map(|r| r.hidden_errors).sum();
+    if hidden > 0 {
+        eprintln!("... {} more error(s) not shown (see --max-errors)", hidden);
+    }
+}
+
+/// The text of a file, read on first use
+fn load_source<'a>(sources: &'a mut SourceMap, path: &str) -> Option<&'a SourceFile> {
+    if sources.find(path).is_none() {
+        sources.add(path, fs::read_to_string(path).ok()?);
+    }
+    sources.get(path)
+}
+
+/// Print the summary footer; returns true if any file has errors
+fn print_summary(reports: &[FileDiagnostics], renderer: &Renderer) -> bool {
+    let errors: usize = reports
+        .iter()
+        .map(|r| r.error_count() + r.hidden_errors)
+        .sum();
+    let warnings: usize = reports.iter().map(FileDiagnostics::warning_count).sum();
+    let failed_files = reports
+        .iter()
+        .filter(|r| r.error_count() + r.hidden_errors > 0)
+        .count();
+
+    if errors > 0 {
+        eprintln!(
+            "{} Checked {} file(s): {} error(s), {} warning(s) in {} file(s)",
+            renderer.marker(Severity::Error),
+            reports.len(),
+            errors,
+            warnings,
+            failed_files
+        );
+        let first_code = reports
+            .iter()
+            .flat_map(|r| &r.diagnostics)
+            .find_map(|d| d.error_code.as_deref().filter(|_| d.is_error()));
+        if let Some(code) = first_code {
+            eprintln!(
+                "For more information about an error, try `silk explain {}`",
+                code
+            );
+        }
+        true
+    } else {
+        status!(
+            "✓ Checked {} file(s): {} warning(s)",
+            reports.len(),
+            warnings
+        );
+        false
+    }
+}
+
+/// Print per-phase durations (summed over all files) and memory statistics
+fn print_timings(reports: &[FileDiagnostics], started: Instant) {
+    let mut timings = Timings::new();
+    for report in reports {
+        timings.merge(&report.timings);
+    }
+    let memory = memory::stats();
+
+    eprintln!("Timings ({} file(s), summed over threads):", reports.len());
+    eprintln!("{}", timings);
+    eprintln!(
+        "  {:<14}{:>10.3} ms",
+        "wall clock",
+        started.elapsed().as_secs_f64() * 1000.0
+    );
+    eprintln!(
+        "Memory: peak {:.1} KiB, {} allocation(s)",
+        memory.peak as f64 / 1024.0,
+        memory.allocations
+    );
+}
@@ -0,0 +1,97 @@
+/// Exit statuses and output streams
+///
+/// Scripts and editors can rely on:
+/// - exit status 0 on success, 1 when errors were reported (diagnostics,
+///   invalid tests), 2 for usage errors (bad arguments, unreadable inputs
+///   or configuration) and 101 for internal errors
+/// - machine-readable output (`--json`, `analyze`, `completions`, test
+///   lists) on stdout, and everything meant for people on stderr
+/// - `--quiet` leaving out progress and success messages, but never
+///   diagnostics or warnings
+/// - output piped into a reader that stops early (`silk explain | head`)
+///   ending silk quietly, with status 0
+use std::fmt;
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Errors were reported
+pub const FAILURE: i32 = 1;
+
+/// The command line, an input or the configuration is invalid
+pub const USAGE: i32 = 2;
+
+/// Silk itself failed: an internal compiler error (the same status as a
+/// panic)
+pub const INTERNAL: i32 = 101;
+
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// Leave out progress and success messages (`--quiet`)
+pub fn set_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
+pub fn is_quiet() -> bool {
+    QUIET.load(Ordering::Relaxed)
+}
+
+/// Print a progress or success message to stderr, unless `--quiet`
+macro_rules! status {
+    ($($arg:tt)*) => {
+        if !$crate::output::is_quiet() {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+pub(crate) use status;
+
+/// Print machine-readable output to stdout (see `write_stdout`)
+macro_rules! out {
+    ($($arg:tt)*) => {
+        $crate::output::write_stdout(format_args!($($arg)*))
+    };
+}
+
+/// `out!` with a newline
+macro_rules! outln {
+    () => {
+        $crate::output::write_stdout(format_args!("\n"))
+    };
+    ($($arg:tt)*) => {
+        $crate::output::write_stdout(format_args!("{}\n", format_args!($($arg)*)))
+    };
+}
+
+pub(crate) use {out, outln};
+
+/// Write to stdout through its locked handle
+///
+/// Unlike `print!`, a failed write does not panic: a reader that has gone
+/// away only wants no more output, so silk exits with status 0, and any
+/// other failure is reported as an internal error.
+pub fn write_stdout(args: fmt::Arguments) {
+    if let Err(e) = io::stdout().lock().write_fmt(args) {
+        stdout_failed(e)
+    }
+}
+
+/// Exit after a failed write to stdout
+pub fn stdout_failed(error: io::Error) -> ! {
+    if error.kind() == io::ErrorKind::BrokenPipe {
+        std::process::exit(0)
+    }
+    eprintln!("✗ Could not write the output: {}", error);
+    std::process::exit(INTERNAL)
+}
+
+/// Report a usage error and exit
+pub fn usage_error(message: impl std::fmt::Display) -> ! {
+    eprintln!("✗ {}", message);
+    std::process::exit(USAGE)
+}
+
+/// Exit with `FAILURE`
+pub fn fail() -> ! {
+    std::process::exit(FAILURE)
+}
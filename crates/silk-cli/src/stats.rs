@@ -0,0 +1,135 @@
+/// Compiler statistics for `--stats`
+///
+/// After checking, each file goes through the front end again, one phase
+/// at a time and one file at a time, so the counting allocator can tell the
+/// heap used by each phase apart:
+/// - peak: the most memory a phase had allocated at once, beyond what was
+///   allocated before it (largest over the files)
+/// - retained: what the result of a phase (tokens, AST, symbol table) still
+///   holds when it ends (summed over the files)
+use crate::memory;
+use silk_compiler::{Compiler, Phase};
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+
+/// Heap used by one phase
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PhaseMemory {
+    pub peak: usize,
+    pub retained: usize,
+}
+
+/// Sizes and memory use of the front end over some files
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub files: usize,
+    pub tokens: usize,
+    pub nodes: usize,
+    pub symbols: usize,
+    /// Memory of the lex, parse and semantic phases
+    pub phases: [PhaseMemory; 3],
+}
+
+const PHASES: [Phase; 3] = [Phase::Lex, Phase::Parse, Phase::Semantic];
+
+impl Stats {
+    /// Measure the files that can be read and lexed; what a phase cannot
+    /// process is left out of the later phases
+    pub fn collect(compiler: &Compiler, files: &[PathBuf]) -> Self {
+        let mut stats = Stats::default();
+        for file in files {
+            if let Ok(source) = fs::read_to_string(file) {
+                stats.files += 1;
+                stats.add(compiler, &source);
+            }
+        }
+        stats
+    }
+
+    fn add(&mut self, compiler: &Compiler, source: &str) {
+        let [lex, parse, semantic] = &mut self.phases;
+
+        let Ok(tokens) = measure(lex, || compiler.lex(source)) else {
+            return;
+        };
+        self.tokens += tokens.len();
+        let Ok(parsed) = measure(parse, || compiler.parse_tokens(tokens)) else {
+            return;
+        };
+        self.nodes += parsed.program.node_count;
+        let analyzer = measure(semantic, || {
+            let mut analyzer = compiler.semantic_analyzer();
+            let _ = analyzer.analyze(&parsed.program);
+            analyzer
+        });
+        self.symbols += analyzer
+            .symbol_table()
+            .scopes()
+            .iter()
+            .map(|scope| scope.symbols().len())
+            .sum::<usize>();
+    }
+}
+
+/// Run a phase, recording its peak and what its result retains
+fn measure<T>(usage: &mut PhaseMemory, phase: impl FnOnce() -> T) -> T {
+    let before = memory::current();
+    memory::reset_peak();
+    let result = phase();
+    let peak = memory::stats().peak.saturating_sub(before);
+    usage.peak = usage.peak.max(peak);
+    usage.retained += memory::current().saturating_sub(before);
+    result
+}
+
+fn size(bytes: usize) -> String {
+    if bytes >= 1 << 20 {
+        format!("{:.1} MiB", bytes as f64 / (1 << 20) as f64)
+    } else {
+        format!("{:.1} KiB", bytes as f64 / 1024.0)
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Stats ({} file(s)):", self.files)?;
+        writeln!(f, "  {:<14}{:>12}", "tokens", self.tokens)?;
+        writeln!(f, "  {:<14}{:>12}", "AST nodes", self.nodes)?;
+        writeln!(f, "  {:<14}{:>12}", "symbols", self.symbols)?;
+        writeln!(f, "  {:<14}{:>12}{:>14}", "phase", "peak", "retained")?;
+        for (phase, usage) in PHASES.iter().zip(&self.phases) {
+            writeln!(
+                f,
+                "  {:<14}{:>12}{:>14}",
+                phase.name(),
+                size(usage.peak),
+                size(usage.retained)
+            )?;
+        }
+        match memory::peak_rss() {
+            Some(rss) => write!(f, "  {:<14}{:>12}", "peak RSS", size(rss)),
+            None => write!(f, "  {:<14}{:>12}", "peak RSS", "unknown"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counts() {
+        let mut stats = Stats::default();
+        stats.add(&Compiler::new(), "x = 1\nprint(x)\n");
+        assert_eq!(stats.tokens, 10);
+        assert_eq!(stats.nodes, 7);
+        assert!(stats.symbols >= 1);
+
+        // Later phases are skipped for a file that does not parse
+        let mut stats = Stats::default();
+        stats.add(&Compiler::new(), "print((\n");
+        assert!(stats.tokens > 0);
+        assert_eq!((stats.nodes, stats.symbols), (0, 0));
+    }
+}
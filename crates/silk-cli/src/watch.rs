@@ -0,0 +1,169 @@
+/// Watch mode (`--watch`)
+///
+/// Re-runs a command whenever a Silk source file or `silk.toml` under the
+/// watched paths changes. Bursts of filesystem events (editors often write
+/// a file several times per save) are debounced into a single re-run.
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher as _};
+use std::io::{self, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::time::Duration;
+
+/// Quiet period that ends a burst of filesystem events
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// Filesystem watcher over a set of files and directories
+pub struct Watcher {
+    // Kept alive so events keep arriving on `events`
+    _watcher: RecommendedWatcher,
+    events: Receiver<notify::Result<Event>>,
+}
+
+impl Watcher {
+    /// Start watching `roots` (directories are watched recursively)
+    pub fn new(roots: &[PathBuf]) -> notify::Result<Self> {
+        let (sender, events) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(sender)?;
+        for root in roots {
+            watcher.watch(root, RecursiveMode::Recursive)?;
+        }
+        Ok(Self {
+            _watcher: watcher,
+            events,
+        })
+    }
+
+    /// Block until a relevant change happens and the burst has settled
+    ///
+    /// Returns false if the watcher stopped delivering events.
+    pub fn wait_for_change(&self) -> bool {
+        loop {
+            match self.events.recv() {
+                Ok(Ok(event)) if is_relevant(&event) => break,
+                Ok(_) => continue,
+                Err(_) => return false,
+            }
+        }
+
+        // Swallow the rest of the burst
+        loop {
+            match self.events.recv_timeout(DEBOUNCE) {
+                Ok(_) => continue,
+                Err(RecvTimeoutError::Timeout) => return true,
+                Err(RecvTimeoutError::Disconnected) => return false,
+            }
+        }
+    }
+}
+
+/// Check if an event touches a Silk source file, the project manifest or a
+/// `.silkrc`
+fn is_relevant(event: &Event) -> bool {
+    matches!(
+        event.kind,
+        EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+    ) && event.paths.iter().any(|path| is_watched_file(path))
+}
+
+fn is_watched_file(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "silk")
+        || path
+            .file_name()
+            .is_some_and(|name| name == "silk.toml" || name == ".silkrc")
+}
+
+/// Paths to watch for a list of command-line paths
+///
+/// Glob patterns are watched from their longest literal prefix directory.
+pub fn watch_roots(paths: &[PathBuf]) -> Vec<PathBuf> {
+    let mut roots: Vec<PathBuf> = paths
+        .iter()
+        .map(|path| {
+            let literal: PathBuf = path
+                .components()
+                .take_while(|c| !c.as_os_str().to_string_lossy().contains(['*', '?', '[']))
+                .collect();
+            if literal.as_os_str().is_empty() {
+                PathBuf::from(".")
+            } else {
+                literal
+            }
+        })
+        .collect();
+    roots.sort();
+    roots.dedup();
+    roots
+}
+
+/// Clear the terminal before a re-run
+///
+/// The escape codes go to stderr, with the rest of the human-readable
+/// output, so stdout stays machine output only (`--json`); nothing is
+/// written when stderr is redirected.
+pub fn clear_screen() {
+    let mut stderr = io::stderr();
+    if stderr.is_terminal() {
+        let _ = write!(stderr, "\x1B[2J\x1B[H");
+        let _ = stderr.flush();
+    }
+}
+
+/// Run `action` now and again after every change under `roots`
+pub fn watch(roots: &[PathBuf], mut action: impl FnMut()) -> notify::Result<()> {
+    let watcher = Watcher::new(roots)?;
+
+    loop {
+        clear_screen();
+        action();
+        eprintln!("\n👀 Watching for changes (Ctrl+C to stop)...");
+
+        if !watcher.wait_for_change() {
+            return Ok(());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notify::event::{CreateKind, ModifyKind};
+
+    #[test]
+    fn test_relevant_files() {
+        assert!(is_watched_file(Path::new("src/main.silk")));
+        assert!(is_watched_file(Path::new("project/silk.toml")));
+        assert!(!is_watched_file(Path::new("notes.txt")));
+        assert!(!is_watched_file(Path::new("main.silk.swp")));
+    }
+
+    #[test]
+    fn test_relevant_events() {
+        let modify = Event::new(EventKind::Modify(ModifyKind::Any)).add_path("a.silk".into());
+        assert!(is_relevant(&modify));
+
+        let create = Event::new(EventKind::Create(CreateKind::File)).add_path("a.txt".into());
+        assert!(!is_relevant(&create));
+
+        let access =
+            Event::new(EventKind::Access(notify::event::AccessKind::Any)).add_path("a.silk".into());
+        assert!(!is_relevant(&access));
+    }
+
+    #[test]
+    fn test_watch_roots() {
+        let roots = watch_roots(&[
+            PathBuf::from("src"),
+            PathBuf::from("tests/**/*.silk"),
+            PathBuf::from("*.silk"),
+            PathBuf::from("src"),
+        ]);
+        assert_eq!(
+            roots,
+            [
+                PathBuf::from("."),
+                PathBuf::from("src"),
+                PathBuf::from("tests")
+            ]
+        );
+    }
+}
@@ -0,0 +1,141 @@
+//! Tests for the exit statuses and output streams of the `silk` binary
+
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+use tempfile::TempDir;
+
+fn silk(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_silk"))
+        .args(args)
+        .current_dir(dir)
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap()
+}
+
+fn project() -> TempDir {
+    let dir = TempDir::new().unwrap();
+    fs::write(dir.path().join("ok.silk"), "print(1)\n").unwrap();
+    fs::write(dir.path().join("bad.silk"), "print(q)\n").unwrap();
+    dir
+}
+
+#[test]
+fn test_exit_statuses() {
+    let dir = project();
+    let status = |args: &[&str]| silk(dir.path(), args).status.code();
+
+    assert_eq!(status(&["check", "ok.silk"]), Some(0));
+    assert_eq!(status(&["check", "bad.silk"]), Some(1));
+    assert_eq!(status(&["check", "missing.silk"]), Some(2));
+    assert_eq!(status(&["check", "--no-such-flag"]), Some(2));
+    assert_eq!(status(&["explain", "E9999"]), Some(2));
+    assert_eq!(status(&["lex", "missing.silk"]), Some(2));
+    assert_eq!(status(&["diff", "ok.silk", "bad.silk"]), Some(0));
+    assert_eq!(status(&["diff", "ok.silk", "missing.silk"]), Some(2));
+}
+
+#[test]
+fn test_lint_flags_take_only_lints() {
+    let dir = project();
+    fs::write(dir.path().join("unused.silk"), "x = 1\n").unwrap();
+
+    // Lints are given by name or by code
+    let output = silk(dir.path(), &["check", "-A", "E0500", "unused.silk"]);
+    assert_eq!(output.status.code(), Some(0));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(!stderr.contains("Unused variable"));
+    let output = silk(
+        dir.path(),
+        &["check", "-D", "unused-variable", "unused.silk"],
+    );
+    assert_eq!(output.status.code(), Some(1));
+
+    for lint in ["E0200", "undefined-variable", "no-such-lint"] {
+        let output = silk(dir.path(), &["check", "-A", lint, "bad.silk"]);
+        assert_eq!(output.status.code(), Some(2), "-A {lint}");
+    }
+    let output = silk(dir.path(), &["check", "-W", "E0200", "ok.silk"]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("E0200 (undefined-variable) is an error, not a lint"));
+}
+
+#[test]
+fn test_human_output_goes_to_stderr() {
+    let dir = project();
+
+    let output = silk(dir.path(), &["check", "ok.silk", "bad.silk"]);
+    assert!(output.stdout.is_empty());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("✓ ok.silk"));
+    assert!(stderr.contains("Undefined variable 'q'"));
+
+    // JSON lines are the only thing on stdout
+    let output = silk(dir.path(), &["check", "--json", "bad.silk"]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout
+        .lines()
+        .all(|line| line.starts_with('{') && line.ends_with('}')));
+    assert!(!stdout.is_empty());
+}
+
+#[test]
+fn test_quiet_keeps_only_diagnostics() {
+    let dir = project();
+
+    let output = silk(dir.path(), &["check", "--quiet", "ok.silk"]);
+    assert_eq!(output.status.code(), Some(0));
+    assert!(output.stdout.is_empty() && output.stderr.is_empty());
+
+    let output = silk(dir.path(), &["check", "-q", "ok.silk", "bad.silk"]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(!stderr.contains("✓"));
+    assert!(stderr.contains("Undefined variable 'q'"));
+}
+
+#[test]
+fn test_closed_stdout_is_not_an_internal_error() {
+    use std::io::{BufRead, BufReader};
+    use std::process::Stdio;
+
+    // Far more tokens than a pipe holds, so silk is still writing when the
+    // reader goes away
+    let dir = project();
+    fs::write(dir.path().join("long.silk"), "x = 1\n".repeat(50_000)).unwrap();
+    let mut child = Command::new(env!("CARGO_BIN_EXE_silk"))
+        .args(["lex", "long.silk"])
+        .current_dir(dir.path())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut first = String::new();
+    BufReader::new(child.stdout.take().unwrap())
+        .read_line(&mut first)
+        .unwrap();
+    assert!(first.starts_with("Tokens for"));
+
+    let output = child.wait_with_output().unwrap();
+    assert_eq!(output.status.code(), Some(0));
+    assert!(
+        output.stderr.is_empty(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    // Other write failures are reported, without a panic
+    if let Ok(full) = fs::OpenOptions::new().write(true).open("/dev/full") {
+        let output = Command::new(env!("CARGO_BIN_EXE_silk"))
+            .args(["explain", "E0200"])
+            .stdout(full)
+            .output()
+            .unwrap();
+        assert_eq!(output.status.code(), Some(101));
+        let stderr = String::from_utf8(output.stderr).unwrap();
+        assert!(
+            stderr.starts_with("✗ Could not write the output"),
+            "{stderr}"
+        );
+    }
+}
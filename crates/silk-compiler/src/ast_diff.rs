@@ -0,0 +1,293 @@
+/// Structural differences between two versions of a program
+///
+/// Backs `silk diff old.silk new.silk`. Functions, classes and methods are
+/// matched by qualified name (`Point.norm`) and compared through the
+/// unparser, so layout, comments and quoting do not count as changes:
+/// - definitions only in one version are added or removed (the methods of
+///   an added or removed class are not listed separately)
+/// - a definition whose decorators, parameters, bases or return annotation
+///   differ has a changed signature
+/// - the statements of each function, class body and the module itself
+///   are diffed one statement at a time; nested functions are statements
+///   of the function they are defined in
+use silk_ast::{Program, Statement, StatementKind};
+use std::fmt;
+
+/// What a definition defines
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DefinitionKind {
+    Function,
+    Method,
+    Class,
+}
+
+impl DefinitionKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DefinitionKind::Function => "function",
+            DefinitionKind::Method => "method",
+            DefinitionKind::Class => "class",
+        }
+    }
+}
+
+/// A function, method or class in one version of the program
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Definition {
+    pub kind: DefinitionKind,
+    /// Qualified name (`Point.norm` for a method)
+    pub name: String,
+    /// Decorators and the `def`/`class` line, as printed by the unparser
+    pub header: String,
+    pub line: usize,
+}
+
+/// A statement only in one version of a body
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StatementChange {
+    Removed { line: usize, text: String },
+    Added { line: usize, text: String },
+}
+
+/// One structural difference
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change {
+    Added(Definition),
+    Removed(Definition),
+    SignatureChanged {
+        old: Definition,
+        new: Definition,
+    },
+    /// Statements of a body differ; `scope` is `None` for the module
+    BodyChanged {
+        scope: Option<String>,
+        statements: Vec<StatementChange>,
+    },
+}
+
+/// A definition or the module, with the statements of its own body
+struct Scope<'a> {
+    definition: Option<Definition>,
+    /// Qualified name of the enclosing class
+    parent: Option<String>,
+    statements: Vec<&'a Statement>,
+}
+
+impl Scope<'_> {
+    fn name(&self) -> Option<&str> {
+        self.definition
+            .as_ref()
+            .map(|definition| definition.name.as_str())
+    }
+}
+
+/// Differences from `old` to `new`: removed definitions first, then the
+/// rest in the order of the new program, module-level statements last
+pub fn diff(old: &Program, new: &Program) -> Vec<Change> {
+    let old_scopes = scopes(old);
+    let new_scopes = scopes(new);
+    let find = |scopes: &[Scope], name: Option<&str>| -> Option<usize> {
+        scopes.iter().position(|scope| scope.name() == name)
+    };
+
+    let mut changes = Vec::new();
+    for scope in &old_scopes {
+        if find(&new_scopes, scope.name()).is_none()
+            && find(&new_scopes, scope.parent.as_deref()).is_some()
+        {
+            changes.push(Change::Removed(scope.definition.clone().unwrap()));
+        }
+    }
+    for scope in &new_scopes {
+        let Some(index) = find(&old_scopes, scope.name()) else {
+            if find(&old_scopes, scope.parent.as_deref()).is_some() {
+                changes.push(Change::Added(scope.definition.clone().unwrap()));
+            }
+            continue;
+        };
+        let old_scope = &old_scopes[index];
+        if let (Some(old), Some(new)) = (&old_scope.definition, &scope.definition) {
+            if old.header != new.header {
+                changes.push(Change::SignatureChanged {
+                    old: old.clone(),
+                    new: new.clone(),
+                });
+            }
+        }
+        let statements = diff_statements(&old_scope.statements, &scope.statements);
+        if !statements.is_empty() {
+            changes.push(Change::BodyChanged {
+                scope: scope.name().map(str::to_string),
+                statements,
+            });
+        }
+    }
+    changes
+}
+
+/// Every function, class and method in source order, then the module
+fn scopes(program: &Program) -> Vec<Scope<'_>> {
+    let mut scopes = vec![Scope {
+        definition: None,
+        parent: None,
+        statements: Vec::new(),
+    }];
+    collect(&program.statements, 0, None, &mut scopes);
+    // Report the module after its definitions
+    scopes.rotate_left(1);
+    scopes
+}
+
+/// Add the statements of a module or class body to the scope at `index`,
+/// and a scope for each definition among them
+fn collect<'a>(
+    body: &'a [Statement],
+    index: usize,
+    class: Option<&str>,
+    scopes: &mut Vec<Scope<'a>>,
+) {
+    for statement in body {
+        let (kind, name, body) = match &statement.kind {
+            StatementKind::FunctionDef { name, body, .. } if class.is_some() => {
+                (DefinitionKind::Method, name, body)
+            }
+            StatementKind::FunctionDef { name, body, .. } => (DefinitionKind::Function, name, body),
+            StatementKind::ClassDef { name, body, .. } => (DefinitionKind::Class, name, body),
+            _ => {
+                scopes[index].statements.push(statement);
+                continue;
+            }
+        };
+        let name = match class {
+            Some(class) => format!("{}.{}", class, name),
+            None => name.clone(),
+        };
+        scopes.push(Scope {
+            definition: Some(Definition {
+                kind,
+                name: name.clone(),
+                header: header(statement),
+                line: statement.span.line,
+            }),
+            parent: class.map(str::to_string),
+            statements: Vec::new(),
+        });
+        let scope = scopes.len() - 1;
+        if kind == DefinitionKind::Class {
+            collect(body, scope, Some(&name), scopes);
+        } else {
+            scopes[scope].statements.extend(body);
+        }
+    }
+}
+
+/// Decorators and the `def`/`class` line of a definition
+fn header(definition: &Statement) -> String {
+    let mut empty = definition.clone();
+    if let StatementKind::FunctionDef { body, .. } | StatementKind::ClassDef { body, .. } =
+        &mut empty.kind
+    {
+        body.clear();
+    }
+    let printed = empty.to_string();
+    // Drop the `pass` printed for the empty body
+    let lines: Vec<&str> = printed.lines().collect();
+    lines[..lines.len() - 1].join("\n")
+}
+
+/// Statements removed and added between two bodies, by a longest common
+/// subsequence of their printed forms
+fn diff_statements(old: &[&Statement], new: &[&Statement]) -> Vec<StatementChange> {
+    let old_text: Vec<String> = old.iter().map(|statement| text(statement)).collect();
+    let new_text: Vec<String> = new.iter().map(|statement| text(statement)).collect();
+
+    // common[i][j]: length of the longest common subsequence of old[i..] and new[j..]
+    let mut common = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i][j] = if old_text[i] == new_text[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let mut changes = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old_text[i] == new_text[j] {
+            i += 1;
+            j += 1;
+        } else if j == new.len() || (i < old.len() && common[i + 1][j] >= common[i][j + 1]) {
+            changes.push(StatementChange::Removed {
+                line: old[i].span.line,
+                text: old_text[i].clone(),
+            });
+            i += 1;
+        } else {
+            changes.push(StatementChange::Added {
+                line: new[j].span.line,
+                text: new_text[j].clone(),
+            });
+            j += 1;
+        }
+    }
+    changes
+}
+
+fn text(statement: &Statement) -> String {
+    statement.to_string().trim_end().to_string()
+}
+
+/// Write `text` indented, marking its first line with `marker` and the
+/// source line it starts on
+fn write_lines(f: &mut fmt::Formatter<'_>, marker: &str, line: usize, text: &str) -> fmt::Result {
+    let number = line.to_string();
+    for (index, text) in text.lines().enumerate() {
+        if index == 0 {
+            writeln!(f, "    {} {} | {}", marker, number, text)?;
+        } else {
+            writeln!(f, "    {:width$} | {}", "", text, width = number.len() + 2)?;
+        }
+    }
+    Ok(())
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Change::Added(definition) => {
+                let kind = definition.kind.as_str();
+                writeln!(f, "added {} '{}'", kind, definition.name)?;
+                write_lines(f, "+", definition.line, &definition.header)
+            }
+            Change::Removed(definition) => {
+                let kind = definition.kind.as_str();
+                writeln!(f, "removed {} '{}'", kind, definition.name)?;
+                write_lines(f, "-", definition.line, &definition.header)
+            }
+            Change::SignatureChanged { old, new } => {
+                let kind = new.kind.as_str();
+                writeln!(f, "changed signature of {} '{}'", kind, new.name)?;
+                write_lines(f, "-", old.line, &old.header)?;
+                write_lines(f, "+", new.line, &new.header)
+            }
+            Change::BodyChanged { scope, statements } => {
+                match scope {
+                    Some(name) => writeln!(f, "changed statements in '{}'", name)?,
+                    None => writeln!(f, "changed module-level statements")?,
+                }
+                for statement in statements {
+                    match statement {
+                        StatementChange::Removed { line, text } => {
+                            write_lines(f, "-", *line, text)?
+                        }
+                        StatementChange::Added { line, text } => write_lines(f, "+", *line, text)?,
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+}
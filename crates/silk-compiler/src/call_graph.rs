@@ -0,0 +1,132 @@
+/// Call graph export
+///
+/// Renders the call graphs of checked files for `silk analyze --call-graph`:
+/// - `dot`: a Graphviz digraph with one cluster per file; edges are
+///   labelled with the `line:column` of the call
+/// - `json`: one object per file listing its functions and caller→callee
+///   edges with spans
+///
+/// Calls are resolved within a file; calls into other modules are not
+/// part of the graph.
+use crate::Compiler;
+use silk_lexer::Span;
+use silk_semantic::call_graph::MODULE_NODE;
+use silk_semantic::CallGraph;
+use std::fmt::Write;
+use std::path::PathBuf;
+
+/// Call graph of one source file
+#[derive(Debug, Clone)]
+pub struct FileCallGraph {
+    pub path: PathBuf,
+    pub graph: CallGraph,
+}
+
+impl Compiler {
+    /// Parse `source` and build its call graph
+    pub fn call_graph(
+        &self,
+        path: PathBuf,
+        source: &str,
+    ) -> Result<FileCallGraph, crate::ParseError> {
+        let program = self.parse(source)?;
+        Ok(FileCallGraph {
+            path,
+            graph: CallGraph::build(&program),
+        })
+    }
+}
+
+/// Render call graphs as a Graphviz `digraph`
+pub fn to_dot(graphs: &[FileCallGraph]) -> String {
+    let mut dot = String::from("digraph calls {\n    node [shape=box];\n");
+    for (index, file) in graphs.iter().enumerate() {
+        let path = file.path.display().to_string();
+        let id = |name: &str| quote(&format!("{}::{}", path, name));
+
+        let _ = writeln!(dot, "    subgraph cluster_{} {{", index);
+        let _ = writeln!(dot, "        label={};", quote(&path));
+        if file.graph.callees(MODULE_NODE).next().is_some() {
+            let _ = writeln!(
+                dot,
+                "        {} [label={}, shape=ellipse];",
+                id(MODULE_NODE),
+                quote(MODULE_NODE)
+            );
+        }
+        for function in file.graph.functions() {
+            let _ = writeln!(
+                dot,
+                "        {} [label={}];",
+                id(&function.name),
+                quote(&function.name)
+            );
+        }
+        dot.push_str("    }\n");
+
+        for edge in file.graph.edges() {
+            let _ = writeln!(
+                dot,
+                "    {} -> {} [label=\"{}:{}\"];",
+                id(&edge.caller),
+                id(&edge.callee),
+                edge.span.line,
+                edge.span.column
+            );
+        }
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+/// Render call graphs as a JSON document
+pub fn to_json(graphs: &[FileCallGraph]) -> serde_json::Value {
+    let files: Vec<serde_json::Value> = graphs
+        .iter()
+        .map(|file| {
+            let functions: Vec<serde_json::Value> = file
+                .graph
+                .functions()
+                .iter()
+                .map(|function| {
+                    serde_json::json!({
+                        "name": function.name,
+                        "span": span_json(function.span),
+                    })
+                })
+                .collect();
+            let edges: Vec<serde_json::Value> = file
+                .graph
+                .edges()
+                .iter()
+                .map(|edge| {
+                    serde_json::json!({
+                        "caller": edge.caller,
+                        "callee": edge.callee,
+                        "span": span_json(edge.span),
+                    })
+                })
+                .collect();
+            serde_json::json!({
+                "file": file.path.display().to_string(),
+                "functions": functions,
+                "edges": edges,
+            })
+        })
+        .collect();
+    serde_json::json!({ "files": files })
+}
+
+fn span_json(span: Span) -> serde_json::Value {
+    serde_json::json!({
+        "start": span.start,
+        "end": span.end,
+        "line": span.line,
+        "column": span.column,
+    })
+}
+
+/// Quote a DOT identifier
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
@@ -0,0 +1,320 @@
+/// Per-project lint and formatter configuration
+///
+/// The tables below configure checking for every file under the directory
+/// holding them. They live in the project's `silk.toml`, or in a `.silkrc`
+/// for code that is not a Silk project:
+///
+/// ```toml
+/// [lints]
+/// unused-function = "error"
+///
+/// [sources]
+/// ignore = ["build/**", "vendor"]
+///
+/// [[overrides]]
+/// files = ["tests/**"]
+/// lints = { unused-variable = "allow" }
+///
+/// [format]
+/// indent-width = 4
+/// line-length = 100
+///
+/// [duplicate-code]
+/// min-nodes = 25
+///
+/// [metrics]
+/// max-complexity = 10
+/// max-nesting = 4
+/// max-parameters = 6
+/// max-lines = 60
+/// ```
+///
+/// Patterns are globs relative to the directory of the file; a pattern
+/// that matches a directory matches everything in it. Sources found by
+/// searching directories are skipped when ignored, but files named on the
+/// command line are always checked.
+///
+/// Lint levels given on the command line win over overrides, and
+/// overrides win over the `[lints]` table.
+use crate::manifest::{Manifest, ManifestError, MANIFEST_FILE};
+use crate::LintLevel;
+use glob::{MatchOptions, Pattern};
+use serde::{Deserialize, Serialize};
+use silk_semantic::{MetricLimits, DEFAULT_DUPLICATE_MIN_NODES};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// File name of the configuration of a directory that is not a project
+pub const CONFIG_FILE: &str = ".silkrc";
+
+/// The `[sources]` table
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct Sources {
+    /// Glob patterns of files and directories left out of checks
+    #[serde(default)]
+    pub ignore: Vec<String>,
+}
+
+impl Sources {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.ignore.is_empty()
+    }
+}
+
+/// An `[[overrides]]` entry: lint levels for some of the files
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct LintOverride {
+    /// Glob patterns of the files the levels apply to
+    pub files: Vec<String>,
+    /// Lint levels by diagnostic code
+    #[serde(default)]
+    pub lints: BTreeMap<String, LintLevel>,
+}
+
+impl LintOverride {
+    /// Check whether a file or one of its directories matches a pattern;
+    /// patterns and path must both be absolute, or both relative
+    pub fn matches(&self, path: &Path) -> bool {
+        matches_any(&self.files, path)
+    }
+}
+
+/// The `[format]` table
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct FormatConfig {
+    /// Spaces per indentation level
+    #[serde(default = "default_indent_width")]
+    pub indent_width: usize,
+    /// Longest line the formatter aims for
+    #[serde(default = "default_line_length")]
+    pub line_length: usize,
+}
+
+fn default_indent_width() -> usize {
+    4
+}
+
+fn default_line_length() -> usize {
+    100
+}
+
+impl Default for FormatConfig {
+    fn default() -> Self {
+        Self {
+            indent_width: default_indent_width(),
+            line_length: default_line_length(),
+        }
+    }
+}
+
+impl FormatConfig {
+    pub(crate) fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Settings of the `duplicate-code` lint
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct DuplicateCodeConfig {
+    /// Smallest duplicate reported, in statements and expressions
+    #[serde(default = "default_min_nodes")]
+    pub min_nodes: usize,
+}
+
+fn default_min_nodes() -> usize {
+    DEFAULT_DUPLICATE_MIN_NODES
+}
+
+impl Default for DuplicateCodeConfig {
+    fn default() -> Self {
+        Self {
+            min_nodes: default_min_nodes(),
+        }
+    }
+}
+
+impl DuplicateCodeConfig {
+    pub(crate) fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Limits of the `complex-function` lint; a function is reported when a
+/// metric is above its limit
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct MetricsConfig {
+    /// Cyclomatic complexity
+    pub max_complexity: usize,
+    /// Depth of nested compound statements
+    pub max_nesting: usize,
+    /// Parameters, not counting `self` or `cls`
+    pub max_parameters: usize,
+    /// Lines from the `def` line to the end of the body
+    pub max_lines: usize,
+}
+
+impl Default for MetricsConfig {
+    fn default() -> Self {
+        let limits = MetricLimits::default();
+        Self {
+            max_complexity: limits.complexity,
+            max_nesting: limits.nesting,
+            max_parameters: limits.parameters,
+            max_lines: limits.lines,
+        }
+    }
+}
+
+impl MetricsConfig {
+    pub(crate) fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// The limits as checked by the analyzer
+    pub fn limits(&self) -> MetricLimits {
+        MetricLimits {
+            complexity: self.max_complexity,
+            nesting: self.max_nesting,
+            parameters: self.max_parameters,
+            lines: self.max_lines,
+        }
+    }
+}
+
+/// Lint and formatter settings of a directory, from `silk.toml` or
+/// `.silkrc`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// Lint levels by diagnostic code
+    #[serde(default)]
+    pub lints: BTreeMap<String, LintLevel>,
+    /// Files left out of checks
+    #[serde(default)]
+    pub sources: Sources,
+    /// Lint levels for some of the files, later entries winning
+    #[serde(default)]
+    pub overrides: Vec<LintOverride>,
+    /// Formatter settings
+    #[serde(default)]
+    pub format: FormatConfig,
+    /// Settings of the `duplicate-code` lint
+    #[serde(default, rename = "duplicate-code")]
+    pub duplicate_code: DuplicateCodeConfig,
+    /// Limits of the `complex-function` lint
+    #[serde(default)]
+    pub metrics: MetricsConfig,
+    /// Directory patterns are relative to (not serialized)
+    #[serde(skip)]
+    pub root: PathBuf,
+}
+
+impl Config {
+    /// Parse `.silkrc` contents; `path` is the file, whose directory
+    /// patterns are relative to
+    pub fn parse(contents: &str, path: &Path) -> Result<Self, ManifestError> {
+        let invalid = |message: String| ManifestError::Invalid {
+            path: path.to_path_buf(),
+            message,
+        };
+        let mut config: Config =
+            toml::from_str(contents).map_err(|e| invalid(e.message().to_string()))?;
+        config.validate().map_err(invalid)?;
+        config.root = path.parent().map(Path::to_path_buf).unwrap_or_default();
+        Ok(config)
+    }
+
+    /// Load a `.silkrc` file
+    pub fn load(path: &Path) -> Result<Self, ManifestError> {
+        Self::parse(&fs::read_to_string(path)?, path)
+    }
+
+    /// Find the configuration of `start`: the nearest `silk.toml` or
+    /// `.silkrc` in it or its ancestors (`silk.toml` first when a directory
+    /// has both)
+    pub fn discover(start: &Path) -> Result<Option<Self>, ManifestError> {
+        for dir in start.ancestors() {
+            let manifest = dir.join(MANIFEST_FILE);
+            if manifest.is_file() {
+                return Manifest::load(&manifest).map(|manifest| Some(manifest.config()));
+            }
+            let config = dir.join(CONFIG_FILE);
+            if config.is_file() {
+                return Self::load(&config).map(Some);
+            }
+        }
+        Ok(None)
+    }
+
+    /// Check the patterns and the formatter and lint settings
+    pub fn validate(&self) -> Result<(), String> {
+        let patterns = self
+            .sources
+            .ignore
+            .iter()
+            .chain(self.overrides.iter().flat_map(|o| &o.files));
+        for pattern in patterns {
+            Pattern::new(pattern).map_err(|e| format!("invalid pattern '{}': {}", pattern, e))?;
+        }
+        if self.format.indent_width == 0 {
+            return Err("format.indent-width must be at least 1".to_string());
+        }
+        if self.format.line_length == 0 {
+            return Err("format.line-length must be at least 1".to_string());
+        }
+        if self.duplicate_code.min_nodes == 0 {
+            return Err("duplicate-code.min-nodes must be at least 1".to_string());
+        }
+        Ok(())
+    }
+
+    /// A pattern of this configuration, made absolute against its root
+    pub fn resolve(&self, pattern: &str) -> String {
+        let root = absolute(&self.root);
+        let root = Pattern::escape(&root.to_string_lossy());
+        format!(
+            "{}/{}",
+            root.trim_end_matches('/'),
+            pattern.trim_start_matches('/')
+        )
+    }
+
+    /// Check whether `[sources] ignore` leaves a file out
+    pub fn is_ignored(&self, path: &Path) -> bool {
+        let patterns: Vec<String> = self
+            .sources
+            .ignore
+            .iter()
+            .map(|pattern| self.resolve(pattern))
+            .collect();
+        matches_any(&patterns, &absolute(path))
+    }
+}
+
+/// Check whether a path or one of its ancestors matches a glob pattern
+fn matches_any(patterns: &[String], path: &Path) -> bool {
+    let options = MatchOptions {
+        require_literal_separator: true,
+        ..MatchOptions::new()
+    };
+    let patterns: Vec<Pattern> = patterns
+        .iter()
+        .filter_map(|pattern| Pattern::new(pattern).ok())
+        .collect();
+    path.ancestors().any(|path| {
+        patterns
+            .iter()
+            .any(|pattern| pattern.matches_path_with(path, options))
+    })
+}
+
+/// A path made absolute against the current directory
+pub(crate) fn absolute(path: &Path) -> PathBuf {
+    std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf())
+}
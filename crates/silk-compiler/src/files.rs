@@ -0,0 +1,178 @@
+/// Source file discovery and per-file checking
+///
+/// Command-line paths may name files, directories (searched recursively for
+/// `.silk` files), or glob patterns such as `src/**/*.silk`.
+use crate::{sort_diagnostics, Compiler, Diagnostic, Timings};
+use rayon::prelude::*;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// File extension of Silk source files
+pub const SOURCE_EXTENSION: &str = "silk";
+
+/// Name diagnostics of a program read from standard input are labeled with
+pub const STDIN_NAME: &str = "<stdin>";
+
+/// Name diagnostics of a program given on the command line are labeled with
+pub const CMDLINE_NAME: &str = "<cmdline>";
+
+/// Diagnostics produced for a single source file
+#[derive(Debug, Clone)]
+pub struct FileDiagnostics {
+    /// Path of the checked file
+    pub path: PathBuf,
+    /// Diagnostics for the file, each tagged with `path`
+    pub diagnostics: Vec<Diagnostic>,
+    /// Time spent in each compiler phase
+    pub timings: Timings,
+    /// Errors of the file left out of `diagnostics` by the error cap
+    pub hidden_errors: usize,
+}
+
+impl FileDiagnostics {
+    /// Number of error diagnostics
+    pub fn error_count(&self) -> usize {
+        self.diagnostics.iter().filter(|d| d.is_error()).count()
+    }
+
+    /// Number of warning diagnostics
+    pub fn warning_count(&self) -> usize {
+        self.diagnostics.len() - self.error_count()
+    }
+}
+
+/// Expand command-line paths into a sorted, de-duplicated list of source files
+///
+/// Explicit files are kept regardless of extension; directories and glob
+/// patterns only contribute `.silk` files.
+pub fn collect_source_files(paths: &[PathBuf]) -> io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+
+    for path in paths {
+        if path.is_dir() {
+            collect_directory(path, &mut files)?;
+        } else if path.exists() {
+            files.push(path.clone());
+        } else if is_glob_pattern(path) {
+            collect_glob(path, &mut files)?;
+        } else {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("No such file or directory: {}", path.display()),
+            ));
+        }
+    }
+
+    files.sort();
+    files.dedup();
+    Ok(files)
+}
+
+/// The diagnostic of a file that cannot be read
+pub(crate) fn unreadable(file: &str, error: &io::Error) -> Diagnostic {
+    Diagnostic::error("io-error", format!("Cannot read {}: {}", file, error), None)
+}
+
+fn is_glob_pattern(path: &Path) -> bool {
+    path.to_string_lossy().contains(['*', '?', '['])
+}
+
+fn is_source_file(path: &Path) -> bool {
+    path.is_file() && path.extension().is_some_and(|ext| ext == SOURCE_EXTENSION)
+}
+
+fn collect_directory(dir: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_directory(&path, files)?;
+        } else if is_source_file(&path) {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn collect_glob(pattern: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    let entries = glob::glob(&pattern.to_string_lossy())
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e.to_string()))?;
+
+    for entry in entries {
+        let path = entry.map_err(io::Error::from)?;
+        if path.is_dir() {
+            collect_directory(&path, files)?;
+        } else if is_source_file(&path) {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+impl Compiler {
+    /// Check a single file, reporting unreadable files as a diagnostic
+    ///
+    /// Lint overrides of the options that match the path apply.
+    pub fn check_file(&self, path: &Path) -> FileDiagnostics {
+        if let Some(options) = self.options().for_file(path) {
+            return Compiler::with_options(options).check_file(path);
+        }
+        let file = path.display().to_string();
+        let (diagnostics, timings) = match fs::read_to_string(path) {
+            Ok(source) => self.check_timed(&source),
+            Err(e) => (vec![unreadable(&file, &e)], Timings::new()),
+        };
+
+        FileDiagnostics {
+            path: path.to_path_buf(),
+            diagnostics: diagnostics
+                .into_iter()
+                .map(|d| d.with_file(file.clone()))
+                .collect(),
+            timings,
+            hidden_errors: 0,
+        }
+    }
+
+    /// Check a program that does not come from a file (standard input,
+    /// `silk run -c`); its diagnostics are labeled `name`, and the error
+    /// cap of the options applies
+    pub fn check_source(&self, name: &str, source: &str) -> FileDiagnostics {
+        let (diagnostics, timings) = self.check_timed(source);
+        let mut report = FileDiagnostics {
+            path: PathBuf::from(name),
+            diagnostics: diagnostics.into_iter().map(|d| d.with_file(name)).collect(),
+            timings,
+            hidden_errors: 0,
+        };
+        report.hidden_errors = self.options().cap_errors([&mut report.diagnostics])[0];
+        report
+    }
+
+    /// Check many files in parallel; results keep the order of `paths`
+    ///
+    /// Import cycles between the files are reported as well. The error cap
+    /// of the options applies to all the files together, in order.
+    pub fn check_files(&self, paths: &[PathBuf]) -> Vec<FileDiagnostics> {
+        let mut reports: Vec<FileDiagnostics> =
+            paths.par_iter().map(|path| self.check_file(path)).collect();
+        self.finish_reports(&mut reports);
+        reports
+    }
+
+    /// Add the import cycles between the checked files to their reports,
+    /// sort each report and apply the error cap to all of them, in order
+    pub(crate) fn finish_reports(&self, reports: &mut [FileDiagnostics]) {
+        self.check_import_cycles(reports);
+        for report in reports.iter_mut() {
+            sort_diagnostics(&mut report.diagnostics);
+        }
+
+        let hidden = self
+            .options()
+            .cap_errors(reports.iter_mut().map(|report| &mut report.diagnostics));
+        for (report, hidden) in reports.iter_mut().zip(hidden) {
+            report.hidden_errors = hidden;
+        }
+    }
+}
@@ -0,0 +1,101 @@
+/// Machine-applicable fixes for lint diagnostics
+///
+/// Fixes are built from a semantic error and the source it was found in:
+/// - `unused-import`: delete the import statement when none of its names
+///   are used, otherwise delete the unused name and its separating comma
+/// - `unused-variable`: prefix the variable with `_` to mark it unused
+///
+/// A fix is only offered when the edit is known to leave the surrounding
+/// code intact: a whole import statement is only deleted when no other code
+/// shares its lines. `silk check --fix` also re-parses the result before
+/// writing it.
+use crate::{Edit, Fix, SemanticError, SourceFile};
+use silk_lexer::Span;
+
+/// Fix for a semantic error, if it has one
+pub fn fix_for(error: &SemanticError, source: &str) -> Option<Fix> {
+    let file = SourceFile::new("", source);
+    match error {
+        SemanticError::UnusedImport { span, fix_span, .. } if fix_span == span => {
+            let edit = Edit::delete(remove_list_item(&file, *span)?);
+            Some(Fix::new("remove the unused import", vec![edit]))
+        }
+        SemanticError::UnusedImport { fix_span, .. } => {
+            let edit = Edit::delete(whole_lines(&file, *fix_span)?);
+            Some(Fix::new("remove the unused import", vec![edit]))
+        }
+        SemanticError::UnusedVariable { name, span, .. }
+            if source
+                .get(file.byte_offset(span.start)?..)?
+                .starts_with(name.as_str()) =>
+        {
+            let edit = Edit::insert_before(*span, "_");
+            Some(Fix::new(format!("rename to '_{}'", name), vec![edit]))
+        }
+        _ => None,
+    }
+}
+
+/// Byte range of a span, whose offsets count characters
+fn byte_range(file: &SourceFile, span: Span) -> Option<(usize, usize)> {
+    Some((file.byte_offset(span.start)?, file.byte_offset(span.end)?))
+}
+
+/// Extend a statement span to cover its whole line(s), including leading
+/// indentation and the trailing newline; `None` if other code shares the
+/// first or last line
+fn whole_lines(file: &SourceFile, span: Span) -> Option<Span> {
+    let source = file.text();
+    let (start, end) = byte_range(file, span)?;
+    let before = source.get(..start)?;
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    if !before[line_start..].trim().is_empty() {
+        return None;
+    }
+
+    // The statement span runs up to the token after it: a newline, or the
+    // end of the file
+    let text = source.get(start..end)?;
+    let end = if text.ends_with('\n') {
+        end
+    } else {
+        let rest = source.get(end..)?;
+        if !rest.trim().is_empty() {
+            return None;
+        }
+        source.len()
+    };
+    Some(Span::new(
+        file.char_offset(line_start)?,
+        file.char_offset(end)?,
+        span.line,
+        1,
+    ))
+}
+
+/// Extend the span of one name in an import list over the comma that
+/// separates it from its neighbour
+fn remove_list_item(file: &SourceFile, span: Span) -> Option<Span> {
+    let source = file.text();
+    let (start, end) = byte_range(file, span)?;
+    let before = source.get(..start)?.trim_end_matches([' ', '\t']);
+    if let Some(start) = before.strip_suffix(',').map(str::len) {
+        return Some(Span::new(
+            file.char_offset(start)?,
+            span.end,
+            span.line,
+            span.column,
+        ));
+    }
+
+    let after = source.get(end..)?;
+    let trimmed = after.trim_start_matches([' ', '\t']);
+    let rest = trimmed.strip_prefix(',')?;
+    let end = source.len() - rest.trim_start_matches([' ', '\t']).len();
+    Some(Span::new(
+        span.start,
+        file.char_offset(end)?,
+        span.line,
+        span.column,
+    ))
+}
@@ -0,0 +1,163 @@
+/// Internal compiler errors
+///
+/// A panic inside a compiler phase is a bug in Silk, not in the program
+/// being checked. `catch` turns it into an `InternalError`, which is
+/// reported as an `internal-compiler-error` diagnostic carrying what a bug
+/// report needs (version, phase, panic location, size of the input)
+/// instead of a raw Rust panic message:
+///
+/// ```text
+/// main.silk:
+///   ✗ Internal compiler error in the semantic phase: index out of bounds
+///       note: silk 0.1.0, panicked at crates/silk-semantic/src/analyzer.rs:120:9
+///       note: while checking 42 line(s) of source
+///       note: this is a bug in Silk; please report it at https://...
+/// ```
+///
+/// With `CompilerOptions::ice_dump_ast`, the AST of the program is also
+/// written to a temporary file mentioned in the report.
+///
+/// Failing to write the output is not a bug in Silk, so it is not reported
+/// here: the CLI handles it where it writes, ending quietly when the reader
+/// of a pipe has gone away.
+use crate::{Diagnostic, Note, Phase, Program};
+use std::cell::{Cell, RefCell};
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Once;
+
+/// Diagnostic code of internal compiler errors
+pub const ICE_CODE: &str = "internal-compiler-error";
+
+/// Where internal compiler errors should be reported
+pub const BUG_REPORT_URL: &str = "https://github.com/juliuspleunes4/silk/issues/new";
+
+/// Version of the compiler, included in reports
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// A panic caught in a compiler phase
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InternalError {
+    /// Phase that panicked
+    pub phase: Phase,
+    /// Panic message
+    pub message: String,
+    /// Source location of the panic in the compiler, if known
+    pub location: Option<String>,
+    /// File the AST of the program was written to
+    pub ast_dump: Option<PathBuf>,
+}
+
+impl InternalError {
+    /// Write the AST of the program being checked to a temporary file and
+    /// mention it in the report; the report goes without it if the file
+    /// cannot be written
+    pub fn dump_ast(&mut self, program: &Program) {
+        static DUMPS: AtomicUsize = AtomicUsize::new(0);
+        let path = std::env::temp_dir().join(format!(
+            "silk-ice-{}-{}.ast",
+            std::process::id(),
+            DUMPS.fetch_add(1, Ordering::Relaxed)
+        ));
+        if fs::write(&path, format!("{:#?}\n", program)).is_ok() {
+            self.ast_dump = Some(path);
+        }
+    }
+
+    /// The report, as an error diagnostic with one note per detail
+    pub fn to_diagnostic(&self, source: &str) -> Diagnostic {
+        let mut build = format!("silk {}", VERSION);
+        if let Some(location) = &self.location {
+            build.push_str(&format!(", panicked at {}", location));
+        }
+        let mut notes = vec![
+            build,
+            format!(
+                "while checking {} line(s) of source",
+                source.lines().count()
+            ),
+        ];
+        if let Some(path) = &self.ast_dump {
+            notes.push(format!("the AST was written to {}", path.display()));
+        }
+        notes.push(format!(
+            "this is a bug in Silk; please report it at {} with this report and the file that triggered it",
+            BUG_REPORT_URL
+        ));
+
+        notes.into_iter().fold(
+            Diagnostic::error(
+                ICE_CODE,
+                format!(
+                    "Internal compiler error in the {} phase: {}",
+                    self.phase, self.message
+                ),
+                None,
+            ),
+            |diagnostic, note| diagnostic.with_note(Note::new(note, None, None)),
+        )
+    }
+}
+
+/// Check whether a diagnostic reports an internal compiler error
+pub fn is_ice(diagnostic: &Diagnostic) -> bool {
+    diagnostic.code == ICE_CODE
+}
+
+thread_local! {
+    /// Number of `catch` calls running on this thread
+    static CATCHING: Cell<usize> = const { Cell::new(0) };
+    /// Message and location of the last panic caught on this thread
+    static CAUGHT: RefCell<Option<(String, Option<String>)>> = const { RefCell::new(None) };
+}
+
+/// Run a phase, turning a panic into an `InternalError`
+///
+/// The panic message is not printed; panics outside of `catch` still go
+/// to the previous panic hook.
+pub fn catch<T>(phase: Phase, f: impl FnOnce() -> T) -> Result<T, InternalError> {
+    install_hook();
+    CATCHING.with(|depth| depth.set(depth.get() + 1));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    CATCHING.with(|depth| depth.set(depth.get() - 1));
+
+    result.map_err(|payload| {
+        let (message, location) = CAUGHT
+            .with(|caught| caught.borrow_mut().take())
+            .unwrap_or_else(|| (payload_message(payload.as_ref()), None));
+        InternalError {
+            phase,
+            message,
+            location,
+            ast_dump: None,
+        }
+    })
+}
+
+/// Install (once) the panic hook that records panics inside `catch`
+fn install_hook() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if CATCHING.with(Cell::get) == 0 {
+                return previous(info);
+            }
+            let location = info.location().map(|location| location.to_string());
+            let message = payload_message(info.payload());
+            CAUGHT.with(|caught| *caught.borrow_mut() = Some((message, location)));
+        }));
+    });
+}
+
+fn payload_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
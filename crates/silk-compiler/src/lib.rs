@@ -1,15 +1,94 @@
 /// Silk compiler library
 ///
 /// Main entry point for the Silk compiler.
+pub mod ast_diff;
+pub mod call_graph;
+pub mod config;
+pub mod files;
+pub mod fixes;
+pub mod ice;
+pub mod manifest;
+pub mod metrics;
+pub mod modules;
+pub mod options;
+pub mod queries;
+pub mod suppressions;
+pub mod testing;
+pub mod timings;
+
+pub use call_graph::FileCallGraph;
+pub use config::{Config, DuplicateCodeConfig, FormatConfig, LintOverride, MetricsConfig};
+pub use files::{collect_source_files, FileDiagnostics, CMDLINE_NAME, STDIN_NAME};
+pub use ice::InternalError;
+pub use manifest::{LintLevel, Manifest, ManifestError};
+pub use metrics::FileMetrics;
+pub use modules::{CycleStep, ImportCycle, ModuleGraph, ModuleImport};
+pub use options::CompilerOptions;
+pub use queries::{Database, Query};
+pub use silk_ast::{Feature, FeatureError, FeatureSet, Program, ReleaseChannel};
+pub use silk_diagnostics::codes;
+pub use silk_diagnostics::{
+    apply_fixes, render_snippet, sort_diagnostics, ColorChoice, Diagnostic, Edit, ErrorCode, Fix,
+    Note, Renderer, Severity, SourceFile, SourceMap,
+};
 pub use silk_lexer::{LexError, Lexer, Token, TokenKind};
+pub use silk_parser::ParseError;
+pub use silk_semantic::{
+    CallEdge, CallGraph, FunctionMetrics, FunctionNode, MetricLimits, SemanticError, TypedProgram,
+};
+pub use suppressions::{Suppression, Suppressions};
+pub use testing::{discover_tests, TestCase};
+pub use timings::{Phase, Timings};
+
+use silk_lexer::Span;
+use silk_parser::Parser;
+use silk_semantic::{ControlFlowAnalyzer, SemanticAnalyzer};
+use std::sync::Arc;
 
+#[derive(Clone)]
 pub struct Compiler {
-    // TODO: Add parser, semantic analyzer, etc.
+    /// Configuration shared by every phase
+    options: CompilerOptions,
+    // TODO: Add codegen, etc.
 }
 
 impl Compiler {
     pub fn new() -> Self {
-        Self {}
+        Self::with_options(CompilerOptions::new())
+    }
+
+    /// Create a compiler with a set of unstable features enabled
+    pub fn with_features(features: FeatureSet) -> Self {
+        Self::with_options(CompilerOptions::new().with_features(features))
+    }
+
+    /// Create a compiler from a full configuration
+    pub fn with_options(options: CompilerOptions) -> Self {
+        Self { options }
+    }
+
+    /// Report a lint that is allowed by default (e.g. `missing-docstring`)
+    /// as a warning, unless it already has a level
+    pub fn enable_lint(&mut self, code: impl Into<String>) {
+        self.options
+            .lints
+            .entry(code.into())
+            .or_insert(LintLevel::Warn);
+    }
+
+    /// Unstable features enabled for this compiler
+    pub fn features(&self) -> &FeatureSet {
+        &self.options.features
+    }
+
+    /// The configuration of this compiler
+    pub fn options(&self) -> &CompilerOptions {
+        &self.options
+    }
+
+    /// The configuration of this compiler, for changing it in place
+    pub fn options_mut(&mut self) -> &mut CompilerOptions {
+        &mut self.options
     }
 
     /// Compile Silk source code to tokens (Phase 1: Lexer only)
@@ -17,6 +96,207 @@ impl Compiler {
         let mut lexer = Lexer::new(source);
         lexer.tokenize()
     }
+
+    /// Parse Silk source code into an AST (Phase 2)
+    pub fn parse(&self, source: &str) -> Result<Program, ParseError> {
+        Parser::parse_with_features(source, self.features().clone())
+    }
+
+    /// A semantic analyzer with this compiler's features and lints, for
+    /// tools that need more than the result of the analysis (the symbol
+    /// table, classes)
+    pub fn semantic_analyzer(&self) -> SemanticAnalyzer {
+        let mut analyzer = SemanticAnalyzer::new();
+        analyzer.set_features(self.features().clone());
+        for code in self.options.enabled_lints() {
+            analyzer.enable_lint(code);
+        }
+        if let Some(nodes) = self.options.duplicate_min_nodes {
+            analyzer.set_duplicate_min_nodes(nodes);
+        }
+        if let Some(limits) = self.options.metric_limits {
+            analyzer.set_metric_limits(limits);
+        }
+        analyzer
+    }
+
+    /// Run semantic analysis on a parsed program (Phase 3)
+    pub fn analyze(&self, program: &Program) -> Result<(), Vec<SemanticError>> {
+        self.semantic_analyzer().analyze(program)
+    }
+
+    /// Run semantic analysis on a parsed program, keeping the type of every
+    /// expression (Phase 3)
+    ///
+    /// The typed program carries the errors found, and is produced even
+    /// when there are some. Passing an `Arc` shares the program with the
+    /// typed program instead of moving it.
+    pub fn analyze_typed(&self, program: impl Into<Arc<Program>>) -> TypedProgram {
+        self.semantic_analyzer().analyze_typed(program)
+    }
+
+    /// Run the full front-end pipeline (parse, semantic and control flow
+    /// analysis) and collect every problem as a diagnostic, in source
+    /// order, under the lint levels and error cap of the options
+    pub fn check(&self, source: &str) -> Vec<Diagnostic> {
+        let mut diagnostics = self.check_timed(source).0;
+        self.options.cap_errors([&mut diagnostics]);
+        diagnostics
+    }
+
+    /// Like `check`, also measuring the time spent in each phase; the error
+    /// cap is left to the caller, which may be checking several files
+    ///
+    /// A panic in a phase is reported as an internal compiler error.
+    pub fn check_timed(&self, source: &str) -> (Vec<Diagnostic>, Timings) {
+        let mut timings = Timings::new();
+        let diagnostics = self
+            .run_phases(source, &mut timings)
+            .unwrap_or_else(|ice| vec![ice.to_diagnostic(source)]);
+        (diagnostics, timings)
+    }
+
+    fn run_phases(
+        &self,
+        source: &str,
+        timings: &mut Timings,
+    ) -> Result<Vec<Diagnostic>, InternalError> {
+        let tokens = match run_phase(timings, Phase::Lex, || self.lex(source))? {
+            Ok(tokens) => tokens,
+            Err(e) => return Ok(parse_error_diagnostics(source, ParseError::LexError(e))),
+        };
+        match run_phase(timings, Phase::Parse, || self.parse_tokens(tokens))? {
+            Ok(parsed) => self.check_parsed(source, &parsed, timings),
+            Err(e) => Ok(parse_error_diagnostics(source, e)),
+        }
+    }
+
+    /// Parse a token stream, keeping the parser's lint warnings
+    pub fn parse_tokens(&self, tokens: Vec<Token>) -> Result<Parsed, ParseError> {
+        let mut parser = Parser::from_tokens(tokens, self.features().clone());
+        let program = parser.parse_program()?;
+        Ok(Parsed {
+            program: Arc::new(program),
+            warnings: parser.warnings().to_vec(),
+        })
+    }
+
+    /// The semantic and control-flow phases of `check_timed`, on a program
+    /// that parsed
+    pub(crate) fn check_parsed(
+        &self,
+        source: &str,
+        parsed: &Parsed,
+        timings: &mut Timings,
+    ) -> Result<Vec<Diagnostic>, InternalError> {
+        let program = &parsed.program;
+        let with_ast = |mut ice: InternalError| {
+            if self.options.ice_dump_ast {
+                ice.dump_ast(program);
+            }
+            ice
+        };
+
+        // Same passes as `analyze`, run separately so each can be timed
+        let mut errors = run_phase(timings, Phase::Semantic, || {
+            let mut analyzer = self.semantic_analyzer();
+            analyzer.set_control_flow_enabled(false);
+            analyzer.analyze(program).err().unwrap_or_default()
+        })
+        .map_err(with_ast)?;
+        errors.extend(
+            run_phase(timings, Phase::ControlFlow, || {
+                let mut control_flow = ControlFlowAnalyzer::new();
+                control_flow.set_features(self.features().clone());
+                for code in self.options.enabled_lints() {
+                    control_flow.enable_lint(code);
+                }
+                if let Some(nodes) = self.options.duplicate_min_nodes {
+                    control_flow.set_duplicate_min_nodes(nodes);
+                }
+                if let Some(limits) = self.options.metric_limits {
+                    control_flow.set_metric_limits(limits);
+                }
+                control_flow.analyze(program).err().unwrap_or_default()
+            })
+            .map_err(with_ast)?,
+        );
+
+        let mut diagnostics: Vec<Diagnostic> = parsed
+            .warnings
+            .iter()
+            .map(|warning| {
+                Diagnostic::warning(warning.code(), warning.to_string(), warning.span())
+                    .with_error_code(warning.error_code())
+            })
+            .collect();
+        diagnostics.extend(
+            errors
+                .iter()
+                .map(|error| semantic_diagnostic(error, source)),
+        );
+        let diagnostics = Suppressions::new(source, program).apply(diagnostics);
+        Ok(self.options.apply(diagnostics))
+    }
+}
+
+/// A parsed program with the lint warnings of the parser
+///
+/// The program is shared, so the typed program of the `typed` query can
+/// hold the same one.
+#[derive(Debug, Clone)]
+pub struct Parsed {
+    pub program: Arc<Program>,
+    pub warnings: Vec<ParseError>,
+}
+
+/// The diagnostic of a source that does not lex or parse
+pub(crate) fn parse_error_diagnostics(source: &str, e: ParseError) -> Vec<Diagnostic> {
+    let span = e.span().map(|span| locate(source, span));
+    vec![Diagnostic::error(e.code(), e.to_string(), span).with_error_code(e.error_code())]
+}
+
+/// Run and time a phase, catching a panic as an internal compiler error
+pub(crate) fn run_phase<T>(
+    timings: &mut Timings,
+    phase: Phase,
+    f: impl FnOnce() -> T,
+) -> Result<T, InternalError> {
+    timings.time(phase, || ice::catch(phase, f))
+}
+
+/// Fill in the offsets of a span that only knows its line and column
+///
+/// Most lexer and parser errors record a position as a line and column
+/// with a placeholder `0..1` offset range; snippets need the offset of that
+/// column.
+fn locate(source: &str, span: Span) -> Span {
+    if span.start != 0 || span.end != 1 {
+        return span;
+    }
+    let file = SourceFile::new("", source);
+    match file.line_start(span.line) {
+        Some(start) => {
+            let offset = start + span.column.saturating_sub(1);
+            Span::new(offset, offset + 1, span.line, span.column)
+        }
+        None => span,
+    }
+}
+
+/// Convert a semantic error, downgrading lints to warnings and attaching
+/// its fix, if any
+fn semantic_diagnostic(error: &SemanticError, source: &str) -> Diagnostic {
+    let diagnostic = if error.is_warning() {
+        Diagnostic::warning(error.code(), error.to_string(), error.span())
+    } else {
+        Diagnostic::error(error.code(), error.to_string(), error.span())
+    }
+    .with_error_code(error.error_code());
+    match fixes::fix_for(error, source) {
+        Some(fix) => diagnostic.with_fix(fix),
+        None => diagnostic,
+    }
 }
 
 impl Default for Compiler {
@@ -0,0 +1,305 @@
+/// Project manifest (`silk.toml`)
+///
+/// A project is a directory containing a `silk.toml`:
+///
+/// ```toml
+/// [package]
+/// name = "hello"
+/// version = "0.1.0"
+/// entry = "src/main.silk"
+/// source-dirs = ["src"]
+///
+/// [lints]
+/// unused-variable = "allow"
+/// unused-function = "error"
+/// missing-docstring = "warn"
+/// ```
+///
+/// Most lints are reported unless allowed; a few, such as
+/// `missing-docstring`, are only reported when given a level. The
+/// `[sources]`, `[[overrides]]`, `[format]`, `[duplicate-code]` and
+/// `[metrics]` tables of `config` may appear here too.
+///
+/// `silk build`, `silk check` and `silk run` fall back to the manifest when
+/// no paths are given on the command line.
+use crate::config::{
+    Config, DuplicateCodeConfig, FormatConfig, LintOverride, MetricsConfig, Sources,
+};
+use crate::options;
+use crate::Diagnostic;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// File name of the project manifest
+pub const MANIFEST_FILE: &str = "silk.toml";
+
+/// Errors produced while loading or creating a project
+#[derive(Error, Debug)]
+pub enum ManifestError {
+    #[error("Could not find {MANIFEST_FILE} in {0} or any parent directory")]
+    NotFound(PathBuf),
+
+    #[error("Invalid configuration {path}: {message}")]
+    Invalid { path: PathBuf, message: String },
+
+    #[error("Destination {0} already exists")]
+    AlreadyExists(PathBuf),
+
+    #[error("Already inside the project of {0} (use --force to create a nested project)")]
+    InsideProject(PathBuf),
+
+    #[error(
+        "Invalid project name '{0}' (use letters, digits, '-' and '_', starting with a letter)"
+    )]
+    InvalidName(String),
+
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+/// How a lint is reported
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LintLevel {
+    /// Not reported
+    Allow,
+    /// Reported as a warning
+    Warn,
+    /// Reported as an error (fails the check)
+    Error,
+}
+
+/// The `[package]` table
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct Package {
+    /// Project name
+    pub name: String,
+    /// Project version
+    pub version: String,
+    /// Entry point used by `silk run` (relative to the manifest)
+    #[serde(default = "default_entry")]
+    pub entry: PathBuf,
+    /// Directories searched for sources by `silk build`/`silk check`
+    #[serde(default = "default_source_dirs")]
+    pub source_dirs: Vec<PathBuf>,
+}
+
+fn default_entry() -> PathBuf {
+    PathBuf::from("src/main.silk")
+}
+
+fn default_source_dirs() -> Vec<PathBuf> {
+    vec![PathBuf::from("src")]
+}
+
+/// Parsed `silk.toml`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Manifest {
+    /// Package metadata and source layout
+    pub package: Package,
+    /// Lint levels by diagnostic code (e.g. `unused-variable = "allow"`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub lints: BTreeMap<String, LintLevel>,
+    /// Files left out of checks
+    #[serde(default, skip_serializing_if = "Sources::is_empty")]
+    pub sources: Sources,
+    /// Lint levels for some of the files
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub overrides: Vec<LintOverride>,
+    /// Formatter settings
+    #[serde(default, skip_serializing_if = "FormatConfig::is_default")]
+    pub format: FormatConfig,
+    /// Settings of the `duplicate-code` lint
+    #[serde(
+        default,
+        rename = "duplicate-code",
+        skip_serializing_if = "DuplicateCodeConfig::is_default"
+    )]
+    pub duplicate_code: DuplicateCodeConfig,
+    /// Limits of the `complex-function` lint
+    #[serde(default, skip_serializing_if = "MetricsConfig::is_default")]
+    pub metrics: MetricsConfig,
+    /// Directory containing the manifest (not serialized)
+    #[serde(skip)]
+    pub root: PathBuf,
+}
+
+impl Manifest {
+    /// Create a manifest with the default layout for a new project
+    pub fn new(name: &str, root: PathBuf) -> Self {
+        Self {
+            package: Package {
+                name: name.to_string(),
+                version: "0.1.0".to_string(),
+                entry: default_entry(),
+                source_dirs: default_source_dirs(),
+            },
+            lints: BTreeMap::new(),
+            sources: Sources::default(),
+            overrides: Vec::new(),
+            format: FormatConfig::default(),
+            duplicate_code: DuplicateCodeConfig::default(),
+            metrics: MetricsConfig::default(),
+            root,
+        }
+    }
+
+    /// Parse manifest contents; `root` is the directory paths are relative to
+    pub fn parse(contents: &str, root: PathBuf) -> Result<Self, ManifestError> {
+        let invalid = |message: String| ManifestError::Invalid {
+            path: root.join(MANIFEST_FILE),
+            message,
+        };
+        let mut manifest: Manifest =
+            toml::from_str(contents).map_err(|e| invalid(e.message().to_string()))?;
+        manifest.config().validate().map_err(invalid)?;
+        manifest.root = root;
+        Ok(manifest)
+    }
+
+    /// Load a manifest file
+    pub fn load(path: &Path) -> Result<Self, ManifestError> {
+        let contents = fs::read_to_string(path)?;
+        let root = path.parent().map(Path::to_path_buf).unwrap_or_default();
+        Self::parse(&contents, root)
+    }
+
+    /// Find and load the nearest `silk.toml` in `start` or its ancestors
+    pub fn discover(start: &Path) -> Result<Self, ManifestError> {
+        start
+            .ancestors()
+            .map(|dir| dir.join(MANIFEST_FILE))
+            .find(|path| path.is_file())
+            .ok_or_else(|| ManifestError::NotFound(start.to_path_buf()))
+            .and_then(|path| Self::load(&path))
+    }
+
+    /// Serialize back to TOML
+    pub fn to_toml_string(&self) -> String {
+        toml::to_string(self).expect("manifest is always representable as TOML")
+    }
+
+    /// Absolute path of the entry point
+    pub fn entry_path(&self) -> PathBuf {
+        self.root.join(&self.package.entry)
+    }
+
+    /// Absolute paths of the source directories
+    pub fn source_paths(&self) -> Vec<PathBuf> {
+        self.package
+            .source_dirs
+            .iter()
+            .map(|dir| self.root.join(dir))
+            .collect()
+    }
+
+    /// The lint and formatter settings of the manifest
+    pub fn config(&self) -> Config {
+        Config {
+            lints: self.lints.clone(),
+            sources: self.sources.clone(),
+            overrides: self.overrides.clone(),
+            format: self.format.clone(),
+            duplicate_code: self.duplicate_code.clone(),
+            metrics: self.metrics.clone(),
+            root: self.root.clone(),
+        }
+    }
+
+    /// Codes given a `warn` or `error` level in the `[lints]` table; lints
+    /// that are allowed by default are only reported when listed here
+    pub fn enabled_lints(&self) -> impl Iterator<Item = &str> {
+        options::enabled_lints(&self.lints)
+    }
+
+    /// Apply the `[lints]` table: drop allowed lints and re-level the rest
+    pub fn apply_lints(&self, diagnostics: Vec<Diagnostic>) -> Vec<Diagnostic> {
+        options::apply_lint_levels(&self.lints, diagnostics)
+    }
+}
+
+/// Check that a project name is usable as a directory and package name
+fn validate_name(name: &str) -> Result<(), ManifestError> {
+    let mut chars = name.chars();
+    let valid = chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(ManifestError::InvalidName(name.to_string()))
+    }
+}
+
+/// Contents of the entry point written by `silk new` / `silk init`
+const MAIN_TEMPLATE: &str = "def main():\n    print(\"Hello, Silk!\")\n\n\nmain()\n";
+
+/// Initialize a project in an existing directory (`silk init`)
+///
+/// Writes `silk.toml` and, unless it already exists, the entry point.
+/// Refuses to create a project inside another one (a `silk.toml` in an
+/// ancestor of `dir`) unless `force` is set.
+pub fn init_project(dir: &Path, name: &str, force: bool) -> Result<Manifest, ManifestError> {
+    validate_name(name)?;
+
+    let manifest_path = dir.join(MANIFEST_FILE);
+    if manifest_path.exists() {
+        return Err(ManifestError::AlreadyExists(manifest_path));
+    }
+    if !force {
+        if let Some(enclosing) = dir.parent().and_then(enclosing_manifest) {
+            return Err(ManifestError::InsideProject(enclosing));
+        }
+    }
+
+    write_project(dir, name)
+}
+
+/// Create a new project directory `parent/name` (`silk new`)
+///
+/// Refuses to create a project inside another one (a `silk.toml` in
+/// `parent` or its ancestors) unless `force` is set.
+pub fn new_project(parent: &Path, name: &str, force: bool) -> Result<Manifest, ManifestError> {
+    validate_name(name)?;
+
+    if !force {
+        if let Some(enclosing) = enclosing_manifest(parent) {
+            return Err(ManifestError::InsideProject(enclosing));
+        }
+    }
+
+    let dir = parent.join(name);
+    if dir.exists() {
+        return Err(ManifestError::AlreadyExists(dir));
+    }
+    fs::create_dir_all(&dir)?;
+    write_project(&dir, name)
+}
+
+/// The `silk.toml` in `dir` or the closest of its ancestors
+fn enclosing_manifest(dir: &Path) -> Option<PathBuf> {
+    dir.ancestors()
+        .map(|dir| dir.join(MANIFEST_FILE))
+        .find(|path| path.is_file())
+}
+
+/// Write `silk.toml` in `dir` and, unless it already exists, the entry point
+fn write_project(dir: &Path, name: &str) -> Result<Manifest, ManifestError> {
+    let manifest = Manifest::new(name, dir.to_path_buf());
+    fs::write(dir.join(MANIFEST_FILE), manifest.to_toml_string())?;
+
+    let entry = manifest.entry_path();
+    if !entry.exists() {
+        if let Some(parent) = entry.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&entry, MAIN_TEMPLATE)?;
+    }
+
+    Ok(manifest)
+}
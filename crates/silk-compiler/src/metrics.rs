@@ -0,0 +1,95 @@
+/// Function metrics report
+///
+/// Renders the metrics of checked files for `silk analyze --metrics`: one
+/// table per file with a row for each function and method, giving its
+/// cyclomatic complexity, nesting depth, parameter count and line count.
+/// Values above the limits of the `complex-function` lint are marked with
+/// `*`.
+use crate::Compiler;
+use crate::{FunctionMetrics, MetricLimits};
+use silk_semantic::metrics::function_metrics;
+use std::fmt::Write;
+use std::path::PathBuf;
+
+/// Function metrics of one source file
+#[derive(Debug, Clone)]
+pub struct FileMetrics {
+    pub path: PathBuf,
+    pub functions: Vec<FunctionMetrics>,
+}
+
+impl Compiler {
+    /// Parse `source` and measure its functions
+    pub fn metrics(&self, path: PathBuf, source: &str) -> Result<FileMetrics, crate::ParseError> {
+        let program = self.parse(source)?;
+        Ok(FileMetrics {
+            path,
+            functions: function_metrics(&program),
+        })
+    }
+
+    /// Limits of the `complex-function` lint in effect
+    pub fn metric_limits(&self) -> MetricLimits {
+        self.options.metric_limits.unwrap_or_default()
+    }
+}
+
+/// Column headers; those of marked columns leave room for the mark
+const HEADERS: [&str; 6] = [
+    "function",
+    "line",
+    "complexity ",
+    "nesting ",
+    "params ",
+    "lines ",
+];
+
+/// Render the metrics as one table per file
+pub fn to_table(files: &[FileMetrics], limits: &MetricLimits) -> String {
+    let mut table = String::new();
+    for (index, file) in files.iter().enumerate() {
+        if index > 0 {
+            table.push('\n');
+        }
+        let _ = writeln!(table, "{}", file.path.display());
+        if file.functions.is_empty() {
+            table.push_str("  (no functions)\n");
+            continue;
+        }
+
+        let rows: Vec<[String; 6]> = file
+            .functions
+            .iter()
+            .map(|function| {
+                let value = |value: usize, limit: usize| {
+                    let mark = if value > limit { "*" } else { " " };
+                    format!("{}{}", value, mark)
+                };
+                [
+                    function.name.clone(),
+                    function.span.line.to_string(),
+                    value(function.complexity, limits.complexity),
+                    value(function.nesting, limits.nesting),
+                    value(function.parameters, limits.parameters),
+                    value(function.lines, limits.lines),
+                ]
+            })
+            .collect();
+        let mut widths = HEADERS.map(str::len);
+        for row in &rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+
+        let headers = HEADERS.map(str::to_string);
+        for row in std::iter::once(&headers).chain(&rows) {
+            let mut line = format!("  {:<width$}", row[0], width = widths[0]);
+            for (cell, width) in row.iter().zip(widths).skip(1) {
+                let _ = write!(line, "  {:>width$}", cell, width = width);
+            }
+            let _ = writeln!(table, "{}", line.trim_end());
+        }
+    }
+    table
+}
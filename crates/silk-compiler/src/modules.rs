@@ -0,0 +1,419 @@
+/// Module graph and circular import detection
+///
+/// Every checked file is a module. Module-level imports (including those in
+/// top-level `if` and `try` blocks, but not `if TYPE_CHECKING:`) are resolved
+/// to other checked files, Python style:
+/// - `import a.b` looks for `a/b.silk` or `a/b/__init__.silk` next to the
+///   importing file and next to its outermost package (a directory chain
+///   containing `__init__.silk`)
+/// - `from . import b` and `from ..a import b` are relative to the importing
+///   file's package
+/// - `from a import b` prefers the submodule `a/b.silk` when it exists
+/// - a module of a namespace package (a directory without `__init__.silk`)
+///   also resolves absolute imports from the directory it was imported from
+///
+/// Imports that do not resolve to a checked file (standard library,
+/// third-party packages) are not part of the graph.
+use crate::options;
+use crate::{Compiler, Diagnostic, FileDiagnostics};
+use rayon::prelude::*;
+use silk_ast::{ExpressionKind, Program, Statement, StatementKind};
+use silk_diagnostics::Note;
+use silk_lexer::Span;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Package marker file
+const PACKAGE_INIT: &str = "__init__.silk";
+
+/// An import statement that resolved to another checked module
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModuleImport {
+    /// Imported module file
+    pub target: PathBuf,
+    /// Location of the import statement
+    pub span: Span,
+}
+
+/// One edge of an import cycle: `from` imports `import.target`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CycleStep {
+    pub from: PathBuf,
+    pub import: ModuleImport,
+}
+
+/// A chain of imports that leads back to its first module
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportCycle {
+    /// Steps in import order; the last step imports the first module
+    pub steps: Vec<CycleStep>,
+}
+
+impl ImportCycle {
+    /// Module names along the cycle, starting and ending with the first module
+    pub fn chain(&self) -> Vec<String> {
+        let mut chain: Vec<String> = self
+            .steps
+            .iter()
+            .map(|step| module_name(&step.from))
+            .collect();
+        if let Some(first) = chain.first().cloned() {
+            chain.push(first);
+        }
+        chain
+    }
+
+    /// Error reported on the first import of the cycle, with a note for
+    /// every import statement in the chain
+    pub fn to_diagnostic(&self) -> Diagnostic {
+        let first = &self.steps[0];
+        let mut diagnostic = Diagnostic::error(
+            "circular-import",
+            format!("Circular import: {}", self.chain().join(" imports ")),
+            Some(first.import.span),
+        )
+        .with_error_code("E0206")
+        .with_file(first.from.display().to_string());
+
+        for step in &self.steps {
+            diagnostic = diagnostic.with_note(Note::new(
+                format!(
+                    "'{}' imports '{}' here",
+                    module_name(&step.from),
+                    module_name(&step.import.target)
+                ),
+                Some(step.from.display().to_string()),
+                Some(step.import.span),
+            ));
+        }
+        diagnostic
+    }
+}
+
+/// Imports between a set of modules
+#[derive(Debug, Clone, Default)]
+pub struct ModuleGraph {
+    imports: BTreeMap<PathBuf, Vec<ModuleImport>>,
+}
+
+impl ModuleGraph {
+    /// Build the graph from parsed modules; files that failed to parse can
+    /// be left out
+    pub fn new(modules: &[(PathBuf, Program)]) -> Self {
+        // Resolve through canonical paths so `./a.silk` and `a.silk` match
+        let known: HashMap<PathBuf, PathBuf> = modules
+            .iter()
+            .map(|(path, _)| (canonical(path), path.clone()))
+            .collect();
+
+        // Directories a module was imported from by absolute name, when its
+        // own search roots do not include them: `import pkg.c` makes `c`
+        // search the importer's root. Resolving may add roots to modules
+        // already resolved, so repeat until nothing changes.
+        let mut extra_roots: HashMap<PathBuf, Vec<PathBuf>> = HashMap::new();
+        loop {
+            let mut found_roots = Vec::new();
+            let imports = modules
+                .iter()
+                .map(|(path, program)| {
+                    let mut roots = search_roots(path);
+                    for root in extra_roots.get(path).into_iter().flatten() {
+                        if !roots.contains(root) {
+                            roots.push(root.clone());
+                        }
+                    }
+
+                    let mut imports = Vec::new();
+                    collect_imports(
+                        &program.statements,
+                        path,
+                        &roots,
+                        &mut |stmt, candidates| {
+                            let target = candidates.iter().find_map(|(candidate, root)| {
+                                Some((known.get(&canonical(candidate))?, root))
+                            });
+                            if let Some((target, root)) = target {
+                                if let Some(root) = root {
+                                    found_roots.push((target.clone(), root.clone()));
+                                }
+                                let import = ModuleImport {
+                                    target: target.clone(),
+                                    span: stmt.span,
+                                };
+                                // `from a import x, y` is one edge
+                                if !imports.contains(&import) {
+                                    imports.push(import);
+                                }
+                            }
+                        },
+                    );
+                    (path.clone(), imports)
+                })
+                .collect();
+
+            let mut changed = false;
+            for (target, root) in found_roots {
+                if search_roots(&target).contains(&root) {
+                    continue;
+                }
+                let roots = extra_roots.entry(target).or_default();
+                if !roots.contains(&root) {
+                    roots.push(root);
+                    changed = true;
+                }
+            }
+            if !changed {
+                return Self { imports };
+            }
+        }
+    }
+
+    /// Imports of a module that resolved to other modules, in source order
+    pub fn imports(&self, path: &Path) -> &[ModuleImport] {
+        self.imports.get(path).map_or(&[], Vec::as_slice)
+    }
+
+    /// Find import cycles, one per import that closes a cycle
+    ///
+    /// Modules are visited in path order and imports in source order, so
+    /// the result is deterministic.
+    pub fn cycles(&self) -> Vec<ImportCycle> {
+        let mut visits = HashMap::new();
+        let mut path = Vec::new();
+        let mut cycles = Vec::new();
+        for module in self.imports.keys() {
+            if !visits.contains_key(module.as_path()) {
+                self.visit(module, &mut visits, &mut path, &mut cycles);
+            }
+        }
+        cycles
+    }
+
+    fn visit<'a>(
+        &'a self,
+        module: &'a Path,
+        visits: &mut HashMap<&'a Path, Visit>,
+        path: &mut Vec<(&'a Path, &'a ModuleImport)>,
+        cycles: &mut Vec<ImportCycle>,
+    ) {
+        visits.insert(module, Visit::InProgress);
+        for import in self.imports(module) {
+            match visits.get(import.target.as_path()) {
+                Some(Visit::InProgress) => {
+                    // The target is on the current path: the cycle starts
+                    // where the path leaves it
+                    let start = path
+                        .iter()
+                        .position(|(from, _)| *from == import.target)
+                        .unwrap_or(path.len());
+                    let steps = path[start..]
+                        .iter()
+                        .copied()
+                        .chain([(module, import)])
+                        .map(|(from, import)| CycleStep {
+                            from: from.to_path_buf(),
+                            import: import.clone(),
+                        })
+                        .collect();
+                    cycles.push(ImportCycle { steps });
+                }
+                Some(Visit::Done) => {}
+                None => {
+                    path.push((module, import));
+                    self.visit(&import.target, visits, path, cycles);
+                    path.pop();
+                }
+            }
+        }
+        visits.insert(module, Visit::Done);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Visit {
+    InProgress,
+    Done,
+}
+
+/// Call `found` with every module-level import statement and the files it
+/// may refer to, most specific first; each file comes with the search root
+/// it is under for absolute imports, or `None` for relative ones
+fn collect_imports(
+    statements: &[Statement],
+    file: &Path,
+    roots: &[PathBuf],
+    found: &mut impl FnMut(&Statement, Vec<(PathBuf, Option<PathBuf>)>),
+) {
+    for stmt in statements {
+        match &stmt.kind {
+            StatementKind::Import { names } => {
+                for alias in names {
+                    let parts: Vec<&str> = alias.name.split('.').collect();
+                    let candidates = roots
+                        .iter()
+                        .flat_map(|root| {
+                            module_files(root, &parts)
+                                .into_iter()
+                                .map(|file| (file, Some(root.clone())))
+                        })
+                        .collect();
+                    found(stmt, candidates);
+                }
+            }
+            StatementKind::ImportFrom {
+                module,
+                names,
+                level,
+            } => {
+                let roots: Vec<(PathBuf, Option<PathBuf>)> = match *level {
+                    0 => roots
+                        .iter()
+                        .map(|root| (root.clone(), Some(root.clone())))
+                        .collect(),
+                    level => relative_root(file, level)
+                        .map(|root| (root, None))
+                        .into_iter()
+                        .collect(),
+                };
+                let module: Vec<&str> = module.iter().flat_map(|m| m.split('.')).collect();
+                for alias in names {
+                    let mut submodule = module.clone();
+                    submodule.push(&alias.name);
+                    let candidates = roots
+                        .iter()
+                        .flat_map(|(root, absolute)| {
+                            let mut files = module_files(root, &submodule);
+                            if !module.is_empty() {
+                                files.extend(module_files(root, &module));
+                            }
+                            files.into_iter().map(|file| (file, absolute.clone()))
+                        })
+                        .collect();
+                    found(stmt, candidates);
+                }
+            }
+            StatementKind::If { test, body, orelse } => {
+                // `if TYPE_CHECKING:` imports never run
+                if !matches!(&test.kind, ExpressionKind::Identifier(name) if name == "TYPE_CHECKING")
+                {
+                    collect_imports(body, file, roots, found);
+                }
+                collect_imports(orelse, file, roots, found);
+            }
+            StatementKind::Try {
+                body,
+                handlers,
+                orelse,
+                finalbody,
+                ..
+            } => {
+                collect_imports(body, file, roots, found);
+                for handler in handlers {
+                    collect_imports(&handler.body, file, roots, found);
+                }
+                collect_imports(orelse, file, roots, found);
+                collect_imports(finalbody, file, roots, found);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Directories searched for absolute imports: the importing file's own
+/// directory and the directory containing its outermost package
+fn search_roots(file: &Path) -> Vec<PathBuf> {
+    let dir = parent_dir(file);
+    let mut package_root = dir.clone();
+    while package_root.join(PACKAGE_INIT).is_file() {
+        match package_root.parent() {
+            Some(parent) => package_root = parent.to_path_buf(),
+            None => break,
+        }
+    }
+
+    let mut roots = vec![dir];
+    if package_root != roots[0] {
+        roots.push(package_root);
+    }
+    roots
+}
+
+/// Package directory for a relative import with `level` leading dots
+fn relative_root(file: &Path, level: usize) -> Option<PathBuf> {
+    let mut dir = parent_dir(file);
+    for _ in 1..level {
+        dir = dir.parent()?.to_path_buf();
+    }
+    Some(dir)
+}
+
+/// The two files a dotted module name may live in under `root`
+fn module_files(root: &Path, parts: &[&str]) -> Vec<PathBuf> {
+    if parts.is_empty() {
+        return vec![root.join(PACKAGE_INIT)];
+    }
+    let dir: PathBuf = parts
+        .iter()
+        .fold(root.to_path_buf(), |dir, part| dir.join(part));
+    vec![
+        dir.with_extension(crate::files::SOURCE_EXTENSION),
+        dir.join(PACKAGE_INIT),
+    ]
+}
+
+fn parent_dir(file: &Path) -> PathBuf {
+    match file.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+        _ => PathBuf::from("."),
+    }
+}
+
+fn canonical(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Display name of a module: its file stem, or the package directory name
+/// for `__init__.silk`
+fn module_name(path: &Path) -> String {
+    let path = if path.file_name().is_some_and(|name| name == PACKAGE_INIT) {
+        path.parent().unwrap_or(path)
+    } else {
+        path
+    };
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.display().to_string())
+}
+
+impl Compiler {
+    /// Parse `paths` and build their module graph; files that cannot be read
+    /// or parsed are left out (checking reports them)
+    pub fn module_graph(&self, paths: &[PathBuf]) -> ModuleGraph {
+        let modules: Vec<(PathBuf, Program)> = paths
+            .par_iter()
+            .filter_map(|path| {
+                let source = fs::read_to_string(path).ok()?;
+                let program = self.parse(&source).ok()?;
+                Some((path.clone(), program))
+            })
+            .collect();
+        ModuleGraph::new(&modules)
+    }
+
+    /// Report every import cycle among `reports`' files on the file where
+    /// the cycle's first import is
+    pub(crate) fn check_import_cycles(&self, reports: &mut [FileDiagnostics]) {
+        let paths: Vec<PathBuf> = reports.iter().map(|r| r.path.clone()).collect();
+        for cycle in self.module_graph(&paths).cycles() {
+            let first = &cycle.steps[0].from;
+            if let Some(report) = reports.iter_mut().find(|r| &r.path == first) {
+                let diagnostics = vec![cycle.to_diagnostic()];
+                report.diagnostics.extend(options::apply_lint_levels(
+                    &self.options().lints,
+                    diagnostics,
+                ));
+            }
+        }
+    }
+}
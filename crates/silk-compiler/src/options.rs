@@ -0,0 +1,238 @@
+/// Compiler configuration
+///
+/// Everything that changes what the compiler reports or produces lives in
+/// `CompilerOptions`, so the command line, the language server and programs
+/// embedding the compiler configure it the same way:
+///
+/// ```
+/// use silk_compiler::{Compiler, CompilerOptions, LintLevel};
+///
+/// let options = CompilerOptions::new()
+///     .with_lint("unused-variable", LintLevel::Allow)
+///     .with_max_errors(10);
+/// let compiler = Compiler::with_options(options);
+/// assert!(compiler.check("x = 1\n").is_empty());
+/// ```
+use crate::config::{self, Config, LintOverride};
+use crate::{sort_diagnostics, ColorChoice, Diagnostic, FeatureSet, LintLevel, Manifest, Severity};
+use silk_semantic::MetricLimits;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Highest supported optimization level
+pub const MAX_OPT_LEVEL: u8 = 3;
+
+/// Settings shared by every compiler phase
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CompilerOptions {
+    /// Optimization level, from 0 (none) to `MAX_OPT_LEVEL`
+    pub opt_level: u8,
+    /// Target triple to compile for; `None` is the host
+    pub target: Option<String>,
+    /// Lint levels by diagnostic code (e.g. `unused-variable`); lints that
+    /// are allowed by default are only reported when given a level
+    pub lints: BTreeMap<String, LintLevel>,
+    /// Lint levels for the files matching absolute glob patterns, applied
+    /// on top of `lints` by `Compiler::check_file`
+    pub overrides: Vec<LintOverride>,
+    /// Unstable features enabled for every phase
+    pub features: FeatureSet,
+    /// Most errors reported by a check, over all of its files; `None`
+    /// reports all of them
+    pub max_errors: Option<usize>,
+    /// When rendered diagnostics are colored
+    pub color: ColorChoice,
+    /// On an internal compiler error, write the AST of the program to a
+    /// temporary file named in the report
+    pub ice_dump_ast: bool,
+    /// Smallest duplicate reported by `duplicate-code`, in nodes; `None`
+    /// uses `DEFAULT_DUPLICATE_MIN_NODES`
+    pub duplicate_min_nodes: Option<usize>,
+    /// Limits checked by `complex-function`; `None` uses the defaults of
+    /// `MetricLimits`
+    pub metric_limits: Option<MetricLimits>,
+}
+
+impl CompilerOptions {
+    /// Default options: no optimization, the host target, default lint
+    /// levels, no unstable features
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the optimization level, capped at `MAX_OPT_LEVEL`
+    pub fn with_opt_level(mut self, level: u8) -> Self {
+        self.opt_level = level.min(MAX_OPT_LEVEL);
+        self
+    }
+
+    /// Compile for a target other than the host
+    pub fn with_target(mut self, target: impl Into<String>) -> Self {
+        self.target = Some(target.into());
+        self
+    }
+
+    /// Set the level of a lint
+    pub fn with_lint(mut self, code: impl Into<String>, level: LintLevel) -> Self {
+        self.lints.insert(code.into(), level);
+        self
+    }
+
+    /// Enable a set of unstable features
+    pub fn with_features(mut self, features: FeatureSet) -> Self {
+        self.features = features;
+        self
+    }
+
+    /// Report at most `count` errors
+    pub fn with_max_errors(mut self, count: usize) -> Self {
+        self.max_errors = Some(count);
+        self
+    }
+
+    /// Choose when diagnostics are colored
+    pub fn with_color(mut self, color: ColorChoice) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Write the AST to a temporary file on internal compiler errors
+    pub fn with_ice_dump_ast(mut self, dump: bool) -> Self {
+        self.ice_dump_ast = dump;
+        self
+    }
+
+    /// Report only duplicates of at least `nodes` statements and
+    /// expressions (`duplicate-code`)
+    pub fn with_duplicate_min_nodes(mut self, nodes: usize) -> Self {
+        self.duplicate_min_nodes = Some(nodes);
+        self
+    }
+
+    /// Report functions with a metric above these limits
+    /// (`complex-function`)
+    pub fn with_metric_limits(mut self, limits: MetricLimits) -> Self {
+        self.metric_limits = Some(limits);
+        self
+    }
+
+    /// Take the lint levels of a project's `[lints]` table and overrides;
+    /// levels already set (e.g. on the command line) win
+    pub fn merge_manifest(&mut self, manifest: &Manifest) {
+        self.merge_config(&manifest.config());
+    }
+
+    /// Take the lint levels of a `silk.toml` or `.silkrc`, and its
+    /// `[duplicate-code]` and `[metrics]` limits; levels already set (e.g. on the
+    /// command line) win over its overrides, which win over its `[lints]`
+    /// table
+    pub fn merge_config(&mut self, config: &Config) {
+        for lint_override in &config.overrides {
+            self.overrides.push(LintOverride {
+                files: lint_override
+                    .files
+                    .iter()
+                    .map(|pattern| config.resolve(pattern))
+                    .collect(),
+                lints: lint_override
+                    .lints
+                    .iter()
+                    .filter(|(code, _)| !self.lints.contains_key(*code))
+                    .map(|(code, level)| (code.clone(), *level))
+                    .collect(),
+            });
+        }
+        for (code, level) in &config.lints {
+            self.lints.entry(code.clone()).or_insert(*level);
+        }
+        self.duplicate_min_nodes
+            .get_or_insert(config.duplicate_code.min_nodes);
+        self.metric_limits.get_or_insert(config.metrics.limits());
+    }
+
+    /// Options for checking one file, with the overrides matching it
+    /// applied; `None` when no override matches
+    pub fn for_file(&self, path: &Path) -> Option<Self> {
+        let path = config::absolute(path);
+        let mut matching = self
+            .overrides
+            .iter()
+            .filter(|lint_override| lint_override.matches(&path))
+            .peekable();
+        matching.peek()?;
+
+        let mut options = Self {
+            overrides: Vec::new(),
+            ..self.clone()
+        };
+        for lint_override in matching {
+            options.lints.extend(lint_override.lints.clone());
+        }
+        Some(options)
+    }
+
+    /// Codes given a `warn` or `error` level
+    pub fn enabled_lints(&self) -> impl Iterator<Item = &str> {
+        enabled_lints(&self.lints)
+    }
+
+    /// Apply the lint levels to a source's diagnostics and sort them by
+    /// position
+    pub fn apply(&self, diagnostics: Vec<Diagnostic>) -> Vec<Diagnostic> {
+        let mut diagnostics = apply_lint_levels(&self.lints, diagnostics);
+        sort_diagnostics(&mut diagnostics);
+        diagnostics
+    }
+
+    /// Keep the first `max_errors` errors of `diagnostics`, taken in
+    /// order across all the lists, and every warning; returns the number
+    /// of errors dropped from each list
+    pub fn cap_errors<'a>(
+        &self,
+        diagnostics: impl IntoIterator<Item = &'a mut Vec<Diagnostic>>,
+    ) -> Vec<usize> {
+        let mut remaining = self.max_errors.unwrap_or(usize::MAX);
+        diagnostics
+            .into_iter()
+            .map(|diagnostics| {
+                let before = diagnostics.len();
+                diagnostics.retain(|diagnostic| {
+                    if !diagnostic.is_error() {
+                        return true;
+                    }
+                    let keep = remaining > 0;
+                    remaining = remaining.saturating_sub(1);
+                    keep
+                });
+                before - diagnostics.len()
+            })
+            .collect()
+    }
+}
+
+/// Codes of a lint table given a `warn` or `error` level
+pub(crate) fn enabled_lints(lints: &BTreeMap<String, LintLevel>) -> impl Iterator<Item = &str> {
+    lints
+        .iter()
+        .filter(|(_, level)| **level != LintLevel::Allow)
+        .map(|(code, _)| code.as_str())
+}
+
+/// Drop allowed lints and re-level the rest
+pub(crate) fn apply_lint_levels(
+    lints: &BTreeMap<String, LintLevel>,
+    diagnostics: Vec<Diagnostic>,
+) -> Vec<Diagnostic> {
+    diagnostics
+        .into_iter()
+        .filter_map(|mut diagnostic| {
+            match lints.get(&diagnostic.code) {
+                Some(LintLevel::Allow) => return None,
+                Some(LintLevel::Warn) => diagnostic.severity = Severity::Warning,
+                Some(LintLevel::Error) => diagnostic.severity = Severity::Error,
+                None => {}
+            }
+            Some(diagnostic)
+        })
+        .collect()
+}
@@ -0,0 +1,304 @@
+/// Memoized compiler queries
+///
+/// Editors and watch mode check the same files over and over, usually after
+/// a change to one of them. A `Database` holds the text of each file as an
+/// input and remembers the result of every query on it, so only the queries
+/// of a file whose text changed run again:
+///
+/// ```text
+/// text ──► tokens ──► parse ──┬─► typed (symbols and expression types)
+///                             └─► diagnostics
+/// ```
+///
+/// Queries take `&self` and may run in parallel on different files.
+/// Changing an input (the text of a file, the compiler) takes `&mut self`
+/// and drops the results that depended on it; setting a file to the text it
+/// already has keeps them.
+use crate::files::unreadable;
+use crate::{
+    parse_error_diagnostics, run_phase, Compiler, Diagnostic, FileDiagnostics, InternalError,
+    LexError, ParseError, Parsed, Phase, Timings, Token, TypedProgram,
+};
+use rayon::prelude::*;
+use std::borrow::Cow;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// The queries a `Database` memoizes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Query {
+    Tokens,
+    Parse,
+    Typed,
+    Diagnostics,
+}
+
+impl Query {
+    pub const ALL: [Query; 4] = [
+        Query::Tokens,
+        Query::Parse,
+        Query::Typed,
+        Query::Diagnostics,
+    ];
+}
+
+/// Result of the `tokens` query
+pub type TokensResult = Arc<Result<Vec<Token>, LexError>>;
+
+/// Result of the `parse` query
+pub type ParseResult = Arc<Result<Parsed, ParseError>>;
+
+/// Result of the `diagnostics` query: the diagnostics of a file, before the
+/// error cap, and the time each phase took when they were computed
+#[derive(Debug, Clone)]
+pub struct Checked {
+    pub diagnostics: Vec<Diagnostic>,
+    pub timings: Timings,
+}
+
+/// Query results of one file, dropped together when its text changes
+#[derive(Default)]
+struct Memos {
+    tokens: Option<TokensResult>,
+    parse: Option<ParseResult>,
+    typed: Option<Option<Arc<TypedProgram>>>,
+    diagnostics: Option<Arc<Checked>>,
+}
+
+struct File {
+    text: Arc<str>,
+    memos: Mutex<Memos>,
+}
+
+impl File {
+    fn new(text: Arc<str>) -> Self {
+        Self {
+            text,
+            memos: Mutex::new(Memos::default()),
+        }
+    }
+}
+
+/// Source files and the memoized results of the queries on them
+pub struct Database {
+    compiler: Compiler,
+    files: HashMap<PathBuf, File>,
+    /// Number of times each query was computed rather than reused
+    executions: [AtomicUsize; Query::ALL.len()],
+}
+
+impl Database {
+    pub fn new(compiler: Compiler) -> Self {
+        Self {
+            compiler,
+            files: HashMap::new(),
+            executions: Default::default(),
+        }
+    }
+
+    /// The compiler queries run with
+    pub fn compiler(&self) -> &Compiler {
+        &self.compiler
+    }
+
+    /// Run queries with another compiler; results are kept if its options
+    /// are the same
+    pub fn set_compiler(&mut self, compiler: Compiler) {
+        if compiler.options() != self.compiler.options() {
+            for file in self.files.values_mut() {
+                *file.memos.get_mut().unwrap() = Memos::default();
+            }
+        }
+        self.compiler = compiler;
+    }
+
+    /// Set the text of a file, adding the file if needed; returns whether
+    /// the text changed
+    pub fn set_file_text(&mut self, path: impl Into<PathBuf>, text: impl Into<Arc<str>>) -> bool {
+        let text = text.into();
+        match self.files.entry(path.into()) {
+            Entry::Occupied(entry) if entry.get().text == text => false,
+            Entry::Occupied(mut entry) => {
+                entry.insert(File::new(text));
+                true
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(File::new(text));
+                true
+            }
+        }
+    }
+
+    /// Forget a file and its results; returns whether it was known
+    pub fn remove_file(&mut self, path: &Path) -> bool {
+        self.files.remove(path).is_some()
+    }
+
+    /// The text of a file
+    pub fn file_text(&self, path: &Path) -> Option<Arc<str>> {
+        self.files.get(path).map(|file| file.text.clone())
+    }
+
+    /// Number of times a query was computed rather than reused
+    pub fn executions(&self, query: Query) -> usize {
+        self.executions[query as usize].load(Ordering::Relaxed)
+    }
+
+    /// Tokens of a file
+    pub fn tokens(&self, path: &Path) -> Option<TokensResult> {
+        self.memoized(
+            path,
+            Query::Tokens,
+            |memos| &mut memos.tokens,
+            |file| Some(Arc::new(self.compiler.lex(&file.text))),
+        )
+    }
+
+    /// AST of a file, with the lint warnings of the parser
+    pub fn parse(&self, path: &Path) -> Option<ParseResult> {
+        self.memoized(
+            path,
+            Query::Parse,
+            |memos| &mut memos.parse,
+            |_| {
+                let parsed = match &*self.tokens(path)? {
+                    Ok(tokens) => self.compiler.parse_tokens(tokens.clone()),
+                    Err(e) => Err(ParseError::LexError(e.clone())),
+                };
+                Some(Arc::new(parsed))
+            },
+        )
+    }
+
+    /// Analyzed program of a file, with its symbols resolved and the type
+    /// of each expression; `None` if the file does not parse
+    pub fn typed(&self, path: &Path) -> Option<Arc<TypedProgram>> {
+        self.memoized(
+            path,
+            Query::Typed,
+            |memos| &mut memos.typed,
+            |_| {
+                let typed = match &*self.parse(path)? {
+                    Ok(parsed) => Some(Arc::new(
+                        self.compiler.analyze_typed(parsed.program.clone()),
+                    )),
+                    Err(_) => None,
+                };
+                Some(typed)
+            },
+        )
+        .flatten()
+    }
+
+    /// Diagnostics of a file, as `Compiler::check_timed` reports them; the
+    /// lint overrides that match the path apply
+    pub fn diagnostics(&self, path: &Path) -> Option<Arc<Checked>> {
+        self.memoized(
+            path,
+            Query::Diagnostics,
+            |memos| &mut memos.diagnostics,
+            |file| {
+                let mut timings = Timings::new();
+                let diagnostics = self
+                    .check(path, &file.text, &mut timings)?
+                    .unwrap_or_else(|ice| vec![ice.to_diagnostic(&file.text)]);
+                Some(Arc::new(Checked {
+                    diagnostics,
+                    timings,
+                }))
+            },
+        )
+    }
+
+    /// Read files from disk and check them like `Compiler::check_files`,
+    /// reusing the results of files whose text did not change
+    ///
+    /// Files checked before but not in `paths` are forgotten.
+    pub fn check_files(&mut self, paths: &[PathBuf]) -> Vec<FileDiagnostics> {
+        let wanted: HashSet<&PathBuf> = paths.iter().collect();
+        self.files.retain(|path, _| wanted.contains(path));
+        let mut errors = HashMap::new();
+        for path in paths {
+            match fs::read_to_string(path) {
+                Ok(text) => {
+                    self.set_file_text(path.clone(), text);
+                }
+                Err(e) => {
+                    self.remove_file(path);
+                    errors.insert(path, e);
+                }
+            }
+        }
+
+        let database = &*self;
+        let mut reports: Vec<FileDiagnostics> = paths
+            .par_iter()
+            .map(|path| {
+                let file = path.display().to_string();
+                let (diagnostics, timings) = match database.diagnostics(path) {
+                    Some(checked) => (checked.diagnostics.clone(), checked.timings.clone()),
+                    None => (vec![unreadable(&file, &errors[path])], Timings::new()),
+                };
+                FileDiagnostics {
+                    path: path.clone(),
+                    diagnostics: diagnostics
+                        .into_iter()
+                        .map(|d| d.with_file(file.clone()))
+                        .collect(),
+                    timings,
+                    hidden_errors: 0,
+                }
+            })
+            .collect();
+        self.compiler.finish_reports(&mut reports);
+        reports
+    }
+
+    /// The phases of `Compiler::check_timed`, through the `tokens` and
+    /// `parse` queries
+    fn check(
+        &self,
+        path: &Path,
+        source: &str,
+        timings: &mut Timings,
+    ) -> Option<Result<Vec<Diagnostic>, InternalError>> {
+        let compiler = match self.compiler.options().for_file(path) {
+            Some(options) => Cow::Owned(Compiler::with_options(options)),
+            None => Cow::Borrowed(&self.compiler),
+        };
+        let parsed = run_phase(timings, Phase::Lex, || self.tokens(path))
+            .and_then(|_| run_phase(timings, Phase::Parse, || self.parse(path)));
+        let parsed = match parsed {
+            Ok(parsed) => parsed?,
+            Err(ice) => return Some(Err(ice)),
+        };
+        Some(match &*parsed {
+            Ok(parsed) => compiler.check_parsed(source, parsed, timings),
+            Err(e) => Ok(parse_error_diagnostics(source, e.clone())),
+        })
+    }
+
+    /// Reuse the result of a query on a file, or compute and remember it;
+    /// `None` for unknown files
+    fn memoized<T: Clone>(
+        &self,
+        path: &Path,
+        query: Query,
+        slot: fn(&mut Memos) -> &mut Option<T>,
+        compute: impl FnOnce(&File) -> Option<T>,
+    ) -> Option<T> {
+        let file = self.files.get(path)?;
+        if let Some(value) = slot(&mut file.memos.lock().unwrap()).clone() {
+            return Some(value);
+        }
+        // Not locked while computing, so the query can use other queries
+        let value = compute(file)?;
+        self.executions[query as usize].fetch_add(1, Ordering::Relaxed);
+        *slot(&mut file.memos.lock().unwrap()) = Some(value.clone());
+        Some(value)
+    }
+}
@@ -0,0 +1,306 @@
+/// Suppression comments
+///
+/// A comment can silence lints where a warning is expected:
+///
+/// ```text
+/// x = compute()  # noqa: unused-variable
+///
+/// # silk: ignore[unused-function, E0510]
+/// def helper():
+///     pass
+/// ```
+///
+/// `# noqa` and `# silk: ignore` name lints by code or error code, or
+/// silence every lint when they name none. A comment after code applies to
+/// its line; a comment on a line of its own applies to the next statement,
+/// including the whole block of a compound statement. Only lints can be
+/// suppressed; errors always fail the check.
+///
+/// A suppression that silences nothing is reported as `unused-suppression`,
+/// so stale comments do not pile up.
+use crate::{Diagnostic, Program, Severity, SourceFile};
+use silk_ast::{NodeIndex, StatementKind};
+use silk_lexer::{is_identifier_continue, Lexer, Span, TokenKind, TriviaKind};
+use std::cmp::Ordering;
+use std::ops::RangeInclusive;
+
+/// A suppression comment and what it silenced
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suppression {
+    /// Location of the comment
+    pub span: Span,
+    /// Lines it applies to
+    pub lines: RangeInclusive<usize>,
+    /// Codes or error codes it names; empty for every lint
+    pub codes: Vec<String>,
+    /// For each code (or once, for every lint): whether it silenced a lint
+    used: Vec<bool>,
+}
+
+impl Suppression {
+    /// Check whether this suppression silences a diagnostic
+    fn matches(&self, diagnostic: &Diagnostic) -> Option<usize> {
+        let line = diagnostic.span?.line;
+        if !self.lines.contains(&line) {
+            return None;
+        }
+        if self.codes.is_empty() {
+            return Some(0);
+        }
+        self.codes.iter().position(|code| {
+            *code == diagnostic.code || Some(code.as_str()) == diagnostic.error_code.as_deref()
+        })
+    }
+}
+
+/// The suppression comments of a source
+#[derive(Debug, Clone, Default)]
+pub struct Suppressions {
+    suppressions: Vec<Suppression>,
+}
+
+impl Suppressions {
+    /// Find the suppression comments of a source; `program` is the source
+    /// parsed, which gives the extent of blocks
+    pub fn new(source: &str, program: &Program) -> Self {
+        let Ok(tokens) = Lexer::new(source).tokenize_lossless() else {
+            return Self::default();
+        };
+        let file = SourceFile::new("", source);
+        let index = NodeIndex::new(program);
+
+        let mut suppressions = Vec::new();
+        for token in &tokens {
+            let standalone = (token.token.kind == TokenKind::Comment)
+                .then(|| (token.token.lexeme.as_str(), token.token.span));
+            let trivia = token
+                .leading
+                .iter()
+                .chain(&token.trailing)
+                .filter(|trivia| trivia.kind == TriviaKind::Comment)
+                .map(|trivia| (trivia.text.as_str(), trivia.span));
+
+            for (text, span) in standalone.into_iter().chain(trivia) {
+                let Some(codes) = parse_directive(text) else {
+                    continue;
+                };
+                let lines = applies_to(&file, &index, span);
+                let used = vec![false; codes.len().max(1)];
+                suppressions.push(Suppression {
+                    span,
+                    lines,
+                    codes,
+                    used,
+                });
+            }
+        }
+        suppressions.sort_by_key(|suppression| suppression.span.start);
+        Self { suppressions }
+    }
+
+    /// The suppressions found, in source order
+    pub fn iter(&self) -> impl Iterator<Item = &Suppression> {
+        self.suppressions.iter()
+    }
+
+    /// Drop the lints a suppression silences, then report the suppressions
+    /// (or the codes of one) that silenced nothing
+    pub fn apply(mut self, diagnostics: Vec<Diagnostic>) -> Vec<Diagnostic> {
+        let mut kept: Vec<Diagnostic> = diagnostics
+            .into_iter()
+            .filter(|diagnostic| {
+                if diagnostic.severity != Severity::Warning {
+                    return true;
+                }
+                let mut silenced = false;
+                for suppression in &mut self.suppressions {
+                    if let Some(code) = suppression.matches(diagnostic) {
+                        suppression.used[code] = true;
+                        silenced = true;
+                    }
+                }
+                !silenced
+            })
+            .collect();
+
+        for suppression in &self.suppressions {
+            let Span { line, column, .. } = suppression.span;
+            if suppression.codes.is_empty() {
+                if !suppression.used[0] {
+                    kept.push(unused(
+                        format!(
+                            "Unused suppression at line {}, column {}: no lint is reported here",
+                            line, column
+                        ),
+                        suppression.span,
+                    ));
+                }
+                continue;
+            }
+            for (code, used) in suppression.codes.iter().zip(&suppression.used) {
+                if !used {
+                    kept.push(unused(
+                        format!(
+                            "Unused suppression of '{}' at line {}, column {}: it is not reported here",
+                            code, line, column
+                        ),
+                        suppression.span,
+                    ));
+                }
+            }
+        }
+        kept
+    }
+}
+
+fn unused(message: String, span: Span) -> Diagnostic {
+    Diagnostic::warning("unused-suppression", message, Some(span)).with_error_code("E0517")
+}
+
+/// Codes named by a `# noqa[: codes]` or `# silk: ignore[[codes]]` comment;
+/// `None` if the comment is not a suppression
+///
+/// A comment may hold other text before the directive, after another `#`
+/// (`# type: int  # noqa`).
+fn parse_directive(comment: &str) -> Option<Vec<String>> {
+    comment.split('#').skip(1).find_map(|part| {
+        let part = part.trim();
+        if part
+            .get(..4)
+            .is_some_and(|word| word.eq_ignore_ascii_case("noqa"))
+        {
+            let rest = &part[4..];
+            return match rest.strip_prefix(':') {
+                Some(codes) => Some(split_codes(codes)),
+                None if rest.is_empty() || rest.starts_with(char::is_whitespace) => {
+                    Some(Vec::new())
+                }
+                None => None,
+            };
+        }
+        let rest = part
+            .strip_prefix("silk:")?
+            .trim_start()
+            .strip_prefix("ignore")?;
+        match rest.strip_prefix('[') {
+            Some(codes) => Some(split_codes(codes.split(']').next().unwrap_or_default())),
+            None if rest.is_empty() || rest.starts_with(char::is_whitespace) => Some(Vec::new()),
+            None => None,
+        }
+    })
+}
+
+/// Codes separated by commas; text after the last code explains it
+fn split_codes(codes: &str) -> Vec<String> {
+    let mut result = Vec::new();
+    for piece in codes.split(',') {
+        let mut words = piece.split_whitespace();
+        let Some(code) = words.next() else {
+            break;
+        };
+        result.push(code.to_string());
+        if words.next().is_some() {
+            break;
+        }
+    }
+    result
+}
+
+/// Lines a suppression comment applies to: its own line after code, or
+/// the next statement (with its block) on a line of its own
+fn applies_to(file: &SourceFile, index: &NodeIndex, comment: Span) -> RangeInclusive<usize> {
+    let line = comment.line;
+    let before = file
+        .line_text(line)
+        .map(|text| text.chars().take(comment.column - 1).collect::<String>())
+        .unwrap_or_default();
+    if !before.trim().is_empty() {
+        return line..=line;
+    }
+
+    // The next line with code on it
+    let next = (line + 1..=file.line_count()).find(|&next| {
+        file.line_text(next).is_some_and(|text| {
+            let text = text.trim();
+            !text.is_empty() && !text.starts_with('#')
+        })
+    });
+    let Some(next) = next else {
+        return line..=line;
+    };
+    // Decorators belong to the definition below them
+    let header = (next..=file.line_count())
+        .find(|&header| {
+            file.line_text(header)
+                .is_some_and(|text| !text.trim().starts_with('@'))
+        })
+        .unwrap_or(next);
+    let indent = indentation(file.line_text(header).unwrap_or_default());
+    let statement_at = |line: usize| {
+        let offset = file.line_start(line).unwrap_or_default() + indent;
+        index
+            .nodes_at(offset)
+            .into_iter()
+            .filter_map(|node| node.as_statement())
+            .find(|statement| statement.span.line == line)
+    };
+    let statement = statement_at(next).or_else(|| statement_at(header));
+    let end = match statement {
+        Some(statement) if is_compound(&statement.kind) => block_end(file, header, indent),
+        // The span of a simple statement ends with its newline
+        Some(statement) => file.location(statement.span.end.saturating_sub(1)).0,
+        None => header,
+    };
+    next..=end.max(header)
+}
+
+fn is_compound(kind: &StatementKind) -> bool {
+    matches!(
+        kind,
+        StatementKind::If { .. }
+            | StatementKind::While { .. }
+            | StatementKind::For { .. }
+            | StatementKind::With { .. }
+            | StatementKind::Match { .. }
+            | StatementKind::Try { .. }
+            | StatementKind::FunctionDef { .. }
+            | StatementKind::ClassDef { .. }
+    )
+}
+
+/// Last line of the compound statement whose header starts on `header`:
+/// the block runs while lines are indented deeper than the header, or
+/// continue it (`else:`, `except:`, a closing bracket)
+fn block_end(file: &SourceFile, header: usize, indent: usize) -> usize {
+    let mut end = header;
+    for line in header + 1..=file.line_count() {
+        let text = file.line_text(line).unwrap_or_default();
+        let code = text.trim_start();
+        if code.is_empty() || code.starts_with('#') {
+            continue;
+        }
+        let continues = match indentation(text).cmp(&indent) {
+            Ordering::Greater => true,
+            Ordering::Equal => {
+                code.starts_with([')', ']', '}'])
+                    || CLAUSES.iter().any(|clause| {
+                        code.strip_prefix(clause)
+                            .is_some_and(|rest| !rest.starts_with(is_identifier_continue))
+                    })
+            }
+            Ordering::Less => false,
+        };
+        if !continues {
+            break;
+        }
+        end = line;
+    }
+    end
+}
+
+/// Keywords of the clauses that continue a compound statement
+const CLAUSES: &[&str] = &["elif", "else", "except", "finally"];
+
+fn indentation(text: &str) -> usize {
+    text.chars().take_while(|c| c.is_whitespace()).count()
+}
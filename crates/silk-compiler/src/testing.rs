@@ -0,0 +1,60 @@
+/// Test discovery for `silk test`
+///
+/// A test is a top-level function named `test_*` or decorated with `@test`.
+/// Tests take no arguments; a test with required parameters is still
+/// discovered but reported as invalid so it is not silently skipped.
+use silk_ast::{ExpressionKind, Program, StatementKind};
+use silk_lexer::Span;
+
+/// A test function found in a program
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestCase {
+    /// Function name
+    pub name: String,
+    /// Location of the function definition
+    pub span: Span,
+    /// Number of parameters without defaults (must be 0 to run)
+    pub required_params: usize,
+}
+
+impl TestCase {
+    /// Check if the test can be called without arguments
+    pub fn is_runnable(&self) -> bool {
+        self.required_params == 0
+    }
+}
+
+/// Find the tests defined at the top level of a program, in source order
+pub fn discover_tests(program: &Program) -> Vec<TestCase> {
+    program
+        .statements
+        .iter()
+        .filter_map(|statement| match &statement.kind {
+            StatementKind::FunctionDef {
+                name,
+                params,
+                decorator_list,
+                ..
+            } => {
+                let decorated = decorator_list
+                    .iter()
+                    .any(|d| matches!(&d.kind, ExpressionKind::Identifier(id) if id == "test"));
+                if !decorated && !name.starts_with("test_") {
+                    return None;
+                }
+
+                let required_params = params
+                    .positional()
+                    .chain(&params.kwonlyargs)
+                    .filter(|param| param.default.is_none())
+                    .count();
+                Some(TestCase {
+                    name: name.clone(),
+                    span: statement.span,
+                    required_params,
+                })
+            }
+            _ => None,
+        })
+        .collect()
+}
@@ -0,0 +1,113 @@
+/// Per-phase compile timings (`--timings`)
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// A compiler phase
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Phase {
+    /// Source text to tokens
+    Lex,
+    /// Tokens to AST
+    Parse,
+    /// Symbol resolution and type checking
+    Semantic,
+    /// Reachability, initialization and unused-code analysis
+    ControlFlow,
+}
+
+impl Phase {
+    /// All phases in pipeline order
+    pub const ALL: &'static [Phase] = &[
+        Phase::Lex,
+        Phase::Parse,
+        Phase::Semantic,
+        Phase::ControlFlow,
+    ];
+
+    /// Name shown in the timings report
+    pub fn name(self) -> &'static str {
+        match self {
+            Phase::Lex => "lex",
+            Phase::Parse => "parse",
+            Phase::Semantic => "semantic",
+            Phase::ControlFlow => "control-flow",
+        }
+    }
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Time spent in each phase (summed when merged across files)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Timings {
+    durations: [Duration; 4],
+}
+
+impl Timings {
+    /// Create empty timings
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Run `f`, adding its wall-clock time to `phase`
+    pub fn time<T>(&mut self, phase: Phase, f: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let result = f();
+        self.record(phase, start.elapsed());
+        result
+    }
+
+    /// Add a duration to a phase
+    pub fn record(&mut self, phase: Phase, duration: Duration) {
+        self.durations[phase as usize] += duration;
+    }
+
+    /// Time spent in a phase
+    pub fn get(&self, phase: Phase) -> Duration {
+        self.durations[phase as usize]
+    }
+
+    /// Time spent in all phases
+    pub fn total(&self) -> Duration {
+        self.durations.iter().sum()
+    }
+
+    /// Add another set of timings to this one
+    pub fn merge(&mut self, other: &Timings) {
+        for &phase in Phase::ALL {
+            self.record(phase, other.get(phase));
+        }
+    }
+}
+
+impl fmt::Display for Timings {
+    /// One line per phase with its share of the total, then the total
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let total = self.total();
+        for &phase in Phase::ALL {
+            let duration = self.get(phase);
+            let percent = if total.is_zero() {
+                0.0
+            } else {
+                duration.as_secs_f64() / total.as_secs_f64() * 100.0
+            };
+            writeln!(
+                f,
+                "  {:<14}{:>10.3} ms {:>6.1}%",
+                phase.name(),
+                duration.as_secs_f64() * 1000.0,
+                percent
+            )?;
+        }
+        write!(
+            f,
+            "  {:<14}{:>10.3} ms",
+            "total",
+            total.as_secs_f64() * 1000.0
+        )
+    }
+}
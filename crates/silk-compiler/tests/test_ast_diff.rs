@@ -0,0 +1,113 @@
+//! Tests for structural diffs (`silk diff`)
+
+use pretty_assertions::assert_eq;
+use silk_compiler::ast_diff::{self, Change, DefinitionKind, StatementChange};
+use silk_compiler::Compiler;
+
+fn diff(old: &str, new: &str) -> Vec<Change> {
+    let compiler = Compiler::new();
+    ast_diff::diff(&compiler.parse(old).unwrap(), &compiler.parse(new).unwrap())
+}
+
+fn render(old: &str, new: &str) -> String {
+    diff(old, new).iter().map(ToString::to_string).collect()
+}
+
+#[test]
+fn test_formatting_and_comments_are_ignored() {
+    let old = "def add(a, b):\n    return a + b\n\nprint(add(1, 2))\n";
+    let new = "# Adding\ndef add(a,b):\n    return (a+b)  # sum\nprint(add(1,\n          2))\n";
+    assert_eq!(diff(old, new), []);
+    assert_eq!(diff("x = 'a'\n", "x = \"a\"\n"), []);
+}
+
+#[test]
+fn test_definitions_added_removed_and_changed() {
+    let old = "def add(a, b):\n    return a + b\n\ndef helper():\n    pass\n";
+    let new =
+        "def greet(name):\n    print(name)\n\ndef add(a: int, b: int) -> int:\n    return a + b\n";
+    assert_eq!(
+        render(old, new),
+        "removed function 'helper'\n    - 4 | def helper():\n\
+         added function 'greet'\n    + 1 | def greet(name):\n\
+         changed signature of function 'add'\n    - 1 | def add(a, b):\n    + 4 | def add(a: int, b: int) -> int:\n"
+    );
+
+    // Decorators are part of the signature
+    let changes = diff("def f():\n    pass\n", "@cache\ndef f():\n    pass\n");
+    let [Change::SignatureChanged { new, .. }] = changes.as_slice() else {
+        panic!("{:?}", changes);
+    };
+    assert_eq!(new.header, "@cache\ndef f():");
+}
+
+#[test]
+fn test_changed_statements() {
+    let old = "def main():\n    x = 1\n    print(x)\n\nmain()\n";
+    let new = "def main():\n    x = 2\n    print(x)\n    print('done')\n\nmain()\nmain()\n";
+    assert_eq!(
+        diff(old, new),
+        [
+            Change::BodyChanged {
+                scope: Some("main".to_string()),
+                statements: vec![
+                    StatementChange::Removed {
+                        line: 2,
+                        text: "x = 1".to_string()
+                    },
+                    StatementChange::Added {
+                        line: 2,
+                        text: "x = 2".to_string()
+                    },
+                    StatementChange::Added {
+                        line: 4,
+                        text: "print(\"done\")".to_string()
+                    },
+                ],
+            },
+            Change::BodyChanged {
+                scope: None,
+                statements: vec![StatementChange::Added {
+                    line: 7,
+                    text: "main()".to_string()
+                }],
+            },
+        ]
+    );
+
+    // Compound statements are shown whole
+    assert_eq!(
+        render("if x:\n    y = 1\n", "if x:\n    y = 2\n"),
+        "changed module-level statements\n    - 1 | if x:\n        |     y = 1\n    + 1 | if x:\n        |     y = 2\n"
+    );
+}
+
+#[test]
+fn test_classes_and_methods() {
+    let old = "class Point:\n    x = 0\n    def norm(self):\n        return self.x\n\nclass Old:\n    def run(self):\n        pass\n";
+    let new = "class Point(Base):\n    x = 0\n    def norm(self):\n        return abs(self.x)\n    def scale(self, k):\n        pass\n";
+    let changes = diff(old, new);
+    assert_eq!(changes.len(), 4, "{:?}", changes);
+
+    // The methods of a removed class are not listed
+    let Change::Removed(class) = &changes[0] else {
+        panic!("{:?}", changes[0]);
+    };
+    assert_eq!(
+        (class.kind, class.name.as_str()),
+        (DefinitionKind::Class, "Old")
+    );
+    assert!(
+        matches!(&changes[1], Change::SignatureChanged { new, .. } if new.header == "class Point(Base):")
+    );
+    assert!(
+        matches!(&changes[2], Change::BodyChanged { scope: Some(name), .. } if name == "Point.norm")
+    );
+    let Change::Added(method) = &changes[3] else {
+        panic!("{:?}", changes[3]);
+    };
+    assert_eq!(
+        (method.kind, method.name.as_str()),
+        (DefinitionKind::Method, "Point.scale")
+    );
+}
@@ -0,0 +1,65 @@
+//! Tests for call graph export (`silk analyze --call-graph`)
+
+use pretty_assertions::assert_eq;
+use silk_compiler::{call_graph, Compiler, FileCallGraph};
+use std::path::PathBuf;
+
+const SOURCE: &str = r#"
+def helper() -> int:
+    return 1
+
+def main():
+    print(helper())
+
+main()
+"#;
+
+fn graphs() -> Vec<FileCallGraph> {
+    let graph = Compiler::new()
+        .call_graph(PathBuf::from("app.silk"), SOURCE)
+        .unwrap();
+    vec![graph]
+}
+
+#[test]
+fn test_dot_output() {
+    assert_eq!(
+        call_graph::to_dot(&graphs()),
+        r#"digraph calls {
+    node [shape=box];
+    subgraph cluster_0 {
+        label="app.silk";
+        "app.silk::<module>" [label="<module>", shape=ellipse];
+        "app.silk::helper" [label="helper"];
+        "app.silk::main" [label="main"];
+    }
+    "app.silk::main" -> "app.silk::helper" [label="6:11"];
+    "app.silk::<module>" -> "app.silk::main" [label="8:1"];
+}
+"#
+    );
+}
+
+#[test]
+fn test_json_output() {
+    let json = call_graph::to_json(&graphs());
+    let file = &json["files"][0];
+    assert_eq!(file["file"], "app.silk");
+    assert_eq!(file["functions"][0]["name"], "helper");
+    assert_eq!(file["functions"][0]["span"]["line"], 2);
+
+    let edges = file["edges"].as_array().unwrap();
+    assert_eq!(edges.len(), 2);
+    assert_eq!(edges[0]["caller"], "main");
+    assert_eq!(edges[0]["callee"], "helper");
+    assert_eq!(edges[0]["span"]["line"], 6);
+    assert_eq!(edges[0]["span"]["column"], 11);
+    assert_eq!(edges[1]["caller"], "<module>");
+}
+
+#[test]
+fn test_parse_error_is_returned() {
+    assert!(Compiler::new()
+        .call_graph(PathBuf::from("bad.silk"), "def (:\n")
+        .is_err());
+}
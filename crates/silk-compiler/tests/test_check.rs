@@ -0,0 +1,150 @@
+//! Tests for the full check pipeline (parse + semantic + control flow)
+
+use silk_compiler::{Compiler, Severity};
+use std::sync::Arc;
+
+#[test]
+fn test_clean_program_has_no_diagnostics() {
+    let source = "def add(a: int, b: int) -> int:\n    return a + b\n\nprint(add(1, 2))\n";
+    assert!(Compiler::new().check(source).is_empty());
+}
+
+#[test]
+fn test_parse_error_becomes_single_diagnostic() {
+    let diagnostics = Compiler::new().check("x = (\n");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].severity, Severity::Error);
+    assert!(diagnostics[0].span.is_some());
+}
+
+#[test]
+fn test_semantic_errors_have_codes_and_spans() {
+    let diagnostics = Compiler::new().check("print(y)\n");
+    let undefined = diagnostics
+        .iter()
+        .find(|d| d.code == "undefined-variable")
+        .expect("undefined variable should be reported");
+    assert_eq!(undefined.severity, Severity::Error);
+    assert_eq!(undefined.span.unwrap().line, 1);
+}
+
+#[test]
+fn test_control_flow_lints_are_warnings() {
+    let diagnostics = Compiler::new().check("x = 1\n");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].code, "unused-variable");
+    assert_eq!(diagnostics[0].severity, Severity::Warning);
+}
+
+// ========== PHASES ==========
+
+#[test]
+fn test_phases_compose() {
+    let compiler = Compiler::new();
+    let source = "count = len('abc')\nprint(count + missing)\n";
+    let program = Arc::new(compiler.parse(source).unwrap());
+    let typed = compiler.analyze_typed(program.clone());
+
+    // The typed program shares the parsed one instead of copying it
+    assert!(Arc::ptr_eq(typed.shared_program(), &program));
+    assert_eq!(
+        typed.type_at_offset(0).map(|(_, ty)| ty.to_string()),
+        Some("int".to_string())
+    );
+    let codes: Vec<_> = typed.errors().iter().map(|err| err.code()).collect();
+    assert_eq!(codes, ["undefined-variable", "uninitialized-variable"]);
+    assert_eq!(
+        compiler.analyze(&program).unwrap_err().len(),
+        typed.errors().len()
+    );
+}
+
+#[test]
+fn test_phases_share_the_compiler_configuration() {
+    let program = "def f():\n    return 1\n\nprint(f())\n";
+    let mut compiler = Compiler::new();
+    let parsed = Arc::new(compiler.parse(program).unwrap());
+    assert!(!compiler.analyze_typed(parsed.clone()).has_errors());
+
+    compiler.enable_lint("missing-docstring");
+    let codes: Vec<_> = compiler
+        .analyze_typed(parsed)
+        .errors()
+        .iter()
+        .map(|err| err.code())
+        .collect();
+    assert_eq!(codes, ["missing-docstring"]);
+}
+
+// ========== LONG CHAINS ==========
+
+#[test]
+fn test_long_chains_are_checked() {
+    // Each link is one more level of the tree; the passes must not run out
+    // of stack on the test thread
+    let n = 1_000;
+    let sources = [
+        format!("x = 1{}\nprint(x)\n", " + 1".repeat(n)),
+        format!("x = True{}\nprint(x)\n", " and True".repeat(n)),
+        format!("x = 'a'.upper(){}\nprint(x)\n", ".upper()".repeat(n)),
+        format!("x = 'abc'{}\nprint(x)\n", "[0]".repeat(n)),
+    ];
+    for source in &sources {
+        let diagnostics = Compiler::new().check(source);
+        assert!(
+            diagnostics.iter().all(|d| d.severity != Severity::Error),
+            "{diagnostics:?}"
+        );
+    }
+}
+
+#[test]
+fn test_long_elif_ladders_are_checked() {
+    let n = 2_000;
+    let mut source = String::from("def f(x: int) -> int:\n    if x == 0:\n        return 0\n");
+    for i in 1..n {
+        source.push_str(&format!("    elif x == {i}:\n        return {i}\n"));
+    }
+    source.push_str("    else:\n        return -1\n\nprint(f(3))\n");
+    assert!(Compiler::new().check(&source).is_empty());
+}
+
+// ========== TIMINGS ==========
+
+#[test]
+fn test_timings_merge_and_total() {
+    use silk_compiler::{Phase, Timings};
+    use std::time::Duration;
+
+    let mut a = Timings::new();
+    a.record(Phase::Lex, Duration::from_millis(2));
+    a.record(Phase::Semantic, Duration::from_millis(3));
+    let mut b = Timings::new();
+    b.record(Phase::Lex, Duration::from_millis(1));
+    b.record(Phase::ControlFlow, Duration::from_millis(4));
+
+    a.merge(&b);
+    assert_eq!(a.get(Phase::Lex), Duration::from_millis(3));
+    assert_eq!(a.get(Phase::Parse), Duration::ZERO);
+    assert_eq!(a.total(), Duration::from_millis(10));
+}
+
+#[test]
+fn test_check_timed_matches_check() {
+    let source = "x = 1\nprint(y)\n";
+    let compiler = Compiler::new();
+    let (diagnostics, _) = compiler.check_timed(source);
+    assert_eq!(diagnostics, compiler.check(source));
+}
+
+#[test]
+fn test_check_timed_stops_after_lex_error() {
+    use silk_compiler::Phase;
+    use std::time::Duration;
+
+    let (diagnostics, timings) = Compiler::new().check_timed("x = $\n");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].code, "lex-error");
+    assert_eq!(timings.get(Phase::Parse), Duration::ZERO);
+    assert_eq!(timings.get(Phase::Semantic), Duration::ZERO);
+}
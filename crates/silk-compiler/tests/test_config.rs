@@ -0,0 +1,198 @@
+//! Tests for lint configuration from silk.toml and .silkrc
+
+use silk_compiler::config::CONFIG_FILE;
+use silk_compiler::{
+    Compiler, CompilerOptions, Config, FormatConfig, LintLevel, Manifest, ManifestError,
+    MetricLimits, Severity,
+};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+const SETTINGS: &str = r#"
+[lints]
+unused-variable = "error"
+
+[sources]
+ignore = ["build", "**/generated_*.silk"]
+
+[[overrides]]
+files = ["tests/**"]
+lints = { unused-variable = "allow", unused-function = "warn" }
+
+[format]
+line-length = 88
+
+[duplicate-code]
+min-nodes = 12
+
+[metrics]
+max-parameters = 3
+"#;
+
+#[test]
+fn test_silkrc_and_manifest_share_the_tables() {
+    let config = Config::parse(SETTINGS, Path::new("/project/.silkrc")).unwrap();
+    assert_eq!(config.root, Path::new("/project"));
+    assert_eq!(config.lints["unused-variable"], LintLevel::Error);
+    assert_eq!(config.sources.ignore, ["build", "**/generated_*.silk"]);
+    assert_eq!(config.overrides[0].files, ["tests/**"]);
+    assert_eq!(
+        config.overrides[0].lints["unused-variable"],
+        LintLevel::Allow
+    );
+    assert_eq!(
+        config.format,
+        FormatConfig {
+            indent_width: 4,
+            line_length: 88
+        }
+    );
+    assert_eq!(config.duplicate_code.min_nodes, 12);
+    assert_eq!(config.metrics.max_parameters, 3);
+    assert_eq!(config.metrics.max_complexity, 10);
+
+    let manifest = format!(
+        "[package]\nname = \"demo\"\nversion = \"0.1.0\"\n{}",
+        SETTINGS
+    );
+    let manifest = Manifest::parse(&manifest, PathBuf::from("/project")).unwrap();
+    assert_eq!(manifest.config(), config);
+
+    // New projects do not write the optional tables
+    let toml = Manifest::new("demo", PathBuf::new()).to_toml_string();
+    assert!(!toml.contains("[format]") && !toml.contains("[sources]"));
+    assert!(!toml.contains("[duplicate-code]") && !toml.contains("[metrics]"));
+}
+
+#[test]
+fn test_invalid_settings_are_rejected() {
+    for contents in [
+        "[sources]\nignore = [\"[\"]\n",
+        "[[overrides]]\nfiles = [\"a/[b\"]\n",
+        "[format]\nindent-width = 0\n",
+        "[duplicate-code]\nmin-nodes = 0\n",
+        "ignore = [\"build\"]\n",
+    ] {
+        let error = Config::parse(contents, Path::new(".silkrc")).unwrap_err();
+        assert!(
+            matches!(error, ManifestError::Invalid { .. }),
+            "{}: {}",
+            contents,
+            error
+        );
+    }
+}
+
+#[test]
+fn test_discover_finds_the_nearest_configuration() {
+    let dir = TempDir::new().unwrap();
+    fs::write(dir.path().join(CONFIG_FILE), SETTINGS).unwrap();
+    fs::create_dir_all(dir.path().join("app/src")).unwrap();
+    fs::write(
+        dir.path().join("app/silk.toml"),
+        "[package]\nname = \"app\"\nversion = \"0.1.0\"\n",
+    )
+    .unwrap();
+
+    let config = Config::discover(&dir.path().join("app/src"))
+        .unwrap()
+        .unwrap();
+    assert_eq!(config.root, dir.path().join("app"));
+    assert!(config.lints.is_empty());
+
+    let config = Config::discover(dir.path()).unwrap().unwrap();
+    assert_eq!(config.root, dir.path());
+    assert_eq!(config.lints["unused-variable"], LintLevel::Error);
+}
+
+#[test]
+fn test_ignore_and_overrides_apply_by_path() {
+    let dir = TempDir::new().unwrap();
+    let config = Config::parse(SETTINGS, &dir.path().join(CONFIG_FILE)).unwrap();
+    assert!(config.is_ignored(&dir.path().join("build/out.silk")));
+    assert!(config.is_ignored(&dir.path().join("src/generated_api.silk")));
+    assert!(!config.is_ignored(&dir.path().join("src/main.silk")));
+
+    let source = "def f():\n    x = 1\n    return 2\n\nprint(f())\n";
+    let files = [
+        dir.path().join("src/a.silk"),
+        dir.path().join("tests/a.silk"),
+    ];
+    for file in &files {
+        fs::create_dir_all(file.parent().unwrap()).unwrap();
+        fs::write(file, source).unwrap();
+    }
+    let severities = |options: CompilerOptions| -> Vec<Vec<Severity>> {
+        Compiler::with_options(options)
+            .check_files(&files)
+            .iter()
+            .map(|report| report.diagnostics.iter().map(|d| d.severity).collect())
+            .collect()
+    };
+
+    let mut options = CompilerOptions::new();
+    options.merge_config(&config);
+    assert_eq!(severities(options), [vec![Severity::Error], vec![]]);
+
+    // Levels set on the command line win over overrides
+    let mut options = CompilerOptions::new().with_lint("unused-variable", LintLevel::Warn);
+    options.merge_config(&config);
+    assert_eq!(
+        severities(options),
+        [vec![Severity::Warning], vec![Severity::Warning]]
+    );
+}
+
+#[test]
+fn test_duplicate_code_threshold_is_configured() {
+    let config = Config::parse(SETTINGS, Path::new("/project/.silkrc")).unwrap();
+    let source = "def a(x):\n    y = x * 2 + 1\n    return y\n\n\
+                  def b(x):\n    y = x * 2 + 1\n    return y\n\n\
+                  print(a(1), b(2))\n";
+    let duplicates = |options: CompilerOptions| {
+        Compiler::with_options(options.with_lint("duplicate-code", LintLevel::Warn))
+            .check(source)
+            .iter()
+            .filter(|diagnostic| diagnostic.code == "duplicate-code")
+            .count()
+    };
+
+    // The body has 9 nodes, below the default threshold
+    assert_eq!(duplicates(CompilerOptions::new()), 0);
+    let mut options = CompilerOptions::new().with_duplicate_min_nodes(9);
+    assert_eq!(duplicates(options.clone()), 1);
+
+    // A threshold set in code wins over the configuration
+    options.merge_config(&config);
+    assert_eq!(options.duplicate_min_nodes, Some(9));
+    let mut options = CompilerOptions::new();
+    options.merge_config(&config);
+    assert_eq!(duplicates(options), 0);
+}
+
+#[test]
+fn test_metric_limits_are_configured() {
+    let config = Config::parse(SETTINGS, Path::new("/project/.silkrc")).unwrap();
+    let source = "def box(x, y, width, height):\n    return x + y + width + height\n\n\
+                  print(box(1, 2, 3, 4))\n";
+    let complex = |options: CompilerOptions| {
+        Compiler::with_options(options.with_lint("complex-function", LintLevel::Warn))
+            .check(source)
+            .iter()
+            .filter(|diagnostic| diagnostic.code == "complex-function")
+            .count()
+    };
+
+    // 4 parameters are within the default limit of 6, but not the
+    // configured limit of 3
+    assert_eq!(complex(CompilerOptions::new()), 0);
+    let mut options = CompilerOptions::new();
+    options.merge_config(&config);
+    assert_eq!(complex(options), 1);
+
+    // Limits set in code win over the configuration
+    let mut options = CompilerOptions::new().with_metric_limits(MetricLimits::default());
+    options.merge_config(&config);
+    assert_eq!(complex(options), 0);
+}
@@ -0,0 +1,113 @@
+//! Golden-file tests for rendered diagnostics
+//!
+//! Every `tests/snapshots/*.silk` fixture is checked and its diagnostics are
+//! rendered the way `silk check` prints them, without colors. The output is
+//! compared with the `.stderr` file next to the fixture, so a change to a
+//! message, span or snippet shows up as a reviewable diff of that file.
+//!
+//! To add a fixture or accept changed output, run the tests with
+//! `SILK_BLESS=1` and review the updated `.stderr` files:
+//!
+//! ```text
+//! SILK_BLESS=1 cargo test -p silk-compiler --test test_diagnostic_snapshots
+//! ```
+
+use silk_compiler::{Compiler, Renderer, SourceFile};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Fixture directory, relative to the crate so that paths in the output do
+/// not depend on where the repository is checked out
+const SNAPSHOTS: &str = "tests/snapshots";
+
+fn fixtures() -> Vec<PathBuf> {
+    let mut fixtures: Vec<PathBuf> = fs::read_dir(SNAPSHOTS)
+        .expect("snapshot directory should exist")
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "silk"))
+        .collect();
+    fixtures.sort();
+    fixtures
+}
+
+/// Diagnostics of a fixture as `silk check` prints them
+fn render(path: &Path) -> String {
+    let report = Compiler::new().check_file(path);
+    let text = fs::read_to_string(path).unwrap();
+    let file = SourceFile::new(path.display().to_string(), text);
+    let renderer = Renderer::plain();
+
+    let mut out = format!("{}:\n", path.display());
+    for diagnostic in &report.diagnostics {
+        out.push_str(&renderer.render(diagnostic, Some(&file)));
+    }
+    out
+}
+
+/// The first line where two outputs differ
+fn first_difference(expected: &str, actual: &str) -> String {
+    let mut expected_lines = expected.lines();
+    let mut actual_lines = actual.lines();
+    for line in 1.. {
+        match (expected_lines.next(), actual_lines.next()) {
+            (Some(e), Some(a)) if e == a => continue,
+            (None, None) => break,
+            (e, a) => {
+                return format!(
+                    "line {}:\n  expected: {}\n  actual:   {}",
+                    line,
+                    e.unwrap_or("<end of file>"),
+                    a.unwrap_or("<end of file>")
+                )
+            }
+        }
+    }
+    "trailing newline".to_string()
+}
+
+#[test]
+fn test_diagnostic_snapshots() {
+    let bless = std::env::var_os("SILK_BLESS").is_some();
+    let fixtures = fixtures();
+    assert!(!fixtures.is_empty(), "no fixtures in {}", SNAPSHOTS);
+
+    let mut failures = Vec::new();
+    for fixture in &fixtures {
+        let snapshot = fixture.with_extension("stderr");
+        let actual = render(fixture);
+        let expected = fs::read_to_string(&snapshot).ok();
+        if expected.as_deref() == Some(actual.as_str()) {
+            continue;
+        }
+        if bless {
+            fs::write(&snapshot, &actual).unwrap();
+            continue;
+        }
+        failures.push(match expected {
+            Some(expected) => format!(
+                "{} changed, {}\n\nnew output:\n{}",
+                snapshot.display(),
+                first_difference(&expected, &actual),
+                actual
+            ),
+            None => format!("{} is missing; new output:\n{}", snapshot.display(), actual),
+        });
+    }
+
+    assert!(
+        failures.is_empty(),
+        "{}\n\nrerun with SILK_BLESS=1 to accept the new output",
+        failures.join("\n\n")
+    );
+}
+
+#[test]
+fn test_every_snapshot_has_a_fixture() {
+    let stale: Vec<PathBuf> = fs::read_dir(SNAPSHOTS)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "stderr"))
+        .filter(|path| !path.with_extension("silk").exists())
+        .collect();
+    assert!(stale.is_empty(), "snapshots without a fixture: {:?}", stale);
+}
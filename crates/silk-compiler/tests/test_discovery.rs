@@ -0,0 +1,74 @@
+//! Tests for `silk test` test discovery
+
+use silk_compiler::{discover_tests, Compiler};
+
+fn tests_in(source: &str) -> Vec<(String, usize)> {
+    let program = Compiler::new()
+        .parse(source)
+        .expect("Parser should succeed");
+    discover_tests(&program)
+        .into_iter()
+        .map(|t| (t.name, t.required_params))
+        .collect()
+}
+
+#[test]
+fn test_prefix_and_decorator_discovery() {
+    let source = r#"
+def test_add():
+    assert 1 + 1 == 2
+
+@test
+def subtraction_works():
+    assert 2 - 1 == 1
+
+def helper():
+    return 1
+"#;
+    assert_eq!(
+        tests_in(source),
+        [
+            ("test_add".to_string(), 0),
+            ("subtraction_works".to_string(), 0)
+        ]
+    );
+}
+
+#[test]
+fn test_required_parameters_are_counted() {
+    let source = r#"
+def test_needs_arg(x, y=1, *, z):
+    pass
+"#;
+    let program = Compiler::new().parse(source).unwrap();
+    let tests = discover_tests(&program);
+    assert_eq!(tests[0].required_params, 2);
+    assert!(!tests[0].is_runnable());
+}
+
+#[test]
+fn test_nested_functions_are_not_tests() {
+    let source = r#"
+def outer():
+    def test_inner():
+        pass
+    test_inner()
+
+outer()
+"#;
+    assert!(tests_in(source).is_empty());
+}
+
+#[test]
+fn test_test_functions_check_cleanly() {
+    // Tests are never called directly, and `test` is a builtin decorator
+    let source = r#"
+def test_add():
+    assert 1 + 1 == 2
+
+@test
+def subtraction_works():
+    assert 2 - 1 == 1
+"#;
+    assert_eq!(Compiler::new().check(source), []);
+}
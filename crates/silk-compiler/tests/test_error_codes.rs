@@ -0,0 +1,160 @@
+//! Tests for error codes and the examples in their explanations
+
+use silk_compiler::codes::{lookup, ERROR_CODES};
+use silk_compiler::{Compiler, Diagnostic};
+use std::collections::HashSet;
+use std::fs;
+use tempfile::TempDir;
+
+/// Code blocks of an explanation: (is the erroneous example, source)
+fn examples(explanation: &str) -> Vec<(bool, String)> {
+    let mut blocks = Vec::new();
+    let mut current: Option<(bool, String)> = None;
+    for line in explanation.lines() {
+        match (&mut current, line) {
+            (None, "```silk,error") => current = Some((true, String::new())),
+            (None, "```silk") => current = Some((false, String::new())),
+            (Some(_), "```") => blocks.extend(current.take()),
+            (Some((_, source)), line) => {
+                source.push_str(line);
+                source.push('\n');
+            }
+            _ => {}
+        }
+    }
+    blocks
+}
+
+fn check(source: &str) -> Vec<Diagnostic> {
+    let mut compiler = Compiler::new();
+    compiler.enable_lint("missing-docstring");
+    compiler.enable_lint("duplicate-code");
+    compiler.enable_lint("complex-function");
+    compiler.enable_lint("import-side-effect");
+    if !source.starts_with("# file: ") {
+        return compiler.check(source);
+    }
+
+    // An example of several files, each starting with `# file: <name>`
+    let dir = TempDir::new().unwrap();
+    let mut paths = Vec::new();
+    for file in source.split("# file: ").skip(1) {
+        let (name, text) = file.split_once('\n').unwrap();
+        let path = dir.path().join(name);
+        fs::write(&path, text).unwrap();
+        paths.push(path);
+    }
+    compiler
+        .check_files(&paths)
+        .into_iter()
+        .flat_map(|report| report.diagnostics)
+        .collect()
+}
+
+#[test]
+fn test_codes_are_unique_and_ordered() {
+    let mut seen = HashSet::new();
+    for pair in ERROR_CODES.windows(2) {
+        assert!(pair[0].code < pair[1].code, "{} out of order", pair[1].code);
+    }
+    for entry in ERROR_CODES {
+        assert!(seen.insert(entry.code), "{} listed twice", entry.code);
+        assert!(!entry.summary().is_empty(), "{} has no summary", entry.code);
+        assert!(
+            !entry.summary().starts_with('#'),
+            "{}: the summary is a heading",
+            entry.code
+        );
+    }
+}
+
+#[test]
+fn test_names_are_unique() {
+    // `lookup` finds a code by its name, so two codes may not share one
+    let mut seen = HashSet::new();
+    for entry in ERROR_CODES {
+        assert!(
+            seen.insert(entry.name),
+            "{} is the name of two codes",
+            entry.name
+        );
+        assert_eq!(lookup(entry.name).map(|found| found.code), Some(entry.code));
+    }
+}
+
+#[test]
+fn test_erroneous_examples_report_their_code() {
+    let mut failures = Vec::new();
+    for entry in ERROR_CODES {
+        let blocks = examples(entry.explanation);
+        let erroneous: Vec<_> = blocks.iter().filter(|(error, _)| *error).collect();
+        if erroneous.is_empty() == entry.is_emitted() {
+            failures.push(format!(
+                "{}: codes the compiler reports need an erroneous example, and only those",
+                entry.code
+            ));
+        }
+
+        for (_, source) in erroneous {
+            let diagnostics = check(source);
+            match diagnostics
+                .iter()
+                .find(|d| d.error_code.as_deref() == Some(entry.code))
+            {
+                // Lints are the warnings, and only those
+                Some(reported) if reported.is_error() == entry.lint => failures.push(format!(
+                    "{}: reported as a {}, but lint is {}",
+                    entry.code, reported.severity, entry.lint
+                )),
+                // Lexer errors share the `lex-error` name in diagnostics
+                Some(reported) if entry.code.starts_with("E00") || reported.code == entry.name => {}
+                _ => failures.push(format!(
+                    "{}: example does not report it:\n{}{:#?}",
+                    entry.code, source, diagnostics
+                )),
+            }
+        }
+    }
+    assert!(failures.is_empty(), "{}", failures.join("\n\n"));
+}
+
+#[test]
+fn test_corrected_examples_are_clean() {
+    let mut failures = Vec::new();
+    for entry in ERROR_CODES {
+        for (_, source) in examples(entry.explanation)
+            .into_iter()
+            .filter(|(error, _)| !error)
+        {
+            let diagnostics = check(&source);
+            if diagnostics
+                .iter()
+                .any(|d| d.is_error() || d.error_code.as_deref() == Some(entry.code))
+            {
+                failures.push(format!(
+                    "{}: corrected example reports problems:\n{}{:#?}",
+                    entry.code, source, diagnostics
+                ));
+            }
+        }
+    }
+    assert!(failures.is_empty(), "{}", failures.join("\n\n"));
+}
+
+#[test]
+fn test_lookup() {
+    assert_eq!(lookup("E0200").map(|e| e.name), Some("undefined-variable"));
+    assert_eq!(lookup("e0200").map(|e| e.code), Some("E0200"));
+    assert_eq!(lookup("200").map(|e| e.code), Some("E0200"));
+    assert_eq!(lookup("missing-return").map(|e| e.code), Some("E0405"));
+    assert_eq!(lookup("E9999"), None);
+    assert_eq!(lookup("no-such-error"), None);
+}
+
+#[test]
+fn test_diagnostics_carry_error_codes() {
+    let diagnostics = Compiler::new().check("print(count)\n");
+    assert_eq!(diagnostics[0].error_code.as_deref(), Some("E0200"));
+    let diagnostics = Compiler::new().check("x = \"open\n");
+    assert_eq!(diagnostics[0].error_code.as_deref(), Some("E0002"));
+}
@@ -0,0 +1,149 @@
+//! Tests for multi-file discovery and parallel checking
+
+use silk_compiler::{collect_source_files, Compiler, CompilerOptions, CMDLINE_NAME, STDIN_NAME};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+/// Create a project tree: a.silk, notes.txt, sub/b.silk, sub/c.silk
+fn project() -> TempDir {
+    let dir = TempDir::new().unwrap();
+    fs::create_dir(dir.path().join("sub")).unwrap();
+    fs::write(dir.path().join("a.silk"), "print(1)\n").unwrap();
+    fs::write(dir.path().join("notes.txt"), "not silk\n").unwrap();
+    fs::write(dir.path().join("sub/b.silk"), "x = 1\n").unwrap();
+    fs::write(dir.path().join("sub/c.silk"), "print(q)\n").unwrap();
+    dir
+}
+
+fn names(root: &Path, files: &[PathBuf]) -> Vec<String> {
+    files
+        .iter()
+        .map(|f| {
+            f.strip_prefix(root)
+                .unwrap()
+                .to_string_lossy()
+                .replace('\\', "/")
+        })
+        .collect()
+}
+
+#[test]
+fn test_directory_is_searched_recursively() {
+    let dir = project();
+    let files = collect_source_files(&[dir.path().to_path_buf()]).unwrap();
+    assert_eq!(
+        names(dir.path(), &files),
+        ["a.silk", "sub/b.silk", "sub/c.silk"]
+    );
+}
+
+#[test]
+fn test_glob_pattern() {
+    let dir = project();
+    let pattern = dir.path().join("sub").join("*.silk");
+    let files = collect_source_files(&[pattern]).unwrap();
+    assert_eq!(names(dir.path(), &files), ["sub/b.silk", "sub/c.silk"]);
+}
+
+#[test]
+fn test_duplicates_are_removed() {
+    let dir = project();
+    let file = dir.path().join("a.silk");
+    let files = collect_source_files(&[file.clone(), dir.path().to_path_buf()]).unwrap();
+    assert_eq!(files.iter().filter(|f| **f == file).count(), 1);
+}
+
+#[test]
+fn test_missing_path_is_an_error() {
+    let dir = project();
+    assert!(collect_source_files(&[dir.path().join("missing.silk")]).is_err());
+}
+
+#[test]
+fn test_check_files_reports_per_file() {
+    let dir = project();
+    let files = collect_source_files(&[dir.path().to_path_buf()]).unwrap();
+    let reports = Compiler::new().check_files(&files);
+
+    // Results keep input order even though files are checked in parallel
+    let paths: Vec<PathBuf> = reports.iter().map(|r| r.path.clone()).collect();
+    assert_eq!(paths, files);
+
+    assert!(reports[0].diagnostics.is_empty());
+    assert_eq!(reports[1].warning_count(), 1);
+    assert_eq!(reports[1].error_count(), 0);
+    assert!(reports[2].error_count() > 0);
+
+    // Every diagnostic knows which file it came from
+    let c_file = files[2].display().to_string();
+    assert!(reports[2]
+        .diagnostics
+        .iter()
+        .all(|d| d.file.as_deref() == Some(c_file.as_str())));
+}
+
+#[test]
+fn test_diagnostics_are_in_source_order() {
+    // Unused names are collected in hash maps, whose order varies by run
+    let source: String = (0..20).map(|i| format!("name{} = {}\n", i, i)).collect();
+    let lines: Vec<usize> = Compiler::new()
+        .check(&source)
+        .iter()
+        .map(|d| d.span.unwrap().line)
+        .collect();
+    assert_eq!(lines, (1..=20).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_error_cap_applies_across_files() {
+    let dir = TempDir::new().unwrap();
+    fs::write(dir.path().join("a.silk"), "x: int = 'one'\nprint(x)\n").unwrap();
+    fs::write(
+        dir.path().join("b.silk"),
+        "unused = 1\ny: int = 'two'\nprint(y)\n",
+    )
+    .unwrap();
+    fs::write(dir.path().join("c.silk"), "z: int = 'three'\nprint(z)\n").unwrap();
+    let files = collect_source_files(&[dir.path().to_path_buf()]).unwrap();
+
+    let compiler = Compiler::with_options(CompilerOptions::new().with_max_errors(1));
+    let reports = compiler.check_files(&files);
+    let shown: Vec<usize> = reports.iter().map(|r| r.error_count()).collect();
+    let hidden: Vec<usize> = reports.iter().map(|r| r.hidden_errors).collect();
+    assert_eq!(shown, [1, 0, 0]);
+    assert_eq!(hidden, [0, 1, 1]);
+    // Warnings are never capped
+    assert_eq!(reports[1].warning_count(), 1);
+}
+
+#[test]
+fn test_unreadable_file_becomes_diagnostic() {
+    let dir = project();
+    let report = Compiler::new().check_file(&dir.path().join("missing.silk"));
+    assert_eq!(report.diagnostics.len(), 1);
+    assert_eq!(report.diagnostics[0].code, "io-error");
+}
+
+#[test]
+fn test_source_without_file_is_labeled() {
+    let compiler = Compiler::with_options(CompilerOptions::new().with_max_errors(1));
+    let report = compiler.check_source(STDIN_NAME, "print(a)\nprint(b)\n");
+    assert_eq!(report.path, Path::new("<stdin>"));
+    assert_eq!(report.error_count(), 1);
+    assert_eq!(report.hidden_errors, 3);
+    assert_eq!(report.diagnostics[0].file.as_deref(), Some("<stdin>"));
+
+    let report = Compiler::new().check_source(CMDLINE_NAME, "print(1 + 1)");
+    assert!(report.diagnostics.is_empty());
+}
+
+#[test]
+fn test_analyzers_are_thread_safe() {
+    fn assert_send<T: Send>() {}
+    fn assert_sync<T: Sync>() {}
+
+    assert_send::<silk_semantic::SemanticAnalyzer>();
+    assert_send::<silk_semantic::ControlFlowAnalyzer>();
+    assert_sync::<Compiler>();
+}
@@ -0,0 +1,101 @@
+//! Tests for machine-applicable fixes of lint diagnostics
+
+use pretty_assertions::assert_eq;
+use silk_compiler::{apply_fixes, Compiler};
+
+/// Source after applying every fix `silk check` offers
+fn fixed(source: &str) -> String {
+    let diagnostics = Compiler::new().check(source);
+    apply_fixes(source, &diagnostics).0
+}
+
+// ========== UNUSED IMPORTS ==========
+
+#[test]
+fn test_unused_import_statement_is_removed() {
+    assert_eq!(fixed("import os\nprint(1)\n"), "print(1)\n");
+}
+
+#[test]
+fn test_statement_with_several_unused_names_is_removed_once() {
+    let source = "import os, sys\nprint(1)\n";
+    let diagnostics = Compiler::new().check(source);
+    assert_eq!(diagnostics.len(), 2);
+    assert_eq!(
+        apply_fixes(source, &diagnostics),
+        ("print(1)\n".to_string(), 2)
+    );
+}
+
+#[test]
+fn test_unused_name_is_removed_from_the_list() {
+    assert_eq!(
+        fixed("from m import a, b as c\nprint(a)\n"),
+        "from m import a\nprint(a)\n"
+    );
+    assert_eq!(
+        fixed("from m import a, b, c\nprint(a, c)\n"),
+        "from m import a, c\nprint(a, c)\n"
+    );
+    assert_eq!(
+        fixed("from m import a, b\nprint(b)\n"),
+        "from m import b\nprint(b)\n"
+    );
+}
+
+#[test]
+fn test_indented_import_is_removed_with_its_indentation() {
+    let source = "def f():\n    import os\n    return 1\n\nprint(f())\n";
+    assert_eq!(fixed(source), "def f():\n    return 1\n\nprint(f())\n");
+}
+
+#[test]
+fn test_import_on_the_last_line_is_removed() {
+    assert_eq!(fixed("print(1)\nimport os"), "print(1)\n");
+}
+
+#[test]
+fn test_fixes_after_non_ascii_text() {
+    // Offsets count characters, so text before the import must not shift
+    // the edits
+    assert_eq!(
+        fixed("# café\nimport os\nprint('é')\n"),
+        "# café\nprint('é')\n"
+    );
+    assert_eq!(
+        fixed("# 日本\nfrom m import a, b\nprint(a)\n"),
+        "# 日本\nfrom m import a\nprint(a)\n"
+    );
+    assert_eq!(
+        fixed("# é\ndef f():\n    total = 1\n    return 0\n\nprint(f())\n"),
+        "# é\ndef f():\n    _total = 1\n    return 0\n\nprint(f())\n"
+    );
+}
+
+// ========== UNUSED VARIABLES ==========
+
+#[test]
+fn test_unused_variable_is_prefixed() {
+    let source = "def f():\n    total = 1\n    return 0\n\nprint(f())\n";
+    let diagnostics = Compiler::new().check(source);
+    let fix = diagnostics[0].fix.as_ref().unwrap();
+    assert_eq!(fix.message, "rename to '_total'");
+    assert_eq!(
+        apply_fixes(source, &diagnostics).0,
+        "def f():\n    _total = 1\n    return 0\n\nprint(f())\n"
+    );
+}
+
+#[test]
+fn test_fixed_code_checks_cleanly() {
+    let source = "import os\nfrom m import a, b\n\nfor i in [1, 2]:\n    x = a\n";
+    let result = fixed(source);
+    assert_eq!(result, "from m import a\n\nfor _i in [1, 2]:\n    _x = a\n");
+    assert!(Compiler::new().check(&result).is_empty());
+}
+
+#[test]
+fn test_other_diagnostics_have_no_fix() {
+    let diagnostics = Compiler::new().check("print(y)\n");
+    assert!(diagnostics.iter().all(|d| d.fix.is_none()));
+}
@@ -0,0 +1,44 @@
+//! Tests for internal compiler error reports
+
+use silk_compiler::ice::{self, BUG_REPORT_URL, ICE_CODE, VERSION};
+use silk_compiler::{Compiler, Phase, Severity};
+use std::fs;
+
+#[test]
+fn test_panics_become_internal_errors() {
+    assert_eq!(ice::catch(Phase::Parse, || 42), Ok(42));
+
+    let error = ice::catch::<()>(Phase::Semantic, || panic!("boom {}", 1)).unwrap_err();
+    assert_eq!(error.phase, Phase::Semantic);
+    assert_eq!(error.message, "boom 1");
+    assert!(error.location.as_deref().unwrap().contains("test_ice.rs"));
+
+    // Nested catches report to the innermost one
+    let outer = ice::catch(Phase::Lex, || ice::catch(Phase::Parse, || panic!("inner")));
+    assert_eq!(outer.unwrap().unwrap_err().message, "inner");
+}
+
+#[test]
+fn test_report_carries_what_a_bug_report_needs() {
+    let mut error = ice::catch(Phase::ControlFlow, || panic!("oops")).unwrap_err();
+    let program = Compiler::new().parse("x = 1\nprint(x)\n").unwrap();
+    error.dump_ast(&program);
+    let path = error.ast_dump.clone().unwrap();
+    assert!(fs::read_to_string(&path).unwrap().contains("Program"));
+
+    let diagnostic = error.to_diagnostic("x = 1\nprint(x)\n");
+    fs::remove_file(&path).unwrap();
+    assert!(ice::is_ice(&diagnostic));
+    assert_eq!(diagnostic.code, ICE_CODE);
+    assert_eq!(diagnostic.severity, Severity::Error);
+    assert!(diagnostic.message.contains("control-flow phase: oops"));
+    let notes: Vec<&str> = diagnostic
+        .notes
+        .iter()
+        .map(|n| n.message.as_str())
+        .collect();
+    assert!(notes[0].starts_with(&format!("silk {}, panicked at", VERSION)));
+    assert_eq!(notes[1], "while checking 2 line(s) of source");
+    assert!(notes[2].contains(&path.display().to_string()));
+    assert!(notes[3].contains(BUG_REPORT_URL));
+}
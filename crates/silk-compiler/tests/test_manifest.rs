@@ -0,0 +1,214 @@
+//! Tests for the silk.toml project manifest and project scaffolding
+
+use silk_compiler::manifest::{init_project, new_project, MANIFEST_FILE};
+use silk_compiler::{Compiler, LintLevel, Manifest, ManifestError, Severity};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+#[test]
+fn test_parse_full_manifest() {
+    let source = r#"
+[package]
+name = "hello"
+version = "1.2.3"
+entry = "app/start.silk"
+source-dirs = ["app", "lib"]
+
+[lints]
+unused-variable = "allow"
+unused-function = "error"
+"#;
+    let manifest = Manifest::parse(source, PathBuf::from("/project")).unwrap();
+    assert_eq!(manifest.package.name, "hello");
+    assert_eq!(manifest.package.version, "1.2.3");
+    assert_eq!(manifest.entry_path(), Path::new("/project/app/start.silk"));
+    assert_eq!(
+        manifest.source_paths(),
+        [PathBuf::from("/project/app"), PathBuf::from("/project/lib")]
+    );
+    assert_eq!(manifest.lints["unused-variable"], LintLevel::Allow);
+    assert_eq!(manifest.lints["unused-function"], LintLevel::Error);
+}
+
+#[test]
+fn test_defaults() {
+    let source = "[package]\nname = \"hello\"\nversion = \"0.1.0\"\n";
+    let manifest = Manifest::parse(source, PathBuf::from("root")).unwrap();
+    assert_eq!(manifest.package.entry, Path::new("src/main.silk"));
+    assert_eq!(manifest.package.source_dirs, [PathBuf::from("src")]);
+    assert!(manifest.lints.is_empty());
+}
+
+#[test]
+fn test_invalid_manifests() {
+    let missing_name = "[package]\nversion = \"0.1.0\"\n";
+    assert!(matches!(
+        Manifest::parse(missing_name, PathBuf::new()),
+        Err(ManifestError::Invalid { .. })
+    ));
+
+    let unknown_key = "[package]\nname = \"a\"\nversion = \"0.1.0\"\nauthor = \"me\"\n";
+    assert!(Manifest::parse(unknown_key, PathBuf::new()).is_err());
+
+    let bad_level = "[package]\nname = \"a\"\nversion = \"0.1.0\"\n[lints]\nx = \"loud\"\n";
+    assert!(Manifest::parse(bad_level, PathBuf::new()).is_err());
+}
+
+#[test]
+fn test_round_trip() {
+    let mut manifest = Manifest::new("demo", PathBuf::from("root"));
+    manifest
+        .lints
+        .insert("unused-variable".to_string(), LintLevel::Warn);
+    let parsed = Manifest::parse(&manifest.to_toml_string(), PathBuf::from("root")).unwrap();
+    assert_eq!(parsed, manifest);
+}
+
+#[test]
+fn test_apply_lints() {
+    let source = r#"
+[package]
+name = "hello"
+version = "0.1.0"
+
+[lints]
+unused-variable = "allow"
+unused-function = "error"
+"#;
+    let manifest = Manifest::parse(source, PathBuf::new()).unwrap();
+    let diagnostics = Compiler::new().check("x = 1\n\ndef f():\n    pass\n");
+    let diagnostics = manifest.apply_lints(diagnostics);
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].code, "unused-function");
+    assert_eq!(diagnostics[0].severity, Severity::Error);
+}
+
+#[test]
+fn test_lints_allowed_by_default_are_enabled_by_a_level() {
+    let source = r#"
+[package]
+name = "hello"
+version = "0.1.0"
+
+[lints]
+missing-docstring = "error"
+unused-variable = "allow"
+"#;
+    let manifest = Manifest::parse(source, PathBuf::new()).unwrap();
+    assert_eq!(
+        manifest.enabled_lints().collect::<Vec<_>>(),
+        ["missing-docstring"]
+    );
+
+    let program = "def f():\n    return 1\n\nprint(f())\n";
+    assert!(Compiler::new().check(program).is_empty());
+
+    let mut compiler = Compiler::new();
+    for code in manifest.enabled_lints() {
+        compiler.enable_lint(code);
+    }
+    let diagnostics = manifest.apply_lints(compiler.check(program));
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].code, "missing-docstring");
+    assert_eq!(diagnostics[0].severity, Severity::Error);
+}
+
+#[test]
+fn test_new_project_layout() {
+    let dir = TempDir::new().unwrap();
+    let manifest = new_project(dir.path(), "demo", false).unwrap();
+
+    assert_eq!(manifest.root, dir.path().join("demo"));
+    assert!(dir.path().join("demo").join(MANIFEST_FILE).is_file());
+
+    // The generated entry point checks cleanly
+    let main = fs::read_to_string(manifest.entry_path()).unwrap();
+    assert!(Compiler::new().check(&main).is_empty());
+
+    // Creating it again fails
+    assert!(matches!(
+        new_project(dir.path(), "demo", false),
+        Err(ManifestError::AlreadyExists(_))
+    ));
+}
+
+#[test]
+fn test_new_project_inside_a_project() {
+    let dir = TempDir::new().unwrap();
+    new_project(dir.path(), "outer", false).unwrap();
+    let src = dir.path().join("outer").join("src");
+
+    match new_project(&src, "inner", false) {
+        Err(ManifestError::InsideProject(path)) => {
+            assert_eq!(path, dir.path().join("outer").join(MANIFEST_FILE))
+        }
+        other => panic!("Expected InsideProject, got {:?}", other),
+    }
+    assert!(!src.join("inner").exists());
+
+    let manifest = new_project(&src, "inner", true).unwrap();
+    assert_eq!(manifest.root, src.join("inner"));
+}
+
+#[test]
+fn test_new_project_rejects_bad_names() {
+    let dir = TempDir::new().unwrap();
+    for name in ["", "9lives", "has space", "../escape"] {
+        assert!(matches!(
+            new_project(dir.path(), name, false),
+            Err(ManifestError::InvalidName(_))
+        ));
+    }
+}
+
+#[test]
+fn test_init_keeps_existing_entry_point() {
+    let dir = TempDir::new().unwrap();
+    fs::create_dir(dir.path().join("src")).unwrap();
+    fs::write(dir.path().join("src/main.silk"), "print(42)\n").unwrap();
+
+    init_project(dir.path(), "existing", false).unwrap();
+    assert_eq!(
+        fs::read_to_string(dir.path().join("src/main.silk")).unwrap(),
+        "print(42)\n"
+    );
+    assert!(init_project(dir.path(), "existing", false).is_err());
+}
+
+#[test]
+fn test_init_inside_a_project() {
+    let dir = TempDir::new().unwrap();
+    new_project(dir.path(), "outer", false).unwrap();
+    let tools = dir.path().join("outer").join("tools");
+    fs::create_dir(&tools).unwrap();
+
+    match init_project(&tools, "tools", false) {
+        Err(ManifestError::InsideProject(path)) => {
+            assert_eq!(path, dir.path().join("outer").join(MANIFEST_FILE))
+        }
+        other => panic!("Expected InsideProject, got {:?}", other),
+    }
+    assert!(!tools.join(MANIFEST_FILE).exists());
+
+    let manifest = init_project(&tools, "tools", true).unwrap();
+    assert_eq!(manifest.root, tools);
+    assert!(tools.join(MANIFEST_FILE).is_file());
+}
+
+#[test]
+fn test_discover_from_subdirectory() {
+    let dir = TempDir::new().unwrap();
+    new_project(dir.path(), "demo", false).unwrap();
+    let nested = dir.path().join("demo").join("src");
+
+    let manifest = Manifest::discover(&nested).unwrap();
+    assert_eq!(manifest.package.name, "demo");
+    assert_eq!(manifest.root, dir.path().join("demo"));
+
+    assert!(matches!(
+        Manifest::discover(dir.path()),
+        Err(ManifestError::NotFound(_))
+    ));
+}
@@ -0,0 +1,57 @@
+//! Tests for the function metrics report (`silk analyze --metrics`)
+
+use pretty_assertions::assert_eq;
+use silk_compiler::{metrics, Compiler, CompilerOptions, FileMetrics, MetricLimits};
+use std::path::PathBuf;
+
+const SOURCE: &str = r#"
+def classify(values, limit):
+    for value in values:
+        if value > limit and value % 2 == 0:
+            print(value)
+
+class Shape:
+    def area(self, scale):
+        return scale if scale else 1
+"#;
+
+fn measure(compiler: &Compiler) -> Vec<FileMetrics> {
+    let files = ["app.silk", "empty.silk"];
+    let sources = [SOURCE, "print(1)\n"];
+    files
+        .iter()
+        .zip(sources)
+        .map(|(path, source)| compiler.metrics(PathBuf::from(path), source).unwrap())
+        .collect()
+}
+
+#[test]
+fn test_metrics_table() {
+    let compiler = Compiler::new();
+    assert_eq!(
+        metrics::to_table(&measure(&compiler), &compiler.metric_limits()),
+        "\
+app.silk
+  function    line  complexity   nesting   params   lines
+  classify       2           4         2        2       4
+  Shape.area     8           2         0        1       2
+
+empty.silk
+  (no functions)
+"
+    );
+}
+
+#[test]
+fn test_values_above_the_limits_are_marked() {
+    let limits = MetricLimits {
+        complexity: 3,
+        nesting: 4,
+        parameters: 1,
+        lines: 60,
+    };
+    let compiler = Compiler::with_options(CompilerOptions::new().with_metric_limits(limits));
+    let table = metrics::to_table(&measure(&compiler), &compiler.metric_limits());
+    assert!(table.contains("  classify       2           4*        2        2*      4\n"));
+    assert!(table.contains("  Shape.area     8           2         0        1       2\n"));
+}
@@ -0,0 +1,157 @@
+//! Tests for the module graph and circular import detection
+
+use pretty_assertions::assert_eq;
+use silk_compiler::{collect_source_files, Compiler};
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+/// Create a project from (relative path, source) pairs
+fn project(files: &[(&str, &str)]) -> TempDir {
+    let dir = TempDir::new().unwrap();
+    for (path, source) in files {
+        let path = dir.path().join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, source).unwrap();
+    }
+    dir
+}
+
+/// Messages of every `circular-import` diagnostic in the project
+fn cycle_messages(dir: &Path) -> Vec<String> {
+    let files = collect_source_files(&[dir.to_path_buf()]).unwrap();
+    Compiler::new()
+        .check_files(&files)
+        .iter()
+        .flat_map(|report| &report.diagnostics)
+        .filter(|d| d.code == "circular-import")
+        .map(|d| d.message.clone())
+        .collect()
+}
+
+#[test]
+fn test_no_cycle() {
+    let dir = project(&[("a.silk", "import b\nprint(b)\n"), ("b.silk", "x = 1\n")]);
+    assert!(cycle_messages(dir.path()).is_empty());
+}
+
+#[test]
+fn test_three_module_cycle_chain() {
+    let dir = project(&[
+        ("a.silk", "import b\nprint(b)\n"),
+        ("b.silk", "import c\nprint(c)\n"),
+        ("c.silk", "x = 1\nimport a\nprint(a)\n"),
+    ]);
+    assert_eq!(
+        cycle_messages(dir.path()),
+        ["Circular import: a imports b imports c imports a"]
+    );
+}
+
+#[test]
+fn test_cycle_notes_point_at_each_import() {
+    let dir = project(&[
+        ("a.silk", "from b import f\nf()\n"),
+        ("b.silk", "x = 1\nimport a\nprint(a)\n"),
+    ]);
+    let files = collect_source_files(&[dir.path().to_path_buf()]).unwrap();
+    let reports = Compiler::new().check_files(&files);
+
+    // Reported once, on the file where the cycle starts
+    assert_eq!(reports[0].error_count(), 1);
+    assert_eq!(reports[1].error_count(), 0);
+    let diagnostic = &reports[0].diagnostics[0];
+    assert_eq!(diagnostic.span.unwrap().line, 1);
+
+    let notes: Vec<_> = diagnostic
+        .notes
+        .iter()
+        .map(|note| (note.message.as_str(), note.span.unwrap().line))
+        .collect();
+    assert_eq!(
+        notes,
+        [("'a' imports 'b' here", 1), ("'b' imports 'a' here", 2)]
+    );
+    assert!(diagnostic.notes[1]
+        .file
+        .as_ref()
+        .unwrap()
+        .ends_with("b.silk"));
+}
+
+#[test]
+fn test_self_import_is_a_cycle() {
+    let dir = project(&[("a.silk", "import a\nprint(a)\n")]);
+    assert_eq!(cycle_messages(dir.path()), ["Circular import: a imports a"]);
+}
+
+#[test]
+fn test_package_and_relative_imports() {
+    let dir = project(&[
+        ("main.silk", "from pkg import util\nprint(util)\n"),
+        ("pkg/__init__.silk", ""),
+        ("pkg/util.silk", "from . import helpers\nprint(helpers)\n"),
+        ("pkg/helpers.silk", "import pkg.util\nprint(pkg)\n"),
+    ]);
+    assert_eq!(
+        cycle_messages(dir.path()),
+        ["Circular import: util imports helpers imports util"]
+    );
+}
+
+#[test]
+fn test_namespace_package_members_import_from_the_project_root() {
+    // `pkg` has no `__init__.silk`, so `c` finds `a` next to its importer
+    let dir = project(&[
+        ("a.silk", "import b\nprint(b)\n"),
+        ("b.silk", "import pkg.c\nprint(pkg)\n"),
+        ("pkg/c.silk", "import a\nprint(a)\n"),
+    ]);
+    assert_eq!(
+        cycle_messages(dir.path()),
+        ["Circular import: a imports b imports c imports a"]
+    );
+
+    let dir = project(&[
+        ("a.silk", "from pkg.sub import c\nprint(c)\n"),
+        ("pkg/sub/c.silk", "from a import run\nrun()\n"),
+    ]);
+    assert_eq!(
+        cycle_messages(dir.path()),
+        ["Circular import: a imports c imports a"]
+    );
+}
+
+#[test]
+fn test_cycles_have_an_error_code() {
+    let dir = project(&[("a.silk", "import a\nprint(a)\n")]);
+    let files = collect_source_files(&[dir.path().to_path_buf()]).unwrap();
+    let reports = Compiler::new().check_files(&files);
+    let cycle = reports[0]
+        .diagnostics
+        .iter()
+        .find(|d| d.code == "circular-import")
+        .unwrap();
+    assert_eq!(cycle.error_code.as_deref(), Some("E0206"));
+}
+
+#[test]
+fn test_function_level_and_type_checking_imports_are_ignored() {
+    let dir = project(&[
+        ("a.silk", "import b\nprint(b)\n"),
+        (
+            "b.silk",
+            "if TYPE_CHECKING:\n    import a\n\ndef f():\n    import a\n    return a\n",
+        ),
+    ]);
+    assert!(cycle_messages(dir.path()).is_empty());
+}
+
+#[test]
+fn test_unresolved_imports_are_ignored() {
+    let dir = project(&[("a.silk", "import os\nimport numpy as np\nprint(os, np)\n")]);
+    let files = collect_source_files(&[dir.path().to_path_buf()]).unwrap();
+    let graph = Compiler::new().module_graph(&files);
+    assert!(graph.imports(&files[0]).is_empty());
+    assert!(graph.cycles().is_empty());
+}
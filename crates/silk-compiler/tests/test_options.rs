@@ -0,0 +1,128 @@
+//! Tests for configuring the compiler through `CompilerOptions`
+
+use silk_compiler::{
+    Compiler, CompilerOptions, Feature, FeatureSet, LintLevel, Manifest, Severity,
+};
+use std::path::PathBuf;
+
+#[test]
+fn test_lint_levels_apply_to_check() {
+    let source = "def f():\n    x = 1\n    return 2\n\nprint(f())\n";
+    let codes = |compiler: &Compiler| -> Vec<(String, Severity)> {
+        compiler
+            .check(source)
+            .into_iter()
+            .map(|d| (d.code, d.severity))
+            .collect()
+    };
+
+    assert_eq!(
+        codes(&Compiler::new()),
+        [("unused-variable".to_string(), Severity::Warning)]
+    );
+
+    let options = CompilerOptions::new()
+        .with_lint("unused-variable", LintLevel::Error)
+        .with_lint("missing-docstring", LintLevel::Warn);
+    assert_eq!(
+        codes(&Compiler::with_options(options)),
+        [
+            ("missing-docstring".to_string(), Severity::Warning),
+            ("unused-variable".to_string(), Severity::Error),
+        ]
+    );
+
+    let options = CompilerOptions::new().with_lint("unused-variable", LintLevel::Allow);
+    assert!(codes(&Compiler::with_options(options)).is_empty());
+}
+
+#[test]
+fn test_optional_access_is_a_lint() {
+    let source = "def f(text: str | None) -> str:\n    return text.upper()\n\nprint(f(None))\n";
+    let severities = |options: CompilerOptions| -> Vec<Severity> {
+        Compiler::with_options(options)
+            .check(source)
+            .into_iter()
+            .filter(|d| d.code == "optional-access")
+            .map(|d| d.severity)
+            .collect()
+    };
+
+    assert_eq!(severities(CompilerOptions::new()), [Severity::Warning]);
+    let options = CompilerOptions::new().with_lint("optional-access", LintLevel::Error);
+    assert_eq!(severities(options), [Severity::Error]);
+    let options = CompilerOptions::new().with_lint("optional-access", LintLevel::Allow);
+    assert!(severities(options).is_empty());
+}
+
+#[test]
+fn test_max_errors_keeps_the_first_errors_and_all_warnings() {
+    let source = "unused = 1\nprint(a)\nprint(b)\nprint(c)\n";
+    let compiler = Compiler::with_options(CompilerOptions::new().with_max_errors(2));
+    let diagnostics = compiler.check(source);
+
+    // 'a' is both undefined and uninitialized; those two errors come first
+    let errors: Vec<_> = diagnostics.iter().filter(|d| d.is_error()).collect();
+    assert_eq!(errors.len(), 2);
+    assert!(errors.iter().all(|d| d.message.contains("'a'")));
+    assert!(diagnostics.iter().any(|d| d.code == "unused-variable"));
+}
+
+#[test]
+fn test_manifest_lints_do_not_override_explicit_levels() {
+    let manifest = Manifest::parse(
+        r#"
+[package]
+name = "demo"
+version = "0.1.0"
+
+[lints]
+unused-variable = "allow"
+unused-function = "error"
+"#,
+        PathBuf::new(),
+    )
+    .unwrap();
+
+    let mut options = CompilerOptions::new().with_lint("unused-variable", LintLevel::Error);
+    options.merge_manifest(&manifest);
+    assert_eq!(options.lints["unused-variable"], LintLevel::Error);
+    assert_eq!(options.lints["unused-function"], LintLevel::Error);
+    assert_eq!(
+        options.enabled_lints().collect::<Vec<_>>(),
+        ["unused-function", "unused-variable"]
+    );
+}
+
+#[test]
+fn test_compiler_constructors_share_the_options() {
+    let mut features = FeatureSet::new();
+    features.enable(Feature::ComprehensionAssignments);
+    let compiler = Compiler::with_features(features.clone());
+    assert_eq!(
+        compiler.options(),
+        &CompilerOptions::new().with_features(features)
+    );
+
+    let mut compiler = Compiler::new();
+    compiler.enable_lint("missing-docstring");
+    compiler
+        .options_mut()
+        .lints
+        .insert("unused-variable".to_string(), LintLevel::Allow);
+    compiler.enable_lint("unused-variable");
+    assert_eq!(
+        compiler.options().lints["missing-docstring"],
+        LintLevel::Warn
+    );
+    assert_eq!(
+        compiler.options().lints["unused-variable"],
+        LintLevel::Allow
+    );
+
+    let options = CompilerOptions::new()
+        .with_opt_level(9)
+        .with_target("wasm32-unknown-unknown");
+    assert_eq!(options.opt_level, 3);
+    assert_eq!(options.target.as_deref(), Some("wasm32-unknown-unknown"));
+}
@@ -0,0 +1,89 @@
+//! Tests for memoized compiler queries
+
+use silk_compiler::{Compiler, CompilerOptions, Database, LintLevel, Query};
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+const SOURCE: &str = "def f():\n    x = 1\n    return 2\n\nprint(f() + y)\n";
+
+#[test]
+fn test_queries_are_reused_until_the_text_changes() {
+    let compiler = Compiler::new();
+    let mut database = Database::new(compiler.clone());
+    let path = Path::new("main.silk");
+    assert!(database.diagnostics(path).is_none());
+
+    assert!(database.set_file_text(path, SOURCE));
+    let checked = database.diagnostics(path).unwrap();
+    assert_eq!(checked.diagnostics, compiler.check_timed(SOURCE).0);
+    database.diagnostics(path).unwrap();
+    database.typed(path).unwrap();
+    assert_eq!(database.executions(Query::Tokens), 1);
+    assert_eq!(database.executions(Query::Parse), 1);
+    assert_eq!(database.executions(Query::Diagnostics), 1);
+
+    // The same text keeps the results
+    assert!(!database.set_file_text(path, SOURCE));
+    database.diagnostics(path).unwrap();
+    assert_eq!(database.executions(Query::Diagnostics), 1);
+
+    assert!(database.set_file_text(path, "print(("));
+    assert!(database.typed(path).is_none());
+    assert_eq!(database.diagnostics(path).unwrap().diagnostics.len(), 1);
+    assert_eq!(database.executions(Query::Parse), 2);
+    assert_eq!(database.executions(Query::Typed), 2);
+}
+
+#[test]
+fn test_other_options_drop_the_results() {
+    let mut database = Database::new(Compiler::new());
+    let path = Path::new("main.silk");
+    database.set_file_text(path, SOURCE);
+    database.diagnostics(path).unwrap();
+
+    database.set_compiler(Compiler::new());
+    database.diagnostics(path).unwrap();
+    assert_eq!(database.executions(Query::Diagnostics), 1);
+
+    let options = CompilerOptions::new().with_lint("unused-variable", LintLevel::Allow);
+    database.set_compiler(Compiler::with_options(options));
+    let checked = database.diagnostics(path).unwrap();
+    assert!(checked
+        .diagnostics
+        .iter()
+        .all(|d| d.code != "unused-variable"));
+    assert_eq!(database.executions(Query::Diagnostics), 2);
+}
+
+#[test]
+fn test_check_files_only_checks_changed_files() {
+    let dir = TempDir::new().unwrap();
+    let files = [dir.path().join("a.silk"), dir.path().join("b.silk")];
+    for file in &files {
+        fs::write(file, SOURCE).unwrap();
+    }
+    let compiler = Compiler::new();
+    let mut database = Database::new(compiler.clone());
+    let messages = |reports: &[silk_compiler::FileDiagnostics]| -> Vec<Vec<String>> {
+        reports
+            .iter()
+            .map(|r| r.diagnostics.iter().map(|d| d.to_string()).collect())
+            .collect()
+    };
+
+    let reports = database.check_files(&files);
+    assert_eq!(messages(&reports), messages(&compiler.check_files(&files)));
+    assert_eq!(database.executions(Query::Diagnostics), 2);
+
+    fs::write(&files[1], "print(1)\n").unwrap();
+    let reports = database.check_files(&files);
+    assert_eq!(messages(&reports), messages(&compiler.check_files(&files)));
+    assert!(reports[1].diagnostics.is_empty());
+    assert_eq!(database.executions(Query::Diagnostics), 3);
+
+    fs::remove_file(&files[0]).unwrap();
+    let reports = database.check_files(&files);
+    assert_eq!(reports[0].diagnostics[0].code, "io-error");
+    assert_eq!(database.file_text(&files[0]), None);
+}
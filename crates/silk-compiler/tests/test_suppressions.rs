@@ -0,0 +1,90 @@
+//! Tests for `# noqa` and `# silk: ignore` suppression comments
+
+use silk_compiler::{Compiler, CompilerOptions, LintLevel, Suppressions};
+
+/// Codes and lines of the diagnostics of a source
+fn check(source: &str) -> Vec<(String, usize)> {
+    Compiler::new()
+        .check(source)
+        .into_iter()
+        .map(|d| (d.code, d.span.unwrap().line))
+        .collect()
+}
+
+fn reported(code: &str, line: usize) -> (String, usize) {
+    (code.to_string(), line)
+}
+
+#[test]
+fn test_trailing_comment_silences_its_line() {
+    let source =
+        "a = 1  # noqa\nb = 2  # noqa: unused-variable\nc = 3  # silk: ignore[E0500]\nd = 4\n";
+    assert_eq!(check(source), [reported("unused-variable", 4)]);
+}
+
+#[test]
+fn test_comment_on_its_own_line_silences_the_next_statement_and_block() {
+    let source = "\
+# silk: ignore[unused-function, unused-variable]
+
+def helper():
+    z = 3
+    return 1
+
+# noqa
+@staticmethod
+def other(flag):
+    if flag:
+        pass
+    else:
+        w = 4
+
+x = 1
+";
+    assert_eq!(check(source), [reported("unused-variable", 15)]);
+}
+
+#[test]
+fn test_errors_are_never_suppressed() {
+    let diagnostics = check("print(q)  # noqa\n");
+    assert!(diagnostics.contains(&reported("undefined-variable", 1)));
+    // Nothing was silenced, so the comment is reported too
+    assert!(diagnostics.contains(&reported("unused-suppression", 1)));
+}
+
+#[test]
+fn test_stale_codes_are_reported() {
+    let source = "x = 1  # noqa: unused-variable, unused-import\n";
+    let diagnostics = Compiler::new().check(source);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].code, "unused-suppression");
+    assert_eq!(diagnostics[0].error_code.as_deref(), Some("E0517"));
+    assert!(diagnostics[0].message.contains("'unused-import'"));
+
+    // The meta-lint follows lint levels like any other
+    let options = CompilerOptions::new().with_lint("unused-suppression", LintLevel::Allow);
+    assert!(Compiler::with_options(options).check(source).is_empty());
+}
+
+#[test]
+fn test_directives_are_parsed() {
+    let source = "\
+a = 1  # type: int  # NOQA: E0500 because the value is logged
+b = '# noqa'
+c = 1  # noqa-like comment
+d = 1  #silk:ignore[ E0500 ,unused-variable ]
+";
+    let compiler = Compiler::new();
+    let program = compiler.parse(source).unwrap();
+    let found: Vec<(usize, Vec<String>)> = Suppressions::new(source, &program)
+        .iter()
+        .map(|s| (s.span.line, s.codes.clone()))
+        .collect();
+    assert_eq!(
+        found,
+        [
+            (1, vec!["E0500".to_string()]),
+            (4, vec!["E0500".to_string(), "unused-variable".to_string()]),
+        ]
+    );
+}
@@ -0,0 +1,188 @@
+//! Error codes and their explanations
+//!
+//! Every kind of lexer, parser and semantic error has a stable code such as
+//! `E0200`, printed next to its message. `silk explain E0200` prints the
+//! extended explanation, which shows an erroneous example and how to fix
+//! it. Codes are grouped by phase: lexer errors from E0001, parser errors
+//! from E0100, and semantic errors from E0200. A code is never reused, so
+//! codes that are no longer reported keep their entry.
+//!
+//! The explanations are the Markdown files in `src/error_codes/`. Examples
+//! in ```` ```silk,error ```` blocks must report their code, and the other
+//! ```` ```silk ```` blocks must not; the compiler's test suite checks both.
+//! An example of several files starts each of them with a `# file: <name>`
+//! line.
+
+/// An error code with its explanation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ErrorCode {
+    /// The code, e.g. `E0200`
+    pub code: &'static str,
+    /// Kebab-case name of the error kind, e.g. `undefined-variable`
+    pub name: &'static str,
+    /// Whether this is a lint: a warning by default, whose level can be
+    /// changed with `-A`/`-W`/`-D` or a `[lints]` table
+    pub lint: bool,
+    /// Markdown explanation; the first line summarizes it
+    pub explanation: &'static str,
+}
+
+impl ErrorCode {
+    const fn new(
+        code: &'static str,
+        name: &'static str,
+        lint: bool,
+        explanation: &'static str,
+    ) -> Self {
+        Self {
+            code,
+            name,
+            lint,
+            explanation,
+        }
+    }
+
+    /// First line of the explanation, after the note on codes that are
+    /// no longer emitted
+    pub fn summary(&self) -> &'static str {
+        self.explanation
+            .lines()
+            .find(|line| !line.is_empty() && !line.starts_with("#### Note:"))
+            .unwrap_or_default()
+    }
+
+    /// Whether the compiler still reports this code
+    pub fn is_emitted(&self) -> bool {
+        !self.explanation.starts_with("#### Note:")
+    }
+}
+
+/// Define `ERROR_CODES`; the explanation of a code is read from
+/// `error_codes/<code>.md`, and lints are marked `lint`
+macro_rules! error_codes {
+    ($($code:ident: $name:literal $($lint:ident)?,)*) => {
+        /// Every error code, in order
+        pub const ERROR_CODES: &[ErrorCode] = &[$(
+            ErrorCode::new(
+                stringify!($code),
+                $name,
+                is_lint!($($lint)?),
+                include_str!(concat!("error_codes/", stringify!($code), ".md")),
+            ),
+        )*];
+    };
+}
+
+macro_rules! is_lint {
+    () => {
+        false
+    };
+    (lint) => {
+        true
+    };
+}
+
+error_codes! {
+    E0001: "unexpected-character",
+    E0002: "unterminated-string",
+    E0003: "invalid-number",
+    E0004: "indentation-error",
+    E0005: "invalid-escape",
+    E0006: "invalid-unicode-escape",
+    E0007: "invalid-fstring",
+    E0008: "invalid-byte-string",
+    E0009: "unexpected-eof-in-token",
+    E0100: "unexpected-token",
+    E0101: "unexpected-eof",
+    E0102: "invalid-syntax",
+    E0103: "invalid-expression",
+    E0104: "invalid-statement",
+    E0105: "inconsistent-block-indentation",
+    E0106: "invalid-pattern",
+    E0107: "lambda-non-default-after-default",
+    E0108: "feature-not-enabled",
+    E0109: "misspelled-keyword",
+    E0110: "nesting-too-deep",
+    E0111: "invalid-feature-pragma",
+    E0200: "undefined-variable",
+    E0201: "redefined-variable",
+    E0202: "undefined-function",
+    E0203: "undefined-class",
+    E0204: "invalid-scope",
+    E0205: "unknown-module-member",
+    E0206: "circular-import",
+    E0300: "type-mismatch",
+    E0301: "assignment-type-mismatch",
+    E0302: "argument-type-mismatch",
+    E0303: "return-type-mismatch",
+    E0304: "invalid-binary-operation",
+    E0305: "invalid-unary-operation",
+    E0306: "invalid-subscript",
+    E0307: "division-by-zero",
+    E0308: "integer-overflow",
+    E0309: "shift-out-of-range",
+    E0310: "argument-count-mismatch",
+    E0311: "optional-access" lint,
+    E0312: "unknown-attribute",
+    E0313: "invalid-format",
+    E0314: "invalid-regex",
+    E0315: "unhashable-type",
+    E0400: "break-outside-loop",
+    E0401: "continue-outside-loop",
+    E0402: "return-outside-function",
+    E0403: "unreachable-code" lint,
+    E0404: "uninitialized-variable",
+    E0405: "missing-return",
+    E0406: "infinite-loop",
+    E0407: "dead-code" lint,
+    E0408: "infinite-recursion" lint,
+    E0409: "jump-out-of-except-star",
+    E0500: "unused-variable" lint,
+    E0501: "unused-function" lint,
+    E0502: "unused-import" lint,
+    E0503: "unused-parameter" lint,
+    E0504: "shadowed-name" lint,
+    E0505: "reassigned-definition" lint,
+    E0506: "constant-condition" lint,
+    E0507: "self-comparison" lint,
+    E0508: "incompatible-comparison" lint,
+    E0509: "duplicate-branch" lint,
+    E0510: "missing-docstring" lint,
+    E0511: "undocumented-parameter" lint,
+    E0512: "unknown-documented-parameter" lint,
+    E0513: "undocumented-return" lint,
+    E0514: "mutable-default" lint,
+    E0515: "confusable-identifier" lint,
+    E0516: "implicit-string-concatenation" lint,
+    E0517: "unused-suppression" lint,
+    E0518: "duplicate-code" lint,
+    E0519: "complex-function" lint,
+    E0520: "import-side-effect" lint,
+    E0600: "invalid-method-receiver" lint,
+    E0601: "static-method-receiver" lint,
+    E0602: "inconsistent-mro",
+    E0603: "super-outside-class",
+    E0604: "invalid-property-signature",
+    E0605: "positional-only-argument",
+    E0606: "keyword-only-argument",
+    E0607: "unexpected-keyword-argument",
+    E0608: "duplicate-argument",
+    E0609: "missing-argument",
+    E0610: "non-default-after-default",
+    E0611: "impure-function",
+}
+
+/// Look up a code, ignoring case (`E0200`, `e0200`, or just `0200` and
+/// `200`), or the name of its error kind (`undefined-variable`)
+pub fn lookup(query: &str) -> Option<&'static ErrorCode> {
+    let query = query.trim();
+    let digits = query
+        .strip_prefix(['E', 'e'])
+        .unwrap_or(query)
+        .parse::<u32>()
+        .ok();
+    ERROR_CODES.iter().find(|entry| match digits {
+        Some(number) => entry.code[1..].parse() == Ok(number),
+        None => entry.name == query,
+    })
+}
@@ -0,0 +1,326 @@
+/// Silk diagnostics
+///
+/// Compiler-phase-independent representation of errors and warnings, shared by
+/// every renderer (terminal output, JSON lines for CI and editor plugins).
+pub mod codes;
+mod render;
+
+use silk_lexer::Span;
+use std::fmt;
+
+pub use codes::{ErrorCode, ERROR_CODES};
+pub use render::{render_snippet, ColorChoice, Renderer, MAX_SNIPPET_LINES};
+pub use silk_lexer::{SnippetLine, SourceFile, SourceMap};
+
+/// How serious a diagnostic is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// Suspicious code that does not stop compilation
+    Warning,
+    /// Invalid code; compilation fails
+    Error,
+}
+
+impl Severity {
+    /// Lowercase name used in machine-readable output
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// A secondary location that explains a diagnostic (e.g. each import in a
+/// circular import chain)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Note {
+    /// Human-readable explanation of this location
+    pub message: String,
+    /// File the note points into, if known
+    pub file: Option<String>,
+    /// Location in the file
+    pub span: Option<Span>,
+}
+
+impl Note {
+    /// Create a note pointing at `span` in `file`
+    pub fn new(message: impl Into<String>, file: Option<String>, span: Option<Span>) -> Self {
+        Self {
+            message: message.into(),
+            file,
+            span,
+        }
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "message": self.message,
+            "file": self.file,
+            "span": self.span.map(span_json),
+        })
+    }
+}
+
+/// A single text replacement: `span` (character offsets `start..end`,
+/// like every `Span`) is replaced by `replacement`; an empty span inserts, an empty replacement
+/// deletes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Edit {
+    /// Source range to replace
+    pub span: Span,
+    /// Text to put in its place
+    pub replacement: String,
+}
+
+impl Edit {
+    /// Replace `span` with `replacement`
+    pub fn replace(span: Span, replacement: impl Into<String>) -> Self {
+        Self {
+            span,
+            replacement: replacement.into(),
+        }
+    }
+
+    /// Delete the text covered by `span`
+    pub fn delete(span: Span) -> Self {
+        Self::replace(span, "")
+    }
+
+    /// Insert `text` at the start of `span`
+    pub fn insert_before(span: Span, text: impl Into<String>) -> Self {
+        let at = Span::new(span.start, span.start, span.line, span.column);
+        Self::replace(at, text)
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "span": span_json(self.span),
+            "replacement": self.replacement,
+        })
+    }
+}
+
+/// A machine-applicable fix for a diagnostic: edits that are safe to apply
+/// without review
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fix {
+    /// What the fix does (e.g. "remove the unused import")
+    pub message: String,
+    /// Edits to the diagnostic's file; they never overlap
+    pub edits: Vec<Edit>,
+}
+
+impl Fix {
+    /// Create a fix made of `edits`
+    pub fn new(message: impl Into<String>, edits: Vec<Edit>) -> Self {
+        Self {
+            message: message.into(),
+            edits,
+        }
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "message": self.message,
+            "edits": self.edits.iter().map(Edit::to_json).collect::<Vec<_>>(),
+        })
+    }
+}
+
+/// Apply the fixes of `diagnostics` to `source`
+///
+/// Fixes are applied whole or not at all: a fix with an edit overlapping an
+/// edit of an earlier fix is skipped, except that identical edits (several
+/// unused names of one import statement all deleting the statement) are
+/// applied once. Returns the new source and the number of diagnostics
+/// fixed.
+pub fn apply_fixes<'a>(
+    source: &str,
+    diagnostics: impl IntoIterator<Item = &'a Diagnostic>,
+) -> (String, usize) {
+    let file = SourceFile::new("", source);
+    let mut accepted: Vec<&Edit> = Vec::new();
+    let mut fixed = 0;
+
+    for fix in diagnostics.into_iter().filter_map(|d| d.fix.as_ref()) {
+        let overlaps = |edit: &Edit| {
+            accepted.iter().any(|other| {
+                *other != edit
+                    && edit.span.start < other.span.end.max(other.span.start + 1)
+                    && other.span.start < edit.span.end.max(edit.span.start + 1)
+            })
+        };
+        let in_bounds = |edit: &Edit| {
+            edit.span.start <= edit.span.end && file.byte_offset(edit.span.end).is_some()
+        };
+        if fix
+            .edits
+            .iter()
+            .any(|edit| overlaps(edit) || !in_bounds(edit))
+        {
+            continue;
+        }
+        for edit in &fix.edits {
+            if !accepted.contains(&edit) {
+                accepted.push(edit);
+            }
+        }
+        fixed += 1;
+    }
+
+    // Apply from the end so earlier offsets stay valid
+    accepted.sort_by_key(|edit| std::cmp::Reverse((edit.span.start, edit.span.end)));
+    let mut result = source.to_string();
+    for edit in accepted {
+        // Spans count characters; `replace_range` takes bytes
+        let start = file.byte_offset(edit.span.start).unwrap_or(source.len());
+        let end = file.byte_offset(edit.span.end).unwrap_or(source.len());
+        result.replace_range(start..end, &edit.replacement);
+    }
+    (result, fixed)
+}
+
+/// A single error or warning reported by any compiler phase
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    /// Stable identifier of the diagnostic kind (e.g. `undefined-variable`)
+    pub code: String,
+    /// Code explained by `silk explain` (e.g. `E0200`), for diagnostics of
+    /// the parser and the semantic analyzers
+    pub error_code: Option<String>,
+    /// Error or warning
+    pub severity: Severity,
+    /// Human-readable message
+    pub message: String,
+    /// File the diagnostic belongs to, if known
+    pub file: Option<String>,
+    /// Location in the file (None for errors such as unexpected end of file)
+    pub span: Option<Span>,
+    /// Secondary locations, in order
+    pub notes: Vec<Note>,
+    /// Machine-applicable fix, applied by `silk check --fix`
+    pub fix: Option<Fix>,
+}
+
+impl Diagnostic {
+    /// Create an error diagnostic
+    pub fn error(code: impl Into<String>, message: impl Into<String>, span: Option<Span>) -> Self {
+        Self {
+            code: code.into(),
+            error_code: None,
+            severity: Severity::Error,
+            message: message.into(),
+            file: None,
+            span,
+            notes: Vec::new(),
+            fix: None,
+        }
+    }
+
+    /// Create a warning diagnostic
+    pub fn warning(
+        code: impl Into<String>,
+        message: impl Into<String>,
+        span: Option<Span>,
+    ) -> Self {
+        Self {
+            severity: Severity::Warning,
+            ..Self::error(code, message, span)
+        }
+    }
+
+    /// Attach the code `silk explain` explains
+    pub fn with_error_code(mut self, error_code: impl Into<String>) -> Self {
+        self.error_code = Some(error_code.into());
+        self
+    }
+
+    /// Attach the file the diagnostic belongs to
+    pub fn with_file(mut self, file: impl Into<String>) -> Self {
+        self.file = Some(file.into());
+        self
+    }
+
+    /// Attach a secondary location
+    pub fn with_note(mut self, note: Note) -> Self {
+        self.notes.push(note);
+        self
+    }
+
+    /// Attach a machine-applicable fix
+    pub fn with_fix(mut self, fix: Fix) -> Self {
+        self.fix = Some(fix);
+        self
+    }
+
+    /// Check if this diagnostic fails compilation
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+
+    /// Convert to a JSON value
+    ///
+    /// ```json
+    /// {"code":"undefined-variable","error_code":"E0200","severity":"error",
+    ///  "message":"...",
+    ///  "file":"main.silk","span":{"start":4,"end":5,"line":1,"column":5},
+    ///  "notes":[],"fix":null}
+    /// ```
+    ///
+    /// A fix is rendered as
+    /// `{"message":"...","edits":[{"span":{...},"replacement":"..."}]}`.
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "code": self.code,
+            "error_code": self.error_code,
+            "severity": self.severity.as_str(),
+            "message": self.message,
+            "file": self.file,
+            "span": self.span.map(span_json),
+            "notes": self.notes.iter().map(Note::to_json).collect::<Vec<_>>(),
+            "fix": self.fix.as_ref().map(Fix::to_json),
+        })
+    }
+
+    /// Render as a single JSON line (no trailing newline)
+    pub fn to_json_line(&self) -> String {
+        self.to_json().to_string()
+    }
+}
+
+/// Sort diagnostics by file, then by line and column, so output does not
+/// depend on the order in which the analyses found the problems
+///
+/// The sort is stable: diagnostics at the same position keep their order.
+/// Diagnostics without a position come first within their file.
+pub fn sort_diagnostics(diagnostics: &mut [Diagnostic]) {
+    diagnostics.sort_by(|a, b| {
+        let key = |d: &Diagnostic| (d.file.clone(), d.span.map(|span| (span.line, span.column)));
+        key(a).cmp(&key(b))
+    });
+}
+
+fn span_json(span: Span) -> serde_json::Value {
+    serde_json::json!({
+        "start": span.start,
+        "end": span.end,
+        "line": span.line,
+        "column": span.column,
+    })
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{}: ", file)?;
+        }
+        write!(f, "{}[{}]: {}", self.severity, self.code, self.message)
+    }
+}
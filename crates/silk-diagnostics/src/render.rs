@@ -0,0 +1,248 @@
+/// Terminal rendering of diagnostics
+///
+/// `silk check` prints each diagnostic as a marker and message, the source
+/// lines it points at with the span underlined, and its notes. With colors
+/// on, errors are red, warnings yellow and notes cyan, and the span is
+/// underlined in the source line as well as marked with `^`.
+use crate::{Diagnostic, Note, Severity, SourceFile};
+use silk_lexer::Span;
+use std::fmt;
+use std::str::FromStr;
+
+/// Most source lines shown for one span
+pub const MAX_SNIPPET_LINES: usize = 3;
+
+const RESET: &str = "\x1b[0m";
+const BOLD: &str = "\x1b[1m";
+const RED: &str = "\x1b[1;31m";
+const YELLOW: &str = "\x1b[1;33m";
+const CYAN: &str = "\x1b[1;36m";
+const BLUE: &str = "\x1b[1;34m";
+const UNDERLINE: &str = "\x1b[4m";
+
+/// When to use ANSI colors (`--color`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorChoice {
+    /// Color terminals, unless `NO_COLOR` is set
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    /// Whether to color output written to a stream, following the
+    /// environment's `NO_COLOR`
+    pub fn use_color(self, is_terminal: bool) -> bool {
+        let no_color = std::env::var("NO_COLOR").ok();
+        self.resolve(is_terminal, no_color.as_deref())
+    }
+
+    /// Whether to color output given whether the stream is a terminal and
+    /// the value of `NO_COLOR`; an empty `NO_COLOR` does not count
+    pub fn resolve(self, is_terminal: bool, no_color: Option<&str>) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => is_terminal && no_color.is_none_or(str::is_empty),
+        }
+    }
+}
+
+impl FromStr for ColorChoice {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(ColorChoice::Auto),
+            "always" => Ok(ColorChoice::Always),
+            "never" => Ok(ColorChoice::Never),
+            _ => Err(format!(
+                "invalid color choice '{}' (expected auto, always or never)",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for ColorChoice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ColorChoice::Auto => "auto",
+            ColorChoice::Always => "always",
+            ColorChoice::Never => "never",
+        })
+    }
+}
+
+/// Renders diagnostics for a terminal, with or without colors
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Renderer {
+    color: bool,
+}
+
+impl Renderer {
+    pub fn new(color: bool) -> Self {
+        Self { color }
+    }
+
+    /// A renderer without colors
+    pub fn plain() -> Self {
+        Self::new(false)
+    }
+
+    pub fn is_colored(&self) -> bool {
+        self.color
+    }
+
+    /// `✗` for errors, `⚠` for warnings
+    pub fn marker(&self, severity: Severity) -> String {
+        match severity {
+            Severity::Error => self.paint(RED, "✗"),
+            Severity::Warning => self.paint(YELLOW, "⚠"),
+        }
+    }
+
+    /// A diagnostic with its snippet (when `source` is the file it belongs
+    /// to) and notes; each line ends with a newline
+    ///
+    /// ```text
+    ///   ✗ [E0200] Undefined variable 'count' at line 2, column 9
+    ///       2 | total = count + 1
+    ///         |         ^^^^^
+    ///       note: main.silk:1:1: ...
+    /// ```
+    pub fn render(&self, diagnostic: &Diagnostic, source: Option<&SourceFile>) -> String {
+        let code = match &diagnostic.error_code {
+            Some(code) => format!(
+                "{} ",
+                self.severity(diagnostic.severity, &format!("[{}]", code))
+            ),
+            None => String::new(),
+        };
+        let mut out = format!(
+            "  {} {}{}\n",
+            self.marker(diagnostic.severity),
+            code,
+            self.paint(BOLD, &diagnostic.message)
+        );
+        if let (Some(span), Some(file)) = (diagnostic.span, source) {
+            for line in self.snippet(file, span, diagnostic.severity).lines() {
+                out.push_str(&format!("      {}\n", line));
+            }
+        }
+        for note in &diagnostic.notes {
+            out.push_str(&format!("      {}\n", self.note(note)));
+        }
+        out
+    }
+
+    /// The source lines covered by `span`, with the covered part
+    /// underlined in the color of `severity`
+    ///
+    /// ```text
+    /// 3 |     total = count + 1
+    ///   |             ^^^^^
+    /// ```
+    ///
+    /// Spans over more than `MAX_SNIPPET_LINES` lines (a whole function)
+    /// show their first lines and `...`. Each line ends with a newline;
+    /// empty if the span is outside the file.
+    pub fn snippet(&self, file: &SourceFile, span: Span, severity: Severity) -> String {
+        let style = match severity {
+            Severity::Error => RED,
+            Severity::Warning => YELLOW,
+        };
+        let mut lines = file.snippet(span);
+        let truncated = lines.len() > MAX_SNIPPET_LINES;
+        lines.truncate(MAX_SNIPPET_LINES);
+        let gutter = lines.last().map_or(0, |last| last.line.to_string().len());
+        let bar = |number: &str| self.paint(BLUE, &format!("{:>gutter$} |", number));
+
+        let mut out = String::new();
+        for line in lines {
+            let (before, inside, after) = split_at_columns(&line.text, &line.highlight);
+            let text = if self.color {
+                format!(
+                    "{}{}{}",
+                    before,
+                    self.paint_all(&[UNDERLINE, style], inside),
+                    after
+                )
+            } else {
+                line.text.clone()
+            };
+            let source = format!("{} {}", bar(&line.line.to_string()), text);
+            out.push_str(source.trim_end());
+            out.push('\n');
+            out.push_str(&format!(
+                "{} {}{}\n",
+                bar(""),
+                " ".repeat(line.highlight.start),
+                self.paint(style, &"^".repeat(line.highlight.len()))
+            ));
+        }
+        if truncated {
+            out.push_str(&format!("{} ...\n", bar("")));
+        }
+        out
+    }
+
+    /// `note: file:line:column: message`
+    pub fn note(&self, note: &Note) -> String {
+        let location = match (&note.file, note.span) {
+            (Some(file), Some(span)) => format!("{}:{}:{}: ", file, span.line, span.column),
+            (Some(file), None) => format!("{}: ", file),
+            _ => String::new(),
+        };
+        format!("{} {}{}", self.paint(CYAN, "note:"), location, note.message)
+    }
+
+    /// `text` in the color of `severity`
+    pub fn severity(&self, severity: Severity, text: &str) -> String {
+        match severity {
+            Severity::Error => self.paint(RED, text),
+            Severity::Warning => self.paint(YELLOW, text),
+        }
+    }
+
+    fn paint(&self, style: &str, text: &str) -> String {
+        self.paint_all(&[style], text)
+    }
+
+    fn paint_all(&self, styles: &[&str], text: &str) -> String {
+        if self.color && !text.is_empty() {
+            format!("{}{}{}", styles.concat(), text, RESET)
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+/// Split a line at two display columns
+fn split_at_columns<'a>(
+    text: &'a str,
+    columns: &std::ops::Range<usize>,
+) -> (&'a str, &'a str, &'a str) {
+    let mut width = 0;
+    let mut start = text.len();
+    let mut end = text.len();
+    for (index, c) in text.char_indices() {
+        if width >= columns.start && start == text.len() {
+            start = index;
+        }
+        if width >= columns.end {
+            end = index;
+            break;
+        }
+        width += silk_lexer::source_map::char_width(c);
+    }
+    let end = end.max(start);
+    (&text[..start], &text[start..end], &text[end..])
+}
+
+/// The source lines covered by `span` with the covered part marked, without
+/// colors; see `Renderer::snippet`
+pub fn render_snippet(file: &SourceFile, span: Span) -> String {
+    Renderer::plain().snippet(file, span, Severity::Error)
+}
@@ -0,0 +1,141 @@
+//! Tests for machine-readable diagnostic output
+
+use pretty_assertions::assert_eq;
+use silk_diagnostics::{apply_fixes, Diagnostic, Edit, Fix, Note, Severity};
+use silk_lexer::Span;
+
+#[test]
+fn test_error_json_fields() {
+    let diagnostic = Diagnostic::error(
+        "undefined-variable",
+        "Undefined variable 'y' at line 2, column 7",
+        Some(Span::new(12, 13, 2, 7)),
+    )
+    .with_file("main.silk");
+
+    let json = diagnostic.to_json();
+    assert_eq!(json["code"], "undefined-variable");
+    assert_eq!(json["severity"], "error");
+    assert_eq!(
+        json["message"],
+        "Undefined variable 'y' at line 2, column 7"
+    );
+    assert_eq!(json["file"], "main.silk");
+    assert_eq!(json["span"]["start"], 12);
+    assert_eq!(json["span"]["end"], 13);
+    assert_eq!(json["span"]["line"], 2);
+    assert_eq!(json["span"]["column"], 7);
+}
+
+#[test]
+fn test_missing_file_and_span_are_null() {
+    let json = Diagnostic::error("unexpected-eof", "Unexpected end of file", None).to_json();
+    assert!(json["file"].is_null());
+    assert!(json["span"].is_null());
+}
+
+#[test]
+fn test_warning_severity() {
+    let diagnostic = Diagnostic::warning("unused-variable", "Unused variable 'x'", None);
+    assert_eq!(diagnostic.severity, Severity::Warning);
+    assert!(!diagnostic.is_error());
+    assert_eq!(diagnostic.to_json()["severity"], "warning");
+}
+
+#[test]
+fn test_json_line_is_single_line() {
+    let diagnostic = Diagnostic::error("invalid-syntax", "line one\nline two", None);
+    let line = diagnostic.to_json_line();
+    assert!(!line.contains('\n'));
+    let parsed: serde_json::Value = serde_json::from_str(&line).unwrap();
+    assert_eq!(parsed["message"], "line one\nline two");
+}
+
+#[test]
+fn test_display() {
+    let diagnostic =
+        Diagnostic::warning("unused-function", "Unused function 'f'", None).with_file("lib.silk");
+    assert_eq!(
+        diagnostic.to_string(),
+        "lib.silk: warning[unused-function]: Unused function 'f'"
+    );
+}
+
+#[test]
+fn test_notes_json() {
+    let diagnostic =
+        Diagnostic::error("circular-import", "Circular import", None).with_note(Note::new(
+            "'a' imports 'b' here",
+            Some("a.silk".to_string()),
+            Some(Span::new(0, 8, 1, 1)),
+        ));
+
+    let json = diagnostic.to_json();
+    assert_eq!(json["notes"][0]["message"], "'a' imports 'b' here");
+    assert_eq!(json["notes"][0]["file"], "a.silk");
+    assert_eq!(json["notes"][0]["span"]["end"], 8);
+    assert_eq!(
+        Diagnostic::error("invalid-syntax", "x", None).to_json()["notes"],
+        serde_json::json!([])
+    );
+}
+
+// ========== FIXES ==========
+
+#[test]
+fn test_fix_json() {
+    let span = Span::new(0, 10, 1, 1);
+    let diagnostic = Diagnostic::warning("unused-import", "Unused import 'os'", Some(span))
+        .with_fix(Fix::new(
+            "remove the unused import",
+            vec![Edit::delete(span)],
+        ));
+
+    let json = diagnostic.to_json();
+    assert_eq!(json["fix"]["message"], "remove the unused import");
+    assert_eq!(json["fix"]["edits"][0]["span"]["end"], 10);
+    assert_eq!(json["fix"]["edits"][0]["replacement"], "");
+    assert!(Diagnostic::error("x", "y", None).to_json()["fix"].is_null());
+}
+
+#[test]
+fn test_apply_fixes_skips_overlapping_edits() {
+    let source = "abcdef";
+    let fix = |start, end, text: &str| {
+        Diagnostic::warning("lint", "", None).with_fix(Fix::new(
+            "",
+            vec![Edit::replace(Span::new(start, end, 1, 1), text)],
+        ))
+    };
+    let diagnostics = [
+        fix(4, 6, "X"),
+        fix(0, 2, ""),
+        fix(1, 3, "overlaps"),
+        fix(0, 2, ""),
+        Diagnostic::warning("lint", "no fix", None),
+    ];
+    assert_eq!(apply_fixes(source, &diagnostics), ("cdX".to_string(), 3));
+}
+
+#[test]
+fn test_insertions() {
+    let span = Span::new(2, 5, 1, 3);
+    let diagnostic = Diagnostic::warning("unused-variable", "", Some(span))
+        .with_fix(Fix::new("", vec![Edit::insert_before(span, "_")]));
+    assert_eq!(
+        apply_fixes("a total", [&diagnostic]),
+        ("a _total".to_string(), 1)
+    );
+}
+
+#[test]
+fn test_fix_offsets_count_characters() {
+    // 'é' is two bytes but one character
+    let span = Span::new(11, 16, 2, 5);
+    let diagnostic = Diagnostic::warning("unused-variable", "", Some(span))
+        .with_fix(Fix::new("", vec![Edit::insert_before(span, "_")]));
+    assert_eq!(
+        apply_fixes("# café\nx = total", [&diagnostic]),
+        ("# café\nx = _total".to_string(), 1)
+    );
+}
@@ -0,0 +1,77 @@
+//! Tests for terminal rendering and color control
+
+use pretty_assertions::assert_eq;
+use silk_diagnostics::{ColorChoice, Diagnostic, Note, Renderer, Severity, SourceFile};
+use silk_lexer::Span;
+
+fn diagnostic() -> Diagnostic {
+    Diagnostic::warning(
+        "unused-variable",
+        "Unused variable 'count'",
+        Some(Span::new(4, 9, 2, 1)),
+    )
+    .with_note(Note::new(
+        "defined here",
+        Some("main.silk".to_string()),
+        Some(Span::new(4, 9, 2, 1)),
+    ))
+}
+
+#[test]
+fn test_plain_rendering() {
+    let file = SourceFile::new("main.silk", "x=1\ncount = 2\n");
+    assert_eq!(
+        Renderer::plain().render(&diagnostic(), Some(&file)),
+        "  ⚠ Unused variable 'count'\n      2 | count = 2\n        | ^^^^^\n      note: main.silk:2:1: defined here\n"
+    );
+    // Without the source, only the message and notes
+    assert_eq!(
+        Renderer::plain().render(&diagnostic(), None),
+        "  ⚠ Unused variable 'count'\n      note: main.silk:2:1: defined here\n"
+    ); // Error codes go in front of the message
+    assert_eq!(
+        Renderer::plain().render(&diagnostic().with_error_code("E0500"), None),
+        "  ⚠ [E0500] Unused variable 'count'\n      note: main.silk:2:1: defined here\n"
+    );
+}
+
+#[test]
+fn test_colored_rendering() {
+    let file = SourceFile::new("main.silk", "x=1\ncount = 2\n");
+    let rendered = Renderer::new(true).render(&diagnostic(), Some(&file));
+    let lines: Vec<&str> = rendered.lines().collect();
+    // Warnings are yellow, notes cyan, and the span is underlined
+    assert_eq!(
+        lines[0],
+        "  \x1b[1;33m⚠\x1b[0m \x1b[1mUnused variable 'count'\x1b[0m"
+    );
+    assert_eq!(
+        lines[1],
+        "      \x1b[1;34m2 |\x1b[0m \x1b[4m\x1b[1;33mcount\x1b[0m = 2"
+    );
+    assert_eq!(
+        lines[2],
+        "      \x1b[1;34m  |\x1b[0m \x1b[1;33m^^^^^\x1b[0m"
+    );
+    assert!(lines[3].starts_with("      \x1b[1;36mnote:\x1b[0m main.silk:2:1"));
+
+    assert_eq!(
+        Renderer::new(true).marker(Severity::Error),
+        "\x1b[1;31m✗\x1b[0m"
+    );
+    assert_eq!(Renderer::plain().marker(Severity::Error), "✗");
+}
+
+#[test]
+fn test_color_choice() {
+    assert!(ColorChoice::Auto.resolve(true, None));
+    assert!(ColorChoice::Auto.resolve(true, Some("")));
+    assert!(!ColorChoice::Auto.resolve(true, Some("1")));
+    assert!(!ColorChoice::Auto.resolve(false, None));
+    assert!(ColorChoice::Always.resolve(false, Some("1")));
+    assert!(!ColorChoice::Never.resolve(true, None));
+
+    assert_eq!("always".parse(), Ok(ColorChoice::Always));
+    assert_eq!(ColorChoice::Never.to_string(), "never");
+    assert!("sometimes".parse::<ColorChoice>().is_err());
+}
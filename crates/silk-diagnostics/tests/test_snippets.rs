@@ -0,0 +1,26 @@
+//! Tests for source snippets under diagnostics
+
+use pretty_assertions::assert_eq;
+use silk_diagnostics::{render_snippet, SourceFile};
+use silk_lexer::Span;
+
+#[test]
+fn test_snippet_underlines_the_span() {
+    let file = SourceFile::new("main.silk", "x = 1\ntotal = count + 1\n");
+    let start = "x = 1\ntotal = ".len();
+    assert_eq!(
+        render_snippet(&file, Span::new(start, start + 5, 2, 9)),
+        "2 | total = count + 1\n  |         ^^^^^\n"
+    );
+}
+
+#[test]
+fn test_long_spans_are_truncated() {
+    let source: String = (1..=12).map(|i| format!("line{}\n", i)).collect();
+    let file = SourceFile::new("main.silk", source.as_str());
+    let start = source.find("line9").unwrap();
+    assert_eq!(
+        render_snippet(&file, Span::new(start, source.len(), 9, 1)),
+        " 9 | line9\n   | ^^^^^\n10 | line10\n   | ^^^^^^\n11 | line11\n   | ^^^^^^\n   | ...\n"
+    );
+}
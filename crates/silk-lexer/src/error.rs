@@ -18,11 +18,14 @@ pub enum LexError {
     #[error("Indentation error at line {0}: {1}")]
     IndentationError(usize, String),
 
-    #[error("Invalid escape sequence '\\{0}' at line {1}, column {2}")]
-    InvalidEscape(char, usize, usize),
+    /// Unknown escape sequence, as written, and where it is
+    #[error("Invalid escape sequence '{0}' at line {line}, column {column}", line = .1.line, column = .1.column)]
+    InvalidEscape(String, Span),
 
-    #[error("Invalid Unicode escape at line {0}, column {1}")]
-    InvalidUnicodeEscape(usize, usize),
+    /// Malformed `\x`, `\u`, `\U` or `\N{...}` escape, as written, why it
+    /// is invalid, and where it is
+    #[error("Invalid Unicode escape '{0}' at line {line}, column {column}: {1}", line = .2.line, column = .2.column)]
+    InvalidUnicodeEscape(String, String, Span),
 
     #[error("Invalid f-string: {0} at line {1}, column {2}")]
     InvalidFString(String, usize, usize),
@@ -35,14 +38,29 @@ pub enum LexError {
 }
 
 impl LexError {
+    /// Stable error code, explained by `silk explain` (e.g. `E0001`)
+    pub fn error_code(&self) -> &'static str {
+        match self {
+            LexError::UnexpectedCharacter(..) => "E0001",
+            LexError::UnterminatedString(..) => "E0002",
+            LexError::InvalidNumber(..) => "E0003",
+            LexError::IndentationError(..) => "E0004",
+            LexError::InvalidEscape(..) => "E0005",
+            LexError::InvalidUnicodeEscape(..) => "E0006",
+            LexError::InvalidFString(..) => "E0007",
+            LexError::InvalidByteString(..) => "E0008",
+            LexError::UnexpectedEof => "E0009",
+        }
+    }
+
     pub fn span(&self) -> Option<Span> {
         match self {
             LexError::UnexpectedCharacter(_, line, col) => Some(Span::new(0, 1, *line, *col)),
             LexError::UnterminatedString(line, col) => Some(Span::new(0, 1, *line, *col)),
             LexError::InvalidNumber(line, col, _) => Some(Span::new(0, 1, *line, *col)),
             LexError::IndentationError(line, _) => Some(Span::new(0, 1, *line, 1)),
-            LexError::InvalidEscape(_, line, col) => Some(Span::new(0, 1, *line, *col)),
-            LexError::InvalidUnicodeEscape(line, col) => Some(Span::new(0, 1, *line, *col)),
+            LexError::InvalidEscape(_, span) => Some(*span),
+            LexError::InvalidUnicodeEscape(_, _, span) => Some(*span),
             LexError::InvalidFString(_, line, col) => Some(Span::new(0, 1, *line, *col)),
             LexError::InvalidByteString(_, line, col) => Some(Span::new(0, 1, *line, *col)),
             LexError::UnexpectedEof => std::option::Option::None,
@@ -0,0 +1,176 @@
+//! Token text that shares the source buffer
+//!
+//! Every token carries its text. Copying it into a `String` per token made
+//! allocation the main cost of lexing large files, so a `Lexeme` is instead
+//! a byte range into the source, which all tokens of a file share through
+//! one reference-counted buffer. Text that does not appear in the source as
+//! is, such as an identifier normalized to NFKC, is stored on its own.
+
+use std::borrow::Borrow;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::{Deref, Range};
+use std::sync::Arc;
+
+/// The text of a token; derefs to `str`
+#[derive(Clone)]
+pub struct Lexeme(Repr);
+
+#[derive(Clone)]
+enum Repr {
+    /// A range of the shared source text
+    Source {
+        text: Arc<str>,
+        start: usize,
+        end: usize,
+    },
+    /// Text of its own
+    Owned(Box<str>),
+}
+
+impl Lexeme {
+    /// The text of `range`, a byte range of `text` on character boundaries
+    pub fn from_source(text: &Arc<str>, range: Range<usize>) -> Self {
+        debug_assert!(
+            text.get(range.clone()).is_some(),
+            "range must be in the text"
+        );
+        Lexeme(Repr::Source {
+            text: Arc::clone(text),
+            start: range.start,
+            end: range.end,
+        })
+    }
+
+    pub fn as_str(&self) -> &str {
+        match &self.0 {
+            Repr::Source { text, start, end } => &text[*start..*end],
+            Repr::Owned(text) => text,
+        }
+    }
+}
+
+impl Default for Lexeme {
+    fn default() -> Self {
+        Lexeme(Repr::Owned(Box::default()))
+    }
+}
+
+impl Deref for Lexeme {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for Lexeme {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl Borrow<str> for Lexeme {
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl From<&str> for Lexeme {
+    fn from(text: &str) -> Self {
+        Lexeme(Repr::Owned(text.into()))
+    }
+}
+
+impl From<String> for Lexeme {
+    fn from(text: String) -> Self {
+        Lexeme(Repr::Owned(text.into_boxed_str()))
+    }
+}
+
+impl From<Lexeme> for String {
+    fn from(lexeme: Lexeme) -> Self {
+        match lexeme.0 {
+            Repr::Owned(text) => text.into_string(),
+            Repr::Source { .. } => lexeme.as_str().to_string(),
+        }
+    }
+}
+
+impl fmt::Debug for Lexeme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl fmt::Display for Lexeme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl PartialEq for Lexeme {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for Lexeme {}
+
+impl Hash for Lexeme {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state)
+    }
+}
+
+impl PartialEq<str> for Lexeme {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for Lexeme {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl PartialEq<String> for Lexeme {
+    fn eq(&self, other: &String) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<Lexeme> for str {
+    fn eq(&self, other: &Lexeme) -> bool {
+        self == other.as_str()
+    }
+}
+
+impl PartialEq<Lexeme> for &str {
+    fn eq(&self, other: &Lexeme) -> bool {
+        *self == other.as_str()
+    }
+}
+
+impl PartialEq<Lexeme> for String {
+    fn eq(&self, other: &Lexeme) -> bool {
+        self == other.as_str()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_source_and_owned_lexemes_compare_by_text() {
+        let text: Arc<str> = Arc::from("let café = 1");
+        let name = Lexeme::from_source(&text, 4..9);
+        assert_eq!(name, "café");
+        assert_eq!(name, Lexeme::from("café"));
+        assert_eq!(String::from(name.clone()), "café");
+        assert_eq!(format!("{} {:?}", name, name), "café \"café\"");
+        assert_eq!(name.len(), 5);
+    }
+}
@@ -2,73 +2,220 @@
 ///
 /// Converts source code text into a stream of tokens.
 use crate::error::{LexError, LexResult};
+use crate::lexeme::Lexeme;
+use crate::source_map::SourceFile;
 use crate::token::{FStringPart, Span, Token, TokenKind};
+use crate::trivia::{LosslessToken, Trivia, TriviaKind};
+use std::sync::Arc;
+use unicode_normalization::UnicodeNormalization;
 
 pub struct Lexer {
-    input: Vec<char>,
+    /// The source text, shared by the lexemes of all tokens
+    text: Arc<str>,
+    /// The same text, for line and column lookup
+    source: SourceFile,
+    /// Character offset of the current position, as used by spans
     position: usize,
+    /// Byte offset of `position` in `text`
+    byte_position: usize,
+    /// Line of `position`, and the offset where that line starts; kept up
+    /// to date so that the location of the current position needs no lookup
     line: usize,
-    column: usize,
+    line_start: usize,
     indent_stack: Vec<usize>,
     at_line_start: bool,
     pending_dedents: usize,
+    /// Number of unclosed `(`, `[` and `{`; newlines inside them are ignored
+    bracket_depth: usize,
+    /// Set once `Eof` or an error has been yielded by the iterator
+    finished: bool,
 }
 
 impl Lexer {
     /// Create a new lexer from source code
     pub fn new(source: &str) -> Self {
+        let text: Arc<str> = Arc::from(source);
         Self {
-            input: source.chars().collect(),
+            source: SourceFile::new("", text.clone()),
+            text,
             position: 0,
+            byte_position: 0,
             line: 1,
-            column: 1,
+            line_start: 0,
             indent_stack: vec![0], // Start with 0 indentation
             at_line_start: true,
             pending_dedents: 0,
+            bracket_depth: 0,
+            finished: false,
         }
     }
 
     /// Tokenize the entire source
+    ///
+    /// Collects the token stream; use the `Iterator` implementation to
+    /// process tokens one at a time instead.
     pub fn tokenize(&mut self) -> LexResult<Vec<Token>> {
+        let _span = tracing::debug_span!("lex", bytes = self.text.len()).entered();
+        let tokens = self.by_ref().collect::<LexResult<Vec<Token>>>()?;
+
+        tracing::debug!(tokens = tokens.len(), "lexing finished");
+        Ok(tokens)
+    }
+
+    /// Tokenize without stopping at lexical errors
+    ///
+    /// Each error is recorded and the offending input becomes an `Error`
+    /// token; lexing then resumes after it, so tools can still see the rest
+    /// of the file. The token stream always ends with `Eof`.
+    pub fn tokenize_lossy(&mut self) -> (Vec<Token>, Vec<LexError>) {
         let mut tokens = Vec::new();
+        let mut errors = Vec::new();
 
         loop {
-            // Handle pending dedents first
-            if self.pending_dedents > 0 {
-                self.pending_dedents -= 1;
-                let token = Token {
-                    kind: TokenKind::Dedent,
-                    lexeme: String::new(),
-                    span: Span::new(self.position, self.position, self.line, self.column),
-                };
-                tokens.push(token);
-                continue;
+            let start = self.position;
+            match self.next() {
+                Some(Ok(token)) => tokens.push(token),
+                Some(Err(error)) => {
+                    tokens.push(self.recover(start, &error));
+                    errors.push(error);
+                    self.finished = false;
+                }
+                None => break,
             }
+        }
+
+        (tokens, errors)
+    }
+
+    /// Build the `Error` token for a failed token starting at `start`,
+    /// skipping at least one character so lexing always makes progress
+    fn recover(&mut self, start: usize, error: &LexError) -> Token {
+        // The failed token starts after any inline whitespace
+        let start = start
+            + self
+                .text_from(start)
+                .chars()
+                .take_while(|c| matches!(c, ' ' | '\t' | '\r'))
+                .count();
+        if self.position == start && !self.is_at_end() {
+            self.advance();
+        }
+        if self.position > start && self.text[..self.byte_position].ends_with('\n') {
+            self.at_line_start = true;
+        }
 
-            let token = self.next_token()?;
-            let is_eof = token.kind == TokenKind::Eof;
-
-            // Don't add EOF yet if we have pending dedents
-            if is_eof {
-                // Generate dedents for remaining indentation levels
-                while self.indent_stack.len() > 1 {
-                    self.indent_stack.pop();
-                    tokens.push(Token {
-                        kind: TokenKind::Dedent,
-                        lexeme: String::new(),
-                        span: Span::new(self.position, self.position, self.line, self.column),
-                    });
+        let (line, column) = error.span().map_or(self.location(self.position), |span| {
+            (span.line, span.column)
+        });
+        Token {
+            kind: TokenKind::Error,
+            lexeme: self.lexeme(start),
+            span: Span::new(start, self.position, line, column),
+        }
+    }
+
+    /// Tokenize keeping whitespace, comments and exact source text
+    ///
+    /// Produces the same tokens as `tokenize`, each wrapped with its original
+    /// text and trivia; `render_lossless` turns the result back into the
+    /// exact source.
+    pub fn tokenize_lossless(&mut self) -> LexResult<Vec<LosslessToken>> {
+        let mut tokens: Vec<LosslessToken> = Vec::new();
+
+        loop {
+            let start = self.position;
+            let token = match self.next() {
+                Some(token) => token?,
+                None => break,
+            };
+
+            // Everything consumed for this token: trivia first, then its text
+            let region = self.text_from(start);
+            let trivia_len = match token.kind {
+                TokenKind::Indent | TokenKind::Dedent | TokenKind::Eof => region.len(),
+                TokenKind::Comment => {
+                    region.len() - region.trim_start_matches([' ', '\t', '\r']).len()
+                }
+                TokenKind::Newline => region.len() - 1,
+                _ => Self::trivia_prefix_len(region),
+            };
+            let trivia = self.split_trivia(&region[..trivia_len], start);
+            let text = region[trivia_len..].to_string();
+
+            // Same-line trivia belongs to the previous token
+            let mut leading = trivia;
+            if let Some(previous) = tokens.last_mut() {
+                let starts_line = matches!(
+                    previous.token.kind,
+                    TokenKind::Newline | TokenKind::Indent | TokenKind::Dedent
+                );
+                if !starts_line {
+                    previous.trailing.append(&mut leading);
                 }
             }
 
-            tokens.push(token);
+            tokens.push(LosslessToken {
+                token,
+                text,
+                leading,
+                trailing: Vec::new(),
+            });
+        }
 
-            if is_eof {
-                break;
+        Ok(tokens)
+    }
+
+    /// Length in bytes of the whitespace/comment prefix of a consumed region
+    ///
+    /// Newlines only appear here when joined into one logical line, either
+    /// inside brackets or after a `\` continuation.
+    fn trivia_prefix_len(region: &str) -> usize {
+        let mut len = 0;
+        while let Some(c) = region[len..].chars().next() {
+            match c {
+                ' ' | '\t' | '\r' | '\n' | '\\' => len += 1,
+                // An inline comment runs to the end of the line
+                '#' => len += region[len..].find('\n').unwrap_or(region.len() - len),
+                _ => break,
             }
         }
+        len
+    }
 
-        Ok(tokens)
+    /// Split trivia text starting at character `start` into whitespace and
+    /// comment pieces with spans
+    fn split_trivia(&self, text: &str, start: usize) -> Vec<Trivia> {
+        let mut pieces = Vec::new();
+        let mut rest = text;
+        let mut offset = start;
+
+        while !rest.is_empty() {
+            let (kind, end) = if rest.starts_with('#') {
+                (TriviaKind::Comment, rest.find('\n'))
+            } else {
+                (TriviaKind::Whitespace, rest.find('#'))
+            };
+            let (piece, tail) = rest.split_at(end.unwrap_or(rest.len()));
+            let len = piece.chars().count();
+            let (line, column) = self.location(offset);
+            pieces.push(Trivia {
+                kind,
+                text: piece.to_string(),
+                span: Span::new(offset, offset + len, line, column),
+            });
+            offset += len;
+            rest = tail;
+        }
+
+        pieces
+    }
+
+    fn make_dedent(&self) -> Token {
+        Token {
+            kind: TokenKind::Dedent,
+            lexeme: Lexeme::default(),
+            span: Span::new(self.position, self.position, self.line(), self.column()),
+        }
     }
 
     /// Get the next token
@@ -85,8 +232,8 @@ impl Lexer {
         }
 
         let start_pos = self.position;
-        let start_line = self.line;
-        let start_col = self.column;
+        let start_line = self.line();
+        let start_col = self.column();
 
         let ch = self.current_char();
 
@@ -96,7 +243,7 @@ impl Lexer {
             self.at_line_start = true;
             return Ok(Token {
                 kind: TokenKind::Newline,
-                lexeme: "\n".to_string(),
+                lexeme: self.lexeme(start_pos),
                 span: Span::new(start_pos, self.position, start_line, start_col),
             });
         }
@@ -106,12 +253,14 @@ impl Lexer {
         // Only standalone comments are lexed in handle_indentation
 
         // Handle identifiers and keywords
-        if ch.is_alphabetic() || ch == '_' {
+        if is_identifier_start(ch) {
             return self.lex_identifier();
         }
 
-        // Handle numbers
-        if ch.is_ascii_digit() {
+        // Handle numbers, including floats with a leading dot (`.5`)
+        if ch.is_ascii_digit()
+            || (ch == '.' && self.peek_char(1).is_some_and(|c| c.is_ascii_digit()))
+        {
             return self.lex_number();
         }
 
@@ -127,34 +276,58 @@ impl Lexer {
     // Helper methods
 
     fn current_char(&self) -> char {
-        self.input[self.position]
+        self.peek_char(0).expect("read past the end of the input")
     }
 
     fn peek_char(&self, offset: usize) -> Option<char> {
-        let pos = self.position + offset;
-        if pos < self.input.len() {
-            Some(self.input[pos])
-        } else {
-            std::option::Option::None
-        }
+        self.text[self.byte_position..].chars().nth(offset)
     }
 
     fn advance(&mut self) -> char {
-        let ch = self.input[self.position];
+        let ch = self.current_char();
         self.position += 1;
-
+        self.byte_position += ch.len_utf8();
         if ch == '\n' {
             self.line += 1;
-            self.column = 1;
-        } else {
-            self.column += 1;
+            self.line_start = self.position;
         }
-
         ch
     }
 
+    /// The source text from character `start` to the current position
+    fn text_from(&self, start: usize) -> &str {
+        let len: usize = self.text[..self.byte_position]
+            .chars()
+            .rev()
+            .take(self.position - start)
+            .map(char::len_utf8)
+            .sum();
+        &self.text[self.byte_position - len..self.byte_position]
+    }
+
+    /// The lexeme from character `start` to the current position
+    fn lexeme(&self, start: usize) -> Lexeme {
+        let len = self.text_from(start).len();
+        Lexeme::from_source(&self.text, self.byte_position - len..self.byte_position)
+    }
+
+    /// Line and column of an offset
+    fn location(&self, offset: usize) -> (usize, usize) {
+        self.source.location(offset)
+    }
+
+    /// Line of the current position
+    fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Column of the current position
+    fn column(&self) -> usize {
+        self.position - self.line_start + 1
+    }
+
     fn is_at_end(&self) -> bool {
-        self.position >= self.input.len()
+        self.byte_position >= self.text.len()
     }
 
     fn skip_whitespace_inline(&mut self) {
@@ -167,19 +340,36 @@ impl Lexer {
                 while !self.is_at_end() && self.current_char() != '\n' {
                     self.advance();
                 }
-                break; // Stop after comment (newline will be handled separately)
+            } else if ch == '\n' && self.bracket_depth > 0 {
+                // Implicit line joining inside brackets
+                self.advance();
+            } else if ch == '\\' && self.at_line_continuation() {
+                // Explicit line continuation: skip the backslash and newline
+                while self.current_char() != '\n' {
+                    self.advance();
+                }
+                self.advance();
             } else {
                 break;
             }
         }
     }
 
+    /// Check for a `\` followed by a newline (optionally `\r\n`)
+    fn at_line_continuation(&self) -> bool {
+        match self.peek_char(1) {
+            Some('\n') => true,
+            Some('\r') => self.peek_char(2) == Some('\n'),
+            _ => false,
+        }
+    }
+
     fn handle_indentation(&mut self) -> LexResult<Token> {
         self.at_line_start = false;
 
         let start_pos = self.position;
-        let start_line = self.line;
-        let start_col = self.column;
+        let start_line = self.line();
+        let start_col = self.column();
 
         // Skip blank lines and comments
         while !self.is_at_end() {
@@ -197,7 +387,7 @@ impl Lexer {
                 self.at_line_start = true;
                 return Ok(Token {
                     kind: TokenKind::Newline,
-                    lexeme: "\n".to_string(),
+                    lexeme: self.lexeme(start_pos),
                     span: Span::new(start_pos, self.position, start_line, start_col),
                 });
             }
@@ -217,7 +407,7 @@ impl Lexer {
         }
 
         // Calculate indentation level (column - 1 because column starts at 1)
-        let indent_level = self.column - 1;
+        let indent_level = self.column() - 1;
         let current_indent = *self.indent_stack.last().unwrap();
 
         if indent_level > current_indent {
@@ -225,7 +415,7 @@ impl Lexer {
             self.indent_stack.push(indent_level);
             return Ok(Token {
                 kind: TokenKind::Indent,
-                lexeme: String::new(),
+                lexeme: Lexeme::default(),
                 span: Span::new(start_pos, self.position, start_line, start_col),
             });
         } else if indent_level < current_indent {
@@ -253,7 +443,7 @@ impl Lexer {
                 self.pending_dedents = dedent_count - 1;
                 return Ok(Token {
                     kind: TokenKind::Dedent,
-                    lexeme: String::new(),
+                    lexeme: Lexeme::default(),
                     span: Span::new(start_pos, self.position, start_line, start_col),
                 });
             }
@@ -274,12 +464,14 @@ impl Lexer {
         }
 
         // Handle identifiers and keywords
-        if ch.is_alphabetic() || ch == '_' {
+        if is_identifier_start(ch) {
             return self.lex_identifier();
         }
 
-        // Handle numbers
-        if ch.is_ascii_digit() {
+        // Handle numbers, including floats with a leading dot (`.5`)
+        if ch.is_ascii_digit()
+            || (ch == '.' && self.peek_char(1).is_some_and(|c| c.is_ascii_digit()))
+        {
             return self.lex_number();
         }
 
@@ -291,13 +483,13 @@ impl Lexer {
         // Handle newlines
         if ch == '\n' {
             let start_pos = self.position;
-            let start_line = self.line;
-            let start_col = self.column;
+            let start_line = self.line();
+            let start_col = self.column();
             self.advance();
             self.at_line_start = true;
             return Ok(Token {
                 kind: TokenKind::Newline,
-                lexeme: "\n".to_string(),
+                lexeme: self.lexeme(start_pos),
                 span: Span::new(start_pos, self.position, start_line, start_col),
             });
         }
@@ -307,21 +499,18 @@ impl Lexer {
     }
 
     fn make_token(&self, kind: TokenKind, lexeme: &str) -> Token {
+        let start = self.position - lexeme.chars().count();
+        let (line, column) = self.location(start);
         Token {
             kind,
-            lexeme: lexeme.to_string(),
-            span: Span::new(
-                self.position - lexeme.len(),
-                self.position,
-                self.line,
-                self.column - lexeme.len(),
-            ),
+            lexeme: Lexeme::from(lexeme),
+            span: Span::new(start, self.position, line, column),
         }
     }
 
     fn lex_comment(&mut self) -> LexResult<Token> {
         let start_pos = self.position;
-        let start_col = self.column;
+        let start_col = self.column();
 
         // Skip '#'
         self.advance();
@@ -331,24 +520,23 @@ impl Lexer {
             self.advance();
         }
 
-        let lexeme: String = self.input[start_pos..self.position].iter().collect();
+        let lexeme = self.lexeme(start_pos);
 
         Ok(Token {
             kind: TokenKind::Comment,
             lexeme,
-            span: Span::new(start_pos, self.position, self.line, start_col),
+            span: Span::new(start_pos, self.position, self.line(), start_col),
         })
     }
 
     fn lex_identifier(&mut self) -> LexResult<Token> {
         let start_pos = self.position;
-        let start_col = self.column;
+        let start_col = self.column();
 
         // Check for byte raw string prefix (br"..." or rb"...")
-        if self.position + 2 < self.input.len() {
+        if let (Some(ch2), Some(ch3)) = (self.peek_char(1), self.peek_char(2)) {
             let ch1 = self.current_char().to_ascii_lowercase();
-            let ch2 = self.input[self.position + 1].to_ascii_lowercase();
-            let ch3 = self.input[self.position + 2];
+            let ch2 = ch2.to_ascii_lowercase();
 
             if ((ch1 == 'b' && ch2 == 'r') || (ch1 == 'r' && ch2 == 'b'))
                 && (ch3 == '"' || ch3 == '\'')
@@ -358,63 +546,54 @@ impl Lexer {
         }
 
         // Check for byte string prefix (b"..." or b'...')
-        if (self.current_char() == 'b' || self.current_char() == 'B')
-            && !self.is_at_end()
-            && self.position + 1 < self.input.len()
+        if matches!(self.current_char(), 'b' | 'B') && matches!(self.peek_char(1), Some('"' | '\''))
         {
-            let next_ch = self.input[self.position + 1];
-            if next_ch == '"' || next_ch == '\'' {
-                return self.lex_byte_string();
-            }
+            return self.lex_byte_string();
         }
 
         // Check for raw string prefix (r"..." or r'...')
-        if (self.current_char() == 'r' || self.current_char() == 'R')
-            && !self.is_at_end()
-            && self.position + 1 < self.input.len()
+        if matches!(self.current_char(), 'r' | 'R') && matches!(self.peek_char(1), Some('"' | '\''))
         {
-            let next_ch = self.input[self.position + 1];
-            if next_ch == '"' || next_ch == '\'' {
-                return self.lex_raw_string();
-            }
+            return self.lex_raw_string();
         }
 
         // Check for f-string prefix (f"..." or f'...')
-        if (self.current_char() == 'f' || self.current_char() == 'F')
-            && !self.is_at_end()
-            && self.position + 1 < self.input.len()
+        if matches!(self.current_char(), 'f' | 'F') && matches!(self.peek_char(1), Some('"' | '\''))
         {
-            let next_ch = self.input[self.position + 1];
-            if next_ch == '"' || next_ch == '\'' {
-                return self.lex_fstring();
-            }
+            return self.lex_fstring();
         }
 
         while !self.is_at_end() {
             let ch = self.current_char();
-            if ch.is_alphanumeric() || ch == '_' {
+            if is_identifier_continue(ch) {
                 self.advance();
             } else {
                 break;
             }
         }
 
-        let lexeme: String = self.input[start_pos..self.position].iter().collect();
+        let lexeme = self.lexeme(start_pos);
 
-        // Check if it's a keyword
+        // Check if it's a keyword. As in Python, only the spelling in the
+        // source can be a keyword; normalization applies to identifiers
         let kind = TokenKind::keyword(&lexeme).unwrap_or(TokenKind::Identifier);
+        let lexeme = if lexeme.is_ascii() {
+            lexeme
+        } else {
+            Lexeme::from(lexeme.nfkc().collect::<String>())
+        };
 
         Ok(Token {
             kind,
             lexeme,
-            span: Span::new(start_pos, self.position, self.line, start_col),
+            span: Span::new(start_pos, self.position, self.line(), start_col),
         })
     }
 
     fn lex_number(&mut self) -> LexResult<Token> {
         let start_pos = self.position;
-        let start_col = self.column;
-        let start_line = self.line;
+        let start_col = self.column();
+        let start_line = self.line();
 
         let mut is_float = false;
 
@@ -432,14 +611,15 @@ impl Lexer {
                             self.advance();
                         }
 
-                        let lexeme: String = self.input[start_pos..self.position].iter().collect();
-                        let digits: String = self.input[digits_start..self.position]
-                            .iter()
-                            .filter(|&&c| c != '_')
-                            .collect();
+                        let lexeme = self.lexeme(start_pos);
+                        let digits = self.text_from(digits_start).replace('_', "");
 
                         if digits.is_empty() {
-                            return Err(LexError::InvalidNumber(start_line, start_col, lexeme));
+                            return Err(LexError::InvalidNumber(
+                                start_line,
+                                start_col,
+                                lexeme.to_string(),
+                            ));
                         }
 
                         match i64::from_str_radix(&digits, 2) {
@@ -456,7 +636,11 @@ impl Lexer {
                                 });
                             }
                             Err(_) => {
-                                return Err(LexError::InvalidNumber(start_line, start_col, lexeme));
+                                return Err(LexError::InvalidNumber(
+                                    start_line,
+                                    start_col,
+                                    lexeme.to_string(),
+                                ));
                             }
                         }
                     }
@@ -470,14 +654,15 @@ impl Lexer {
                             self.advance();
                         }
 
-                        let lexeme: String = self.input[start_pos..self.position].iter().collect();
-                        let digits: String = self.input[digits_start..self.position]
-                            .iter()
-                            .filter(|&&c| c != '_')
-                            .collect();
+                        let lexeme = self.lexeme(start_pos);
+                        let digits = self.text_from(digits_start).replace('_', "");
 
                         if digits.is_empty() {
-                            return Err(LexError::InvalidNumber(start_line, start_col, lexeme));
+                            return Err(LexError::InvalidNumber(
+                                start_line,
+                                start_col,
+                                lexeme.to_string(),
+                            ));
                         }
 
                         match i64::from_str_radix(&digits, 8) {
@@ -494,7 +679,11 @@ impl Lexer {
                                 });
                             }
                             Err(_) => {
-                                return Err(LexError::InvalidNumber(start_line, start_col, lexeme));
+                                return Err(LexError::InvalidNumber(
+                                    start_line,
+                                    start_col,
+                                    lexeme.to_string(),
+                                ));
                             }
                         }
                     }
@@ -511,14 +700,15 @@ impl Lexer {
                             self.advance();
                         }
 
-                        let lexeme: String = self.input[start_pos..self.position].iter().collect();
-                        let digits: String = self.input[digits_start..self.position]
-                            .iter()
-                            .filter(|&&c| c != '_')
-                            .collect();
+                        let lexeme = self.lexeme(start_pos);
+                        let digits = self.text_from(digits_start).replace('_', "");
 
                         if digits.is_empty() {
-                            return Err(LexError::InvalidNumber(start_line, start_col, lexeme));
+                            return Err(LexError::InvalidNumber(
+                                start_line,
+                                start_col,
+                                lexeme.to_string(),
+                            ));
                         }
 
                         match i64::from_str_radix(&digits, 16) {
@@ -535,7 +725,11 @@ impl Lexer {
                                 });
                             }
                             Err(_) => {
-                                return Err(LexError::InvalidNumber(start_line, start_col, lexeme));
+                                return Err(LexError::InvalidNumber(
+                                    start_line,
+                                    start_col,
+                                    lexeme.to_string(),
+                                ));
                             }
                         }
                     }
@@ -554,18 +748,21 @@ impl Lexer {
             self.advance();
         }
 
-        // Check for decimal point
+        // Check for decimal point. Digits after it are optional (`1.`), but
+        // a name right after it is an attribute of the integer (`1.real`);
+        // `1..real` is the float `1.` followed by `.real`
         if !self.is_at_end() && self.current_char() == '.' {
-            if let Some(next) = self.peek_char(1) {
-                if next.is_ascii_digit() {
-                    is_float = true;
-                    self.advance(); // consume '.'
+            let attribute = self
+                .peek_char(1)
+                .is_some_and(|next| is_identifier_start(next) && !self.at_exponent(1));
+            if !attribute {
+                is_float = true;
+                self.advance(); // consume '.'
 
-                    while !self.is_at_end()
-                        && (self.current_char().is_ascii_digit() || self.current_char() == '_')
-                    {
-                        self.advance();
-                    }
+                while !self.is_at_end()
+                    && (self.current_char().is_ascii_digit() || self.current_char() == '_')
+                {
+                    self.advance();
                 }
             }
         }
@@ -594,7 +791,7 @@ impl Lexer {
             }
         }
 
-        let lexeme: String = self.input[start_pos..self.position].iter().collect();
+        let lexeme = self.lexeme(start_pos);
         // Remove underscores for parsing
         let clean_lexeme: String = lexeme.chars().filter(|&c| c != '_').collect();
 
@@ -602,14 +799,22 @@ impl Lexer {
             match clean_lexeme.parse::<f64>() {
                 Ok(val) => TokenKind::Float(val),
                 Err(_) => {
-                    return Err(LexError::InvalidNumber(start_line, start_col, lexeme));
+                    return Err(LexError::InvalidNumber(
+                        start_line,
+                        start_col,
+                        lexeme.to_string(),
+                    ));
                 }
             }
         } else {
             match clean_lexeme.parse::<i64>() {
                 Ok(val) => TokenKind::Integer(val),
                 Err(_) => {
-                    return Err(LexError::InvalidNumber(start_line, start_col, lexeme));
+                    return Err(LexError::InvalidNumber(
+                        start_line,
+                        start_col,
+                        lexeme.to_string(),
+                    ));
                 }
             }
         };
@@ -621,10 +826,23 @@ impl Lexer {
         })
     }
 
+    /// Whether an exponent (`e5`, `E-3`) starts `offset` characters ahead
+    fn at_exponent(&self, offset: usize) -> bool {
+        if !matches!(self.peek_char(offset), Some('e' | 'E')) {
+            return false;
+        }
+        match self.peek_char(offset + 1) {
+            Some('+' | '-') => self
+                .peek_char(offset + 2)
+                .is_some_and(|c| c.is_ascii_digit()),
+            next => next.is_some_and(|c| c.is_ascii_digit()),
+        }
+    }
+
     fn lex_byte_string(&mut self) -> LexResult<Token> {
         let start_pos = self.position;
-        let start_col = self.column;
-        let start_line = self.line;
+        let start_col = self.column();
+        let start_line = self.line();
 
         self.advance(); // Consume 'b' or 'B'
         let quote = self.advance(); // Consume opening quote
@@ -639,6 +857,8 @@ impl Lexer {
         };
 
         let mut bytes = Vec::new();
+        // Reported after the closing quote, as in `lex_string`
+        let mut invalid_escape = None;
 
         loop {
             if self.is_at_end() {
@@ -662,42 +882,21 @@ impl Lexer {
                     self.advance();
                     break;
                 }
-            } else if ch == '\\' && !is_triple {
+            } else if ch == '\\' {
                 // Handle escape sequences
+                let backslash = self.position;
                 self.advance();
                 if self.is_at_end() {
                     return Err(LexError::UnterminatedString(start_line, start_col));
                 }
-
-                let escaped = self.advance();
-                let byte_val = match escaped {
-                    'n' => b'\n',
-                    'r' => b'\r',
-                    't' => b'\t',
-                    '\\' => b'\\',
-                    '\'' => b'\'',
-                    '"' => b'"',
-                    '0' => b'\0',
-                    'x' => {
-                        // Hex escape: \xHH
-                        if self.position + 2 > self.input.len() {
-                            return Err(LexError::InvalidEscape(escaped, self.line, self.column));
-                        }
-                        let hex1 = self.advance();
-                        let hex2 = self.advance();
-                        let hex_str = format!("{}{}", hex1, hex2);
-                        match u8::from_str_radix(&hex_str, 16) {
-                            Ok(val) => val,
-                            Err(_) => {
-                                return Err(LexError::InvalidEscape('x', self.line, self.column))
-                            }
-                        }
-                    }
-                    _ => {
-                        return Err(LexError::InvalidEscape(escaped, self.line, self.column));
+                match self.lex_escape(backslash, true) {
+                    // Byte escapes never go above \xff
+                    Ok(Some(escaped)) => bytes.push(escaped as u8),
+                    Ok(None) => {}
+                    Err(error) => {
+                        invalid_escape.get_or_insert(error);
                     }
-                };
-                bytes.push(byte_val);
+                }
             } else if ch == '\n' && !is_triple {
                 return Err(LexError::UnterminatedString(start_line, start_col));
             } else {
@@ -705,18 +904,22 @@ impl Lexer {
                 if !ch.is_ascii() {
                     return Err(LexError::InvalidByteString(
                         "Non-ASCII character in byte string".to_string(),
-                        self.line,
-                        self.column,
+                        self.line(),
+                        self.column(),
                     ));
                 }
                 bytes.push(self.advance() as u8);
             }
         }
 
-        let lexeme: String = self.input[start_pos..self.position].iter().collect();
+        if let Some(error) = invalid_escape {
+            return Err(error);
+        }
+
+        let lexeme = self.lexeme(start_pos);
 
         Ok(Token {
-            kind: TokenKind::ByteString(bytes),
+            kind: TokenKind::ByteString(Box::new(bytes)),
             lexeme,
             span: Span::new(start_pos, self.position, start_line, start_col),
         })
@@ -724,8 +927,8 @@ impl Lexer {
 
     fn lex_raw_string(&mut self) -> LexResult<Token> {
         let start_pos = self.position;
-        let start_col = self.column;
-        let start_line = self.line;
+        let start_col = self.column();
+        let start_line = self.line();
 
         self.advance(); // Consume 'r' or 'R'
         let quote = self.advance(); // Consume opening quote
@@ -771,10 +974,10 @@ impl Lexer {
             }
         }
 
-        let lexeme: String = self.input[start_pos..self.position].iter().collect();
+        let lexeme = self.lexeme(start_pos);
 
         Ok(Token {
-            kind: TokenKind::RawString(value),
+            kind: TokenKind::RawString(Box::new(value)),
             lexeme,
             span: Span::new(start_pos, self.position, start_line, start_col),
         })
@@ -782,8 +985,8 @@ impl Lexer {
 
     fn lex_byte_raw_string(&mut self) -> LexResult<Token> {
         let start_pos = self.position;
-        let start_col = self.column;
-        let start_line = self.line;
+        let start_col = self.column();
+        let start_line = self.line();
 
         // Consume prefix (br/rb/BR/RB/Br/etc.)
         self.advance(); // first char (b/r)
@@ -821,8 +1024,8 @@ impl Lexer {
                         if !ch.is_ascii() {
                             return Err(LexError::InvalidByteString(
                                 "Non-ASCII character in byte raw string".to_string(),
-                                self.line,
-                                self.column,
+                                self.line(),
+                                self.column(),
                             ));
                         }
                         bytes.push(self.advance() as u8);
@@ -838,8 +1041,8 @@ impl Lexer {
                 if !ch.is_ascii() {
                     return Err(LexError::InvalidByteString(
                         "Non-ASCII character in byte raw string".to_string(),
-                        self.line,
-                        self.column,
+                        self.line(),
+                        self.column(),
                     ));
                 }
                 // Raw strings: preserve everything literally, including backslashes
@@ -847,10 +1050,10 @@ impl Lexer {
             }
         }
 
-        let lexeme: String = self.input[start_pos..self.position].iter().collect();
+        let lexeme = self.lexeme(start_pos);
 
         Ok(Token {
-            kind: TokenKind::ByteRawString(bytes),
+            kind: TokenKind::ByteRawString(Box::new(bytes)),
             lexeme,
             span: Span::new(start_pos, self.position, start_line, start_col),
         })
@@ -858,8 +1061,8 @@ impl Lexer {
 
     fn lex_fstring(&mut self) -> LexResult<Token> {
         let start_pos = self.position;
-        let start_col = self.column;
-        let start_line = self.line;
+        let start_col = self.column();
+        let start_line = self.line();
 
         self.advance(); // Consume 'f' or 'F'
         let quote = self.advance(); // Consume opening quote
@@ -875,6 +1078,8 @@ impl Lexer {
 
         let mut parts = Vec::new();
         let mut current_text = String::new();
+        // Reported after the closing quote, as in `lex_string`
+        let mut invalid_escape = None;
 
         loop {
             if self.is_at_end() {
@@ -918,56 +1123,7 @@ impl Lexer {
                     }
 
                     self.advance(); // Consume {
-                    let mut expr_code = String::new();
-                    let mut brace_depth = 1;
-                    let mut format_spec = None;
-
-                    // Read expression until closing }
-                    loop {
-                        if self.is_at_end() {
-                            return Err(LexError::UnterminatedString(start_line, start_col));
-                        }
-
-                        let expr_ch = self.current_char();
-
-                        if expr_ch == '{' {
-                            brace_depth += 1;
-                            expr_code.push(self.advance());
-                        } else if expr_ch == '}' {
-                            brace_depth -= 1;
-                            if brace_depth == 0 {
-                                self.advance(); // Consume closing }
-                                break;
-                            }
-                            expr_code.push(self.advance());
-                        } else if expr_ch == ':' && brace_depth == 1 {
-                            // Format specifier
-                            self.advance(); // Consume :
-                            let mut spec = String::new();
-                            loop {
-                                if self.is_at_end() {
-                                    return Err(LexError::UnterminatedString(
-                                        start_line, start_col,
-                                    ));
-                                }
-                                let spec_ch = self.current_char();
-                                if spec_ch == '}' {
-                                    break;
-                                }
-                                spec.push(self.advance());
-                            }
-                            format_spec = Some(spec);
-                            self.advance(); // Consume closing }
-                            break;
-                        } else {
-                            expr_code.push(self.advance());
-                        }
-                    }
-
-                    parts.push(FStringPart::Expression {
-                        code: expr_code.trim().to_string(),
-                        format_spec,
-                    });
+                    parts.push(self.lex_fstring_field(is_triple, start_line, start_col)?);
                 }
             } else if ch == '}' {
                 // Check for escaped brace }}
@@ -978,31 +1134,24 @@ impl Lexer {
                 } else {
                     return Err(LexError::InvalidFString(
                         "Unmatched '}' in f-string".to_string(),
-                        self.line,
-                        self.column,
+                        self.line(),
+                        self.column(),
                     ));
                 }
-            } else if ch == '\\' && !is_triple {
+            } else if ch == '\\' {
                 // Handle escape sequences
+                let backslash = self.position;
                 self.advance();
                 if self.is_at_end() {
                     return Err(LexError::UnterminatedString(start_line, start_col));
                 }
-
-                let escaped = self.advance();
-                let escaped_char = match escaped {
-                    'n' => '\n',
-                    'r' => '\r',
-                    't' => '\t',
-                    '\\' => '\\',
-                    '\'' => '\'',
-                    '"' => '"',
-                    '0' => '\0',
-                    _ => {
-                        return Err(LexError::InvalidEscape(escaped, self.line, self.column));
+                match self.lex_escape(backslash, false) {
+                    Ok(Some(escaped)) => current_text.push(escaped),
+                    Ok(None) => {}
+                    Err(error) => {
+                        invalid_escape.get_or_insert(error);
                     }
-                };
-                current_text.push(escaped_char);
+                }
             } else if ch == '\n' && !is_triple {
                 return Err(LexError::UnterminatedString(start_line, start_col));
             } else {
@@ -1010,19 +1159,190 @@ impl Lexer {
             }
         }
 
-        let lexeme: String = self.input[start_pos..self.position].iter().collect();
+        if let Some(error) = invalid_escape {
+            return Err(error);
+        }
+
+        let lexeme = self.lexeme(start_pos);
 
         Ok(Token {
-            kind: TokenKind::FString(parts),
+            kind: TokenKind::FString(Box::new(parts)),
             lexeme,
             span: Span::new(start_pos, self.position, start_line, start_col),
         })
     }
 
+    /// Lex a replacement field of an f-string after its `{`, up to and
+    /// including the closing `}`
+    ///
+    /// The expression may contain brackets and string literals of its own,
+    /// in any quotes. Outside of brackets, `!r`, `!s` or `!a` and a `:`
+    /// format spec end it. Replacement fields nested in the format spec
+    /// (`{x:{width}}`) are kept in the spec text.
+    fn lex_fstring_field(
+        &mut self,
+        is_triple: bool,
+        start_line: usize,
+        start_col: usize,
+    ) -> LexResult<FStringPart> {
+        let mut code = String::new();
+        let mut conversion = None;
+        let mut format_spec = None;
+        // Brackets opened inside the expression
+        let mut depth = 0;
+
+        loop {
+            let Some(ch) = self.peek_char(0) else {
+                return Err(LexError::UnterminatedString(start_line, start_col));
+            };
+            match ch {
+                '(' | '[' | '{' => {
+                    depth += 1;
+                    code.push(self.advance());
+                }
+                ')' | ']' => {
+                    depth = usize::saturating_sub(depth, 1);
+                    code.push(self.advance());
+                }
+                '}' if depth > 0 => {
+                    depth -= 1;
+                    code.push(self.advance());
+                }
+                '}' => {
+                    self.advance();
+                    break;
+                }
+                '\'' | '"' => self.lex_fstring_nested_string(&mut code, start_line, start_col)?,
+                '\n' if depth == 0 && !is_triple => {
+                    return Err(LexError::UnterminatedString(start_line, start_col));
+                }
+                // `!=` is an operator, any other `!` starts a conversion
+                '!' if depth == 0 && self.peek_char(1) != Some('=') => {
+                    self.advance();
+                    conversion = match self.peek_char(0) {
+                        Some(flag @ ('r' | 's' | 'a')) => Some(flag),
+                        _ => {
+                            return Err(LexError::InvalidFString(
+                                "expected 'r', 's' or 'a' after '!'".to_string(),
+                                self.line(),
+                                self.column(),
+                            ))
+                        }
+                    };
+                    self.advance();
+                    match self.peek_char(0) {
+                        Some(':') => {
+                            self.advance();
+                            format_spec =
+                                Some(self.lex_format_spec(is_triple, start_line, start_col)?);
+                        }
+                        Some('}') => {
+                            self.advance();
+                        }
+                        _ => {
+                            return Err(LexError::InvalidFString(
+                                "expected ':' or '}' after the conversion".to_string(),
+                                self.line(),
+                                self.column(),
+                            ))
+                        }
+                    }
+                    break;
+                }
+                ':' if depth == 0 => {
+                    self.advance();
+                    format_spec = Some(self.lex_format_spec(is_triple, start_line, start_col)?);
+                    break;
+                }
+                _ => code.push(self.advance()),
+            }
+        }
+
+        Ok(FStringPart::Expression {
+            code: code.trim().to_string(),
+            conversion,
+            format_spec,
+        })
+    }
+
+    /// Lex the format spec of an f-string replacement field up to and
+    /// including the closing `}`, keeping nested replacement fields as text
+    fn lex_format_spec(
+        &mut self,
+        is_triple: bool,
+        start_line: usize,
+        start_col: usize,
+    ) -> LexResult<String> {
+        let mut spec = String::new();
+        let mut depth = 0;
+        loop {
+            match self.peek_char(0) {
+                Some('{') => depth += 1,
+                Some('}') if depth > 0 => depth -= 1,
+                Some('}') => {
+                    self.advance();
+                    return Ok(spec);
+                }
+                Some('\n') if !is_triple => {
+                    return Err(LexError::UnterminatedString(start_line, start_col))
+                }
+                Some(_) => {}
+                None => return Err(LexError::UnterminatedString(start_line, start_col)),
+            }
+            spec.push(self.advance());
+        }
+    }
+
+    /// Copy a string literal inside an f-string expression to `code`, as
+    /// written; it may use the same quotes as the f-string itself
+    fn lex_fstring_nested_string(
+        &mut self,
+        code: &mut String,
+        start_line: usize,
+        start_col: usize,
+    ) -> LexResult<()> {
+        let quote = self.advance();
+        code.push(quote);
+        let is_triple = self.peek_char(0) == Some(quote) && self.peek_char(1) == Some(quote);
+        if is_triple {
+            code.push(self.advance());
+            code.push(self.advance());
+        }
+
+        loop {
+            match self.peek_char(0) {
+                None => return Err(LexError::UnterminatedString(start_line, start_col)),
+                Some('\n') if !is_triple => {
+                    return Err(LexError::UnterminatedString(start_line, start_col))
+                }
+                Some('\\') => {
+                    code.push(self.advance());
+                    if !self.is_at_end() {
+                        code.push(self.advance());
+                    }
+                }
+                Some(ch) if ch == quote => {
+                    if !is_triple {
+                        code.push(self.advance());
+                        return Ok(());
+                    }
+                    if self.peek_char(1) == Some(quote) && self.peek_char(2) == Some(quote) {
+                        for _ in 0..3 {
+                            code.push(self.advance());
+                        }
+                        return Ok(());
+                    }
+                    code.push(self.advance());
+                }
+                Some(_) => code.push(self.advance()),
+            }
+        }
+    }
+
     fn lex_string(&mut self) -> LexResult<Token> {
         let start_pos = self.position;
-        let start_col = self.column;
-        let start_line = self.line;
+        let start_col = self.column();
+        let start_line = self.line();
 
         let quote = self.advance(); // Consume opening quote
 
@@ -1036,6 +1356,9 @@ impl Lexer {
         };
 
         let mut value = String::new();
+        // The first invalid escape, reported once the string is complete so
+        // that error recovery resumes after it
+        let mut invalid_escape = None;
 
         loop {
             if self.is_at_end() {
@@ -1059,27 +1382,20 @@ impl Lexer {
                     self.advance();
                     break;
                 }
-            } else if ch == '\\' && !is_triple {
+            } else if ch == '\\' {
                 // Handle escape sequences
+                let backslash = self.position;
                 self.advance();
                 if self.is_at_end() {
                     return Err(LexError::UnterminatedString(start_line, start_col));
                 }
-
-                let escaped = self.advance();
-                let escaped_char = match escaped {
-                    'n' => '\n',
-                    'r' => '\r',
-                    't' => '\t',
-                    '\\' => '\\',
-                    '\'' => '\'',
-                    '"' => '"',
-                    '0' => '\0',
-                    _ => {
-                        return Err(LexError::InvalidEscape(escaped, self.line, self.column));
+                match self.lex_escape(backslash, false) {
+                    Ok(Some(escaped)) => value.push(escaped),
+                    Ok(None) => {}
+                    Err(error) => {
+                        invalid_escape.get_or_insert(error);
                     }
-                };
-                value.push(escaped_char);
+                }
             } else if ch == '\n' && !is_triple {
                 return Err(LexError::UnterminatedString(start_line, start_col));
             } else {
@@ -1087,28 +1403,152 @@ impl Lexer {
             }
         }
 
-        let lexeme: String = self.input[start_pos..self.position].iter().collect();
+        if let Some(error) = invalid_escape {
+            return Err(error);
+        }
+
+        let lexeme = self.lexeme(start_pos);
 
         Ok(Token {
-            kind: TokenKind::String(value),
+            kind: TokenKind::String(Box::new(value)),
             lexeme,
             span: Span::new(start_pos, self.position, start_line, start_col),
         })
     }
 
+    /// Decode the escape sequence after the `\` at `backslash`
+    ///
+    /// Supports the escapes of Python: `\\`, quotes, `\a`, `\b`, `\f`,
+    /// `\n`, `\r`, `\t`, `\v`, up to three octal digits and `\xHH`, and
+    /// in text strings also `\uXXXX`, `\UXXXXXXXX` and `\N{name}`. Returns
+    /// `None` for a backslash at the end of a line, which continues the
+    /// string on the next line. An invalid escape is consumed all the same,
+    /// so the caller can finish the string before reporting it.
+    fn lex_escape(&mut self, backslash: usize, bytes: bool) -> LexResult<Option<char>> {
+        let escaped = self.advance();
+        let ch = match escaped {
+            '\n' => return Ok(None),
+            '\r' => {
+                if self.peek_char(0) == Some('\n') {
+                    self.advance();
+                }
+                return Ok(None);
+            }
+            '\\' | '\'' | '"' => escaped,
+            'a' => '\x07',
+            'b' => '\x08',
+            'f' => '\x0c',
+            'n' => '\n',
+            'r' => '\r',
+            't' => '\t',
+            'v' => '\x0b',
+            '0'..='7' => {
+                let mut value = escaped.to_digit(8).unwrap();
+                for _ in 0..2 {
+                    match self.peek_char(0).and_then(|digit| digit.to_digit(8)) {
+                        Some(digit) => {
+                            value = value * 8 + digit;
+                            self.advance();
+                        }
+                        None => break,
+                    }
+                }
+                if bytes && value > 0o377 {
+                    return Err(self.unicode_escape_error(backslash, "a byte is at most \\377"));
+                }
+                // At most 0o777, so always a character
+                char::from_u32(value).unwrap()
+            }
+            'x' => self.lex_hex_escape(backslash, 2)?,
+            'u' if !bytes => self.lex_hex_escape(backslash, 4)?,
+            'U' if !bytes => self.lex_hex_escape(backslash, 8)?,
+            'N' if !bytes => self.lex_named_escape(backslash)?,
+            _ => {
+                let (text, span) = self.escape_text(backslash);
+                return Err(LexError::InvalidEscape(text, span));
+            }
+        };
+        Ok(Some(ch))
+    }
+
+    /// The character of a `\x`, `\u` or `\U` escape with exactly `digits`
+    /// hex digits
+    fn lex_hex_escape(&mut self, backslash: usize, digits: usize) -> LexResult<char> {
+        let mut value = 0;
+        for _ in 0..digits {
+            match self.peek_char(0).and_then(|digit| digit.to_digit(16)) {
+                Some(digit) => {
+                    value = value * 16 + digit;
+                    self.advance();
+                }
+                None => {
+                    let reason = format!("expected {} hex digits", digits);
+                    return Err(self.unicode_escape_error(backslash, &reason));
+                }
+            }
+        }
+        char::from_u32(value)
+            .ok_or_else(|| self.unicode_escape_error(backslash, "not a Unicode character"))
+    }
+
+    /// The character of a `\N{name}` escape, named as in the Unicode
+    /// character database (case does not matter)
+    fn lex_named_escape(&mut self, backslash: usize) -> LexResult<char> {
+        if self.peek_char(0) != Some('{') {
+            return Err(self.unicode_escape_error(backslash, "expected '{' after \\N"));
+        }
+        self.advance();
+        let name_start = self.position;
+        while let Some(ch) = self.peek_char(0) {
+            if matches!(ch, '}' | '"' | '\'' | '\n') {
+                break;
+            }
+            self.advance();
+        }
+        if self.peek_char(0) != Some('}') {
+            return Err(self.unicode_escape_error(backslash, "expected '}' after the name"));
+        }
+        let name = self.text_from(name_start).to_string();
+        self.advance();
+        unicode_names2::character(&name)
+            .ok_or_else(|| self.unicode_escape_error(backslash, "unknown character name"))
+    }
+
+    /// Text and span of the escape from `backslash` to the current position
+    fn escape_text(&self, backslash: usize) -> (String, Span) {
+        let (line, column) = self.location(backslash);
+        let text = self.text_from(backslash).to_string();
+        (text, Span::new(backslash, self.position, line, column))
+    }
+
+    fn unicode_escape_error(&self, backslash: usize, reason: &str) -> LexError {
+        let (text, span) = self.escape_text(backslash);
+        LexError::InvalidUnicodeEscape(text, reason.to_string(), span)
+    }
+
     fn lex_operator_or_delimiter(&mut self) -> LexResult<Token> {
         let start_pos = self.position;
-        let start_col = self.column;
+        let start_col = self.column();
 
         let ch = self.advance();
 
         let kind = match ch {
-            '(' => TokenKind::LeftParen,
-            ')' => TokenKind::RightParen,
-            '[' => TokenKind::LeftBracket,
-            ']' => TokenKind::RightBracket,
-            '{' => TokenKind::LeftBrace,
-            '}' => TokenKind::RightBrace,
+            '(' | '[' | '{' => {
+                self.bracket_depth += 1;
+                match ch {
+                    '(' => TokenKind::LeftParen,
+                    '[' => TokenKind::LeftBracket,
+                    _ => TokenKind::LeftBrace,
+                }
+            }
+            ')' | ']' | '}' => {
+                self.bracket_depth = self.bracket_depth.saturating_sub(1);
+                match ch {
+                    ')' => TokenKind::RightParen,
+                    ']' => TokenKind::RightBracket,
+                    _ => TokenKind::RightBrace,
+                }
+            }
             ',' => TokenKind::Comma,
             ';' => TokenKind::Semicolon,
             ':' => {
@@ -1255,13 +1695,9 @@ impl Lexer {
                 }
             }
 
-            '!' => {
-                if self.peek_char(0) == Some('=') {
-                    self.advance();
-                    TokenKind::NotEqual
-                } else {
-                    return Err(LexError::UnexpectedCharacter(ch, self.line, start_col));
-                }
+            '!' if self.peek_char(0) == Some('=') => {
+                self.advance();
+                TokenKind::NotEqual
             }
 
             '.' => {
@@ -1274,24 +1710,86 @@ impl Lexer {
                 }
             }
 
-            '@' => TokenKind::At,
+            '@' => {
+                if self.peek_char(0) == Some('=') {
+                    self.advance();
+                    TokenKind::AtAssign
+                } else {
+                    TokenKind::At
+                }
+            }
 
             _ => {
-                return Err(LexError::UnexpectedCharacter(ch, self.line, start_col));
+                return Err(LexError::UnexpectedCharacter(ch, self.line(), start_col));
             }
         };
 
-        let lexeme: String = self.input[start_pos..self.position].iter().collect();
+        let lexeme = self.lexeme(start_pos);
 
         Ok(Token {
             kind,
             lexeme,
-            span: Span::new(start_pos, self.position, self.line, start_col),
+            span: Span::new(start_pos, self.position, self.line(), start_col),
         })
     }
 }
 
+/// Whether `ch` can start an identifier: `_` or a character with the
+/// Unicode `XID_Start` property, as in Python
+pub fn is_identifier_start(ch: char) -> bool {
+    ch == '_' || unicode_ident::is_xid_start(ch)
+}
+
+/// Whether `ch` can continue an identifier: a character with the Unicode
+/// `XID_Continue` property, which includes digits and `_`
+pub fn is_identifier_continue(ch: char) -> bool {
+    unicode_ident::is_xid_continue(ch)
+}
+
+/// Streams tokens one at a time, ending with `Eof`
+///
+/// Dedents for any open indentation levels are emitted before `Eof`. The
+/// iterator stops after yielding `Eof` or the first error, so callers can
+/// report a lexical error without lexing the rest of the file.
+impl Iterator for Lexer {
+    type Item = LexResult<Token>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        if self.pending_dedents > 0 {
+            self.pending_dedents -= 1;
+            return Some(Ok(self.make_dedent()));
+        }
+
+        let token = match self.next_token() {
+            Ok(token) => token,
+            Err(e) => {
+                self.finished = true;
+                return Some(Err(e));
+            }
+        };
+
+        if token.kind == TokenKind::Eof {
+            // Close remaining indentation levels before the final Eof
+            if self.indent_stack.len() > 1 {
+                self.pending_dedents = self.indent_stack.len() - 2;
+                self.indent_stack.truncate(1);
+                return Some(Ok(self.make_dedent()));
+            }
+            self.finished = true;
+        }
+
+        Some(Ok(token))
+    }
+}
+
+impl std::iter::FusedIterator for Lexer {}
+
 #[cfg(test)]
+#[allow(clippy::approx_constant)]
 mod tests {
     use super::*;
 
@@ -1349,7 +1847,7 @@ mod tests {
 
         assert!(matches!(tokens[0].kind, TokenKind::String(_)));
         if let TokenKind::String(ref s) = tokens[0].kind {
-            assert_eq!(s, "hello");
+            assert_eq!(s.as_str(), "hello");
         }
     }
 
@@ -1,11 +1,20 @@
 pub mod error;
+pub mod lexeme;
 pub mod lexer;
+pub mod source_map;
+pub mod suggest;
 /// Silk programming language lexer
 ///
 /// This module provides lexical analysis (tokenization) for Silk source code.
 /// It transforms raw source text into a stream of tokens that can be parsed.
 pub mod token;
+pub mod token_index;
+pub mod trivia;
 
 pub use error::{LexError, LexResult};
-pub use lexer::Lexer;
-pub use token::{FStringPart, Span, Token, TokenKind};
+pub use lexeme::Lexeme;
+pub use lexer::{is_identifier_continue, is_identifier_start, Lexer};
+pub use source_map::{display_width, FileId, SnippetLine, SourceFile, SourceMap};
+pub use token::{FStringPart, Span, Token, TokenKind, KEYWORDS};
+pub use token_index::TokenIndex;
+pub use trivia::{render_lossless, LosslessToken, Trivia, TriviaKind};
@@ -0,0 +1,287 @@
+/// Source files and position lookup
+///
+/// A `SourceMap` owns the text of every file being compiled. Each
+/// `SourceFile` maps offsets to lines and columns on demand: the table of
+/// line starts is built the first time a position is asked for, so files
+/// that never report a problem never pay for it.
+///
+/// Offsets are those of `Span`: character offsets into the file. Lines and
+/// columns are 1-based, columns count characters, and both `\n` and `\r\n`
+/// end a line. Snippets for terminal output are measured in display
+/// columns instead, where a tab is four columns and wide characters (CJK,
+/// emoji) take two.
+use crate::token::Span;
+use std::ops::Range;
+use std::sync::{Arc, OnceLock};
+
+/// Columns a tab occupies in a rendered snippet
+pub const TAB_WIDTH: usize = 4;
+
+/// Handle of a file in a `SourceMap`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FileId(usize);
+
+/// Every source file of a compilation
+#[derive(Debug, Default)]
+pub struct SourceMap {
+    files: Vec<SourceFile>,
+}
+
+impl SourceMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a file; adding a name again replaces its text
+    pub fn add(&mut self, name: impl Into<String>, text: impl Into<Arc<str>>) -> FileId {
+        let file = SourceFile::new(name, text);
+        match self.find(file.name()) {
+            Some(id) => {
+                self.files[id.0] = file;
+                id
+            }
+            None => {
+                self.files.push(file);
+                FileId(self.files.len() - 1)
+            }
+        }
+    }
+
+    pub fn file(&self, id: FileId) -> &SourceFile {
+        &self.files[id.0]
+    }
+
+    /// The file added under `name`
+    pub fn find(&self, name: &str) -> Option<FileId> {
+        self.files
+            .iter()
+            .position(|file| file.name() == name)
+            .map(FileId)
+    }
+
+    /// The file added under `name`, if any
+    pub fn get(&self, name: &str) -> Option<&SourceFile> {
+        self.find(name).map(|id| self.file(id))
+    }
+
+    pub fn files(&self) -> impl Iterator<Item = &SourceFile> {
+        self.files.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+}
+
+/// One line of a snippet, ready to print under a diagnostic
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnippetLine {
+    /// 1-based line number
+    pub line: usize,
+    /// Text of the line, without its line ending and with tabs expanded
+    pub text: String,
+    /// Display columns of `text` covered by the span; never empty, so an
+    /// empty span still gets a marker
+    pub highlight: Range<usize>,
+}
+
+/// The text of one file with lazy line lookup
+///
+/// The text is shared, so a file can be built from the same `Arc` as the
+/// lexer's tokens without copying it.
+#[derive(Debug)]
+pub struct SourceFile {
+    name: String,
+    text: Arc<str>,
+    lines: OnceLock<LineTable>,
+}
+
+#[derive(Debug)]
+struct LineTable {
+    starts: Vec<LineStart>,
+    /// Length of the file in characters
+    len: usize,
+}
+
+/// Where a line starts, as a character offset and as a byte offset
+#[derive(Debug, Clone, Copy)]
+struct LineStart {
+    offset: usize,
+    byte: usize,
+}
+
+impl SourceFile {
+    pub fn new(name: impl Into<String>, text: impl Into<Arc<str>>) -> Self {
+        Self {
+            name: name.into(),
+            text: text.into(),
+            lines: OnceLock::new(),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Number of lines; text after the last newline counts as a line
+    pub fn line_count(&self) -> usize {
+        self.lines().starts.len()
+    }
+
+    /// Line and column of an offset; offsets past the end are clamped to
+    /// the end of the file
+    pub fn location(&self, offset: usize) -> (usize, usize) {
+        let lines = self.lines();
+        let offset = offset.min(lines.len);
+        let index = lines.starts.partition_point(|start| start.offset <= offset) - 1;
+        (index + 1, offset - lines.starts[index].offset + 1)
+    }
+
+    /// Character offset where a 1-based line starts
+    pub fn line_start(&self, line: usize) -> Option<usize> {
+        let index = line.checked_sub(1)?;
+        self.lines().starts.get(index).map(|start| start.offset)
+    }
+
+    /// Byte offset of a character offset, for slicing `text()`; `None`
+    /// past the end of the file
+    pub fn byte_offset(&self, offset: usize) -> Option<usize> {
+        let lines = self.lines();
+        if offset > lines.len {
+            return None;
+        }
+        let index = lines.starts.partition_point(|start| start.offset <= offset) - 1;
+        let start = lines.starts[index];
+        let byte = self.text[start.byte..]
+            .char_indices()
+            .nth(offset - start.offset)
+            .map_or(self.text.len(), |(byte, _)| start.byte + byte);
+        Some(byte)
+    }
+
+    /// Character offset of a byte offset; `None` past the end of the file
+    /// or inside a character
+    pub fn char_offset(&self, byte: usize) -> Option<usize> {
+        if !self.text.is_char_boundary(byte) {
+            return None;
+        }
+        let starts = &self.lines().starts;
+        let start = starts[starts.partition_point(|start| start.byte <= byte) - 1];
+        Some(start.offset + self.text[start.byte..byte].chars().count())
+    }
+
+    /// Text of a 1-based line, without its line ending
+    pub fn line_text(&self, line: usize) -> Option<&str> {
+        let index = line.checked_sub(1)?;
+        let starts = &self.lines().starts;
+        let start = starts.get(index)?.byte;
+        let end = starts
+            .get(index + 1)
+            .map_or(self.text.len(), |next| next.byte);
+        let text = &self.text[start..end];
+        let text = text.strip_suffix('\n').unwrap_or(text);
+        Some(text.strip_suffix('\r').unwrap_or(text))
+    }
+
+    /// Lines covered by a span, with the covered part of each
+    pub fn snippet(&self, span: Span) -> Vec<SnippetLine> {
+        let (first, first_column) = self.location(span.start);
+        // A span ending right after a newline does not cover the next line
+        let (last, last_column) = self.location(span.end.max(span.start + 1) - 1);
+        let last_column = if span.end > span.start {
+            last_column + 1
+        } else {
+            last_column
+        };
+
+        (first..=last)
+            .filter_map(|line| {
+                let text = self.line_text(line)?;
+                let chars: Vec<char> = text.chars().collect();
+                let from = if line == first { first_column - 1 } else { 0 };
+                let to = if line == last {
+                    last_column - 1
+                } else {
+                    chars.len()
+                };
+                let start = width(&chars[..from.min(chars.len())]);
+                let end = width(&chars[..to.min(chars.len())]).max(start + 1);
+                Some(SnippetLine {
+                    line,
+                    text: expand_tabs(text),
+                    highlight: start..end,
+                })
+            })
+            .collect()
+    }
+
+    fn lines(&self) -> &LineTable {
+        self.lines.get_or_init(|| {
+            let mut starts = vec![LineStart { offset: 0, byte: 0 }];
+            let mut len = 0;
+            for (byte, c) in self.text.char_indices() {
+                len += 1;
+                if c == '\n' {
+                    starts.push(LineStart {
+                        offset: len,
+                        byte: byte + 1,
+                    });
+                }
+            }
+            LineTable { starts, len }
+        })
+    }
+}
+
+/// Columns a character occupies on a terminal
+pub fn char_width(c: char) -> usize {
+    match c {
+        '\t' => TAB_WIDTH,
+        c if c.is_control() => 0,
+        // Combining marks, zero-width spaces and joiners, variation selectors
+        '\u{0300}'..='\u{036F}'
+        | '\u{1AB0}'..='\u{1AFF}'
+        | '\u{1DC0}'..='\u{1DFF}'
+        | '\u{200B}'..='\u{200F}'
+        | '\u{20D0}'..='\u{20FF}'
+        | '\u{FE00}'..='\u{FE0F}'
+        | '\u{FE20}'..='\u{FE2F}' => 0,
+        // East Asian wide and fullwidth characters, and emoji
+        '\u{1100}'..='\u{115F}'
+        | '\u{2E80}'..='\u{303E}'
+        | '\u{3041}'..='\u{33FF}'
+        | '\u{3400}'..='\u{4DBF}'
+        | '\u{4E00}'..='\u{9FFF}'
+        | '\u{A000}'..='\u{A4CF}'
+        | '\u{AC00}'..='\u{D7A3}'
+        | '\u{F900}'..='\u{FAFF}'
+        | '\u{FE30}'..='\u{FE4F}'
+        | '\u{FF00}'..='\u{FF60}'
+        | '\u{FFE0}'..='\u{FFE6}'
+        | '\u{1F300}'..='\u{1F64F}'
+        | '\u{1F900}'..='\u{1F9FF}'
+        | '\u{20000}'..='\u{3FFFD}' => 2,
+        _ => 1,
+    }
+}
+
+/// Columns a string occupies on a terminal
+pub fn display_width(text: &str) -> usize {
+    text.chars().map(char_width).sum()
+}
+
+fn width(chars: &[char]) -> usize {
+    chars.iter().copied().map(char_width).sum()
+}
+
+fn expand_tabs(text: &str) -> String {
+    text.replace('\t', &" ".repeat(TAB_WIDTH))
+}
@@ -0,0 +1,64 @@
+//! "Did you mean" suggestions for misspelled names
+//!
+//! Parse errors suggest the keyword a name was probably meant to be
+//! (`retrun` → `return`), and the semantic analyzer suggests defined names
+//! and class members. A candidate is suggested when it is within a third of
+//! the name's length in edits, so short names only match near-identical
+//! candidates. Any two one-character names are one edit apart, so those
+//! are only matched by a difference in case.
+
+/// Edit distance between two strings: the number of single-character
+/// insertions, deletions and substitutions (Levenshtein distance), with a
+/// swap of two adjacent characters counting as one edit, so `whlie` is one
+/// edit away from `while`
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    // Rows for the prefixes of `a` of length i - 2, i - 1 and i
+    let mut before: Vec<usize> = Vec::new();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for i in 1..=a.len() {
+        let mut current = vec![i; b.len() + 1];
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            current[j] = (previous[j] + 1)
+                .min(current[j - 1] + 1)
+                .min(previous[j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                current[j] = current[j].min(before[j - 2] + 1);
+            }
+        }
+        before = std::mem::replace(&mut previous, current);
+    }
+    previous[b.len()]
+}
+
+/// The candidate closest to `name`, if any is close enough to be a likely
+/// misspelling
+///
+/// A candidate that differs only in case wins; other ties go to the earliest
+/// candidate. `name` itself is never suggested.
+pub fn closest_match<'a>(
+    name: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+    let limit = match name.chars().count() {
+        0 | 1 => 0,
+        length => (length / 3).max(1),
+    };
+    candidates
+        .into_iter()
+        .filter(|candidate| *candidate != name)
+        .map(|candidate| {
+            // A difference in case alone is the likeliest mistake
+            let distance = if candidate.eq_ignore_ascii_case(name) {
+                0
+            } else {
+                edit_distance(name, candidate)
+            };
+            (distance, candidate)
+        })
+        .filter(|(distance, _)| *distance <= limit)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
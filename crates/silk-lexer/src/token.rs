@@ -1,6 +1,7 @@
 /// Token types for the Silk programming language
 ///
 /// Represents all possible token types in Python/Silk syntax.
+use crate::lexeme::Lexeme;
 use std::fmt;
 
 /// Parts of an f-string: literal text or embedded expression
@@ -11,7 +12,8 @@ pub enum FStringPart {
     /// Expression code to be evaluated (stored as string, parsed later)
     Expression {
         code: String,
-        format_spec: Option<String>, // e.g., ".2f" in {value:.2f}
+        conversion: Option<char>,    // 'r', 's' or 'a' in {value!r}
+        format_spec: Option<String>, // e.g., ".2f" in {value:.2f}, "{width}" in {value:{width}}
     },
 }
 
@@ -19,12 +21,12 @@ pub enum FStringPart {
 #[derive(Debug, Clone, PartialEq)]
 pub struct Token {
     pub kind: TokenKind,
-    pub lexeme: String,
+    pub lexeme: Lexeme,
     pub span: Span,
 }
 
 /// Source location span (start and end positions)
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Span {
     pub start: usize,
     pub end: usize,
@@ -43,6 +45,52 @@ impl Span {
     }
 }
 
+/// Every keyword, in the spelling the lexer recognizes
+pub const KEYWORDS: &[&str] = &[
+    "and",
+    "as",
+    "assert",
+    "async",
+    "await",
+    "break",
+    "class",
+    "continue",
+    "def",
+    "del",
+    "elif",
+    "else",
+    "except",
+    "False",
+    "finally",
+    "for",
+    "from",
+    "global",
+    "if",
+    "import",
+    "in",
+    "is",
+    "lambda",
+    "None",
+    "nonlocal",
+    "not",
+    "NotImplemented",
+    "or",
+    "pass",
+    "raise",
+    "return",
+    "True",
+    "try",
+    "while",
+    "with",
+    "yield",
+    "match",
+    "case",
+];
+
+// A large file lexes to hundreds of thousands of tokens
+#[cfg(target_pointer_width = "64")]
+const _: () = assert!(std::mem::size_of::<TokenKind>() <= 16);
+
 /// All token kinds in Silk
 #[derive(Debug, Clone, PartialEq)]
 pub enum TokenKind {
@@ -87,14 +135,17 @@ pub enum TokenKind {
     Case,
 
     // Identifiers and literals
+    //
+    // String payloads are boxed so that a `TokenKind` is two words: most
+    // tokens carry none, and a file's token vector stays compact
     Identifier,
     Integer(i64),
     Float(f64),
-    String(String),
-    RawString(String),         // r"text\n" - escape sequences not processed
-    ByteString(Vec<u8>),       // b"bytes" - byte literal
-    ByteRawString(Vec<u8>),    // br"bytes\n" or rb"bytes\n" - raw byte literal
-    FString(Vec<FStringPart>), // f"text {expr} text"
+    String(Box<String>),
+    RawString(Box<String>),      // r"text\n" - escape sequences not processed
+    ByteString(Box<Vec<u8>>),    // b"bytes" - byte literal
+    ByteRawString(Box<Vec<u8>>), // br"bytes\n" or rb"bytes\n" - raw byte literal
+    FString(Box<Vec<FStringPart>>), // f"text {expr} text"
 
     // Operators
     Plus,        // +
@@ -132,6 +183,7 @@ pub enum TokenKind {
     CaretAssign,       // ^=
     LeftShiftAssign,   // <<=
     RightShiftAssign,  // >>=
+    AtAssign,          // @=
 
     // Delimiters
     LeftParen,    // (
@@ -158,6 +210,9 @@ pub enum TokenKind {
 
     // Comments (usually ignored but tracked for completeness)
     Comment,
+
+    // Unlexable input, produced only by `Lexer::tokenize_lossy`
+    Error,
 }
 
 impl TokenKind {
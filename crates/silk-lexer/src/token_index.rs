@@ -0,0 +1,62 @@
+//! Offset to token lookup
+//!
+//! Editor features and error recovery need the token at or around a
+//! position. Tokens come out of the lexer in source order, so a binary
+//! search over their spans finds it in O(log n) instead of a scan of the
+//! whole file.
+
+use crate::token::Token;
+
+/// Binary-search lookup over a token stream, by character offset
+///
+/// The tokens must be in source order, as the lexer produces them (also
+/// with `tokenize_lossy`). Zero-width tokens such as `Dedent` and `Eof`
+/// never cover an offset.
+#[derive(Debug, Clone, Copy)]
+pub struct TokenIndex<'a> {
+    tokens: &'a [Token],
+}
+
+impl<'a> TokenIndex<'a> {
+    pub fn new(tokens: &'a [Token]) -> Self {
+        debug_assert!(
+            tokens
+                .windows(2)
+                .all(|pair| pair[0].span.start <= pair[1].span.start),
+            "tokens must be in source order"
+        );
+        Self { tokens }
+    }
+
+    /// The indexed tokens
+    pub fn tokens(&self) -> &'a [Token] {
+        self.tokens
+    }
+
+    /// Index of the token whose text contains `offset`
+    pub fn at(&self, offset: usize) -> Option<usize> {
+        // The last non-empty token starting at or before the offset is the
+        // only one that can contain it
+        let mut index = self.first_from(offset + 1);
+        while index > 0 {
+            index -= 1;
+            let span = self.tokens[index].span;
+            if span.start < span.end {
+                return (offset < span.end).then_some(index);
+            }
+        }
+        None
+    }
+
+    /// Index of the first token starting at or after `offset`; the number
+    /// of tokens if there is none
+    pub fn first_from(&self, offset: usize) -> usize {
+        self.tokens
+            .partition_point(|token| token.span.start < offset)
+    }
+
+    /// Index of the last token starting before `offset`
+    pub fn before(&self, offset: usize) -> Option<usize> {
+        self.first_from(offset).checked_sub(1)
+    }
+}
@@ -0,0 +1,72 @@
+/// Lossless (trivia-preserving) tokens
+///
+/// The regular token stream drops inline whitespace and comments. Lossless
+/// tokens keep them as *trivia* attached to the neighbouring token, together
+/// with each token's exact source text, so that concatenating every token
+/// reproduces the original source exactly. Formatters and refactoring tools
+/// build on this.
+///
+/// Attachment follows the usual convention: trivia on the same line after a
+/// token is that token's *trailing* trivia; indentation and anything else
+/// before a token at the start of a line is its *leading* trivia.
+use crate::token::{Span, Token, TokenKind};
+
+/// Kind of trivia
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TriviaKind {
+    /// Spaces, tabs, carriage returns, and line breaks joined by brackets
+    /// or a `\` continuation
+    Whitespace,
+    /// An inline `# ...` comment (not including the newline)
+    Comment,
+}
+
+/// Source text that carries no syntactic meaning
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trivia {
+    pub kind: TriviaKind,
+    /// Exact source text
+    pub text: String,
+    pub span: Span,
+}
+
+/// A token together with its exact source text and surrounding trivia
+#[derive(Debug, Clone, PartialEq)]
+pub struct LosslessToken {
+    /// The token as produced by the regular lexer
+    pub token: Token,
+    /// Exact source text of the token (e.g. a string literal with its quotes
+    /// and escapes; empty for `Indent`, `Dedent` and `Eof`)
+    pub text: String,
+    /// Trivia before the token
+    pub leading: Vec<Trivia>,
+    /// Trivia after the token on the same line
+    pub trailing: Vec<Trivia>,
+}
+
+impl LosslessToken {
+    /// Kind of the underlying token
+    pub fn kind(&self) -> TokenKind {
+        self.token.kind.clone()
+    }
+
+    /// Append the token's full source text (trivia included) to `out`
+    pub fn write_source(&self, out: &mut String) {
+        for trivia in &self.leading {
+            out.push_str(&trivia.text);
+        }
+        out.push_str(&self.text);
+        for trivia in &self.trailing {
+            out.push_str(&trivia.text);
+        }
+    }
+}
+
+/// Reassemble source text from lossless tokens
+pub fn render_lossless(tokens: &[LosslessToken]) -> String {
+    let mut out = String::new();
+    for token in tokens {
+        token.write_source(&mut out);
+    }
+    out
+}
@@ -1,3 +1,5 @@
+#![allow(clippy::approx_constant)]
+
 use pretty_assertions::assert_eq;
 /// Comprehensive test suite for Silk lexer
 ///
@@ -7,7 +9,7 @@ use pretty_assertions::assert_eq;
 /// - Error conditions
 /// - Source location tracking
 /// - Complex integration scenarios
-use silk_lexer::{FStringPart, LexError, Lexer, TokenKind};
+use silk_lexer::{render_lossless, FStringPart, LexError, Lexer, TokenKind, TriviaKind};
 
 // ========== KEYWORD TESTS ==========
 
@@ -60,6 +62,15 @@ fn test_all_keywords_exhaustive() {
     }
 }
 
+#[test]
+fn test_keyword_list_matches_the_lexer() {
+    for keyword in silk_lexer::KEYWORDS {
+        let kind = TokenKind::keyword(keyword).expect(keyword);
+        assert!(kind.is_keyword(), "{}", keyword);
+    }
+    assert_eq!(silk_lexer::KEYWORDS.len(), 38);
+}
+
 #[test]
 fn test_keywords_case_sensitive() {
     let source = "def Def DEF dEf";
@@ -142,6 +153,47 @@ fn test_identifiers_unicode() {
     assert_eq!(tokens[4].lexeme, "变量");
 }
 
+#[test]
+fn test_identifiers_are_nfkc_normalized() {
+    // Ligature, fullwidth letters and a decomposed accent
+    let source = "\u{fb01}le \u{ff46}\u{ff49}\u{ff4c}\u{ff45} cafe\u{301} file";
+    let tokens = Lexer::new(source).tokenize().unwrap();
+
+    assert_eq!(tokens[0].lexeme, "file");
+    assert_eq!(tokens[1].lexeme, "file");
+    assert_eq!(tokens[2].lexeme, "caf\u{e9}");
+    assert_eq!(tokens[3].lexeme, "file");
+    // Spans still cover the characters as written
+    assert_eq!(tokens[0].span.end - tokens[0].span.start, 3);
+    assert_eq!(tokens[2].span.end - tokens[2].span.start, 5);
+
+    let lossless = Lexer::new(source).tokenize_lossless().unwrap();
+    assert_eq!(render_lossless(&lossless), source);
+}
+
+#[test]
+fn test_keywords_are_matched_before_normalization() {
+    // A fullwidth spelling of a keyword is an identifier, as in Python
+    let tokens = Lexer::new("\u{ff49}\u{ff46} if").tokenize().unwrap();
+    assert_eq!(tokens[0].kind, TokenKind::Identifier);
+    assert_eq!(tokens[0].lexeme, "if");
+    assert_eq!(tokens[1].kind, TokenKind::If);
+}
+
+#[test]
+fn test_identifiers_use_xid_properties() {
+    // Combining marks and connector punctuation may continue but not
+    // start an identifier
+    let tokens = Lexer::new("a\u{300}\u{203f}b").tokenize().unwrap();
+    assert_eq!(tokens[0].kind, TokenKind::Identifier);
+    assert_eq!(tokens[0].lexeme, "\u{e0}\u{203f}b");
+    assert!(Lexer::new("\u{300}a").tokenize().is_err());
+
+    // Format characters such as bidi controls are not part of identifiers
+    assert!(Lexer::new("ab\u{202e}cd").tokenize().is_err());
+    assert!(Lexer::new("a\u{200b}b").tokenize().is_err());
+}
+
 #[test]
 fn test_identifiers_with_digits() {
     let source = "var1 test2var _123 a1b2c3 x0y9z";
@@ -298,7 +350,6 @@ fn test_floats_edge_cases() {
     // Scientific notation edge cases
     assert!(matches!(tokens[1].kind, TokenKind::Float(_)));
     assert_eq!(tokens[1].lexeme, "1.0e0");
-    // Note: Trailing dot ("0.") not supported - parses as integer then dot
 }
 
 #[test]
@@ -323,7 +374,94 @@ fn test_number_followed_by_dot_method() {
     // Float with decimal point and digit
     assert!(matches!(tokens[0].kind, TokenKind::Float(_)));
     assert_eq!(tokens[0].lexeme, "123.5");
-    // Note: Trailing dot ("123.") not supported - requires digit after dot
+}
+
+/// (kind, lexeme) of every token but EOF
+fn kinds_and_lexemes(source: &str) -> Vec<(TokenKind, String)> {
+    let mut tokens = Lexer::new(source).tokenize().unwrap();
+    tokens.pop();
+    tokens
+        .into_iter()
+        .map(|token| (token.kind, token.lexeme.to_string()))
+        .collect()
+}
+
+#[test]
+fn test_floats_with_trailing_dot() {
+    assert_eq!(
+        kinds_and_lexemes("123. 0. 1_0. 1.e5 1.E-2"),
+        vec![
+            (TokenKind::Float(123.0), "123.".to_string()),
+            (TokenKind::Float(0.0), "0.".to_string()),
+            (TokenKind::Float(10.0), "1_0.".to_string()),
+            (TokenKind::Float(1e5), "1.e5".to_string()),
+            (TokenKind::Float(0.01), "1.E-2".to_string()),
+        ]
+    );
+    assert_eq!(
+        kinds_and_lexemes("x = [1., 2.]")[3..7],
+        [
+            (TokenKind::Float(1.0), "1.".to_string()),
+            (TokenKind::Comma, ",".to_string()),
+            (TokenKind::Float(2.0), "2.".to_string()),
+            (TokenKind::RightBracket, "]".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_floats_with_leading_dot() {
+    assert_eq!(
+        kinds_and_lexemes(".5 .25e1 ._5"),
+        vec![
+            (TokenKind::Float(0.5), ".5".to_string()),
+            (TokenKind::Float(2.5), ".25e1".to_string()),
+            (TokenKind::Dot, ".".to_string()),
+            (TokenKind::Identifier, "_5".to_string()),
+        ]
+    );
+    // A slice bound and a float after an operator
+    let tokens = kinds_and_lexemes("data[:.5] + -.5");
+    assert_eq!(tokens[3], (TokenKind::Float(0.5), ".5".to_string()));
+    assert_eq!(tokens[7], (TokenKind::Float(0.5), ".5".to_string()));
+}
+
+#[test]
+fn test_number_dot_attribute_corner_cases() {
+    // A name after the dot is an attribute of the integer
+    assert_eq!(
+        kinds_and_lexemes("1.real 1.e"),
+        vec![
+            (TokenKind::Integer(1), "1".to_string()),
+            (TokenKind::Dot, ".".to_string()),
+            (TokenKind::Identifier, "real".to_string()),
+            (TokenKind::Integer(1), "1".to_string()),
+            (TokenKind::Dot, ".".to_string()),
+            (TokenKind::Identifier, "e".to_string()),
+        ]
+    );
+    // Two dots: the float `1.` and then an attribute
+    assert_eq!(
+        kinds_and_lexemes("1..real"),
+        vec![
+            (TokenKind::Float(1.0), "1.".to_string()),
+            (TokenKind::Dot, ".".to_string()),
+            (TokenKind::Identifier, "real".to_string()),
+        ]
+    );
+    // A dot before a dot is never a float, so `...` stays an ellipsis
+    assert_eq!(
+        kinds_and_lexemes("x[...] x..5"),
+        vec![
+            (TokenKind::Identifier, "x".to_string()),
+            (TokenKind::LeftBracket, "[".to_string()),
+            (TokenKind::Ellipsis, "...".to_string()),
+            (TokenKind::RightBracket, "]".to_string()),
+            (TokenKind::Identifier, "x".to_string()),
+            (TokenKind::Dot, ".".to_string()),
+            (TokenKind::Float(0.5), ".5".to_string()),
+        ]
+    );
 }
 
 // ========== STRING TESTS ==========
@@ -336,16 +474,16 @@ fn test_strings_single_quote() {
 
     assert!(matches!(tokens[0].kind, TokenKind::String(_)));
     if let TokenKind::String(ref s) = tokens[0].kind {
-        assert_eq!(s, "hello");
+        assert_eq!(**s, "hello");
     }
     assert!(matches!(tokens[1].kind, TokenKind::String(_)));
     if let TokenKind::String(ref s) = tokens[1].kind {
-        assert_eq!(s, "world");
+        assert_eq!(**s, "world");
     }
     // Empty string
     assert!(matches!(tokens[2].kind, TokenKind::String(_)));
     if let TokenKind::String(ref s) = tokens[2].kind {
-        assert_eq!(s, "");
+        assert_eq!(**s, "");
     }
 }
 
@@ -357,11 +495,11 @@ fn test_strings_double_quote() {
 
     assert!(matches!(tokens[0].kind, TokenKind::String(_)));
     if let TokenKind::String(ref s) = tokens[0].kind {
-        assert_eq!(s, "hello");
+        assert_eq!(**s, "hello");
     }
     assert!(matches!(tokens[1].kind, TokenKind::String(_)));
     if let TokenKind::String(ref s) = tokens[1].kind {
-        assert_eq!(s, "world");
+        assert_eq!(**s, "world");
     }
 }
 
@@ -373,16 +511,126 @@ fn test_strings_escape_sequences() {
 
     assert!(matches!(tokens[0].kind, TokenKind::String(_)));
     if let TokenKind::String(ref s) = tokens[0].kind {
-        assert_eq!(s, "line1\nline2");
+        assert_eq!(**s, "line1\nline2");
     }
     assert!(matches!(tokens[1].kind, TokenKind::String(_)));
     if let TokenKind::String(ref s) = tokens[1].kind {
-        assert_eq!(s, "tab\there");
+        assert_eq!(**s, "tab\there");
     }
     assert!(matches!(tokens[2].kind, TokenKind::String(_)));
     if let TokenKind::String(ref s) = tokens[2].kind {
-        assert_eq!(s, "quote's");
+        assert_eq!(**s, "quote's");
+    }
+}
+
+fn string_value(source: &str) -> String {
+    match Lexer::new(source).tokenize().unwrap()[0].kind {
+        TokenKind::String(ref value) => value.to_string(),
+        ref other => panic!("Expected string token, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_strings_numeric_and_named_escapes() {
+    assert_eq!(string_value(r"'α\U0001F600\x41'"), "α😀A");
+    assert_eq!(
+        string_value(r#""\N{GREEK SMALL LETTER ALPHA}\N{bullet}""#),
+        "α•"
+    );
+    // Up to three octal digits
+    assert_eq!(string_value(r"'\101\0\12\1234'"), "A\0\nS4");
+    assert_eq!(string_value(r"'\a\b\f\v'"), "\x07\x08\x0c\x0b");
+}
+
+#[test]
+fn test_strings_escaped_newline_continues_the_string() {
+    assert_eq!(string_value("'abc\\\ndef'"), "abcdef");
+    assert_eq!(string_value("'abc\\\r\ndef'"), "abcdef");
+}
+
+#[test]
+fn test_triple_quoted_strings_process_escapes() {
+    assert_eq!(string_value(r#""""tab\there\"""""#), "tab\there\"");
+}
+
+#[test]
+fn test_fstring_and_byte_string_escapes() {
+    let tokens = Lexer::new(r#"f"\N{BULLET} {x}é" b"\101\xff\n""#)
+        .tokenize()
+        .unwrap();
+    match &tokens[0].kind {
+        TokenKind::FString(parts) => {
+            assert_eq!(parts[0], FStringPart::Text("• ".to_string()));
+            assert_eq!(parts[2], FStringPart::Text("é".to_string()));
+        }
+        other => panic!("Expected f-string token, got {:?}", other),
+    }
+    assert_eq!(
+        tokens[1].kind,
+        TokenKind::ByteString(Box::new(b"A\xff\n".to_vec()))
+    );
+}
+
+#[test]
+fn test_invalid_escape_points_at_the_escape() {
+    let error = Lexer::new(r#"x = "a\qb""#).tokenize().unwrap_err();
+    let span = error.span().unwrap();
+    assert!(matches!(error, LexError::InvalidEscape(ref text, _) if text == r"\q"));
+    assert_eq!((span.start, span.end, span.line, span.column), (6, 8, 1, 7));
+    assert_eq!(
+        error.to_string(),
+        r"Invalid escape sequence '\q' at line 1, column 7"
+    );
+}
+
+#[test]
+fn test_invalid_unicode_escapes() {
+    let cases = [
+        (r#""\u12""#, r"\u12", "expected 4 hex digits"),
+        (r#""\xg""#, r"\x", "expected 2 hex digits"),
+        (r#""\U00110000""#, r"\U00110000", "not a Unicode character"),
+        (r#""\udc00""#, r"\udc00", "not a Unicode character"),
+        (
+            r#""\N{NO SUCH NAME}""#,
+            r"\N{NO SUCH NAME}",
+            "unknown character name",
+        ),
+        (r#""\Nx""#, r"\N", "expected '{' after \\N"),
+        (
+            r#""\N{BULLET""#,
+            r"\N{BULLET",
+            "expected '}' after the name",
+        ),
+        (r#"b"\400""#, r"\400", "a byte is at most \\377"),
+    ];
+    for (source, escape, expected) in cases {
+        match Lexer::new(source).tokenize() {
+            Err(LexError::InvalidUnicodeEscape(text, reason, span)) => {
+                assert_eq!((text.as_str(), reason.as_str()), (escape, expected));
+                assert_eq!(span.column, 2 + source.starts_with('b') as usize);
+            }
+            other => panic!(
+                "Expected invalid Unicode escape for {}, got {:?}",
+                source, other
+            ),
+        }
     }
+    // Byte strings have no Unicode escapes
+    assert!(matches!(
+        Lexer::new(r#"b"\u0041""#).tokenize(),
+        Err(LexError::InvalidEscape(ref text, _)) if text == r"\u"
+    ));
+}
+
+#[test]
+fn test_invalid_escape_recovery_skips_the_string() {
+    let (tokens, errors) = Lexer::new("x = \"a\\qb\\N{NOPE}\" + 1\ny = 2\n").tokenize_lossy();
+    // Only the first invalid escape of a string is reported
+    assert_eq!(errors.len(), 1);
+    assert_eq!(tokens[2].kind, TokenKind::Error);
+    assert_eq!(tokens[2].lexeme, "\"a\\qb\\N{NOPE}\"");
+    assert_eq!(tokens[3].kind, TokenKind::Plus);
+    assert_eq!(tokens[6].lexeme, "y");
 }
 
 #[test]
@@ -410,11 +658,11 @@ fn test_strings_with_unicode() {
 
     assert!(matches!(tokens[0].kind, TokenKind::String(_)));
     if let TokenKind::String(ref s) = tokens[0].kind {
-        assert_eq!(s, "café");
+        assert_eq!(**s, "café");
     }
     assert!(matches!(tokens[1].kind, TokenKind::String(_)));
     if let TokenKind::String(ref s) = tokens[1].kind {
-        assert_eq!(s, "日本語");
+        assert_eq!(**s, "日本語");
     }
 }
 
@@ -426,11 +674,11 @@ fn test_strings_empty_variations() {
 
     assert!(matches!(tokens[0].kind, TokenKind::String(_)));
     if let TokenKind::String(ref s) = tokens[0].kind {
-        assert_eq!(s, "");
+        assert_eq!(**s, "");
     }
     assert!(matches!(tokens[1].kind, TokenKind::String(_)));
     if let TokenKind::String(ref s) = tokens[1].kind {
-        assert_eq!(s, "");
+        assert_eq!(**s, "");
     }
 }
 
@@ -547,7 +795,18 @@ fn test_operators_special() {
     let tokens = lexer.tokenize().unwrap();
 
     assert_eq!(tokens[0].kind, TokenKind::Arrow);
-    // Note: @ and := are not yet implemented
+}
+
+#[test]
+fn test_operators_matmult() {
+    let source = "a @ b\na @= b\n@decorator";
+    let mut lexer = Lexer::new(source);
+    let tokens = lexer.tokenize().unwrap();
+
+    assert_eq!(tokens[1].kind, TokenKind::At);
+    assert_eq!(tokens[5].kind, TokenKind::AtAssign);
+    assert_eq!(tokens[5].lexeme, "@=");
+    assert_eq!(tokens[8].kind, TokenKind::At);
 }
 
 #[test]
@@ -678,11 +937,14 @@ fn test_comments_single_line() {
     // Inline comments are now skipped (not tokenized)
     assert_eq!(tokens[0].lexeme, "x");
     assert_eq!(tokens[1].kind, TokenKind::Assign);
-    
+
     // Check that no Comment tokens exist for inline comments
     let has_comment = tokens.iter().any(|t| t.kind == TokenKind::Comment);
-    assert!(!has_comment, "Inline comments should not generate Comment tokens");
-    
+    assert!(
+        !has_comment,
+        "Inline comments should not generate Comment tokens"
+    );
+
     // Check that we have the expected tokens: x, =, 5, newline, y, =, 10, eof
     assert_eq!(tokens.len(), 8);
     assert_eq!(tokens[0].kind, TokenKind::Identifier);
@@ -776,6 +1038,53 @@ fn test_whitespace_only() {
     assert_eq!(tokens.last().unwrap().kind, TokenKind::Eof);
 }
 
+#[test]
+fn test_newlines_inside_brackets_are_ignored() {
+    let source = "f(\n    1,\n    [2,\n     3],  # three\n    {4: 5}\n)\nx";
+    let mut lexer = Lexer::new(source);
+    let tokens = lexer.tokenize().unwrap();
+
+    let newlines = tokens
+        .iter()
+        .filter(|t| t.kind == TokenKind::Newline)
+        .count();
+    assert_eq!(newlines, 1);
+    assert!(!tokens
+        .iter()
+        .any(|t| matches!(t.kind, TokenKind::Indent | TokenKind::Dedent)));
+    assert_eq!(tokens[tokens.len() - 2].lexeme, "x");
+    assert_eq!(tokens[tokens.len() - 2].span.line, 7);
+}
+
+#[test]
+fn test_newline_after_closing_bracket_is_kept() {
+    let source = "a = (1 +\n     2)\nif a:\n    pass";
+    let mut lexer = Lexer::new(source);
+    let kinds: Vec<_> = lexer
+        .tokenize()
+        .unwrap()
+        .into_iter()
+        .map(|t| t.kind)
+        .collect();
+
+    assert_eq!(kinds[7], TokenKind::Newline);
+    assert!(kinds.contains(&TokenKind::Indent));
+}
+
+#[test]
+fn test_explicit_line_continuation() {
+    let source = "total = 1 + \\\n        2\r\nif a and \\\r\n   b:\n    pass";
+    let mut lexer = Lexer::new(source);
+    let tokens = lexer.tokenize().unwrap();
+
+    assert_eq!(tokens[4].kind, TokenKind::Integer(2));
+    assert_eq!(tokens[4].span.line, 2);
+    assert_eq!(tokens[5].kind, TokenKind::Newline);
+    // The continued `if` header has no Newline before `b`
+    assert_eq!(tokens[9].lexeme, "b");
+    assert_eq!(tokens[10].kind, TokenKind::Colon);
+}
+
 // ========== SOURCE LOCATION TESTS ==========
 
 #[test]
@@ -1141,6 +1450,7 @@ fn test_fstring_basic() {
         if let FStringPart::Expression {
             ref code,
             ref format_spec,
+            ..
         } = parts[1]
         {
             assert_eq!(code, "name");
@@ -1203,6 +1513,7 @@ fn test_fstring_with_format_spec() {
         if let FStringPart::Expression {
             ref code,
             ref format_spec,
+            ..
         } = parts[0]
         {
             assert_eq!(code, "value");
@@ -1339,6 +1650,123 @@ fn test_fstring_unmatched_brace_error() {
     }
 }
 
+/// (code, conversion, format spec) of the replacement fields in `source`
+fn fstring_fields(source: &str) -> Vec<(String, Option<char>, Option<String>)> {
+    let tokens = Lexer::new(source).tokenize().unwrap();
+    match &tokens[0].kind {
+        TokenKind::FString(parts) => parts
+            .iter()
+            .filter_map(|part| match part {
+                FStringPart::Expression {
+                    code,
+                    conversion,
+                    format_spec,
+                } => Some((code.clone(), *conversion, format_spec.clone())),
+                FStringPart::Text(_) => None,
+            })
+            .collect(),
+        other => panic!("Expected FString token, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_fstring_triple_quoted() {
+    let source = "f\"\"\"Name: {name}\nSays \"hi\" and {\n    count + 1\n}\"\"\"";
+    let tokens = Lexer::new(source).tokenize().unwrap();
+    assert_eq!(tokens.len(), 2);
+    if let TokenKind::FString(ref parts) = tokens[0].kind {
+        assert_eq!(parts[0], FStringPart::Text("Name: ".to_string()));
+        assert_eq!(
+            parts[2],
+            FStringPart::Text("\nSays \"hi\" and ".to_string())
+        );
+    } else {
+        panic!("Expected FString token");
+    }
+    assert_eq!(
+        fstring_fields(source),
+        vec![
+            ("name".to_string(), None, None),
+            ("count + 1".to_string(), None, None)
+        ]
+    );
+    assert_eq!(fstring_fields("f'''{a}'''")[0].0, "a");
+}
+
+#[test]
+fn test_fstring_quotes_in_expressions() {
+    assert_eq!(fstring_fields(r#"f"{d['key']}""#)[0].0, "d['key']");
+    // The same quotes as the f-string itself, and a '}' inside a string
+    assert_eq!(fstring_fields(r#"f"{d["key"]}""#)[0].0, r#"d["key"]"#);
+    assert_eq!(
+        fstring_fields(r#"f"{'}'.join(items)}""#)[0].0,
+        "'}'.join(items)"
+    );
+    assert_eq!(fstring_fields(r#"f"{'\'' + x}""#)[0].0, r"'\'' + x");
+}
+
+#[test]
+fn test_fstring_colons_and_bangs_inside_brackets() {
+    // Only a ':' or '!' outside brackets starts a conversion or format spec
+    assert_eq!(
+        fstring_fields(r#"f"{items[1:2]} {(lambda x: x)(1)} {a != b}""#),
+        vec![
+            ("items[1:2]".to_string(), None, None),
+            ("(lambda x: x)(1)".to_string(), None, None),
+            ("a != b".to_string(), None, None),
+        ]
+    );
+    assert_eq!(
+        fstring_fields(r#"f"{ {'a': 1}['a'] }""#)[0].0,
+        "{'a': 1}['a']"
+    );
+}
+
+#[test]
+fn test_fstring_conversion() {
+    assert_eq!(
+        fstring_fields(r#"f"{name!r} {name!s:>10} {name!a}""#),
+        vec![
+            ("name".to_string(), Some('r'), None),
+            ("name".to_string(), Some('s'), Some(">10".to_string())),
+            ("name".to_string(), Some('a'), None),
+        ]
+    );
+}
+
+#[test]
+fn test_fstring_invalid_conversion() {
+    let err = Lexer::new(r#"f"{name!x}""#).tokenize().unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("expected 'r', 's' or 'a' after '!'"));
+    let err = Lexer::new(r#"f"{name!rr}""#).tokenize().unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("expected ':' or '}' after the conversion"));
+}
+
+#[test]
+fn test_fstring_nested_format_spec() {
+    assert_eq!(
+        fstring_fields(r#"f"{value:{width}.{precision}f} {x:{'>'}{w}}""#),
+        vec![
+            (
+                "value".to_string(),
+                None,
+                Some("{width}.{precision}f".to_string())
+            ),
+            ("x".to_string(), None, Some("{'>'}{w}".to_string())),
+        ]
+    );
+}
+
+#[test]
+fn test_fstring_field_must_close_on_its_line() {
+    let err = Lexer::new("f\"{name\n}\"").tokenize().unwrap_err();
+    assert!(matches!(err, LexError::UnterminatedString(1, 1)));
+}
+
 #[test]
 fn test_raw_string_basic() {
     let source = r#"r"Hello\nWorld""#;
@@ -1349,7 +1777,7 @@ fn test_raw_string_basic() {
 
     if let TokenKind::RawString(ref value) = tokens[0].kind {
         // Raw strings preserve backslashes literally
-        assert_eq!(value, r"Hello\nWorld");
+        assert_eq!(**value, r"Hello\nWorld");
     } else {
         panic!("Expected raw string token");
     }
@@ -1362,7 +1790,7 @@ fn test_raw_string_backslashes() {
     let tokens = lexer.tokenize().unwrap();
 
     if let TokenKind::RawString(ref value) = tokens[0].kind {
-        assert_eq!(value, r"C:\Users\name\file.txt");
+        assert_eq!(**value, r"C:\Users\name\file.txt");
     } else {
         panic!("Expected raw string token");
     }
@@ -1375,7 +1803,7 @@ fn test_raw_string_single_quotes() {
     let tokens = lexer.tokenize().unwrap();
 
     if let TokenKind::RawString(ref value) = tokens[0].kind {
-        assert_eq!(value, r"Hello\tWorld");
+        assert_eq!(**value, r"Hello\tWorld");
     } else {
         panic!("Expected raw string token");
     }
@@ -1398,7 +1826,7 @@ fn test_raw_string_regex_pattern() {
     let tokens = lexer.tokenize().unwrap();
 
     if let TokenKind::RawString(ref value) = tokens[0].kind {
-        assert_eq!(value, r"\d+\.\d+");
+        assert_eq!(**value, r"\d+\.\d+");
     } else {
         panic!("Expected raw string token");
     }
@@ -1411,7 +1839,7 @@ fn test_raw_string_triple_quoted() {
     let tokens = lexer.tokenize().unwrap();
 
     if let TokenKind::RawString(ref value) = tokens[0].kind {
-        assert_eq!(value, r"Line 1\nLine 2\tTab");
+        assert_eq!(**value, r"Line 1\nLine 2\tTab");
     } else {
         panic!("Expected raw string token");
     }
@@ -1425,7 +1853,7 @@ fn test_raw_string_with_backslash_at_end() {
     let tokens = lexer.tokenize().unwrap();
 
     if let TokenKind::RawString(ref value) = tokens[0].kind {
-        assert_eq!(value, r"path\to\file");
+        assert_eq!(**value, r"path\to\file");
     } else {
         panic!("Expected raw string token");
     }
@@ -1438,7 +1866,7 @@ fn test_raw_string_multiple_backslashes() {
     let tokens = lexer.tokenize().unwrap();
 
     if let TokenKind::RawString(ref value) = tokens[0].kind {
-        assert_eq!(value, r"\\\\");
+        assert_eq!(**value, r"\\\\");
     } else {
         panic!("Expected raw string token");
     }
@@ -1452,14 +1880,14 @@ fn test_raw_vs_regular_string() {
 
     // First is raw string
     if let TokenKind::RawString(ref value) = tokens[0].kind {
-        assert_eq!(value, r"\n"); // Literal backslash-n
+        assert_eq!(**value, r"\n"); // Literal backslash-n
     } else {
         panic!("Expected raw string token");
     }
 
     // Second is regular string
     if let TokenKind::String(ref value) = tokens[1].kind {
-        assert_eq!(value, "\n"); // Actual newline character
+        assert_eq!(**value, "\n"); // Actual newline character
     } else {
         panic!("Expected regular string token");
     }
@@ -1472,7 +1900,7 @@ fn test_raw_string_latex() {
     let tokens = lexer.tokenize().unwrap();
 
     if let TokenKind::RawString(ref value) = tokens[0].kind {
-        assert_eq!(value, r"\alpha + \beta = \gamma");
+        assert_eq!(**value, r"\alpha + \beta = \gamma");
     } else {
         panic!("Expected raw string token");
     }
@@ -1487,7 +1915,7 @@ fn test_byte_string_basic() {
     assert_eq!(tokens.len(), 2); // ByteString + EOF
 
     if let TokenKind::ByteString(ref bytes) = tokens[0].kind {
-        assert_eq!(bytes, b"Hello");
+        assert_eq!(**bytes, b"Hello");
     } else {
         panic!("Expected byte string token");
     }
@@ -1500,7 +1928,7 @@ fn test_byte_string_with_escape_sequences() {
     let tokens = lexer.tokenize().unwrap();
 
     if let TokenKind::ByteString(ref bytes) = tokens[0].kind {
-        assert_eq!(bytes, b"Line1\nLine2\tTab");
+        assert_eq!(**bytes, b"Line1\nLine2\tTab");
     } else {
         panic!("Expected byte string token");
     }
@@ -1513,7 +1941,7 @@ fn test_byte_string_hex_escape() {
     let tokens = lexer.tokenize().unwrap();
 
     if let TokenKind::ByteString(ref bytes) = tokens[0].kind {
-        assert_eq!(bytes, b"Hello");
+        assert_eq!(**bytes, b"Hello");
         assert_eq!(bytes[0], 0x48); // 'H'
     } else {
         panic!("Expected byte string token");
@@ -1527,7 +1955,7 @@ fn test_byte_string_single_quotes() {
     let tokens = lexer.tokenize().unwrap();
 
     if let TokenKind::ByteString(ref bytes) = tokens[0].kind {
-        assert_eq!(bytes, b"data");
+        assert_eq!(**bytes, b"data");
     } else {
         panic!("Expected byte string token");
     }
@@ -1579,7 +2007,7 @@ fn test_byte_string_with_backslashes() {
     let tokens = lexer.tokenize().unwrap();
 
     if let TokenKind::ByteString(ref bytes) = tokens[0].kind {
-        assert_eq!(bytes, b"\\path\\file");
+        assert_eq!(**bytes, b"\\path\\file");
     } else {
         panic!("Expected byte string token");
     }
@@ -1592,7 +2020,7 @@ fn test_byte_string_binary_data() {
     let tokens = lexer.tokenize().unwrap();
 
     if let TokenKind::ByteString(ref bytes) = tokens[0].kind {
-        assert_eq!(bytes, &[0x00, 0x01, 0x02, 0xFF]);
+        assert_eq!(**bytes, &[0x00, 0x01, 0x02, 0xFF]);
     } else {
         panic!("Expected byte string token");
     }
@@ -1623,7 +2051,7 @@ fn test_byte_raw_string_basic_br() {
     assert_eq!(tokens.len(), 2); // ByteRawString + EOF
     if let TokenKind::ByteRawString(bytes) = &tokens[0].kind {
         // Raw string should preserve \n literally (2 chars: backslash and 'n')
-        assert_eq!(bytes, b"Hello\\nWorld");
+        assert_eq!(**bytes, b"Hello\\nWorld");
     } else {
         panic!("Expected ByteRawString token");
     }
@@ -1638,7 +2066,7 @@ fn test_byte_raw_string_basic_rb() {
     assert_eq!(tokens.len(), 2); // ByteRawString + EOF
     if let TokenKind::ByteRawString(bytes) = &tokens[0].kind {
         // Raw string should preserve \n literally
-        assert_eq!(bytes, b"Hello\\nWorld");
+        assert_eq!(**bytes, b"Hello\\nWorld");
     } else {
         panic!("Expected ByteRawString token");
     }
@@ -1653,7 +2081,7 @@ fn test_byte_raw_string_windows_path() {
     assert_eq!(tokens.len(), 2);
     if let TokenKind::ByteRawString(bytes) = &tokens[0].kind {
         // All backslashes preserved literally
-        assert_eq!(bytes, b"C:\\Users\\username\\file.txt");
+        assert_eq!(**bytes, b"C:\\Users\\username\\file.txt");
     } else {
         panic!("Expected ByteRawString token");
     }
@@ -1668,7 +2096,7 @@ fn test_byte_raw_string_regex_pattern() {
     assert_eq!(tokens.len(), 2);
     if let TokenKind::ByteRawString(bytes) = &tokens[0].kind {
         // Regex pattern preserved with backslashes
-        assert_eq!(bytes, b"\\d+\\.\\d+");
+        assert_eq!(**bytes, b"\\d+\\.\\d+");
     } else {
         panic!("Expected ByteRawString token");
     }
@@ -1682,7 +2110,7 @@ fn test_byte_raw_string_single_quotes() {
 
     assert_eq!(tokens.len(), 2);
     if let TokenKind::ByteRawString(bytes) = &tokens[0].kind {
-        assert_eq!(bytes, b"Hello\\tWorld");
+        assert_eq!(**bytes, b"Hello\\tWorld");
     } else {
         panic!("Expected ByteRawString token");
     }
@@ -1696,7 +2124,7 @@ fn test_byte_raw_string_uppercase_br() {
 
     assert_eq!(tokens.len(), 2);
     if let TokenKind::ByteRawString(bytes) = &tokens[0].kind {
-        assert_eq!(bytes, b"Test\\n");
+        assert_eq!(**bytes, b"Test\\n");
     } else {
         panic!("Expected ByteRawString token");
     }
@@ -1710,7 +2138,7 @@ fn test_byte_raw_string_uppercase_rb() {
 
     assert_eq!(tokens.len(), 2);
     if let TokenKind::ByteRawString(bytes) = &tokens[0].kind {
-        assert_eq!(bytes, b"Test\\r\\n");
+        assert_eq!(**bytes, b"Test\\r\\n");
     } else {
         panic!("Expected ByteRawString token");
     }
@@ -1724,7 +2152,7 @@ fn test_byte_raw_string_mixed_case() {
 
     assert_eq!(tokens.len(), 2);
     if let TokenKind::ByteRawString(bytes) = &tokens[0].kind {
-        assert_eq!(bytes, b"Mixed\\nCase");
+        assert_eq!(**bytes, b"Mixed\\nCase");
     } else {
         panic!("Expected ByteRawString token");
     }
@@ -1740,7 +2168,7 @@ fn test_byte_raw_string_triple_quoted() {
     if let TokenKind::ByteRawString(bytes) = &tokens[0].kind {
         // Triple-quoted preserves actual newlines AND backslashes
         let expected = b"Line 1\\nLine 2\\nLine 3";
-        assert_eq!(bytes, expected);
+        assert_eq!(**bytes, expected);
     } else {
         panic!("Expected ByteRawString token");
     }
@@ -1754,7 +2182,7 @@ fn test_byte_raw_string_empty() {
 
     assert_eq!(tokens.len(), 2);
     if let TokenKind::ByteRawString(bytes) = &tokens[0].kind {
-        assert_eq!(bytes, b"");
+        assert_eq!(**bytes, b"");
     } else {
         panic!("Expected ByteRawString token");
     }
@@ -1782,8 +2210,231 @@ fn test_byte_raw_string_hex_notation_preserved() {
     assert_eq!(tokens.len(), 2);
     if let TokenKind::ByteRawString(bytes) = &tokens[0].kind {
         // \x41 stays as literal \x41 (not converted to 'A')
-        assert_eq!(bytes, b"\\x41\\x42\\x43");
+        assert_eq!(**bytes, b"\\x41\\x42\\x43");
     } else {
         panic!("Expected ByteRawString token");
     }
 }
+
+// ============================================================================
+// STREAMING (ITERATOR) API
+// ============================================================================
+
+#[test]
+fn test_iterator_matches_tokenize() {
+    let source = "def f(x):\n    if x:\n        return 1\n    return 2\n# done\nprint(f(1))";
+    let expected = Lexer::new(source).tokenize().unwrap();
+    let streamed: Vec<_> = Lexer::new(source).map(|t| t.unwrap()).collect();
+    assert_eq!(streamed, expected);
+}
+
+#[test]
+fn test_iterator_closes_indentation_before_eof() {
+    let kinds: Vec<_> = Lexer::new("if x:\n    if y:\n        pass")
+        .map(|t| t.unwrap().kind)
+        .collect();
+    assert_eq!(
+        &kinds[kinds.len() - 3..],
+        &[TokenKind::Dedent, TokenKind::Dedent, TokenKind::Eof]
+    );
+}
+
+#[test]
+fn test_iterator_ends_after_eof() {
+    let mut lexer = Lexer::new("x");
+    assert_eq!(lexer.next().unwrap().unwrap().kind, TokenKind::Identifier);
+    assert_eq!(lexer.next().unwrap().unwrap().kind, TokenKind::Eof);
+    assert!(lexer.next().is_none());
+    assert!(lexer.next().is_none());
+}
+
+#[test]
+fn test_iterator_stops_at_first_error() {
+    let mut lexer = Lexer::new("x = 1\ny = $\nz = \"unterminated");
+    let results: Vec<_> = lexer.by_ref().collect();
+
+    // Tokens before the error are delivered, then the error, then nothing
+    assert!(results[..results.len() - 1].iter().all(|r| r.is_ok()));
+    assert!(matches!(
+        results.last(),
+        Some(Err(LexError::UnexpectedCharacter('$', 2, 5)))
+    ));
+    assert!(lexer.next().is_none());
+}
+
+// ============================================================================
+// LOSSLESS (TRIVIA-PRESERVING) MODE
+// ============================================================================
+
+fn lossless_round_trip(source: &str) -> String {
+    let tokens = Lexer::new(source).tokenize_lossless().unwrap();
+    render_lossless(&tokens)
+}
+
+#[test]
+fn test_lossless_round_trip_is_exact() {
+    let sources = [
+        "",
+        "x = 1",
+        "x   =  1   # trailing comment\n",
+        "# header\n\n\ndef f(a, b):\n    # inside\n    return a+b  # sum\n\n   \nprint( f(1,2) )\n",
+        "if x:\n    if y:\n        pass\n\t\nz = 'a\\tb' + \"c\\\"d\"\n",
+        "x = 1\r\ny = 2\r\n",
+        "name = \"héllo wörld\"  # ünïcode\nπ = 3.14\n",
+        "s = f\"{x!r:>10}\"\nb = b'\\x00'\n",
+        "x = 1   \n   ",
+        "f(a,  # first\n  b,\n\n  c)\ny = 1 + \\\n    2\n",
+    ];
+    for source in sources {
+        assert_eq!(lossless_round_trip(source), source, "source: {:?}", source);
+    }
+}
+
+#[test]
+fn test_lossless_tokens_match_tokenize() {
+    let source = "def f(x):\n    return x  # id\n# end\n";
+    let expected = Lexer::new(source).tokenize().unwrap();
+    let lossless = Lexer::new(source).tokenize_lossless().unwrap();
+    let tokens: Vec<_> = lossless.into_iter().map(|t| t.token).collect();
+    assert_eq!(tokens, expected);
+}
+
+#[test]
+fn test_lossless_same_line_trivia_is_trailing() {
+    let tokens = Lexer::new("x  = 1  # note\ny = 2")
+        .tokenize_lossless()
+        .unwrap();
+
+    assert_eq!(tokens[0].text, "x");
+    assert_eq!(tokens[0].trailing[0].text, "  ");
+    assert_eq!(tokens[0].trailing[0].kind, TriviaKind::Whitespace);
+
+    let one = &tokens[2];
+    assert_eq!(one.text, "1");
+    let trailing: Vec<_> = one
+        .trailing
+        .iter()
+        .map(|t| (t.kind, t.text.as_str()))
+        .collect();
+    assert_eq!(
+        trailing,
+        vec![
+            (TriviaKind::Whitespace, "  "),
+            (TriviaKind::Comment, "# note")
+        ]
+    );
+    assert_eq!(one.trailing[1].span.column, 9);
+    assert_eq!(tokens[3].kind(), TokenKind::Newline);
+    assert!(tokens[4].leading.is_empty());
+}
+
+#[test]
+fn test_lossless_indentation_is_leading() {
+    let tokens = Lexer::new("if x:\n    pass\n").tokenize_lossless().unwrap();
+    let indent = tokens
+        .iter()
+        .find(|t| t.kind() == TokenKind::Indent)
+        .unwrap();
+    assert_eq!(indent.text, "");
+    assert_eq!(indent.leading[0].text, "    ");
+    assert_eq!(indent.leading[0].span.line, 2);
+}
+
+#[test]
+fn test_lossless_keeps_exact_literal_text() {
+    let tokens = Lexer::new("s = 'a\\nb'").tokenize_lossless().unwrap();
+    assert_eq!(tokens[2].text, "'a\\nb'");
+    assert_eq!(
+        tokens[2].kind(),
+        TokenKind::String(Box::new("a\nb".to_string()))
+    );
+}
+
+// ============================================================================
+// LOSSY (ERROR-RECOVERING) MODE
+// ============================================================================
+
+#[test]
+fn test_lossy_matches_tokenize_on_valid_input() {
+    let source = "def f(x):\n    return x\n";
+    let expected = Lexer::new(source).tokenize().unwrap();
+    let (tokens, errors) = Lexer::new(source).tokenize_lossy();
+    assert!(errors.is_empty());
+    assert_eq!(tokens, expected);
+}
+
+#[test]
+fn test_lossy_skips_unexpected_characters() {
+    let (tokens, errors) = Lexer::new("x = 1 $ 2\ny = ?").tokenize_lossy();
+
+    assert_eq!(
+        errors,
+        vec![
+            LexError::UnexpectedCharacter('$', 1, 7),
+            LexError::UnexpectedCharacter('?', 2, 5),
+        ]
+    );
+
+    let error_tokens: Vec<_> = tokens
+        .iter()
+        .filter(|t| t.kind == TokenKind::Error)
+        .collect();
+    assert_eq!(error_tokens.len(), 2);
+    assert_eq!(error_tokens[0].lexeme, "$");
+    assert_eq!(error_tokens[0].span.line, 1);
+    assert_eq!(error_tokens[0].span.column, 7);
+    assert_eq!(error_tokens[1].lexeme, "?");
+
+    // Lexing continued after each bad character
+    let kinds: Vec<_> = tokens.iter().map(|t| t.kind.clone()).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            TokenKind::Identifier,
+            TokenKind::Assign,
+            TokenKind::Integer(1),
+            TokenKind::Error,
+            TokenKind::Integer(2),
+            TokenKind::Newline,
+            TokenKind::Identifier,
+            TokenKind::Assign,
+            TokenKind::Error,
+            TokenKind::Eof,
+        ]
+    );
+}
+
+#[test]
+fn test_lossy_recovers_inside_indented_blocks() {
+    let (tokens, errors) = Lexer::new("if x:\n    y = $\n    z = 1\n").tokenize_lossy();
+    assert_eq!(errors.len(), 1);
+    let kinds: Vec<_> = tokens.iter().map(|t| t.kind.clone()).collect();
+    assert!(kinds.contains(&TokenKind::Indent));
+    assert_eq!(
+        &kinds[kinds.len() - 2..],
+        &[TokenKind::Dedent, TokenKind::Eof]
+    );
+    assert_eq!(
+        tokens
+            .iter()
+            .filter(|t| t.kind == TokenKind::Identifier)
+            .count(),
+        3
+    );
+}
+
+#[test]
+fn test_lossy_always_terminates() {
+    let sources = [
+        "$$$",
+        "\"unterminated",
+        "x = '\\q'\n$",
+        "`\n\t\n  ~~ !",
+        "f'{'",
+    ];
+    for source in sources {
+        let (tokens, errors) = Lexer::new(source).tokenize_lossy();
+        assert!(!errors.is_empty(), "source: {:?}", source);
+        assert_eq!(tokens.last().unwrap().kind, TokenKind::Eof);
+    }
+}
@@ -0,0 +1,75 @@
+//! Property-based tests for the lexer: arbitrary input never panics, and
+//! lossless tokens always reproduce the source
+
+use proptest::prelude::*;
+use silk_lexer::{render_lossless, Lexer, TokenKind};
+
+/// Pieces of Silk source, so that generated input reaches deep into the
+/// lexer instead of failing on the first character
+const FRAGMENTS: &[&str] = &[
+    "def", "class", "if", "elif", "else", "for", "in", "while", "return", "lambda", "not", "and",
+    "or", "is", "None", "True", "x", "value", "_name", "0", "42", "3.14", "1e10", "0x1F", "0b101",
+    "0o17", "1_000", "(", ")", "[", "]", "{", "}", ":", ",", ".", "...", "+", "-", "**", "//",
+    "->", ":=", "==", "!=", "<<=", "@", "\\", "#", "# note", " ", "    ", "\t", "\n", "\r\n", "é",
+    "变量", "\u{200b}", "$", "?", "\"", "'", "f\"{",
+];
+
+/// String literals, complete and broken
+const STRINGS: &[&str] = &[
+    "\"text\"",
+    "'a\\n'",
+    "r\"\\d\"",
+    "b\"\\x00\"",
+    "f\"{x}\"",
+    "f\"{x:>4}\"",
+    "\"\"\"doc\n\"\"\"",
+    "\"\\q\"",
+];
+
+fn silk_like() -> impl Strategy<Value = String> {
+    let fragment =
+        prop::sample::select(FRAGMENTS.iter().chain(STRINGS).copied().collect::<Vec<_>>());
+    prop::collection::vec(fragment, 0..60).prop_map(|parts| parts.concat())
+}
+
+/// Lex with error recovery and check the invariants every token stream
+/// keeps, whatever the input
+fn check_lossy(source: &str) -> Result<(), TestCaseError> {
+    let length = source.chars().count();
+    let (tokens, errors) = Lexer::new(source).tokenize_lossy();
+    prop_assert_eq!(
+        tokens.last().map(|token| &token.kind),
+        Some(&TokenKind::Eof)
+    );
+    for token in &tokens {
+        prop_assert!(token.span.start <= token.span.end, "{:?}", token);
+        prop_assert!(token.span.end <= length, "{:?}", token);
+    }
+    let error_tokens = tokens
+        .iter()
+        .filter(|token| token.kind == TokenKind::Error)
+        .count();
+    prop_assert_eq!(error_tokens, errors.len());
+    // The strict lexer fails exactly when recovery had something to recover
+    prop_assert_eq!(Lexer::new(source).tokenize().is_ok(), errors.is_empty());
+    Ok(())
+}
+
+proptest! {
+    #[test]
+    fn test_arbitrary_input_never_panics(source in any::<String>()) {
+        check_lossy(&source)?;
+    }
+
+    #[test]
+    fn test_silk_like_input_never_panics(source in silk_like()) {
+        check_lossy(&source)?;
+    }
+
+    #[test]
+    fn test_lossless_tokens_reproduce_the_source(source in silk_like()) {
+        if let Ok(tokens) = Lexer::new(&source).tokenize_lossless() {
+            prop_assert_eq!(render_lossless(&tokens), source);
+        }
+    }
+}
@@ -0,0 +1,113 @@
+//! Tests for source files and position lookup
+
+use silk_lexer::{display_width, Lexer, SnippetLine, SourceFile, SourceMap, Span, TokenKind};
+
+fn span(start: usize, end: usize) -> Span {
+    Span::new(start, end, 0, 0)
+}
+
+#[test]
+fn test_locations_and_line_text() {
+    let file = SourceFile::new("main.silk", "a = 1\r\nbb = 2\n\nc");
+    assert_eq!(file.line_count(), 4);
+    assert_eq!(file.location(0), (1, 1));
+    assert_eq!(file.location(4), (1, 5));
+    assert_eq!(file.location(7), (2, 1));
+    assert_eq!(file.location(14), (3, 1));
+    assert_eq!(file.location(15), (4, 1));
+    // Past the end: the end of the file
+    assert_eq!(file.location(100), (4, 2));
+
+    assert_eq!(file.line_text(1), Some("a = 1"));
+    assert_eq!(file.line_text(2), Some("bb = 2"));
+    assert_eq!(file.line_text(3), Some(""));
+    assert_eq!(file.line_text(4), Some("c"));
+    assert_eq!(file.line_text(5), None);
+    assert_eq!(file.line_start(2), Some(7));
+}
+
+#[test]
+fn test_offsets_count_characters() {
+    let file = SourceFile::new("main.silk", "s = \"héllo\"\nx");
+    assert_eq!(file.location(12), (2, 1));
+    assert_eq!(file.line_text(1), Some("s = \"héllo\""));
+}
+
+#[test]
+fn test_byte_and_char_offsets() {
+    let file = SourceFile::new("main.silk", "# é\nx = \"日本\"\n");
+    assert_eq!(file.byte_offset(0), Some(0));
+    assert_eq!(file.byte_offset(3), Some(4));
+    assert_eq!(file.byte_offset(4), Some(5));
+    assert_eq!(file.byte_offset(10), Some(13));
+    assert_eq!(file.byte_offset(13), Some(file.text().len()));
+    assert_eq!(file.byte_offset(14), None);
+
+    assert_eq!(file.char_offset(4), Some(3));
+    assert_eq!(file.char_offset(13), Some(10));
+    assert_eq!(file.char_offset(file.text().len()), Some(13));
+    // Inside 'é'
+    assert_eq!(file.char_offset(3), None);
+    assert_eq!(file.char_offset(100), None);
+}
+
+#[test]
+fn test_snippet_highlights_display_columns() {
+    let file = SourceFile::new("main.silk", "\tname = \"日本\" + count\n");
+    let offset = file.text().chars().position(|c| c == 'c').unwrap();
+    assert_eq!(
+        file.snippet(span(offset, offset + 5)),
+        vec![SnippetLine {
+            line: 1,
+            text: "    name = \"日本\" + count".to_string(),
+            highlight: 20..25,
+        }]
+    );
+    assert_eq!(display_width("日本"), 4);
+    assert_eq!(display_width("e\u{301}"), 1);
+}
+
+#[test]
+fn test_snippet_of_multiline_and_empty_spans() {
+    let file = SourceFile::new("main.silk", "if x:\n    pass\ny\n");
+    let lines = file.snippet(span(0, 15));
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0].highlight, 0..5);
+    assert_eq!(lines[1].highlight, 0..8);
+
+    // An empty span at the end of a line still gets a marker
+    assert_eq!(file.snippet(span(5, 5))[0].highlight, 5..6);
+}
+
+#[test]
+fn test_source_map_finds_files_by_name() {
+    let mut sources = SourceMap::new();
+    let main = sources.add("main.silk", "import util\n");
+    let util = sources.add("util.silk", "x = 1\n");
+    assert_ne!(main, util);
+    assert_eq!(sources.find("util.silk"), Some(util));
+    assert_eq!(sources.file(main).name(), "main.silk");
+    assert!(sources.get("other.silk").is_none());
+
+    // Adding a name again replaces its text
+    assert_eq!(sources.add("util.silk", "x = 2\n"), util);
+    assert_eq!(sources.len(), 2);
+    assert_eq!(sources.file(util).text(), "x = 2\n");
+}
+
+#[test]
+fn test_lexer_positions_agree_with_source_file() {
+    let source = "def f(α):\r\n    return α + \"é\" * 2\n";
+    let file = SourceFile::new("main.silk", source);
+    let tokens = Lexer::new(source).tokenize().unwrap();
+    for token in tokens.iter().filter(|t| t.kind != TokenKind::Eof) {
+        assert_eq!(
+            (token.span.line, token.span.column),
+            file.location(token.span.start),
+            "{:?}",
+            token
+        );
+    }
+    let star = tokens.iter().find(|t| t.kind == TokenKind::Star).unwrap();
+    assert_eq!((star.span.line, star.span.column), (2, 20));
+}
@@ -0,0 +1,38 @@
+//! Tests for "did you mean" suggestions
+
+use silk_lexer::suggest::{closest_match, edit_distance};
+
+#[test]
+fn test_edit_distance() {
+    assert_eq!(edit_distance("while", "while"), 0);
+    assert_eq!(edit_distance("", "abc"), 3);
+    assert_eq!(edit_distance("retrun", "return"), 1);
+    assert_eq!(edit_distance("whlie", "while"), 1);
+    assert_eq!(edit_distance("clas", "class"), 1);
+    assert_eq!(edit_distance("kitten", "sitting"), 3);
+}
+
+#[test]
+fn test_closest_match() {
+    let keywords = ["def", "del", "return", "while", "with"];
+    assert_eq!(closest_match("retrun", keywords), Some("return"));
+    assert_eq!(closest_match("whle", keywords), Some("while"));
+    // Ties go to the earliest candidate
+    assert_eq!(closest_match("dex", keywords), Some("def"));
+    // Too far away, or the name itself
+    assert_eq!(closest_match("banana", keywords), None);
+    assert_eq!(closest_match("while", keywords), None);
+}
+
+#[test]
+fn test_one_character_names() {
+    // Every other one-character name is a single edit away
+    assert_eq!(closest_match("k", ["c", "x", "kk"]), None);
+    assert_eq!(closest_match("x", ["X"]), Some("X"));
+    assert_eq!(closest_match("fi", ["if"]), Some("if"));
+}
+
+#[test]
+fn test_case_difference_wins() {
+    assert_eq!(closest_match("count", ["counts", "Count"]), Some("Count"));
+}
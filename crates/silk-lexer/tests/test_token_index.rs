@@ -0,0 +1,55 @@
+//! Tests for offset to token lookup
+
+use silk_lexer::{Lexer, TokenIndex, TokenKind};
+
+#[test]
+fn test_token_at_offset() {
+    let tokens = Lexer::new("total = value + 1\n").tokenize().unwrap();
+    let index = TokenIndex::new(&tokens);
+    let lexeme_at = |offset| index.at(offset).map(|i| tokens[i].lexeme.to_string());
+
+    assert_eq!(lexeme_at(0).as_deref(), Some("total"));
+    assert_eq!(lexeme_at(4).as_deref(), Some("total"));
+    // Whitespace between tokens belongs to none of them
+    assert_eq!(lexeme_at(5), None);
+    assert_eq!(lexeme_at(6).as_deref(), Some("="));
+    assert_eq!(lexeme_at(10).as_deref(), Some("value"));
+    assert_eq!(lexeme_at(17).as_deref(), Some("\n"));
+    assert_eq!(lexeme_at(18), None);
+}
+
+#[test]
+fn test_zero_width_tokens_cover_nothing() {
+    let source = "if x:\n    y = 1\nz = 2\n";
+    let tokens = Lexer::new(source).tokenize().unwrap();
+    let index = TokenIndex::new(&tokens);
+    // `z` starts where the zero-width `Dedent` before it is
+    let z = index.at(16).unwrap();
+    assert_eq!(tokens[z - 1].kind, TokenKind::Dedent);
+    assert_eq!(tokens[z].lexeme, "z");
+    assert_eq!(
+        tokens[index.at(source.len() - 1).unwrap()].kind,
+        TokenKind::Newline
+    );
+}
+
+#[test]
+fn test_tokens_around_offset() {
+    let tokens = Lexer::new("f(a, b)").tokenize().unwrap();
+    let index = TokenIndex::new(&tokens);
+    assert_eq!(index.first_from(0), 0);
+    // The first token at or after the space is `b`
+    assert_eq!(tokens[index.first_from(4)].lexeme, "b");
+    assert_eq!(tokens[index.before(4).unwrap()].kind, TokenKind::Comma);
+    assert_eq!(index.before(0), None);
+    assert_eq!(index.first_from(100), tokens.len());
+}
+
+#[test]
+fn test_lossy_token_streams_are_indexed() {
+    let (tokens, errors) = Lexer::new("a = $ + b").tokenize_lossy();
+    assert_eq!(errors.len(), 1);
+    let index = TokenIndex::new(&tokens);
+    assert_eq!(tokens[index.at(4).unwrap()].kind, TokenKind::Error);
+    assert_eq!(tokens[index.at(8).unwrap()].lexeme, "b");
+}
@@ -0,0 +1,151 @@
+//! Everything the editor features need to know about one document
+//!
+//! A document is lexed without stopping at errors, so lexical features keep
+//! working while the user types. When it also parses, it is analyzed and
+//! the symbol table and expression types are kept for position queries.
+
+use crate::line_index::LineIndex;
+use silk_compiler::Compiler;
+use silk_lexer::{Lexer, Span, Token, TokenIndex, TokenKind};
+use silk_semantic::{ClassInfo, SemanticAnalyzer, SymbolId, SymbolKind, Type, TypedProgram};
+
+/// Analysis results for one version of a document
+pub struct Analysis {
+    /// Offset and position conversion for the document
+    pub line_index: LineIndex,
+    /// Tokens of the document, including `Error` tokens for unlexable input
+    pub tokens: Vec<Token>,
+    /// The analyzed program with its expression types and diagnostics;
+    /// `None` when the document does not parse
+    pub typed: Option<TypedProgram>,
+    /// The analyzer after the analysis, for its symbol table and classes
+    pub analyzer: SemanticAnalyzer,
+}
+
+impl Analysis {
+    /// Lex, parse and analyze a document with the compiler's features and
+    /// lints
+    pub fn new(compiler: &Compiler, source: &str) -> Self {
+        let (tokens, _) = Lexer::new(source).tokenize_lossy();
+        let mut analyzer = compiler.semantic_analyzer();
+        let typed = compiler
+            .parse(source)
+            .ok()
+            .map(|program| analyzer.analyze_typed(program));
+        Self {
+            line_index: LineIndex::new(source),
+            tokens,
+            typed,
+            analyzer,
+        }
+    }
+
+    /// Offset to token lookup over `tokens`
+    pub fn token_index(&self) -> TokenIndex<'_> {
+        TokenIndex::new(&self.tokens)
+    }
+
+    /// Index of the first identifier token spelled `name` that starts at
+    /// or after `offset`
+    ///
+    /// Definitions are recorded with the span of the whole construct
+    /// (`def name(...)`, `*args`, `import a as name`), so this finds the
+    /// token of the name itself.
+    pub fn identifier_after(&self, offset: usize, name: &str) -> Option<usize> {
+        let first = self.token_index().first_from(offset);
+        (first..self.tokens.len()).find(|&i| {
+            self.tokens[i].kind == TokenKind::Identifier && self.tokens[i].lexeme == name
+        })
+    }
+
+    /// Symbol an identifier token defines or refers to
+    pub fn symbol_id_of_token(&self, index: usize) -> Option<SymbolId> {
+        let token = self.tokens.get(index)?;
+        let symbols = self.analyzer.symbol_table();
+        if let Some(id) = symbols.symbol_id_at(token.span) {
+            return Some(id.clone());
+        }
+        // A definition token: the first matching identifier of a symbol's
+        // definition span
+        symbols
+            .scopes()
+            .iter()
+            .enumerate()
+            .filter_map(|(scope, s)| Some((scope, s.lookup_local(&token.lexeme)?)))
+            .find(|(_, symbol)| {
+                self.identifier_after(symbol.span.start, &symbol.name) == Some(index)
+            })
+            .map(|(scope, symbol)| SymbolId {
+                scope,
+                name: symbol.name.clone(),
+            })
+    }
+
+    /// Class whose members an expression has: the class of an instance,
+    /// or the class itself for a class name
+    pub fn class_of(&self, span: Span, ty: &Type) -> Option<&ClassInfo> {
+        match ty {
+            Type::Instance(qualname) => self.analyzer.class_info(qualname),
+            _ => match self.analyzer.symbol_table().symbol_at(span) {
+                Some(symbol) if symbol.kind == SymbolKind::Class => {
+                    self.analyzer.class_info_of(symbol)
+                }
+                _ => None,
+            },
+        }
+    }
+
+    /// A class and its bases in method resolution order
+    pub fn method_resolution_order<'a>(&'a self, class: &'a ClassInfo) -> Vec<&'a ClassInfo> {
+        if class.mro.is_empty() {
+            return vec![class];
+        }
+        class
+            .mro
+            .iter()
+            .filter_map(|name| self.analyzer.class_info(name))
+            .collect()
+    }
+}
+
+/// Analyze the first of several versions of a document that parses; each
+/// comes with an offset, which is returned with its analysis
+///
+/// Editor requests arrive while code is being typed, so position features
+/// try the document as it is and then with edits that make it parse.
+pub fn first_parsing(
+    compiler: &Compiler,
+    attempts: Vec<(String, usize)>,
+) -> Option<(Analysis, usize)> {
+    attempts
+        .into_iter()
+        .map(|(source, offset)| (Analysis::new(compiler, &source), offset))
+        .find(|(analysis, _)| analysis.typed.is_some())
+}
+
+/// The document with the line containing `offset` replaced by `pass` at
+/// the same indentation, and the offset of that `pass`
+///
+/// The rest of the document keeps its scopes, so the names visible on the
+/// line can still be found when the line itself does not parse.
+pub fn stub_line(chars: &[char], offset: usize) -> (String, usize) {
+    let line_start = chars[..offset]
+        .iter()
+        .rposition(|&c| c == '\n')
+        .map_or(0, |i| i + 1);
+    let line_end = chars[offset..]
+        .iter()
+        .position(|&c| c == '\n')
+        .map_or(chars.len(), |i| offset + i);
+    let indent = chars[line_start..line_end]
+        .iter()
+        .take_while(|c| matches!(c, ' ' | '\t'))
+        .count();
+    let text = |range: &[char]| range.iter().collect::<String>();
+    let source = format!(
+        "{}pass{}",
+        text(&chars[..line_start + indent]),
+        text(&chars[line_end..])
+    );
+    (source, line_start + indent)
+}
@@ -0,0 +1,261 @@
+//! Code completion (`textDocument/completion`)
+//!
+//! After `obj.` the members of the class of `obj` are offered, inherited
+//! ones included: methods and properties from the class member tables. In
+//! any other position the candidates are the symbols visible at the cursor,
+//! then the builtins, then the keywords. Candidates are filtered by the
+//! part of the name already typed.
+//!
+//! The code being completed rarely parses: `obj.` is missing its attribute
+//! and a half-typed line is usually incomplete. A placeholder name is put
+//! after a trailing dot, and when the document still does not parse the
+//! line with the cursor is replaced by `pass`, so the rest of the document
+//! can still be analyzed.
+
+use crate::analysis::{self, Analysis};
+use silk_compiler::Compiler;
+use silk_lexer::{TokenKind, KEYWORDS};
+use silk_semantic::builtins::{BUILTIN_EXCEPTIONS, BUILTIN_FUNCTIONS};
+use silk_semantic::{Symbol, SymbolKind, Type};
+use std::collections::HashSet;
+
+/// Name inserted after a dot that has nothing after it yet
+const PLACEHOLDER: &str = "__completion__";
+
+/// What a completion candidate is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompletionKind {
+    Variable,
+    Parameter,
+    Function,
+    Method,
+    Property,
+    Class,
+    Module,
+    Keyword,
+}
+
+impl CompletionKind {
+    /// LSP `CompletionItemKind`
+    pub fn lsp_kind(self) -> u32 {
+        match self {
+            CompletionKind::Method => 2,
+            CompletionKind::Function => 3,
+            CompletionKind::Variable | CompletionKind::Parameter => 6,
+            CompletionKind::Class => 7,
+            CompletionKind::Module => 9,
+            CompletionKind::Property => 10,
+            CompletionKind::Keyword => 14,
+        }
+    }
+}
+
+/// A completion candidate
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompletionItem {
+    pub label: String,
+    pub kind: CompletionKind,
+    /// Type or signature, when known
+    pub detail: Option<String>,
+}
+
+impl CompletionItem {
+    fn new(label: impl Into<String>, kind: CompletionKind, detail: Option<String>) -> Self {
+        Self {
+            label: label.into(),
+            kind,
+            detail,
+        }
+    }
+}
+
+/// Completion candidates at a character offset, best first
+pub fn completions(compiler: &Compiler, source: &str, offset: usize) -> Vec<CompletionItem> {
+    let chars: Vec<char> = source.chars().collect();
+    let offset = offset.min(chars.len());
+    let start = (0..offset)
+        .rev()
+        .find(|&i| !is_identifier_char(chars[i]))
+        .map_or(0, |i| i + 1);
+    let prefix: String = chars[start..offset].iter().collect();
+    if prefix.starts_with(|c: char| c.is_ascii_digit()) {
+        return Vec::new();
+    }
+
+    let after_dot = start > 0 && chars[start - 1] == '.';
+    let Some((analysis, scope_offset)) = analyze_around(compiler, &chars, start, offset, after_dot)
+    else {
+        return if after_dot {
+            Vec::new()
+        } else {
+            filter(global_candidates(), &prefix)
+        };
+    };
+    if in_string_or_comment(&analysis, offset) {
+        return Vec::new();
+    }
+
+    let candidates = if after_dot {
+        member_candidates(&analysis, start - 1)
+    } else {
+        let mut candidates = symbol_candidates(&analysis, scope_offset);
+        candidates.extend(global_candidates());
+        candidates
+    };
+    filter(candidates, &prefix)
+}
+
+fn is_identifier_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Analyze the document as it is, or with the edits that make the code at
+/// the cursor parse, together with the offset of the cursor in the analyzed
+/// text; `None` if nothing parses
+fn analyze_around(
+    compiler: &Compiler,
+    chars: &[char],
+    start: usize,
+    offset: usize,
+    after_dot: bool,
+) -> Option<(Analysis, usize)> {
+    let text = |range: &[char]| range.iter().collect::<String>();
+    let mut attempts = Vec::new();
+    if after_dot && start == offset {
+        let source = format!(
+            "{}{}{}",
+            text(&chars[..offset]),
+            PLACEHOLDER,
+            text(&chars[offset..])
+        );
+        attempts.push((source, offset));
+    } else {
+        attempts.push((text(chars), offset));
+    }
+    if !after_dot {
+        attempts.push(analysis::stub_line(chars, offset));
+    }
+    analysis::first_parsing(compiler, attempts)
+}
+
+fn in_string_or_comment(analysis: &Analysis, offset: usize) -> bool {
+    analysis.token_index().at(offset).is_some_and(|index| {
+        let token = &analysis.tokens[index];
+        token.span.start < offset
+            && matches!(
+                token.kind,
+                TokenKind::String(_)
+                    | TokenKind::RawString(_)
+                    | TokenKind::ByteString(_)
+                    | TokenKind::ByteRawString(_)
+                    | TokenKind::FString(_)
+                    | TokenKind::Comment
+            )
+    })
+}
+
+/// Members of the class of the expression that ends at `dot`
+fn member_candidates(analysis: &Analysis, dot: usize) -> Vec<CompletionItem> {
+    let Some(typed) = &analysis.typed else {
+        return Vec::new();
+    };
+    // The outermost expression ending at the dot: on `a.b.`, `a.b`
+    let Some((span, ty)) = typed
+        .expression_types()
+        .into_iter()
+        .find(|(span, _)| span.end == dot)
+    else {
+        return Vec::new();
+    };
+
+    let Some(class) = analysis.class_of(span, ty) else {
+        return Vec::new();
+    };
+
+    let mut seen = HashSet::new();
+    let mut candidates = Vec::new();
+    for class in analysis.method_resolution_order(class) {
+        let mut methods: Vec<_> = class.methods.values().collect();
+        methods.sort_by(|a, b| a.name.cmp(&b.name));
+        for method in methods {
+            if !seen.insert(method.name.clone()) {
+                continue;
+            }
+            candidates.push(if method.kind.is_property() {
+                let detail = known(&method.return_type).map(|ty| ty.to_string());
+                CompletionItem::new(&method.name, CompletionKind::Property, detail)
+            } else {
+                let detail = signature_detail(&method.params.to_string(), &method.return_type);
+                CompletionItem::new(&method.name, CompletionKind::Method, Some(detail))
+            });
+        }
+    }
+    candidates
+}
+
+/// Symbols visible at the cursor
+fn symbol_candidates(analysis: &Analysis, offset: usize) -> Vec<CompletionItem> {
+    analysis
+        .analyzer
+        .symbol_table()
+        .visible_symbols(offset)
+        .into_iter()
+        .filter(|symbol| symbol.name != PLACEHOLDER)
+        .map(symbol_item)
+        .collect()
+}
+
+fn symbol_item(symbol: &Symbol) -> CompletionItem {
+    let kind = match symbol.kind {
+        SymbolKind::Variable => CompletionKind::Variable,
+        SymbolKind::Parameter => CompletionKind::Parameter,
+        SymbolKind::Function => CompletionKind::Function,
+        SymbolKind::Class => CompletionKind::Class,
+        SymbolKind::Module => CompletionKind::Module,
+    };
+    let detail = match &symbol.ty {
+        Type::Function {
+            params: Some(params),
+            return_type,
+        } => Some(signature_detail(&params.to_string(), return_type)),
+        ty => known(ty).map(|ty| ty.to_string()),
+    };
+    CompletionItem::new(&symbol.name, kind, detail)
+}
+
+/// Builtins and keywords, which are valid anywhere
+fn global_candidates() -> Vec<CompletionItem> {
+    let functions = BUILTIN_FUNCTIONS
+        .iter()
+        .map(|name| CompletionItem::new(*name, CompletionKind::Function, None));
+    let exceptions = BUILTIN_EXCEPTIONS
+        .iter()
+        .map(|name| CompletionItem::new(*name, CompletionKind::Class, None));
+    let keywords = KEYWORDS
+        .iter()
+        .map(|name| CompletionItem::new(*name, CompletionKind::Keyword, None));
+    functions.chain(exceptions).chain(keywords).collect()
+}
+
+/// Candidates starting with the typed prefix, each label once; private
+/// names (`_x`, `__x__`) only when the prefix starts with `_`
+fn filter(candidates: Vec<CompletionItem>, prefix: &str) -> Vec<CompletionItem> {
+    let mut seen = HashSet::new();
+    candidates
+        .into_iter()
+        .filter(|item| item.label.starts_with(prefix))
+        .filter(|item| prefix.starts_with('_') || !item.label.starts_with('_'))
+        .filter(|item| seen.insert(item.label.clone()))
+        .collect()
+}
+
+fn known(ty: &Type) -> Option<&Type> {
+    (*ty != Type::Unknown).then_some(ty)
+}
+
+fn signature_detail(params: &str, return_type: &Type) -> String {
+    match known(return_type) {
+        Some(ty) => format!("{} -> {}", params, ty),
+        None => params.to_string(),
+    }
+}
@@ -0,0 +1,258 @@
+//! Hover information (`textDocument/hover`)
+//!
+//! Hovering a name shows what it is and its inferred type: the declaration
+//! line of a function, method or class with its docstring, or the type of a
+//! variable, parameter or attribute at that point of the program (so a
+//! narrowed `x` inside `if x is not None:` shows the narrowed type).
+//! Hovering a literal or another part of an expression shows the type of
+//! the innermost expression there.
+//!
+//! The name under the cursor is resolved with the symbol table; the
+//! expression under the cursor and the definitions that hold docstrings
+//! are found with the node index of the typed program.
+
+use crate::analysis::Analysis;
+use silk_ast::{ExpressionKind, NodeIndex, StatementKind};
+use silk_compiler::Compiler;
+use silk_lexer::{Span, Token, TokenKind};
+use silk_semantic::builtins;
+use silk_semantic::{docstring_of, short_name, MethodInfo, ScopeKind, Symbol, SymbolKind, Type};
+
+/// What to show for the position under the cursor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hover {
+    /// Markdown: the declaration or type in a code block, then the
+    /// docstring if there is one
+    pub contents: String,
+    /// Character range of the hovered token or expression
+    pub span: Span,
+}
+
+/// Hover information at a character offset
+pub fn hover(compiler: &Compiler, source: &str, offset: usize) -> Option<Hover> {
+    let analysis = Analysis::new(compiler, source);
+    let typed = analysis.typed.as_ref()?;
+    let index = typed.node_index();
+    let token_index = analysis.token_index().at(offset)?;
+    let token = &analysis.tokens[token_index];
+
+    if token.kind != TokenKind::Identifier {
+        return expression_hover(&analysis, &index, token);
+    }
+    let after_dot = token_index > 0 && analysis.tokens[token_index - 1].kind == TokenKind::Dot;
+    let (declaration, docstring) = if after_dot {
+        attribute_hover(&analysis, &index, token)?
+    } else {
+        name_hover(&analysis, &index, token, token_index)?
+    };
+    Some(Hover {
+        contents: markdown(&declaration, docstring.as_deref()),
+        span: token.span,
+    })
+}
+
+/// Declaration line and docstring of a name
+fn name_hover(
+    analysis: &Analysis,
+    index: &NodeIndex,
+    token: &Token,
+    token_index: usize,
+) -> Option<(String, Option<String>)> {
+    let symbols = analysis.analyzer.symbol_table();
+    let name = token.lexeme.as_str();
+    let Some(id) = analysis.symbol_id_of_token(token_index) else {
+        // Builtins and names the analysis does not record a reference for,
+        // such as those in type annotations
+        if let Some(symbol) = symbols
+            .visible_symbols(token.span.start)
+            .into_iter()
+            .find(|symbol| symbol.name == name)
+        {
+            return Some(symbol_hover(analysis, index, symbol, false, &symbol.ty));
+        }
+        return if builtins::is_builtin_exception(name) {
+            Some((format!("(class) {}", name), None))
+        } else if builtins::is_builtin_function(name) {
+            Some((format!("(function) {}", name), None))
+        } else {
+            None
+        };
+    };
+
+    let symbol = symbols.symbol(&id)?;
+    let in_class = symbols.scopes()[id.scope].kind == ScopeKind::Class;
+    // The type at this point of the program, which may be narrowed
+    let ty = analysis
+        .typed
+        .as_ref()
+        .and_then(|typed| typed.type_at(token.span))
+        .unwrap_or(&symbol.ty);
+    Some(symbol_hover(analysis, index, symbol, in_class, ty))
+}
+
+fn symbol_hover(
+    analysis: &Analysis,
+    index: &NodeIndex,
+    symbol: &Symbol,
+    in_class: bool,
+    ty: &Type,
+) -> (String, Option<String>) {
+    let name = &symbol.name;
+    match symbol.kind {
+        SymbolKind::Function => {
+            let label = if in_class { "method" } else { "function" };
+            let declaration = format!("({}) def {}{}", label, name, function_suffix(ty));
+            (declaration, docstring_at(index, symbol.span))
+        }
+        SymbolKind::Class => {
+            let bases = analysis
+                .analyzer
+                .class_info_of(symbol)
+                .map(|class| {
+                    let bases: Vec<&str> =
+                        class.bases.iter().map(|base| short_name(base)).collect();
+                    bases.join(", ")
+                })
+                .filter(|bases| !bases.is_empty())
+                .map_or(String::new(), |bases| format!("({})", bases));
+            let declaration = format!("(class) class {}{}", name, bases);
+            (declaration, docstring_at(index, symbol.span))
+        }
+        SymbolKind::Module => (format!("(module) {}", name), None),
+        SymbolKind::Parameter => (typed_name("parameter", name, ty), None),
+        SymbolKind::Variable => (typed_name("variable", name, ty), None),
+    }
+}
+
+/// Declaration line and docstring of the attribute `token` in `value.name`
+fn attribute_hover(
+    analysis: &Analysis,
+    index: &NodeIndex,
+    token: &Token,
+) -> Option<(String, Option<String>)> {
+    let typed = analysis.typed.as_ref()?;
+    let (attribute, value, attr) = index
+        .nodes_at(token.span.start)
+        .into_iter()
+        .rev()
+        .filter_map(|node| node.as_expression())
+        .filter(|expr| expr.span.end == token.span.end)
+        .find_map(|expr| match &expr.kind {
+            ExpressionKind::Attribute { value, attr } if *attr == token.lexeme => {
+                Some((expr, value, attr))
+            }
+            _ => None,
+        })?;
+
+    let method = typed
+        .type_of(value)
+        .and_then(|ty| analysis.class_of(value.span, ty))
+        .and_then(|class| {
+            analysis
+                .method_resolution_order(class)
+                .into_iter()
+                .find_map(|class| class.method(attr))
+        });
+    match method {
+        Some(method) => Some(method_hover(index, method)),
+        // Instance attributes assigned in methods are not class members
+        None => {
+            let ty = typed.type_of(attribute)?;
+            Some((typed_name("attribute", attr, ty), None))
+        }
+    }
+}
+
+fn method_hover(index: &NodeIndex, method: &MethodInfo) -> (String, Option<String>) {
+    let declaration = if method.kind.is_property() {
+        typed_name("property", &method.name, &method.return_type)
+    } else {
+        let ty = Type::Function {
+            params: Some(method.params.clone()),
+            return_type: Box::new(method.return_type.clone()),
+        };
+        format!("(method) def {}{}", method.name, function_suffix(&ty))
+    };
+    (declaration, docstring_at(index, method.span))
+}
+
+/// Type of the innermost expression at a token that is not a name
+fn expression_hover(analysis: &Analysis, index: &NodeIndex, token: &Token) -> Option<Hover> {
+    let typed = analysis.typed.as_ref()?;
+    let expr = index.expression_at(token.span.start)?;
+    let ty = typed.type_of(expr).filter(|ty| **ty != Type::Unknown)?;
+    Some(Hover {
+        contents: markdown(&ty.to_string(), None),
+        span: expr.span,
+    })
+}
+
+/// `(params) -> return type` of a function type
+fn function_suffix(ty: &Type) -> String {
+    match ty {
+        Type::Function {
+            params,
+            return_type,
+        } => {
+            let params = params
+                .as_ref()
+                .map_or_else(|| "()".to_string(), |params| params.to_string());
+            match **return_type {
+                Type::Unknown => params,
+                ref ty => format!("{} -> {}", params, ty),
+            }
+        }
+        _ => "()".to_string(),
+    }
+}
+
+/// `(kind) name: type`, without the type when it is unknown
+fn typed_name(kind: &str, name: &str, ty: &Type) -> String {
+    match ty {
+        Type::Unknown => format!("({}) {}", kind, name),
+        ty => format!("({}) {}: {}", kind, name, ty),
+    }
+}
+
+/// Docstring of the function or class defined by the statement at `span`
+fn docstring_at(index: &NodeIndex, span: Span) -> Option<String> {
+    let stmt = index.statement(span)?;
+    let body = match &stmt.kind {
+        StatementKind::FunctionDef { body, .. } | StatementKind::ClassDef { body, .. } => body,
+        _ => return None,
+    };
+    docstring_of(body).map(|(text, _)| clean_docstring(text))
+}
+
+/// A docstring without the indentation of its continuation lines and
+/// without leading and trailing blank lines
+fn clean_docstring(text: &str) -> String {
+    let mut lines: Vec<&str> = text.lines().collect();
+    let indent = lines
+        .iter()
+        .skip(1)
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    for line in lines.iter_mut().skip(1) {
+        *line = line.get(indent..).unwrap_or("").trim_end();
+    }
+    if let Some(first) = lines.first_mut() {
+        *first = first.trim();
+    }
+    while lines.last().is_some_and(|line| line.is_empty()) {
+        lines.pop();
+    }
+    let start = lines.iter().take_while(|line| line.is_empty()).count();
+    lines[start..].join("\n")
+}
+
+fn markdown(code: &str, docstring: Option<&str>) -> String {
+    match docstring {
+        Some(docstring) if !docstring.is_empty() => {
+            format!("```silk\n{}\n```\n---\n{}", code, docstring)
+        }
+        _ => format!("```silk\n{}\n```", code),
+    }
+}
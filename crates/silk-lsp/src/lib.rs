@@ -0,0 +1,38 @@
+//! Silk language server
+//!
+//! Implements the Language Server Protocol over stdin/stdout:
+//! - Diagnostics from `silk check`, published on open and change
+//! - Semantic tokens classified with the results of semantic analysis
+//! - Completion of names in scope, builtins, keywords and class members
+//! - Signature help inside calls
+//! - Hover with the inferred type and docstring of the name under the cursor
+//!
+//! Started by `silk lsp`.
+
+pub mod analysis;
+pub mod completion;
+pub mod hover;
+pub mod line_index;
+pub mod protocol;
+pub mod semantic_tokens;
+pub mod server;
+pub mod signature_help;
+
+pub use analysis::Analysis;
+pub use completion::{completions, CompletionItem, CompletionKind};
+pub use hover::{hover, Hover};
+pub use line_index::{LineIndex, Position};
+pub use semantic_tokens::{semantic_tokens, SemanticToken, TokenType};
+pub use server::Server;
+pub use signature_help::{signature_help, SignatureHelp};
+
+use silk_compiler::Compiler;
+use std::io;
+
+/// Serve the protocol on stdin/stdout until the client exits; returns
+/// whether the client shut the server down cleanly
+pub fn run_stdio(compiler: Compiler) -> io::Result<bool> {
+    let stdin = io::stdin();
+    let stdout = io::stdout();
+    Server::new(compiler).run(&mut stdin.lock(), &mut stdout.lock())
+}
@@ -0,0 +1,178 @@
+//! Conversion between source offsets and LSP positions
+//!
+//! Spans count characters from the start of the file, while LSP positions
+//! are a zero-based line and a column in UTF-16 code units. A `LineIndex`
+//! is built once per document version and converts both ways.
+
+use silk_lexer::Span;
+
+/// A position in a document as LSP sends it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Position {
+    /// Zero-based line
+    pub line: u32,
+    /// Zero-based column in UTF-16 code units
+    pub character: u32,
+}
+
+impl Position {
+    pub fn new(line: u32, character: u32) -> Self {
+        Self { line, character }
+    }
+
+    /// JSON form, `{"line": .., "character": ..}`
+    pub fn to_json(self) -> serde_json::Value {
+        serde_json::json!({ "line": self.line, "character": self.character })
+    }
+
+    /// Read a position from its JSON form
+    pub fn from_json(value: &serde_json::Value) -> Option<Self> {
+        Some(Self {
+            line: u32::try_from(value.get("line")?.as_u64()?).ok()?,
+            character: u32::try_from(value.get("character")?.as_u64()?).ok()?,
+        })
+    }
+}
+
+/// Line starts of a document, for offset and position conversion
+#[derive(Debug, Clone)]
+pub struct LineIndex {
+    chars: Vec<char>,
+    /// Character offset where each line starts
+    line_starts: Vec<usize>,
+}
+
+impl LineIndex {
+    pub fn new(source: &str) -> Self {
+        let chars: Vec<char> = source.chars().collect();
+        let mut line_starts = vec![0];
+        line_starts.extend(
+            chars
+                .iter()
+                .enumerate()
+                .filter(|(_, c)| **c == '\n')
+                .map(|(i, _)| i + 1),
+        );
+        Self { chars, line_starts }
+    }
+
+    /// Number of characters in the document
+    pub fn len(&self) -> usize {
+        self.chars.len()
+    }
+
+    /// Whether the document is empty
+    pub fn is_empty(&self) -> bool {
+        self.chars.is_empty()
+    }
+
+    /// Position of a character offset; offsets past the end give the end
+    /// of the document
+    pub fn position(&self, offset: usize) -> Position {
+        let offset = offset.min(self.chars.len());
+        let line = self.line_starts.partition_point(|&start| start <= offset) - 1;
+        let character = utf16_len(&self.chars[self.line_starts[line]..offset]);
+        Position::new(line as u32, character)
+    }
+
+    /// Character offset of a position; columns past the end of the line
+    /// give the end of the line, lines past the end give the end of the
+    /// document
+    pub fn offset(&self, position: Position) -> usize {
+        let Some(&start) = self.line_starts.get(position.line as usize) else {
+            return self.chars.len();
+        };
+        let end = self.line_end(position.line as usize);
+        let mut units = 0;
+        for (i, c) in self.chars[start..end].iter().enumerate() {
+            if units >= position.character {
+                return start + i;
+            }
+            units += c.len_utf16() as u32;
+        }
+        end
+    }
+
+    /// LSP range of a span, as JSON
+    pub fn range(&self, span: Span) -> serde_json::Value {
+        serde_json::json!({
+            "start": self.position(span.start).to_json(),
+            "end": self.position(span.end).to_json(),
+        })
+    }
+
+    /// Pieces of a span on each line it covers, without line breaks, as
+    /// (start, end) character offsets
+    pub fn line_pieces(&self, start: usize, end: usize) -> Vec<(usize, usize)> {
+        let end = end.min(self.chars.len());
+        let mut pieces = Vec::new();
+        let mut piece_start = start;
+        while piece_start < end {
+            let line = self.line_starts.partition_point(|&s| s <= piece_start) - 1;
+            let piece_end = self.line_end(line).min(end);
+            if piece_end > piece_start {
+                pieces.push((piece_start, piece_end));
+            }
+            piece_start = self.line_starts.get(line + 1).copied().unwrap_or(end);
+        }
+        pieces
+    }
+
+    /// Length of a character range in UTF-16 code units
+    pub fn utf16_len(&self, start: usize, end: usize) -> u32 {
+        utf16_len(&self.chars[start.min(end)..end.min(self.chars.len())])
+    }
+
+    /// Text of a character range
+    pub fn text(&self, start: usize, end: usize) -> String {
+        self.chars[start.min(end)..end.min(self.chars.len())]
+            .iter()
+            .collect()
+    }
+
+    /// Offset of the end of a line, before its `\r\n` or `\n`
+    fn line_end(&self, line: usize) -> usize {
+        let mut end = self
+            .line_starts
+            .get(line + 1)
+            .map_or(self.chars.len(), |next| next - 1);
+        if end > self.line_starts[line] && self.chars.get(end - 1) == Some(&'\r') {
+            end -= 1;
+        }
+        end
+    }
+}
+
+fn utf16_len(chars: &[char]) -> u32 {
+    chars.iter().map(|c| c.len_utf16() as u32).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_positions_count_utf16_units() {
+        let index = LineIndex::new("a = \"é𝄞\"\nb = 1\n");
+        assert_eq!(index.position(0), Position::new(0, 0));
+        // 𝄞 is two UTF-16 code units
+        assert_eq!(index.position(7), Position::new(0, 8));
+        assert_eq!(index.position(9), Position::new(1, 0));
+        assert_eq!(index.offset(Position::new(0, 8)), 7);
+        assert_eq!(index.offset(Position::new(1, 4)), 13);
+    }
+
+    #[test]
+    fn test_offsets_are_clamped() {
+        let index = LineIndex::new("ab\r\ncd");
+        assert_eq!(index.offset(Position::new(0, 10)), 2);
+        assert_eq!(index.offset(Position::new(5, 0)), 6);
+        assert_eq!(index.position(100), Position::new(1, 2));
+    }
+
+    #[test]
+    fn test_line_pieces_split_at_line_breaks() {
+        let index = LineIndex::new("x = \"\"\"a\r\nbc\"\"\"\n");
+        assert_eq!(index.line_pieces(4, 15), vec![(4, 8), (10, 15)]);
+    }
+}
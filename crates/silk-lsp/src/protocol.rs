@@ -0,0 +1,67 @@
+//! JSON-RPC message framing over a byte stream
+//!
+//! Every message is a `Content-Length` header, a blank line and a JSON body
+//! of exactly that many bytes. Other headers (`Content-Type`) are ignored.
+
+use serde_json::Value;
+use std::io::{self, BufRead, Write};
+
+/// Read the next message; `None` at the end of the stream
+pub fn read_message(reader: &mut impl BufRead) -> io::Result<Option<Value>> {
+    let mut content_length = None;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 {
+            return Ok(None);
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            if content_length.is_some() {
+                break;
+            }
+            continue;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = Some(value.trim().parse::<usize>().map_err(invalid_data)?);
+            }
+        }
+    }
+
+    let mut body = vec![0; content_length.unwrap_or_default()];
+    reader.read_exact(&mut body)?;
+    serde_json::from_slice(&body)
+        .map(Some)
+        .map_err(invalid_data)
+}
+
+/// Write a message with its header
+pub fn write_message(writer: &mut impl Write, message: &Value) -> io::Result<()> {
+    let body = message.to_string();
+    write!(writer, "Content-Length: {}\r\n\r\n{}", body.len(), body)?;
+    writer.flush()
+}
+
+fn invalid_data(err: impl std::error::Error + Send + Sync + 'static) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_round_trip() {
+        let mut out = Vec::new();
+        write_message(&mut out, &json!({"jsonrpc": "2.0", "method": "exit"})).unwrap();
+        write_message(&mut out, &json!({"text": "é"})).unwrap();
+
+        let mut reader = io::BufReader::new(out.as_slice());
+        let first = read_message(&mut reader).unwrap().unwrap();
+        assert_eq!(first["method"], "exit");
+        let second = read_message(&mut reader).unwrap().unwrap();
+        assert_eq!(second["text"], "é");
+        assert!(read_message(&mut reader).unwrap().is_none());
+    }
+}
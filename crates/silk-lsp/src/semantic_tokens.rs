@@ -0,0 +1,249 @@
+//! Semantic tokens (`textDocument/semanticTokens/full`)
+//!
+//! Keywords, literals and comments come straight from the lexer. Names are
+//! classified with the results of semantic analysis: each identifier is
+//! resolved through the symbol table to a parameter, variable, function,
+//! method, class or imported module, and names that resolve to nothing are
+//! checked against the builtins. Symbols reported by the unused lints get
+//! the `unused` modifier at every occurrence, so editors can dim them.
+//! A document that does not parse only gets the lexical tokens.
+
+use crate::analysis::Analysis;
+use crate::line_index::LineIndex;
+use silk_lexer::TokenKind;
+use silk_semantic::{builtins, ScopeKind, SemanticError, SymbolId, SymbolKind, Type};
+use std::collections::HashSet;
+
+/// Token types, in the order of the legend sent to the client
+pub const TOKEN_TYPES: &[&str] = &[
+    "namespace",
+    "class",
+    "function",
+    "method",
+    "property",
+    "parameter",
+    "variable",
+    "keyword",
+    "string",
+    "number",
+    "comment",
+];
+
+/// Token modifiers, in the order of the legend sent to the client
+pub const TOKEN_MODIFIERS: &[&str] = &["declaration", "defaultLibrary", "unused"];
+
+/// Kind of a semantic token; the discriminant is its index in `TOKEN_TYPES`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenType {
+    Namespace,
+    Class,
+    Function,
+    Method,
+    Property,
+    Parameter,
+    Variable,
+    Keyword,
+    String,
+    Number,
+    Comment,
+}
+
+impl TokenType {
+    /// Name of the token type in the legend
+    pub fn as_str(self) -> &'static str {
+        TOKEN_TYPES[self as usize]
+    }
+}
+
+/// Modifier bits; bit `i` is `TOKEN_MODIFIERS[i]`
+pub mod modifier {
+    /// The name is being defined here
+    pub const DECLARATION: u32 = 1 << 0;
+    /// The name is a builtin
+    pub const DEFAULT_LIBRARY: u32 = 1 << 1;
+    /// The symbol is never used
+    pub const UNUSED: u32 = 1 << 2;
+}
+
+/// A classified range of the source, in character offsets
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SemanticToken {
+    pub start: usize,
+    pub end: usize,
+    pub token_type: TokenType,
+    /// Bit set of `modifier` constants
+    pub modifiers: u32,
+}
+
+/// Classify the tokens of an analyzed document, in source order
+pub fn semantic_tokens(analysis: &Analysis) -> Vec<SemanticToken> {
+    let unused = unused_symbols(analysis);
+    let tokens = &analysis.tokens;
+    let mut result = Vec::new();
+
+    for (index, token) in tokens.iter().enumerate() {
+        let token_type = match &token.kind {
+            kind if kind.is_keyword() => Some((TokenType::Keyword, 0)),
+            TokenKind::Integer(_) | TokenKind::Float(_) => Some((TokenType::Number, 0)),
+            TokenKind::String(_)
+            | TokenKind::RawString(_)
+            | TokenKind::ByteString(_)
+            | TokenKind::ByteRawString(_)
+            | TokenKind::FString(_) => Some((TokenType::String, 0)),
+            TokenKind::Comment => Some((TokenType::Comment, 0)),
+            TokenKind::Identifier => classify_identifier(analysis, index, &unused),
+            _ => None,
+        };
+        if let Some((token_type, modifiers)) = token_type {
+            result.push(SemanticToken {
+                start: token.span.start,
+                end: token.span.end,
+                token_type,
+                modifiers,
+            });
+        }
+    }
+    result
+}
+
+/// Encode tokens for the LSP response: five integers per token (line
+/// delta, start delta, length, type, modifiers), with tokens that span
+/// several lines split into one token per line
+pub fn encode(tokens: &[SemanticToken], line_index: &LineIndex) -> Vec<u32> {
+    let mut data = Vec::with_capacity(tokens.len() * 5);
+    let (mut line, mut character) = (0, 0);
+    for token in tokens {
+        for (start, end) in line_index.line_pieces(token.start, token.end) {
+            let position = line_index.position(start);
+            let delta_line = position.line - line;
+            let delta_start = if delta_line == 0 {
+                position.character - character
+            } else {
+                position.character
+            };
+            data.extend([
+                delta_line,
+                delta_start,
+                line_index.utf16_len(start, end),
+                token.token_type as u32,
+                token.modifiers,
+            ]);
+            (line, character) = (position.line, position.character);
+        }
+    }
+    data
+}
+
+/// Symbols reported by an unused lint, and identifier tokens reported by
+/// one that do not resolve to a symbol
+struct Unused {
+    symbols: HashSet<SymbolId>,
+    tokens: HashSet<usize>,
+}
+
+fn unused_symbols(analysis: &Analysis) -> Unused {
+    let mut unused = Unused {
+        symbols: HashSet::new(),
+        tokens: HashSet::new(),
+    };
+    let Some(typed) = &analysis.typed else {
+        return unused;
+    };
+
+    for error in typed.errors() {
+        let (name, span) = match error {
+            SemanticError::UnusedVariable { name, span, .. }
+            | SemanticError::UnusedFunction { name, span, .. }
+            | SemanticError::UnusedParameter { name, span, .. } => (name.as_str(), span),
+            // `import a.b` binds `a`
+            SemanticError::UnusedImport { name, span, .. } => {
+                (name.split('.').next().unwrap_or(name), span)
+            }
+            _ => continue,
+        };
+        if let Some(index) = analysis.identifier_after(span.start, name) {
+            match analysis.symbol_id_of_token(index) {
+                Some(id) => unused.symbols.insert(id),
+                None => unused.tokens.insert(index),
+            };
+        }
+    }
+    unused
+}
+
+fn classify_identifier(
+    analysis: &Analysis,
+    index: usize,
+    unused: &Unused,
+) -> Option<(TokenType, u32)> {
+    let tokens = &analysis.tokens;
+    let token = &tokens[index];
+    let previous = index.checked_sub(1).map(|i| &tokens[i].kind);
+    let next = tokens.get(index + 1).map(|t| &t.kind);
+
+    // Attributes are not resolved: `obj.name(...)` is a method call,
+    // anything else after a dot a property
+    if previous == Some(&TokenKind::Dot) {
+        return Some(if next == Some(&TokenKind::LeftParen) {
+            (TokenType::Method, 0)
+        } else {
+            (TokenType::Property, 0)
+        });
+    }
+
+    let symbols = analysis.analyzer.symbol_table();
+    let mut modifiers = 0;
+    if unused.tokens.contains(&index) {
+        modifiers |= modifier::UNUSED;
+    }
+
+    if analysis.typed.is_some() {
+        if let Some(id) = analysis.symbol_id_of_token(index) {
+            let symbol = symbols.symbol(&id)?;
+            if unused.symbols.contains(&id) {
+                modifiers |= modifier::UNUSED;
+            }
+            if symbols.symbol_id_at(token.span).is_none() || symbol.span == token.span {
+                modifiers |= modifier::DECLARATION;
+            }
+            let in_class = symbols.scopes()[id.scope].kind == ScopeKind::Class;
+            return Some((symbol_type(&symbol.kind, in_class), modifiers));
+        }
+
+        // Names the analysis does not record a reference for, such as
+        // those in type annotations
+        if let Some(symbol) = symbols
+            .visible_symbols(token.span.start)
+            .into_iter()
+            .find(|symbol| symbol.name == token.lexeme)
+        {
+            return Some((symbol_type(&symbol.kind, false), modifiers));
+        }
+    }
+
+    let name = token.lexeme.as_str();
+    if builtins::is_builtin_exception(name) || Type::from_str(name).is_some() || name == "object" {
+        return Some((TokenType::Class, modifiers | modifier::DEFAULT_LIBRARY));
+    }
+    if builtins::is_builtin_function(name) {
+        return Some((TokenType::Function, modifiers | modifier::DEFAULT_LIBRARY));
+    }
+
+    // Keyword arguments: `f(name=...)`
+    let in_call = matches!(previous, Some(TokenKind::LeftParen | TokenKind::Comma));
+    if in_call && next == Some(&TokenKind::Assign) {
+        return Some((TokenType::Parameter, modifiers));
+    }
+    None
+}
+
+fn symbol_type(kind: &SymbolKind, in_class: bool) -> TokenType {
+    match kind {
+        SymbolKind::Variable => TokenType::Variable,
+        SymbolKind::Parameter => TokenType::Parameter,
+        SymbolKind::Function if in_class => TokenType::Method,
+        SymbolKind::Function => TokenType::Function,
+        SymbolKind::Class => TokenType::Class,
+        SymbolKind::Module => TokenType::Namespace,
+    }
+}
@@ -0,0 +1,347 @@
+//! The language server: document store and request dispatch
+//!
+//! `Server::handle` takes one incoming message and returns the messages to
+//! send back (the response, plus notifications such as published
+//! diagnostics), so it can be driven by tests without any I/O. Documents
+//! are synchronized in full on every change.
+
+use crate::analysis::Analysis;
+use crate::completion;
+use crate::hover::hover;
+use crate::line_index::{LineIndex, Position};
+use crate::protocol::{read_message, write_message};
+use crate::semantic_tokens::{self, TOKEN_MODIFIERS, TOKEN_TYPES};
+use crate::signature_help::signature_help;
+use serde_json::{json, Value};
+use silk_compiler::{Compiler, Database, Diagnostic, Severity};
+use std::collections::HashMap;
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+
+/// JSON-RPC error codes
+mod error_code {
+    pub const INVALID_REQUEST: i64 = -32600;
+    pub const METHOD_NOT_FOUND: i64 = -32601;
+    pub const INVALID_PARAMS: i64 = -32602;
+}
+
+/// An open document
+struct Document {
+    text: String,
+    version: i64,
+}
+
+/// Language server state
+pub struct Server {
+    /// Texts of the open documents, keyed by URI, with memoized results so
+    /// that only changed documents are checked again
+    database: Database,
+    documents: HashMap<String, Document>,
+    shutdown_requested: bool,
+    exited: bool,
+}
+
+impl Server {
+    /// Create a server that checks documents with `compiler`
+    pub fn new(compiler: Compiler) -> Self {
+        Self {
+            database: Database::new(compiler),
+            documents: HashMap::new(),
+            shutdown_requested: false,
+            exited: false,
+        }
+    }
+
+    /// Whether the client sent `exit`
+    pub fn has_exited(&self) -> bool {
+        self.exited
+    }
+
+    /// Serve messages from `reader` until `exit` or the end of the input;
+    /// returns whether the client shut the server down cleanly
+    pub fn run(&mut self, reader: &mut impl BufRead, writer: &mut impl Write) -> io::Result<bool> {
+        while let Some(message) = read_message(reader)? {
+            for reply in self.handle(&message) {
+                write_message(writer, &reply)?;
+            }
+            if self.exited {
+                break;
+            }
+        }
+        Ok(self.shutdown_requested)
+    }
+
+    /// Handle one message and return the messages to send back
+    pub fn handle(&mut self, message: &Value) -> Vec<Value> {
+        let method = message["method"].as_str().unwrap_or_default();
+        let params = &message["params"];
+        match message.get("id") {
+            Some(id) => {
+                let result = if self.shutdown_requested {
+                    Err((
+                        error_code::INVALID_REQUEST,
+                        "server is shutting down".into(),
+                    ))
+                } else {
+                    self.request(method, params)
+                };
+                vec![response(id, result)]
+            }
+            None => self.notification(method, params),
+        }
+    }
+
+    fn request(&mut self, method: &str, params: &Value) -> Result<Value, (i64, String)> {
+        match method {
+            "initialize" => Ok(initialize_result()),
+            "shutdown" => {
+                self.shutdown_requested = true;
+                Ok(Value::Null)
+            }
+            "textDocument/semanticTokens/full" => {
+                let analysis = self.analysis(params)?;
+                let tokens = semantic_tokens::semantic_tokens(&analysis);
+                let data = semantic_tokens::encode(&tokens, &analysis.line_index);
+                Ok(json!({ "data": data }))
+            }
+            "textDocument/completion" => {
+                let (document, offset) = self.position(params)?;
+                let items: Vec<Value> =
+                    completion::completions(self.database.compiler(), &document.text, offset)
+                        .into_iter()
+                        .enumerate()
+                        .map(|(rank, item)| {
+                            json!({
+                                "label": item.label,
+                                "kind": item.kind.lsp_kind(),
+                                "detail": item.detail,
+                                // Keep the ranking instead of sorting by label
+                                "sortText": format!("{:04}", rank),
+                            })
+                        })
+                        .collect();
+                Ok(json!(items))
+            }
+            "textDocument/signatureHelp" => {
+                let (document, offset) = self.position(params)?;
+                let Some(help) = signature_help(self.database.compiler(), &document.text, offset)
+                else {
+                    return Ok(Value::Null);
+                };
+                // Parameter labels are [start, end) in UTF-16 code units
+                let utf16 = |byte: usize| help.label[..byte].encode_utf16().count();
+                let parameters: Vec<Value> = help
+                    .params
+                    .iter()
+                    .map(|range| json!({ "label": [utf16(range.start), utf16(range.end)] }))
+                    .collect();
+                Ok(json!({
+                    "signatures": [{ "label": help.label, "parameters": parameters }],
+                    "activeSignature": 0,
+                    "activeParameter": help.active_param,
+                }))
+            }
+            "textDocument/hover" => {
+                let (document, offset) = self.position(params)?;
+                let Some(hover) = hover(self.database.compiler(), &document.text, offset) else {
+                    return Ok(Value::Null);
+                };
+                Ok(json!({
+                    "contents": { "kind": "markdown", "value": hover.contents },
+                    "range": LineIndex::new(&document.text).range(hover.span),
+                }))
+            }
+            _ => Err((
+                error_code::METHOD_NOT_FOUND,
+                format!("unknown method '{}'", method),
+            )),
+        }
+    }
+
+    fn notification(&mut self, method: &str, params: &Value) -> Vec<Value> {
+        let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+        match method {
+            "exit" => {
+                self.exited = true;
+                Vec::new()
+            }
+            "textDocument/didOpen" => {
+                let document = &params["textDocument"];
+                let text = document["text"].as_str().unwrap_or_default();
+                self.database.set_file_text(uri, text);
+                self.documents.insert(
+                    uri.to_string(),
+                    Document {
+                        text: text.to_string(),
+                        version: document["version"].as_i64().unwrap_or_default(),
+                    },
+                );
+                self.publish_diagnostics(uri)
+            }
+            "textDocument/didChange" => {
+                // Full synchronization: the last change holds the whole text
+                let text = params["contentChanges"]
+                    .as_array()
+                    .and_then(|changes| changes.last())
+                    .and_then(|change| change["text"].as_str());
+                match (self.documents.get_mut(uri), text) {
+                    (Some(document), Some(text)) => {
+                        self.database.set_file_text(uri, text);
+                        document.text = text.to_string();
+                        document.version = params["textDocument"]["version"]
+                            .as_i64()
+                            .unwrap_or(document.version + 1);
+                        self.publish_diagnostics(uri)
+                    }
+                    _ => Vec::new(),
+                }
+            }
+            "textDocument/didClose" => {
+                self.documents.remove(uri);
+                self.database.remove_file(Path::new(uri));
+                vec![notification(
+                    "textDocument/publishDiagnostics",
+                    json!({ "uri": uri, "diagnostics": [] }),
+                )]
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// The document a request is about
+    fn document(&self, params: &Value) -> Result<&Document, (i64, String)> {
+        let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+        self.documents.get(uri).ok_or_else(|| {
+            (
+                error_code::INVALID_PARAMS,
+                format!("unknown document '{}'", uri),
+            )
+        })
+    }
+
+    /// Analysis of the document a request is about
+    fn analysis(&self, params: &Value) -> Result<Analysis, (i64, String)> {
+        Ok(Analysis::new(
+            self.database.compiler(),
+            &self.document(params)?.text,
+        ))
+    }
+
+    /// The document and character offset a position request is about
+    fn position(&self, params: &Value) -> Result<(&Document, usize), (i64, String)> {
+        let document = self.document(params)?;
+        let position = Position::from_json(&params["position"])
+            .ok_or_else(|| (error_code::INVALID_PARAMS, "missing position".to_string()))?;
+        let offset = LineIndex::new(&document.text).offset(position);
+        Ok((document, offset))
+    }
+
+    fn publish_diagnostics(&self, uri: &str) -> Vec<Value> {
+        let Some(document) = self.documents.get(uri) else {
+            return Vec::new();
+        };
+        let line_index = LineIndex::new(&document.text);
+        // Same as `Compiler::check`, reusing the results of an unchanged text
+        let mut diagnostics = self
+            .database
+            .diagnostics(Path::new(uri))
+            .map(|checked| checked.diagnostics.clone())
+            .unwrap_or_default();
+        self.database
+            .compiler()
+            .options()
+            .cap_errors([&mut diagnostics]);
+        let diagnostics: Vec<Value> = diagnostics
+            .iter()
+            .map(|diagnostic| lsp_diagnostic(diagnostic, uri, &line_index))
+            .collect();
+        vec![notification(
+            "textDocument/publishDiagnostics",
+            json!({
+                "uri": uri,
+                "version": document.version,
+                "diagnostics": diagnostics,
+            }),
+        )]
+    }
+}
+
+fn initialize_result() -> Value {
+    json!({
+        "capabilities": {
+            "positionEncoding": "utf-16",
+            // Full document synchronization
+            "textDocumentSync": 1,
+            "completionProvider": { "triggerCharacters": ["."] },
+            "signatureHelpProvider": { "triggerCharacters": ["(", ","] },
+            "hoverProvider": true,
+            "semanticTokensProvider": {
+                "legend": {
+                    "tokenTypes": TOKEN_TYPES,
+                    "tokenModifiers": TOKEN_MODIFIERS,
+                },
+                "full": true,
+                "range": false,
+            },
+        },
+        "serverInfo": {
+            "name": "silk-lsp",
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+    })
+}
+
+/// Diagnostic in LSP form; unused code is tagged so editors fade it out
+fn lsp_diagnostic(diagnostic: &Diagnostic, uri: &str, line_index: &LineIndex) -> Value {
+    let range = |span| match span {
+        Some(span) => line_index.range(span),
+        None => {
+            let end = line_index.position(line_index.len()).to_json();
+            json!({ "start": end, "end": end })
+        }
+    };
+    let mut value = json!({
+        "range": range(diagnostic.span),
+        "severity": match diagnostic.severity {
+            Severity::Error => 1,
+            Severity::Warning => 2,
+        },
+        "code": diagnostic.code,
+        "source": "silk",
+        "message": diagnostic.message,
+    });
+    if diagnostic.code.starts_with("unused-") {
+        // DiagnosticTag.Unnecessary
+        value["tags"] = json!([1]);
+    }
+    let related: Vec<Value> = diagnostic
+        .notes
+        .iter()
+        .filter(|note| note.file.is_none())
+        .map(|note| {
+            json!({
+                "location": { "uri": uri, "range": range(note.span) },
+                "message": note.message,
+            })
+        })
+        .collect();
+    if !related.is_empty() {
+        value["relatedInformation"] = json!(related);
+    }
+    value
+}
+
+fn response(id: &Value, result: Result<Value, (i64, String)>) -> Value {
+    match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err((code, message)) => json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": { "code": code, "message": message },
+        }),
+    }
+}
+
+fn notification(method: &str, params: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "method": method, "params": params })
+}
@@ -0,0 +1,206 @@
+//! Signature help (`textDocument/signatureHelp`)
+//!
+//! Inside the parentheses of a call, shows the signature of the function
+//! being called with the parameter that receives the argument under the
+//! cursor highlighted. The call is found from the tokens before the
+//! cursor, so it works in calls that are still being written. The callee
+//! is resolved with the typed program: functions and methods have their
+//! parameter lists in their type, and calling a class shows the parameters
+//! of its `__init__`.
+
+use crate::analysis::{self, Analysis};
+use silk_compiler::Compiler;
+use silk_lexer::{Lexer, Token, TokenIndex, TokenKind};
+use silk_semantic::{Signature, SymbolKind, Type};
+use std::ops::Range;
+
+/// A signature with one parameter highlighted
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignatureHelp {
+    /// `name(params) -> return type`
+    pub label: String,
+    /// Byte range of each parameter in the label
+    pub params: Vec<Range<usize>>,
+    /// Parameter that receives the argument under the cursor, if any
+    pub active_param: Option<usize>,
+}
+
+/// The innermost call around the cursor
+struct Call {
+    /// Index of the token before the `(`, the end of the callee
+    callee: usize,
+    /// Positional arguments before the one under the cursor
+    position: usize,
+    /// Name of the argument under the cursor, when it is `name=...`
+    keyword: Option<String>,
+}
+
+/// An open bracket before the cursor
+struct Open {
+    index: usize,
+    position: usize,
+    /// First token of the argument being written
+    argument: usize,
+}
+
+/// Signature help at a character offset, if the cursor is inside a call
+pub fn signature_help(compiler: &Compiler, source: &str, offset: usize) -> Option<SignatureHelp> {
+    let (tokens, _) = Lexer::new(source).tokenize_lossy();
+    let (call, open) = find_call(&tokens, offset)?;
+    let callee = &tokens[call.callee];
+
+    // Close every open bracket at the cursor so the call parses
+    let chars: Vec<char> = source.chars().collect();
+    let offset = offset.min(chars.len());
+    let mut closed: String = chars[..offset].iter().collect();
+    // `f(name=` needs a value
+    if TokenIndex::new(&tokens)
+        .before(offset)
+        .is_some_and(|index| tokens[index].kind == TokenKind::Assign)
+    {
+        closed.push_str("None");
+    }
+    for &index in open.iter().rev() {
+        closed.push(match tokens[index].kind {
+            TokenKind::LeftBracket => ']',
+            TokenKind::LeftBrace => '}',
+            _ => ')',
+        });
+    }
+    let line_end = chars[offset..]
+        .iter()
+        .position(|&c| c == '\n')
+        .map_or(chars.len(), |i| offset + i);
+    closed.extend(&chars[line_end..]);
+
+    let attempts = vec![
+        (source.to_string(), offset),
+        (closed, offset),
+        analysis::stub_line(&chars, offset),
+    ];
+    let (analysis, scope_offset) = analysis::first_parsing(compiler, attempts)?;
+    let name = match callee.kind {
+        TokenKind::Identifier => callee.lexeme.to_string(),
+        _ => String::new(),
+    };
+    let (signature, return_type) = resolve_callee(&analysis, callee, &name, scope_offset)?;
+
+    let mut label = format!("{}{}", name, signature);
+    if return_type != Type::Unknown {
+        label.push_str(&format!(" -> {}", return_type));
+    }
+    Some(SignatureHelp {
+        label,
+        params: signature
+            .param_ranges()
+            .into_iter()
+            .map(|range| range.start + name.len()..range.end + name.len())
+            .collect(),
+        active_param: signature.param_for_argument(call.position, call.keyword.as_deref()),
+    })
+}
+
+/// The innermost call whose parentheses contain the offset, and the
+/// indices of every bracket still open there
+fn find_call(tokens: &[Token], offset: usize) -> Option<(Call, Vec<usize>)> {
+    let mut stack: Vec<Open> = Vec::new();
+    let end = TokenIndex::new(tokens).first_from(offset);
+    for (index, token) in tokens[..end].iter().enumerate() {
+        match token.kind {
+            TokenKind::LeftParen | TokenKind::LeftBracket | TokenKind::LeftBrace => {
+                stack.push(Open {
+                    index,
+                    position: 0,
+                    argument: index + 1,
+                })
+            }
+            TokenKind::RightParen | TokenKind::RightBracket | TokenKind::RightBrace => {
+                stack.pop();
+            }
+            TokenKind::Comma => {
+                if let Some(open) = stack.last_mut() {
+                    if keyword_of(tokens, open.argument, offset).is_none() {
+                        open.position += 1;
+                    }
+                    open.argument = index + 1;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let open: Vec<usize> = stack.iter().map(|open| open.index).collect();
+    let call = stack.iter().rev().find_map(|open| {
+        let callee = open.index.checked_sub(1)?;
+        let is_call = tokens[open.index].kind == TokenKind::LeftParen
+            && matches!(
+                tokens[callee].kind,
+                TokenKind::Identifier | TokenKind::RightParen | TokenKind::RightBracket
+            )
+            // `def f(` declares parameters
+            && !(callee > 0 && tokens[callee - 1].kind == TokenKind::Def);
+        is_call.then(|| Call {
+            callee,
+            position: open.position,
+            keyword: keyword_of(tokens, open.argument, offset),
+        })
+    })?;
+    Some((call, open))
+}
+
+/// The keyword of an argument starting at token `start`, if it is `name=`
+fn keyword_of(tokens: &[Token], start: usize, offset: usize) -> Option<String> {
+    let name = tokens.get(start)?;
+    let assign = tokens.get(start + 1)?;
+    (name.kind == TokenKind::Identifier
+        && assign.kind == TokenKind::Assign
+        && assign.span.start < offset)
+        .then(|| name.lexeme.to_string())
+}
+
+/// Parameters and return type of the callee ending with token `callee`
+fn resolve_callee(
+    analysis: &Analysis,
+    callee: &Token,
+    name: &str,
+    scope_offset: usize,
+) -> Option<(Signature, Type)> {
+    let symbols = analysis.analyzer.symbol_table();
+    // The outermost typed expression ending with the callee token: on
+    // `a.b(`, `a.b`
+    let typed = analysis.typed.as_ref()?;
+    let expression = typed
+        .expression_types()
+        .into_iter()
+        .find(|(span, _)| span.end == callee.span.end);
+
+    let (ty, symbol) = match expression {
+        Some((span, ty)) => (ty.clone(), symbols.symbol_at(span)),
+        // The call did not survive the edits that made the document
+        // parse; look the name up instead
+        None => {
+            let symbol = symbols
+                .visible_symbols(scope_offset)
+                .into_iter()
+                .find(|symbol| symbol.name == name)?;
+            (symbol.ty.clone(), Some(symbol))
+        }
+    };
+
+    match ty {
+        Type::Function {
+            params: Some(params),
+            return_type,
+        } => Some((params, *return_type)),
+        _ => {
+            let symbol = symbol.filter(|s| s.kind == SymbolKind::Class)?;
+            let class = analysis.analyzer.class_info_of(symbol)?;
+            let init = analysis
+                .method_resolution_order(class)
+                .into_iter()
+                .find_map(|class| class.method("__init__"));
+            let params = init.map(|init| init.params.clone()).unwrap_or_default();
+            Some((params, Type::Instance(class.qualname.clone())))
+        }
+    }
+}
@@ -0,0 +1,105 @@
+//! Tests for code completion
+
+use silk_compiler::Compiler;
+use silk_lsp::{completions, CompletionItem, CompletionKind};
+
+/// Completions at the `|` in `source`
+fn complete(source: &str) -> Vec<CompletionItem> {
+    let offset = source.chars().position(|c| c == '|').expect("no cursor");
+    let source = source.replacen('|', "", 1);
+    completions(&Compiler::new(), &source, offset)
+}
+
+fn labels(items: &[CompletionItem]) -> Vec<&str> {
+    items.iter().map(|item| item.label.as_str()).collect()
+}
+
+fn item<'a>(items: &'a [CompletionItem], label: &str) -> &'a CompletionItem {
+    items
+        .iter()
+        .find(|item| item.label == label)
+        .unwrap_or_else(|| panic!("no completion {:?} in {:?}", label, labels(items)))
+}
+
+#[test]
+fn test_names_in_scope_come_first() {
+    let items = complete("total = 1\n\ndef f(count: int) -> int:\n    return co|\n");
+    assert_eq!(labels(&items), vec!["count", "compile", "continue"]);
+    assert_eq!(item(&items, "count").kind, CompletionKind::Parameter);
+    assert_eq!(item(&items, "count").detail.as_deref(), Some("int"));
+    assert_eq!(item(&items, "continue").kind, CompletionKind::Keyword);
+}
+
+#[test]
+fn test_locals_are_not_visible_outside_their_function() {
+    let source = "def f():\n    inner = 1\n    return inner\n\nin|\n";
+    let items = complete(source);
+    assert!(!labels(&items).contains(&"inner"));
+    assert!(labels(&items).contains(&"input"));
+    assert!(labels(&items).contains(&"in"));
+}
+
+#[test]
+fn test_functions_show_their_signature() {
+    let source = "def scale(value: float, factor: float = 2.0) -> float:\n    return value * factor\n\nx = sc|\n";
+    let items = complete(source);
+    let scale = item(&items, "scale");
+    assert_eq!(scale.kind, CompletionKind::Function);
+    assert_eq!(
+        scale.detail.as_deref(),
+        Some("(value: float, factor: float = ...) -> float")
+    );
+}
+
+#[test]
+fn test_incomplete_line_still_completes() {
+    let source = "name = \"silk\"\nprint(na|\n";
+    let items = complete(source);
+    assert_eq!(item(&items, "name").detail.as_deref(), Some("str"));
+}
+
+#[test]
+fn test_members_after_dot() {
+    let source = r#"
+class Shape:
+    def area(self) -> float:
+        return 0.0
+
+    def _cache(self):
+        pass
+
+class Square(Shape):
+    def __init__(self, side: float):
+        self.side = side
+
+    @property
+    def perimeter(self) -> float:
+        return 4.0
+
+s = Square(2.0)
+s.|
+"#;
+    let items = complete(source);
+    assert_eq!(labels(&items), vec!["perimeter", "area"]);
+    assert_eq!(item(&items, "perimeter").kind, CompletionKind::Property);
+    assert_eq!(item(&items, "area").kind, CompletionKind::Method);
+    assert_eq!(item(&items, "area").detail.as_deref(), Some("() -> float"));
+}
+
+#[test]
+fn test_members_are_filtered_by_prefix() {
+    let source = "class A:\n    def run(self):\n        pass\n\n    def reset(self):\n        pass\n\nA().re|\n";
+    assert_eq!(labels(&complete(source)), vec!["reset"]);
+}
+
+#[test]
+fn test_private_members_need_an_underscore() {
+    let source = "class A:\n    def __init__(self):\n        pass\n\n    def _helper(self):\n        pass\n\nA()._|\n";
+    assert_eq!(labels(&complete(source)), vec!["__init__", "_helper"]);
+}
+
+#[test]
+fn test_no_completions_inside_strings_or_after_unknown_receivers() {
+    assert!(complete("x = \"pri|\"\n").is_empty());
+    assert!(complete("x = 1\nx.|\n").is_empty());
+}
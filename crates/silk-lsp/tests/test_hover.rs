@@ -0,0 +1,126 @@
+//! Tests for hover information
+
+use silk_compiler::Compiler;
+use silk_lsp::hover;
+
+const SOURCE: &str = r#"
+def scale(value: float, factor: float = 2.0) -> float:
+    """Multiply a value.
+
+    Args:
+        value (float): the value
+        factor (float): the multiplier
+    """
+    return value * factor
+
+class Shape:
+    """A shape."""
+
+    def area(self) -> float:
+        """Area of the shape."""
+        return 0.0
+
+class Square(Shape):
+    def __init__(self, side: float):
+        self.side = side
+
+    @property
+    def perimeter(self) -> float:
+        return 4.0 * self.side
+
+def describe(label: str | None) -> str:
+    if label is not None:
+        return label
+    return "none"
+
+square = Square(2.0)
+total = scale(square.area()) + square.perimeter
+print(square.side, [1, 2])
+"#;
+
+/// Hover contents at the `nth` occurrence of `text`, with the cursor
+/// `within` characters into it
+fn hover_at(text: &str, nth: usize, within: usize) -> Option<String> {
+    let (start, _) = SOURCE.match_indices(text).nth(nth).expect("no such text");
+    let offset = SOURCE[..start].chars().count() + within;
+    hover(&Compiler::new(), SOURCE, offset).map(|hover| hover.contents)
+}
+
+fn code(text: &str) -> String {
+    format!("```silk\n{}\n```", text)
+}
+
+#[test]
+fn test_function_shows_signature_and_docstring() {
+    let expected = format!(
+        "{}\n---\nMultiply a value.\n\nArgs:\n    value (float): the value\n    factor (float): the multiplier",
+        code("(function) def scale(value: float, factor: float = ...) -> float")
+    );
+    // Both on the definition and on a call
+    assert_eq!(hover_at("scale", 0, 1).as_deref(), Some(expected.as_str()));
+    assert_eq!(hover_at("scale", 1, 0).as_deref(), Some(expected.as_str()));
+}
+
+#[test]
+fn test_variables_and_parameters_show_their_type() {
+    assert_eq!(
+        hover_at("total", 0, 2),
+        Some(code("(variable) total: float"))
+    );
+    assert_eq!(
+        hover_at("factor", 0, 0),
+        Some(code("(parameter) factor: float"))
+    );
+}
+
+#[test]
+fn test_narrowed_type_at_the_cursor() {
+    assert_eq!(
+        hover_at("label", 0, 0),
+        Some(code("(parameter) label: str | None"))
+    );
+    assert_eq!(
+        hover_at("label", 2, 0),
+        Some(code("(parameter) label: str"))
+    );
+}
+
+#[test]
+fn test_class_shows_bases_and_docstring() {
+    assert_eq!(
+        hover_at("Shape", 0, 0),
+        Some(format!("{}\n---\nA shape.", code("(class) class Shape")))
+    );
+    assert_eq!(
+        hover_at("Square", 0, 0),
+        Some(code("(class) class Square(Shape)"))
+    );
+}
+
+#[test]
+fn test_members_through_an_instance() {
+    assert_eq!(
+        hover_at("area", 1, 0),
+        Some(format!(
+            "{}\n---\nArea of the shape.",
+            code("(method) def area() -> float")
+        ))
+    );
+    assert_eq!(
+        hover_at("perimeter", 1, 3),
+        Some(code("(property) perimeter: float"))
+    );
+}
+
+#[test]
+fn test_expressions_show_their_type() {
+    assert_eq!(hover_at("[1, 2]", 0, 0), Some(code("list[int]")));
+    assert_eq!(hover_at("2.0)", 1, 0), Some(code("float")));
+}
+
+#[test]
+fn test_builtins_and_empty_positions() {
+    assert_eq!(hover_at("print", 0, 0), Some(code("(function) print")));
+    assert_eq!(hover_at("return", 0, 0), None);
+    assert_eq!(hover_at("\n\nclass Shape", 0, 1), None);
+}
@@ -0,0 +1,162 @@
+//! Tests for semantic token classification and encoding
+
+use silk_compiler::Compiler;
+use silk_lsp::semantic_tokens::{encode, modifier};
+use silk_lsp::{semantic_tokens, Analysis, TokenType};
+
+/// (text, type, modifiers) of every semantic token
+fn classify(source: &str) -> Vec<(String, TokenType, u32)> {
+    let analysis = Analysis::new(&Compiler::new(), source);
+    semantic_tokens(&analysis)
+        .into_iter()
+        .map(|token| {
+            let text = analysis.line_index.text(token.start, token.end);
+            (text, token.token_type, token.modifiers)
+        })
+        .collect()
+}
+
+/// Classification of the `nth` token spelled `text`
+fn token(source: &str, text: &str, nth: usize) -> (TokenType, u32) {
+    classify(source)
+        .into_iter()
+        .filter(|(t, _, _)| t == text)
+        .map(|(_, token_type, modifiers)| (token_type, modifiers))
+        .nth(nth)
+        .unwrap_or_else(|| panic!("no token {:?} #{}", text, nth))
+}
+
+#[test]
+fn test_lexical_tokens() {
+    let source = "x = 1 if True else 2.5\ny = \"text\"\n";
+    assert_eq!(token(source, "1", 0), (TokenType::Number, 0));
+    assert_eq!(token(source, "2.5", 0), (TokenType::Number, 0));
+    assert_eq!(token(source, "if", 0), (TokenType::Keyword, 0));
+    assert_eq!(token(source, "True", 0), (TokenType::Keyword, 0));
+    assert_eq!(token(source, "\"text\"", 0), (TokenType::String, 0));
+}
+
+#[test]
+fn test_parameters_and_functions() {
+    let source = "def area(width, height):\n    return width * height\n\nprint(area(2, 3))\n";
+    assert_eq!(
+        token(source, "area", 0),
+        (TokenType::Function, modifier::DECLARATION)
+    );
+    assert_eq!(token(source, "area", 1), (TokenType::Function, 0));
+    assert_eq!(
+        token(source, "width", 0),
+        (TokenType::Parameter, modifier::DECLARATION)
+    );
+    assert_eq!(token(source, "width", 1), (TokenType::Parameter, 0));
+}
+
+#[test]
+fn test_classes_methods_and_attributes() {
+    let source = r#"
+class Point:
+    def __init__(self, x: int):
+        self.x = x
+
+    def norm(self) -> int:
+        return self.x
+
+p: Point = Point(1)
+print(p.norm(), p.x)
+"#;
+    assert_eq!(
+        token(source, "Point", 0),
+        (TokenType::Class, modifier::DECLARATION)
+    );
+    // In an annotation and in a call
+    assert_eq!(token(source, "Point", 1).0, TokenType::Class);
+    assert_eq!(token(source, "Point", 2).0, TokenType::Class);
+    assert_eq!(
+        token(source, "norm", 0),
+        (TokenType::Method, modifier::DECLARATION)
+    );
+    assert_eq!(token(source, "norm", 1), (TokenType::Method, 0));
+    assert_eq!(token(source, "x", 4), (TokenType::Property, 0));
+}
+
+#[test]
+fn test_builtins_are_default_library() {
+    let source = "def f(n: int) -> str:\n    raise ValueError(len(str(n)))\n";
+    assert_eq!(
+        token(source, "int", 0),
+        (TokenType::Class, modifier::DEFAULT_LIBRARY)
+    );
+    assert_eq!(
+        token(source, "ValueError", 0),
+        (TokenType::Class, modifier::DEFAULT_LIBRARY)
+    );
+    assert_eq!(
+        token(source, "len", 0),
+        (TokenType::Function, modifier::DEFAULT_LIBRARY)
+    );
+}
+
+#[test]
+fn test_unused_symbols_are_marked_everywhere() {
+    let source = "import os\n\ndef f(unused, used):\n    local = 1\n    return used\n";
+    assert_eq!(
+        token(source, "os", 0),
+        (
+            TokenType::Namespace,
+            modifier::DECLARATION | modifier::UNUSED
+        )
+    );
+    assert_eq!(
+        token(source, "local", 0),
+        (
+            TokenType::Variable,
+            modifier::DECLARATION | modifier::UNUSED
+        )
+    );
+    assert_eq!(
+        token(source, "used", 0),
+        (TokenType::Parameter, modifier::DECLARATION)
+    );
+    assert_eq!(token(source, "used", 1), (TokenType::Parameter, 0));
+}
+
+#[test]
+fn test_keyword_arguments_are_parameters() {
+    let source = "def f(a, b=1):\n    return a + b\n\nf(1, b=2)\n";
+    assert_eq!(token(source, "b", 2), (TokenType::Parameter, 0));
+}
+
+#[test]
+fn test_unparsable_document_gets_lexical_tokens() {
+    let tokens = classify("def broken(:\n    return 1\n");
+    assert_eq!(
+        tokens,
+        vec![
+            ("def".to_string(), TokenType::Keyword, 0),
+            ("return".to_string(), TokenType::Keyword, 0),
+            ("1".to_string(), TokenType::Number, 0),
+        ]
+    );
+}
+
+#[test]
+fn test_encoding_uses_deltas_and_splits_multiline_tokens() {
+    let source = "x = 1\ns = \"\"\"a\nbc\"\"\"\n";
+    let analysis = Analysis::new(&Compiler::new(), source);
+    let data = encode(&semantic_tokens(&analysis), &analysis.line_index);
+    let variable = TokenType::Variable as u32;
+    let number = TokenType::Number as u32;
+    let string = TokenType::String as u32;
+    // Neither variable is used
+    let declared = modifier::DECLARATION | modifier::UNUSED;
+    assert_eq!(
+        data,
+        vec![
+            0, 0, 1, variable, declared, // x
+            0, 4, 1, number, 0, // 1
+            1, 0, 1, variable, declared, // s
+            0, 4, 4, string, 0, // """a
+            1, 0, 5, string, 0, // bc"""
+        ]
+    );
+}
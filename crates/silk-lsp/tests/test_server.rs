@@ -0,0 +1,197 @@
+//! Tests for the language server message handling
+
+use serde_json::{json, Value};
+use silk_compiler::Compiler;
+use silk_lsp::Server;
+
+const URI: &str = "file:///main.silk";
+
+fn request(id: i64, method: &str, params: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params })
+}
+
+fn notification(method: &str, params: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "method": method, "params": params })
+}
+
+fn open(server: &mut Server, text: &str) -> Vec<Value> {
+    server.handle(&notification(
+        "textDocument/didOpen",
+        json!({ "textDocument": { "uri": URI, "languageId": "silk", "version": 1, "text": text } }),
+    ))
+}
+
+#[test]
+fn test_initialize_advertises_semantic_tokens() {
+    let mut server = Server::new(Compiler::new());
+    let replies = server.handle(&request(1, "initialize", json!({})));
+    assert_eq!(replies.len(), 1);
+    assert_eq!(replies[0]["id"], 1);
+    let provider = &replies[0]["result"]["capabilities"]["semanticTokensProvider"];
+    assert_eq!(provider["full"], true);
+    assert_eq!(provider["legend"]["tokenTypes"][5], "parameter");
+    assert_eq!(provider["legend"]["tokenModifiers"][2], "unused");
+}
+
+#[test]
+fn test_open_and_change_publish_diagnostics() {
+    let mut server = Server::new(Compiler::new());
+    let replies = open(&mut server, "import os\nprint(missing)\n");
+    assert_eq!(replies.len(), 1);
+    assert_eq!(replies[0]["method"], "textDocument/publishDiagnostics");
+    let diagnostics = replies[0]["params"]["diagnostics"].as_array().unwrap();
+    let codes: Vec<&str> = diagnostics
+        .iter()
+        .map(|d| d["code"].as_str().unwrap())
+        .collect();
+    assert!(codes.contains(&"undefined-variable"));
+    let unused = diagnostics
+        .iter()
+        .find(|d| d["code"] == "unused-import")
+        .unwrap();
+    assert_eq!(unused["severity"], 2);
+    assert_eq!(unused["tags"], json!([1]));
+    assert_eq!(
+        unused["range"],
+        json!({ "start": { "line": 0, "character": 7 }, "end": { "line": 0, "character": 9 } })
+    );
+
+    let replies = server.handle(&notification(
+        "textDocument/didChange",
+        json!({
+            "textDocument": { "uri": URI, "version": 2 },
+            "contentChanges": [{ "text": "print(1)\n" }],
+        }),
+    ));
+    assert_eq!(replies[0]["params"]["version"], 2);
+    assert_eq!(replies[0]["params"]["diagnostics"], json!([]));
+}
+
+#[test]
+fn test_semantic_tokens_request() {
+    let mut server = Server::new(Compiler::new());
+    open(&mut server, "x = 1\n");
+    let replies = server.handle(&request(
+        2,
+        "textDocument/semanticTokens/full",
+        json!({ "textDocument": { "uri": URI } }),
+    ));
+    assert_eq!(
+        replies[0]["result"]["data"],
+        json!([0, 0, 1, 6, 5, 0, 4, 1, 9, 0])
+    );
+}
+
+#[test]
+fn test_errors_for_unknown_methods_and_documents() {
+    let mut server = Server::new(Compiler::new());
+    let replies = server.handle(&request(3, "textDocument/unknown", json!({})));
+    assert_eq!(replies[0]["error"]["code"], -32601);
+
+    let replies = server.handle(&request(
+        4,
+        "textDocument/semanticTokens/full",
+        json!({ "textDocument": { "uri": "file:///closed.silk" } }),
+    ));
+    assert_eq!(replies[0]["error"]["code"], -32602);
+}
+
+#[test]
+fn test_shutdown_and_exit() {
+    let mut input = Vec::new();
+    for message in [
+        request(1, "initialize", json!({})),
+        request(2, "shutdown", Value::Null),
+        notification("exit", Value::Null),
+    ] {
+        silk_lsp::protocol::write_message(&mut input, &message).unwrap();
+    }
+
+    let mut server = Server::new(Compiler::new());
+    let mut output = Vec::new();
+    let clean = server.run(&mut input.as_slice(), &mut output).unwrap();
+    assert!(clean);
+    assert!(server.has_exited());
+
+    let mut reader = output.as_slice();
+    let first = silk_lsp::protocol::read_message(&mut reader)
+        .unwrap()
+        .unwrap();
+    assert_eq!(first["id"], 1);
+    let second = silk_lsp::protocol::read_message(&mut reader)
+        .unwrap()
+        .unwrap();
+    assert_eq!(second["id"], 2);
+    assert_eq!(second["result"], Value::Null);
+}
+
+#[test]
+fn test_completion_request() {
+    let mut server = Server::new(Compiler::new());
+    open(&mut server, "value = 1\nprint(val)\n");
+    let replies = server.handle(&request(
+        5,
+        "textDocument/completion",
+        json!({ "textDocument": { "uri": URI }, "position": { "line": 1, "character": 9 } }),
+    ));
+    let items = replies[0]["result"].as_array().unwrap();
+    assert_eq!(items[0]["label"], "value");
+    assert_eq!(items[0]["kind"], 6);
+    assert_eq!(items[0]["detail"], "int");
+    assert_eq!(items[0]["sortText"], "0000");
+}
+
+#[test]
+fn test_signature_help_request() {
+    let mut server = Server::new(Compiler::new());
+    open(
+        &mut server,
+        "def add(a: int, b: int) -> int:\n    return a + b\n\nadd(1, 2)\n",
+    );
+    let replies = server.handle(&request(
+        6,
+        "textDocument/signatureHelp",
+        json!({ "textDocument": { "uri": URI }, "position": { "line": 3, "character": 7 } }),
+    ));
+    let result = &replies[0]["result"];
+    assert_eq!(
+        result["signatures"][0]["label"],
+        "add(a: int, b: int) -> int"
+    );
+    assert_eq!(
+        result["signatures"][0]["parameters"][1]["label"],
+        json!([12, 18])
+    );
+    assert_eq!(result["activeParameter"], 1);
+
+    let replies = server.handle(&request(
+        7,
+        "textDocument/signatureHelp",
+        json!({ "textDocument": { "uri": URI }, "position": { "line": 0, "character": 0 } }),
+    ));
+    assert_eq!(replies[0]["result"], Value::Null);
+}
+
+#[test]
+fn test_hover_request() {
+    let mut server = Server::new(Compiler::new());
+    open(&mut server, "count = 1\nprint(count)\n");
+    let replies = server.handle(&request(
+        8,
+        "textDocument/hover",
+        json!({ "textDocument": { "uri": URI }, "position": { "line": 1, "character": 8 } }),
+    ));
+    let result = &replies[0]["result"];
+    assert_eq!(result["contents"]["kind"], "markdown");
+    assert_eq!(
+        result["contents"]["value"],
+        "```silk\n(variable) count: int\n```"
+    );
+    assert_eq!(
+        result["range"],
+        json!({
+            "start": { "line": 1, "character": 6 },
+            "end": { "line": 1, "character": 11 },
+        })
+    );
+}
@@ -0,0 +1,111 @@
+//! Tests for signature help
+
+use silk_compiler::Compiler;
+use silk_lsp::{signature_help, SignatureHelp};
+
+const DEFINITIONS: &str = r#"
+def scale(value: float, factor: float = 2.0, *, clamp: bool = False) -> float:
+    return value * factor
+
+class Point:
+    def __init__(self, x: int, y: int):
+        self.x = x
+
+    def moved(self, dx: int, dy: int) -> int:
+        return self.x + dx + dy
+
+origin = Point(0, 0)
+"#;
+
+/// Signature help at the `|` in `code`, written after `DEFINITIONS`
+fn help_at(code: &str) -> Option<SignatureHelp> {
+    let source = format!("{}{}", DEFINITIONS, code);
+    let offset = source.chars().position(|c| c == '|').expect("no cursor");
+    let source = source.replacen('|', "", 1);
+    signature_help(&Compiler::new(), &source, offset)
+}
+
+/// The label and the text of the active parameter
+fn active(help: &SignatureHelp) -> (&str, Option<&str>) {
+    let param = help
+        .active_param
+        .map(|index| &help.label[help.params[index].clone()]);
+    (help.label.as_str(), param)
+}
+
+#[test]
+fn test_function_signature_with_active_parameter() {
+    let help = help_at("result = scale(1.0, |2.0)\n").unwrap();
+    assert_eq!(
+        active(&help),
+        (
+            "scale(value: float, factor: float = ..., *, clamp: bool = ...) -> float",
+            Some("factor: float = ...")
+        )
+    );
+    assert_eq!(help.params.len(), 3);
+}
+
+#[test]
+fn test_call_being_written() {
+    let help = help_at("result = scale(|\n").unwrap();
+    assert_eq!(active(&help).1, Some("value: float"));
+
+    let help = help_after("result = scale(1.0, ");
+    assert_eq!(active(&help).1, Some("factor: float = ..."));
+}
+
+/// Signature help with the cursor at the end of `code`
+fn help_after(code: &str) -> SignatureHelp {
+    help_at(&format!("{}|\n", code)).unwrap()
+}
+
+#[test]
+fn test_keyword_argument_is_active() {
+    let help = help_after("result = scale(1.0, clamp=");
+    assert_eq!(active(&help).1, Some("clamp: bool = ..."));
+
+    let help = help_at("result = scale(factor=3.0, |value=1.0)\n").unwrap();
+    assert_eq!(active(&help).1, Some("value: float"));
+}
+
+#[test]
+fn test_class_call_shows_init_parameters() {
+    let help = help_after("p = Point(1, ");
+    assert_eq!(
+        active(&help),
+        ("Point(x: int, y: int) -> Point", Some("y: int"))
+    );
+}
+
+#[test]
+fn test_method_call_through_instance() {
+    let help = help_after("p = origin.moved(");
+    assert_eq!(
+        active(&help),
+        ("moved(dx: int, dy: int) -> int", Some("dx: int"))
+    );
+}
+
+#[test]
+fn test_nested_calls_use_the_innermost() {
+    let help = help_after("x = scale(Point(1, 2).moved(3, ");
+    assert_eq!(active(&help).1, Some("dy: int"));
+
+    // Brackets inside an argument do not count as arguments
+    let help = help_after("x = scale([1, 2, 3], ");
+    assert_eq!(active(&help).1, Some("factor: float = ..."));
+}
+
+#[test]
+fn test_no_help_outside_calls() {
+    assert!(help_at("x = [1, |2]\n").is_none());
+    assert!(help_at("def other(|a):\n    pass\n").is_none());
+    assert!(help_at("x = scale(1.0)|\n").is_none());
+}
+
+#[test]
+fn test_too_many_arguments_have_no_active_parameter() {
+    let help = help_after("p = Point(1, 2, ");
+    assert_eq!(active(&help).1, None);
+}
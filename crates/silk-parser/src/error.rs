@@ -1,5 +1,6 @@
 /// Parser error types
-use silk_lexer::{LexError, Token};
+use silk_ast::Feature;
+use silk_lexer::{LexError, Span, Token};
 use thiserror::Error;
 
 pub type ParseResult<T> = Result<T, ParseError>;
@@ -12,7 +13,7 @@ pub enum ParseError {
     #[error("Unexpected token: expected {expected}, found {found:?} - {message}")]
     UnexpectedToken {
         expected: String,
-        found: Token,
+        found: Box<Token>,
         message: String,
     },
 
@@ -36,4 +37,120 @@ pub enum ParseError {
 
     #[error("Non-default parameter follows default parameter at line {0}, column {1}")]
     NonDefaultParamAfterDefault(usize, usize),
+
+    #[error("Unexpected name '{found}' at line {line}, column {column}: did you mean the keyword '{keyword}'?")]
+    MisspelledKeyword {
+        found: String,
+        keyword: String,
+        line: usize,
+        column: usize,
+        span: Span,
+    },
+
+    #[error("Nesting too deep at line {line}, column {column}: more than {limit} levels of nested expressions or blocks")]
+    NestingTooDeep {
+        limit: usize,
+        line: usize,
+        column: usize,
+        span: Span,
+    },
+
+    #[error("{construct} requires the unstable feature '{feature}' at line {line}, column {column} (enable it with --unstable-features {feature} or a `# feature: {feature}` comment at the top of the file)")]
+    FeatureNotEnabled {
+        feature: Feature,
+        construct: String,
+        line: usize,
+        column: usize,
+    },
+
+    #[error("Invalid feature pragma at line {line}, column {column}: {message}")]
+    InvalidFeaturePragma {
+        message: String,
+        line: usize,
+        column: usize,
+        span: Span,
+    },
+
+    /// A warning rather than an error: strings joined on one line inside
+    /// a list, tuple or set, where a comma was probably meant; `span` is
+    /// the second string
+    #[error("Implicit string concatenation in a {collection} at line {line}, column {column}: the adjacent strings form one element (is a comma missing?)")]
+    ImplicitConcatenation {
+        collection: &'static str,
+        line: usize,
+        column: usize,
+        span: Span,
+    },
+}
+
+impl ParseError {
+    /// Stable kebab-case identifier of this error kind (e.g. `unexpected-token`)
+    pub fn code(&self) -> &'static str {
+        match self {
+            ParseError::LexError(_) => "lex-error",
+            ParseError::UnexpectedToken { .. } => "unexpected-token",
+            ParseError::UnexpectedEof => "unexpected-eof",
+            ParseError::InvalidSyntax(..) => "invalid-syntax",
+            ParseError::InvalidExpression(..) => "invalid-expression",
+            ParseError::InvalidStatement(..) => "invalid-statement",
+            ParseError::IndentationError(..) => "inconsistent-block-indentation",
+            ParseError::InvalidPattern(..) => "invalid-pattern",
+            ParseError::NonDefaultParamAfterDefault(..) => "lambda-non-default-after-default",
+            ParseError::FeatureNotEnabled { .. } => "feature-not-enabled",
+            ParseError::InvalidFeaturePragma { .. } => "invalid-feature-pragma",
+            ParseError::MisspelledKeyword { .. } => "misspelled-keyword",
+            ParseError::NestingTooDeep { .. } => "nesting-too-deep",
+            ParseError::ImplicitConcatenation { .. } => "implicit-string-concatenation",
+        }
+    }
+
+    /// Stable error code, explained by `silk explain` (e.g. `E0100`);
+    /// lexer errors keep their own code
+    pub fn error_code(&self) -> &'static str {
+        match self {
+            ParseError::LexError(e) => e.error_code(),
+            ParseError::UnexpectedToken { .. } => "E0100",
+            ParseError::UnexpectedEof => "E0101",
+            ParseError::InvalidSyntax(..) => "E0102",
+            ParseError::InvalidExpression(..) => "E0103",
+            ParseError::InvalidStatement(..) => "E0104",
+            ParseError::IndentationError(..) => "E0105",
+            ParseError::InvalidPattern(..) => "E0106",
+            ParseError::NonDefaultParamAfterDefault(..) => "E0107",
+            ParseError::FeatureNotEnabled { .. } => "E0108",
+            ParseError::InvalidFeaturePragma { .. } => "E0111",
+            ParseError::MisspelledKeyword { .. } => "E0109",
+            ParseError::NestingTooDeep { .. } => "E0110",
+            ParseError::ImplicitConcatenation { .. } => "E0516",
+        }
+    }
+
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            ParseError::LexError(e) => e.span(),
+            ParseError::UnexpectedToken { found, .. } => Some(found.span),
+            ParseError::UnexpectedEof => None,
+            ParseError::InvalidSyntax(_, line, col) => Some(Span::new(0, 1, *line, *col)),
+            ParseError::InvalidExpression(line, col) => Some(Span::new(0, 1, *line, *col)),
+            ParseError::InvalidStatement(line, col) => Some(Span::new(0, 1, *line, *col)),
+            ParseError::IndentationError(line, _) => Some(Span::new(0, 1, *line, 1)),
+            ParseError::InvalidPattern(line, col) => Some(Span::new(0, 1, *line, *col)),
+            ParseError::NonDefaultParamAfterDefault(line, col) => {
+                Some(Span::new(0, 1, *line, *col))
+            }
+            ParseError::FeatureNotEnabled { line, column, .. } => {
+                Some(Span::new(0, 1, *line, *column))
+            }
+            ParseError::InvalidFeaturePragma { span, .. } => Some(*span),
+            ParseError::MisspelledKeyword { span, .. } => Some(*span),
+            ParseError::NestingTooDeep { span, .. } => Some(*span),
+            ParseError::ImplicitConcatenation { span, .. } => Some(*span),
+        }
+    }
+
+    /// Check if this is a lint warning, collected by `Parser::warnings`
+    /// instead of failing the parse
+    pub fn is_warning(&self) -> bool {
+        matches!(self, ParseError::ImplicitConcatenation { .. })
+    }
 }
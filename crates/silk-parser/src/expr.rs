@@ -1,28 +1,102 @@
 use crate::{ParseError, ParseResult, Parser};
 /// Expression parsing with operator precedence
 use silk_ast::{
-    BinaryOperator, CompareOperator, Expression, ExpressionKind, LogicalOperator, UnaryOperator,
+    BinaryOperator, CompareOperator, Expression, ExpressionKind, Feature, LogicalOperator,
+    UnaryOperator,
 };
-use silk_lexer::TokenKind;
+use silk_lexer::{FStringPart, Token, TokenKind};
 
 /// Operator precedence levels (higher = tighter binding)
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub(crate) enum Precedence {
     None = 0,
     Walrus = 1,          // := (named expression)
-    Or = 2,              // or
-    And = 3,             // and
-    Not = 4,             // not (prefix)
-    Comparison = 5,      // ==, !=, <, >, <=, >=, in, not in, is, is not
-    BitwiseOr = 6,       // |
-    BitwiseXor = 7,      // ^
-    BitwiseAnd = 8,      // &
-    Shift = 9,           // <<, >>
-    Addition = 10,       // +, -
-    Multiplication = 11, // *, /, //, %, @
-    Unary = 12,          // +, -, ~
-    Power = 13,          // **
-    Primary = 14,        // ., [], ()
+    Ternary = 2,         // x if c else y
+    Or = 3,              // or
+    And = 4,             // and
+    Not = 5,             // not (prefix)
+    Comparison = 6,      // ==, !=, <, >, <=, >=, in, not in, is, is not
+    BitwiseOr = 7,       // |
+    BitwiseXor = 8,      // ^
+    BitwiseAnd = 9,      // &
+    Shift = 10,          // <<, >>
+    Addition = 11,       // +, -
+    Multiplication = 12, // *, /, //, %, @
+    Unary = 13,          // +, -, ~
+    Power = 14,          // **
+    Primary = 15,        // ., [], ()
+}
+
+/// The node a binary, comparison or logical operator builds
+#[derive(Debug, Clone, Copy)]
+enum InfixOperator {
+    Binary(BinaryOperator),
+    Compare(CompareOperator),
+    Logical(LogicalOperator),
+}
+
+impl InfixOperator {
+    fn build(self, left: Expression, right: Expression) -> ExpressionKind {
+        let left = Box::new(left);
+        match self {
+            InfixOperator::Binary(op) => ExpressionKind::BinaryOp {
+                left,
+                op,
+                right: Box::new(right),
+            },
+            InfixOperator::Compare(op) => ExpressionKind::Compare {
+                left,
+                ops: vec![op],
+                comparators: vec![right],
+            },
+            InfixOperator::Logical(op) => ExpressionKind::LogicalOp {
+                left,
+                op,
+                right: Box::new(right),
+            },
+        }
+    }
+}
+
+/// The operator table: precedence and node of each binary, comparison and
+/// logical operator token
+///
+/// Adding an operator takes one entry here. Every operator is
+/// left-associative except `**`; `not` stands for `not in`, and `is` is
+/// followed by an optional `not`.
+fn infix_operator(kind: &TokenKind) -> Option<(Precedence, InfixOperator)> {
+    use InfixOperator::{Binary, Compare, Logical};
+
+    let entry = match kind {
+        TokenKind::Or => (Precedence::Or, Logical(LogicalOperator::Or)),
+        TokenKind::And => (Precedence::And, Logical(LogicalOperator::And)),
+
+        TokenKind::Equal => (Precedence::Comparison, Compare(CompareOperator::Eq)),
+        TokenKind::NotEqual => (Precedence::Comparison, Compare(CompareOperator::NotEq)),
+        TokenKind::Less => (Precedence::Comparison, Compare(CompareOperator::Lt)),
+        TokenKind::Greater => (Precedence::Comparison, Compare(CompareOperator::Gt)),
+        TokenKind::LessEqual => (Precedence::Comparison, Compare(CompareOperator::LtE)),
+        TokenKind::GreaterEqual => (Precedence::Comparison, Compare(CompareOperator::GtE)),
+        TokenKind::In => (Precedence::Comparison, Compare(CompareOperator::In)),
+        TokenKind::Not => (Precedence::Comparison, Compare(CompareOperator::NotIn)),
+        TokenKind::Is => (Precedence::Comparison, Compare(CompareOperator::Is)),
+
+        TokenKind::Pipe => (Precedence::BitwiseOr, Binary(BinaryOperator::BitOr)),
+        TokenKind::Caret => (Precedence::BitwiseXor, Binary(BinaryOperator::BitXor)),
+        TokenKind::Ampersand => (Precedence::BitwiseAnd, Binary(BinaryOperator::BitAnd)),
+        TokenKind::LeftShift => (Precedence::Shift, Binary(BinaryOperator::LShift)),
+        TokenKind::RightShift => (Precedence::Shift, Binary(BinaryOperator::RShift)),
+        TokenKind::Plus => (Precedence::Addition, Binary(BinaryOperator::Add)),
+        TokenKind::Minus => (Precedence::Addition, Binary(BinaryOperator::Sub)),
+        TokenKind::Star => (Precedence::Multiplication, Binary(BinaryOperator::Mult)),
+        TokenKind::Slash => (Precedence::Multiplication, Binary(BinaryOperator::Div)),
+        TokenKind::DoubleSlash => (Precedence::Multiplication, Binary(BinaryOperator::FloorDiv)),
+        TokenKind::Percent => (Precedence::Multiplication, Binary(BinaryOperator::Mod)),
+        TokenKind::At => (Precedence::Multiplication, Binary(BinaryOperator::MatMult)),
+        TokenKind::DoubleStar => (Precedence::Power, Binary(BinaryOperator::Pow)),
+        _ => return None,
+    };
+    Some(entry)
 }
 
 impl Parser {
@@ -31,26 +105,37 @@ impl Parser {
         self.parse_precedence(Precedence::None)
     }
 
-    /// Parse expression with operator precedence climbing
-    pub(crate) fn parse_precedence(&mut self, min_precedence: Precedence) -> ParseResult<Expression> {
-        let mut left = self.parse_primary()?;
+    /// Parse an expression whose operators all bind at least as tightly as
+    /// `min_precedence`
+    ///
+    /// A single precedence-climbing loop: each operator that binds tightly
+    /// enough takes the expression so far as its left operand and parses its
+    /// right operand one level tighter, so the recursion depth follows the
+    /// nesting of the source, not the number of precedence levels.
+    pub(crate) fn parse_precedence(
+        &mut self,
+        min_precedence: Precedence,
+    ) -> ParseResult<Expression> {
+        self.nested(|parser| {
+            let mut left = parser.parse_primary()?;
 
-        while !self.is_at_end() {
-            let precedence = self.get_precedence();
+            while !parser.is_at_end() {
+                let precedence = parser.get_precedence();
 
-            // Stop if precedence is None (not an operator) or less than minimum
-            if precedence == Precedence::None || precedence < min_precedence {
-                break;
-            }
+                // Stop if precedence is None (not an operator) or less than minimum
+                if precedence == Precedence::None || precedence < min_precedence {
+                    break;
+                }
 
-            left = self.parse_infix(left, precedence)?;
-        }
+                left = parser.parse_infix(left, precedence)?;
+            }
 
-        Ok(left)
+            Ok(left)
+        })
     }
 
     /// Parse a primary expression (literals, identifiers, prefix operators, grouping)
-    fn parse_primary(&mut self) -> ParseResult<Expression> {
+    pub(crate) fn parse_primary(&mut self) -> ParseResult<Expression> {
         let start = self.current_token().span;
 
         let kind = match &self.current_token().kind {
@@ -65,31 +150,11 @@ impl Parser {
                 self.advance();
                 ExpressionKind::Float(value)
             }
-            TokenKind::String(value) => {
-                let value = value.clone();
-                self.advance();
-                ExpressionKind::String(value)
-            }
-            TokenKind::RawString(value) => {
-                let value = value.clone();
-                self.advance();
-                ExpressionKind::RawString(value)
-            }
-            TokenKind::ByteString(bytes) => {
-                let bytes = bytes.clone();
-                self.advance();
-                ExpressionKind::ByteString(bytes)
-            }
-            TokenKind::ByteRawString(bytes) => {
-                let bytes = bytes.clone();
-                self.advance();
-                ExpressionKind::ByteRawString(bytes)
-            }
-            TokenKind::FString(parts) => {
-                let parts = parts.clone();
-                self.advance();
-                ExpressionKind::FString { parts }
-            }
+            TokenKind::String(_)
+            | TokenKind::RawString(_)
+            | TokenKind::ByteString(_)
+            | TokenKind::ByteRawString(_)
+            | TokenKind::FString(_) => self.parse_string_literals()?,
             TokenKind::True => {
                 self.advance();
                 ExpressionKind::Boolean(true)
@@ -113,7 +178,7 @@ impl Parser {
 
             // Identifier
             TokenKind::Identifier => {
-                let name = self.current_token().lexeme.clone();
+                let name = self.current_token().lexeme.to_string();
                 self.advance();
                 ExpressionKind::Identifier(name)
             }
@@ -154,6 +219,7 @@ impl Parser {
                         }
 
                         self.expect(TokenKind::RightParen, "Expected ')' after tuple elements")?;
+                        self.check_implicit_concatenations(&elements, "tuple");
                         ExpressionKind::Tuple { elements }
                     } else {
                         // No comma, just a parenthesized expression
@@ -196,7 +262,7 @@ impl Parser {
                 // Check for empty list
                 if self.check(TokenKind::RightBracket) {
                     self.advance();
-                    return Ok(Expression::new(
+                    return Ok(self.expression(
                         ExpressionKind::List { elements: vec![] },
                         silk_lexer::Span::new(
                             start.start,
@@ -230,6 +296,7 @@ impl Parser {
                 }
 
                 self.expect(TokenKind::RightBracket, "Expected ']' after list elements")?;
+                self.check_implicit_concatenations(&elements, "list");
                 ExpressionKind::List { elements }
             }
 
@@ -240,7 +307,7 @@ impl Parser {
                 // Empty dict (by default, {} is a dict, not a set)
                 if self.check(TokenKind::RightBrace) {
                     self.advance();
-                    return Ok(Expression::new(
+                    return Ok(self.expression(
                         ExpressionKind::Dict {
                             keys: vec![],
                             values: vec![],
@@ -313,6 +380,7 @@ impl Parser {
                     }
 
                     self.expect(TokenKind::RightBrace, "Expected '}' after set elements")?;
+                    self.check_implicit_concatenations(&elements, "set");
                     ExpressionKind::Set { elements }
                 }
             }
@@ -330,7 +398,8 @@ impl Parser {
                         let param_start = self.current_token().span;
                         let name = self
                             .expect(TokenKind::Identifier, "Expected parameter name in lambda")?
-                            .lexeme;
+                            .lexeme
+                            .to_string();
 
                         // Check for default value
                         let default = if self.check(TokenKind::Assign) {
@@ -393,226 +462,17 @@ impl Parser {
 
         let span = silk_lexer::Span::new(start.start, end_pos, start.line, start.column);
 
-        Ok(Expression::new(kind, span))
+        Ok(self.expression(kind, span))
     }
 
-    /// Parse infix operators (binary, comparison, logical, postfix)
-    fn parse_infix(
-        &mut self,
-        left: Expression,
-        _precedence: Precedence,
-    ) -> ParseResult<Expression> {
-        let start = left.span.clone();
-        let op_start = self.current_token().span.clone();
+    /// Parse the operator at the current token with `left` as its left
+    /// operand: a binary, comparison or logical operator from the operator
+    /// table, or a walrus, ternary, call, subscript or attribute access
+    fn parse_infix(&mut self, left: Expression, precedence: Precedence) -> ParseResult<Expression> {
+        let start = left.span;
+        let op_start = self.current_token().span;
 
         let kind = match self.current_token().kind {
-            // Binary operators
-            TokenKind::Plus => {
-                self.advance();
-                let right = self.parse_precedence(Precedence::Addition.succ())?;
-                ExpressionKind::BinaryOp {
-                    left: Box::new(left),
-                    op: BinaryOperator::Add,
-                    right: Box::new(right),
-                }
-            }
-            TokenKind::Minus => {
-                self.advance();
-                let right = self.parse_precedence(Precedence::Addition.succ())?;
-                ExpressionKind::BinaryOp {
-                    left: Box::new(left),
-                    op: BinaryOperator::Sub,
-                    right: Box::new(right),
-                }
-            }
-            TokenKind::Star => {
-                self.advance();
-                let right = self.parse_precedence(Precedence::Multiplication.succ())?;
-                ExpressionKind::BinaryOp {
-                    left: Box::new(left),
-                    op: BinaryOperator::Mult,
-                    right: Box::new(right),
-                }
-            }
-            TokenKind::Slash => {
-                self.advance();
-                let right = self.parse_precedence(Precedence::Multiplication.succ())?;
-                ExpressionKind::BinaryOp {
-                    left: Box::new(left),
-                    op: BinaryOperator::Div,
-                    right: Box::new(right),
-                }
-            }
-            TokenKind::DoubleStar => {
-                self.advance();
-                // Power is right-associative
-                let right = self.parse_precedence(Precedence::Power)?;
-                ExpressionKind::BinaryOp {
-                    left: Box::new(left),
-                    op: BinaryOperator::Pow,
-                    right: Box::new(right),
-                }
-            }
-            TokenKind::DoubleSlash => {
-                self.advance();
-                let right = self.parse_precedence(Precedence::Multiplication.succ())?;
-                ExpressionKind::BinaryOp {
-                    left: Box::new(left),
-                    op: BinaryOperator::FloorDiv,
-                    right: Box::new(right),
-                }
-            }
-            TokenKind::Percent => {
-                self.advance();
-                let right = self.parse_precedence(Precedence::Multiplication.succ())?;
-                ExpressionKind::BinaryOp {
-                    left: Box::new(left),
-                    op: BinaryOperator::Mod,
-                    right: Box::new(right),
-                }
-            }
-
-            // Bitwise operators
-            TokenKind::Pipe => {
-                self.advance();
-                let right = self.parse_precedence(Precedence::BitwiseOr.succ())?;
-                ExpressionKind::BinaryOp {
-                    left: Box::new(left),
-                    op: BinaryOperator::BitOr,
-                    right: Box::new(right),
-                }
-            }
-            TokenKind::Caret => {
-                self.advance();
-                let right = self.parse_precedence(Precedence::BitwiseXor.succ())?;
-                ExpressionKind::BinaryOp {
-                    left: Box::new(left),
-                    op: BinaryOperator::BitXor,
-                    right: Box::new(right),
-                }
-            }
-            TokenKind::Ampersand => {
-                self.advance();
-                let right = self.parse_precedence(Precedence::BitwiseAnd.succ())?;
-                ExpressionKind::BinaryOp {
-                    left: Box::new(left),
-                    op: BinaryOperator::BitAnd,
-                    right: Box::new(right),
-                }
-            }
-            TokenKind::LeftShift => {
-                self.advance();
-                let right = self.parse_precedence(Precedence::Shift.succ())?;
-                ExpressionKind::BinaryOp {
-                    left: Box::new(left),
-                    op: BinaryOperator::LShift,
-                    right: Box::new(right),
-                }
-            }
-            TokenKind::RightShift => {
-                self.advance();
-                let right = self.parse_precedence(Precedence::Shift.succ())?;
-                ExpressionKind::BinaryOp {
-                    left: Box::new(left),
-                    op: BinaryOperator::RShift,
-                    right: Box::new(right),
-                }
-            }
-
-            // Comparison operators
-            TokenKind::Equal => {
-                self.advance();
-                let right = self.parse_precedence(Precedence::Comparison.succ())?;
-                ExpressionKind::Compare {
-                    left: Box::new(left),
-                    ops: vec![CompareOperator::Eq],
-                    comparators: vec![right],
-                }
-            }
-            TokenKind::NotEqual => {
-                self.advance();
-                let right = self.parse_precedence(Precedence::Comparison.succ())?;
-                ExpressionKind::Compare {
-                    left: Box::new(left),
-                    ops: vec![CompareOperator::NotEq],
-                    comparators: vec![right],
-                }
-            }
-            TokenKind::Less => {
-                self.advance();
-                let right = self.parse_precedence(Precedence::Comparison.succ())?;
-                ExpressionKind::Compare {
-                    left: Box::new(left),
-                    ops: vec![CompareOperator::Lt],
-                    comparators: vec![right],
-                }
-            }
-            TokenKind::Greater => {
-                self.advance();
-                let right = self.parse_precedence(Precedence::Comparison.succ())?;
-                ExpressionKind::Compare {
-                    left: Box::new(left),
-                    ops: vec![CompareOperator::Gt],
-                    comparators: vec![right],
-                }
-            }
-            TokenKind::LessEqual => {
-                self.advance();
-                let right = self.parse_precedence(Precedence::Comparison.succ())?;
-                ExpressionKind::Compare {
-                    left: Box::new(left),
-                    ops: vec![CompareOperator::LtE],
-                    comparators: vec![right],
-                }
-            }
-            TokenKind::GreaterEqual => {
-                self.advance();
-                let right = self.parse_precedence(Precedence::Comparison.succ())?;
-                ExpressionKind::Compare {
-                    left: Box::new(left),
-                    ops: vec![CompareOperator::GtE],
-                    comparators: vec![right],
-                }
-            }
-            TokenKind::Is => {
-                self.advance();
-                let right = self.parse_precedence(Precedence::Comparison.succ())?;
-                ExpressionKind::Compare {
-                    left: Box::new(left),
-                    ops: vec![CompareOperator::Is],
-                    comparators: vec![right],
-                }
-            }
-            TokenKind::In => {
-                self.advance();
-                let right = self.parse_precedence(Precedence::Comparison.succ())?;
-                ExpressionKind::Compare {
-                    left: Box::new(left),
-                    ops: vec![CompareOperator::In],
-                    comparators: vec![right],
-                }
-            }
-
-            // Logical operators
-            TokenKind::And => {
-                self.advance();
-                let right = self.parse_precedence(Precedence::And.succ())?;
-                ExpressionKind::LogicalOp {
-                    left: Box::new(left),
-                    op: LogicalOperator::And,
-                    right: Box::new(right),
-                }
-            }
-            TokenKind::Or => {
-                self.advance();
-                let right = self.parse_precedence(Precedence::Or.succ())?;
-                ExpressionKind::LogicalOp {
-                    left: Box::new(left),
-                    op: LogicalOperator::Or,
-                    right: Box::new(right),
-                }
-            }
-
             // Named expression (walrus operator :=)
             TokenKind::ColonEqual => {
                 self.advance(); // consume ':='
@@ -639,8 +499,8 @@ impl Parser {
             TokenKind::If => {
                 self.advance(); // consume 'if'
 
-                // Parse the test condition
-                let test = Box::new(self.parse_precedence(Precedence::Or.succ())?);
+                // The test may use `or` but not another unparenthesized ternary
+                let test = Box::new(self.parse_precedence(Precedence::Or)?);
 
                 // Expect 'else'
                 self.expect(TokenKind::Else, "Expected 'else' in conditional expression")?;
@@ -669,7 +529,21 @@ impl Parser {
                 self.parse_attribute(left)?
             }
 
-            _ => return Ok(left),
+            // Binary, comparison and logical operators
+            ref kind => {
+                let Some((_, operator)) = infix_operator(kind) else {
+                    return Ok(left);
+                };
+                let operator = self.parse_operator(operator)?;
+                // `**` is right-associative: its right operand may hold another `**`
+                let right_precedence = if precedence == Precedence::Power {
+                    precedence
+                } else {
+                    precedence.succ()
+                };
+                let right = self.parse_precedence(right_precedence)?;
+                operator.build(left, right)
+            }
         };
 
         // Get end position from the previous token (we've moved past it)
@@ -681,35 +555,41 @@ impl Parser {
 
         let span = silk_lexer::Span::new(start.start, end_pos, start.line, start.column);
 
-        Ok(Expression::new(kind, span))
+        Ok(self.expression(kind, span))
+    }
+
+    /// Consume the tokens of an operator from the operator table, including
+    /// the second token of `not in` and `is not`
+    fn parse_operator(&mut self, operator: InfixOperator) -> ParseResult<InfixOperator> {
+        self.advance();
+        match operator {
+            // A prefix `not` never reaches the infix loop
+            InfixOperator::Compare(CompareOperator::NotIn) => {
+                self.expect(TokenKind::In, "Expected 'in' after 'not'")?;
+            }
+            // `is not` is a single operator, not `is (not x)`
+            InfixOperator::Compare(CompareOperator::Is) if self.check(TokenKind::Not) => {
+                self.advance();
+                return Ok(InfixOperator::Compare(CompareOperator::IsNot));
+            }
+            _ => {}
+        }
+        Ok(operator)
     }
 
     /// Get precedence of current token
     fn get_precedence(&self) -> Precedence {
         match self.current_token().kind {
             TokenKind::ColonEqual => Precedence::Walrus,
-            TokenKind::If => Precedence::Or, // Ternary is at Or precedence level
-            TokenKind::Or => Precedence::Or,
-            TokenKind::And => Precedence::And,
-            TokenKind::Equal
-            | TokenKind::NotEqual
-            | TokenKind::Less
-            | TokenKind::Greater
-            | TokenKind::LessEqual
-            | TokenKind::GreaterEqual
-            | TokenKind::In
-            | TokenKind::Is => Precedence::Comparison,
-            TokenKind::Pipe => Precedence::BitwiseOr,
-            TokenKind::Caret => Precedence::BitwiseXor,
-            TokenKind::Ampersand => Precedence::BitwiseAnd,
-            TokenKind::LeftShift | TokenKind::RightShift => Precedence::Shift,
-            TokenKind::Plus | TokenKind::Minus => Precedence::Addition,
-            TokenKind::Star | TokenKind::Slash | TokenKind::DoubleSlash | TokenKind::Percent => {
-                Precedence::Multiplication
+            TokenKind::If => Precedence::Ternary,
+            // `not in`; a `not` followed by anything else ends the expression
+            TokenKind::Not
+                if !matches!(self.peek_token(1).map(|t| &t.kind), Some(TokenKind::In)) =>
+            {
+                Precedence::None
             }
-            TokenKind::DoubleStar => Precedence::Power,
             TokenKind::LeftParen | TokenKind::LeftBracket | TokenKind::Dot => Precedence::Primary,
-            _ => Precedence::None,
+            ref kind => infix_operator(kind).map_or(Precedence::None, |(precedence, _)| precedence),
         }
     }
 
@@ -721,7 +601,7 @@ impl Parser {
         let mut seen_keyword = false;
 
         while !self.check(TokenKind::RightParen) && !self.is_at_end() {
-            let arg_start = self.current_token().span.clone();
+            let arg_start = self.current_token().span;
 
             // Check for **kwargs
             if self.check(TokenKind::DoubleStar) {
@@ -740,7 +620,7 @@ impl Parser {
                 if let Some(next_tok) = self.peek_token(1) {
                     if matches!(next_tok.kind, TokenKind::Assign) {
                         // This is a keyword argument: name=value
-                        let name = self.current_token().lexeme.clone();
+                        let name = self.current_token().lexeme.to_string();
                         self.advance(); // consume identifier
                         self.advance(); // consume '='
                         let value = self.parse_expression()?;
@@ -765,7 +645,7 @@ impl Parser {
                         // Check for generator expression
                         if self.check(TokenKind::For) {
                             let generators = self.parse_comprehension_generators()?;
-                            args.push(Expression::new(
+                            args.push(self.expression(
                                 ExpressionKind::GeneratorExp {
                                     element: Box::new(expr),
                                     generators,
@@ -796,7 +676,7 @@ impl Parser {
                     // Check for generator expression
                     if self.check(TokenKind::For) {
                         let generators = self.parse_comprehension_generators()?;
-                        args.push(Expression::new(
+                        args.push(self.expression(
                             ExpressionKind::GeneratorExp {
                                 element: Box::new(expr),
                                 generators,
@@ -829,7 +709,7 @@ impl Parser {
                 // Check for generator expression: func(x for x in items)
                 if self.check(TokenKind::For) {
                     let generators = self.parse_comprehension_generators()?;
-                    args.push(Expression::new(
+                    args.push(self.expression(
                         ExpressionKind::GeneratorExp {
                             element: Box::new(expr),
                             generators,
@@ -902,8 +782,8 @@ impl Parser {
             self.expect(TokenKind::RightBracket, "Expected ']' after slice")?;
 
             // Create a Slice expression as the index
-            let start = self.current_token().span.clone();
-            let slice_expr = Expression::new(
+            let start = self.current_token().span;
+            let slice_expr = self.expression(
                 ExpressionKind::Slice {
                     lower: first,
                     upper: stop,
@@ -927,14 +807,69 @@ impl Parser {
         }
     }
 
+    /// Parse one or more adjacent string literals as a single literal
+    ///
+    /// As in Python, `"a" "b"` is `"ab"`: text strings join into a string,
+    /// or into an f-string when one of them is an f-string, and bytes join
+    /// with bytes only. The result is raw only when every piece is.
+    fn parse_string_literals(&mut self) -> ParseResult<ExpressionKind> {
+        // The pieces are read in place; only their values are copied
+        let first = self.position;
+        self.advance();
+        while is_string_literal(&self.current_token().kind) {
+            self.advance();
+        }
+        let pieces = &self.tokens[first..self.position];
+
+        let kind = match pieces {
+            [single] => single_literal(&single.kind),
+            _ => join_literals(pieces)?,
+        };
+
+        // Strings on one line are remembered for the lint on collection
+        // elements; a triple-quoted string may end on a later line
+        let same_line = pieces.windows(2).find(|pair| {
+            pair[0].span.line + pair[0].lexeme.matches('\n').count() == pair[1].span.line
+        });
+        if let Some(pair) = same_line {
+            let (start, end) = (pieces[0].span.start, pair[1].span);
+            self.same_line_concatenations.insert(start, end);
+        }
+        Ok(kind)
+    }
+
+    /// Warn about elements of a list, tuple or set display that are strings
+    /// joined on one line: `["a", "b" "c"]` most likely misses a comma
+    fn check_implicit_concatenations(&mut self, elements: &[Expression], collection: &'static str) {
+        for element in elements {
+            if let Some(&span) = self.same_line_concatenations.get(&element.span.start) {
+                self.warnings.push(ParseError::ImplicitConcatenation {
+                    collection,
+                    line: span.line,
+                    column: span.column,
+                    span,
+                });
+            }
+        }
+    }
+
     fn parse_attribute(&mut self, value: Expression) -> ParseResult<ExpressionKind> {
         self.advance(); // consume '.'
 
-        let attr = self.expect(TokenKind::Identifier, "Expected attribute name after '.'")?;
+        // `match` and `case` are soft keywords in Python, so attributes may
+        // be named after them (`re.match`)
+        let attr = if matches!(
+            self.current_token().kind,
+            TokenKind::Match | TokenKind::Case
+        ) {
+            self.advance().clone()
+        } else {
+            self.expect(TokenKind::Identifier, "Expected attribute name after '.'")?
+        };
 
         Ok(ExpressionKind::Attribute {
             value: Box::new(value),
-            attr: attr.lexeme,
+            attr: attr.lexeme.to_string(),
         })
     }
 
@@ -950,8 +885,8 @@ impl Parser {
             "Expected ']' after list comprehension",
         )?;
 
-        let end = self.current_token().span.clone();
-        Ok(Expression::new(
+        let end = self.current_token().span;
+        Ok(self.expression(
             ExpressionKind::ListComp {
                 element: Box::new(element),
                 generators,
@@ -973,7 +908,7 @@ impl Parser {
             TokenKind::RightBrace,
             "Expected '}' after dict comprehension",
         )?;
-        Ok(Expression::new(
+        Ok(self.expression(
             ExpressionKind::DictComp {
                 key: Box::new(key),
                 value: Box::new(value),
@@ -995,7 +930,7 @@ impl Parser {
             TokenKind::RightBrace,
             "Expected '}' after set comprehension",
         )?;
-        Ok(Expression::new(
+        Ok(self.expression(
             ExpressionKind::SetComp {
                 element: Box::new(element),
                 generators,
@@ -1016,7 +951,7 @@ impl Parser {
             TokenKind::RightParen,
             "Expected ')' after generator expression",
         )?;
-        Ok(Expression::new(
+        Ok(self.expression(
             ExpressionKind::GeneratorExp {
                 element: Box::new(element),
                 generators,
@@ -1042,8 +977,15 @@ impl Parser {
             // Expect 'in'
             self.expect(TokenKind::In, "Expected 'in' after comprehension target")?;
 
-            // Parse iterator - use Comparison precedence to stop before 'if' or ']'
-            let iter = self.parse_precedence(Precedence::Comparison)?;
+            // Parse iterator - use Or precedence to stop before 'if' or ']'
+            let iter = self.parse_precedence(Precedence::Or)?;
+            if matches!(iter.kind, ExpressionKind::NamedExpr { .. }) {
+                self.require_feature(
+                    Feature::ComprehensionAssignments,
+                    "Assignment expression in a comprehension iterable",
+                    iter.span,
+                )?;
+            }
 
             // Parse optional 'if' filters for this generator
             let mut ifs = Vec::new();
@@ -1051,8 +993,8 @@ impl Parser {
                 self.advance(); // consume 'if'
 
                 // Parse the filter condition
-                // Use And precedence (one level below Or) to stop BEFORE ternary 'if'
-                let filter = self.parse_precedence(Precedence::And)?;
+                // Use Or precedence (one level above Ternary) to stop BEFORE ternary 'if'
+                let filter = self.parse_precedence(Precedence::Or)?;
                 ifs.push(filter);
             }
 
@@ -1077,8 +1019,115 @@ impl Precedence {
     /// Get next higher precedence level (for left-associative operators)
     pub(crate) fn succ(self) -> Self {
         match self as u8 {
-            x if x < Precedence::Primary as u8 => unsafe { std::mem::transmute(x + 1) },
+            x if x < Precedence::Primary as u8 => unsafe {
+                std::mem::transmute::<u8, Precedence>(x + 1)
+            },
             _ => self,
         }
     }
 }
+
+fn is_string_literal(kind: &TokenKind) -> bool {
+    matches!(
+        kind,
+        TokenKind::String(_)
+            | TokenKind::RawString(_)
+            | TokenKind::ByteString(_)
+            | TokenKind::ByteRawString(_)
+            | TokenKind::FString(_)
+    )
+}
+
+/// The literal of a single string token
+fn single_literal(kind: &TokenKind) -> ExpressionKind {
+    match kind {
+        TokenKind::String(value) => ExpressionKind::String(value.to_string()),
+        TokenKind::RawString(value) => ExpressionKind::RawString(value.to_string()),
+        TokenKind::ByteString(bytes) => ExpressionKind::ByteString(bytes.to_vec()),
+        TokenKind::ByteRawString(bytes) => ExpressionKind::ByteRawString(bytes.to_vec()),
+        TokenKind::FString(parts) => ExpressionKind::FString {
+            parts: parts.to_vec(),
+        },
+        _ => unreachable!("not a string literal: {:?}", kind),
+    }
+}
+
+/// The literal of two or more adjacent string tokens
+fn join_literals(pieces: &[Token]) -> ParseResult<ExpressionKind> {
+    let is_bytes = |token: &Token| {
+        matches!(
+            token.kind,
+            TokenKind::ByteString(_) | TokenKind::ByteRawString(_)
+        )
+    };
+    let bytes = is_bytes(&pieces[0]);
+    if let Some(mixed) = pieces.iter().find(|piece| is_bytes(piece) != bytes) {
+        return Err(ParseError::InvalidSyntax(
+            "cannot join bytes and non-bytes string literals".to_string(),
+            mixed.span.line,
+            mixed.span.column,
+        ));
+    }
+    let all_raw = pieces.iter().all(|piece| {
+        matches!(
+            piece.kind,
+            TokenKind::RawString(_) | TokenKind::ByteRawString(_)
+        )
+    });
+
+    if bytes {
+        let mut value = Vec::new();
+        for piece in pieces {
+            if let TokenKind::ByteString(bytes) | TokenKind::ByteRawString(bytes) = &piece.kind {
+                value.extend_from_slice(bytes);
+            }
+        }
+        return Ok(if all_raw {
+            ExpressionKind::ByteRawString(value)
+        } else {
+            ExpressionKind::ByteString(value)
+        });
+    }
+
+    if !pieces
+        .iter()
+        .any(|piece| matches!(piece.kind, TokenKind::FString(_)))
+    {
+        let mut value = String::new();
+        for piece in pieces {
+            if let TokenKind::String(text) | TokenKind::RawString(text) = &piece.kind {
+                value.push_str(text);
+            }
+        }
+        return Ok(if all_raw {
+            ExpressionKind::RawString(value)
+        } else {
+            ExpressionKind::String(value)
+        });
+    }
+
+    // Text next to text becomes one part
+    fn push_text(parts: &mut Vec<FStringPart>, text: &str) {
+        match parts.last_mut() {
+            Some(FStringPart::Text(last)) => last.push_str(text),
+            _ if text.is_empty() => {}
+            _ => parts.push(FStringPart::Text(text.to_string())),
+        }
+    }
+    let mut parts = Vec::new();
+    for piece in pieces {
+        match &piece.kind {
+            TokenKind::String(text) | TokenKind::RawString(text) => push_text(&mut parts, text),
+            TokenKind::FString(piece_parts) => {
+                for part in piece_parts.iter() {
+                    match part {
+                        FStringPart::Text(text) => push_text(&mut parts, text),
+                        expression => parts.push(expression.clone()),
+                    }
+                }
+            }
+            _ => unreachable!("bytes were rejected above"),
+        }
+    }
+    Ok(ExpressionKind::FString { parts })
+}
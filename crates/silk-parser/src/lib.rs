@@ -3,29 +3,87 @@
 /// Implements a recursive descent parser that converts tokens into an AST.
 pub mod error;
 pub mod expr;
+pub mod pattern;
 pub mod stmt;
 
 pub use error::*;
 
-use silk_ast::Program;
-use silk_lexer::{Lexer, Token, TokenKind};
+use silk_ast::{
+    Expression, ExpressionKind, Feature, FeatureSet, NodeId, Program, ReleaseChannel, Statement,
+    StatementKind,
+};
+use silk_lexer::{Lexer, Span, Token, TokenKind};
+use std::collections::HashMap;
+
+/// Deepest nesting of expressions, blocks and type annotations the parser
+/// accepts
+///
+/// Each level of nesting is a level of recursion in the parser, so without
+/// a limit an input such as ten thousand `(` would overflow the stack. The
+/// limit leaves room on a main thread's stack even in debug builds.
+///
+/// Only constructs the parser recurses on count: brackets, blocks, unary
+/// operators, lambdas and the right operand of `**` and `if`-`else`.
+/// Operator, attribute, call and subscript chains (`1 + 1 + ...`) and
+/// `elif` ladders are parsed in a loop and may be of any length; later
+/// passes grow their stack on them (`silk_ast::ensure_sufficient_stack`).
+pub const MAX_NESTING_DEPTH: usize = 100;
 
 /// Parser state
 pub struct Parser {
     tokens: Vec<Token>,
     position: usize,
+    /// Current nesting depth, at most `MAX_NESTING_DEPTH`
+    depth: usize,
+    /// Unstable features enabled for this parse, including those of the
+    /// source's feature pragmas
+    features: FeatureSet,
+    /// Features enabled by `# feature:` pragmas, recorded on the program
+    pragma_features: FeatureSet,
+    /// First invalid feature pragma, reported when parsing starts
+    pragma_error: Option<ParseError>,
+    /// Start offsets of string literals joined from strings on one line,
+    /// with the span of the second string
+    same_line_concatenations: HashMap<usize, Span>,
+    /// Lint warnings found so far
+    warnings: Vec<ParseError>,
+    /// Id of the next statement or expression created
+    next_node_id: u32,
 }
 
 impl Parser {
     /// Create a new parser from source code
     pub fn new(source: &str) -> ParseResult<Self> {
+        Self::with_features(source, FeatureSet::new())
+    }
+
+    /// Create a new parser with a set of unstable features enabled
+    pub fn with_features(source: &str, features: FeatureSet) -> ParseResult<Self> {
         let mut lexer = Lexer::new(source);
         let tokens = lexer.tokenize().map_err(ParseError::LexError)?;
 
-        Ok(Self {
+        Ok(Self::from_tokens(tokens, features))
+    }
+
+    /// Create a parser over an already lexed token stream (ending in `Eof`)
+    ///
+    /// Comment tokens, which the lexer keeps for tools, are dropped once
+    /// the feature pragmas among them have been read.
+    pub fn from_tokens(mut tokens: Vec<Token>, mut features: FeatureSet) -> Self {
+        let (pragma_features, pragma_error) = feature_pragmas(&tokens);
+        features.extend(pragma_features.iter());
+        tokens.retain(|token| token.kind != TokenKind::Comment);
+        Self {
             tokens,
             position: 0,
-        })
+            depth: 0,
+            features,
+            pragma_features,
+            pragma_error,
+            same_line_concatenations: HashMap::new(),
+            warnings: Vec::new(),
+            next_node_id: 0,
+        }
     }
 
     /// Parse source code into an AST
@@ -34,9 +92,30 @@ impl Parser {
         parser.parse_program()
     }
 
+    /// Parse source code into an AST with a set of unstable features enabled
+    pub fn parse_with_features(source: &str, features: FeatureSet) -> ParseResult<Program> {
+        let mut parser = Self::with_features(source, features)?;
+        parser.parse_program()
+    }
+
+    /// Unstable features enabled for this parser
+    pub fn features(&self) -> &FeatureSet {
+        &self.features
+    }
+
+    /// Lint warnings found while parsing, such as a likely missing comma;
+    /// see `ParseError::is_warning`
+    pub fn warnings(&self) -> &[ParseError] {
+        &self.warnings
+    }
+
     /// Parse a program (sequence of statements)
-    fn parse_program(&mut self) -> ParseResult<Program> {
-        let start_span = self.current_token().span.clone();
+    pub fn parse_program(&mut self) -> ParseResult<Program> {
+        let _span = tracing::debug_span!("parse", tokens = self.tokens.len()).entered();
+        if let Some(error) = self.pragma_error.take() {
+            return Err(error);
+        }
+        let start_span = self.current_token().span;
         let mut statements = Vec::new();
 
         while !self.is_at_end() {
@@ -50,9 +129,9 @@ impl Parser {
         }
 
         let end_span = if statements.is_empty() {
-            start_span.clone()
+            start_span
         } else {
-            statements.last().unwrap().span.clone()
+            statements.last().unwrap().span
         };
 
         let span = silk_lexer::Span::new(
@@ -62,16 +141,34 @@ impl Parser {
             start_span.column,
         );
 
-        Ok(Program::new(statements, span))
+        let mut program = Program::new(statements, span);
+        program.features = self.pragma_features.clone();
+        program.node_count = self.next_node_id as usize;
+        Ok(program)
     }
 
     // Helper methods
 
+    /// A new expression node, with the next node id
+    fn expression(&mut self, kind: ExpressionKind, span: Span) -> Expression {
+        Expression::with_id(kind, span, self.node_id())
+    }
+
+    /// A new statement node, with the next node id
+    fn statement(&mut self, kind: StatementKind, span: Span) -> Statement {
+        Statement::with_id(kind, span, self.node_id())
+    }
+
+    fn node_id(&mut self) -> NodeId {
+        let id = NodeId::new(self.next_node_id);
+        self.next_node_id += 1;
+        id
+    }
+
     fn current_token(&self) -> &Token {
         &self.tokens[self.position]
     }
 
-    #[allow(dead_code)] // TODO: Used for lookahead parsing
     fn peek_token(&self, offset: usize) -> Option<&Token> {
         self.tokens.get(self.position + offset)
     }
@@ -83,6 +180,36 @@ impl Parser {
         &self.tokens[self.position - 1]
     }
 
+    /// Span from `start` to the end of the last line of code consumed: its
+    /// last token that is not layout (a newline, indent or dedent) and the
+    /// newline after it, so a block ends on its own last line rather than at
+    /// the statement after it, and still contains its last statement
+    fn span_to_last_line(&self, start: Span) -> Span {
+        let consumed = &self.tokens[..self.position];
+        let is_layout = |token: &Token| {
+            matches!(
+                token.kind,
+                TokenKind::Newline | TokenKind::Indent | TokenKind::Dedent
+            )
+        };
+        let end = match consumed.iter().rposition(|token| !is_layout(token)) {
+            Some(last) => match consumed.get(last + 1) {
+                Some(next) if matches!(next.kind, TokenKind::Newline) => next.span.end,
+                _ => consumed[last].span.end,
+            },
+            None => start.end,
+        };
+        Span::new(start.start, end, start.line, start.column)
+    }
+
+    /// Span from `start` to the end of the most recently consumed token
+    fn span_since(&self, start: Span) -> Span {
+        let end = self.tokens[..self.position]
+            .last()
+            .map_or(start.end, |token| token.span.end);
+        Span::new(start.start, end, start.line, start.column)
+    }
+
     fn check(&self, kind: TokenKind) -> bool {
         !self.is_at_end()
             && std::mem::discriminant(&self.current_token().kind) == std::mem::discriminant(&kind)
@@ -99,15 +226,86 @@ impl Parser {
         }
     }
 
+    /// Run `parse` one level of nesting deeper, failing with
+    /// `NestingTooDeep` past `MAX_NESTING_DEPTH`
+    fn nested<T>(&mut self, parse: impl FnOnce(&mut Self) -> ParseResult<T>) -> ParseResult<T> {
+        if self.depth >= MAX_NESTING_DEPTH {
+            let span = self.current_token().span;
+            return Err(ParseError::NestingTooDeep {
+                limit: MAX_NESTING_DEPTH,
+                line: span.line,
+                column: span.column,
+                span,
+            });
+        }
+        self.depth += 1;
+        let result = parse(self);
+        self.depth -= 1;
+        result
+    }
+
+    /// Reject a gated construct unless its feature is enabled
+    fn require_feature(&self, feature: Feature, construct: &str, span: Span) -> ParseResult<()> {
+        if self.features.is_enabled(feature) {
+            Ok(())
+        } else {
+            Err(ParseError::FeatureNotEnabled {
+                feature,
+                construct: construct.to_string(),
+                line: span.line,
+                column: span.column,
+            })
+        }
+    }
+
     fn expect(&mut self, kind: TokenKind, msg: &str) -> ParseResult<Token> {
         if self.check(kind.clone()) {
             Ok(self.advance().clone())
         } else {
             Err(ParseError::UnexpectedToken {
                 expected: format!("{:?}", kind),
-                found: self.current_token().clone(),
+                found: Box::new(self.current_token().clone()),
                 message: msg.to_string(),
             })
         }
     }
 }
+
+/// Features enabled by `# feature:` pragmas, and the first pragma that is
+/// invalid: one naming an unknown feature, used on the stable channel, or
+/// placed after the first statement
+fn feature_pragmas(tokens: &[Token]) -> (FeatureSet, Option<ParseError>) {
+    let mut features = FeatureSet::new();
+    let mut before_statements = true;
+    for token in tokens {
+        match token.kind {
+            TokenKind::Comment => {}
+            TokenKind::Newline => continue,
+            _ => {
+                before_statements = false;
+                continue;
+            }
+        }
+        let Some(pragma) = FeatureSet::from_pragma(&token.lexeme, ReleaseChannel::current()) else {
+            continue;
+        };
+        let message = match pragma {
+            Ok(_) if !before_statements => {
+                "feature pragmas must come before the first statement".to_string()
+            }
+            Ok(pragma) => {
+                features.extend(pragma.iter());
+                continue;
+            }
+            Err(e) => e.to_string(),
+        };
+        let error = ParseError::InvalidFeaturePragma {
+            message,
+            line: token.span.line,
+            column: token.span.column,
+            span: token.span,
+        };
+        return (features, Some(error));
+    }
+    (features, None)
+}
@@ -0,0 +1,427 @@
+//! Pattern parsing for `match` statements
+//!
+//! Case patterns have a grammar of their own (PEP 634), so they are parsed
+//! from tokens rather than converted from an expression: literals, captures
+//! and `_`, dotted values (`Color.RED`), sequences (`[first, *rest]`),
+//! mappings (`{"id": id, **rest}`), classes (`Point(x, y=0)`), or-patterns
+//! (`1 | 2`) and `as` patterns. The rules CPython checks when compiling a
+//! pattern, such as alternatives binding the same names, are checked here.
+
+use crate::{ParseError, ParseResult, Parser};
+use silk_ast::{Expression, ExpressionKind, Pattern, PatternKind, UnaryOperator};
+use silk_lexer::{Span, TokenKind};
+use std::collections::HashSet;
+
+impl Parser {
+    /// Parse the pattern of a `case` clause, which may be a sequence without
+    /// brackets (`case x, y:`)
+    pub(crate) fn parse_case_pattern(&mut self) -> ParseResult<Pattern> {
+        let start = self.current_token().span;
+        let first = self.parse_maybe_star_pattern()?;
+        let pattern = if self.check(TokenKind::Comma) {
+            let mut patterns = vec![first];
+            while self.check(TokenKind::Comma) {
+                self.advance(); // consume ','
+                if self.check(TokenKind::Colon) || self.check(TokenKind::If) {
+                    break;
+                }
+                patterns.push(self.parse_maybe_star_pattern()?);
+            }
+            Pattern::new(PatternKind::Sequence { patterns }, self.span_since(start))
+        } else {
+            first
+        };
+        check_pattern(&pattern)?;
+        Ok(pattern)
+    }
+
+    /// `pattern ['as' name]`
+    fn parse_pattern(&mut self) -> ParseResult<Pattern> {
+        self.nested(|parser| {
+            let start = parser.current_token().span;
+            let pattern = parser.parse_or_pattern()?;
+            if !parser.check(TokenKind::As) {
+                return Ok(pattern);
+            }
+            parser.advance(); // consume 'as'
+            let name = parser.expect(TokenKind::Identifier, "Expected a name after 'as'")?;
+            if name.lexeme == "_" {
+                return Err(invalid("cannot use '_' as a target", name.span));
+            }
+            Ok(Pattern::new(
+                PatternKind::As {
+                    pattern: Box::new(pattern),
+                    name: name.lexeme.to_string(),
+                },
+                parser.span_since(start),
+            ))
+        })
+    }
+
+    /// A pattern, or `*name` inside a sequence
+    fn parse_maybe_star_pattern(&mut self) -> ParseResult<Pattern> {
+        if !self.check(TokenKind::Star) {
+            return self.parse_pattern();
+        }
+        let start = self.advance().span; // consume '*'
+        let name = self.expect(TokenKind::Identifier, "Expected a name after '*'")?;
+        let name = (name.lexeme != "_").then(|| name.lexeme.to_string());
+        Ok(Pattern::new(
+            PatternKind::Star(name),
+            self.span_since(start),
+        ))
+    }
+
+    /// `closed_pattern ('|' closed_pattern)*`
+    fn parse_or_pattern(&mut self) -> ParseResult<Pattern> {
+        let start = self.current_token().span;
+        let first = self.parse_closed_pattern()?;
+        if !self.check(TokenKind::Pipe) {
+            return Ok(first);
+        }
+        let mut patterns = vec![first];
+        while self.check(TokenKind::Pipe) {
+            self.advance(); // consume '|'
+            patterns.push(self.parse_closed_pattern()?);
+        }
+        Ok(Pattern::new(
+            PatternKind::Or { patterns },
+            self.span_since(start),
+        ))
+    }
+
+    fn parse_closed_pattern(&mut self) -> ParseResult<Pattern> {
+        let start = self.current_token().span;
+        let kind = match self.current_token().kind {
+            TokenKind::Identifier => return self.parse_name_pattern(),
+            TokenKind::LeftBracket => {
+                self.advance(); // consume '['
+                let patterns = self.parse_sequence_items(TokenKind::RightBracket)?;
+                self.expect(
+                    TokenKind::RightBracket,
+                    "Expected ']' after sequence pattern",
+                )?;
+                PatternKind::Sequence { patterns }
+            }
+            TokenKind::LeftParen => {
+                self.advance(); // consume '('
+                if self.check(TokenKind::RightParen) {
+                    self.advance();
+                    PatternKind::Sequence {
+                        patterns: Vec::new(),
+                    }
+                } else {
+                    let first = self.parse_maybe_star_pattern()?;
+                    if self.check(TokenKind::Comma) {
+                        self.advance(); // consume ','
+                        let mut patterns = vec![first];
+                        patterns.extend(self.parse_sequence_items(TokenKind::RightParen)?);
+                        self.expect(TokenKind::RightParen, "Expected ')' after sequence pattern")?;
+                        PatternKind::Sequence { patterns }
+                    } else {
+                        // A group: `(1 | 2)`
+                        self.expect(TokenKind::RightParen, "Expected ')' after pattern")?;
+                        if matches!(first.kind, PatternKind::Star(_)) {
+                            return Err(invalid("starred pattern outside a sequence", first.span));
+                        }
+                        return Ok(first);
+                    }
+                }
+            }
+            TokenKind::LeftBrace => self.parse_mapping_pattern()?,
+            _ => PatternKind::Literal(self.parse_literal_pattern()?),
+        };
+        Ok(Pattern::new(kind, self.span_since(start)))
+    }
+
+    /// Comma-separated patterns up to `close`, with an optional trailing comma
+    fn parse_sequence_items(&mut self, close: TokenKind) -> ParseResult<Vec<Pattern>> {
+        let mut patterns = Vec::new();
+        while !self.check(close.clone()) && !self.is_at_end() {
+            patterns.push(self.parse_maybe_star_pattern()?);
+            if !self.check(TokenKind::Comma) {
+                break;
+            }
+            self.advance(); // consume ','
+        }
+        Ok(patterns)
+    }
+
+    /// A capture, `_`, a dotted value (`Color.RED`) or a class pattern
+    fn parse_name_pattern(&mut self) -> ParseResult<Pattern> {
+        let start = self.current_token().span;
+        let name = self.advance().lexeme.to_string();
+        if !self.check(TokenKind::Dot) && !self.check(TokenKind::LeftParen) {
+            let kind = if name == "_" {
+                PatternKind::Wildcard
+            } else {
+                PatternKind::Name(name)
+            };
+            return Ok(Pattern::new(kind, start));
+        }
+
+        let value = self.parse_dotted_name(name, start)?;
+        if !self.check(TokenKind::LeftParen) {
+            let span = value.span;
+            return Ok(Pattern::new(PatternKind::Literal(value), span));
+        }
+
+        self.advance(); // consume '('
+        let mut patterns = Vec::new();
+        let mut kwd_patterns: Vec<(String, Pattern)> = Vec::new();
+        while !self.check(TokenKind::RightParen) && !self.is_at_end() {
+            let keyword = self.check(TokenKind::Identifier)
+                && self
+                    .peek_token(1)
+                    .is_some_and(|token| token.kind == TokenKind::Assign);
+            if keyword {
+                let attribute = self.advance().clone();
+                self.advance(); // consume '='
+                if kwd_patterns
+                    .iter()
+                    .any(|(name, _)| attribute.lexeme == *name)
+                {
+                    return Err(invalid(
+                        &format!(
+                            "attribute name repeated in class pattern: {}",
+                            attribute.lexeme
+                        ),
+                        attribute.span,
+                    ));
+                }
+                kwd_patterns.push((attribute.lexeme.to_string(), self.parse_pattern()?));
+            } else {
+                let pattern = self.parse_pattern()?;
+                if !kwd_patterns.is_empty() {
+                    return Err(invalid(
+                        "positional patterns follow keyword patterns",
+                        pattern.span,
+                    ));
+                }
+                patterns.push(pattern);
+            }
+            if !self.check(TokenKind::Comma) {
+                break;
+            }
+            self.advance(); // consume ','
+        }
+        self.expect(TokenKind::RightParen, "Expected ')' after class pattern")?;
+        Ok(Pattern::new(
+            PatternKind::Class {
+                cls: value,
+                patterns,
+                kwd_patterns,
+            },
+            self.span_since(start),
+        ))
+    }
+
+    /// `name ('.' name)*`, the first name already consumed
+    fn parse_dotted_name(&mut self, name: String, start: Span) -> ParseResult<Expression> {
+        let mut value = self.expression(ExpressionKind::Identifier(name), start);
+        while self.check(TokenKind::Dot) {
+            self.advance(); // consume '.'
+            let attr = self.expect(TokenKind::Identifier, "Expected a name after '.'")?;
+            value = self.expression(
+                ExpressionKind::Attribute {
+                    value: Box::new(value),
+                    attr: attr.lexeme.to_string(),
+                },
+                self.span_since(start),
+            );
+        }
+        Ok(value)
+    }
+
+    /// `{key: pattern, ..., **rest}`
+    fn parse_mapping_pattern(&mut self) -> ParseResult<PatternKind> {
+        self.advance(); // consume '{'
+        let mut keys = Vec::new();
+        let mut patterns = Vec::new();
+        let mut rest = None;
+        while !self.check(TokenKind::RightBrace) && !self.is_at_end() {
+            if self.check(TokenKind::DoubleStar) {
+                self.advance(); // consume '**'
+                let name = self.expect(TokenKind::Identifier, "Expected a name after '**'")?;
+                if name.lexeme == "_" {
+                    return Err(invalid("cannot use '_' as a target", name.span));
+                }
+                rest = Some(name.lexeme.to_string());
+                // The rest comes last, optionally followed by a comma
+                if self.check(TokenKind::Comma) {
+                    self.advance();
+                }
+                break;
+            }
+
+            let key = if self.check(TokenKind::Identifier) {
+                let start = self.current_token().span;
+                let name = self.advance().lexeme.to_string();
+                let key = self.parse_dotted_name(name, start)?;
+                if matches!(key.kind, ExpressionKind::Identifier(_)) {
+                    return Err(invalid(
+                        "mapping pattern keys may only match literals and attribute lookups",
+                        key.span,
+                    ));
+                }
+                key
+            } else {
+                self.parse_literal_pattern()?
+            };
+            self.expect(TokenKind::Colon, "Expected ':' after mapping pattern key")?;
+            keys.push(key);
+            patterns.push(self.parse_pattern()?);
+            if !self.check(TokenKind::Comma) {
+                break;
+            }
+            self.advance(); // consume ','
+        }
+        self.expect(TokenKind::RightBrace, "Expected '}' after mapping pattern")?;
+        Ok(PatternKind::Mapping {
+            keys,
+            patterns,
+            rest,
+        })
+    }
+
+    /// A number (optionally negative), string, bytes, `None`, `True` or
+    /// `False`
+    fn parse_literal_pattern(&mut self) -> ParseResult<Expression> {
+        let start = self.current_token().span;
+        match self.current_token().kind {
+            TokenKind::Minus => {
+                self.advance(); // consume '-'
+                if !matches!(
+                    self.current_token().kind,
+                    TokenKind::Integer(_) | TokenKind::Float(_)
+                ) {
+                    return Err(self.expected_pattern());
+                }
+                let operand = self.parse_primary()?;
+                Ok(self.expression(
+                    ExpressionKind::UnaryOp {
+                        op: UnaryOperator::USub,
+                        operand: Box::new(operand),
+                    },
+                    self.span_since(start),
+                ))
+            }
+            TokenKind::Integer(_)
+            | TokenKind::Float(_)
+            | TokenKind::String(_)
+            | TokenKind::RawString(_)
+            | TokenKind::ByteString(_)
+            | TokenKind::ByteRawString(_)
+            | TokenKind::FString(_)
+            | TokenKind::True
+            | TokenKind::False
+            | TokenKind::None => {
+                let literal = self.parse_primary()?;
+                if matches!(literal.kind, ExpressionKind::FString { .. }) {
+                    return Err(invalid(
+                        "patterns may only match literals and attribute lookups",
+                        literal.span,
+                    ));
+                }
+                Ok(literal)
+            }
+            _ => Err(self.expected_pattern()),
+        }
+    }
+
+    fn expected_pattern(&self) -> ParseError {
+        ParseError::UnexpectedToken {
+            expected: "pattern".to_string(),
+            found: Box::new(self.current_token().clone()),
+            message: "Expected a pattern".to_string(),
+        }
+    }
+}
+
+/// Check the rules on the names a case pattern binds
+fn check_pattern(pattern: &Pattern) -> ParseResult<()> {
+    let mut seen = HashSet::new();
+    for (name, span) in pattern.bound_names() {
+        if !seen.insert(name) {
+            return Err(invalid(
+                &format!("multiple assignments to name '{}' in pattern", name),
+                span,
+            ));
+        }
+    }
+    check_nested_patterns(pattern)
+}
+
+fn check_nested_patterns(pattern: &Pattern) -> ParseResult<()> {
+    match &pattern.kind {
+        PatternKind::Sequence { patterns } => {
+            let mut stars = patterns
+                .iter()
+                .filter(|pattern| matches!(pattern.kind, PatternKind::Star(_)));
+            if let (Some(_), Some(second)) = (stars.next(), stars.next()) {
+                return Err(invalid(
+                    "multiple starred names in sequence pattern",
+                    second.span,
+                ));
+            }
+            patterns
+                .iter()
+                .filter(|pattern| !matches!(pattern.kind, PatternKind::Star(_)))
+                .try_for_each(check_nested_patterns)
+        }
+        PatternKind::Or { patterns } => {
+            let alternatives = &patterns[..patterns.len() - 1];
+            if let Some(irrefutable) = alternatives.iter().find(|p| p.is_irrefutable()) {
+                return Err(unreachable_after(irrefutable));
+            }
+            let names = |pattern: &Pattern| -> HashSet<String> {
+                let names = pattern.bound_names().into_iter();
+                names.map(|(name, _)| name.to_string()).collect()
+            };
+            let first = names(&patterns[0]);
+            if let Some(different) = patterns[1..].iter().find(|p| names(p) != first) {
+                return Err(invalid(
+                    "alternative patterns bind different names",
+                    different.span,
+                ));
+            }
+            patterns.iter().try_for_each(check_nested_patterns)
+        }
+        PatternKind::Mapping { patterns, .. } => {
+            patterns.iter().try_for_each(check_nested_patterns)
+        }
+        PatternKind::Class {
+            patterns,
+            kwd_patterns,
+            ..
+        } => patterns
+            .iter()
+            .chain(kwd_patterns.iter().map(|(_, pattern)| pattern))
+            .try_for_each(check_nested_patterns),
+        PatternKind::As { pattern, .. } => check_nested_patterns(pattern),
+        PatternKind::Star(_) => Err(invalid("starred pattern outside a sequence", pattern.span)),
+        PatternKind::Name(_) | PatternKind::Literal(_) | PatternKind::Wildcard => Ok(()),
+    }
+}
+
+/// The error for an irrefutable pattern followed by other alternatives or
+/// cases, which could never match
+pub(crate) fn unreachable_after(pattern: &Pattern) -> ParseError {
+    let what = match &pattern.kind {
+        PatternKind::Name(name) => format!("name capture '{}'", name),
+        PatternKind::As { pattern, .. } => return unreachable_after(pattern),
+        PatternKind::Or { patterns } => match patterns.iter().find(|p| p.is_irrefutable()) {
+            Some(irrefutable) => return unreachable_after(irrefutable),
+            None => "wildcard".to_string(),
+        },
+        _ => "wildcard".to_string(),
+    };
+    invalid(
+        &format!("{} makes remaining patterns unreachable", what),
+        pattern.span,
+    )
+}
+
+fn invalid(message: &str, span: Span) -> ParseError {
+    ParseError::InvalidSyntax(message.to_string(), span.line, span.column)
+}
@@ -1,13 +1,25 @@
 use crate::{ParseError, ParseResult, Parser};
 /// Statement parsing
 use silk_ast::{FunctionArg, FunctionParams, Keyword, Statement, StatementKind};
-use silk_lexer::TokenKind;
+use silk_lexer::{suggest, TokenKind};
+
+/// Keywords that start a statement, the candidates for a misspelled one
+const STATEMENT_KEYWORDS: &[&str] = &[
+    "assert", "async", "class", "def", "del", "elif", "else", "except", "finally", "for", "from",
+    "global", "if", "import", "match", "nonlocal", "raise", "return", "try", "while", "with",
+    "yield",
+];
 
 impl Parser {
     /// Parse a statement
     pub(crate) fn parse_statement(&mut self) -> ParseResult<Statement> {
-        let start = self.current_token().span.clone();
+        self.nested(Self::parse_statement_kind)
+    }
+
+    fn parse_statement_kind(&mut self) -> ParseResult<Statement> {
+        let start = self.current_token().span;
 
+        let compound = Self::starts_compound_statement(&self.current_token().kind);
         let kind = match self.current_token().kind {
             TokenKind::If => self.parse_if_statement()?,
             TokenKind::While => self.parse_while_statement()?,
@@ -44,10 +56,57 @@ impl Parser {
             }
         };
 
-        let end = self.current_token().span.clone();
-        let span = silk_lexer::Span::new(start.start, end.end, start.line, start.column);
+        // A simple statement runs up to the token after it (its newline); a
+        // compound one ends with the last line of its block
+        let span = if compound {
+            self.span_to_last_line(start)
+        } else {
+            let end = self.current_token().span;
+            silk_lexer::Span::new(start.start, end.end, start.line, start.column)
+        };
+
+        if !compound && self.check(TokenKind::Semicolon) {
+            self.end_simple_statement()?;
+        }
+
+        Ok(self.statement(kind, span))
+    }
+
+    /// Whether a token starts a compound statement, one with a block
+    fn starts_compound_statement(kind: &TokenKind) -> bool {
+        matches!(
+            kind,
+            TokenKind::If
+                | TokenKind::While
+                | TokenKind::For
+                | TokenKind::At
+                | TokenKind::Def
+                | TokenKind::Class
+                | TokenKind::With
+                | TokenKind::Try
+                | TokenKind::Match
+        )
+    }
 
-        Ok(Statement::new(kind, span))
+    /// Consume the `;` after a simple statement (`x = 1; y = 2`)
+    ///
+    /// The next simple statement on the line is parsed as a statement of its
+    /// own. A trailing `;` is allowed, but a compound statement cannot
+    /// follow one, since its block would have to belong to the whole line.
+    fn end_simple_statement(&mut self) -> ParseResult<()> {
+        self.advance(); // consume ';'
+        let next = self.current_token();
+        if Self::starts_compound_statement(&next.kind) {
+            return Err(ParseError::InvalidSyntax(
+                format!(
+                    "'{}' cannot follow ';' on the same line; start it on a new line",
+                    next.lexeme
+                ),
+                next.span.line,
+                next.span.column,
+            ));
+        }
+        Ok(())
     }
 
     fn parse_if_statement(&mut self) -> ParseResult<StatementKind> {
@@ -64,7 +123,7 @@ impl Parser {
         let mut elif_clauses = Vec::new();
         
         while self.check(TokenKind::Elif) {
-            let elif_span_start = self.current_token().span.clone();
+            let elif_span_start = self.current_token().span;
             self.advance(); // consume 'elif'
             let elif_test = self.parse_expression()?;
             self.expect(TokenKind::Colon, "Expected ':' after elif condition")?;
@@ -83,7 +142,7 @@ impl Parser {
 
         // Build elif chain from right to left
         for (elif_span, elif_test, elif_body) in elif_clauses.into_iter().rev() {
-            orelse = vec![Statement::new(
+            orelse = vec![self.statement(
                 StatementKind::If {
                     test: elif_test,
                     body: elif_body,
@@ -125,7 +184,7 @@ impl Parser {
         
         // Check for tuple unpacking: for x, y in ...
         if self.check(TokenKind::Comma) {
-            let start_span = target_expr.span.clone();
+            let start_span = target_expr.span;
             let mut elements = vec![target_expr];
             while self.check(TokenKind::Comma) {
                 self.advance(); // consume comma
@@ -135,14 +194,14 @@ impl Parser {
                 elements.push(self.parse_precedence(crate::expr::Precedence::Comparison.succ())?);
             }
             // Construct tuple expression
-            let end_span = elements.last().unwrap().span.clone();
+            let end_span = elements.last().unwrap().span;
             let span = silk_lexer::Span::new(
                 start_span.start,
                 end_span.end,
                 start_span.line,
                 start_span.column,
             );
-            target_expr = silk_ast::Expression::new(
+            target_expr = self.expression(
                 silk_ast::ExpressionKind::Tuple { elements },
                 span,
             );
@@ -167,7 +226,7 @@ impl Parser {
         };
 
         Ok(StatementKind::For {
-            target,
+            target: Box::new(target),
             iter,
             body,
             orelse,
@@ -187,7 +246,7 @@ impl Parser {
         } else {
             Err(ParseError::UnexpectedToken {
                 expected: "def or class after decorator".to_string(),
-                found: self.current_token().clone(),
+                found: Box::new(self.current_token().clone()),
                 message: "Decorators must be followed by a function or class definition"
                     .to_string(),
             })
@@ -200,26 +259,21 @@ impl Parser {
         while self.check(TokenKind::At) {
             self.advance(); // consume '@'
 
-            // Parse decorator expression - parse_expression handles
-            // dotted names (module.decorator) and calls (@decorator(args))
+            // Any expression may follow '@' (PEP 614): dotted names, calls,
+            // subscripts (`@buttons[0].clicked.connect`), conditional
+            // expressions and unparenthesized `:=`. Whether it is callable is
+            // left to the analyzer.
             let decorator = self.parse_expression()?;
 
             // Expect newline after decorator
-            if !self.check(TokenKind::Newline)
-                && !self.check(TokenKind::At)
-                && !self.check(TokenKind::Def)
-                && !self.check(TokenKind::Class)
-            {
+            if !self.check(TokenKind::Newline) {
                 return Err(ParseError::UnexpectedToken {
                     expected: "newline after decorator".to_string(),
-                    found: self.current_token().clone(),
+                    found: Box::new(self.current_token().clone()),
                     message: "Decorators must be followed by a newline".to_string(),
                 });
             }
-
-            if self.check(TokenKind::Newline) {
-                self.advance(); // consume newline
-            }
+            self.advance(); // consume newline
 
             decorators.push(decorator);
         }
@@ -235,7 +289,7 @@ impl Parser {
 
         let name = self
             .expect(TokenKind::Identifier, "Expected function name")?
-            .lexeme;
+            .lexeme.to_string();
 
         self.expect(TokenKind::LeftParen, "Expected '(' after function name")?;
 
@@ -258,7 +312,7 @@ impl Parser {
 
         Ok(StatementKind::FunctionDef {
             name,
-            params,
+            params: Box::new(params),
             body,
             decorator_list,
             returns,
@@ -274,7 +328,7 @@ impl Parser {
 
         let name = self
             .expect(TokenKind::Identifier, "Expected class name")?
-            .lexeme;
+            .lexeme.to_string();
 
         // Parse base classes
         let mut bases = Vec::new();
@@ -349,9 +403,10 @@ impl Parser {
 
         let mut names = Vec::new();
         loop {
+            let start = self.current_token().span;
             let name = self
                 .expect(TokenKind::Identifier, "Expected module name")?
-                .lexeme;
+                .lexeme.to_string();
 
             // Handle dotted names (e.g., os.path)
             let mut full_name = name;
@@ -359,7 +414,7 @@ impl Parser {
                 self.advance();
                 let part = self
                     .expect(TokenKind::Identifier, "Expected identifier after '.'")?
-                    .lexeme;
+                    .lexeme.to_string();
                 full_name.push('.');
                 full_name.push_str(&part);
             }
@@ -368,7 +423,7 @@ impl Parser {
                 self.advance();
                 Some(
                     self.expect(TokenKind::Identifier, "Expected alias name")?
-                        .lexeme,
+                        .lexeme.to_string(),
                 )
             } else {
                 None
@@ -377,7 +432,7 @@ impl Parser {
             names.push(silk_ast::Alias {
                 name: full_name,
                 asname,
-                span: self.current_token().span.clone(),
+                span: self.span_since(start),
             });
 
             if self.check(TokenKind::Comma) {
@@ -402,7 +457,7 @@ impl Parser {
 
         // Parse module name (if not pure relative import)
         let module = if matches!(self.current_token().kind, TokenKind::Identifier) {
-            let name = self.current_token().lexeme.clone();
+            let name = self.current_token().lexeme.to_string();
             self.advance();
 
             // Handle dotted names
@@ -411,7 +466,7 @@ impl Parser {
                 self.advance();
                 let part = self
                     .expect(TokenKind::Identifier, "Expected identifier")?
-                    .lexeme;
+                    .lexeme.to_string();
                 full_name.push('.');
                 full_name.push_str(&part);
             }
@@ -427,11 +482,12 @@ impl Parser {
 
         // Handle "from x import *"
         if self.check(TokenKind::Star) {
+            let start = self.current_token().span;
             self.advance();
             names.push(silk_ast::Alias {
                 name: "*".to_string(),
                 asname: None,
-                span: self.current_token().span.clone(),
+                span: self.span_since(start),
             });
         } else {
             // Handle parenthesized imports: from x import (a, b, c)
@@ -441,13 +497,14 @@ impl Parser {
             }
 
             loop {
+                let start = self.current_token().span;
                 let name = self
                     .expect(TokenKind::Identifier, "Expected import name")?
-                    .lexeme;
+                    .lexeme.to_string();
 
                 let asname = if self.check(TokenKind::As) {
                     self.advance();
-                    Some(self.expect(TokenKind::Identifier, "Expected alias")?.lexeme)
+                    Some(self.expect(TokenKind::Identifier, "Expected alias")?.lexeme.to_string())
                 } else {
                     None
                 };
@@ -455,7 +512,7 @@ impl Parser {
                 names.push(silk_ast::Alias {
                     name,
                     asname,
-                    span: self.current_token().span.clone(),
+                    span: self.span_since(start),
                 });
 
                 if self.check(TokenKind::Comma) {
@@ -481,16 +538,12 @@ impl Parser {
         self.advance(); // consume 'global'
 
         let mut names = Vec::new();
-        loop {
-            if let TokenKind::Identifier = self.current_token().kind {
-                names.push(self.current_token().lexeme.clone());
-                self.advance();
+        while let TokenKind::Identifier = self.current_token().kind {
+            names.push(self.current_token().lexeme.to_string());
+            self.advance();
 
-                if self.check(TokenKind::Comma) {
-                    self.advance();
-                } else {
-                    break;
-                }
+            if self.check(TokenKind::Comma) {
+                self.advance();
             } else {
                 break;
             }
@@ -503,16 +556,12 @@ impl Parser {
         self.advance(); // consume 'nonlocal'
 
         let mut names = Vec::new();
-        loop {
-            if let TokenKind::Identifier = self.current_token().kind {
-                names.push(self.current_token().lexeme.clone());
-                self.advance();
+        while let TokenKind::Identifier = self.current_token().kind {
+            names.push(self.current_token().lexeme.to_string());
+            self.advance();
 
-                if self.check(TokenKind::Comma) {
-                    self.advance();
-                } else {
-                    break;
-                }
+            if self.check(TokenKind::Comma) {
+                self.advance();
             } else {
                 break;
             }
@@ -563,7 +612,9 @@ impl Parser {
 
         let mut targets = Vec::new();
         loop {
-            targets.push(self.parse_expression()?);
+            let target = self.parse_expression()?;
+            Self::check_delete_target(&target)?;
+            targets.push(target);
 
             if self.check(TokenKind::Comma) {
                 self.advance();
@@ -575,6 +626,39 @@ impl Parser {
         Ok(StatementKind::Delete { targets })
     }
 
+    /// Reject a `del` target that is not a name, attribute, subscript, or a
+    /// tuple or list of them
+    fn check_delete_target(target: &silk_ast::Expression) -> ParseResult<()> {
+        use silk_ast::ExpressionKind;
+
+        let what = match &target.kind {
+            ExpressionKind::Identifier(_)
+            | ExpressionKind::Attribute { .. }
+            | ExpressionKind::Subscript { .. } => return Ok(()),
+            ExpressionKind::Tuple { elements } | ExpressionKind::List { elements } => {
+                return elements.iter().try_for_each(Self::check_delete_target);
+            }
+            ExpressionKind::Integer(_)
+            | ExpressionKind::Float(_)
+            | ExpressionKind::String(_)
+            | ExpressionKind::RawString(_)
+            | ExpressionKind::FString { .. }
+            | ExpressionKind::ByteString(_)
+            | ExpressionKind::ByteRawString(_)
+            | ExpressionKind::Boolean(_)
+            | ExpressionKind::None
+            | ExpressionKind::Ellipsis
+            | ExpressionKind::NotImplemented => "literal",
+            ExpressionKind::Call { .. } => "function call",
+            _ => "expression",
+        };
+        Err(ParseError::InvalidSyntax(
+            format!("cannot delete {}", what),
+            target.span.line,
+            target.span.column,
+        ))
+    }
+
     fn parse_with_statement(&mut self) -> ParseResult<StatementKind> {
         self.advance(); // consume 'with'
 
@@ -593,7 +677,7 @@ impl Parser {
             items.push(silk_ast::WithItem {
                 context_expr,
                 optional_vars,
-                span: self.current_token().span.clone(),
+                span: self.current_token().span,
             });
 
             if self.check(TokenKind::Comma) {
@@ -621,16 +705,35 @@ impl Parser {
         let body = self.parse_block()?;
 
         let mut handlers = Vec::new();
+        // Whether the handlers are `except*` ones, decided by the first
+        let mut is_star = None;
 
         // Parse except clauses
         while self.check(TokenKind::Except) {
-            self.advance();
+            let except = self.advance().span;
+            let star = self.check(TokenKind::Star);
+            if star {
+                self.advance(); // consume '*'
+            }
+            if *is_star.get_or_insert(star) != star {
+                return Err(ParseError::InvalidSyntax(
+                    "cannot have both 'except' and 'except*' on the same 'try'".to_string(),
+                    except.line,
+                    except.column,
+                ));
+            }
 
-            let handler_start = self.current_token().span.clone();
+            let handler_start = self.current_token().span;
 
-            // Parse exception type
+            // Parse exception type; `except*` needs one
             let typ = if !self.check(TokenKind::Colon) {
                 Some(self.parse_expression()?)
+            } else if star {
+                return Err(ParseError::InvalidSyntax(
+                    "expected one or more exception types after 'except*'".to_string(),
+                    handler_start.line,
+                    handler_start.column,
+                ));
             } else {
                 None
             };
@@ -640,7 +743,7 @@ impl Parser {
                 self.advance();
                 Some(
                     self.expect(TokenKind::Identifier, "Expected exception name")?
-                        .lexeme,
+                        .lexeme.to_string(),
                 )
             } else {
                 None
@@ -680,6 +783,7 @@ impl Parser {
             handlers,
             orelse,
             finalbody,
+            is_star: is_star.unwrap_or(false),
         })
     }
 
@@ -698,11 +802,9 @@ impl Parser {
         while self.check(TokenKind::Case) {
             self.advance();
 
-            let case_start = self.current_token().span.clone();
+            let case_start = self.current_token().span;
 
-            // Parse pattern
-            let pattern_expr = self.parse_expression()?;
-            let pattern = self.expr_to_pattern(pattern_expr)?;
+            let pattern = self.parse_case_pattern()?;
 
             // Parse guard
             let guard = if self.check(TokenKind::If) {
@@ -739,10 +841,21 @@ impl Parser {
 
         self.expect(TokenKind::Dedent, "Expected dedentation after match cases")?;
 
+        // A case that matches everything must be the last one
+        if let Some((_, earlier)) = cases.split_last() {
+            if let Some(case) = earlier
+                .iter()
+                .find(|case| case.guard.is_none() && case.pattern.is_irrefutable())
+            {
+                return Err(crate::pattern::unreachable_after(&case.pattern));
+            }
+        }
+
         Ok(StatementKind::Match { subject, cases })
     }
 
     fn parse_expr_or_assign_statement(&mut self) -> ParseResult<StatementKind> {
+        self.check_misspelled_keyword()?;
         let expr = self.parse_expression()?;
 
         // Check for annotated assignment (x: int = 10 or x: int)
@@ -795,6 +908,53 @@ impl Parser {
         Ok(StatementKind::Expr(expr))
     }
 
+    /// Reject a statement that starts with a misspelled keyword
+    ///
+    /// A name followed by more of the statement on the same line (`retrun
+    /// x`, `improt os`), or by a lone `:` that opens a block (`esle:`), is
+    /// not valid code; if the name is close to a keyword that starts a
+    /// statement, that keyword was almost certainly meant.
+    fn check_misspelled_keyword(&self) -> ParseResult<()> {
+        let name = self.current_token();
+        let (Some(next), Some(after)) = (self.peek_token(1), self.peek_token(2)) else {
+            return Ok(());
+        };
+        if name.kind != TokenKind::Identifier {
+            return Ok(());
+        }
+        let juxtaposed = next.span.line == name.span.line
+            && matches!(
+                next.kind,
+                TokenKind::Identifier
+                    | TokenKind::Integer(_)
+                    | TokenKind::Float(_)
+                    | TokenKind::String(_)
+                    | TokenKind::RawString(_)
+                    | TokenKind::FString(_)
+                    | TokenKind::True
+                    | TokenKind::False
+                    | TokenKind::None
+                    | TokenKind::Not
+                    | TokenKind::Lambda
+            );
+        let opens_block = next.kind == TokenKind::Colon
+            && matches!(after.kind, TokenKind::Newline | TokenKind::Eof);
+        if !juxtaposed && !opens_block {
+            return Ok(());
+        }
+
+        match suggest::closest_match(&name.lexeme, STATEMENT_KEYWORDS.iter().copied()) {
+            Some(keyword) => Err(ParseError::MisspelledKeyword {
+                found: name.lexeme.to_string(),
+                keyword: keyword.to_string(),
+                line: name.span.line,
+                column: name.span.column,
+                span: name.span,
+            }),
+            None => Ok(()),
+        }
+    }
+
     fn check_aug_assign(&self) -> Option<silk_ast::AugAssignOperator> {
         use silk_ast::AugAssignOperator::*;
 
@@ -806,6 +966,7 @@ impl Parser {
             TokenKind::DoubleSlashAssign => FloorDiv,
             TokenKind::PercentAssign => Mod,
             TokenKind::DoubleStarAssign => Pow,
+            TokenKind::AtAssign => MatMult,
             TokenKind::AmpersandAssign => BitAnd,
             TokenKind::PipeAssign => BitOr,
             TokenKind::CaretAssign => BitXor,
@@ -869,22 +1030,29 @@ impl Parser {
     }
 
     /// Parse function parameters
+    ///
+    /// Supports positional-only parameters (before `/`), keyword-only
+    /// parameters (after a bare `*` or `*args`), `*args` and `**kwargs`.
     fn parse_function_params(&mut self) -> ParseResult<FunctionParams> {
+        let mut posonlyargs = Vec::new();
         let mut args = Vec::new();
         let mut vararg = None;
-        let kwonlyargs = Vec::new(); // TODO: Implement keyword-only args
+        let mut kwonlyargs = Vec::new();
         let mut kwarg = None;
+        // Set after a bare `*` or `*args`: later parameters are keyword-only
+        let mut keyword_only = false;
+        let mut bare_star = None;
 
         // Parse parameters
         while !self.check(TokenKind::RightParen) && !self.is_at_end() {
-            let param_start = self.current_token().span.clone();
+            let param_start = self.current_token().span;
 
             // Check for **kwargs
             if self.check(TokenKind::DoubleStar) {
                 self.advance(); // consume '**'
                 let name = self
                     .expect(TokenKind::Identifier, "Expected parameter name after '**'")?
-                    .lexeme;
+                    .lexeme.to_string();
 
                 // Parse optional type annotation
                 let annotation = if self.check(TokenKind::Colon) {
@@ -914,13 +1082,49 @@ impl Parser {
                 }
                 break;
             }
+            // Check for the positional-only separator
+            else if self.check(TokenKind::Slash) {
+                let message = if keyword_only {
+                    Some("'/' must come before '*'")
+                } else if !posonlyargs.is_empty() {
+                    Some("'/' may appear only once")
+                } else if args.is_empty() {
+                    Some("at least one parameter must precede '/'")
+                } else {
+                    None
+                };
+                if let Some(message) = message {
+                    return Err(ParseError::InvalidSyntax(
+                        message.to_string(),
+                        param_start.line,
+                        param_start.column,
+                    ));
+                }
+                self.advance(); // consume '/'
+
+                posonlyargs = std::mem::take(&mut args);
+
+                if self.check(TokenKind::Comma) {
+                    self.advance();
+                } else {
+                    break;
+                }
+            }
             // Check for *args
             else if self.check(TokenKind::Star) {
+                if keyword_only {
+                    return Err(ParseError::InvalidSyntax(
+                        "'*' or '*args' may appear only once".to_string(),
+                        param_start.line,
+                        param_start.column,
+                    ));
+                }
                 self.advance(); // consume '*'
+                keyword_only = true;
 
                 // Check if this is just a separator (bare *)
                 if self.check(TokenKind::Comma) || self.check(TokenKind::RightParen) {
-                    // Bare * means keyword-only args follow (not implemented yet)
+                    bare_star = Some(param_start);
                     if self.check(TokenKind::Comma) {
                         self.advance();
                     }
@@ -929,7 +1133,7 @@ impl Parser {
 
                 let name = self
                     .expect(TokenKind::Identifier, "Expected parameter name after '*'")?
-                    .lexeme;
+                    .lexeme.to_string();
 
                 // Parse optional type annotation
                 let annotation = if self.check(TokenKind::Colon) {
@@ -954,7 +1158,7 @@ impl Parser {
             else {
                 let name = self
                     .expect(TokenKind::Identifier, "Expected parameter name")?
-                    .lexeme;
+                    .lexeme.to_string();
 
                 // Parse type annotation
                 let annotation = if self.check(TokenKind::Colon) {
@@ -972,12 +1176,17 @@ impl Parser {
                     None
                 };
 
-                args.push(FunctionArg {
+                let arg = FunctionArg {
                     name,
                     annotation,
                     default,
                     span: param_start,
-                });
+                };
+                if keyword_only {
+                    kwonlyargs.push(arg);
+                } else {
+                    args.push(arg);
+                }
 
                 if self.check(TokenKind::Comma) {
                     self.advance();
@@ -987,7 +1196,19 @@ impl Parser {
             }
         }
 
+        // A bare `*` must be followed by at least one keyword-only parameter
+        if let Some(star) = bare_star {
+            if kwonlyargs.is_empty() {
+                return Err(ParseError::InvalidSyntax(
+                    "named parameters must follow bare '*'".to_string(),
+                    star.line,
+                    star.column,
+                ));
+            }
+        }
+
         Ok(FunctionParams {
+            posonlyargs,
             args,
             vararg,
             kwonlyargs,
@@ -995,11 +1216,33 @@ impl Parser {
         })
     }
 
-    /// Parse a type annotation (simplified for now)
+    /// Parse a type annotation, including unions such as `int | None`
     fn parse_type(&mut self) -> ParseResult<silk_ast::Type> {
+        self.nested(Self::parse_type_union)
+    }
+
+    fn parse_type_union(&mut self) -> ParseResult<silk_ast::Type> {
+        use silk_ast::{Type, TypeKind};
+
+        let start = self.current_token().span;
+        let first = self.parse_type_primary()?;
+        if !self.check(TokenKind::Pipe) {
+            return Ok(first);
+        }
+
+        let mut types = vec![first];
+        while self.check(TokenKind::Pipe) {
+            self.advance();
+            types.push(self.parse_type_primary()?);
+        }
+        Ok(Type::new(TypeKind::Union { types }, self.span_since(start)))
+    }
+
+    /// Parse a single (non-union) type annotation
+    fn parse_type_primary(&mut self) -> ParseResult<silk_ast::Type> {
         use silk_ast::{Type, TypeKind};
 
-        let start = self.current_token().span.clone();
+        let start = self.current_token().span;
 
         // Handle None keyword as a type
         if self.check(TokenKind::None) {
@@ -1009,7 +1252,7 @@ impl Parser {
 
         // For now, just parse simple type names
         if let TokenKind::Identifier = self.current_token().kind {
-            let name = self.current_token().lexeme.clone();
+            let name = self.current_token().lexeme.to_string();
             self.advance();
 
             // Handle generic types like List[int]
@@ -1024,7 +1267,7 @@ impl Parser {
 
                 self.expect(TokenKind::RightBracket, "Expected ']'")?;
 
-                let base = Box::new(Type::new(TypeKind::Name(name), start.clone()));
+                let base = Box::new(Type::new(TypeKind::Name(name), start));
                 return Ok(Type::new(TypeKind::Generic { base, args }, start));
             }
 
@@ -1,3 +1,5 @@
+#![allow(clippy::approx_constant)]
+
 use silk_ast::{ExpressionKind, StatementKind, TypeKind};
 /// Tests for annotated assignment (AnnAssign) parsing
 use silk_parser::Parser;
@@ -170,7 +172,7 @@ fn test_ann_assign_bool_type() {
             // Check value is True
             assert!(value.is_some());
             match &value.as_ref().unwrap().kind {
-                ExpressionKind::Boolean(val) => assert_eq!(*val, true),
+                ExpressionKind::Boolean(val) => assert!(*val),
                 _ => panic!("Expected Boolean"),
             }
         }
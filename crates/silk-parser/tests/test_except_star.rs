@@ -0,0 +1,90 @@
+//! Tests for `except*` handlers, which catch parts of an exception group
+
+use silk_ast::{unparse, ExpressionKind, StatementKind};
+use silk_parser::{ParseError, Parser};
+
+/// The message, line and column of the syntax error in `source`
+fn syntax_error(source: &str) -> (String, usize, usize) {
+    match Parser::parse(source) {
+        Err(ParseError::InvalidSyntax(message, line, column)) => (message, line, column),
+        other => panic!("expected an invalid syntax error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_except_star_handlers() {
+    let source = "\
+try:
+    run()
+except* (ValueError, TypeError) as group:
+    pass
+except *OSError:
+    pass
+";
+    let program = Parser::parse(source).unwrap();
+    match &program.statements[0].kind {
+        StatementKind::Try {
+            handlers, is_star, ..
+        } => {
+            assert!(*is_star);
+            assert_eq!(handlers.len(), 2);
+            assert_eq!(handlers[0].name.as_deref(), Some("group"));
+            assert!(matches!(
+                handlers[0].typ.as_ref().unwrap().kind,
+                ExpressionKind::Tuple { .. }
+            ));
+        }
+        other => panic!("expected a try statement, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_plain_except_is_not_star() {
+    let program = Parser::parse("try:\n    pass\nexcept ValueError:\n    pass\n").unwrap();
+    assert!(matches!(
+        program.statements[0].kind,
+        StatementKind::Try { is_star: false, .. }
+    ));
+}
+
+#[test]
+fn test_cannot_mix_except_and_except_star() {
+    assert_eq!(
+        syntax_error(
+            "try:\n    pass\nexcept* ValueError:\n    pass\nexcept TypeError:\n    pass\n"
+        ),
+        (
+            "cannot have both 'except' and 'except*' on the same 'try'".to_string(),
+            5,
+            1
+        )
+    );
+    syntax_error("try:\n    pass\nexcept ValueError:\n    pass\nexcept* TypeError:\n    pass\n");
+}
+
+#[test]
+fn test_except_star_needs_a_type() {
+    assert_eq!(
+        syntax_error("try:\n    pass\nexcept*:\n    pass\n"),
+        (
+            "expected one or more exception types after 'except*'".to_string(),
+            3,
+            8
+        )
+    );
+}
+
+#[test]
+fn test_except_star_is_printed() {
+    let source = "\
+try:
+    run()
+except* ValueError as group:
+    pass
+else:
+    pass
+finally:
+    pass
+";
+    assert_eq!(unparse(&Parser::parse(source).unwrap()), source);
+}
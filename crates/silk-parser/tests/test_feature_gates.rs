@@ -0,0 +1,142 @@
+/// Tests for unstable feature gates in the parser
+use silk_ast::{Feature, FeatureError, FeatureSet, ReleaseChannel};
+use silk_parser::{ParseError, Parser};
+
+fn comprehension_assignments() -> FeatureSet {
+    let mut features = FeatureSet::new();
+    features.enable(Feature::ComprehensionAssignments);
+    features
+}
+
+#[test]
+fn test_gated_construct_rejected_by_default() {
+    let source = "doubled = [x * 2 for x in (items := data)]";
+    let result = Parser::parse(source);
+
+    match result {
+        Err(ParseError::FeatureNotEnabled {
+            feature,
+            line,
+            column,
+            ..
+        }) => {
+            assert_eq!(feature, Feature::ComprehensionAssignments);
+            assert_eq!(line, 1);
+            assert_eq!(column, 28);
+        }
+        other => panic!("Expected FeatureNotEnabled, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_gate_diagnostic_names_feature() {
+    let source = "doubled = [x * 2 for x in (items := data)]";
+    let message = Parser::parse(source).unwrap_err().to_string();
+
+    assert!(message.contains("requires the unstable feature 'comprehension-assignments'"));
+    assert!(message.contains("--unstable-features comprehension-assignments"));
+}
+
+#[test]
+fn test_gated_construct_accepted_when_enabled() {
+    let source = "doubled = [x * 2 for x in (items := data)]";
+    let result = Parser::parse_with_features(source, comprehension_assignments());
+    assert!(result.is_ok(), "Expected parse to succeed: {:?}", result);
+}
+
+#[test]
+fn test_walrus_in_comprehension_filter_is_stable() {
+    let source = "result = [y for x in items if (y := x * 2) > 5]";
+    assert!(Parser::parse(source).is_ok());
+}
+
+#[test]
+fn test_feature_flag_parsing() {
+    let features =
+        FeatureSet::from_flag("comprehension-assignments", ReleaseChannel::Nightly).unwrap();
+    assert!(features.is_enabled(Feature::ComprehensionAssignments));
+
+    let features =
+        FeatureSet::from_flag(" comprehension-assignments, ", ReleaseChannel::Nightly).unwrap();
+    assert_eq!(features.iter().count(), 1);
+}
+
+#[test]
+fn test_unknown_feature_rejected() {
+    let result = FeatureSet::from_flag(
+        "comprehension-assignments,teleportation",
+        ReleaseChannel::Nightly,
+    );
+    assert_eq!(
+        result,
+        Err(FeatureError::UnknownFeature("teleportation".to_string()))
+    );
+}
+
+#[test]
+fn test_stable_channel_refuses_unstable_features() {
+    let result = FeatureSet::from_flag("comprehension-assignments", ReleaseChannel::Stable);
+    assert_eq!(
+        result,
+        Err(FeatureError::StableChannel(
+            Feature::ComprehensionAssignments
+        ))
+    );
+
+    // An empty list is fine on stable
+    assert!(FeatureSet::from_flag("", ReleaseChannel::Stable)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn test_pragma_enables_feature_for_the_file() {
+    let source = "# A module\n# feature: comprehension-assignments\n\ndoubled = [x * 2 for x in (items := data)]\n";
+    let program = Parser::parse(source).expect("pragma should enable the feature");
+    assert!(program
+        .features
+        .is_enabled(Feature::ComprehensionAssignments));
+
+    // Features from the command line are not recorded on the program
+    let program = Parser::parse_with_features("x = 1\n", comprehension_assignments()).unwrap();
+    assert!(program.features.is_empty());
+}
+
+#[test]
+fn test_invalid_pragmas_are_rejected() {
+    let error = Parser::parse("#feature: teleportation\nx = 1\n").unwrap_err();
+    assert_eq!(error.code(), "invalid-feature-pragma");
+    assert!(error
+        .to_string()
+        .contains("Unknown unstable feature 'teleportation'"));
+
+    let error = Parser::parse("x = 1\n# feature: comprehension-assignments\n").unwrap_err();
+    assert_eq!(error.code(), "invalid-feature-pragma");
+    assert_eq!(error.span().unwrap().line, 2);
+    assert!(error.to_string().contains("before the first statement"));
+
+    // Other comments are not pragmas
+    assert!(Parser::parse("x = 1  # features: none\n# feature flags below\n").is_ok());
+}
+
+#[test]
+fn test_pragma_parsing() {
+    let pragma = FeatureSet::from_pragma(
+        "#  feature: comprehension-assignments",
+        ReleaseChannel::Nightly,
+    );
+    assert_eq!(pragma, Some(Ok(comprehension_assignments())));
+    assert_eq!(
+        FeatureSet::from_pragma(
+            "# feature: comprehension-assignments",
+            ReleaseChannel::Stable
+        ),
+        Some(Err(FeatureError::StableChannel(
+            Feature::ComprehensionAssignments
+        )))
+    );
+    assert_eq!(
+        FeatureSet::from_pragma("# featured", ReleaseChannel::Nightly),
+        None
+    );
+}
@@ -0,0 +1,218 @@
+//! Tests for the structural patterns of `case` clauses
+
+use silk_ast::{unparse, ExpressionKind, Pattern, PatternKind, StatementKind};
+use silk_parser::{ParseError, Parser};
+
+/// The pattern of `case <pattern>:` in a match statement
+fn pattern(source: &str) -> Pattern {
+    let program = Parser::parse(&format!("match x:\n    case {}:\n        pass\n", source))
+        .expect("pattern should parse");
+    match &program.statements[0].kind {
+        StatementKind::Match { cases, .. } => cases[0].pattern.clone(),
+        other => panic!("expected a match statement, got {:?}", other),
+    }
+}
+
+/// The syntax error message for `case <pattern>:`
+fn pattern_error(source: &str) -> String {
+    match Parser::parse(&format!("match x:\n    case {}:\n        pass\n", source)) {
+        Err(ParseError::InvalidSyntax(message, ..)) => message,
+        other => panic!("expected an invalid syntax error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_literal_patterns() {
+    for source in [
+        "1",
+        "-1",
+        "2.5",
+        "'text'",
+        "'con' 'cat'",
+        "b'raw'",
+        "None",
+        "True",
+    ] {
+        assert!(
+            matches!(pattern(source).kind, PatternKind::Literal(_)),
+            "{}",
+            source
+        );
+    }
+    match pattern("-1").kind {
+        PatternKind::Literal(value) => {
+            assert!(matches!(value.kind, ExpressionKind::UnaryOp { .. }))
+        }
+        other => panic!("expected a literal, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_capture_wildcard_and_value_patterns() {
+    assert_eq!(
+        pattern("point").kind,
+        PatternKind::Name("point".to_string())
+    );
+    assert_eq!(pattern("_").kind, PatternKind::Wildcard);
+    match pattern("Color.RED").kind {
+        PatternKind::Literal(value) => {
+            assert!(
+                matches!(value.kind, ExpressionKind::Attribute { ref attr, .. } if attr == "RED")
+            )
+        }
+        other => panic!("expected a value pattern, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_sequence_patterns() {
+    for source in [
+        "[first, *rest]",
+        "(first, *_)",
+        "first, second",
+        "[]",
+        "()",
+        "[x,]",
+    ] {
+        assert!(
+            matches!(pattern(source).kind, PatternKind::Sequence { .. }),
+            "{}",
+            source
+        );
+    }
+    match pattern("[first, *rest]").kind {
+        PatternKind::Sequence { patterns } => {
+            assert_eq!(
+                patterns[1].kind,
+                PatternKind::Star(Some("rest".to_string()))
+            )
+        }
+        other => panic!("expected a sequence, got {:?}", other),
+    }
+    // Parentheses alone group a pattern
+    assert!(matches!(pattern("(1 | 2)").kind, PatternKind::Or { .. }));
+}
+
+#[test]
+fn test_mapping_patterns() {
+    match pattern("{'id': id, Keys.NAME: name, **rest}").kind {
+        PatternKind::Mapping {
+            keys,
+            patterns,
+            rest,
+        } => {
+            assert_eq!(keys.len(), 2);
+            assert_eq!(patterns[0].kind, PatternKind::Name("id".to_string()));
+            assert_eq!(rest.as_deref(), Some("rest"));
+        }
+        other => panic!("expected a mapping, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_class_patterns() {
+    match pattern("shapes.Point(0, y=y)").kind {
+        PatternKind::Class {
+            cls,
+            patterns,
+            kwd_patterns,
+        } => {
+            assert!(matches!(cls.kind, ExpressionKind::Attribute { .. }));
+            assert_eq!(patterns.len(), 1);
+            assert_eq!(kwd_patterns[0].0, "y");
+        }
+        other => panic!("expected a class pattern, got {:?}", other),
+    }
+    assert!(matches!(pattern("Empty()").kind, PatternKind::Class { .. }));
+}
+
+#[test]
+fn test_or_and_as_patterns() {
+    match pattern("[x] | (x, 0) as pair").kind {
+        PatternKind::As { pattern, name } => {
+            assert_eq!(name, "pair");
+            assert!(
+                matches!(pattern.kind, PatternKind::Or { ref patterns } if patterns.len() == 2)
+            );
+        }
+        other => panic!("expected an as pattern, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_binding_rules() {
+    assert_eq!(
+        pattern_error("[x, x]"),
+        "multiple assignments to name 'x' in pattern"
+    );
+    assert_eq!(
+        pattern_error("[x] | [y]"),
+        "alternative patterns bind different names"
+    );
+    assert_eq!(
+        pattern_error("[*a, *b]"),
+        "multiple starred names in sequence pattern"
+    );
+    assert_eq!(
+        pattern_error("x | 1"),
+        "name capture 'x' makes remaining patterns unreachable"
+    );
+    assert_eq!(
+        pattern_error("Point(x=1, x=2)"),
+        "attribute name repeated in class pattern: x"
+    );
+    assert_eq!(
+        pattern_error("Point(x=1, 2)"),
+        "positional patterns follow keyword patterns"
+    );
+    assert_eq!(pattern_error("1 as _"), "cannot use '_' as a target");
+    assert_eq!(pattern_error("*rest"), "starred pattern outside a sequence");
+    assert_eq!(
+        pattern_error("{key: 1}"),
+        "mapping pattern keys may only match literals and attribute lookups"
+    );
+    assert_eq!(
+        pattern_error("f'{x}'"),
+        "patterns may only match literals and attribute lookups"
+    );
+}
+
+#[test]
+fn test_irrefutable_case_must_be_last() {
+    let source = "match x:\n    case _:\n        pass\n    case 1:\n        pass\n";
+    match Parser::parse(source) {
+        Err(ParseError::InvalidSyntax(message, 2, 10)) => {
+            assert_eq!(message, "wildcard makes remaining patterns unreachable")
+        }
+        other => panic!("expected an invalid syntax error, got {:?}", other),
+    }
+    // A guard makes a capture refutable
+    let source = "match x:\n    case y if y:\n        pass\n    case _:\n        pass\n";
+    assert!(Parser::parse(source).is_ok());
+}
+
+#[test]
+fn test_patterns_are_printed() {
+    let source = "\
+match command:
+    case Point(0, y=[first, *rest]) | Point(1, y=[first, *rest]):
+        pass
+    case {\"id\": 1 | 2 as id, **extra}:
+        pass
+    case Color.RED | -1 | \"text\" | None:
+        pass
+    case (x, *_) if x:
+        pass
+    case _:
+        pass
+";
+    // Sequence patterns are printed in parentheses, whichever brackets they had
+    assert_eq!(
+        unparse(&Parser::parse(source).unwrap()),
+        source.replace("[first, *rest]", "(first, *rest)")
+    );
+    assert_eq!(
+        unparse(&Parser::parse("match x:\n    case [y]:\n        pass\n").unwrap()),
+        "match x:\n    case (y,):\n        pass\n"
+    );
+}
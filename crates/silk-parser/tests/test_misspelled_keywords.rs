@@ -0,0 +1,55 @@
+//! Tests for parse errors that suggest a misspelled keyword
+
+use silk_parser::{ParseError, Parser};
+
+/// The keyword suggested for `source`, if parsing fails with one
+fn suggested_keyword(source: &str) -> Option<String> {
+    match Parser::parse(source) {
+        Err(ParseError::MisspelledKeyword { keyword, .. }) => Some(keyword),
+        _ => None,
+    }
+}
+
+#[test]
+fn test_misspelled_statement_keywords() {
+    assert_eq!(
+        suggested_keyword("def f():\n    retrun 1\n").as_deref(),
+        Some("return")
+    );
+    assert_eq!(
+        suggested_keyword("x = 1\nwhlie x:\n    x = 0\n").as_deref(),
+        Some("while")
+    );
+    assert_eq!(
+        suggested_keyword("improt math\n").as_deref(),
+        Some("import")
+    );
+    assert_eq!(
+        suggested_keyword("clas Point:\n    pass\n").as_deref(),
+        Some("class")
+    );
+    assert_eq!(
+        suggested_keyword("if x:\n    pass\nesle:\n    pass\n").as_deref(),
+        Some("else")
+    );
+}
+
+#[test]
+fn test_error_points_at_the_name() {
+    let err = Parser::parse("x = 1\nwhlie x:\n    x = 0\n").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Unexpected name 'whlie' at line 2, column 1: did you mean the keyword 'while'?"
+    );
+    let span = err.span().expect("misspelled keywords have a span");
+    assert_eq!((span.line, span.column), (2, 1));
+}
+
+#[test]
+fn test_names_that_are_not_misspellings() {
+    // Ordinary statements starting with a name similar to a keyword
+    assert!(Parser::parse("ret = 1\nwith_x = ret\nretrun = 2\n").is_ok());
+    assert!(Parser::parse("def f(iff):\n    return iff\n").is_ok());
+    // A name followed by another name, but not close to any keyword
+    assert!(suggested_keyword("banana split\n").is_none());
+}
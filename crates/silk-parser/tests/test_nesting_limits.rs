@@ -0,0 +1,156 @@
+//! Tests for the nesting depth limit on pathological inputs
+
+use silk_parser::{ParseError, ParseResult, Parser, MAX_NESTING_DEPTH};
+
+/// Parse on a thread with the stack of a main thread, which is what the
+/// limit is sized for; the test harness runs tests on smaller stacks
+fn parse(source: String) -> ParseResult<()> {
+    std::thread::Builder::new()
+        .stack_size(8 << 20)
+        .spawn(move || Parser::parse(&source).map(drop))
+        .unwrap()
+        .join()
+        .expect("parser panicked")
+}
+
+fn assert_too_deep(source: String) {
+    match parse(source) {
+        Err(ParseError::NestingTooDeep { limit, .. }) => assert_eq!(limit, MAX_NESTING_DEPTH),
+        other => panic!("expected NestingTooDeep, got {:?}", other),
+    }
+}
+
+/// `if x:` nested `depth` times
+fn nested_blocks(depth: usize) -> String {
+    let mut source = String::new();
+    for level in 0..depth {
+        source.push_str(&format!("{}if x:\n", " ".repeat(level)));
+    }
+    source.push_str(&format!("{}pass\n", " ".repeat(depth)));
+    source
+}
+
+#[test]
+fn test_deep_expressions_are_rejected() {
+    let n = 10_000;
+    assert_too_deep(format!("x = {}1{}\n", "(".repeat(n), ")".repeat(n)));
+    assert_too_deep(format!("x = {}1{}\n", "[".repeat(n), "]".repeat(n)));
+    assert_too_deep(format!("x = {}1\n", "-".repeat(n)));
+    assert_too_deep(format!("x = {}True\n", "not ".repeat(n)));
+    assert_too_deep(format!("x = {}1\n", "lambda: ".repeat(n)));
+    assert_too_deep(format!("x = {}1{}\n", "f(".repeat(n), ")".repeat(n)));
+    assert_too_deep(format!("x = 2{}\n", " ** 2".repeat(n)));
+    assert_too_deep(format!("x = 1{}\n", " if a else 1".repeat(n)));
+}
+
+#[test]
+fn test_long_chains_are_accepted() {
+    // Left-associative chains and `elif` ladders are parsed in a loop, so
+    // they do not count as nesting however long they are
+    let n = 10_000;
+    assert!(parse(format!("x = 1{}\n", " + 1".repeat(n))).is_ok());
+    assert!(parse(format!("x = a{}\n", " and a".repeat(n))).is_ok());
+    assert!(parse(format!("x = a{}\n", " < a".repeat(n))).is_ok());
+    assert!(parse(format!("x = a{}\n", ".b".repeat(n))).is_ok());
+    assert!(parse(format!("x = a{}\n", "[0]".repeat(n))).is_ok());
+    assert!(parse(format!("x = f{}\n", "()".repeat(n))).is_ok());
+
+    let mut source = "if x == 0:\n    pass\n".to_string();
+    for i in 1..n {
+        source.push_str(&format!("elif x == {}:\n    pass\n", i));
+    }
+    assert!(parse(source).is_ok());
+}
+
+#[test]
+fn test_chains_do_not_add_to_nesting() {
+    // Each parenthesis is a level; the chain inside it is not
+    let n = MAX_NESTING_DEPTH - 10;
+    let source = format!("x = {}a{}\n", "(".repeat(n), ".b[0] + 1 + 2)".repeat(n));
+    assert!(parse(source).is_ok());
+}
+
+#[test]
+fn test_deep_blocks_and_types_are_rejected() {
+    assert_too_deep(nested_blocks(500));
+    let n = 10_000;
+    assert_too_deep(format!(
+        "x: {}int{} = 1\n",
+        "list[".repeat(n),
+        "]".repeat(n)
+    ));
+}
+
+#[test]
+fn test_nesting_below_the_limit_is_accepted() {
+    let n = MAX_NESTING_DEPTH - 10;
+    assert!(parse(format!("x = {}1{}\n", "(".repeat(n), ")".repeat(n))).is_ok());
+    assert!(parse(format!("x = 1{}\n", " + 1".repeat(n))).is_ok());
+    assert!(parse(nested_blocks(n)).is_ok());
+    // Many statements and long flat collections do not nest
+    assert!(parse("x = 1\n".repeat(10_000)).is_ok());
+    assert!(parse(format!("x = [{}]\n", "1, ".repeat(10_000))).is_ok());
+}
+
+#[test]
+fn test_error_location() {
+    let err = parse(format!("x = {}1\n", "(".repeat(1_000))).unwrap_err();
+    assert_eq!(err.error_code(), "E0110");
+    let span = err.span().unwrap();
+    assert_eq!(span.line, 1);
+    assert!(
+        err.to_string()
+            .starts_with("Nesting too deep at line 1, column "),
+        "{}",
+        err
+    );
+}
+
+/// Random mixes of openers, operators and operands (a small xorshift
+/// generator, so failures reproduce) never crash the parser
+#[test]
+fn test_random_pathological_inputs() {
+    const FRAGMENTS: &[&str] = &[
+        "(",
+        "[",
+        "{",
+        "-",
+        "not ",
+        "lambda: ",
+        "f(",
+        "a.",
+        "1",
+        " + ",
+        " ** ",
+        " if a else ",
+        ")",
+        "]",
+        "}",
+        ", ",
+        "x",
+        ":",
+    ];
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+    for _ in 0..50 {
+        let length = 500 + (next() % 3_000) as usize;
+        // Biased towards the openers, so most inputs nest deeply
+        let source: String = (0..length)
+            .map(|_| {
+                let roll = next() as usize;
+                FRAGMENTS[roll
+                    % if roll.is_multiple_of(4) {
+                        FRAGMENTS.len()
+                    } else {
+                        8
+                    }]
+            })
+            .collect();
+        let _ = parse(format!("x = {}\n", source));
+    }
+}
@@ -0,0 +1,53 @@
+//! Tests for the node ids the parser assigns
+
+use silk_ast::{ExpressionKind, Node, NodeId, NodeIndex, StatementKind};
+use silk_parser::Parser;
+use std::collections::HashSet;
+
+const SOURCE: &str = "\
+x = 1
+if x > 0:
+    print(x)
+elif x < 0:
+    x
+";
+
+#[test]
+fn test_every_node_has_a_unique_id() {
+    let program = Parser::parse(SOURCE).unwrap();
+    let index = NodeIndex::new(&program);
+    let ids: HashSet<NodeId> = (0..SOURCE.len())
+        .flat_map(|offset| index.nodes_at(offset))
+        .map(|node| node.id())
+        .collect();
+
+    assert_eq!(ids.len(), index.len());
+    assert_eq!(program.node_count, index.len());
+    assert!(ids.iter().all(|id| id.index() < program.node_count));
+}
+
+#[test]
+fn test_nodes_can_be_looked_up_by_id() {
+    let program = Parser::parse(SOURCE).unwrap();
+    let StatementKind::If { orelse, .. } = &program.statements[1].kind else {
+        panic!("expected an if statement");
+    };
+    let StatementKind::If { body, .. } = &orelse[0].kind else {
+        panic!("expected an elif");
+    };
+    // `x` on its own line: a statement and an expression at one position
+    let statement = &body[0];
+    let StatementKind::Expr(expression) = &statement.kind else {
+        panic!("expected an expression statement");
+    };
+    assert_eq!(statement.span.start, expression.span.start);
+    assert_ne!(statement.id, expression.id);
+
+    let index = NodeIndex::new(&program);
+    assert!(matches!(index.get(statement.id), Some(Node::Statement(s)) if s == statement));
+    assert!(matches!(
+        index.get(expression.id),
+        Some(Node::Expression(e)) if e.kind == ExpressionKind::Identifier("x".to_string())
+    ));
+    assert_eq!(index.get(NodeId::DUMMY), None);
+}
@@ -1,3 +1,5 @@
+#![allow(clippy::approx_constant)]
+
 use pretty_assertions::assert_eq;
 use silk_ast::{
     AugAssignOperator, BinaryOperator, CompareOperator, Expression, ExpressionKind,
@@ -357,6 +359,31 @@ fn test_precedence_addition_multiplication() {
     }
 }
 
+#[test]
+fn test_matmult_precedence() {
+    // a + b @ c * d should be parsed as a + ((b @ c) * d)
+    let expr = parse_expr("a + b @ c * d").unwrap();
+    match expr.kind {
+        ExpressionKind::BinaryOp { op, right, .. } => {
+            assert_eq!(op, BinaryOperator::Add);
+            match right.kind {
+                ExpressionKind::BinaryOp { left, op, .. } => {
+                    assert_eq!(op, BinaryOperator::Mult);
+                    assert!(matches!(
+                        left.kind,
+                        ExpressionKind::BinaryOp {
+                            op: BinaryOperator::MatMult,
+                            ..
+                        }
+                    ));
+                }
+                _ => panic!("Expected multiplication, got {:?}", right.kind),
+            }
+        }
+        _ => panic!("Expected binary operation, got {:?}", expr.kind),
+    }
+}
+
 #[test]
 fn test_precedence_parentheses() {
     // (1 + 2) * 3 should be parsed as (1 + 2) * 3
@@ -409,6 +436,64 @@ fn test_power_right_associative() {
     }
 }
 
+#[test]
+fn test_every_operator_builds_its_node() {
+    let binary = [
+        ("+", BinaryOperator::Add),
+        ("-", BinaryOperator::Sub),
+        ("*", BinaryOperator::Mult),
+        ("/", BinaryOperator::Div),
+        ("//", BinaryOperator::FloorDiv),
+        ("%", BinaryOperator::Mod),
+        ("@", BinaryOperator::MatMult),
+        ("**", BinaryOperator::Pow),
+        ("|", BinaryOperator::BitOr),
+        ("^", BinaryOperator::BitXor),
+        ("&", BinaryOperator::BitAnd),
+        ("<<", BinaryOperator::LShift),
+        (">>", BinaryOperator::RShift),
+    ];
+    for (text, expected) in binary {
+        match parse_expr(&format!("a {} b", text)).unwrap().kind {
+            ExpressionKind::BinaryOp { op, .. } => assert_eq!(op, expected, "{}", text),
+            other => panic!("Expected binary operation for {}, got {:?}", text, other),
+        }
+    }
+
+    let compare = [
+        ("==", CompareOperator::Eq),
+        ("!=", CompareOperator::NotEq),
+        ("<", CompareOperator::Lt),
+        (">", CompareOperator::Gt),
+        ("<=", CompareOperator::LtE),
+        (">=", CompareOperator::GtE),
+        ("in", CompareOperator::In),
+        ("not in", CompareOperator::NotIn),
+        ("is", CompareOperator::Is),
+        ("is not", CompareOperator::IsNot),
+    ];
+    for (text, expected) in compare {
+        match parse_expr(&format!("a {} b", text)).unwrap().kind {
+            ExpressionKind::Compare { ops, .. } => assert_eq!(ops, vec![expected], "{}", text),
+            other => panic!("Expected comparison for {}, got {:?}", text, other),
+        }
+    }
+}
+
+#[test]
+fn test_left_associative_chain() {
+    // (a - b) - c
+    let expr = parse_expr("a - b - c").unwrap();
+    match expr.kind {
+        ExpressionKind::BinaryOp { left, op, right } => {
+            assert_eq!(op, BinaryOperator::Sub);
+            assert!(matches!(left.kind, ExpressionKind::BinaryOp { .. }));
+            assert!(matches!(right.kind, ExpressionKind::Identifier(_)));
+        }
+        _ => panic!("Expected binary operation, got {:?}", expr.kind),
+    }
+}
+
 // ============================================================================
 // Unary Operator Tests
 // ============================================================================
@@ -569,6 +654,34 @@ fn test_greater_equal() {
     }
 }
 
+#[test]
+fn test_is_not_comparison() {
+    let expr = parse_expr("x is not None").unwrap();
+    match expr.kind {
+        ExpressionKind::Compare {
+            ops, comparators, ..
+        } => {
+            assert_eq!(ops, vec![CompareOperator::IsNot]);
+            assert!(matches!(comparators[0].kind, ExpressionKind::None));
+        }
+        _ => panic!("Expected comparison, got {:?}", expr.kind),
+    }
+}
+
+#[test]
+fn test_not_in_comparison() {
+    let expr = parse_expr("x not in items and y").unwrap();
+    match expr.kind {
+        ExpressionKind::LogicalOp { left, .. } => match left.kind {
+            ExpressionKind::Compare { ops, .. } => {
+                assert_eq!(ops, vec![CompareOperator::NotIn]);
+            }
+            _ => panic!("Expected comparison, got {:?}", left.kind),
+        },
+        _ => panic!("Expected logical operation, got {:?}", expr.kind),
+    }
+}
+
 // ============================================================================
 // Logical Operator Tests
 // ============================================================================
@@ -793,6 +906,17 @@ fn test_chained_attribute_access() {
     }
 }
 
+#[test]
+fn test_soft_keyword_attribute_names() {
+    for (source, name) in [("re.match", "match"), ("node.case", "case")] {
+        let expr = parse_expr(source).unwrap();
+        match expr.kind {
+            ExpressionKind::Attribute { attr, .. } => assert_eq!(attr, name),
+            _ => panic!("Expected attribute access, got {:?}", expr.kind),
+        }
+    }
+}
+
 #[test]
 fn test_attribute_method_call() {
     let expr = parse_expr("obj.method()").unwrap();
@@ -943,6 +1067,17 @@ fn test_augmented_assignment_mult() {
     }
 }
 
+#[test]
+fn test_augmented_assignment_matmult() {
+    let stmt = parse_stmt("m @= other").unwrap();
+    match stmt.kind {
+        StatementKind::AugAssign { op, .. } => {
+            assert_eq!(op, AugAssignOperator::MatMult);
+        }
+        _ => panic!("Expected augmented assignment, got {:?}", stmt.kind),
+    }
+}
+
 #[test]
 fn test_return_statement_with_value() {
     let stmt = parse_stmt("return 42").unwrap();
@@ -994,6 +1129,57 @@ fn test_continue_statement() {
     }
 }
 
+#[test]
+fn test_import_alias_spans() {
+    let source = "import os.path as p, sys";
+    let stmt = parse_stmt(source).unwrap();
+    match stmt.kind {
+        StatementKind::Import { names } => {
+            let spans: Vec<_> = names
+                .iter()
+                .map(|alias| &source[alias.span.start..alias.span.end])
+                .collect();
+            assert_eq!(spans, vec!["os.path as p", "sys"]);
+            assert_eq!(names[0].bound_name(), "p");
+            assert_eq!(names[1].bound_name(), "sys");
+        }
+        _ => panic!("Expected import statement, got {:?}", stmt.kind),
+    }
+}
+
+#[test]
+fn test_from_import_alias_spans() {
+    let source = "from m import (a as b, c)";
+    let stmt = parse_stmt(source).unwrap();
+    match stmt.kind {
+        StatementKind::ImportFrom { names, .. } => {
+            let spans: Vec<_> = names
+                .iter()
+                .map(|alias| &source[alias.span.start..alias.span.end])
+                .collect();
+            assert_eq!(spans, vec!["a as b", "c"]);
+        }
+        _ => panic!("Expected from-import statement, got {:?}", stmt.kind),
+    }
+}
+
+#[test]
+fn test_compound_statement_spans_end_with_their_block() {
+    let source = "def f(n):\n    if n:\n        return n\n\n\ndef g(n):\n    return n\n";
+    let stmts = parse_program(source).unwrap();
+    let spans: Vec<_> = stmts
+        .iter()
+        .map(|stmt| &source[stmt.span.start..stmt.span.end])
+        .collect();
+    assert_eq!(
+        spans,
+        vec![
+            "def f(n):\n    if n:\n        return n\n",
+            "def g(n):\n    return n\n"
+        ]
+    );
+}
+
 // ============================================================================
 // Multiple Statement Tests
 // ============================================================================
@@ -2096,6 +2282,36 @@ fn test_ternary_with_expressions() {
     }
 }
 
+#[test]
+fn test_ternary_test_with_or() {
+    let expr = parse_expr("x if a or b else y").unwrap();
+    match expr.kind {
+        ExpressionKind::IfExp { test, .. } => match &test.kind {
+            ExpressionKind::LogicalOp { op, .. } => assert_eq!(*op, LogicalOperator::Or),
+            _ => panic!("Expected 'or' in test, got {:?}", test.kind),
+        },
+        _ => panic!("Expected ternary expression, got {:?}", expr.kind),
+    }
+}
+
+#[test]
+fn test_comprehension_with_or() {
+    let expr = parse_expr("[x for x in a or b if x or y]").unwrap();
+    match expr.kind {
+        ExpressionKind::ListComp { generators, .. } => {
+            assert!(matches!(
+                generators[0].iter.kind,
+                ExpressionKind::LogicalOp { .. }
+            ));
+            assert!(matches!(
+                generators[0].ifs[0].kind,
+                ExpressionKind::LogicalOp { .. }
+            ));
+        }
+        _ => panic!("Expected list comprehension, got {:?}", expr.kind),
+    }
+}
+
 #[test]
 fn test_nested_ternary() {
     let expr = parse_expr("a if x > 0 else b if x < 0 else c").unwrap();
@@ -2595,6 +2811,30 @@ fn test_function_kwargs_with_type_annotation() {
     }
 }
 
+#[test]
+fn test_function_union_type_annotation() {
+    let stmt = parse_stmt("def func(x: int | None) -> str | int:\n    pass").unwrap();
+    match stmt.kind {
+        StatementKind::FunctionDef {
+            params, returns, ..
+        } => {
+            let annotation = params.args[0].annotation.as_ref().unwrap();
+            match &annotation.kind {
+                silk_ast::TypeKind::Union { types } => {
+                    assert_eq!(types.len(), 2);
+                    assert!(matches!(types[1].kind, silk_ast::TypeKind::None));
+                }
+                other => panic!("Expected union type, got {:?}", other),
+            }
+            assert!(matches!(
+                returns.unwrap().kind,
+                silk_ast::TypeKind::Union { .. }
+            ));
+        }
+        _ => panic!("Expected function definition"),
+    }
+}
+
 #[test]
 fn test_function_with_defaults_and_vararg() {
     let stmt = parse_stmt("def func(a, b=10, *args):\n    pass").unwrap();
@@ -2609,6 +2849,68 @@ fn test_function_with_defaults_and_vararg() {
     }
 }
 
+#[test]
+fn test_function_positional_only_params() {
+    let stmt = parse_stmt("def func(a, b=1, /, c=2):\n    pass").unwrap();
+    match stmt.kind {
+        StatementKind::FunctionDef { params, .. } => {
+            let posonly: Vec<_> = params.posonlyargs.iter().map(|p| p.name.as_str()).collect();
+            assert_eq!(posonly, vec!["a", "b"]);
+            assert_eq!(params.args.len(), 1);
+            assert_eq!(params.args[0].name, "c");
+        }
+        _ => panic!("Expected function definition"),
+    }
+}
+
+#[test]
+fn test_function_keyword_only_params() {
+    let stmt = parse_stmt("def func(a, /, b, *, c, d=4, **kwargs):\n    pass").unwrap();
+    match stmt.kind {
+        StatementKind::FunctionDef { params, .. } => {
+            assert_eq!(params.posonlyargs.len(), 1);
+            assert_eq!(params.args.len(), 1);
+            assert!(params.vararg.is_none());
+            let kwonly: Vec<_> = params.kwonlyargs.iter().map(|p| p.name.as_str()).collect();
+            assert_eq!(kwonly, vec!["c", "d"]);
+            assert!(params.kwarg.is_some());
+            assert_eq!(params.all().count(), 5);
+        }
+        _ => panic!("Expected function definition"),
+    }
+}
+
+#[test]
+fn test_function_params_after_vararg_are_keyword_only() {
+    let stmt = parse_stmt("def func(a, *args, flag=False):\n    pass").unwrap();
+    match stmt.kind {
+        StatementKind::FunctionDef { params, .. } => {
+            assert_eq!(params.args.len(), 1);
+            assert_eq!(params.kwonlyargs.len(), 1);
+            assert_eq!(params.kwonlyargs[0].name, "flag");
+        }
+        _ => panic!("Expected function definition"),
+    }
+}
+
+#[test]
+fn test_function_invalid_param_separators() {
+    for source in [
+        "def f(/, a):\n    pass",
+        "def f(a, /, b, /):\n    pass",
+        "def f(*, a, /):\n    pass",
+        "def f(a, *):\n    pass",
+        "def f(*, **kwargs):\n    pass",
+        "def f(*a, *b):\n    pass",
+    ] {
+        assert!(
+            matches!(Parser::parse(source), Err(ParseError::InvalidSyntax(..))),
+            "Expected syntax error for {:?}",
+            source
+        );
+    }
+}
+
 // ==================== Decorator Tests ====================
 
 #[test]
@@ -2779,6 +3081,38 @@ fn test_decorator_with_complex_call() {
     }
 }
 
+#[test]
+fn test_decorator_any_expression() {
+    // PEP 614: subscripts, conditional expressions and `:=` after '@'
+    let stmt = parse_stmt("@buttons[0].clicked.connect\ndef func():\n    pass").unwrap();
+    match stmt.kind {
+        StatementKind::FunctionDef { decorator_list, .. } => {
+            assert!(matches!(
+                decorator_list[0].kind,
+                ExpressionKind::Attribute { .. }
+            ));
+        }
+        _ => panic!("Expected function definition"),
+    }
+    let stmt = parse_stmt("@first if debug else second\nclass C:\n    pass").unwrap();
+    match stmt.kind {
+        StatementKind::ClassDef { decorator_list, .. } => {
+            assert!(matches!(
+                decorator_list[0].kind,
+                ExpressionKind::IfExp { .. }
+            ));
+        }
+        _ => panic!("Expected class definition"),
+    }
+    assert!(parse_stmt("@(lambda f: f)\n@chosen := pick()\ndef func():\n    pass").is_ok());
+}
+
+#[test]
+fn test_decorator_needs_its_own_line() {
+    assert!(parse_stmt("@decorator def func():\n    pass").is_err());
+    assert!(parse_stmt("@decorator class C:\n    pass").is_err());
+}
+
 // ==================== Walrus Operator Tests ====================
 
 #[test]
@@ -4169,3 +4503,84 @@ fn test_comp_with_attribute_access() {
         _ => panic!("Expected list comprehension"),
     }
 }
+
+// ============================================================================
+// Multi-line Statement Tests (bracket joining and `\` continuation)
+// ============================================================================
+
+#[test]
+fn test_multiline_call_arguments() {
+    let expr = parse_expr("print(\n    1,\n    2,  # second\n)").unwrap();
+    match expr.kind {
+        ExpressionKind::Call { args, .. } => assert_eq!(args.len(), 2),
+        _ => panic!("Expected call, got {:?}", expr.kind),
+    }
+}
+
+#[test]
+fn test_multiline_collection_literals() {
+    let statements =
+        parse_program("xs = [\n    1,\n    2,\n]\nd = {\n    'a': 1,\n\n    'b': 2,\n}\n").unwrap();
+    assert_eq!(statements.len(), 2);
+}
+
+#[test]
+fn test_multiline_function_parameters() {
+    let source = "def f(\n    a: int,\n    b: int = 2,\n) -> int:\n    return a + b\n";
+    let stmt = parse_stmt(source).unwrap();
+    match stmt.kind {
+        StatementKind::FunctionDef { params, body, .. } => {
+            assert_eq!(params.args.len(), 2);
+            assert_eq!(body.len(), 1);
+        }
+        _ => panic!("Expected function definition, got {:?}", stmt.kind),
+    }
+}
+
+#[test]
+fn test_backslash_line_continuation() {
+    let statements =
+        parse_program("x = 1 + \\\n    2\nif x > 1 and \\\n   x < 5:\n    pass\n").unwrap();
+    assert_eq!(statements.len(), 2);
+    match &statements[0].kind {
+        StatementKind::Assign { value, .. } => {
+            assert!(matches!(
+                value.kind,
+                ExpressionKind::BinaryOp {
+                    op: BinaryOperator::Add,
+                    ..
+                }
+            ));
+        }
+        other => panic!("Expected assignment, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_comment_lines_are_skipped() {
+    let source = "# Header\nx = 1\n\ndef f():\n    # Body comment\n    return x  # trailing\n";
+    let statements = parse_program(source).unwrap();
+    assert_eq!(statements.len(), 2);
+    match &statements[1].kind {
+        StatementKind::FunctionDef { body, .. } => assert_eq!(body.len(), 1),
+        other => panic!("Expected function definition, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_del_invalid_targets() {
+    for (source, message) in [
+        ("del 1", "cannot delete literal"),
+        ("del x, 'text'", "cannot delete literal"),
+        ("del (a, None)", "cannot delete literal"),
+        ("del f()", "cannot delete function call"),
+        ("del a + b", "cannot delete expression"),
+        ("del [x, -y]", "cannot delete expression"),
+    ] {
+        match Parser::parse(source) {
+            Err(ParseError::InvalidSyntax(found, 1, _)) => assert_eq!(found, message),
+            other => panic!("expected {:?} for {:?}, got {:?}", message, source, other),
+        }
+    }
+    assert!(Parser::parse("del x, a.b, a[0], a[1:], (c, [d])").is_ok());
+}
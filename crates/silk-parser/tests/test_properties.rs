@@ -0,0 +1,315 @@
+//! Property-based tests for the parser: random token soup never panics or
+//! hangs, and printing a parsed program gives source that parses back to
+//! the same tree
+
+use proptest::prelude::*;
+use silk_ast::{unparse, Program};
+use silk_parser::{ParseResult, Parser};
+use std::sync::mpsc;
+use std::time::Duration;
+
+/// Longest a parse of generated input may take before it counts as a hang
+const PARSE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Parse on a thread with the stack of a main thread, failing the test if
+/// the parser panics or does not finish in time
+fn parse_bounded(source: String) -> Result<ParseResult<Program>, TestCaseError> {
+    let (sender, receiver) = mpsc::channel();
+    let shown = source.clone();
+    std::thread::Builder::new()
+        .stack_size(8 << 20)
+        .spawn(move || {
+            let _ = sender.send(Parser::parse(&source));
+        })
+        .unwrap();
+    match receiver.recv_timeout(PARSE_TIMEOUT) {
+        Ok(result) => Ok(result),
+        Err(mpsc::RecvTimeoutError::Timeout) => Err(TestCaseError::fail(format!(
+            "parser did not finish within {:?} on {:?}",
+            PARSE_TIMEOUT, shown
+        ))),
+        Err(mpsc::RecvTimeoutError::Disconnected) => Err(TestCaseError::fail(format!(
+            "parser panicked on {:?}",
+            shown
+        ))),
+    }
+}
+
+/// Tokens of Silk source, including ones that only make sense in pairs, so
+/// that random sequences reach the parser instead of failing in the lexer
+const TOKENS: &[&str] = &[
+    "def", "class", "if", "elif", "else", "for", "in", "while", "return", "lambda", "not", "and",
+    "or", "is", "try", "except", "finally", "with", "as", "match", "case", "import", "from", "del",
+    "global", "pass", "break", "raise", "assert", "None", "True", "value", "item", "0", "42",
+    "3.14", "\"text\"", "(", ")", "[", "]", "{", "}", ":", ",", ".", "...", "+", "-", "*", "**",
+    "/", "//", "%", "@", "|", "&", "^", "~", "<<", "<", "==", "!=", "=", "+=", "->", ":=", ";",
+];
+
+/// Lines of random tokens, some indented, so that blocks open and close
+fn token_soup() -> impl Strategy<Value = String> {
+    let token = prop_oneof![
+        prop::sample::select(TOKENS).prop_map(String::from),
+        Just("f\"{value}\"".to_string()),
+    ];
+    let line = (0..3usize, prop::collection::vec(token, 1..8))
+        .prop_map(|(indent, tokens)| format!("{}{}\n", "    ".repeat(indent), tokens.join(" ")));
+    prop::collection::vec(line, 0..12).prop_map(|lines| lines.concat())
+}
+
+/// Names used where a statement may start with them; short names could be
+/// read as a misspelled keyword (`a b` as `as b`)
+fn name() -> impl Strategy<Value = String> {
+    prop::sample::select(vec!["value", "item", "total", "count", "data"]).prop_map(String::from)
+}
+
+fn atom() -> impl Strategy<Value = String> {
+    prop_oneof![
+        name(),
+        (0..100_000i64).prop_map(|n| n.to_string()),
+        prop::sample::select(vec!["0.5", "3.25", "1e3", "2.5e-3", "2.", ".75"])
+            .prop_map(String::from),
+        prop::sample::select(vec![
+            "\"text\"",
+            "'it\\'s'",
+            "\"tab\\there\"",
+            "\"\\0\\101\\u00e9\\N{BULLET}\\x7f\"",
+            "\"{braces}\"",
+            "\"con\" 'cat'",
+            "'a' f\"{value}\" r'\\d'",
+            "r\"\\d+\"",
+            "b\"ab\\x00\"",
+            "f\"{value}!\"",
+            "f\"{{{total:>8}}}\"",
+            "f\"{data['key']!r:>{count}}\"",
+            "True",
+            "False",
+            "None",
+            "...",
+        ])
+        .prop_map(String::from),
+    ]
+}
+
+/// Expressions, parenthesized more than needed so that they always parse;
+/// printing decides which parentheses to keep
+fn expression() -> impl Strategy<Value = String> {
+    atom().prop_recursive(5, 64, 4, |inner| {
+        let operator = prop::sample::select(vec![
+            "+", "-", "*", "/", "//", "%", "**", "@", "|", "^", "&", "<<", ">>", "<", "<=", ">",
+            ">=", "==", "!=", "in", "not in", "is", "is not", "and", "or",
+        ]);
+        let arguments = prop::collection::vec(inner.clone(), 0..3).prop_map(|args| args.join(", "));
+        prop_oneof![
+            // With and without parentheses, to exercise precedence both ways
+            (inner.clone(), operator.clone(), inner.clone())
+                .prop_map(|(left, op, right)| format!("({} {} {})", left, op, right)),
+            (inner.clone(), operator, inner.clone())
+                .prop_map(|(left, op, right)| format!("{} {} {}", left, op, right)),
+            (
+                prop::sample::select(vec!["-", "+", "~", "not "]),
+                inner.clone()
+            )
+                .prop_map(|(op, operand)| format!("({}{})", op, operand)),
+            (name(), arguments.clone()).prop_map(|(func, args)| format!("{}({})", func, args)),
+            (inner.clone(), name())
+                .prop_map(|(func, key)| format!("print({}, {}=1, **options)", func, key)),
+            inner.clone().prop_map(|value| format!("({}).real", value)),
+            (inner.clone(), inner.clone())
+                .prop_map(|(value, index)| format!("({})[{}]", value, index)),
+            (inner.clone(), inner.clone())
+                .prop_map(|(lower, upper)| format!("data[{}:{}:2]", lower, upper)),
+            arguments.clone().prop_map(|items| format!("[{}]", items)),
+            prop::collection::vec(inner.clone(), 1..3)
+                .prop_map(|items| format!("({},)", items.join(", "))),
+            (inner.clone(), inner.clone())
+                .prop_map(|(key, value)| format!("{{{}: {}}}", key, value)),
+            inner.clone().prop_map(|item| format!("{{{}, 1}}", item)),
+            (inner.clone(), inner.clone(), inner.clone())
+                .prop_map(|(body, test, orelse)| format!("({} if {} else {})", body, test, orelse)),
+            inner
+                .clone()
+                .prop_map(|body| format!("(lambda x, y=1: {})", body)),
+            (inner.clone(), inner.clone()).prop_map(|(element, condition)| format!(
+                "[{} for x in data if {}]",
+                element, condition
+            )),
+            inner
+                .clone()
+                .prop_map(|element| format!("{{x: {} for (x, y) in data}}", element)),
+            inner
+                .clone()
+                .prop_map(|element| format!("({} for x in data)", element)),
+            inner.prop_map(|value| format!("(total := {})", value)),
+        ]
+    })
+}
+
+/// Patterns of a `case` clause
+fn pattern() -> impl Strategy<Value = String> {
+    prop::sample::select(vec![
+        "(value, item)",
+        "[value, *rest] if value",
+        "{\"key\": item, **rest}",
+        "Point(0, y=[value, *_]) | Point(1, y=[value, *_])",
+        "-1 | 2.5 | \"text\" | None as item",
+        "module.CONSTANT",
+        "_",
+    ])
+    .prop_map(String::from)
+}
+
+fn indent(lines: Vec<String>) -> Vec<String> {
+    lines
+        .into_iter()
+        .map(|line| format!("    {}", line))
+        .collect()
+}
+
+/// One statement as lines of source
+fn statement() -> impl Strategy<Value = Vec<String>> {
+    let simple = prop_oneof![
+        expression().prop_map(|value| format!("print({})", value)),
+        (name(), expression()).prop_map(|(target, value)| format!("{} = {}", target, value)),
+        (name(), expression()).prop_map(|(target, value)| format!("{}.attr = {}", target, value)),
+        (name(), expression()).prop_map(|(target, value)| format!("{} += {}", target, value)),
+        (name(), expression())
+            .prop_map(|(target, value)| format!("{}: list[int] | None = {}", target, value)),
+        expression().prop_map(|value| format!("(value, item) = {}", value)),
+        expression().prop_map(|value| format!("return {}", value)),
+        expression().prop_map(|value| format!("assert {}, \"message\"", value)),
+        expression().prop_map(|value| format!("raise ValueError({}) from None", value)),
+        prop::sample::select(vec![
+            "pass",
+            "break",
+            "continue",
+            "return",
+            "raise",
+            "del value, data[0]",
+            "global total, count",
+            "import os.path as path, sys",
+            "from ..package.module import (name, other as alias)",
+            "from . import *",
+            "value = 1; pass;",
+        ])
+        .prop_map(String::from),
+    ]
+    .prop_map(|line| vec![line]);
+
+    simple.prop_recursive(3, 24, 3, |inner| {
+        let block =
+            prop::collection::vec(inner, 1..3).prop_map(|statements| indent(statements.concat()));
+        prop_oneof![
+            (expression(), block.clone(), block.clone(), block.clone()).prop_map(
+                |(test, body, elif, orelse)| {
+                    let mut lines = vec![format!("if {}:", test)];
+                    lines.extend(body);
+                    lines.push("elif value:".to_string());
+                    lines.extend(elif);
+                    lines.push("else:".to_string());
+                    lines.extend(orelse);
+                    lines
+                }
+            ),
+            (expression(), block.clone()).prop_map(|(test, body)| {
+                let mut lines = vec![format!("while {}:", test)];
+                lines.extend(body);
+                lines
+            }),
+            (expression(), block.clone(), block.clone()).prop_map(|(iter, body, orelse)| {
+                let mut lines = vec![format!("for value, item in {}:", iter)];
+                lines.extend(body);
+                lines.push("else:".to_string());
+                lines.extend(orelse);
+                lines
+            }),
+            block.clone().prop_map(|body| {
+                let mut lines = vec![
+                    "@decorator".to_string(),
+                    "@module.factory(1)".to_string(),
+                    "def function(a, /, b: int = 1, *args: str, c, **kwargs) -> int:".to_string(),
+                ];
+                lines.extend(body);
+                lines
+            }),
+            block.clone().prop_map(|body| {
+                let mut lines = vec!["class Shape(Base, metaclass=Meta):".to_string()];
+                lines.extend(body);
+                lines
+            }),
+            (block.clone(), block.clone(), block.clone()).prop_map(|(body, handler, finalbody)| {
+                let mut lines = vec!["try:".to_string()];
+                lines.extend(body);
+                lines.push("except ValueError as error:".to_string());
+                lines.extend(handler);
+                lines.push("finally:".to_string());
+                lines.extend(finalbody);
+                lines
+            }),
+            (expression(), block.clone()).prop_map(|(context, body)| {
+                let mut lines = vec![format!("with open({}) as handle, lock:", context)];
+                lines.extend(body);
+                lines
+            }),
+            (expression(), pattern(), block).prop_map(|(subject, pattern, body)| {
+                let mut lines = vec![
+                    format!("match {}:", subject),
+                    format!("    case {}:", pattern),
+                ];
+                lines.extend(indent(body));
+                lines
+            }),
+        ]
+    })
+}
+
+fn program() -> impl Strategy<Value = String> {
+    prop::collection::vec(statement(), 1..4).prop_map(|statements| {
+        let mut source = statements.concat().join("\n");
+        source.push('\n');
+        source
+    })
+}
+
+/// Debug output of a tree without its spans, which differ between the
+/// original and the printed source
+fn without_spans(program: &Program) -> String {
+    let debug = format!("{:?}", program);
+    let mut out = String::with_capacity(debug.len());
+    let mut rest = debug.as_str();
+    while let Some(start) = rest.find("Span {") {
+        out.push_str(&rest[..start]);
+        let end = rest[start..].find('}').expect("unterminated span");
+        rest = &rest[start + end + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+proptest! {
+    #[test]
+    fn test_token_soup_never_panics_or_hangs(source in token_soup()) {
+        // Parse errors are expected; only panics and hangs fail
+        let _ = parse_bounded(source)?;
+    }
+
+    #[test]
+    fn test_printed_programs_parse_to_the_same_tree(source in program()) {
+        let program = match parse_bounded(source.clone())? {
+            Ok(program) => program,
+            Err(error) => return Err(TestCaseError::fail(format!(
+                "generated program failed to parse: {}\n{}",
+                error, source
+            ))),
+        };
+        let printed = unparse(&program);
+        let reparsed = match parse_bounded(printed.clone())? {
+            Ok(program) => program,
+            Err(error) => return Err(TestCaseError::fail(format!(
+                "printed program failed to parse: {}\n{}",
+                error, printed
+            ))),
+        };
+        prop_assert_eq!(without_spans(&reparsed), without_spans(&program), "{}", printed);
+        prop_assert_eq!(unparse(&reparsed), printed);
+    }
+}
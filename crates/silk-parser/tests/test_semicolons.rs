@@ -0,0 +1,99 @@
+//! Tests for simple statements separated by semicolons
+
+use silk_ast::StatementKind;
+use silk_parser::{ParseError, Parser};
+
+/// Names of the kinds of the top-level statements of `source`
+fn statement_kinds(source: &str) -> Vec<&'static str> {
+    let program = Parser::parse(source).expect("source should parse");
+    program
+        .statements
+        .iter()
+        .map(|statement| match statement.kind {
+            StatementKind::Assign { .. } => "assign",
+            StatementKind::AugAssign { .. } => "aug-assign",
+            StatementKind::Expr(_) => "expr",
+            StatementKind::Pass => "pass",
+            StatementKind::Return { .. } => "return",
+            StatementKind::Import { .. } => "import",
+            StatementKind::If { .. } => "if",
+            _ => "other",
+        })
+        .collect()
+}
+
+/// The message, line and column of the syntax error in `source`
+fn syntax_error(source: &str) -> (String, usize, usize) {
+    match Parser::parse(source) {
+        Err(ParseError::InvalidSyntax(message, line, column)) => (message, line, column),
+        other => panic!("expected an invalid syntax error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_semicolons_separate_statements() {
+    assert_eq!(
+        statement_kinds("x = 1; y = 2; print(x)\n"),
+        vec!["assign", "assign", "expr"]
+    );
+    assert_eq!(
+        statement_kinds("import os; x += 1; pass\n"),
+        vec!["import", "aug-assign", "pass"]
+    );
+}
+
+#[test]
+fn test_trailing_semicolon() {
+    assert_eq!(statement_kinds("x = 1;\n"), vec!["assign"]);
+    assert_eq!(statement_kinds("x = 1;"), vec!["assign"]);
+}
+
+#[test]
+fn test_semicolons_in_blocks() {
+    let program = Parser::parse("def f():\n    x = 1; return x\nf()\n").unwrap();
+    match &program.statements[0].kind {
+        StatementKind::FunctionDef { body, .. } => assert_eq!(body.len(), 2),
+        other => panic!("expected a function, got {:?}", other),
+    }
+    assert_eq!(
+        statement_kinds("if x:\n    pass; pass\ny = 1; z = 2\n"),
+        vec!["if", "assign", "assign"]
+    );
+}
+
+#[test]
+fn test_statement_spans_stop_at_the_semicolon() {
+    let program = Parser::parse("x = 1; y = 2\n").unwrap();
+    let spans: Vec<_> = program
+        .statements
+        .iter()
+        .map(|statement| (statement.span.start, statement.span.column))
+        .collect();
+    assert_eq!(spans, vec![(0, 1), (7, 8)]);
+    assert_eq!(program.statements[0].span.end, 6);
+}
+
+#[test]
+fn test_compound_statement_after_semicolon_is_an_error() {
+    assert_eq!(
+        syntax_error("x = 1; if x:\n    pass\n"),
+        (
+            "'if' cannot follow ';' on the same line; start it on a new line".to_string(),
+            1,
+            8
+        )
+    );
+    for source in [
+        "x = 1; def f():\n    pass\n",
+        "pass; for i in x:\n    pass\n",
+        "pass; @decorator\ndef f():\n    pass\n",
+    ] {
+        syntax_error(source);
+    }
+}
+
+#[test]
+fn test_stray_semicolons_are_errors() {
+    assert!(Parser::parse("; x = 1\n").is_err());
+    assert!(Parser::parse("x = 1;; y = 2\n").is_err());
+}
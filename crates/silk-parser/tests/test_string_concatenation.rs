@@ -0,0 +1,112 @@
+//! Tests for joining adjacent string literals and the warning about
+//! strings joined inside collections
+
+use silk_ast::{ExpressionKind, StatementKind};
+use silk_lexer::FStringPart;
+use silk_parser::{ParseError, Parser};
+
+/// The value of `x = <literal>`
+fn literal(source: &str) -> ExpressionKind {
+    let program = Parser::parse(source).expect("source should parse");
+    match &program.statements[0].kind {
+        StatementKind::Assign { value, .. } => value.kind.clone(),
+        other => panic!("expected an assignment, got {:?}", other),
+    }
+}
+
+/// Messages of the warnings found while parsing `source`
+fn warnings(source: &str) -> Vec<String> {
+    let mut parser = Parser::new(source).unwrap();
+    parser.parse_program().expect("source should parse");
+    parser
+        .warnings()
+        .iter()
+        .inspect(|warning| assert!(warning.is_warning()))
+        .map(|warning| warning.to_string())
+        .collect()
+}
+
+#[test]
+fn test_adjacent_strings_are_joined() {
+    assert_eq!(
+        literal("x = \"abc\" 'def'"),
+        ExpressionKind::String("abcdef".to_string())
+    );
+    assert_eq!(
+        literal("x = r\"\\d\" r'+'"),
+        ExpressionKind::RawString("\\d+".to_string())
+    );
+    assert_eq!(
+        literal("x = r\"\\d\" \"\\n\""),
+        ExpressionKind::String("\\d\n".to_string())
+    );
+    assert_eq!(
+        literal("x = (\"first line \"\n     \"second line\")"),
+        ExpressionKind::String("first line second line".to_string())
+    );
+}
+
+#[test]
+fn test_joining_an_fstring_gives_an_fstring() {
+    let expected = ExpressionKind::FString {
+        parts: vec![
+            FStringPart::Text("Hello, ".to_string()),
+            FStringPart::Expression {
+                code: "name".to_string(),
+                conversion: None,
+                format_spec: None,
+            },
+            FStringPart::Text("! Bye.".to_string()),
+        ],
+    };
+    assert_eq!(literal("x = 'Hello, ' f'{name}!' ' Bye.'"), expected);
+    assert_eq!(literal("x = f'Hello, ' f'{name}' '! Bye.'"), expected);
+}
+
+#[test]
+fn test_bytes_join_only_with_bytes() {
+    assert_eq!(
+        literal("x = b'ab' br'\\c'"),
+        ExpressionKind::ByteString(b"ab\\c".to_vec())
+    );
+    match Parser::parse("x = b'ab' 'cd'") {
+        Err(ParseError::InvalidSyntax(message, 1, 11)) => {
+            assert_eq!(message, "cannot join bytes and non-bytes string literals")
+        }
+        other => panic!("expected an invalid syntax error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_joined_strings_span_every_piece() {
+    let program = Parser::parse("x = 'a' 'b' 'c'\n").unwrap();
+    match &program.statements[0].kind {
+        StatementKind::Assign { value, .. } => {
+            assert_eq!((value.span.start, value.span.end), (4, 15))
+        }
+        other => panic!("expected an assignment, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_strings_joined_on_one_line_in_a_collection_warn() {
+    assert_eq!(
+        warnings("colors = ['red', 'green' 'blue']\n"),
+        vec![
+            "Implicit string concatenation in a list at line 1, column 26: the adjacent strings \
+             form one element (is a comma missing?)"
+        ]
+    );
+    assert_eq!(warnings("x = {'a' 'b', 'c'}\n").len(), 1);
+    assert_eq!(warnings("x = ('a', 'b' f'{c}')\n").len(), 1);
+    // The second string starts on the line where the first one ends
+    assert_eq!(warnings("x = ['''a\n''' 'b']\n").len(), 1);
+}
+
+#[test]
+fn test_intended_joins_do_not_warn() {
+    // Split over lines, outside a collection, or in a call
+    assert!(warnings("x = [\n    'a long '\n    'string',\n    'other',\n]\n").is_empty());
+    assert!(warnings("x = 'a' 'b'\n").is_empty());
+    assert!(warnings("print('a' 'b', 'c')\n").is_empty());
+}
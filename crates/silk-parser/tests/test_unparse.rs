@@ -0,0 +1,86 @@
+//! Tests for printing parsed programs back as source
+
+use silk_ast::unparse;
+use silk_parser::Parser;
+
+fn print(source: &str) -> String {
+    unparse(&Parser::parse(source).expect("source should parse"))
+}
+
+#[test]
+fn test_parentheses_only_where_precedence_needs_them() {
+    assert_eq!(print("x = ((a + b)) * (c * d)"), "x = (a + b) * (c * d)\n");
+    assert_eq!(print("x = (a - b) - c"), "x = a - b - c\n");
+    assert_eq!(print("x = a - (b - c)"), "x = a - (b - c)\n");
+    assert_eq!(print("x = a ** (b ** c)"), "x = a ** b ** c\n");
+    assert_eq!(print("x = (a ** b) ** c"), "x = (a ** b) ** c\n");
+    assert_eq!(print("x = (-a) ** 2"), "x = (-a) ** 2\n");
+    assert_eq!(print("x = -a ** 2"), "x = -a ** 2\n");
+    assert_eq!(print("x = not (a and b) or c"), "x = not (a and b) or c\n");
+    assert_eq!(
+        print("x = (a if b else c) if d else e"),
+        "x = (a if b else c) if d else e\n"
+    );
+    assert_eq!(print("f((lambda: 1) + 2)"), "f((lambda: 1) + 2)\n");
+    assert_eq!(print("y = (x := 5)"), "y = (x := 5)\n");
+    assert_eq!(
+        print("x = (1).real + (a + b).c"),
+        "x = (1).real + (a + b).c\n"
+    );
+}
+
+#[test]
+fn test_literals_are_escaped() {
+    assert_eq!(print("s = 'say \"hi\"\\n'"), "s = \"say \\\"hi\\\"\\n\"\n");
+    assert_eq!(print("b = b'\\x00a'"), "b = b\"\\x00a\"\n");
+    assert_eq!(print("f = f'{{{x:>4}}}!'"), "f = f\"{{{x:>4}}}!\"\n");
+    assert_eq!(
+        print("f = f'{d[\"k\"]!r:{w}}'"),
+        "f = f\"{d[\"k\"]!r:{w}}\"\n"
+    );
+    assert_eq!(
+        print("f = f'''{(a +\n b)}'''"),
+        "f = f\"\"\"{(a +\n b)}\"\"\"\n"
+    );
+    assert_eq!(
+        print("s = '\\0001\\a\\N{BULLET}'"),
+        "s = \"\\x001\\x07•\"\n"
+    );
+    assert_eq!(print("t = (1,)"), "t = (1,)\n");
+    assert_eq!(print("n = 2.0 + 1e3"), "n = 2.0 + 1000.0\n");
+}
+
+#[test]
+fn test_statements_and_blocks() {
+    let source = "\
+@decorator
+def f(a, /, b: int = 1, *, c, **kw) -> int:
+    if a:
+        return b
+    elif c:
+        pass
+    else:
+        for x, y in kw:
+            del x
+    try:
+        raise E from None
+    except E as e:
+        pass
+    finally:
+        pass
+class C(B, metaclass=M):
+    x: int = 1
+from .. import (a, b as c)
+";
+    assert_eq!(print(source), source.replace("(a, b as c)", "a, b as c"));
+}
+
+#[test]
+fn test_decorator_expressions() {
+    let source = "@chosen := pick()\n@a if b else c\n@buttons[0].clicked\ndef f():\n    pass\n";
+    // `:=` is printed in parentheses, which are always allowed
+    assert_eq!(
+        print(source),
+        source.replace("chosen := pick()", "(chosen := pick())")
+    );
+}
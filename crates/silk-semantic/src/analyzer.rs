@@ -5,8 +5,29 @@
 //! 2. Main pass: Define symbols and validate references in one traversal
 //! 3. Control flow analysis: Check for unreachable code, uninitialized variables, etc.
 
+use crate::builtins;
+use crate::call_graph::subexpressions;
+use crate::classes::{c3_linearization, short_name, ClassInfo, MethodInfo, MethodKind};
+use crate::conditions;
+use crate::confusables;
+use crate::const_eval::{self, ConstFunction};
+use crate::constants::{self, Constant, FoldError};
+use crate::formatting;
+use crate::narrowing::{
+    always_exits, filter_members, narrowed_name, NarrowedTypes, NarrowedVar, Narrowing,
+};
+use crate::purity::Purity;
+use crate::signature::{BindError, ParamKind, Signature};
+use crate::stdlib;
+use crate::typed::{ExpressionTypes, TypedProgram};
 use crate::{ControlFlowAnalyzer, ScopeKind, SemanticError, Symbol, SymbolKind, SymbolTable};
-use silk_ast::{Expression, ExpressionKind, PatternKind, Program, Statement, StatementKind};
+use silk_lexer::suggest;
+use silk_ast::{
+    ensure_sufficient_stack, Expression, ExpressionKind, Feature, FeatureSet, PatternKind, Program,
+    Statement, StatementKind,
+};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 
 /// Semantic analyzer for single-pass analysis
 pub struct SemanticAnalyzer {
@@ -18,6 +39,35 @@ pub struct SemanticAnalyzer {
     current_function_return_type: Option<crate::types::Type>,
     /// Whether to run control flow analysis (default: true)
     enable_control_flow: bool,
+    /// Unstable features enabled for this analysis
+    features: FeatureSet,
+    /// Class members collected from class bodies, by qualified name
+    classes: HashMap<String, ClassInfo>,
+    /// Qualified name of each class definition, by its span
+    class_names: HashMap<silk_lexer::Span, String>,
+    /// Qualified names of the enclosing definitions (`Outer`, then
+    /// `Outer.method.<locals>`), innermost last
+    enclosing_names: Vec<String>,
+    /// Qualified name of the class whose body is currently being analyzed
+    current_class: Option<String>,
+    /// Variable types refined by enclosing conditions
+    narrowed: NarrowedTypes,
+    /// Names declared `global` or `nonlocal`, one set per enclosing
+    /// function (innermost last); assigning them binds no local
+    outer_declarations: Vec<HashSet<String>>,
+    /// Type of every analyzed expression, kept only by `analyze_typed`
+    expression_types: Option<ExpressionTypes>,
+    /// Codes of lints that are allowed by default but were enabled
+    enabled_lints: HashSet<String>,
+    /// Smallest duplicate reported by `duplicate-code`, in nodes
+    duplicate_min_nodes: usize,
+    /// Limits of the metrics checked by `complex-function`
+    metric_limits: crate::MetricLimits,
+    /// Inferred purity of the functions of the program
+    purity: Purity,
+    /// Pure module-level functions that calls with constant arguments are
+    /// folded through, by name
+    const_functions: HashMap<String, ConstFunction>,
 }
 
 impl SemanticAnalyzer {
@@ -28,6 +78,19 @@ impl SemanticAnalyzer {
             errors: Vec::new(),
             current_function_return_type: None,
             enable_control_flow: true, // Enabled by default
+            features: FeatureSet::new(),
+            classes: HashMap::new(),
+            class_names: HashMap::new(),
+            enclosing_names: Vec::new(),
+            current_class: None,
+            narrowed: NarrowedTypes::new(),
+            outer_declarations: vec![HashSet::new()],
+            expression_types: None,
+            enabled_lints: HashSet::new(),
+            duplicate_min_nodes: crate::DEFAULT_DUPLICATE_MIN_NODES,
+            metric_limits: crate::MetricLimits::default(),
+            purity: Purity::default(),
+            const_functions: HashMap::new(),
         }
     }
 
@@ -38,6 +101,19 @@ impl SemanticAnalyzer {
             errors: Vec::new(),
             current_function_return_type: None,
             enable_control_flow: false,
+            features: FeatureSet::new(),
+            classes: HashMap::new(),
+            class_names: HashMap::new(),
+            enclosing_names: Vec::new(),
+            current_class: None,
+            narrowed: NarrowedTypes::new(),
+            outer_declarations: vec![HashSet::new()],
+            expression_types: None,
+            enabled_lints: HashSet::new(),
+            duplicate_min_nodes: crate::DEFAULT_DUPLICATE_MIN_NODES,
+            metric_limits: crate::MetricLimits::default(),
+            purity: Purity::default(),
+            const_functions: HashMap::new(),
         }
     }
 
@@ -46,8 +122,35 @@ impl SemanticAnalyzer {
         self.enable_control_flow = enabled;
     }
 
+    /// Set the unstable features enabled for this analysis
+    pub fn set_features(&mut self, features: FeatureSet) {
+        self.features = features;
+    }
+
+    /// Report a lint that is allowed by default (e.g. `missing-docstring`)
+    pub fn enable_lint(&mut self, code: &str) {
+        self.enabled_lints.insert(code.to_string());
+    }
+
+    /// Set the smallest duplicate `duplicate-code` reports, in nodes
+    pub fn set_duplicate_min_nodes(&mut self, nodes: usize) {
+        self.duplicate_min_nodes = nodes;
+    }
+
+    /// Set the limits `complex-function` checks functions against
+    pub fn set_metric_limits(&mut self, limits: crate::MetricLimits) {
+        self.metric_limits = limits;
+    }
+
     /// Analyze a program and return errors if any
     pub fn analyze(&mut self, program: &Program) -> Result<(), Vec<SemanticError>> {
+        let _span =
+            tracing::debug_span!("semantic", statements = program.statements.len()).entered();
+        self.features.extend(program.features.iter());
+
+        // Purity of every function, before function symbols are defined
+        self.infer_purity(program);
+
         // Pre-pass: Collect function and class names for forward references
         self.collect_forward_declarations(program);
 
@@ -56,9 +159,20 @@ impl SemanticAnalyzer {
             self.analyze_statement(statement);
         }
 
+        // Names that could be mistaken for other names
+        self.errors.extend(confusables::check_identifiers(&self.symbol_table));
+
+        tracing::debug!(errors = self.errors.len(), "semantic analysis finished");
+
         // Control flow analysis: Check for unreachable code, uninitialized variables, etc.
         if self.enable_control_flow {
             let mut control_flow = ControlFlowAnalyzer::new();
+            control_flow.set_features(self.features.clone());
+            for code in &self.enabled_lints {
+                control_flow.enable_lint(code);
+            }
+            control_flow.set_duplicate_min_nodes(self.duplicate_min_nodes);
+            control_flow.set_metric_limits(self.metric_limits);
             if let Err(control_flow_errors) = control_flow.analyze(program) {
                 // Merge control flow errors with existing errors
                 self.errors.extend(control_flow_errors);
@@ -73,11 +187,67 @@ impl SemanticAnalyzer {
         }
     }
 
+    /// Analyze a program and annotate it with the type of every expression
+    ///
+    /// Runs the same checks as `analyze`. The typed program is produced
+    /// even when there are errors, which it carries along, so tools can
+    /// still show types for code that does not check yet.
+    ///
+    /// The typed program takes the program, or shares it when given an
+    /// `Arc`; it is never copied.
+    pub fn analyze_typed(&mut self, program: impl Into<Arc<Program>>) -> TypedProgram {
+        let program = program.into();
+        self.expression_types = Some(ExpressionTypes::new());
+        let errors = self.analyze(&program).err().unwrap_or_default();
+        let types = self.expression_types.take().unwrap_or_default();
+        TypedProgram::new(program, types, errors)
+    }
+
     /// Get a reference to the symbol table (for testing)
     pub fn symbol_table(&self) -> &SymbolTable {
         &self.symbol_table
     }
 
+    /// Get the members collected for a class, by qualified name
+    pub fn class_info(&self, qualname: &str) -> Option<&ClassInfo> {
+        self.classes.get(qualname)
+    }
+
+    /// Get the members collected for the class a symbol defines
+    pub fn class_info_of(&self, symbol: &Symbol) -> Option<&ClassInfo> {
+        self.class_info(self.class_names.get(&symbol.span)?)
+    }
+
+    // ========== PRE-PASS: PURITY ==========
+
+    /// Infer which functions are pure, and report functions marked `@pure`
+    /// that are not
+    fn infer_purity(&mut self, program: &Program) {
+        self.purity = Purity::infer(program);
+        self.const_functions = program
+            .statements
+            .iter()
+            .filter_map(|statement| match &statement.kind {
+                StatementKind::FunctionDef { name, .. } if self.purity.is_pure_function(name) => {
+                    Some((name.clone(), ConstFunction::new(statement)?))
+                }
+                _ => None,
+            })
+            .collect();
+
+        for (name, span, impurity) in self.purity.violations() {
+            self.errors.push(SemanticError::ImpureFunction {
+                function_name: name.to_string(),
+                reason: impurity.reason.clone(),
+                reason_line: impurity.span.line,
+                reason_column: impurity.span.column,
+                line: span.line,
+                column: span.column,
+                span,
+            });
+        }
+    }
+
     // ========== PRE-PASS: FORWARD DECLARATIONS ==========
 
     /// Collect function and class names for forward references
@@ -90,58 +260,72 @@ impl SemanticAnalyzer {
                     returns,
                     ..
                 } => {
-                    // Collect parameter types
-                    let mut param_types = Vec::new();
-                    for param in &params.args {
-                        let param_type = if let Some(ann) = &param.annotation {
-                            self.resolve_type_annotation(ann)
-                        } else {
-                            crate::types::Type::Unknown
-                        };
-                        param_types.push((param.name.clone(), param_type));
-                    }
-
-                    // Resolve return type annotation if present
-                    let func_type = if let Some(return_type_ann) = returns {
-                        let return_type = self.resolve_type_annotation(return_type_ann);
-                        crate::types::Type::Function {
-                            params: Some(param_types),
-                            return_type: Box::new(return_type),
-                        }
-                    } else {
-                        // No return type annotation means Unknown
-                        crate::types::Type::Function {
-                            params: Some(param_types),
-                            return_type: Box::new(crate::types::Type::Unknown),
-                        }
-                    };
+                    let func_type = self.function_type(params, returns.as_ref());
 
-                    let func_symbol = Symbol::with_type(
+                    let mut func_symbol = Symbol::with_type(
                         name.clone(),
                         SymbolKind::Function,
                         statement.span,
                         func_type,
                     );
+                    func_symbol.pure = self.purity.is_pure(statement.span);
                     if let Err(err) = self.symbol_table.define_symbol(func_symbol) {
                         self.errors.push(err);
                     }
                 }
-                StatementKind::ClassDef { name, .. } => {
+                StatementKind::ClassDef {
+                    name, bases, body, ..
+                } => {
                     let class_symbol =
                         Symbol::new(name.clone(), SymbolKind::Class, statement.span);
                     if let Err(err) = self.symbol_table.define_symbol(class_symbol) {
                         self.errors.push(err);
                     }
+
+                    self.class_names.insert(statement.span, name.clone());
+                    let class_info = self.collect_class_info(name, bases, body, statement.span);
+                    self.classes.insert(name.clone(), class_info);
                 }
                 _ => {}
             }
         }
+
+        // Base classes may be forward references, so MROs are computed last
+        for statement in &program.statements {
+            if let StatementKind::ClassDef { name, .. } = &statement.kind {
+                self.compute_mro(name, &mut Vec::new());
+            }
+        }
+    }
+
+    /// Build a function type from its parameters and return annotation
+    fn function_type(
+        &self,
+        params: &silk_ast::FunctionParams,
+        returns: Option<&silk_ast::Type>,
+    ) -> crate::types::Type {
+        // Collect parameter kinds and types
+        let signature = Signature::from_params(params, |ann| self.resolve_type_annotation(ann));
+
+        // Resolve return type annotation if present (no annotation means Unknown)
+        let return_type = returns
+            .map(|ann| self.resolve_type_annotation(ann))
+            .unwrap_or(crate::types::Type::Unknown);
+
+        crate::types::Type::Function {
+            params: Some(signature),
+            return_type: Box::new(return_type),
+        }
     }
 
     // ========== MAIN PASS: SINGLE-PASS ANALYSIS ==========
 
     /// Analyze a statement: define symbols and validate references
     fn analyze_statement(&mut self, stmt: &Statement) {
+        ensure_sufficient_stack(|| self.analyze_statement_kind(stmt))
+    }
+
+    fn analyze_statement_kind(&mut self, stmt: &Statement) {
         match &stmt.kind {
             // Expression statement: validate the expression
             StatementKind::Expr(expr) => {
@@ -159,6 +343,7 @@ impl SemanticAnalyzer {
                 // Define the target variables
                 for target in targets {
                     if let ExpressionKind::Identifier(name) = &target.kind {
+                        self.check_variable_binding(name, target.span, Some(value));
                         let symbol = Symbol::with_type(
                             name.clone(),
                             SymbolKind::Variable,
@@ -168,6 +353,7 @@ impl SemanticAnalyzer {
                         if let Err(err) = self.symbol_table.define_symbol(symbol) {
                             self.errors.push(err);
                         }
+                        self.record_type(target, inferred_type.clone());
                     }
                     // TODO: Handle tuple unpacking, attribute assignment, etc.
                 }
@@ -197,10 +383,12 @@ impl SemanticAnalyzer {
 
                 // Define the target variable with annotated type
                 if let ExpressionKind::Identifier(name) = &target.kind {
+                    self.check_variable_binding(name, target.span, value.as_ref());
+                    self.record_type(target, annotated_type.clone());
                     let symbol = Symbol::with_type(
                         name.clone(),
                         SymbolKind::Variable,
-                        target.span.clone(),
+                        target.span,
                         annotated_type,
                     );
                     if let Err(err) = self.symbol_table.define_symbol(symbol) {
@@ -215,44 +403,72 @@ impl SemanticAnalyzer {
             StatementKind::AugAssign { target, value, .. } => {
                 // Check if variable exists (must be defined before use)
                 if let ExpressionKind::Identifier(name) = &target.kind {
-                    if self.symbol_table.resolve_symbol(name).is_none() {
+                    if !self.symbol_table.record_reference(name, target.span) {
                         self.errors.push(SemanticError::UndefinedVariable {
                             name: name.clone(),
+                            suggestion: self.similar_name(name),
                             line: target.span.line,
                             column: target.span.column,
-                            span: target.span.clone(),
+                            span: target.span,
                         });
                     }
+                } else {
+                    // `obj.attr += 1` and `items[i] += 1` read the target first
+                    self.analyze_expression(target);
                 }
                 self.analyze_expression(value);
-                // TODO: Handle attribute/subscript augmented assignment
             }
 
             // Function definition: already declared in pre-pass, now analyze body
             StatementKind::FunctionDef {
-                name: _,
+                name,
                 params,
                 body,
                 decorator_list,
                 returns,
                 ..
             } => {
+                // Methods receive the instance as their first parameter
+                let method_class = if self.symbol_table.current_scope_kind() == ScopeKind::Class {
+                    self.current_class.clone()
+                } else {
+                    None
+                };
+                let method_kind = MethodKind::from_decorators(name, decorator_list);
+
+                // Methods are visible in the class body (e.g. for `@x.setter`);
+                // property setters/deleters reuse the getter's name
+                if method_class.is_some()
+                    && !matches!(
+                        method_kind,
+                        MethodKind::PropertySetter | MethodKind::PropertyDeleter
+                    )
+                {
+                    let func_type = self.function_type(params, returns.as_ref());
+                    let mut func_symbol =
+                        Symbol::with_type(name.clone(), SymbolKind::Function, stmt.span, func_type);
+                    func_symbol.pure = self.purity.is_pure(stmt.span);
+                    if let Err(err) = self.symbol_table.define_symbol(func_symbol) {
+                        self.errors.push(err);
+                    }
+                }
+
+                let receiver_type = method_class
+                    .filter(|_| method_kind.receiver() == Some("self"))
+                    .map(crate::types::Type::Instance);
+
                 // Analyze decorators BEFORE entering scope (evaluated in outer scope)
                 for decorator in decorator_list {
-                    self.analyze_expression(decorator);
+                    self.analyze_decorator(decorator);
                 }
 
                 // Analyze parameter defaults BEFORE entering scope (evaluated in outer scope)
-                for param in &params.args {
-                    if let Some(default_expr) = &param.default {
-                        self.analyze_expression(default_expr);
-                    }
-                }
-                for param in &params.kwonlyargs {
+                for param in params.positional().chain(&params.kwonlyargs) {
                     if let Some(default_expr) = &param.default {
                         self.analyze_expression(default_expr);
                     }
                 }
+                self.check_parameter_defaults(params);
 
                 // Parameter types were already collected in pre-pass
                 // No need to update symbol again
@@ -266,26 +482,52 @@ impl SemanticAnalyzer {
                 let previous_return_type = self.current_function_return_type.replace(return_type);
 
                 // Enter function scope
-                self.symbol_table.enter_scope(ScopeKind::Function);
+                self.symbol_table.enter_scope_at(ScopeKind::Function, block_span(stmt, body));
 
                 // Define parameters
-                for param in &params.args {
-                    self.define_parameter(param);
+                for (i, param) in params.positional().enumerate() {
+                    match &receiver_type {
+                        Some(ty) if i == 0 => {
+                            let symbol = Symbol::with_type(
+                                param.name.clone(),
+                                SymbolKind::Parameter,
+                                param.span,
+                                ty.clone(),
+                            );
+                            if let Err(err) = self.symbol_table.define_symbol(symbol) {
+                                self.errors.push(err);
+                            }
+                        }
+                        _ => self.define_parameter(param, self.parameter_type(param)),
+                    }
                 }
                 if let Some(vararg) = &params.vararg {
-                    self.define_parameter(vararg);
+                    // There is no variadic tuple type yet
+                    self.define_parameter(vararg, crate::types::Type::Unknown);
                 }
                 for param in &params.kwonlyargs {
-                    self.define_parameter(param);
+                    self.define_parameter(param, self.parameter_type(param));
                 }
                 if let Some(kwarg) = &params.kwarg {
-                    self.define_parameter(kwarg);
+                    let ty = crate::types::Type::Dict {
+                        key_type: Box::new(crate::types::Type::Str),
+                        value_type: Box::new(self.parameter_type(kwarg)),
+                    };
+                    self.define_parameter(kwarg, ty);
                 }
 
-                // Analyze function body
+                // Analyze function body; narrowing from the enclosing code
+                // does not hold when the function runs later
+                let outer_narrowed = std::mem::replace(&mut self.narrowed, NarrowedTypes::new());
+                self.outer_declarations.push(HashSet::new());
+                let locals = format!("{}.<locals>", self.qualified_name(name));
+                self.enclosing_names.push(locals);
                 for stmt in body {
                     self.analyze_statement(stmt);
                 }
+                self.enclosing_names.pop();
+                self.outer_declarations.pop();
+                self.narrowed = outer_narrowed;
 
                 // Exit function scope
                 if let Err(err) = self.symbol_table.exit_scope() {
@@ -298,15 +540,15 @@ impl SemanticAnalyzer {
 
             // Class definition: already declared in pre-pass, now analyze body
             StatementKind::ClassDef {
+                name,
                 bases,
                 keywords,
                 decorator_list,
                 body,
-                ..
             } => {
                 // Analyze decorators BEFORE entering scope (evaluated in outer scope)
                 for decorator in decorator_list {
-                    self.analyze_expression(decorator);
+                    self.analyze_decorator(decorator);
                 }
 
                 // Analyze base classes BEFORE entering scope (evaluated in outer scope)
@@ -319,26 +561,68 @@ impl SemanticAnalyzer {
                     self.analyze_expression(&keyword.value);
                 }
 
+                // Nested classes are not seen by the pre-pass
+                if !self.class_names.contains_key(&stmt.span) {
+                    let class_symbol = Symbol::new(name.clone(), SymbolKind::Class, stmt.span);
+                    if let Err(err) = self.symbol_table.define_symbol(class_symbol) {
+                        self.errors.push(err);
+                    }
+
+                    let qualname = self.qualified_name(name);
+                    self.class_names.insert(stmt.span, qualname.clone());
+                    let class_info = self.collect_class_info(&qualname, bases, body, stmt.span);
+                    self.classes.insert(qualname.clone(), class_info);
+                    self.compute_mro(&qualname, &mut Vec::new());
+                }
+                let qualname = self.class_names[&stmt.span].clone();
+
                 // Enter class scope
-                self.symbol_table.enter_scope(ScopeKind::Class);
+                self.symbol_table.enter_scope_at(ScopeKind::Class, block_span(stmt, body));
+                let previous_class = self.current_class.replace(qualname.clone());
+                self.enclosing_names.push(qualname);
 
                 // Analyze class body
                 for stmt in body {
+                    if let StatementKind::FunctionDef {
+                        name,
+                        params,
+                        decorator_list,
+                        ..
+                    } = &stmt.kind
+                    {
+                        self.check_method_signature(name, params, decorator_list, stmt);
+                    }
                     self.analyze_statement(stmt);
                 }
 
                 // Exit class scope
+                self.enclosing_names.pop();
+                self.current_class = previous_class;
                 if let Err(err) = self.symbol_table.exit_scope() {
                     self.errors.push(err);
                 }
             }
 
-            // Import statement: define imported names
+            // Import statement: define imported names (`import a.b` binds `a`)
             StatementKind::Import { names } => {
                 for alias in names {
-                    let import_name = alias.asname.as_ref().unwrap_or(&alias.name);
-                    let symbol =
-                        Symbol::new(import_name.clone(), SymbolKind::Module, stmt.span.clone());
+                    // Modules with stubs are typed, so their members are
+                    // checked; `import os.path` binds `os`
+                    let module = match alias.asname {
+                        Some(_) => alias.name.as_str(),
+                        None => alias.bound_name(),
+                    };
+                    let ty = if stdlib::is_module(module) {
+                        crate::types::Type::Module(module.to_string())
+                    } else {
+                        crate::types::Type::Unknown
+                    };
+                    let symbol = Symbol::with_type(
+                        alias.bound_name().to_string(),
+                        SymbolKind::Module,
+                        alias.span,
+                        ty,
+                    );
                     if let Err(err) = self.symbol_table.define_symbol(symbol) {
                         self.errors.push(err);
                     }
@@ -346,11 +630,29 @@ impl SemanticAnalyzer {
             }
 
             // From...import statement: define imported names
-            StatementKind::ImportFrom { names, .. } => {
+            StatementKind::ImportFrom {
+                module,
+                names,
+                level,
+            } => {
+                let stubs = module
+                    .as_deref()
+                    .filter(|m| *level == 0 && stdlib::is_module(m));
                 for alias in names {
-                    let import_name = alias.asname.as_ref().unwrap_or(&alias.name);
-                    let symbol =
-                        Symbol::new(import_name.clone(), SymbolKind::Module, stmt.span.clone());
+                    // `from m import *` binds names we cannot see
+                    if alias.name == "*" {
+                        continue;
+                    }
+                    let ty = match stubs {
+                        Some(module) => self.module_member(module, &alias.name, alias.span),
+                        None => crate::types::Type::Unknown,
+                    };
+                    let symbol = Symbol::with_type(
+                        alias.bound_name().to_string(),
+                        SymbolKind::Module,
+                        alias.span,
+                        ty,
+                    );
                     if let Err(err) = self.symbol_table.define_symbol(symbol) {
                         self.errors.push(err);
                     }
@@ -360,27 +662,47 @@ impl SemanticAnalyzer {
             // Control flow: if statement
             StatementKind::If { test, body, orelse } => {
                 self.analyze_expression(test);
+
+                // Each branch sees the types its condition implies
+                let narrowing = self.condition_narrowing(test);
+                self.narrowed.push(&narrowing.then);
                 for stmt in body {
                     self.analyze_statement(stmt);
                 }
+                self.narrowed.pop();
+                self.narrowed.push(&narrowing.orelse);
                 for stmt in orelse {
                     self.analyze_statement(stmt);
                 }
+                self.narrowed.pop();
+
+                // Code after the `if` only runs via a branch that falls through
+                match (always_exits(body), always_exits(orelse)) {
+                    (true, false) => self.narrowed.refine(&narrowing.orelse),
+                    (false, true) => self.narrowed.refine(&narrowing.then),
+                    _ => {}
+                }
             }
 
             // Control flow: while loop
             StatementKind::While { test, body, orelse } => {
                 self.analyze_expression(test);
+                let narrowing = self.condition_narrowing(test);
 
                 self.symbol_table.enter_loop();
+                self.narrowed.push(&narrowing.then);
                 for stmt in body {
                     self.analyze_statement(stmt);
                 }
+                self.narrowed.pop();
                 self.symbol_table.exit_loop();
 
+                // `else` runs once the condition is false
+                self.narrowed.push(&narrowing.orelse);
                 for stmt in orelse {
                     self.analyze_statement(stmt);
                 }
+                self.narrowed.pop();
             }
 
             // Control flow: for loop
@@ -396,8 +718,9 @@ impl SemanticAnalyzer {
 
                 // Define loop variable
                 if let PatternKind::Name(name) = &target.kind {
+                    self.check_variable_binding(name, target.span, None);
                     let symbol =
-                        Symbol::new(name.clone(), SymbolKind::Variable, target.span.clone());
+                        Symbol::new(name.clone(), SymbolKind::Variable, target.span);
                     if let Err(err) = self.symbol_table.define_symbol(symbol) {
                         self.errors.push(err);
                     }
@@ -422,10 +745,11 @@ impl SemanticAnalyzer {
                     // Define context manager variable if present
                     if let Some(var_expr) = &item.optional_vars {
                         if let ExpressionKind::Identifier(name) = &var_expr.kind {
+                            self.check_variable_binding(name, var_expr.span, None);
                             let symbol = Symbol::new(
                                 name.clone(),
                                 SymbolKind::Variable,
-                                var_expr.span.clone(),
+                                var_expr.span,
                             );
                             if let Err(err) = self.symbol_table.define_symbol(symbol) {
                                 self.errors.push(err);
@@ -445,6 +769,7 @@ impl SemanticAnalyzer {
                 handlers,
                 orelse,
                 finalbody,
+                is_star,
             } => {
                 for stmt in body {
                     self.analyze_statement(stmt);
@@ -454,11 +779,14 @@ impl SemanticAnalyzer {
                     if let Some(type_expr) = &handler.typ {
                         self.analyze_expression(type_expr);
                     }
+                    if *is_star {
+                        self.check_except_star_body(&handler.body);
+                    }
 
                     // Define exception variable if present
                     if let Some(name) = &handler.name {
                         let symbol =
-                            Symbol::new(name.clone(), SymbolKind::Variable, stmt.span.clone());
+                            Symbol::new(name.clone(), SymbolKind::Variable, stmt.span);
                         if let Err(err) = self.symbol_table.define_symbol(symbol) {
                             self.errors.push(err);
                         }
@@ -483,7 +811,7 @@ impl SemanticAnalyzer {
                     self.errors.push(SemanticError::ReturnOutsideFunction {
                         line: stmt.span.line,
                         column: stmt.span.column,
-                        span: stmt.span.clone(),
+                        span: stmt.span,
                     });
                 }
                 if let Some(expr) = value {
@@ -503,7 +831,7 @@ impl SemanticAnalyzer {
                                 actual_type: "None".to_string(),
                                 line: stmt.span.line,
                                 column: stmt.span.column,
-                                span: stmt.span.clone(),
+                                span: stmt.span,
                             });
                         }
                     }
@@ -511,26 +839,24 @@ impl SemanticAnalyzer {
             }
 
             // Break statement: validate context
-            StatementKind::Break => {
-                if !self.symbol_table.in_loop() {
+            StatementKind::Break
+                if !self.symbol_table.in_loop() => {
                     self.errors.push(SemanticError::BreakOutsideLoop {
                         line: stmt.span.line,
                         column: stmt.span.column,
-                        span: stmt.span.clone(),
+                        span: stmt.span,
                     });
                 }
-            }
 
             // Continue statement: validate context
-            StatementKind::Continue => {
-                if !self.symbol_table.in_loop() {
+            StatementKind::Continue
+                if !self.symbol_table.in_loop() => {
                     self.errors.push(SemanticError::ContinueOutsideLoop {
                         line: stmt.span.line,
                         column: stmt.span.column,
-                        span: stmt.span.clone(),
+                        span: stmt.span,
                     });
                 }
-            }
 
             // Raise statement: validate expressions
             StatementKind::Raise { exc, cause } => {
@@ -545,9 +871,16 @@ impl SemanticAnalyzer {
             // Assert statement: validate expression
             StatementKind::Assert { test, msg } => {
                 self.analyze_expression(test);
+                let narrowing = self.condition_narrowing(test);
                 if let Some(expr) = msg {
+                    // The message is only evaluated when the test fails
+                    self.narrowed.push(&narrowing.orelse);
                     self.analyze_expression(expr);
+                    self.narrowed.pop();
                 }
+
+                // Code after the assert only runs when the test holds
+                self.narrowed.refine(&narrowing.then);
             }
 
             // Delete statement: validate targets
@@ -557,11 +890,21 @@ impl SemanticAnalyzer {
                 }
             }
 
-            // Match statement: validate subject, guards, and case bodies
+            // Match statement: validate subject, patterns, guards, and case bodies
             StatementKind::Match { subject, cases } => {
                 self.analyze_expression(subject);
                 for case in cases {
-                    // TODO: Handle pattern variable binding once pattern analysis is implemented
+                    // Value patterns and class names are looked up; captures bind
+                    for expr in case.pattern.expressions() {
+                        self.analyze_expression(expr);
+                    }
+                    for (name, span) in case.pattern.bound_names() {
+                        self.check_variable_binding(name, span, None);
+                        let symbol = Symbol::new(name.to_string(), SymbolKind::Variable, span);
+                        if let Err(err) = self.symbol_table.define_symbol(symbol) {
+                            self.errors.push(err);
+                        }
+                    }
                     if let Some(guard) = &case.guard {
                         self.analyze_expression(guard);
                     }
@@ -571,47 +914,152 @@ impl SemanticAnalyzer {
                 }
             }
 
-            // Other statements (Pass, Global, Nonlocal, etc.)
+            // Global/nonlocal declarations: later assignments bind the outer name
+            StatementKind::Global { names } | StatementKind::Nonlocal { names } => {
+                if let Some(declared) = self.outer_declarations.last_mut() {
+                    declared.extend(names.iter().cloned());
+                }
+            }
+
+            // Other statements (Pass, etc.)
             _ => {}
         }
     }
 
     /// Analyze an expression: validate all identifier references
     fn analyze_expression(&mut self, expr: &Expression) {
+        ensure_sufficient_stack(|| {
+            self.check_expression(expr);
+            if self.expression_types.is_some() {
+                let ty = self.infer_type_quietly(expr);
+                self.record_type(expr, ty);
+            }
+        })
+    }
+
+    /// Infer a type for a typed program; inference errors are already
+    /// reported by the analysis itself
+    /// Report `break`, `continue` and `return` that would leave an `except*`
+    /// handler
+    ///
+    /// An `except*` handler runs once per matching part of an exception
+    /// group, and the parts it does not handle are raised again after it, so
+    /// jumping out of it early is not allowed. Jumps inside a loop or a
+    /// function nested in the handler are fine.
+    fn check_except_star_body(&mut self, body: &[Statement]) {
+        let mut jumps = Vec::new();
+        except_star_jumps(body, false, &mut jumps);
+        for (statement, span) in jumps {
+            self.errors.push(SemanticError::JumpOutOfExceptStar {
+                statement: statement.to_string(),
+                line: span.line,
+                column: span.column,
+                span,
+            });
+        }
+    }
+
+    /// Analyze a decorator expression, which must evaluate to a callable
+    ///
+    /// Any expression may follow `@`, so a decorator is checked by its type
+    /// rather than its form: `@buttons[0].clicked.connect` is fine, `@1` is
+    /// not.
+    fn analyze_decorator(&mut self, decorator: &Expression) {
+        self.analyze_expression(decorator);
+        let ty = self.infer_type_quietly(decorator);
+        if !ty.is_callable() {
+            self.errors.push(SemanticError::TypeMismatch {
+                message: format!("a decorator must be callable, not '{}'", ty),
+                line: decorator.span.line,
+                column: decorator.span.column,
+                span: decorator.span,
+            });
+        }
+    }
+
+    fn infer_type_quietly(&mut self, expr: &Expression) -> crate::types::Type {
+        let errors = self.errors.len();
+        let ty = self.infer_type(expr);
+        self.errors.truncate(errors);
+        ty
+    }
+
+    /// Record the type of an expression when building a typed program
+    fn record_type(&mut self, expr: &Expression, ty: crate::types::Type) {
+        if let Some(types) = &mut self.expression_types {
+            types.insert(expr.span, ty);
+        }
+    }
+
+    fn check_expression(&mut self, expr: &Expression) {
         match &expr.kind {
             // Identifier: check if defined
-            ExpressionKind::Identifier(name) => {
-                if self.symbol_table.resolve_symbol(name).is_none()
-                    && !Self::is_builtin_function(name)
-                {
+            ExpressionKind::Identifier(name)
+                if !self.symbol_table.record_reference(name, expr.span)
+                    && !builtins::is_builtin(name)
+                => {
                     self.errors.push(SemanticError::UndefinedVariable {
                         name: name.clone(),
+                        suggestion: self.similar_name(name),
                         line: expr.span.line,
                         column: expr.span.column,
-                        span: expr.span.clone(),
+                        span: expr.span,
                     });
                 }
-            }
 
             // Binary operation
-            ExpressionKind::BinaryOp { left, right, .. } => {
+            ExpressionKind::BinaryOp { left, op, right } => {
                 self.analyze_expression(left);
                 self.analyze_expression(right);
+                self.check_constant_operation(left, *op, right, expr.span);
+                if *op == silk_ast::BinaryOperator::Mod {
+                    self.check_percent_format(left, right, expr.span);
+                }
             }
 
             // Unary operation
-            ExpressionKind::UnaryOp { operand, .. } => {
+            ExpressionKind::UnaryOp { op, operand } => {
                 self.analyze_expression(operand);
+                let folded = self
+                    .constant_number(operand)
+                    .and_then(|value| constants::fold_unary(*op, value));
+                if let Some(Err(FoldError::Overflow)) = folded {
+                    self.errors.push(SemanticError::IntegerOverflow {
+                        operator: "-".to_string(),
+                        line: expr.span.line,
+                        column: expr.span.column,
+                        span: expr.span,
+                    });
+                }
+            }
+
+            // Logical operation: the right operand only runs when the left
+            // one is truthy (`and`) or falsy (`or`)
+            ExpressionKind::LogicalOp { left, op, right } => {
+                self.analyze_expression(left);
+                let narrowing = self.condition_narrowing(left);
+                self.narrowed.push(match op {
+                    silk_ast::LogicalOperator::And => &narrowing.then,
+                    silk_ast::LogicalOperator::Or => &narrowing.orelse,
+                });
+                self.analyze_expression(right);
+                self.narrowed.pop();
             }
 
             // Comparison
             ExpressionKind::Compare {
-                left, comparators, ..
+                left,
+                ops,
+                comparators,
             } => {
                 self.analyze_expression(left);
                 for comp in comparators {
                     self.analyze_expression(comp);
                 }
+                let operands = std::iter::once(left.as_ref()).chain(comparators);
+                for ((left, op), right) in operands.clone().zip(ops).zip(operands.skip(1)) {
+                    self.check_self_comparison(left, *op, right, expr.span);
+                }
             }
 
             // Function call
@@ -621,40 +1069,69 @@ impl SemanticAnalyzer {
                 keywords,
             } => {
                 self.analyze_expression(func);
+                // A method call's receiver is checked as an attribute access
+                if !matches!(func.kind, ExpressionKind::Attribute { .. }) {
+                    self.check_optional_access(func, "call");
+                }
                 for arg in args {
                     self.analyze_expression(arg);
                 }
                 for keyword in keywords {
                     self.analyze_expression(&keyword.value);
                 }
+
+                // Zero-argument super() needs an enclosing class
+                if matches!(&func.kind, ExpressionKind::Identifier(name) if name == "super")
+                    && args.is_empty()
+                    && self.current_class.is_none()
+                {
+                    self.errors.push(SemanticError::SuperOutsideClass {
+                        line: expr.span.line,
+                        column: expr.span.column,
+                        span: expr.span,
+                    });
+                }
+
+                self.check_format_call(func, args, keywords, expr.span);
+                self.check_regex_call(func, args, keywords);
+
                 // Infer type to trigger function call type checking
                 self.infer_type(expr);
             }
 
             // Attribute access
-            ExpressionKind::Attribute { value, .. } => {
+            ExpressionKind::Attribute { value, attr } => {
                 self.analyze_expression(value);
+                self.check_optional_access(value, "attribute access");
+                self.check_attribute_exists(value, attr, expr);
             }
 
             // Subscript
             ExpressionKind::Subscript { value, index } => {
                 self.analyze_expression(value);
                 self.analyze_expression(index);
+                self.check_optional_access(value, "subscript");
 
                 // Validate subscript operation
                 let value_type = self.infer_type(value);
                 let index_type = self.infer_type(index);
                 if let Err(err) = self.validate_subscript(&value_type, &index_type, value, index) {
                     self.errors.push(err);
+                } else if matches!(value_type, crate::types::Type::Dict { .. }) {
+                    self.check_hashable(index, "dict key");
                 }
             }
 
             // Collections
-            ExpressionKind::List { elements }
-            | ExpressionKind::Tuple { elements }
-            | ExpressionKind::Set { elements } => {
+            ExpressionKind::List { elements } | ExpressionKind::Tuple { elements } => {
+                for elem in elements {
+                    self.analyze_expression(elem);
+                }
+            }
+            ExpressionKind::Set { elements } => {
                 for elem in elements {
                     self.analyze_expression(elem);
+                    self.check_hashable(elem, "set element");
                 }
             }
 
@@ -662,6 +1139,7 @@ impl SemanticAnalyzer {
             ExpressionKind::Dict { keys, values } => {
                 for key in keys {
                     self.analyze_expression(key);
+                    self.check_hashable(key, "dict key");
                 }
                 for value in values {
                     self.analyze_expression(value);
@@ -671,8 +1149,13 @@ impl SemanticAnalyzer {
             // Ternary/conditional expression
             ExpressionKind::IfExp { test, body, orelse } => {
                 self.analyze_expression(test);
+                let narrowing = self.condition_narrowing(test);
+                self.narrowed.push(&narrowing.then);
                 self.analyze_expression(body);
+                self.narrowed.pop();
+                self.narrowed.push(&narrowing.orelse);
                 self.analyze_expression(orelse);
+                self.narrowed.pop();
             }
 
             // Lambda expression
@@ -684,14 +1167,14 @@ impl SemanticAnalyzer {
                     }
                 }
 
-                self.symbol_table.enter_scope(ScopeKind::Function);
+                self.symbol_table.enter_scope_at(ScopeKind::Function, expr.span);
 
                 // Define lambda parameters
                 for param in params {
                     let param_symbol = Symbol::new(
                         param.name.clone(),
                         SymbolKind::Parameter,
-                        param.span.clone(),
+                        param.span,
                     );
                     if let Err(err) = self.symbol_table.define_symbol(param_symbol) {
                         self.errors.push(err);
@@ -715,7 +1198,8 @@ impl SemanticAnalyzer {
                 element,
                 generators,
             } => {
-                self.symbol_table.enter_scope(ScopeKind::Local);
+                self.symbol_table.enter_scope_at(ScopeKind::Comprehension, expr.span);
+                let mut filters = Vec::new();
 
                 // Process generators
                 for gen in generators {
@@ -730,7 +1214,7 @@ impl SemanticAnalyzer {
                         let symbol = Symbol::with_type(
                             name.clone(),
                             SymbolKind::Variable,
-                            gen.target.span.clone(),
+                            gen.target.span,
                             element_type,
                         );
                         let _ = self.symbol_table.define_symbol(symbol);
@@ -738,10 +1222,21 @@ impl SemanticAnalyzer {
 
                     for filter in &gen.ifs {
                         self.analyze_expression(filter);
+                        filters.push(self.condition_narrowing(filter));
                     }
                 }
 
+                // The element is only computed for items every filter accepts
+                for narrowing in &filters {
+                    self.narrowed.push(&narrowing.then);
+                }
                 self.analyze_expression(element);
+                if matches!(expr.kind, ExpressionKind::SetComp { .. }) {
+                    self.check_hashable(element, "set element");
+                }
+                for _ in &filters {
+                    self.narrowed.pop();
+                }
                 let _ = self.symbol_table.exit_scope();
             }
 
@@ -751,7 +1246,8 @@ impl SemanticAnalyzer {
                 value,
                 generators,
             } => {
-                self.symbol_table.enter_scope(ScopeKind::Local);
+                self.symbol_table.enter_scope_at(ScopeKind::Comprehension, expr.span);
+                let mut filters = Vec::new();
 
                 for gen in generators {
                     self.analyze_expression(&gen.iter);
@@ -765,7 +1261,7 @@ impl SemanticAnalyzer {
                         let symbol = Symbol::with_type(
                             name.clone(),
                             SymbolKind::Variable,
-                            gen.target.span.clone(),
+                            gen.target.span,
                             element_type,
                         );
                         let _ = self.symbol_table.define_symbol(symbol);
@@ -773,11 +1269,19 @@ impl SemanticAnalyzer {
 
                     for filter in &gen.ifs {
                         self.analyze_expression(filter);
+                        filters.push(self.condition_narrowing(filter));
                     }
                 }
 
+                for narrowing in &filters {
+                    self.narrowed.push(&narrowing.then);
+                }
                 self.analyze_expression(key);
+                self.check_hashable(key, "dict key");
                 self.analyze_expression(value);
+                for _ in &filters {
+                    self.narrowed.pop();
+                }
                 let _ = self.symbol_table.exit_scope();
             }
 
@@ -790,13 +1294,23 @@ impl SemanticAnalyzer {
 
                 // Define the target variable
                 if let ExpressionKind::Identifier(name) = &target.kind {
+                    self.check_variable_binding(name, target.span, Some(value));
                     let symbol = Symbol::with_type(
                         name.clone(),
                         SymbolKind::Variable,
-                        target.span.clone(),
+                        target.span,
                         inferred_type,
                     );
-                    if let Err(err) = self.symbol_table.define_symbol(symbol) {
+                    // With comprehension-assignments, walrus targets escape the
+                    // comprehension and bind in the enclosing scope (PEP 572)
+                    let result = if self.features.is_enabled(Feature::ComprehensionAssignments)
+                        && self.symbol_table.current_scope_kind() == ScopeKind::Comprehension
+                    {
+                        self.symbol_table.define_symbol_in_enclosing_scope(symbol)
+                    } else {
+                        self.symbol_table.define_symbol(symbol)
+                    };
+                    if let Err(err) = result {
                         self.errors.push(err);
                     }
                 }
@@ -812,6 +1326,10 @@ impl SemanticAnalyzer {
     /// Returns the inferred type based on the expression kind.
     /// For now, this handles simple cases like literals.
     fn infer_type(&mut self, expr: &Expression) -> crate::types::Type {
+        ensure_sufficient_stack(|| self.infer_expression_type(expr))
+    }
+
+    fn infer_expression_type(&mut self, expr: &Expression) -> crate::types::Type {
         use crate::types::Type;
 
         match &expr.kind {
@@ -821,17 +1339,14 @@ impl SemanticAnalyzer {
             ExpressionKind::String(_)
             | ExpressionKind::RawString(_)
             | ExpressionKind::FString { .. } => Type::Str,
+            ExpressionKind::ByteString(_) | ExpressionKind::ByteRawString(_) => Type::Bytes,
             ExpressionKind::Boolean(_) => Type::Bool,
             ExpressionKind::None => Type::None,
 
             // For identifiers, look up their type in the symbol table
-            ExpressionKind::Identifier(name) => {
-                if let Some(symbol) = self.symbol_table.resolve_symbol(name) {
-                    symbol.ty.clone()
-                } else {
-                    Type::Unknown
-                }
-            }
+            ExpressionKind::Identifier(name) => self
+                .variable_type(name)
+                .map_or(Type::Unknown, |(ty, _)| ty),
 
             // Binary operations
             ExpressionKind::BinaryOp { left, op, right } => {
@@ -863,20 +1378,15 @@ impl SemanticAnalyzer {
             ExpressionKind::Tuple { elements } => self.infer_tuple_type(elements),
 
             // Subscript operations
-            ExpressionKind::Subscript { value, .. } => self.infer_subscript_type(value),
+            ExpressionKind::Subscript { value, index } => self.infer_subscript_type(value, index),
 
-            // Attribute access
-            ExpressionKind::Attribute { value, .. } => {
-                // For now, return Unknown for attribute access
-                // TODO: Implement proper attribute type resolution
-                let _ = self.infer_type(value);
-                Type::Unknown
-            }
+            // Attribute access (methods and properties of known classes)
+            ExpressionKind::Attribute { value, attr } => self.infer_attribute_type(value, attr),
 
             // Comprehensions
             ExpressionKind::ListComp { element, generators } => {
                 // Enter comprehension scope and define generator variables
-                self.symbol_table.enter_scope(ScopeKind::Local);
+                self.symbol_table.enter_scope(ScopeKind::Comprehension);
                 
                 for gen in generators {
                     let iter_type = self.infer_type(&gen.iter);
@@ -886,7 +1396,7 @@ impl SemanticAnalyzer {
                         let symbol = Symbol::with_type(
                             name.clone(),
                             SymbolKind::Variable,
-                            gen.target.span.clone(),
+                            gen.target.span,
                             element_type,
                         );
                         let _ = self.symbol_table.define_symbol(symbol);
@@ -902,7 +1412,7 @@ impl SemanticAnalyzer {
 
             ExpressionKind::SetComp { element, generators } => {
                 // Enter comprehension scope and define generator variables
-                self.symbol_table.enter_scope(ScopeKind::Local);
+                self.symbol_table.enter_scope(ScopeKind::Comprehension);
                 
                 for gen in generators {
                     let iter_type = self.infer_type(&gen.iter);
@@ -912,7 +1422,7 @@ impl SemanticAnalyzer {
                         let symbol = Symbol::with_type(
                             name.clone(),
                             SymbolKind::Variable,
-                            gen.target.span.clone(),
+                            gen.target.span,
                             element_type,
                         );
                         let _ = self.symbol_table.define_symbol(symbol);
@@ -928,7 +1438,7 @@ impl SemanticAnalyzer {
 
             ExpressionKind::DictComp { key, value, generators } => {
                 // Enter comprehension scope and define generator variables
-                self.symbol_table.enter_scope(ScopeKind::Local);
+                self.symbol_table.enter_scope(ScopeKind::Comprehension);
                 
                 for gen in generators {
                     let iter_type = self.infer_type(&gen.iter);
@@ -938,7 +1448,7 @@ impl SemanticAnalyzer {
                         let symbol = Symbol::with_type(
                             name.clone(),
                             SymbolKind::Variable,
-                            gen.target.span.clone(),
+                            gen.target.span,
                             element_type,
                         );
                         let _ = self.symbol_table.define_symbol(symbol);
@@ -991,10 +1501,14 @@ impl SemanticAnalyzer {
                     (Type::Int, Type::Float) | (Type::Float, Type::Int) => Type::Float,
                     // String + String = String
                     (Type::Str, Type::Str) => Type::Str,
+                    // Bytes + Bytes = Bytes
+                    (Type::Bytes, Type::Bytes) => Type::Bytes,
                     // Unknown for other combinations
                     _ => Type::Unknown,
                 }
             }
+            // printf-style formatting: str % values = str
+            BinaryOperator::Mod if left_type == Type::Str => Type::Str,
             BinaryOperator::Sub
             | BinaryOperator::Mult
             | BinaryOperator::Div
@@ -1021,6 +1535,8 @@ impl SemanticAnalyzer {
                 (Type::Int, Type::Int) => Type::Int,
                 _ => Type::Unknown,
             },
+            // Matrix multiplication is only defined by user types (__matmul__)
+            BinaryOperator::MatMult => Type::Unknown,
         }
     }
 
@@ -1102,7 +1618,7 @@ impl SemanticAnalyzer {
         &mut self,
         func: &Expression,
         args: &[Expression],
-        _keywords: &[silk_ast::CallKeyword],
+        keywords: &[silk_ast::CallKeyword],
     ) -> crate::types::Type {
         use crate::types::Type;
 
@@ -1117,6 +1633,11 @@ impl SemanticAnalyzer {
                                 params,
                                 return_type,
                             } => (params.clone(), return_type.as_ref().clone()),
+                            // Calling a class constructs an instance
+                            _ if symbol.kind == SymbolKind::Class => {
+                                let class_name = self.class_names.get(&symbol.span);
+                                return Type::Instance(class_name.unwrap_or(func_name).clone());
+                            }
                             _ => {
                                 // Not a function (e.g., calling an integer or string)
                                 return Type::Unknown;
@@ -1128,9 +1649,9 @@ impl SemanticAnalyzer {
                     };
 
                 // If we got function info, validate the call
-                if let Some(param_list) = params_opt {
+                if let Some(signature) = params_opt {
                     if let Err(err) =
-                        self.check_function_call_types(func_name, &param_list, args, func)
+                        self.check_function_call_types(func_name, &signature, args, keywords, func)
                     {
                         self.errors.push(err);
                     }
@@ -1148,6 +1669,7 @@ impl SemanticAnalyzer {
                     // Built-ins with fixed return types
                     "len" => Type::Int,
                     "str" => Type::Str,
+                    "bytes" => Type::Bytes,
                     "int" => Type::Int,
                     "float" => Type::Float,
                     "bool" => Type::Bool,
@@ -1186,81 +1708,20 @@ impl SemanticAnalyzer {
                 }
             }
 
-            // Method calls: obj.method()
+            // Method calls: obj.method(), Class.static_method()
+            ExpressionKind::Attribute { value, attr } => {
+                self.infer_method_call_type(value, attr, args, keywords, func)
+            }
+
             // Attribute access calls: module.function()
             // Lambda calls: (lambda x: x)(5)
             // TODO: Implement these call patterns:
-            // - Method calls require attribute type system and class method lookup
             // - Attribute calls need module/attribute type tracking
             // - Lambda calls need lambda expression return type inference
             _ => Type::Unknown,
         }
     }
 
-    /// Check if a name is a built-in function
-    ///
-    /// Returns true for Python built-in functions that don't need to be defined.
-    fn is_builtin_function(name: &str) -> bool {
-        matches!(
-            name,
-            "len"
-                | "str"
-                | "int"
-                | "float"
-                | "bool"
-                | "print"
-                | "input"
-                | "abs"
-                | "min"
-                | "max"
-                | "sum"
-                | "list"
-                | "dict"
-                | "set"
-                | "tuple"
-                | "range"
-                | "type"
-                | "isinstance"
-                | "issubclass"
-                | "chr"
-                | "ord"
-                | "hex"
-                | "oct"
-                | "bin"
-                | "round"
-                | "pow"
-                | "divmod"
-                | "all"
-                | "any"
-                | "enumerate"
-                | "filter"
-                | "map"
-                | "zip"
-                | "sorted"
-                | "reversed"
-                | "iter"
-                | "next"
-                | "open"
-                | "help"
-                | "dir"
-                | "vars"
-                | "globals"
-                | "locals"
-                | "eval"
-                | "exec"
-                | "compile"
-                | "getattr"
-                | "setattr"
-                | "hasattr"
-                | "delattr"
-                | "id"
-                | "hash"
-                | "repr"
-                | "ascii"
-                | "format"
-        )
-    }
-
     /// Infer type for list literals
     ///
     /// Analyzes all elements in the list and determines the common element type.
@@ -1464,8 +1925,10 @@ impl SemanticAnalyzer {
 
         match &type_ann.kind {
             silk_ast::TypeKind::Name(name) => {
-                // Try to parse as built-in type
-                Type::from_str(name).unwrap_or(Type::Unknown)
+                // Try to parse as built-in type, then as a user-defined class
+                Type::from_str(name)
+                    .or_else(|| self.class_named(name).map(Type::Instance))
+                    .unwrap_or(Type::Unknown)
             }
 
             silk_ast::TypeKind::Generic { base, args } => {
@@ -1510,6 +1973,12 @@ impl SemanticAnalyzer {
                                 .collect();
                             Type::Tuple(element_types)
                         }
+                        "optional" if args.len() == 1 => {
+                            Type::optional(self.resolve_type_annotation(&args[0]))
+                        }
+                        "union" => {
+                            Type::union(args.iter().map(|t| self.resolve_type_annotation(t)))
+                        }
                         _ => Type::Unknown,
                     }
                 } else {
@@ -1517,103 +1986,1299 @@ impl SemanticAnalyzer {
                 }
             }
 
+            // int | str, Union[...] and Optional[...]
+            silk_ast::TypeKind::Union { types } => {
+                Type::union(types.iter().map(|t| self.resolve_type_annotation(t)))
+            }
+            silk_ast::TypeKind::Optional { inner } => {
+                Type::optional(self.resolve_type_annotation(inner))
+            }
+            silk_ast::TypeKind::None => Type::None,
+            silk_ast::TypeKind::Any => Type::Any,
+
             // For now, other complex types return Unknown
-            // TODO: Handle Union, Optional, Callable, etc.
+            // TODO: Handle Callable, Literal, etc.
             _ => Type::Unknown,
         }
     }
 
-    /// Define a function parameter
-    fn define_parameter(&mut self, arg: &silk_ast::FunctionArg) {
-        let param_symbol = Symbol::new(arg.name.clone(), SymbolKind::Parameter, arg.span);
-        if let Err(err) = self.symbol_table.define_symbol(param_symbol) {
-            self.errors.push(err);
-        }
+    // ========== TYPE NARROWING ==========
+
+    /// Current type of a variable (narrowed if a condition refined it) and
+    /// the span of its binding
+    fn variable_type(&self, name: &str) -> Option<(crate::types::Type, silk_lexer::Span)> {
+        let symbol = self.symbol_table.resolve_symbol(name)?;
+        let ty = self
+            .narrowed
+            .get(name, symbol.span)
+            .unwrap_or(&symbol.ty)
+            .clone();
+        Some((ty, symbol.span))
     }
 
-    /// Check type compatibility for annotated assignments
-    ///
-    /// Validates that the value type is compatible with the declared type annotation.
-    /// Uses Type::is_compatible_with() for the check, which supports:
-    /// - Exact type matches (int = int, str = str)
-    /// - Numeric type compatibility (int can be assigned to float)
-    /// - Unknown type (always compatible - gradual typing)
-    /// - Collection type structural compatibility
+    /// Types refined by a condition in the branches it guards
     ///
-    /// Returns Ok(()) if compatible, Err(SemanticError) if not.
-    fn check_assignment_type(
-        &self,
-        expected_type: &crate::types::Type,
-        value_type: &crate::types::Type,
-        _target: &Expression,
-        value: &Expression,
-    ) -> Result<(), SemanticError> {
-        // Check if value type is compatible with expected type
-        if !value_type.is_compatible_with(expected_type) {
-            return Err(SemanticError::AssignmentTypeMismatch {
-                expected_type: expected_type.to_string(),
-                value_type: value_type.to_string(),
-                line: value.span.line,
-                column: value.span.column,
-                span: value.span,
-            });
-        }
-
-        Ok(())
+    /// Recognizes `isinstance(x, T)`, `x is None`, `x is not None`,
+    /// `x == None`, `x != None`, truthiness of `x`, and `not`/`and`/`or`
+    /// combinations of these.
+    fn condition_narrowing(&self, test: &Expression) -> Narrowing {
+        ensure_sufficient_stack(|| self.condition_narrowing_kind(test))
     }
 
-    /// Check function call argument types and count
-    ///
-    /// Validates that:
-    /// 1. Argument count matches parameter count
-    /// 2. Each argument type is compatible with its corresponding parameter type
-    ///
-    /// Returns Ok(()) if valid, Err(SemanticError) if not.
-    fn check_function_call_types(
-        &mut self,
-        func_name: &str,
-        params: &[(String, crate::types::Type)],
-        args: &[Expression],
-        func_expr: &Expression,
-    ) -> Result<(), SemanticError> {
-        // Check argument count
-        if args.len() != params.len() {
-            return Err(SemanticError::ArgumentCountMismatch {
-                function_name: func_name.to_string(),
-                expected: params.len(),
-                actual: args.len(),
-                line: func_expr.span.line,
-                column: func_expr.span.column,
-                span: func_expr.span,
-            });
-        }
+    fn condition_narrowing_kind(&self, test: &Expression) -> Narrowing {
+        use crate::types::Type;
+        use silk_ast::{CompareOperator, LogicalOperator, UnaryOperator};
 
-        // Check each argument type
-        for (i, (arg, (param_name, param_type))) in args.iter().zip(params.iter()).enumerate() {
-            let arg_type = self.infer_type(arg);
+        match &test.kind {
+            ExpressionKind::UnaryOp {
+                op: UnaryOperator::Not,
+                operand,
+            } => self.condition_narrowing(operand).negate(),
 
-            if !arg_type.is_compatible_with(param_type) {
-                return Err(SemanticError::ArgumentTypeMismatch {
-                    param_name: param_name.clone(),
-                    arg_index: i + 1,
-                    expected_type: param_type.to_string(),
-                    actual_type: arg_type.to_string(),
-                    line: arg.span.line,
-                    column: arg.span.column,
-                    span: arg.span,
+            ExpressionKind::LogicalOp { left, op, right } => {
+                let left = self.condition_narrowing(left);
+                let right = self.condition_narrowing(right);
+                match op {
+                    LogicalOperator::And => left.and(right),
+                    LogicalOperator::Or => left.or(right),
+                }
+            }
+
+            // `(x := value)` narrows `x`
+            ExpressionKind::NamedExpr { target, .. } => self.condition_narrowing(target),
+
+            // A truthy value is not None
+            ExpressionKind::Identifier(name) => self.narrow_variable(name, |declared| {
+                let not_none = filter_members(declared, declared.clone(), |m| *m != Type::None);
+                (not_none, declared.clone())
+            }),
+
+            ExpressionKind::Compare {
+                left,
+                ops,
+                comparators,
+            } if ops.len() == 1 && matches!(comparators[0].kind, ExpressionKind::None) => {
+                let Some(name) = narrowed_name(left) else {
+                    return Narrowing::default();
+                };
+                let is_none = self.narrow_variable(name, |declared| {
+                    (
+                        filter_members(declared, Type::None, |m| *m == Type::None),
+                        filter_members(declared, declared.clone(), |m| *m != Type::None),
+                    )
                 });
+                match ops[0] {
+                    CompareOperator::Is | CompareOperator::Eq => is_none,
+                    CompareOperator::IsNot | CompareOperator::NotEq => is_none.negate(),
+                    _ => Narrowing::default(),
+                }
             }
-        }
 
-        Ok(())
+            ExpressionKind::Call {
+                func,
+                args,
+                keywords,
+            } if matches!(&func.kind, ExpressionKind::Identifier(f) if f == "isinstance")
+                && args.len() == 2
+                && keywords.is_empty() =>
+            {
+                let (Some(name), Some(class)) =
+                    (narrowed_name(&args[0]), self.classinfo_type(&args[1]))
+                else {
+                    return Narrowing::default();
+                };
+                self.narrow_variable(name, |declared| {
+                    (
+                        filter_members(declared, class.clone(), |m| self.is_instance_of(m, &class)),
+                        filter_members(declared, declared.clone(), |m| {
+                            !self.is_instance_of(m, &class)
+                        }),
+                    )
+                })
+            }
+
+            _ => Narrowing::default(),
+        }
     }
 
-    /// Check return statement type against declared function return type
+    /// Report an attribute access, subscript or call on `value` when its
+    /// type is a union that includes None
     ///
-    /// Validates that the return value type is compatible with the declared
-    /// return type of the current function.
-    ///
-    /// Returns Ok(()) if valid, Err(SemanticError) if not.
+    /// Attribute chains such as `self.conn` are not narrowed, so they are
+    /// not reported either.
+    fn check_optional_access(&mut self, value: &Expression, operation: &str) {
+        use crate::types::Type;
+
+        // Read declared types directly: inferring a call's type would
+        // check its arguments a second time
+        let (target, ty) = match &value.kind {
+            ExpressionKind::Identifier(name) => match self.variable_type(name) {
+                Some((ty, _)) => (name.clone(), ty),
+                None => return,
+            },
+            ExpressionKind::Call { func, .. } => match &func.kind {
+                ExpressionKind::Identifier(name) => match self.variable_type(name) {
+                    Some((Type::Function { return_type, .. }, _)) => {
+                        (format!("{}()", name), *return_type)
+                    }
+                    _ => return,
+                },
+                _ => return,
+            },
+            _ => return,
+        };
+
+        if !matches!(ty, Type::Union(_)) || !ty.includes_none() {
+            return;
+        }
+
+        let suggestion = match &value.kind {
+            ExpressionKind::Identifier(name) => format!("add an `if {} is not None` check", name),
+            _ => "assign it to a variable and check that it is not None".to_string(),
+        };
+        self.errors.push(SemanticError::OptionalAccess {
+            target,
+            operation: operation.to_string(),
+            value_type: ty.to_string(),
+            suggestion,
+            line: value.span.line,
+            column: value.span.column,
+            span: value.span,
+        });
+    }
+
+    /// Narrow `name` to the (then, else) types `refine` derives from its
+    /// current type
+    fn narrow_variable(
+        &self,
+        name: &str,
+        refine: impl FnOnce(&crate::types::Type) -> (crate::types::Type, crate::types::Type),
+    ) -> Narrowing {
+        let Some((declared, binding)) = self.variable_type(name) else {
+            return Narrowing::default();
+        };
+        let (then, orelse) = refine(&declared);
+        let var = |ty| NarrowedVar {
+            name: name.to_string(),
+            ty,
+            binding,
+        };
+        Narrowing {
+            then: vec![var(then)],
+            orelse: vec![var(orelse)],
+        }
+    }
+
+    /// The type `isinstance` tests against: a class or a tuple of classes
+    fn classinfo_type(&self, expr: &Expression) -> Option<crate::types::Type> {
+        use crate::types::Type;
+
+        match &expr.kind {
+            ExpressionKind::Identifier(name) => match self.class_named(name) {
+                Some(class_name) => Some(Type::Instance(class_name)),
+                None => Type::from_str(name),
+            },
+            ExpressionKind::Tuple { elements } => elements
+                .iter()
+                .map(|element| self.classinfo_type(element))
+                .collect::<Option<Vec<_>>>()
+                .map(Type::union),
+            _ => None,
+        }
+    }
+
+    /// Check if every value of type `ty` passes `isinstance(value, class)`
+    fn is_instance_of(&self, ty: &crate::types::Type, class: &crate::types::Type) -> bool {
+        use crate::types::Type;
+
+        class.members().iter().any(|class| match (ty, class) {
+            (Type::Instance(value_class), Type::Instance(class)) => {
+                self.mro_of(value_class).contains(class)
+            }
+            // bool is a subclass of int
+            (Type::Bool, Type::Int) => true,
+            _ => std::mem::discriminant(ty) == std::mem::discriminant(class),
+        })
+    }
+
+    // ========== CLASS MEMBERS ==========
+
+    /// Collect the base classes and methods declared directly in a class body
+    fn collect_class_info(
+        &self,
+        qualname: &str,
+        bases: &[Expression],
+        body: &[Statement],
+        span: silk_lexer::Span,
+    ) -> ClassInfo {
+        let mut class_info = ClassInfo::new(qualname.to_string(), span);
+
+        // Only simple names take part in the MRO (`object` is implicit)
+        class_info.unresolved_bases = bases
+            .iter()
+            .any(|base| !matches!(base.kind, ExpressionKind::Identifier(_)));
+        class_info.bases = bases
+            .iter()
+            .filter_map(|base| match &base.kind {
+                ExpressionKind::Identifier(base_name) if base_name != "object" => Some(base_name),
+                _ => None,
+            })
+            .map(|base_name| {
+                self.class_named(base_name)
+                    .unwrap_or_else(|| base_name.clone())
+            })
+            .collect();
+
+        for stmt in body {
+            if let StatementKind::FunctionDef {
+                name,
+                params,
+                returns,
+                decorator_list,
+                body,
+                ..
+            } = &stmt.kind
+            {
+                let kind = MethodKind::from_decorators(name, decorator_list);
+                if kind.receiver().is_some() {
+                    if let Some(receiver) = params.positional().next() {
+                        class_info.add_receiver_attributes(&receiver.name, body);
+                    }
+                }
+                let signature =
+                    Signature::from_params(params, |ann| self.resolve_type_annotation(ann));
+                let method_params = if kind.receiver().is_some() {
+                    signature.without_receiver()
+                } else {
+                    signature
+                };
+                let return_type = returns
+                    .as_ref()
+                    .map(|ann| self.resolve_type_annotation(ann))
+                    .unwrap_or(crate::types::Type::Unknown);
+
+                class_info.add_method(MethodInfo {
+                    name: name.clone(),
+                    kind,
+                    params: method_params,
+                    return_type,
+                    span: stmt.span,
+                });
+            } else {
+                class_info.add_class_variables(stmt);
+            }
+        }
+
+        class_info
+    }
+
+    /// Compute (and cache) the C3 method resolution order of a class
+    ///
+    /// Classes that are not user-defined (e.g. `Exception`) linearize to
+    /// themselves. Circular or inconsistent hierarchies are reported as
+    /// `InconsistentMro` and fall back to the class followed by its bases.
+    fn compute_mro(&mut self, name: &str, visiting: &mut Vec<String>) -> Vec<String> {
+        let (bases, span) = match self.classes.get(name) {
+            Some(info) if !info.mro.is_empty() => return info.mro.clone(),
+            Some(info) => (info.bases.clone(), info.span),
+            None => return vec![name.to_string()],
+        };
+
+        // Circular inheritance: the class that closes the cycle reports it
+        if visiting.iter().any(|v| v == name) {
+            return vec![name.to_string()];
+        }
+
+        visiting.push(name.to_string());
+        let base_mros: Vec<Vec<String>> = bases
+            .iter()
+            .map(|base| self.compute_mro(base, visiting))
+            .collect();
+        visiting.pop();
+
+        let circular = base_mros.iter().any(|mro| mro.iter().any(|c| c == name));
+        let mro = match c3_linearization(name, &bases, base_mros).filter(|_| !circular) {
+            Some(mro) => mro,
+            None => {
+                self.errors.push(SemanticError::InconsistentMro {
+                    class_name: short_name(name).to_string(),
+                    line: span.line,
+                    column: span.column,
+                    span,
+                });
+                std::iter::once(name.to_string()).chain(bases).collect()
+            }
+        };
+
+        if let Some(info) = self.classes.get_mut(name) {
+            info.mro = mro.clone();
+        }
+        mro
+    }
+
+    /// Report a dict key or set element whose type can never be hashed
+    fn check_hashable(&mut self, expr: &Expression, context: &'static str) {
+        let ty = self.infer_type_quietly(expr);
+        if self.is_unhashable(&ty) {
+            self.errors.push(SemanticError::UnhashableType {
+                type_name: ty.to_string(),
+                context,
+                line: expr.span.line,
+                column: expr.span.column,
+                span: expr.span,
+            });
+        }
+    }
+
+    /// Whether no value of a type can be hashed: lists, dicts and sets,
+    /// tuples holding one, and instances of classes that define `__eq__`
+    /// without `__hash__` (which sets `__hash__` to None)
+    fn is_unhashable(&self, ty: &crate::types::Type) -> bool {
+        use crate::types::Type;
+
+        match ty {
+            Type::List(_) | Type::Dict { .. } | Type::Set(_) => true,
+            Type::Tuple(elements) => elements.iter().any(|ty| self.is_unhashable(ty)),
+            Type::Union(members) => members.iter().all(|ty| self.is_unhashable(ty)),
+            Type::Instance(class_name) => {
+                for name in self.mro_of(class_name) {
+                    let Some(class) = self.classes.get(&name) else {
+                        return false;
+                    };
+                    if class.has_member("__hash__") {
+                        return false;
+                    }
+                    if class.has_member("__eq__") {
+                        return true;
+                    }
+                    if class.unresolved_bases {
+                        return false;
+                    }
+                }
+                false
+            }
+            _ => false,
+        }
+    }
+
+    /// Method resolution order of a class (just the class if unknown)
+    fn mro_of(&self, class_name: &str) -> Vec<String> {
+        match self.classes.get(class_name) {
+            Some(info) if !info.mro.is_empty() => info.mro.clone(),
+            _ => vec![class_name.to_string()],
+        }
+    }
+
+    /// Find a method by walking a method resolution order
+    fn lookup_method(&self, mro: &[String], attr: &str) -> Option<MethodInfo> {
+        mro.iter()
+            .filter_map(|class_name| self.classes.get(class_name))
+            .find_map(|info| info.method(attr))
+            .cloned()
+    }
+
+    /// Check assignability, treating instances of subclasses as their bases
+    fn is_assignable(
+        &self,
+        value_type: &crate::types::Type,
+        expected_type: &crate::types::Type,
+    ) -> bool {
+        use crate::types::Type;
+
+        match (value_type, expected_type) {
+            (Type::Union(members), _) => members
+                .iter()
+                .all(|member| self.is_assignable(member, expected_type)),
+            (_, Type::Union(members)) => members
+                .iter()
+                .any(|member| self.is_assignable(value_type, member)),
+            (Type::Instance(value_class), Type::Instance(expected_class)) => self
+                .mro_of(value_class)
+                .iter()
+                .any(|class_name| class_name == expected_class),
+            _ => value_type.is_compatible_with(expected_type),
+        }
+    }
+
+    /// Resolve the receiver of an attribute access
+    ///
+    /// Returns the receiver's class name, the classes to search (in MRO
+    /// order), and whether the receiver is an instance rather than a class.
+    /// `super()` searches the MRO of the enclosing class after the class itself.
+    /// Any other receiver gives back its type, so that a chain such as
+    /// `s.strip().lower()` is not inferred twice at every link.
+    fn resolve_receiver(
+        &mut self,
+        value: &Expression,
+    ) -> Result<(String, Vec<String>, bool), crate::types::Type> {
+        use crate::types::Type;
+
+        if let ExpressionKind::Call { func, args, .. } = &value.kind {
+            if matches!(&func.kind, ExpressionKind::Identifier(name) if name == "super") {
+                let class_name = match args.first().map(|arg| &arg.kind) {
+                    Some(ExpressionKind::Identifier(class_name)) => self
+                        .class_named(class_name)
+                        .unwrap_or_else(|| class_name.clone()),
+                    _ => self.current_class.clone().ok_or(Type::Unknown)?,
+                };
+                let mro = self.mro_of(&class_name).into_iter().skip(1).collect();
+                return Ok((class_name, mro, true));
+            }
+        }
+
+        match self.infer_type(value) {
+            Type::Instance(class_name) => {
+                let mro = self.mro_of(&class_name);
+                Ok((class_name, mro, true))
+            }
+            ty => {
+                let class_name = self.class_of_expression(value).ok_or(ty)?;
+                let mro = self.mro_of(&class_name);
+                Ok((class_name, mro, false))
+            }
+        }
+    }
+
+    /// Report `value.attr` when `value` is an instance or class whose members
+    /// are all known and none of them is `attr`, or a module with stubs that
+    /// does not define `attr`
+    ///
+    /// Only names, `super()` and modules are checked as receivers: inferring
+    /// the type of another expression could report its problems a second
+    /// time.
+    /// Dunder attributes, classes with a base the analyzer does not know,
+    /// and classes with `__getattr__` are never reported.
+    fn check_attribute_exists(&mut self, value: &Expression, attr: &str, expr: &Expression) {
+        let receiver_known = match &value.kind {
+            ExpressionKind::Identifier(_) => true,
+            ExpressionKind::Call { func, .. } => {
+                matches!(&func.kind, ExpressionKind::Identifier(name) if name == "super")
+            }
+            _ => false,
+        };
+        if attr.starts_with("__") && attr.ends_with("__") {
+            return;
+        }
+        if let Some(module) = self.module_of(value) {
+            self.module_member(&module, attr, expr.span);
+            return;
+        }
+        if !receiver_known {
+            return;
+        }
+        let Ok((class_name, mro, _)) = self.resolve_receiver(value) else {
+            return;
+        };
+        let Some(classes) = mro
+            .iter()
+            .map(|name| self.classes.get(name))
+            .collect::<Option<Vec<&ClassInfo>>>()
+        else {
+            return;
+        };
+        let dynamic = |class: &&ClassInfo| {
+            class.unresolved_bases
+                || class.has_member("__getattr__")
+                || class.has_member("__getattribute__")
+        };
+        if classes.iter().any(dynamic) || classes.iter().any(|class| class.has_member(attr)) {
+            return;
+        }
+
+        let suggestion = suggest::closest_match(
+            attr,
+            classes.iter().flat_map(|class| class.member_names()),
+        )
+        .map(str::to_string);
+        self.errors.push(SemanticError::UnknownAttribute {
+            class_name: short_name(&class_name).to_string(),
+            attr: attr.to_string(),
+            suggestion,
+            line: expr.span.line,
+            column: expr.span.column,
+            span: expr.span,
+        });
+    }
+
+    /// Built-in module with stubs that `value` names, directly or as a
+    /// submodule (`os.path`)
+    fn module_of(&self, value: &Expression) -> Option<String> {
+        use crate::types::Type;
+
+        // Walk down `a.b.c` to `a`, then look the members up from there
+        let mut attrs = Vec::new();
+        let mut value = value;
+        while let ExpressionKind::Attribute {
+            value: object,
+            attr,
+        } = &value.kind
+        {
+            attrs.push(attr);
+            value = object;
+        }
+        let ExpressionKind::Identifier(name) = &value.kind else {
+            return None;
+        };
+        let mut ty = self.symbol_table.resolve_symbol(name)?.ty.clone();
+        for attr in attrs.into_iter().rev() {
+            let Type::Module(module) = ty else {
+                return None;
+            };
+            ty = stdlib::member_type(&module, attr)?;
+        }
+        match ty {
+            Type::Module(module) => Some(module),
+            _ => None,
+        }
+    }
+
+    /// Stub type of `module.name`, reporting a member the module does not
+    /// define
+    fn module_member(
+        &mut self,
+        module: &str,
+        name: &str,
+        span: silk_lexer::Span,
+    ) -> crate::types::Type {
+        if let Some(ty) = stdlib::member_type(module, name) {
+            return ty;
+        }
+        let suggestion = stdlib::member_names(module)
+            .and_then(|names| suggest::closest_match(name, names.iter().copied()))
+            .map(str::to_string);
+        self.errors.push(SemanticError::UnknownModuleMember {
+            module: module.to_string(),
+            name: name.to_string(),
+            suggestion,
+            line: span.line,
+            column: span.column,
+            span,
+        });
+        crate::types::Type::Unknown
+    }
+
+    /// A visible name or builtin spelled like `name`, to suggest when `name`
+    /// is undefined
+    fn similar_name(&self, name: &str) -> Option<String> {
+        let visible = self.symbol_table.visible_names();
+        let builtins = builtins::BUILTIN_FUNCTIONS
+            .iter()
+            .chain(builtins::BUILTIN_EXCEPTIONS);
+        suggest::closest_match(name, visible.into_iter().chain(builtins.copied()))
+            .map(str::to_string)
+    }
+
+    /// Check `self`/`cls` conventions and property accessor signatures
+    fn check_method_signature(
+        &mut self,
+        name: &str,
+        params: &silk_ast::FunctionParams,
+        decorator_list: &[Expression],
+        stmt: &Statement,
+    ) {
+        let kind = MethodKind::from_decorators(name, decorator_list);
+        let first = params.positional().next();
+
+        match kind.receiver() {
+            Some(expected) => {
+                if first.map(|arg| arg.name.as_str()) != Some(expected) {
+                    let span = first.map(|arg| arg.span).unwrap_or(stmt.span);
+                    self.errors.push(SemanticError::InvalidMethodReceiver {
+                        method: name.to_string(),
+                        method_kind: kind.description().to_string(),
+                        expected: expected.to_string(),
+                        line: span.line,
+                        column: span.column,
+                        span,
+                    });
+                    return;
+                }
+            }
+            None => {
+                if let Some(arg) = first.filter(|arg| arg.name == "self" || arg.name == "cls") {
+                    self.errors.push(SemanticError::StaticMethodReceiver {
+                        method: name.to_string(),
+                        param: arg.name.clone(),
+                        line: arg.span.line,
+                        column: arg.span.column,
+                        span: arg.span,
+                    });
+                }
+            }
+        }
+
+        // Getters and deleters take only self; setters take self and the value
+        let expected = match kind {
+            MethodKind::Property | MethodKind::PropertyDeleter => 1,
+            MethodKind::PropertySetter => 2,
+            _ => return,
+        };
+        let required = params
+            .positional()
+            .chain(&params.kwonlyargs)
+            .filter(|arg| arg.default.is_none())
+            .count();
+        if required != expected || params.vararg.is_some() || params.kwarg.is_some() {
+            self.errors.push(SemanticError::InvalidPropertySignature {
+                name: name.to_string(),
+                method_kind: kind.description().to_string(),
+                expected,
+                actual: required,
+                line: stmt.span.line,
+                column: stmt.span.column,
+                span: stmt.span,
+            });
+        }
+    }
+
+    /// Resolve the class named by an expression like `Point` (not an instance)
+    fn class_of_expression(&self, expr: &Expression) -> Option<String> {
+        match &expr.kind {
+            ExpressionKind::Identifier(name) => self.class_named(name),
+            _ => None,
+        }
+    }
+
+    /// Qualified name of the class a name refers to in the current scope
+    fn class_named(&self, name: &str) -> Option<String> {
+        let symbol = self.symbol_table.resolve_symbol(name)?;
+        if symbol.kind != SymbolKind::Class {
+            return None;
+        }
+        self.class_names.get(&symbol.span).cloned()
+    }
+
+    /// Qualified name of a definition in the current scope
+    fn qualified_name(&self, name: &str) -> String {
+        match self.enclosing_names.last() {
+            Some(enclosing) => format!("{enclosing}.{name}"),
+            None => name.to_string(),
+        }
+    }
+
+    /// Infer the type of `value.attr`
+    ///
+    /// Reading a property evaluates its getter, so the result is the getter's
+    /// return type. Methods evaluate to their (receiver-less) function type.
+    fn infer_attribute_type(&mut self, value: &Expression, attr: &str) -> crate::types::Type {
+        use crate::types::Type;
+
+        if let Some(module) = self.module_of(value) {
+            return stdlib::member_type(&module, attr).unwrap_or(Type::Unknown);
+        }
+        let Ok((_, mro, _)) = self.resolve_receiver(value) else {
+            return Type::Unknown;
+        };
+
+        match self.lookup_method(&mro, attr) {
+            Some(method) if method.kind.is_property() => method.return_type,
+            Some(method) => Type::Function {
+                params: Some(method.params),
+                return_type: Box::new(method.return_type),
+            },
+            None => Type::Unknown,
+        }
+    }
+
+    /// Infer the type of `value.attr(args)` and validate the arguments
+    ///
+    /// Instance methods called through an instance, class methods, and static
+    /// methods are checked against their parameters without the receiver.
+    fn infer_method_call_type(
+        &mut self,
+        value: &Expression,
+        attr: &str,
+        args: &[Expression],
+        keywords: &[silk_ast::CallKeyword],
+        func: &Expression,
+    ) -> crate::types::Type {
+        use crate::types::Type;
+
+        // Functions of built-in modules: `math.sqrt(x)`
+        if let Some(module) = self.module_of(value) {
+            let Some(Type::Function {
+                params: Some(signature),
+                return_type,
+            }) = stdlib::member_type(&module, attr)
+            else {
+                return Type::Unknown;
+            };
+            let name = format!("{}.{}", module, attr);
+            if let Err(err) =
+                self.check_function_call_types(&name, &signature, args, keywords, func)
+            {
+                self.errors.push(err);
+            }
+            return *return_type;
+        }
+
+        let (class_name, mro, via_instance) = match self.resolve_receiver(value) {
+            Ok(receiver) => receiver,
+            // Methods of built-in types such as `bytes.decode`
+            Err(ty) => return ty.method_return_type(attr).unwrap_or(Type::Unknown),
+        };
+
+        let Some(method) = self.lookup_method(&mro, attr) else {
+            return Type::Unknown;
+        };
+
+        match method.kind {
+            // Calling a property calls the value its getter returned
+            MethodKind::Property | MethodKind::PropertySetter | MethodKind::PropertyDeleter => {
+                Type::Unknown
+            }
+            // Unbound call like `Point.move(p, 1)` passes the receiver explicitly
+            MethodKind::Instance if !via_instance => method.return_type,
+            _ => {
+                let qualified = format!("{}.{}", short_name(&class_name), attr);
+                if let Err(err) =
+                    self.check_function_call_types(&qualified, &method.params, args, keywords, func)
+                {
+                    self.errors.push(err);
+                }
+                method.return_type
+            }
+        }
+    }
+
+    /// Check the placement and values of parameter defaults
+    ///
+    /// Positional parameters without a default may not follow ones with a
+    /// default (keyword-only parameters may). Defaults are evaluated once,
+    /// when the function is defined, so a mutable default is shared by every
+    /// call and is reported as a lint.
+    fn check_parameter_defaults(&mut self, params: &silk_ast::FunctionParams) {
+        let mut seen_default = false;
+        for param in params.positional() {
+            if param.default.is_some() {
+                seen_default = true;
+            } else if seen_default {
+                self.errors.push(SemanticError::NonDefaultAfterDefault {
+                    param: param.name.clone(),
+                    line: param.span.line,
+                    column: param.span.column,
+                    span: param.span,
+                });
+            }
+        }
+
+        for param in params.positional().chain(&params.kwonlyargs) {
+            let Some(default) = &param.default else {
+                continue;
+            };
+            if let Some(kind) = self.mutable_value_kind(default) {
+                self.errors.push(SemanticError::MutableDefault {
+                    param: param.name.clone(),
+                    kind: kind.to_string(),
+                    line: default.span.line,
+                    column: default.span.column,
+                    span: default.span,
+                });
+            }
+        }
+    }
+
+    /// Name of the mutable built-in type an expression creates, if any
+    /// (`[]`, `{}`, `set()`, comprehensions, `list(...)`, ...)
+    fn mutable_value_kind(&self, expr: &Expression) -> Option<&'static str> {
+        match &expr.kind {
+            ExpressionKind::List { .. } | ExpressionKind::ListComp { .. } => Some("list"),
+            ExpressionKind::Dict { .. } | ExpressionKind::DictComp { .. } => Some("dict"),
+            ExpressionKind::Set { .. } | ExpressionKind::SetComp { .. } => Some("set"),
+            ExpressionKind::Call { func, .. } => match &func.kind {
+                // Only the built-in constructors, not user definitions
+                ExpressionKind::Identifier(name)
+                    if self.symbol_table.resolve_symbol(name).is_none() =>
+                {
+                    match name.as_str() {
+                        "list" => Some("list"),
+                        "dict" => Some("dict"),
+                        "set" => Some("set"),
+                        "bytearray" => Some("bytearray"),
+                        _ => None,
+                    }
+                }
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Declared type of a parameter (Unknown if not annotated)
+    fn parameter_type(&self, arg: &silk_ast::FunctionArg) -> crate::types::Type {
+        arg.annotation
+            .as_ref()
+            .map_or(crate::types::Type::Unknown, |ann| {
+                self.resolve_type_annotation(ann)
+            })
+    }
+
+    /// Check `"..." % args` against its literal template
+    fn check_percent_format(
+        &mut self,
+        template: &Expression,
+        args: &Expression,
+        span: silk_lexer::Span,
+    ) {
+        use crate::types::Type;
+
+        let Some(template) = literal_text(template) else {
+            return;
+        };
+        let args = match &args.kind {
+            ExpressionKind::Tuple { elements } => formatting::PercentArgs::Positional(
+                elements
+                    .iter()
+                    .map(|element| self.infer_type_quietly(element))
+                    .collect(),
+            ),
+            ExpressionKind::Dict { keys, .. } => formatting::PercentArgs::Mapping(
+                keys.iter().map(|key| literal_text(key).map(str::to_string)).collect(),
+            ),
+            _ => match self.infer_type_quietly(args) {
+                Type::Tuple(types) => formatting::PercentArgs::Positional(types),
+                Type::Dict { .. } => formatting::PercentArgs::Mapping(None),
+                ty @ (Type::Int
+                | Type::Float
+                | Type::Bool
+                | Type::Str
+                | Type::Bytes
+                | Type::None
+                | Type::List(_)
+                | Type::Set(_)) => formatting::PercentArgs::Positional(vec![ty]),
+                _ => formatting::PercentArgs::Unknown,
+            },
+        };
+        if let Err(message) = formatting::check_percent(template, &args) {
+            self.errors.push(SemanticError::InvalidFormat {
+                message,
+                line: span.line,
+                column: span.column,
+                span,
+            });
+        }
+    }
+
+    /// Check `"...".format(...)` against its literal template
+    fn check_format_call(
+        &mut self,
+        func: &Expression,
+        args: &[Expression],
+        keywords: &[silk_ast::CallKeyword],
+        span: silk_lexer::Span,
+    ) {
+        let ExpressionKind::Attribute { value, attr } = &func.kind else {
+            return;
+        };
+        let Some(template) = literal_text(value).filter(|_| attr == "format") else {
+            return;
+        };
+        let args = formatting::FormatArgs {
+            positional: args.iter().map(|arg| self.infer_type_quietly(arg)).collect(),
+            keywords: keywords
+                .iter()
+                .filter_map(|keyword| {
+                    let name = keyword.arg.as_deref()?;
+                    Some((name, self.infer_type_quietly(&keyword.value)))
+                })
+                .collect(),
+            any_keyword: keywords.iter().any(|keyword| keyword.arg.is_none()),
+        };
+        if let Err(message) = formatting::check_format(template, &args) {
+            self.errors.push(SemanticError::InvalidFormat {
+                message,
+                line: span.line,
+                column: span.column,
+                span,
+            });
+        }
+    }
+
+    /// Check the literal pattern of `re.compile(...)` and the other `re`
+    /// functions taking a pattern
+    ///
+    /// Patterns passed with `flags` are skipped, since `re.VERBOSE` changes
+    /// how they parse.
+    fn check_regex_call(
+        &mut self,
+        func: &Expression,
+        args: &[Expression],
+        keywords: &[silk_ast::CallKeyword],
+    ) {
+        let ExpressionKind::Attribute { value, attr } = &func.kind else {
+            return;
+        };
+        if self.module_of(value).as_deref() != Some("re")
+            || !stdlib::RE_PATTERN_FUNCTIONS.contains(&attr.as_str())
+        {
+            return;
+        }
+        let keyword = |name: &str| {
+            keywords
+                .iter()
+                .find(|keyword| keyword.arg.as_deref() == Some(name))
+                .map(|keyword| &keyword.value)
+        };
+        let flags_position = match attr.as_str() {
+            "compile" => 1,
+            "split" => 3,
+            "sub" | "subn" => 4,
+            _ => 2,
+        };
+        if args.len() > flags_position || keyword("flags").is_some() {
+            return;
+        }
+        let Some(pattern) = args.first().or_else(|| keyword("pattern")) else {
+            return;
+        };
+        let Some(text) = literal_text(pattern) else {
+            return;
+        };
+        if let Err(message) = stdlib::check_regex(text) {
+            self.errors.push(SemanticError::InvalidRegex {
+                message,
+                line: pattern.span.line,
+                column: pattern.span.column,
+                span: pattern.span,
+            });
+        }
+    }
+
+    /// Value of a numeric expression, folding calls of pure module-level
+    /// functions that the name still refers to here
+    fn constant_number(&self, expr: &Expression) -> Option<Constant> {
+        let functions = |name: &str| {
+            let function = self.const_functions.get(name)?;
+            let symbol = self.symbol_table.resolve_symbol(name)?;
+            (symbol.kind == SymbolKind::Function && symbol.pure && symbol.span == function.span)
+                .then_some(function)
+        };
+        const_eval::number_with(expr, &functions)
+    }
+
+    /// Report a comparison of a variable with itself (`x == x`)
+    ///
+    /// Only operands known to be an int, str or bool are reported: a float
+    /// may be NaN, which is not equal to itself, and `x != x` is the usual
+    /// NaN check.
+    fn check_self_comparison(
+        &mut self,
+        left: &Expression,
+        op: silk_ast::CompareOperator,
+        right: &Expression,
+        span: silk_lexer::Span,
+    ) {
+        use crate::types::Type;
+
+        let Some((name, value)) = conditions::self_comparison(left, op, right) else {
+            return;
+        };
+        if matches!(self.infer_type(left), Type::Int | Type::Str | Type::Bool) {
+            self.errors.push(SemanticError::SelfComparison {
+                name,
+                value,
+                line: span.line,
+                column: span.column,
+                span,
+            });
+        }
+    }
+
+    /// Report division by zero, out-of-range shifts and integer overflow
+    ///
+    /// Division by zero and shift counts only need a constant right
+    /// operand (`x / 0`, `x << 64`); overflow needs both operands.
+    fn check_constant_operation(
+        &mut self,
+        left: &Expression,
+        op: silk_ast::BinaryOperator,
+        right: &Expression,
+        span: silk_lexer::Span,
+    ) {
+        let Some(right_value) = self.constant_number(right) else {
+            return;
+        };
+        let left_value = self.constant_number(left);
+        // Any left value works for the checks that only need the right one
+        let placeholder = left_value.unwrap_or(Constant::Int(0));
+        let folded = constants::fold_binary(placeholder, op, right_value);
+        let operator = constants::operator_symbol(op).to_string();
+        let (line, column) = (span.line, span.column);
+        let error = match folded {
+            Some(Err(FoldError::DivisionByZero)) => SemanticError::DivisionByZero {
+                operator,
+                line,
+                column,
+                span,
+            },
+            Some(Err(FoldError::ShiftOutOfRange(count))) => SemanticError::ShiftOutOfRange {
+                count,
+                line,
+                column,
+                span,
+            },
+            Some(Err(FoldError::Overflow)) if left_value.is_some() => {
+                SemanticError::IntegerOverflow {
+                    operator,
+                    line,
+                    column,
+                    span,
+                }
+            }
+            _ => return,
+        };
+        self.errors.push(error);
+    }
+
+    /// Lint a variable binding of `name` in the current scope
+    ///
+    /// Reports an assignment that replaces a function or class defined
+    /// earlier in the same scope (unless `value` reads it, as in
+    /// `f = decorator(f)`), and a new variable that shadows a name of an
+    /// enclosing function or module scope or a builtin.
+    fn check_variable_binding(
+        &mut self,
+        name: &str,
+        span: silk_lexer::Span,
+        value: Option<&Expression>,
+    ) {
+        let scope_kind = self.symbol_table.current_scope_kind();
+        let declared_outer = self
+            .outer_declarations
+            .last()
+            .is_some_and(|declared| declared.contains(name));
+        if scope_kind == ScopeKind::Comprehension || declared_outer {
+            return;
+        }
+
+        if let Some(existing) = self.symbol_table.lookup_local(name) {
+            let kind = match existing.kind {
+                SymbolKind::Function => "function",
+                SymbolKind::Class => "class",
+                _ => return,
+            };
+            // Module-level definitions are declared before the main pass
+            let defined_before = existing.span.start < span.start;
+            if defined_before && !value.is_some_and(|value| mentions_name(value, name)) {
+                self.errors.push(SemanticError::ReassignedDefinition {
+                    name: name.to_string(),
+                    kind: kind.to_string(),
+                    first_line: existing.span.line,
+                    line: span.line,
+                    column: span.column,
+                    span,
+                });
+            }
+            return;
+        }
+
+        let shadowed = match self.symbol_table.resolve_in_enclosing_scopes(name) {
+            Some(outer) if scope_kind == ScopeKind::Function => {
+                format!("the outer '{}' from line {}", name, outer.span.line)
+            }
+            None if scope_kind != ScopeKind::Class && builtins::is_builtin(name) => {
+                format!("the builtin '{}'", name)
+            }
+            _ => return,
+        };
+        self.errors.push(SemanticError::ShadowedName {
+            name: name.to_string(),
+            shadowed,
+            line: span.line,
+            column: span.column,
+            span,
+        });
+    }
+
+    /// Define a function parameter
+    fn define_parameter(&mut self, arg: &silk_ast::FunctionArg, ty: crate::types::Type) {
+        let param_symbol =
+            Symbol::with_type(arg.name.clone(), SymbolKind::Parameter, arg.span, ty);
+        if let Err(err) = self.symbol_table.define_symbol(param_symbol) {
+            self.errors.push(err);
+        }
+    }
+
+    /// Check type compatibility for annotated assignments
+    ///
+    /// Validates that the value type is compatible with the declared type annotation.
+    /// Uses Type::is_compatible_with() for the check, which supports:
+    /// - Exact type matches (int = int, str = str)
+    /// - Numeric type compatibility (int can be assigned to float)
+    /// - Unknown type (always compatible - gradual typing)
+    /// - Collection type structural compatibility
+    ///
+    /// Returns Ok(()) if compatible, Err(SemanticError) if not.
+    fn check_assignment_type(
+        &self,
+        expected_type: &crate::types::Type,
+        value_type: &crate::types::Type,
+        _target: &Expression,
+        value: &Expression,
+    ) -> Result<(), SemanticError> {
+        // Check if value type is compatible with expected type
+        if !self.is_assignable(value_type, expected_type) {
+            return Err(SemanticError::AssignmentTypeMismatch {
+                expected_type: expected_type.to_string(),
+                value_type: value_type.to_string(),
+                line: value.span.line,
+                column: value.span.column,
+                span: value.span,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Check function call arguments against the callee's signature
+    ///
+    /// Validates that:
+    /// 1. Every argument binds to a parameter (positional-only parameters are
+    ///    not passed by keyword, keyword-only parameters not by position)
+    /// 2. Every required parameter receives an argument
+    /// 3. Each argument type is compatible with its parameter type
+    ///
+    /// Returns Ok(()) if valid, Err(SemanticError) if not.
+    fn check_function_call_types(
+        &mut self,
+        func_name: &str,
+        signature: &Signature,
+        args: &[Expression],
+        keywords: &[silk_ast::CallKeyword],
+        func_expr: &Expression,
+    ) -> Result<(), SemanticError> {
+        let keyword_names: Vec<Option<&str>> =
+            keywords.iter().map(|keyword| keyword.arg.as_deref()).collect();
+        let function_name = func_name.to_string();
+
+        // Errors about a keyword point at that keyword
+        let keyword_span = |name: &str| {
+            keywords
+                .iter()
+                .find(|keyword| keyword.arg.as_deref() == Some(name))
+                .map_or(func_expr.span, |keyword| keyword.span)
+        };
+
+        let binding = match signature.bind(args.len(), &keyword_names) {
+            Ok(binding) => binding,
+            Err(BindError::TooManyPositional {
+                keyword_only: Some(param),
+                expected,
+                ..
+            }) => {
+                let span = args[expected].span;
+                return Err(SemanticError::KeywordOnlyArgument {
+                    function_name,
+                    param,
+                    line: span.line,
+                    column: span.column,
+                    span,
+                });
+            }
+            Err(BindError::TooManyPositional {
+                expected, given, ..
+            }) => {
+                return Err(SemanticError::ArgumentCountMismatch {
+                    function_name,
+                    expected,
+                    actual: given,
+                    line: func_expr.span.line,
+                    column: func_expr.span.column,
+                    span: func_expr.span,
+                });
+            }
+            Err(BindError::PositionalOnlyAsKeyword(param)) => {
+                let span = keyword_span(&param);
+                return Err(SemanticError::PositionalOnlyArgument {
+                    function_name,
+                    param,
+                    line: span.line,
+                    column: span.column,
+                    span,
+                });
+            }
+            Err(BindError::UnexpectedKeyword(keyword)) => {
+                let span = keyword_span(&keyword);
+                return Err(SemanticError::UnexpectedKeywordArgument {
+                    function_name,
+                    keyword,
+                    line: span.line,
+                    column: span.column,
+                    span,
+                });
+            }
+            Err(BindError::MultipleValues(param)) => {
+                let span = keyword_span(&param);
+                return Err(SemanticError::DuplicateArgument {
+                    function_name,
+                    param,
+                    line: span.line,
+                    column: span.column,
+                    span,
+                });
+            }
+            // Purely positional calls keep reporting a simple count mismatch
+            Err(BindError::Missing(missing))
+                if keywords.is_empty()
+                    && missing.iter().all(|name| {
+                        signature
+                            .param(name)
+                            .is_some_and(|param| param.kind != ParamKind::KeywordOnly)
+                    }) =>
+            {
+                return Err(SemanticError::ArgumentCountMismatch {
+                    function_name,
+                    expected: signature.required_positional(),
+                    actual: args.len(),
+                    line: func_expr.span.line,
+                    column: func_expr.span.column,
+                    span: func_expr.span,
+                });
+            }
+            Err(BindError::Missing(missing)) => {
+                return Err(SemanticError::MissingArgument {
+                    function_name,
+                    param: missing[0].clone(),
+                    line: func_expr.span.line,
+                    column: func_expr.span.column,
+                    span: func_expr.span,
+                });
+            }
+        };
+
+        // Check each argument type against the parameter it binds to
+        let bound = args.iter().zip(binding.positional.iter().copied()).chain(
+            keywords
+                .iter()
+                .zip(&binding.keywords)
+                .filter_map(|(keyword, slot)| slot.map(|slot| (&keyword.value, slot))),
+        );
+        for (i, (arg, slot)) in bound.enumerate() {
+            let param = &signature.params[slot];
+            let arg_type = self.infer_type(arg);
+
+            if !self.is_assignable(&arg_type, &param.ty) {
+                return Err(SemanticError::ArgumentTypeMismatch {
+                    param_name: param.name.clone(),
+                    arg_index: i + 1,
+                    expected_type: param.ty.to_string(),
+                    actual_type: arg_type.to_string(),
+                    line: arg.span.line,
+                    column: arg.span.column,
+                    span: arg.span,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check return statement type against declared function return type
+    ///
+    /// Validates that the return value type is compatible with the declared
+    /// return type of the current function.
+    ///
+    /// Returns Ok(()) if valid, Err(SemanticError) if not.
     fn check_return_type(
         &mut self,
         return_expr: &Expression,
@@ -1634,7 +3299,7 @@ impl SemanticAnalyzer {
         let actual_type = self.infer_type(return_expr);
 
         // Check if actual type is compatible with expected type
-        if !actual_type.is_compatible_with(&expected_type) {
+        if !self.is_assignable(&actual_type, &expected_type) {
             return Err(SemanticError::ReturnTypeMismatch {
                 expected_type: expected_type.to_string(),
                 actual_type: actual_type.to_string(),
@@ -1667,8 +3332,13 @@ impl SemanticAnalyzer {
             return Ok(());
         }
 
+        // Instances may overload operators through dunder methods
+        if matches!(left_type, Type::Instance(_)) || matches!(right_type, Type::Instance(_)) {
+            return Ok(());
+        }
+
         match op {
-            // Addition: numeric + numeric, or str + str
+            // Addition: numeric + numeric, str + str, or bytes + bytes
             BinaryOperator::Add => {
                 let valid = matches!(
                     (left_type, right_type),
@@ -1677,6 +3347,7 @@ impl SemanticAnalyzer {
                         | (Type::Int, Type::Float)
                         | (Type::Float, Type::Int)
                         | (Type::Str, Type::Str)
+                        | (Type::Bytes, Type::Bytes)
                 );
 
                 if !valid {
@@ -1686,11 +3357,15 @@ impl SemanticAnalyzer {
                         right_type: right_type.to_string(),
                         line: left_expr.span.line,
                         column: left_expr.span.column,
-                        span: left_expr.span.clone(),
+                        span: left_expr.span,
                     });
                 }
             }
 
+            // printf-style formatting accepts any values; the template is
+            // checked by `check_percent_format`
+            BinaryOperator::Mod if *left_type == Type::Str => {}
+
             // Arithmetic operators: only numeric types
             BinaryOperator::Sub
             | BinaryOperator::Mult
@@ -1723,7 +3398,7 @@ impl SemanticAnalyzer {
                         right_type: right_type.to_string(),
                         line: left_expr.span.line,
                         column: left_expr.span.column,
-                        span: left_expr.span.clone(),
+                        span: left_expr.span,
                     });
                 }
             }
@@ -1752,10 +3427,22 @@ impl SemanticAnalyzer {
                         right_type: right_type.to_string(),
                         line: left_expr.span.line,
                         column: left_expr.span.column,
-                        span: left_expr.span.clone(),
+                        span: left_expr.span,
                     });
                 }
             }
+
+            // Matrix multiplication: no built-in type supports `@`
+            BinaryOperator::MatMult => {
+                return Err(SemanticError::InvalidBinaryOperation {
+                    operator: "@".to_string(),
+                    left_type: left_type.to_string(),
+                    right_type: right_type.to_string(),
+                    line: left_expr.span.line,
+                    column: left_expr.span.column,
+                    span: left_expr.span,
+                });
+            }
         }
 
         Ok(())
@@ -1764,20 +3451,51 @@ impl SemanticAnalyzer {
     /// Infer the result type of a subscript operation
     ///
     /// Returns the element type for lists/tuples/sets, value type for dicts, or Unknown
-    fn infer_subscript_type(&mut self, value: &Expression) -> crate::types::Type {
+    fn infer_subscript_type(
+        &mut self,
+        value: &Expression,
+        index: &Expression,
+    ) -> crate::types::Type {
         use crate::types::Type;
 
         let value_type = self.infer_type(value);
 
         match value_type {
+            // List[T][start:stop] -> List[T]
+            list @ Type::List(_) if matches!(index.kind, ExpressionKind::Slice { .. }) => list,
+
             // List[T] -> T
             Type::List(element_type) => (*element_type).clone(),
 
             // Dict[K, V] -> V
             Type::Dict { value_type, .. } => (*value_type).clone(),
 
-            // Tuple[T1, T2, ...] -> Unknown (we don't track individual element types yet)
-            Type::Tuple(_) => Type::Unknown,
+            // Tuple[T1, T2, T3][1:] -> Tuple[T2, T3], when the bounds are constant
+            Type::Tuple(elements) => match &index.kind {
+                ExpressionKind::Slice { lower, upper, step } => {
+                    let bound = |bound: &Option<Box<Expression>>| match bound {
+                        Some(expr) => match self.constant_number(expr) {
+                            Some(Constant::Int(value)) => Some(Some(value)),
+                            _ => None,
+                        },
+                        None => Some(None),
+                    };
+                    match (bound(lower), bound(upper), bound(step)) {
+                        (Some(lower), Some(upper), Some(step)) => slice_indices(
+                            elements.len(),
+                            lower,
+                            upper,
+                            step.unwrap_or(1),
+                        )
+                        .map_or(Type::Unknown, |indices| {
+                            Type::Tuple(indices.into_iter().map(|i| elements[i].clone()).collect())
+                        }),
+                        _ => Type::Unknown,
+                    }
+                }
+                // We don't track individual element types for indexing yet
+                _ => Type::Unknown,
+            },
 
             // Set[T] -> T (though subscripting a set is invalid in Python)
             Type::Set(element_type) => (*element_type).clone(),
@@ -1785,6 +3503,10 @@ impl SemanticAnalyzer {
             // Str[int] -> Str (string subscript returns a string)
             Type::Str => Type::Str,
 
+            // Bytes[int] -> Int, Bytes[start:stop] -> Bytes
+            Type::Bytes if matches!(index.kind, ExpressionKind::Slice { .. }) => Type::Bytes,
+            Type::Bytes => Type::Int,
+
             // Unknown or Any pass through
             Type::Unknown | Type::Any => Type::Unknown,
 
@@ -1816,6 +3538,9 @@ impl SemanticAnalyzer {
             // Str -> Str (iterating over string yields strings of length 1)
             Type::Str => Type::Str,
 
+            // Bytes -> Int (iterating over bytes yields their values)
+            Type::Bytes => Type::Int,
+
             // Unknown, Any, or other types -> Unknown
             _ => Type::Unknown,
         }
@@ -1840,10 +3565,24 @@ impl SemanticAnalyzer {
             return Ok(());
         }
 
+        // Instances may define __getitem__ / __index__
+        if matches!(value_type, Type::Instance(_)) || matches!(index_type, Type::Instance(_)) {
+            return Ok(());
+        }
+
+        // Every member of a union must support the subscript (a None member
+        // is reported as an optional access instead)
+        if let Type::Union(members) = value_type {
+            for member in members.iter().filter(|member| **member != Type::None) {
+                self.validate_subscript(member, index_type, value_expr, index_expr)?;
+            }
+            return Ok(());
+        }
+
         // Check if the value type supports subscripting
         match value_type {
-            // List, Tuple, Str require int index
-            Type::List(_) | Type::Tuple(_) | Type::Str => {
+            // List, Tuple, Str, Bytes require int index
+            Type::List(_) | Type::Tuple(_) | Type::Str | Type::Bytes => {
                 if *index_type != Type::Int {
                     return Err(SemanticError::InvalidSubscript {
                         collection_type: value_type.to_string(),
@@ -1906,3 +3645,138 @@ impl Default for SemanticAnalyzer {
         Self::new()
     }
 }
+
+/// Span of a function or class definition, ending with its last body
+/// statement
+///
+/// Statement spans run up to the token that follows them, which for a
+/// block is the first token of the next statement.
+fn block_span(stmt: &Statement, body: &[Statement]) -> silk_lexer::Span {
+    let end = block_end(body).unwrap_or(stmt.span.end);
+    silk_lexer::Span::new(stmt.span.start, end, stmt.span.line, stmt.span.column)
+}
+
+/// End offset of the last statement of a block, looking inside nested blocks
+fn block_end(body: &[Statement]) -> Option<usize> {
+    let last = body.last()?;
+    let nested = match &last.kind {
+        StatementKind::If { body, orelse, .. }
+        | StatementKind::While { body, orelse, .. }
+        | StatementKind::For { body, orelse, .. } => block_end(orelse).or_else(|| block_end(body)),
+        StatementKind::With { body, .. }
+        | StatementKind::FunctionDef { body, .. }
+        | StatementKind::ClassDef { body, .. } => block_end(body),
+        StatementKind::Try {
+            body,
+            handlers,
+            orelse,
+            finalbody,
+            ..
+        } => block_end(finalbody)
+            .or_else(|| block_end(orelse))
+            .or_else(|| handlers.last().and_then(|handler| block_end(&handler.body)))
+            .or_else(|| block_end(body)),
+        StatementKind::Match { cases, .. } => cases.last().and_then(|case| block_end(&case.body)),
+        _ => None,
+    };
+    Some(nested.unwrap_or(last.span.end))
+}
+
+/// `break`, `continue` and `return` statements in `body` that leave it;
+/// `in_loop` when `body` is in a loop inside the handler
+fn except_star_jumps(
+    body: &[Statement],
+    in_loop: bool,
+    jumps: &mut Vec<(&'static str, silk_lexer::Span)>,
+) {
+    for stmt in body {
+        match &stmt.kind {
+            StatementKind::Break if !in_loop => jumps.push(("break", stmt.span)),
+            StatementKind::Continue if !in_loop => jumps.push(("continue", stmt.span)),
+            StatementKind::Return { .. } => jumps.push(("return", stmt.span)),
+            StatementKind::While { body, orelse, .. } | StatementKind::For { body, orelse, .. } => {
+                except_star_jumps(body, true, jumps);
+                except_star_jumps(orelse, in_loop, jumps);
+            }
+            StatementKind::If { body, orelse, .. } => {
+                except_star_jumps(body, in_loop, jumps);
+                except_star_jumps(orelse, in_loop, jumps);
+            }
+            StatementKind::With { body, .. } => except_star_jumps(body, in_loop, jumps),
+            StatementKind::Try {
+                body,
+                handlers,
+                orelse,
+                finalbody,
+                ..
+            } => {
+                except_star_jumps(body, in_loop, jumps);
+                for handler in handlers {
+                    except_star_jumps(&handler.body, in_loop, jumps);
+                }
+                except_star_jumps(orelse, in_loop, jumps);
+                except_star_jumps(finalbody, in_loop, jumps);
+            }
+            StatementKind::Match { cases, .. } => {
+                for case in cases {
+                    except_star_jumps(&case.body, in_loop, jumps);
+                }
+            }
+            // Definitions start a new function or class body
+            _ => {}
+        }
+    }
+}
+
+/// Text of a string literal
+fn literal_text(expr: &Expression) -> Option<&str> {
+    match &expr.kind {
+        ExpressionKind::String(text) | ExpressionKind::RawString(text) => Some(text),
+        _ => None,
+    }
+}
+
+/// Check if `expr` reads the variable `name`
+fn mentions_name(expr: &Expression, name: &str) -> bool {
+    matches!(&expr.kind, ExpressionKind::Identifier(id) if id == name)
+        || subexpressions(expr)
+            .into_iter()
+            .any(|(sub, _)| mentions_name(sub, name))
+}
+
+/// Indices a slice `[lower:upper:step]` selects from a sequence of `len`
+/// items, following Python's rules for omitted and negative bounds; `None`
+/// for a zero step
+fn slice_indices(
+    len: usize,
+    lower: Option<i64>,
+    upper: Option<i64>,
+    step: i64,
+) -> Option<Vec<usize>> {
+    if step == 0 {
+        return None;
+    }
+    let len = len as i64;
+    // Negative bounds count from the end; out of range bounds are clamped
+    let clamp = |bound: i64, min: i64, max: i64| {
+        let bound = if bound < 0 { bound + len } else { bound };
+        bound.clamp(min, max)
+    };
+    let mut indices = Vec::new();
+    if step > 0 {
+        let mut i = lower.map_or(0, |bound| clamp(bound, 0, len));
+        let end = upper.map_or(len, |bound| clamp(bound, 0, len));
+        while i < end {
+            indices.push(i as usize);
+            i = i.saturating_add(step);
+        }
+    } else {
+        let mut i = lower.map_or(len - 1, |bound| clamp(bound, -1, len - 1));
+        let end = upper.map_or(-1, |bound| clamp(bound, -1, len - 1));
+        while i > end {
+            indices.push(i as usize);
+            i = i.saturating_add(step);
+        }
+    }
+    Some(indices)
+}
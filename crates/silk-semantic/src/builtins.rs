@@ -0,0 +1,178 @@
+//! Names that are always in scope
+//!
+//! Built-in functions, exception classes and module attributes resolve
+//! without a definition, both in name resolution and in initialization
+//! checks. Some built-in
+//! functions are also marked pure, for the checks that need to know which
+//! calls can be left out or moved without changing what a program does.
+
+/// Python built-in functions that don't need to be defined
+pub const BUILTIN_FUNCTIONS: &[&str] = &[
+    "len",
+    "str",
+    "int",
+    "float",
+    "bool",
+    "bytes",
+    "print",
+    "input",
+    "abs",
+    "min",
+    "max",
+    "sum",
+    "list",
+    "dict",
+    "set",
+    "tuple",
+    "range",
+    "type",
+    "isinstance",
+    "issubclass",
+    "chr",
+    "ord",
+    "hex",
+    "oct",
+    "bin",
+    "round",
+    "pow",
+    "divmod",
+    "all",
+    "any",
+    "enumerate",
+    "filter",
+    "map",
+    "zip",
+    "sorted",
+    "reversed",
+    "iter",
+    "next",
+    "open",
+    "help",
+    "dir",
+    "vars",
+    "globals",
+    "locals",
+    "eval",
+    "exec",
+    "compile",
+    "getattr",
+    "setattr",
+    "hasattr",
+    "delattr",
+    "id",
+    "hash",
+    "repr",
+    "ascii",
+    "format",
+    "property",
+    "staticmethod",
+    "classmethod",
+    "super",
+    "object",
+    "test",
+    "pure",
+];
+
+/// Built-in functions that are pure: the result depends only on the
+/// arguments, and the call has no effect besides returning it (no I/O, no
+/// change to an argument or to global state). Methods of the arguments
+/// that these call, such as `__len__`, are assumed to be pure as well.
+pub const PURE_BUILTIN_FUNCTIONS: &[&str] = &[
+    "len",
+    "str",
+    "int",
+    "float",
+    "bool",
+    "bytes",
+    "abs",
+    "min",
+    "max",
+    "sum",
+    "list",
+    "dict",
+    "set",
+    "tuple",
+    "range",
+    "type",
+    "isinstance",
+    "issubclass",
+    "chr",
+    "ord",
+    "hex",
+    "oct",
+    "bin",
+    "round",
+    "pow",
+    "divmod",
+    "all",
+    "any",
+    "enumerate",
+    "filter",
+    "map",
+    "zip",
+    "sorted",
+    "reversed",
+    "iter",
+    "hash",
+    "repr",
+    "ascii",
+    "format",
+    "property",
+    "staticmethod",
+    "classmethod",
+    "object",
+];
+
+/// Built-in exception classes
+pub const BUILTIN_EXCEPTIONS: &[&str] = &[
+    "BaseException",
+    "BaseExceptionGroup",
+    "Exception",
+    "ExceptionGroup",
+    "ArithmeticError",
+    "AssertionError",
+    "AttributeError",
+    "EOFError",
+    "ImportError",
+    "IndexError",
+    "KeyError",
+    "KeyboardInterrupt",
+    "LookupError",
+    "ModuleNotFoundError",
+    "NameError",
+    "NotImplementedError",
+    "OSError",
+    "OverflowError",
+    "RecursionError",
+    "RuntimeError",
+    "StopIteration",
+    "SystemExit",
+    "TypeError",
+    "ValueError",
+    "ZeroDivisionError",
+];
+
+/// Attributes every module has (`if __name__ == "__main__":`)
+pub const MODULE_ATTRIBUTES: &[&str] = &["__name__", "__file__", "__doc__"];
+
+/// Check if a name is a built-in function, exception class or module
+/// attribute
+pub fn is_builtin(name: &str) -> bool {
+    is_builtin_function(name) || is_builtin_exception(name) || MODULE_ATTRIBUTES.contains(&name)
+}
+
+/// Check if a name is a built-in function
+pub fn is_builtin_function(name: &str) -> bool {
+    BUILTIN_FUNCTIONS.contains(&name)
+}
+
+/// Check if a name is a built-in exception class
+pub fn is_builtin_exception(name: &str) -> bool {
+    BUILTIN_EXCEPTIONS.contains(&name)
+}
+
+/// Check if a name is a pure built-in function or an exception class,
+/// whose constructor is pure as well
+pub fn is_pure_builtin(name: &str) -> bool {
+    PURE_BUILTIN_FUNCTIONS.contains(&name) || is_builtin_exception(name)
+}
@@ -0,0 +1,858 @@
+//! Call graph construction
+//!
+//! Nodes are the functions and methods a program defines, named by their
+//! qualified path (`outer.inner`, `Point.move`). Property setters and
+//! deleters are named `Class.name.setter` and `Class.name.deleter`. Code
+//! outside any function is the `<module>` node.
+//!
+//! An edge is recorded for every call whose callee can be resolved
+//! statically:
+//! - `f()` resolves to the innermost enclosing definition of `f` (class
+//!   bodies are not enclosing scopes for their methods, as in Python)
+//! - `self.m()` and `cls.m()` resolve to the enclosing class's method `m`;
+//!   reading `self.p` calls the getter of property `p`, and assigning to it
+//!   calls the setter
+//! - `Class.m()` resolves to `m` of a known class, and `Class()` to its
+//!   `__init__`
+//!
+//! Calls through other values (`obj.m()`, callbacks) are not resolved.
+//!
+//! While building the graph, each function is also checked for recursion
+//! without a base case: a function that calls itself on every path through
+//! its body never returns.
+
+use crate::classes::MethodKind;
+use silk_ast::{
+    ensure_sufficient_stack, Expression, ExpressionKind, FunctionParams, Program, Statement,
+    StatementKind,
+};
+use silk_lexer::Span;
+use std::collections::{HashMap, HashSet};
+
+/// Name of the node for module-level code
+pub const MODULE_NODE: &str = "<module>";
+
+/// A function or method definition
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionNode {
+    /// Qualified name (`outer.inner`, `Class.method`)
+    pub name: String,
+    /// Location of the definition
+    pub span: Span,
+}
+
+/// A call from one function to another
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallEdge {
+    /// Qualified name of the calling function, or [`MODULE_NODE`]
+    pub caller: String,
+    /// Qualified name of the called function
+    pub callee: String,
+    /// Location of the call
+    pub span: Span,
+}
+
+/// Statically resolved calls between the functions of a program
+#[derive(Debug, Clone, Default)]
+pub struct CallGraph {
+    functions: Vec<FunctionNode>,
+    edges: Vec<CallEdge>,
+    /// Functions that call themselves on every path
+    always_recursive: HashSet<String>,
+}
+
+impl CallGraph {
+    /// Build the call graph of a program
+    pub fn build(program: &Program) -> Self {
+        let mut builder = Builder {
+            graph: CallGraph::default(),
+            scopes: Vec::new(),
+            caller: MODULE_NODE.to_string(),
+            receiver: None,
+            classes: Vec::new(),
+        };
+        builder.enter_scope(&program.statements, "", false);
+        builder.visit_block(&program.statements);
+        builder.graph
+    }
+
+    /// Every function and method, in definition order
+    pub fn functions(&self) -> &[FunctionNode] {
+        &self.functions
+    }
+
+    /// Every call edge, in source order
+    pub fn edges(&self) -> &[CallEdge] {
+        &self.edges
+    }
+
+    /// Look up a function by qualified name
+    pub fn function(&self, name: &str) -> Option<&FunctionNode> {
+        self.functions.iter().find(|function| function.name == name)
+    }
+
+    /// Calls made by `caller`
+    pub fn callees<'a>(&'a self, caller: &'a str) -> impl Iterator<Item = &'a CallEdge> {
+        self.edges.iter().filter(move |edge| edge.caller == caller)
+    }
+
+    /// Calls made to `callee`
+    pub fn callers<'a>(&'a self, callee: &'a str) -> impl Iterator<Item = &'a CallEdge> {
+        self.edges.iter().filter(move |edge| edge.callee == callee)
+    }
+
+    /// Whether `name` can call itself, directly or through other functions
+    pub fn is_recursive(&self, name: &str) -> bool {
+        let mut seen = HashSet::new();
+        let mut pending: Vec<&str> = vec![name];
+        while let Some(current) = pending.pop() {
+            for edge in self.callees(current) {
+                if edge.callee == name {
+                    return true;
+                }
+                if seen.insert(edge.callee.as_str()) {
+                    pending.push(&edge.callee);
+                }
+            }
+        }
+        false
+    }
+
+    /// Whether `name` calls itself on every path through its body, so it
+    /// can never return
+    ///
+    /// Generators and async functions are never reported: calling them only
+    /// creates a generator or coroutine.
+    pub fn always_recurses(&self, name: &str) -> bool {
+        self.always_recursive.contains(name)
+    }
+}
+
+/// What a name resolves to in a scope
+#[derive(Debug, Clone)]
+enum Definition {
+    Function(String),
+    Class {
+        name: String,
+        /// Member name to the kinds it is defined as (a property may have a
+        /// getter, a setter and a deleter)
+        members: HashMap<String, Vec<MethodKind>>,
+    },
+    /// A parameter or other local hiding outer definitions
+    Local,
+}
+
+struct Scope {
+    names: HashMap<String, Definition>,
+    is_class: bool,
+}
+
+/// The receiver parameter of the enclosing method and its class
+#[derive(Debug, Clone)]
+struct Receiver {
+    param: String,
+    class: Definition,
+}
+
+/// How a block of statements ends, for recursion detection
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Outcome {
+    /// Every path calls the function
+    Recurses,
+    /// Some path may leave the block without calling it
+    MayExit,
+    /// No call, and control continues after the block
+    FallsThrough,
+}
+
+struct Builder {
+    graph: CallGraph,
+    scopes: Vec<Scope>,
+    caller: String,
+    receiver: Option<Receiver>,
+    /// Classes whose bodies are being visited, innermost last
+    classes: Vec<Definition>,
+}
+
+impl Builder {
+    /// Push a scope holding the definitions made directly in `body`
+    fn enter_scope(&mut self, body: &[Statement], prefix: &str, is_class: bool) {
+        let mut names = HashMap::new();
+        collect_definitions(body, prefix, &mut names);
+        self.scopes.push(Scope { names, is_class });
+    }
+
+    /// Resolve a name the way Python does: class scopes are only visible
+    /// to code directly in the class body
+    fn resolve_name(&self, name: &str) -> Option<&Definition> {
+        let innermost = self.scopes.len().saturating_sub(1);
+        self.scopes
+            .iter()
+            .enumerate()
+            .rev()
+            .filter(|(index, scope)| !scope.is_class || *index == innermost)
+            .find_map(|(_, scope)| scope.names.get(name))
+    }
+
+    /// The class a receiver expression (`self`, `cls`) or class name refers to
+    fn resolve_class(&self, value: &Expression) -> Option<&Definition> {
+        let ExpressionKind::Identifier(name) = &value.kind else {
+            return None;
+        };
+        match &self.receiver {
+            Some(receiver) if receiver.param == *name => Some(&receiver.class),
+            _ => self
+                .resolve_name(name)
+                .filter(|def| matches!(def, Definition::Class { .. })),
+        }
+    }
+
+    /// Member `attr` of the class `value` refers to, if it is defined as
+    /// one of `kinds`, with the qualified name `suffix` gives it
+    fn resolve_member(
+        &self,
+        value: &Expression,
+        attr: &str,
+        kinds: &[MethodKind],
+        suffix: &str,
+    ) -> Option<String> {
+        let Definition::Class { name, members } = self.resolve_class(value)? else {
+            return None;
+        };
+        members
+            .get(attr)?
+            .iter()
+            .any(|kind| kinds.contains(kind))
+            .then(|| format!("{}.{}{}", name, attr, suffix))
+    }
+
+    /// Function called by `func(...)`
+    fn call_target(&self, func: &Expression) -> Option<String> {
+        match &func.kind {
+            ExpressionKind::Identifier(name) => match self.resolve_name(name)? {
+                Definition::Function(qualified) => Some(qualified.clone()),
+                Definition::Class { name, members } => members
+                    .contains_key("__init__")
+                    .then(|| format!("{}.__init__", name)),
+                Definition::Local => None,
+            },
+            ExpressionKind::Attribute { value, attr } => self.resolve_member(
+                value,
+                attr,
+                &[MethodKind::Instance, MethodKind::Class, MethodKind::Static],
+                "",
+            ),
+            _ => None,
+        }
+    }
+
+    /// Property getter called by reading `expr`
+    fn getter_target(&self, expr: &Expression) -> Option<String> {
+        match &expr.kind {
+            ExpressionKind::Attribute { value, attr } => {
+                self.resolve_member(value, attr, &[MethodKind::Property], "")
+            }
+            _ => None,
+        }
+    }
+
+    /// Property setter called by assigning to `target`
+    fn setter_target(&self, target: &Expression) -> Option<String> {
+        match &target.kind {
+            ExpressionKind::Attribute { value, attr } => {
+                self.resolve_member(value, attr, &[MethodKind::PropertySetter], ".setter")
+            }
+            _ => None,
+        }
+    }
+
+    fn add_edge(&mut self, callee: String, span: Span) {
+        self.graph.edges.push(CallEdge {
+            caller: self.caller.clone(),
+            callee,
+            span,
+        });
+    }
+
+    fn visit_block(&mut self, statements: &[Statement]) {
+        for stmt in statements {
+            self.visit_statement(stmt);
+        }
+    }
+
+    fn visit_statement(&mut self, stmt: &Statement) {
+        ensure_sufficient_stack(|| self.visit_statement_kind(stmt))
+    }
+
+    fn visit_statement_kind(&mut self, stmt: &Statement) {
+        match &stmt.kind {
+            StatementKind::FunctionDef {
+                name,
+                params,
+                body,
+                decorator_list,
+                is_async,
+                ..
+            } => {
+                for decorator in decorator_list {
+                    self.visit_expression(decorator);
+                }
+                for param in params.all() {
+                    if let Some(default) = &param.default {
+                        self.visit_expression(default);
+                    }
+                }
+                self.visit_function(name, params, body, decorator_list, *is_async, stmt.span);
+            }
+            StatementKind::ClassDef {
+                name,
+                bases,
+                keywords,
+                body,
+                decorator_list,
+            } => {
+                for expr in decorator_list.iter().chain(bases) {
+                    self.visit_expression(expr);
+                }
+                for keyword in keywords {
+                    self.visit_expression(&keyword.value);
+                }
+                let Some(class @ Definition::Class { .. }) = self.resolve_name(name).cloned()
+                else {
+                    return;
+                };
+                let Definition::Class {
+                    name: qualified, ..
+                } = &class
+                else {
+                    return;
+                };
+                self.enter_scope(body, qualified, true);
+                self.classes.push(class);
+                self.visit_block(body);
+                self.classes.pop();
+                self.scopes.pop();
+            }
+            StatementKind::Assign { targets, value, .. } => {
+                self.visit_expression(value);
+                for target in targets {
+                    self.visit_target(target);
+                }
+            }
+            StatementKind::AugAssign { target, value, .. } => {
+                self.visit_expression(target);
+                self.visit_expression(value);
+                self.visit_target(target);
+            }
+            StatementKind::AnnAssign { target, value, .. } => {
+                if let Some(value) = value {
+                    self.visit_expression(value);
+                    self.visit_target(target);
+                }
+            }
+            _ => {
+                for_each_statement_expression(stmt, &mut |expr| self.visit_expression(expr));
+                for_each_nested_block(stmt, &mut |block| self.visit_block(block));
+            }
+        }
+    }
+
+    /// Assignment target: property setters are calls
+    fn visit_target(&mut self, target: &Expression) {
+        if let Some(setter) = self.setter_target(target) {
+            self.add_edge(setter, target.span);
+        }
+        match &target.kind {
+            ExpressionKind::Attribute { value, .. } => self.visit_expression(value),
+            ExpressionKind::Subscript { value, index } => {
+                self.visit_expression(value);
+                self.visit_expression(index);
+            }
+            ExpressionKind::Tuple { elements } | ExpressionKind::List { elements } => {
+                for element in elements {
+                    self.visit_target(element);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn visit_function(
+        &mut self,
+        name: &str,
+        params: &FunctionParams,
+        body: &[Statement],
+        decorator_list: &[Expression],
+        is_async: bool,
+        span: Span,
+    ) {
+        let in_class = self.scopes.last().is_some_and(|scope| scope.is_class);
+        let class = self.classes.last().filter(|_| in_class).cloned();
+        let kind = MethodKind::from_decorators(name, decorator_list);
+        let qualified = match &class {
+            Some(Definition::Class { name: class, .. }) => match kind {
+                MethodKind::PropertySetter => format!("{}.{}.setter", class, name),
+                MethodKind::PropertyDeleter => format!("{}.{}.deleter", class, name),
+                _ => format!("{}.{}", class, name),
+            },
+            _ => match self.scopes.last().and_then(|scope| scope.names.get(name)) {
+                Some(Definition::Function(qualified)) => qualified.clone(),
+                _ => return,
+            },
+        };
+        self.graph.functions.push(FunctionNode {
+            name: qualified.clone(),
+            span,
+        });
+
+        // Methods resolve `self.m()` (or `cls.m()`) through their receiver
+        let previous_receiver = self.receiver.clone();
+        if kind != MethodKind::Static {
+            if let (Some(param), Some(class)) = (params.positional().next(), class) {
+                self.receiver = Some(Receiver {
+                    param: param.name.clone(),
+                    class,
+                });
+            }
+        }
+
+        self.enter_scope(body, &qualified, false);
+        if let Some(scope) = self.scopes.last_mut() {
+            for param in params.all() {
+                scope.names.insert(param.name.clone(), Definition::Local);
+            }
+        }
+
+        if !is_async
+            && !contains_yield(body)
+            && self.block_outcome(body, &qualified) == Outcome::Recurses
+        {
+            self.graph.always_recursive.insert(qualified.clone());
+        }
+
+        let previous_caller = std::mem::replace(&mut self.caller, qualified);
+        self.visit_block(body);
+        self.caller = previous_caller;
+        self.scopes.pop();
+        self.receiver = previous_receiver;
+    }
+
+    fn visit_expression(&mut self, expr: &Expression) {
+        ensure_sufficient_stack(|| self.visit_expression_kind(expr))
+    }
+
+    fn visit_expression_kind(&mut self, expr: &Expression) {
+        match &expr.kind {
+            ExpressionKind::Call { func, .. } => {
+                if let Some(callee) = self.call_target(func) {
+                    self.add_edge(callee, expr.span);
+                }
+            }
+            ExpressionKind::Attribute { .. } => {
+                if let Some(getter) = self.getter_target(expr) {
+                    self.add_edge(getter, expr.span);
+                }
+            }
+            _ => {}
+        }
+        for (child, _) in subexpressions(expr) {
+            self.visit_expression(child);
+        }
+    }
+
+    // ========== RECURSION DETECTION ==========
+
+    /// Whether evaluating `expr` always calls `function`
+    fn expression_recurses(&self, expr: &Expression, function: &str) -> bool {
+        let direct = match &expr.kind {
+            ExpressionKind::Call { func, .. } => self.call_target(func),
+            ExpressionKind::Attribute { .. } => self.getter_target(expr),
+            _ => None,
+        };
+        direct.as_deref() == Some(function)
+            || ensure_sufficient_stack(|| {
+                subexpressions(expr)
+                    .into_iter()
+                    .any(|(child, always)| always && self.expression_recurses(child, function))
+            })
+    }
+
+    fn block_outcome(&self, statements: &[Statement], function: &str) -> Outcome {
+        for stmt in statements {
+            match self.statement_outcome(stmt, function) {
+                Outcome::FallsThrough => {}
+                outcome => return outcome,
+            }
+        }
+        Outcome::FallsThrough
+    }
+
+    fn statement_outcome(&self, stmt: &Statement, function: &str) -> Outcome {
+        ensure_sufficient_stack(|| self.statement_kind_outcome(stmt, function))
+    }
+
+    fn statement_kind_outcome(&self, stmt: &Statement, function: &str) -> Outcome {
+        let recurses = |expr: &Expression| self.expression_recurses(expr, function);
+        let recurses_opt = |expr: &Option<Expression>| expr.as_ref().is_some_and(recurses);
+
+        match &stmt.kind {
+            StatementKind::Expr(expr) if recurses(expr) => Outcome::Recurses,
+            StatementKind::Assign { targets, value, .. }
+                if recurses(value)
+                    || targets
+                        .iter()
+                        .any(|t| self.setter_target(t).as_deref() == Some(function)) =>
+            {
+                Outcome::Recurses
+            }
+            StatementKind::AugAssign { target, value, .. }
+                if recurses(target) || recurses(value) =>
+            {
+                Outcome::Recurses
+            }
+            StatementKind::AnnAssign { value, .. } if recurses_opt(value) => Outcome::Recurses,
+            StatementKind::Return { value } if recurses_opt(value) => Outcome::Recurses,
+            StatementKind::Raise { exc, .. } if recurses_opt(exc) => Outcome::Recurses,
+            StatementKind::Assert { test, .. } if recurses(test) => Outcome::Recurses,
+            StatementKind::Return { .. }
+            | StatementKind::Raise { .. }
+            | StatementKind::Break
+            | StatementKind::Continue
+            | StatementKind::Assert { .. } => Outcome::MayExit,
+
+            StatementKind::If { test, body, orelse } => {
+                if recurses(test) {
+                    return Outcome::Recurses;
+                }
+                match (
+                    self.block_outcome(body, function),
+                    self.block_outcome(orelse, function),
+                ) {
+                    (Outcome::Recurses, Outcome::Recurses) => Outcome::Recurses,
+                    (Outcome::MayExit, _) | (_, Outcome::MayExit) => Outcome::MayExit,
+                    _ => Outcome::FallsThrough,
+                }
+            }
+            // A loop body may not run at all, so it never guarantees a call
+            StatementKind::While { test, body, orelse } => {
+                if recurses(test) {
+                    return Outcome::Recurses;
+                }
+                loop_outcome(&[
+                    self.block_outcome(body, function),
+                    self.block_outcome(orelse, function),
+                ])
+            }
+            StatementKind::For {
+                iter, body, orelse, ..
+            } => {
+                if recurses(iter) {
+                    return Outcome::Recurses;
+                }
+                loop_outcome(&[
+                    self.block_outcome(body, function),
+                    self.block_outcome(orelse, function),
+                ])
+            }
+            StatementKind::With { items, body, .. } => {
+                if items.iter().any(|item| recurses(&item.context_expr)) {
+                    return Outcome::Recurses;
+                }
+                self.block_outcome(body, function)
+            }
+            // An exception may leave the body early, into a handler
+            StatementKind::Try {
+                body,
+                handlers,
+                orelse,
+                finalbody,
+                ..
+            } => {
+                let finally = self.block_outcome(finalbody, function);
+                if finally == Outcome::Recurses {
+                    return Outcome::Recurses;
+                }
+                let outcomes: Vec<Outcome> = [body, orelse]
+                    .into_iter()
+                    .chain(handlers.iter().map(|handler| &handler.body))
+                    .map(|block| self.block_outcome(block, function))
+                    .chain([finally])
+                    .collect();
+                if outcomes.contains(&Outcome::MayExit) {
+                    Outcome::MayExit
+                } else if outcomes[0] == Outcome::Recurses {
+                    Outcome::Recurses
+                } else {
+                    Outcome::FallsThrough
+                }
+            }
+            StatementKind::Match { subject, cases } => {
+                if recurses(subject) {
+                    return Outcome::Recurses;
+                }
+                loop_outcome(
+                    &cases
+                        .iter()
+                        .map(|case| self.block_outcome(&case.body, function))
+                        .collect::<Vec<_>>(),
+                )
+            }
+            _ => Outcome::FallsThrough,
+        }
+    }
+}
+
+/// Outcome of blocks that may or may not run
+fn loop_outcome(blocks: &[Outcome]) -> Outcome {
+    if blocks.contains(&Outcome::MayExit) {
+        Outcome::MayExit
+    } else {
+        Outcome::FallsThrough
+    }
+}
+
+/// Record the functions and classes defined directly in `body` (including
+/// inside `if`, `try`, loops and `with`, which do not open a scope)
+fn collect_definitions(body: &[Statement], prefix: &str, names: &mut HashMap<String, Definition>) {
+    ensure_sufficient_stack(|| collect_block_definitions(body, prefix, names))
+}
+
+fn collect_block_definitions(
+    body: &[Statement],
+    prefix: &str,
+    names: &mut HashMap<String, Definition>,
+) {
+    let qualify = |name: &str| {
+        if prefix.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}.{}", prefix, name)
+        }
+    };
+    for stmt in body {
+        match &stmt.kind {
+            StatementKind::FunctionDef { name, .. } => {
+                names.insert(name.clone(), Definition::Function(qualify(name)));
+            }
+            StatementKind::ClassDef {
+                name,
+                body: members,
+                ..
+            } => {
+                let mut kinds: HashMap<String, Vec<MethodKind>> = HashMap::new();
+                for member in members {
+                    if let StatementKind::FunctionDef {
+                        name: method,
+                        decorator_list,
+                        ..
+                    } = &member.kind
+                    {
+                        kinds
+                            .entry(method.clone())
+                            .or_default()
+                            .push(MethodKind::from_decorators(method, decorator_list));
+                    }
+                }
+                names.insert(
+                    name.clone(),
+                    Definition::Class {
+                        name: qualify(name),
+                        members: kinds,
+                    },
+                );
+            }
+            _ => {
+                for_each_nested_block(stmt, &mut |block| collect_definitions(block, prefix, names))
+            }
+        }
+    }
+}
+
+/// Call `f` with each expression a statement evaluates directly (not those
+/// in nested blocks)
+fn for_each_statement_expression(stmt: &Statement, f: &mut impl FnMut(&Expression)) {
+    match &stmt.kind {
+        StatementKind::Expr(expr) => f(expr),
+        StatementKind::Assert { test, msg } => {
+            f(test);
+            msg.iter().for_each(f);
+        }
+        StatementKind::Delete { targets } => targets.iter().for_each(f),
+        StatementKind::Return { value } => value.iter().for_each(f),
+        StatementKind::Raise { exc, cause } => exc.iter().chain(cause).for_each(f),
+        StatementKind::If { test, .. } | StatementKind::While { test, .. } => f(test),
+        StatementKind::For { iter, .. } => f(iter),
+        StatementKind::With { items, .. } => {
+            for item in items {
+                f(&item.context_expr);
+            }
+        }
+        StatementKind::Match { subject, cases } => {
+            f(subject);
+            for case in cases {
+                case.pattern.expressions().into_iter().for_each(&mut *f);
+                case.guard.iter().for_each(&mut *f);
+            }
+        }
+        StatementKind::Try { handlers, .. } => {
+            handlers.iter().filter_map(|h| h.typ.as_ref()).for_each(f);
+        }
+        _ => {}
+    }
+}
+
+/// Call `f` with each block nested in a compound statement, except function
+/// and class bodies
+pub(crate) fn for_each_nested_block<'a>(stmt: &'a Statement, f: &mut impl FnMut(&'a [Statement])) {
+    match &stmt.kind {
+        StatementKind::If { body, orelse, .. }
+        | StatementKind::While { body, orelse, .. }
+        | StatementKind::For { body, orelse, .. } => {
+            f(body);
+            f(orelse);
+        }
+        StatementKind::With { body, .. } => f(body),
+        StatementKind::Try {
+            body,
+            handlers,
+            orelse,
+            finalbody,
+            ..
+        } => {
+            f(body);
+            for handler in handlers {
+                f(&handler.body);
+            }
+            f(orelse);
+            f(finalbody);
+        }
+        StatementKind::Match { cases, .. } => {
+            for case in cases {
+                f(&case.body);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Direct subexpressions of `expr`, each paired with whether evaluating
+/// `expr` always evaluates it
+///
+/// Lambda bodies are not included: they run when the lambda is called.
+pub(crate) fn subexpressions(expr: &Expression) -> Vec<(&Expression, bool)> {
+    fn always(expr: &Expression) -> (&Expression, bool) {
+        (expr, true)
+    }
+
+    match &expr.kind {
+        ExpressionKind::BinaryOp { left, right, .. } => vec![always(left), always(right)],
+        ExpressionKind::UnaryOp { operand, .. } => vec![always(operand)],
+        // Chained comparisons stop at the first false comparison
+        ExpressionKind::Compare {
+            left, comparators, ..
+        } => std::iter::once(always(left))
+            .chain(
+                comparators
+                    .iter()
+                    .enumerate()
+                    .map(|(index, comparator)| (comparator, index == 0)),
+            )
+            .collect(),
+        ExpressionKind::LogicalOp { left, right, .. } => vec![always(left), (right, false)],
+        ExpressionKind::Call {
+            func,
+            args,
+            keywords,
+        } => std::iter::once(always(func))
+            .chain(args.iter().map(always))
+            .chain(keywords.iter().map(|keyword| always(&keyword.value)))
+            .collect(),
+        ExpressionKind::Attribute { value, .. } => vec![always(value)],
+        ExpressionKind::Subscript { value, index } => vec![always(value), always(index)],
+        ExpressionKind::Slice { lower, upper, step } => [lower, upper, step]
+            .into_iter()
+            .flatten()
+            .map(|e| always(e))
+            .collect(),
+        ExpressionKind::List { elements }
+        | ExpressionKind::Tuple { elements }
+        | ExpressionKind::Set { elements } => elements.iter().map(always).collect(),
+        ExpressionKind::Dict { keys, values } => keys.iter().chain(values).map(always).collect(),
+        // Only the first iterable is evaluated before the first item
+        ExpressionKind::ListComp {
+            element,
+            generators,
+        }
+        | ExpressionKind::SetComp {
+            element,
+            generators,
+        }
+        | ExpressionKind::GeneratorExp {
+            element,
+            generators,
+        } => comprehension_parts(generators, [element.as_ref()]),
+        ExpressionKind::DictComp {
+            key,
+            value,
+            generators,
+        } => comprehension_parts(generators, [key.as_ref(), value.as_ref()]),
+        ExpressionKind::Lambda { params, .. } => params
+            .iter()
+            .filter_map(|param| param.default.as_ref())
+            .map(always)
+            .collect(),
+        ExpressionKind::IfExp { test, body, orelse } => {
+            vec![always(test), (body, false), (orelse, false)]
+        }
+        ExpressionKind::NamedExpr { value, .. } => vec![always(value)],
+        ExpressionKind::Await { value } | ExpressionKind::YieldFrom { value } => {
+            vec![always(value)]
+        }
+        ExpressionKind::Yield { value } => value.iter().map(|e| always(e)).collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn comprehension_parts<'a, const N: usize>(
+    generators: &'a [silk_ast::Comprehension],
+    elements: [&'a Expression; N],
+) -> Vec<(&'a Expression, bool)> {
+    let mut parts = Vec::new();
+    for (index, generator) in generators.iter().enumerate() {
+        parts.push((&generator.iter, index == 0));
+        parts.extend(generator.ifs.iter().map(|cond| (cond, false)));
+    }
+    parts.extend(elements.into_iter().map(|element| (element, false)));
+    parts
+}
+
+/// Whether a function body yields (making the function a generator)
+pub(crate) fn contains_yield(body: &[Statement]) -> bool {
+    fn expression_yields(expr: &Expression) -> bool {
+        ensure_sufficient_stack(|| {
+            matches!(
+                expr.kind,
+                ExpressionKind::Yield { .. } | ExpressionKind::YieldFrom { .. }
+            ) || subexpressions(expr)
+                .into_iter()
+                .any(|(child, _)| expression_yields(child))
+        })
+    }
+
+    ensure_sufficient_stack(|| {
+        body.iter().any(|stmt| {
+            let mut found = match &stmt.kind {
+                StatementKind::Assign { value, .. } | StatementKind::AugAssign { value, .. } => {
+                    expression_yields(value)
+                }
+                StatementKind::AnnAssign { value, .. } => {
+                    value.as_ref().is_some_and(expression_yields)
+                }
+                _ => false,
+            };
+            for_each_statement_expression(stmt, &mut |expr| found |= expression_yields(expr));
+            for_each_nested_block(stmt, &mut |block| found |= contains_yield(block));
+            found
+        })
+    })
+}
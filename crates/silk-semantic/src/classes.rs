@@ -0,0 +1,310 @@
+//! Class member information for semantic analysis
+//!
+//! Tracks the methods declared in a class body, the attributes it assigns,
+//! and how each method is bound:
+//! - Instance methods receive the instance as `self`
+//! - `@classmethod` methods receive the class as `cls`
+//! - `@staticmethod` methods receive no implicit argument
+//! - `@property` getters are read like attributes (`obj.name` calls the getter)
+//!
+//! Attribute lookup follows the C3 method resolution order (MRO), the same
+//! linearization Python uses for single and multiple inheritance.
+
+use crate::call_graph::for_each_nested_block;
+use crate::signature::Signature;
+use crate::types::Type;
+use silk_ast::{Expression, ExpressionKind, Statement, StatementKind};
+use silk_lexer::Span;
+use std::collections::{HashMap, HashSet};
+
+/// How a method defined in a class body is bound
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MethodKind {
+    /// Regular method, receives `self`
+    Instance,
+    /// `@staticmethod`, receives no implicit argument
+    Static,
+    /// `@classmethod`, receives `cls`
+    Class,
+    /// `@property` getter, receives `self`
+    Property,
+    /// `@name.setter`, receives `self` and the new value
+    PropertySetter,
+    /// `@name.deleter`, receives `self`
+    PropertyDeleter,
+}
+
+impl MethodKind {
+    /// Determine the method kind from its name and decorator list
+    pub fn from_decorators(name: &str, decorators: &[Expression]) -> Self {
+        for decorator in decorators {
+            match &decorator.kind {
+                ExpressionKind::Identifier(id) => match id.as_str() {
+                    "staticmethod" => return MethodKind::Static,
+                    "classmethod" => return MethodKind::Class,
+                    "property" => return MethodKind::Property,
+                    _ => {}
+                },
+                ExpressionKind::Attribute { attr, .. } => match attr.as_str() {
+                    "setter" => return MethodKind::PropertySetter,
+                    "deleter" => return MethodKind::PropertyDeleter,
+                    "getter" => return MethodKind::Property,
+                    _ => {}
+                },
+                _ => {}
+            }
+        }
+
+        // Implicit class methods
+        match name {
+            "__new__" | "__init_subclass__" | "__class_getitem__" => MethodKind::Class,
+            _ => MethodKind::Instance,
+        }
+    }
+
+    /// Name of the conventional first parameter, if the method receives one
+    pub fn receiver(self) -> Option<&'static str> {
+        match self {
+            MethodKind::Instance
+            | MethodKind::Property
+            | MethodKind::PropertySetter
+            | MethodKind::PropertyDeleter => Some("self"),
+            MethodKind::Class => Some("cls"),
+            MethodKind::Static => None,
+        }
+    }
+
+    /// Human-readable description used in diagnostics
+    pub fn description(self) -> &'static str {
+        match self {
+            MethodKind::Instance => "Method",
+            MethodKind::Static => "Static method",
+            MethodKind::Class => "Class method",
+            MethodKind::Property => "Property getter",
+            MethodKind::PropertySetter => "Property setter",
+            MethodKind::PropertyDeleter => "Property deleter",
+        }
+    }
+
+    /// Check if this is a property accessor (getter, setter, or deleter)
+    pub fn is_property(self) -> bool {
+        matches!(
+            self,
+            MethodKind::Property | MethodKind::PropertySetter | MethodKind::PropertyDeleter
+        )
+    }
+}
+
+/// A method declared in a class body
+#[derive(Debug, Clone)]
+pub struct MethodInfo {
+    /// Method name
+    pub name: String,
+    /// How the method is bound
+    pub kind: MethodKind,
+    /// Parameters after the implicit receiver
+    pub params: Signature,
+    /// Declared return type (Unknown if not annotated)
+    pub return_type: Type,
+    /// Location of the method definition
+    pub span: Span,
+}
+
+/// Members of a class collected from its body
+#[derive(Debug, Clone)]
+pub struct ClassInfo {
+    /// Class name
+    pub name: String,
+    /// Name qualified by the enclosing definitions, as Python's
+    /// `__qualname__` (`Outer.Inner`, `make.<locals>.Node`); classes are
+    /// told apart by it
+    pub qualname: String,
+    /// Qualified names of the direct base classes, in declaration order
+    pub bases: Vec<String>,
+    /// Method resolution order by qualified name, starting with the class
+    /// itself (empty until computed by the analyzer)
+    pub mro: Vec<String>,
+    /// Methods by name (property getters are stored under the property name)
+    pub methods: HashMap<String, MethodInfo>,
+    /// Class variables and instance attributes: names assigned in the class
+    /// body, and attributes assigned through the receiver in its methods
+    pub attributes: HashSet<String>,
+    /// Whether a base is not a plain name (e.g. `module.Base`), so members
+    /// may come from a class the analyzer does not know
+    pub unresolved_bases: bool,
+    /// Location of the class definition
+    pub span: Span,
+}
+
+impl ClassInfo {
+    /// Create an empty class description from its qualified name
+    pub fn new(qualname: String, span: Span) -> Self {
+        Self {
+            name: short_name(&qualname).to_string(),
+            qualname,
+            bases: Vec::new(),
+            mro: Vec::new(),
+            methods: HashMap::new(),
+            attributes: HashSet::new(),
+            unresolved_bases: false,
+            span,
+        }
+    }
+
+    /// Record a method; setters and deleters never replace the property getter
+    pub fn add_method(&mut self, method: MethodInfo) {
+        if matches!(
+            method.kind,
+            MethodKind::PropertySetter | MethodKind::PropertyDeleter
+        ) && self.methods.contains_key(&method.name)
+        {
+            return;
+        }
+        self.methods.insert(method.name.clone(), method);
+    }
+
+    /// Look up a method or property by name
+    pub fn method(&self, name: &str) -> Option<&MethodInfo> {
+        self.methods.get(name)
+    }
+
+    /// Check if the class itself defines a method or attribute
+    pub fn has_member(&self, name: &str) -> bool {
+        self.methods.contains_key(name) || self.attributes.contains(name)
+    }
+
+    /// Names of the methods and attributes the class itself defines, sorted
+    pub fn member_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self
+            .methods
+            .keys()
+            .chain(&self.attributes)
+            .map(String::as_str)
+            .collect();
+        names.sort_unstable();
+        names.dedup();
+        names
+    }
+
+    /// Record the names a class body statement assigns
+    pub fn add_class_variables(&mut self, stmt: &Statement) {
+        match &stmt.kind {
+            StatementKind::Assign { targets, .. } => {
+                for target in targets {
+                    add_target_names(target, &mut self.attributes);
+                }
+            }
+            StatementKind::AnnAssign { target, .. } | StatementKind::AugAssign { target, .. } => {
+                add_target_names(target, &mut self.attributes);
+            }
+            StatementKind::ClassDef { name, .. } => {
+                self.attributes.insert(name.clone());
+            }
+            _ => for_each_nested_block(stmt, &mut |block| {
+                block.iter().for_each(|stmt| self.add_class_variables(stmt))
+            }),
+        }
+    }
+
+    /// Record the attributes a method body assigns through its receiver
+    /// (`self.name = ...`), including in nested blocks but not in nested
+    /// functions
+    pub fn add_receiver_attributes(&mut self, receiver: &str, body: &[Statement]) {
+        for stmt in body {
+            let targets: Vec<&Expression> = match &stmt.kind {
+                StatementKind::Assign { targets, .. } => targets.iter().collect(),
+                // `self.x += 1` needs `x` to exist already
+                StatementKind::AnnAssign { target, .. } => vec![target],
+                StatementKind::FunctionDef { .. } | StatementKind::ClassDef { .. } => continue,
+                _ => {
+                    for_each_nested_block(stmt, &mut |block| {
+                        self.add_receiver_attributes(receiver, block)
+                    });
+                    continue;
+                }
+            };
+            for target in targets {
+                add_receiver_targets(receiver, target, &mut self.attributes);
+            }
+        }
+    }
+
+    /// Look up a property getter by name
+    pub fn property(&self, name: &str) -> Option<&MethodInfo> {
+        self.methods.get(name).filter(|m| m.kind.is_property())
+    }
+}
+
+/// Name of a class from its qualified name (`Outer.Inner` is `Inner`)
+pub fn short_name(qualname: &str) -> &str {
+    qualname.rsplit_once('.').map_or(qualname, |(_, name)| name)
+}
+
+/// Attributes of `receiver` bound by an assignment target (`self.x`, or each
+/// of `self.x, self.y`)
+fn add_receiver_targets(receiver: &str, target: &Expression, names: &mut HashSet<String>) {
+    match &target.kind {
+        ExpressionKind::Attribute { value, attr } => {
+            if matches!(&value.kind, ExpressionKind::Identifier(name) if name == receiver) {
+                names.insert(attr.clone());
+            }
+        }
+        ExpressionKind::Tuple { elements } | ExpressionKind::List { elements } => {
+            for element in elements {
+                add_receiver_targets(receiver, element, names);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Names bound by an assignment target (`x`, or each name of `x, y`)
+pub(crate) fn add_target_names(target: &Expression, names: &mut HashSet<String>) {
+    match &target.kind {
+        ExpressionKind::Identifier(name) => {
+            names.insert(name.clone());
+        }
+        ExpressionKind::Tuple { elements } | ExpressionKind::List { elements } => {
+            for element in elements {
+                add_target_names(element, names);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Compute the C3 linearization of a class
+///
+/// `base_mros` holds the linearization of each direct base, in the same order
+/// as `bases`. Returns `None` when no consistent order exists, e.g.
+/// `class C(A, B)` where `B` already inherits from `A`.
+pub fn c3_linearization(
+    name: &str,
+    bases: &[String],
+    base_mros: Vec<Vec<String>>,
+) -> Option<Vec<String>> {
+    let mut result = vec![name.to_string()];
+    let mut sequences = base_mros;
+    sequences.push(bases.to_vec());
+
+    loop {
+        sequences.retain(|seq| !seq.is_empty());
+        if sequences.is_empty() {
+            return Some(result);
+        }
+
+        // Pick the first head that does not appear in the tail of any sequence
+        let candidate = sequences
+            .iter()
+            .map(|seq| &seq[0])
+            .find(|head| !sequences.iter().any(|seq| seq[1..].contains(head)))?
+            .clone();
+
+        for seq in sequences.iter_mut() {
+            if seq[0] == candidate {
+                seq.remove(0);
+            }
+        }
+        result.push(candidate);
+    }
+}
@@ -0,0 +1,171 @@
+//! Duplicated code (`duplicate-code`, allowed by default)
+//!
+//! Function bodies and runs of consecutive statements are compared with
+//! their spans and node ids left out, so copies that differ only in layout
+//! or comments match. Code counts as duplicated when it has at least a
+//! minimum number of nodes (statements and expressions):
+//! - a function whose body repeats the body of an earlier function is
+//!   reported once, on the function, whatever it is named
+//! - elsewhere, the shortest run of statements that is large enough is
+//!   looked up from each statement on, and a repeated run is extended for
+//!   as long as the statements after both copies keep matching
+//!
+//! Each copy is reported against the first one in source order; code
+//! inside a reported copy is not checked again.
+
+use crate::call_graph::for_each_nested_block;
+use crate::conditions::fingerprint;
+use silk_ast::{Statement, StatementKind};
+use silk_lexer::Span;
+use std::collections::HashMap;
+
+/// Default of the smallest duplicate reported, in nodes
+pub const DEFAULT_DUPLICATE_MIN_NODES: usize = 25;
+
+/// A copy of earlier code
+pub(crate) struct Duplicate {
+    /// `"function body"` or `"statements"`
+    pub kind: &'static str,
+    pub nodes: usize,
+    /// Where the first copy starts
+    pub first: Span,
+    pub span: Span,
+}
+
+/// Find the copies among `statements` and the blocks nested in them
+pub(crate) fn duplicate_code(statements: &[Statement], min_nodes: usize) -> Vec<Duplicate> {
+    let mut detector = Detector {
+        min_nodes: min_nodes.max(1),
+        blocks: Vec::new(),
+        runs: HashMap::new(),
+        bodies: HashMap::new(),
+        duplicates: Vec::new(),
+    };
+    detector.block(Block::new(statements));
+    detector.duplicates
+}
+
+/// A block with the fingerprint and node count of each statement
+struct Block<'a> {
+    statements: &'a [Statement],
+    fingerprints: Vec<String>,
+    nodes: Vec<usize>,
+}
+
+impl<'a> Block<'a> {
+    fn new(statements: &'a [Statement]) -> Self {
+        let fingerprints: Vec<String> = statements.iter().map(fingerprint).collect();
+        // Every statement and expression carries an id, which the
+        // fingerprint keeps the name of
+        let nodes = fingerprints
+            .iter()
+            .map(|fingerprint| fingerprint.matches("NodeId(").count())
+            .collect();
+        Self {
+            statements,
+            fingerprints,
+            nodes,
+        }
+    }
+}
+
+struct Detector<'a> {
+    min_nodes: usize,
+    blocks: Vec<Block<'a>>,
+    /// First run of statements with a fingerprint: block and start index
+    runs: HashMap<String, (usize, usize)>,
+    /// First function body with a fingerprint: span of the function
+    bodies: HashMap<String, Span>,
+    duplicates: Vec<Duplicate>,
+}
+
+impl<'a> Detector<'a> {
+    fn block(&mut self, block: Block<'a>) {
+        let statements = block.statements;
+        let index = self.blocks.len();
+        self.blocks.push(block);
+
+        let mut start = 0;
+        while start < statements.len() {
+            if let Some(len) = self.run(index, start) {
+                start += len;
+                continue;
+            }
+            self.nested(&statements[start]);
+            start += 1;
+        }
+    }
+
+    /// Look up the run of statements starting at `start`, registering it
+    /// when it is the first copy; returns the length of a reported copy
+    fn run(&mut self, index: usize, start: usize) -> Option<usize> {
+        let block = &self.blocks[index];
+        let mut nodes = 0;
+        let end = (start..block.statements.len()).find(|&end| {
+            nodes += block.nodes[end];
+            nodes >= self.min_nodes
+        })? + 1;
+        let key = block.fingerprints[start..end].join("\n");
+
+        let Some(&(first_index, first_start)) = self.runs.get(&key) else {
+            self.runs.insert(key, (index, start));
+            return None;
+        };
+        let first = &self.blocks[first_index];
+        let mut len = end - start;
+        while start + len < block.statements.len()
+            && first_start + len < first.statements.len()
+            && !(first_index == index && first_start + len >= start)
+            && first.fingerprints[first_start + len] == block.fingerprints[start + len]
+        {
+            nodes += block.nodes[start + len];
+            len += 1;
+        }
+
+        let (head, last) = (&block.statements[start], &block.statements[start + len - 1]);
+        self.duplicates.push(Duplicate {
+            kind: "statements",
+            nodes,
+            first: first.statements[first_start].span,
+            span: Span::new(
+                head.span.start,
+                last.span.end,
+                head.span.line,
+                head.span.column,
+            ),
+        });
+        Some(len)
+    }
+
+    /// Check the blocks of a statement that is not part of a copy
+    fn nested(&mut self, statement: &'a Statement) {
+        match &statement.kind {
+            StatementKind::FunctionDef { body, .. } => {
+                let block = Block::new(body);
+                let nodes = block.nodes.iter().sum();
+                if nodes >= self.min_nodes {
+                    let key = block.fingerprints.join("\n");
+                    if let Some(&first) = self.bodies.get(&key) {
+                        self.duplicates.push(Duplicate {
+                            kind: "function body",
+                            nodes,
+                            first,
+                            span: statement.span,
+                        });
+                        return;
+                    }
+                    self.bodies.insert(key, statement.span);
+                }
+                self.block(block);
+            }
+            StatementKind::ClassDef { body, .. } => self.block(Block::new(body)),
+            _ => {
+                let mut blocks = Vec::new();
+                for_each_nested_block(statement, &mut |block| blocks.push(block));
+                for block in blocks {
+                    self.block(Block::new(block));
+                }
+            }
+        }
+    }
+}
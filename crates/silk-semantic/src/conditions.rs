@@ -0,0 +1,225 @@
+//! Suspicious conditions and comparisons
+//!
+//! Checks that need no type information, used by control flow analysis:
+//! - Comparisons of a variable with itself (`x == x`)
+//! - Equality between literals of unrelated types (`"a" == 1`)
+//! - `if`/`elif`/`else` chains with a repeated condition or body
+
+use silk_ast::{
+    CompareOperator, Expression, ExpressionKind, Statement, StatementKind, UnaryOperator,
+};
+use silk_lexer::Span;
+use std::fmt::Debug;
+
+/// Value of a comparison between a variable and itself: `x == x`, `x < x`
+///
+/// Only plain names and attribute chains count; calls may return a
+/// different value each time.
+pub(crate) fn self_comparison(
+    left: &Expression,
+    op: CompareOperator,
+    right: &Expression,
+) -> Option<(String, bool)> {
+    let name = dotted_name(left)?;
+    if dotted_name(right).as_deref() != Some(name.as_str()) {
+        return None;
+    }
+    let result = match op {
+        CompareOperator::Eq | CompareOperator::LtE | CompareOperator::GtE | CompareOperator::Is => {
+            true
+        }
+        CompareOperator::NotEq
+        | CompareOperator::Lt
+        | CompareOperator::Gt
+        | CompareOperator::IsNot => false,
+        CompareOperator::In | CompareOperator::NotIn => return None,
+    };
+    Some((name, result))
+}
+
+/// Type names and value of an equality check between literals of types
+/// that are never equal: `"a" == 1` is always false
+pub(crate) fn incompatible_comparison(
+    left: &Expression,
+    op: CompareOperator,
+    right: &Expression,
+) -> Option<(&'static str, &'static str, bool)> {
+    let result = match op {
+        CompareOperator::Eq | CompareOperator::Is => false,
+        CompareOperator::NotEq | CompareOperator::IsNot => true,
+        _ => return None,
+    };
+    let (left, right) = (literal(left)?, literal(right)?);
+    if left.category() == right.category() {
+        return None;
+    }
+    Some((left.type_name(), right.type_name(), result))
+}
+
+/// A branch of an `if`/`elif`/`else` chain that repeats an earlier one
+pub(crate) struct DuplicateBranch {
+    /// `"condition"` or `"body"`
+    pub kind: &'static str,
+    pub first_line: usize,
+    pub span: Span,
+}
+
+/// Find repeated conditions and bodies in the chain starting at an `if`
+/// statement; each branch is reported once, against its first copy
+pub(crate) fn duplicate_branches(stmt: &Statement) -> Vec<DuplicateBranch> {
+    // (condition, body, span) per branch; `else` has no condition
+    let mut branches: Vec<(Option<String>, String, Span)> = Vec::new();
+    let mut current = stmt;
+    while let StatementKind::If { test, body, orelse } = &current.kind {
+        branches.push((
+            Some(fingerprint(test)),
+            body_fingerprint(body),
+            current.span,
+        ));
+        match orelse.as_slice() {
+            [] => break,
+            [next @ Statement {
+                kind: StatementKind::If { .. },
+                ..
+            }] => current = next,
+            _ => {
+                branches.push((None, body_fingerprint(orelse), orelse[0].span));
+                break;
+            }
+        }
+    }
+
+    let mut duplicates = Vec::new();
+    for (index, (test, body, span)) in branches.iter().enumerate() {
+        let earlier = &branches[..index];
+        let same_test = earlier
+            .iter()
+            .find(|(other, ..)| test.is_some() && other == test)
+            .map(|(.., first)| ("condition", first));
+        let same_body = || {
+            earlier
+                .iter()
+                .find(|(_, other, _)| other == body && !body.is_empty())
+                .map(|(.., first)| ("body", first))
+        };
+        if let Some((kind, first)) = same_test.or_else(same_body) {
+            duplicates.push(DuplicateBranch {
+                kind,
+                first_line: first.line,
+                span: *span,
+            });
+        }
+    }
+    duplicates
+}
+
+/// Fingerprint of a branch body; empty for a lone `pass`, `...`, `break`,
+/// `continue` or bare `return`, which are too small to be worth merging
+fn body_fingerprint(body: &[Statement]) -> String {
+    let trivial = match body {
+        [stmt] => match &stmt.kind {
+            StatementKind::Pass
+            | StatementKind::Break
+            | StatementKind::Continue
+            | StatementKind::Return { value: None } => true,
+            StatementKind::Expr(expr) => matches!(expr.kind, ExpressionKind::Ellipsis),
+            _ => false,
+        },
+        _ => false,
+    };
+    if trivial {
+        String::new()
+    } else {
+        fingerprint(&body)
+    }
+}
+
+/// Literal operand of a comparison
+enum Literal {
+    /// Number with its type name (`int`, `float`, `bool`)
+    Number(&'static str),
+    Str,
+    Bytes,
+    None,
+    /// Container display, with its type name
+    Container(&'static str),
+}
+
+impl Literal {
+    /// Literals of different categories are never equal
+    fn category(&self) -> &'static str {
+        match self {
+            Literal::Number(_) => "number",
+            Literal::Str => "str",
+            Literal::Bytes => "bytes",
+            Literal::None => "None",
+            Literal::Container(name) => name,
+        }
+    }
+
+    fn type_name(&self) -> &'static str {
+        match self {
+            Literal::Number(name) => name,
+            other => other.category(),
+        }
+    }
+}
+
+fn literal(expr: &Expression) -> Option<Literal> {
+    Some(match &expr.kind {
+        ExpressionKind::Integer(_) => Literal::Number("int"),
+        ExpressionKind::Float(_) => Literal::Number("float"),
+        ExpressionKind::Boolean(_) => Literal::Number("bool"),
+        ExpressionKind::String(_)
+        | ExpressionKind::RawString(_)
+        | ExpressionKind::FString { .. } => Literal::Str,
+        ExpressionKind::ByteString(_) | ExpressionKind::ByteRawString(_) => Literal::Bytes,
+        ExpressionKind::None => Literal::None,
+        ExpressionKind::List { .. } => Literal::Container("list"),
+        ExpressionKind::Tuple { .. } => Literal::Container("tuple"),
+        ExpressionKind::Dict { .. } => Literal::Container("dict"),
+        ExpressionKind::Set { .. } => Literal::Container("set"),
+        ExpressionKind::UnaryOp {
+            op: UnaryOperator::USub | UnaryOperator::UAdd,
+            operand,
+        } => match literal(operand)? {
+            // `-True` is an int
+            Literal::Number("bool") => Literal::Number("int"),
+            number @ Literal::Number(_) => number,
+            _ => return None,
+        },
+        _ => return None,
+    })
+}
+
+/// `x`, `self.x` or `a.b.c`
+pub(crate) fn dotted_name(expr: &Expression) -> Option<String> {
+    match &expr.kind {
+        ExpressionKind::Identifier(name) => Some(name.clone()),
+        ExpressionKind::Attribute { value, attr } => {
+            Some(format!("{}.{}", dotted_name(value)?, attr))
+        }
+        _ => None,
+    }
+}
+
+/// Debug rendering of AST nodes with every span and node id blanked out,
+/// so the same code at two places renders the same
+pub(crate) fn fingerprint(node: &impl Debug) -> String {
+    let text = format!("{:?}", node);
+    blank(&blank(&text, "Span { ", '}'), "NodeId(", ')')
+}
+
+/// Replace each `open ... close` in a rendering by `open`
+fn blank(text: &str, open: &str, close: char) -> String {
+    let mut blanked = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find(open) {
+        blanked.push_str(&rest[..start + open.len()]);
+        rest = &rest[start..];
+        let end = rest.find(close).map_or(rest.len(), |end| end + 1);
+        rest = &rest[end..];
+    }
+    blanked.push_str(rest);
+    blanked
+}
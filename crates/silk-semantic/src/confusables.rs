@@ -0,0 +1,104 @@
+//! Confusable identifier lint
+//!
+//! `confusable-identifier` warns about a defined name with non-ASCII
+//! characters that a reader could take for a different name:
+//! - a name containing an invisible joiner (U+200C or U+200D)
+//! - a name mixing scripts, such as a Cyrillic `а` in a Latin word
+//! - a name that looks like another defined name or a builtin: both have
+//!   the same skeleton as defined by Unicode TR39, but different spellings
+//!
+//! The lexer already normalizes identifiers to NFKC, so names that only
+//! differ in compatibility forms (`ﬁle` and `file`) are the same name.
+//! Bidirectional control characters are not identifier characters at all
+//! and are rejected by the lexer.
+
+use crate::builtins::{BUILTIN_EXCEPTIONS, BUILTIN_FUNCTIONS};
+use crate::symbol_table::{Symbol, SymbolTable};
+use crate::SemanticError;
+use std::collections::{HashMap, HashSet};
+use unicode_script::{Script, UnicodeScript};
+use unicode_security::{skeleton, MixedScript};
+
+/// Characters that render as nothing but still make names differ
+const INVISIBLE: &[char] = &['\u{200c}', '\u{200d}'];
+
+/// Where a name that another one looks like comes from
+enum Origin {
+    Builtin,
+    /// Defined by the program, on this line
+    Defined(usize),
+}
+
+/// Check every name defined in the program, in source order
+pub(crate) fn check_identifiers(symbols: &SymbolTable) -> Vec<SemanticError> {
+    let mut defined: Vec<&Symbol> = symbols
+        .scopes()
+        .iter()
+        .flat_map(|scope| scope.symbols().values())
+        .collect();
+    defined.sort_by(|a, b| (a.span.start, &a.name).cmp(&(b.span.start, &b.name)));
+    // A name defined in several scopes is reported once, where it first
+    // appears
+    let mut first = HashSet::new();
+    defined.retain(|symbol| first.insert(symbol.name.as_str()));
+
+    let mut seen: HashMap<String, (&str, Origin)> = HashMap::new();
+    for &name in BUILTIN_FUNCTIONS.iter().chain(BUILTIN_EXCEPTIONS) {
+        seen.insert(skeleton(name).collect(), (name, Origin::Builtin));
+    }
+
+    let mut errors = Vec::new();
+    for symbol in defined {
+        let name = symbol.name.as_str();
+        let key: String = skeleton(name).collect();
+        let reason = if name.contains(INVISIBLE) {
+            Some(format!(
+                "it contains the invisible character U+{:04X}",
+                name.chars().find(|ch| INVISIBLE.contains(ch)).unwrap() as u32
+            ))
+        } else if !name.is_ascii() && !name.is_single_script() {
+            Some(format!(
+                "it mixes the {} scripts",
+                scripts(name).join(" and ")
+            ))
+        } else {
+            match seen.get(&key) {
+                // Plain ASCII names that look alike (`l` and `I`) are left
+                // to the font
+                Some((other, _)) if other.is_ascii() && name.is_ascii() => None,
+                Some((other, Origin::Builtin)) => {
+                    Some(format!("it looks like the builtin '{}'", other))
+                }
+                Some((other, Origin::Defined(line))) => Some(format!(
+                    "it looks like '{}' defined at line {}",
+                    other, line
+                )),
+                None => None,
+            }
+        };
+        if let Some(reason) = reason {
+            errors.push(SemanticError::ConfusableIdentifier {
+                name: name.to_string(),
+                reason,
+                line: symbol.span.line,
+                column: symbol.span.column,
+                span: symbol.span,
+            });
+        }
+        seen.entry(key)
+            .or_insert((name, Origin::Defined(symbol.span.line)));
+    }
+    errors
+}
+
+/// Names of the scripts used in `name`, in order of first use
+fn scripts(name: &str) -> Vec<&'static str> {
+    let mut scripts = Vec::new();
+    for ch in name.chars() {
+        let script = ch.script();
+        if !matches!(script, Script::Common | Script::Inherited) && !scripts.contains(&script) {
+            scripts.push(script);
+        }
+    }
+    scripts.iter().map(|script| script.full_name()).collect()
+}
@@ -0,0 +1,515 @@
+//! Values of constant expressions
+//!
+//! One evaluator for every analysis that needs to know what an expression is
+//! without running the program: constant folding, constant conditions,
+//! endless loops, asserts and match cases. Numbers are folded through
+//! [`constants`](crate::constants), so an operation that overflows or
+//! divides by zero has no value. Strings, bytes and tuples are joined and
+//! repeated up to [`MAX_LENGTH`], `and`/`or`/`not` and comparisons follow
+//! Python's rules, and `len` of a literal is known. `len` is assumed to be
+//! the builtin. Calls of pure functions that return one expression are
+//! folded when the caller says which functions those are
+//! ([`evaluate_with`]).
+
+use crate::constants::{self, Constant};
+use crate::docstrings::docstring_of;
+use silk_ast::{
+    ensure_sufficient_stack, BinaryOperator, CompareOperator, Expression, ExpressionKind,
+    LogicalOperator, Pattern, PatternKind, Statement, StatementKind, UnaryOperator,
+};
+use silk_lexer::Span;
+use std::cell::Cell;
+use std::collections::HashMap;
+
+/// Longest string, bytes or tuple that folding builds; longer results are
+/// left unknown
+const MAX_LENGTH: usize = 4096;
+
+/// Deepest chain of calls folded, and most calls folded for one
+/// expression; recursion past either is left unknown
+const MAX_CALL_DEPTH: usize = 64;
+const MAX_CALLS: usize = 1000;
+
+/// Value of a constant expression
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Value {
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+    Str(String),
+    Bytes(Vec<u8>),
+    None,
+    Ellipsis,
+    Tuple(Vec<Value>),
+    /// Display whose items are not all constant: only its type and, for
+    /// lists and tuples, its length are known
+    Container {
+        type_name: &'static str,
+        len: Option<usize>,
+    },
+}
+
+impl Value {
+    /// Value of `bool(self)`
+    pub fn is_truthy(&self) -> bool {
+        match self {
+            Value::Int(value) => *value != 0,
+            Value::Float(value) => *value != 0.0,
+            Value::Bool(value) => *value,
+            Value::Str(text) => !text.is_empty(),
+            Value::Bytes(bytes) => !bytes.is_empty(),
+            Value::None => false,
+            Value::Ellipsis => true,
+            Value::Tuple(items) => !items.is_empty(),
+            // Only non-empty sets and dicts lack a length
+            Value::Container { len, .. } => len != &Some(0),
+        }
+    }
+
+    /// The value as a number; `bool` counts as `int`
+    pub fn number(&self) -> Option<Constant> {
+        match self {
+            Value::Int(value) => Some(Constant::Int(*value)),
+            Value::Float(value) => Some(Constant::Float(*value)),
+            Value::Bool(value) => Some(Constant::Int(i64::from(*value))),
+            _ => None,
+        }
+    }
+
+    /// Value of `len(self)`
+    fn len(&self) -> Option<usize> {
+        match self {
+            Value::Str(text) => Some(text.chars().count()),
+            Value::Bytes(bytes) => Some(bytes.len()),
+            Value::Tuple(items) => Some(items.len()),
+            Value::Container { len, .. } => *len,
+            _ => None,
+        }
+    }
+
+    /// Values of different categories are never equal
+    fn category(&self) -> &'static str {
+        match self {
+            Value::Int(_) | Value::Float(_) | Value::Bool(_) => "number",
+            Value::Str(_) => "str",
+            Value::Bytes(_) => "bytes",
+            Value::None => "None",
+            Value::Ellipsis => "ellipsis",
+            Value::Tuple(_) => "tuple",
+            Value::Container { type_name, .. } => type_name,
+        }
+    }
+}
+
+impl From<Constant> for Value {
+    fn from(constant: Constant) -> Self {
+        match constant {
+            Constant::Int(value) => Value::Int(value),
+            Constant::Float(value) => Value::Float(value),
+        }
+    }
+}
+
+/// Value of `expr`, if it is known without running the program
+pub(crate) fn evaluate(expr: &Expression) -> Option<Value> {
+    evaluate_with(expr, &|_| None)
+}
+
+/// Value of `expr`, folding calls of the functions `functions` finds by
+/// name
+pub(crate) fn evaluate_with<'a>(
+    expr: &Expression,
+    functions: &'a dyn Fn(&str) -> Option<&'a ConstFunction>,
+) -> Option<Value> {
+    let evaluator = Evaluator {
+        functions,
+        bindings: HashMap::new(),
+        depth: 0,
+        calls: &Cell::new(0),
+    };
+    evaluator.eval(expr)
+}
+
+/// A pure function whose body returns one expression: calling it with
+/// constant arguments has a constant value
+#[derive(Debug, Clone)]
+pub(crate) struct ConstFunction {
+    /// Span of the definition
+    pub span: Span,
+    params: Vec<String>,
+    result: Expression,
+}
+
+impl ConstFunction {
+    /// The function of `definition` when it takes only positional
+    /// parameters without defaults and its body, after any docstring, is a
+    /// single `return`; whether it is pure is up to the caller
+    pub fn new(definition: &Statement) -> Option<Self> {
+        let StatementKind::FunctionDef { params, body, .. } = &definition.kind else {
+            return None;
+        };
+        let statements = match docstring_of(body) {
+            Some(_) => &body[1..],
+            None => &body[..],
+        };
+        let [Statement {
+            kind: StatementKind::Return {
+                value: Some(result),
+            },
+            ..
+        }] = statements
+        else {
+            return None;
+        };
+        if params.vararg.is_some()
+            || params.kwarg.is_some()
+            || !params.kwonlyargs.is_empty()
+            || params.all().any(|param| param.default.is_some())
+        {
+            return None;
+        }
+        Some(Self {
+            span: definition.span,
+            params: params.all().map(|param| param.name.clone()).collect(),
+            result: result.clone(),
+        })
+    }
+}
+
+/// Evaluation of one expression, inside the calls it folds
+struct Evaluator<'f, 'c> {
+    functions: &'f dyn Fn(&str) -> Option<&'f ConstFunction>,
+    /// Arguments of the call being folded, by parameter name
+    bindings: HashMap<String, Value>,
+    /// Number of calls being folded around this one
+    depth: usize,
+    /// Calls folded so far, shared by every level
+    calls: &'c Cell<usize>,
+}
+
+impl Evaluator<'_, '_> {
+    fn eval(&self, expr: &Expression) -> Option<Value> {
+        ensure_sufficient_stack(|| self.eval_kind(expr))
+    }
+
+    fn eval_kind(&self, expr: &Expression) -> Option<Value> {
+        Some(match &expr.kind {
+            ExpressionKind::Integer(value) => Value::Int(*value),
+            ExpressionKind::Float(value) => Value::Float(*value),
+            ExpressionKind::Boolean(value) => Value::Bool(*value),
+            ExpressionKind::String(text) | ExpressionKind::RawString(text) => {
+                Value::Str(text.clone())
+            }
+            ExpressionKind::ByteString(bytes) | ExpressionKind::ByteRawString(bytes) => {
+                Value::Bytes(bytes.clone())
+            }
+            ExpressionKind::None => Value::None,
+            ExpressionKind::Ellipsis => Value::Ellipsis,
+            ExpressionKind::Tuple { elements } => match elements.iter().map(evaluate).collect() {
+                Some(items) => Value::Tuple(items),
+                None => Value::Container {
+                    type_name: "tuple",
+                    len: Some(elements.len()),
+                },
+            },
+            ExpressionKind::List { elements } => Value::Container {
+                type_name: "list",
+                len: Some(elements.len()),
+            },
+            // Repeated keys or elements collapse, so only emptiness is certain
+            ExpressionKind::Set { elements } => Value::Container {
+                type_name: "set",
+                len: (elements.len() <= 1).then_some(elements.len()),
+            },
+            ExpressionKind::Dict { keys, .. } => Value::Container {
+                type_name: "dict",
+                len: (keys.len() <= 1).then_some(keys.len()),
+            },
+            ExpressionKind::UnaryOp {
+                op: UnaryOperator::Not,
+                operand,
+            } => Value::Bool(!self.eval(operand)?.is_truthy()),
+            ExpressionKind::UnaryOp { op, operand } => {
+                constants::fold_unary(*op, self.eval(operand)?.number()?)?
+                    .ok()?
+                    .into()
+            }
+            ExpressionKind::BinaryOp { left, op, right } => {
+                binary(self.eval(left)?, *op, self.eval(right)?)?
+            }
+            // `a and b` is `a` when `a` is falsy, otherwise `b`
+            ExpressionKind::LogicalOp { left, op, right } => {
+                let left = self.eval(left)?;
+                match (left.is_truthy(), op) {
+                    (false, LogicalOperator::And) | (true, LogicalOperator::Or) => left,
+                    _ => self.eval(right)?,
+                }
+            }
+            ExpressionKind::Compare {
+                left,
+                ops,
+                comparators,
+            } => {
+                let mut previous = self.eval(left)?;
+                let mut result = true;
+                for (op, next) in ops.iter().zip(comparators) {
+                    let next = self.eval(next)?;
+                    result &= compare(&previous, *op, &next)?;
+                    previous = next;
+                }
+                Value::Bool(result)
+            }
+            ExpressionKind::IfExp { test, body, orelse } => {
+                if self.eval(test)?.is_truthy() {
+                    self.eval(body)?
+                } else {
+                    self.eval(orelse)?
+                }
+            }
+            ExpressionKind::Call {
+                func,
+                args,
+                keywords,
+            } if matches!(&func.kind, ExpressionKind::Identifier(name) if name == "len")
+                && args.len() == 1
+                && keywords.is_empty() =>
+            {
+                Value::Int(i64::try_from(self.eval(&args[0])?.len()?).ok()?)
+            }
+            ExpressionKind::Call {
+                func,
+                args,
+                keywords,
+            } if keywords.is_empty() => self.call(func, args)?,
+            ExpressionKind::Identifier(name) => self.bindings.get(name)?.clone(),
+            _ => return None,
+        })
+    }
+
+    /// Value of a call of a function found by `functions`
+    fn call(&self, func: &Expression, args: &[Expression]) -> Option<Value> {
+        let ExpressionKind::Identifier(name) = &func.kind else {
+            return None;
+        };
+        // A parameter hides the function of the same name
+        if self.bindings.contains_key(name)
+            || self.depth >= MAX_CALL_DEPTH
+            || self.calls.get() >= MAX_CALLS
+        {
+            return None;
+        }
+        let function = (self.functions)(name)?;
+        if args.len() != function.params.len() {
+            return None;
+        }
+        let args = args
+            .iter()
+            .map(|arg| self.eval(arg))
+            .collect::<Option<Vec<_>>>()?;
+        self.calls.set(self.calls.get() + 1);
+        let evaluator = Evaluator {
+            functions: self.functions,
+            bindings: function.params.iter().cloned().zip(args).collect(),
+            depth: self.depth + 1,
+            calls: self.calls,
+        };
+        evaluator.eval(&function.result)
+    }
+}
+
+/// Truthiness of `expr`, if it is known without running the program
+pub(crate) fn truthiness(expr: &Expression) -> Option<bool> {
+    evaluate(expr).map(|value| value.is_truthy())
+}
+
+/// Value of a numeric expression, if every operation in it folds without
+/// error, folding calls of the functions `functions` finds by name
+pub(crate) fn number_with<'a>(
+    expr: &Expression,
+    functions: &'a dyn Fn(&str) -> Option<&'a ConstFunction>,
+) -> Option<Constant> {
+    evaluate_with(expr, functions)?.number()
+}
+
+/// Whether `pattern` matches `subject`; `None` when that depends on more
+/// than literal values
+pub(crate) fn pattern_matches(pattern: &Pattern, subject: &Value) -> Option<bool> {
+    match &pattern.kind {
+        PatternKind::Wildcard | PatternKind::Name(_) => Some(true),
+        PatternKind::As { pattern, .. } => pattern_matches(pattern, subject),
+        PatternKind::Or { patterns } => {
+            let mut result = Some(false);
+            for alternative in patterns {
+                match pattern_matches(alternative, subject) {
+                    Some(true) => return Some(true),
+                    Some(false) => {}
+                    None => result = None,
+                }
+            }
+            result
+        }
+        PatternKind::Literal(expr) => match evaluate(expr)? {
+            // `None`, `True` and `False` are compared with `is`
+            value @ (Value::None | Value::Bool(_)) => Some(&value == subject),
+            value => equals(subject, &value),
+        },
+        _ => None,
+    }
+}
+
+fn binary(left: Value, op: BinaryOperator, right: Value) -> Option<Value> {
+    if let (Some(a), Some(b)) = (left.number(), right.number()) {
+        return Some(constants::fold_binary(a, op, b)?.ok()?.into());
+    }
+    let repeat = |count: &Value, len: usize| match count {
+        Value::Int(_) | Value::Bool(_) => {
+            let Some(Constant::Int(count)) = count.number() else {
+                return None;
+            };
+            let count = usize::try_from(count).unwrap_or(0);
+            len.checked_mul(count)
+                .filter(|total| *total <= MAX_LENGTH)
+                .map(|_| count)
+        }
+        _ => None,
+    };
+    let value = match (left, op, right) {
+        (Value::Str(a), BinaryOperator::Add, Value::Str(b)) => Value::Str(a + &b),
+        (Value::Bytes(a), BinaryOperator::Add, Value::Bytes(b)) => Value::Bytes([a, b].concat()),
+        (Value::Tuple(a), BinaryOperator::Add, Value::Tuple(b)) => Value::Tuple([a, b].concat()),
+        (Value::Str(text), BinaryOperator::Mult, count)
+        | (count, BinaryOperator::Mult, Value::Str(text)) => {
+            Value::Str(text.repeat(repeat(&count, text.len())?))
+        }
+        (Value::Bytes(bytes), BinaryOperator::Mult, count)
+        | (count, BinaryOperator::Mult, Value::Bytes(bytes)) => {
+            Value::Bytes(bytes.repeat(repeat(&count, bytes.len())?))
+        }
+        (Value::Tuple(items), BinaryOperator::Mult, count)
+        | (count, BinaryOperator::Mult, Value::Tuple(items)) => {
+            let count = repeat(&count, items.len())?;
+            Value::Tuple((0..count).flat_map(|_| items.iter().cloned()).collect())
+        }
+        _ => return None,
+    };
+    Some(value).filter(|value| value.len().is_none_or(|len| len <= MAX_LENGTH))
+}
+
+/// Value of `left == right`; `None` for containers whose items are unknown
+fn equals(left: &Value, right: &Value) -> Option<bool> {
+    match (left, right) {
+        (Value::Tuple(a), Value::Tuple(b)) => {
+            if a.len() != b.len() {
+                return Some(false);
+            }
+            let mut result = true;
+            for (a, b) in a.iter().zip(b) {
+                result &= equals(a, b)?;
+            }
+            Some(result)
+        }
+        (Value::Container { .. }, _) | (_, Value::Container { .. }) => None,
+        _ => match (left.number(), right.number()) {
+            (Some(Constant::Int(a)), Some(Constant::Int(b))) => Some(a == b),
+            (Some(a), Some(b)) => Some(as_float(a) == as_float(b)),
+            _ => Some(left == right),
+        },
+    }
+}
+
+/// Value of one comparison
+///
+/// Equality between values of unrelated types is left unknown: the
+/// incompatible-comparison lint reports those instead.
+fn compare(left: &Value, op: CompareOperator, right: &Value) -> Option<bool> {
+    let ordering = || match (left, right) {
+        (Value::Str(a), Value::Str(b)) => Some(a.cmp(b)),
+        (Value::Bytes(a), Value::Bytes(b)) => Some(a.cmp(b)),
+        _ => match (left.number()?, right.number()?) {
+            (Constant::Int(a), Constant::Int(b)) => Some(a.cmp(&b)),
+            (a, b) => as_float(a).partial_cmp(&as_float(b)),
+        },
+    };
+    match op {
+        CompareOperator::Eq | CompareOperator::NotEq => {
+            if left.category() != right.category() {
+                return None;
+            }
+            equals(left, right).map(|equal| equal == (op == CompareOperator::Eq))
+        }
+        CompareOperator::Lt => Some(ordering()?.is_lt()),
+        CompareOperator::LtE => Some(ordering()?.is_le()),
+        CompareOperator::Gt => Some(ordering()?.is_gt()),
+        CompareOperator::GtE => Some(ordering()?.is_ge()),
+        // Identity is only certain for the singletons
+        CompareOperator::Is | CompareOperator::IsNot => match (left, right) {
+            (Value::None | Value::Bool(_), _) | (_, Value::None | Value::Bool(_)) => {
+                Some((left == right) == (op == CompareOperator::Is))
+            }
+            _ => None,
+        },
+        CompareOperator::In | CompareOperator::NotIn => {
+            let contained = match (left, right) {
+                (Value::Str(a), Value::Str(b)) => b.contains(a.as_str()),
+                (_, Value::Tuple(items)) => {
+                    let mut found = false;
+                    for item in items {
+                        found |= equals(left, item)?;
+                    }
+                    found
+                }
+                _ => return None,
+            };
+            Some(contained == (op == CompareOperator::In))
+        }
+    }
+}
+
+fn as_float(constant: Constant) -> f64 {
+    match constant {
+        Constant::Int(value) => value as f64,
+        Constant::Float(value) => value,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use silk_ast::StatementKind;
+
+    fn value(source: &str) -> Option<Value> {
+        let program = silk_parser::Parser::parse(source).unwrap();
+        match &program.statements[0].kind {
+            StatementKind::Expr(expr) => evaluate(expr),
+            other => panic!("expected an expression, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_arithmetic_strings_and_logic() {
+        assert_eq!(value("2 ** 10 - 24 // 5"), Some(Value::Int(1020)));
+        assert_eq!(value("True + 1.5"), Some(Value::Float(2.5)));
+        assert_eq!(value("'ab' + 'c' * 2"), Some(Value::Str("abcc".into())));
+        assert_eq!(value("0 or '' or None"), Some(Value::None));
+        assert_eq!(value("1 < 2 <= 2 != 3"), Some(Value::Bool(true)));
+        assert_eq!(value("'b' in ('a', 'b')"), Some(Value::Bool(true)));
+        assert_eq!(value("len('héllo') + len([x, y])"), Some(Value::Int(7)));
+        assert_eq!(
+            value("(1, x)"),
+            Some(Value::Container {
+                type_name: "tuple",
+                len: Some(2)
+            })
+        );
+    }
+
+    #[test]
+    fn test_unknown_values() {
+        assert_eq!(value("x + 1"), None);
+        assert_eq!(value("1 / 0"), None);
+        assert_eq!(value("'a' * 100000"), None);
+        assert_eq!(value("len({1, 1})"), None);
+        // Left to the incompatible-comparison lint
+        assert_eq!(value("'a' == 1"), None);
+    }
+}
@@ -0,0 +1,209 @@
+//! Compile-time folding of numeric operators
+//!
+//! Folds integer and float values through arithmetic, bitwise and unary
+//! operators for [`const_eval`](crate::const_eval). `int` is a 64-bit integer at runtime, so folding uses checked
+//! `i64` arithmetic: an expression whose value does not fit has no
+//! constant value, and the operation that overflows is reported.
+
+use silk_ast::{BinaryOperator, UnaryOperator};
+
+/// Number of bits in an `int`; shift counts must be below this
+pub(crate) const INT_BITS: i64 = 64;
+
+/// Value of a constant numeric expression
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Constant {
+    Int(i64),
+    Float(f64),
+}
+
+impl Constant {
+    pub fn is_zero(self) -> bool {
+        match self {
+            Constant::Int(value) => value == 0,
+            Constant::Float(value) => value == 0.0,
+        }
+    }
+
+    fn as_float(self) -> f64 {
+        match self {
+            Constant::Int(value) => value as f64,
+            Constant::Float(value) => value,
+        }
+    }
+}
+
+/// Problem found while folding one operation
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum FoldError {
+    DivisionByZero,
+    Overflow,
+    /// Shift count outside `0..INT_BITS`
+    ShiftOutOfRange(i64),
+}
+
+/// Fold a unary operator; `None` when it does not apply to numbers
+pub(crate) fn fold_unary(
+    op: UnaryOperator,
+    operand: Constant,
+) -> Option<Result<Constant, FoldError>> {
+    Some(match (op, operand) {
+        (UnaryOperator::UAdd, value) => Ok(value),
+        (UnaryOperator::USub, Constant::Int(value)) => value
+            .checked_neg()
+            .map(Constant::Int)
+            .ok_or(FoldError::Overflow),
+        (UnaryOperator::USub, Constant::Float(value)) => Ok(Constant::Float(-value)),
+        (UnaryOperator::Invert, Constant::Int(value)) => Ok(Constant::Int(!value)),
+        _ => return None,
+    })
+}
+
+/// Fold a binary operator; `None` when the result is not known (operators
+/// that do not apply to numbers, float powers)
+pub(crate) fn fold_binary(
+    left: Constant,
+    op: BinaryOperator,
+    right: Constant,
+) -> Option<Result<Constant, FoldError>> {
+    use BinaryOperator::*;
+
+    if matches!(op, Div | FloorDiv | Mod) && right.is_zero() {
+        return Some(Err(FoldError::DivisionByZero));
+    }
+
+    let (a, b) = match (left, right) {
+        (Constant::Int(a), Constant::Int(b)) => (a, b),
+        _ => {
+            if matches!(
+                op,
+                LShift | RShift | BitAnd | BitOr | BitXor | MatMult | Pow
+            ) {
+                return None;
+            }
+            return Some(Ok(Constant::Float(fold_float(
+                left.as_float(),
+                op,
+                right.as_float(),
+            ))));
+        }
+    };
+
+    let result = match op {
+        Add => a.checked_add(b),
+        Sub => a.checked_sub(b),
+        Mult => a.checked_mul(b),
+        Div => return Some(Ok(Constant::Float(a as f64 / b as f64))),
+        // Python rounds toward negative infinity, and the remainder takes
+        // the sign of the divisor
+        FloorDiv => a.checked_div(b).map(|q| {
+            if a % b != 0 && (a < 0) != (b < 0) {
+                q - 1
+            } else {
+                q
+            }
+        }),
+        Mod => {
+            let r = a.wrapping_rem(b);
+            Some(if r != 0 && (r < 0) != (b < 0) {
+                r + b
+            } else {
+                r
+            })
+        }
+        Pow => match u32::try_from(b) {
+            Ok(exponent) => a.checked_pow(exponent),
+            // Negative exponents give a float
+            Err(_) => return None,
+        },
+        LShift | RShift if !(0..INT_BITS).contains(&b) => {
+            return Some(Err(FoldError::ShiftOutOfRange(b)));
+        }
+        // Overflows when shifting back does not restore the value
+        LShift => Some(a << b).filter(|shifted| shifted >> b == a),
+        RShift => Some(a >> b),
+        BitAnd => Some(a & b),
+        BitOr => Some(a | b),
+        BitXor => Some(a ^ b),
+        MatMult => return None,
+    };
+    Some(result.map(Constant::Int).ok_or(FoldError::Overflow))
+}
+
+fn fold_float(a: f64, op: BinaryOperator, b: f64) -> f64 {
+    match op {
+        BinaryOperator::Add => a + b,
+        BinaryOperator::Sub => a - b,
+        BinaryOperator::Mult => a * b,
+        BinaryOperator::FloorDiv => (a / b).floor(),
+        BinaryOperator::Mod => a - b * (a / b).floor(),
+        _ => a / b,
+    }
+}
+
+/// Source spelling of a binary operator
+pub(crate) fn operator_symbol(op: BinaryOperator) -> &'static str {
+    match op {
+        BinaryOperator::Add => "+",
+        BinaryOperator::Sub => "-",
+        BinaryOperator::Mult => "*",
+        BinaryOperator::Div => "/",
+        BinaryOperator::FloorDiv => "//",
+        BinaryOperator::Mod => "%",
+        BinaryOperator::Pow => "**",
+        BinaryOperator::MatMult => "@",
+        BinaryOperator::BitOr => "|",
+        BinaryOperator::BitXor => "^",
+        BinaryOperator::BitAnd => "&",
+        BinaryOperator::LShift => "<<",
+        BinaryOperator::RShift => ">>",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fold(a: i64, op: BinaryOperator, b: i64) -> Result<Constant, FoldError> {
+        fold_binary(Constant::Int(a), op, Constant::Int(b)).unwrap()
+    }
+
+    #[test]
+    fn test_floor_division_rounds_toward_negative_infinity() {
+        assert_eq!(fold(7, BinaryOperator::FloorDiv, 2), Ok(Constant::Int(3)));
+        assert_eq!(fold(-7, BinaryOperator::FloorDiv, 2), Ok(Constant::Int(-4)));
+        assert_eq!(fold(7, BinaryOperator::FloorDiv, -2), Ok(Constant::Int(-4)));
+        assert_eq!(fold(-8, BinaryOperator::FloorDiv, 2), Ok(Constant::Int(-4)));
+        assert_eq!(
+            fold(i64::MIN, BinaryOperator::FloorDiv, -1),
+            Err(FoldError::Overflow)
+        );
+    }
+
+    #[test]
+    fn test_modulo_takes_the_sign_of_the_divisor() {
+        assert_eq!(fold(-7, BinaryOperator::Mod, 3), Ok(Constant::Int(2)));
+        assert_eq!(fold(7, BinaryOperator::Mod, -3), Ok(Constant::Int(-2)));
+        assert_eq!(
+            fold(i64::MIN, BinaryOperator::Mod, -1),
+            Ok(Constant::Int(0))
+        );
+    }
+
+    #[test]
+    fn test_shifts() {
+        assert_eq!(
+            fold(-1, BinaryOperator::LShift, 63),
+            Ok(Constant::Int(i64::MIN))
+        );
+        assert_eq!(
+            fold(1, BinaryOperator::LShift, 63),
+            Err(FoldError::Overflow)
+        );
+        assert_eq!(fold(-8, BinaryOperator::RShift, 1), Ok(Constant::Int(-4)));
+        assert_eq!(
+            fold(1, BinaryOperator::RShift, 64),
+            Err(FoldError::ShiftOutOfRange(64))
+        );
+    }
+}
@@ -6,12 +6,50 @@
 //! - Missing return statements in functions
 //! - Infinite loops
 //! - Dead code
-
+//! - Unused imports
+//! - Recursion without a base case
+//! - Unused function parameters
+//! - Constant conditions, self-comparisons and duplicated branches
+//! - Missing and incomplete docstrings
+
+use crate::builtins;
+use crate::call_graph::CallGraph;
+use crate::classes::MethodKind;
+use crate::clones::{self, DEFAULT_DUPLICATE_MIN_NODES};
+use crate::conditions;
+use crate::const_eval;
+use crate::docstrings;
+use crate::metrics::{self, MetricLimits};
+use crate::side_effects;
 use crate::SemanticError;
-use silk_ast::{Expression, ExpressionKind, Pattern, Program, Statement, StatementKind};
-use silk_lexer::Span;
+use silk_ast::{
+    ensure_sufficient_stack, Expression, ExpressionKind, Feature, FeatureSet, Pattern, Program,
+    Statement, StatementKind,
+};
+use silk_lexer::{FStringPart, Span};
 use std::collections::{HashMap, HashSet};
 
+/// A name bound by an import statement
+struct ImportBinding {
+    name: String,
+    /// Span of the imported name (including any `as` clause)
+    span: Span,
+    /// Span of the whole import statement
+    statement: Span,
+    /// `import x as x` and `from m import y as y` mark explicit re-exports
+    reexport: bool,
+}
+
+/// Parameters of one function and whether the body reads them
+struct ParameterFrame {
+    function_name: String,
+    /// Parameter name, span, and whether it was read
+    params: Vec<(String, Span, bool)>,
+    /// Whether unread parameters are reported (not for stubs, dunder
+    /// methods and abstract or overriding methods)
+    report: bool,
+}
+
 /// Control flow analyzer for detecting control flow errors
 pub struct ControlFlowAnalyzer {
     /// Errors collected during analysis
@@ -33,10 +71,30 @@ pub struct ControlFlowAnalyzer {
     assigned_variables: HashMap<String, Span>,
     /// Set of variables that have been used (read)
     used_variables: HashSet<String>,
+    /// Parameters of the enclosing functions, innermost last
+    parameter_frames: Vec<ParameterFrame>,
     /// Map of function names to their definition locations (for unused detection)
     defined_functions: HashMap<String, Span>,
     /// Set of functions that have been called
     called_functions: HashSet<String>,
+    /// Names bound by import statements (for unused import detection)
+    imports: Vec<ImportBinding>,
+    /// Names listed in the module's `__all__`
+    exported_names: HashSet<String>,
+    /// Number of comprehension scopes enclosing the current expression
+    comprehension_depth: usize,
+    /// Whether statements are directly inside a class body (method definitions)
+    in_class_body: bool,
+    /// Whether the next `if` statement is an `elif` of the chain being analyzed
+    in_elif: bool,
+    /// Unstable features enabled for this analysis
+    features: FeatureSet,
+    /// Codes of lints that are allowed by default but were enabled
+    enabled_lints: HashSet<String>,
+    /// Smallest duplicate reported by `duplicate-code`, in nodes
+    duplicate_min_nodes: usize,
+    /// Limits of the metrics checked by `complex-function`
+    metric_limits: MetricLimits,
 }
 
 impl ControlFlowAnalyzer {
@@ -52,13 +110,50 @@ impl ControlFlowAnalyzer {
             scope_stack: vec![HashSet::new()], // Start with global scope
             assigned_variables: HashMap::new(),
             used_variables: HashSet::new(),
+            parameter_frames: Vec::new(),
             defined_functions: HashMap::new(),
             called_functions: HashSet::new(),
+            imports: Vec::new(),
+            exported_names: HashSet::new(),
+            comprehension_depth: 0,
+            in_class_body: false,
+            in_elif: false,
+            features: FeatureSet::new(),
+            enabled_lints: HashSet::new(),
+            duplicate_min_nodes: DEFAULT_DUPLICATE_MIN_NODES,
+            metric_limits: MetricLimits::default(),
         }
     }
 
+    /// Set the unstable features enabled for this analysis
+    pub fn set_features(&mut self, features: FeatureSet) {
+        self.features = features;
+    }
+
+    /// Report a lint that is allowed by default (e.g. `missing-docstring`)
+    pub fn enable_lint(&mut self, code: &str) {
+        self.enabled_lints.insert(code.to_string());
+    }
+
+    /// Set the smallest duplicate `duplicate-code` reports, in nodes
+    /// (statements and expressions)
+    pub fn set_duplicate_min_nodes(&mut self, nodes: usize) {
+        self.duplicate_min_nodes = nodes;
+    }
+
+    /// Set the limits `complex-function` checks functions against
+    pub fn set_metric_limits(&mut self, limits: MetricLimits) {
+        self.metric_limits = limits;
+    }
+
     /// Analyze a program and return errors if any
     pub fn analyze(&mut self, program: &Program) -> Result<(), Vec<SemanticError>> {
+        let _span = tracing::debug_span!("control_flow").entered();
+        self.features.extend(program.features.iter());
+
+        // Names in `__all__` are re-exported, so importing them is a use
+        self.collect_exported_names(program);
+
         // Analyze all statements in the program
         for statement in &program.statements {
             self.analyze_statement(statement);
@@ -69,6 +164,32 @@ impl ControlFlowAnalyzer {
         
         // Report unused functions (excluding those with _ prefix)
         self.report_unused_functions();
+
+        // Report unused imports (excluding re-exports)
+        self.report_unused_imports();
+
+        // Report functions that call themselves on every path
+        self.report_infinite_recursion(program);
+
+        // Report missing and incomplete docstrings
+        self.errors.extend(docstrings::check_docstrings(program));
+
+        // Report copies of code, complex functions and calls run on
+        // import, only when asked to
+        if self.enabled_lints.contains("duplicate-code") {
+            self.report_duplicate_code(program);
+        }
+        if self.enabled_lints.contains("complex-function") {
+            self.report_complex_functions(program);
+        }
+        if self.enabled_lints.contains("import-side-effect") {
+            self.report_import_side_effects(program);
+        }
+
+        let enabled_lints = &self.enabled_lints;
+        self.errors
+            .retain(|err| !err.is_allowed_by_default() || enabled_lints.contains(err.code()));
+        tracing::debug!(errors = self.errors.len(), "control flow analysis finished");
         
         if self.errors.is_empty() {
             Ok(())
@@ -77,9 +198,56 @@ impl ControlFlowAnalyzer {
         }
     }
 
+    /// Report function bodies and runs of statements that repeat earlier code
+    fn report_duplicate_code(&mut self, program: &Program) {
+        for duplicate in clones::duplicate_code(&program.statements, self.duplicate_min_nodes) {
+            self.errors.push(SemanticError::DuplicateCode {
+                kind: duplicate.kind.to_string(),
+                nodes: duplicate.nodes,
+                first_line: duplicate.first.line,
+                first_column: duplicate.first.column,
+                line: duplicate.span.line,
+                column: duplicate.span.column,
+                span: duplicate.span,
+            });
+        }
+    }
+
+    /// Report functions with a metric above its limit
+    fn report_complex_functions(&mut self, program: &Program) {
+        for function in metrics::function_metrics(program) {
+            for (metric, value, limit) in function.exceeded(&self.metric_limits) {
+                self.errors.push(SemanticError::ComplexFunction {
+                    function_name: function.name.clone(),
+                    metric: metric.to_string(),
+                    value,
+                    limit,
+                    line: function.span.line,
+                    column: function.span.column,
+                    span: function.span,
+                });
+            }
+        }
+    }
+
+    /// Report calls that run on import and may have side effects
+    fn report_import_side_effects(&mut self, program: &Program) {
+        for effect in side_effects::import_side_effects(program) {
+            self.errors.push(SemanticError::ImportSideEffect {
+                callee: effect.callee,
+                line: effect.span.line,
+                column: effect.span.column,
+                span: effect.span,
+            });
+        }
+    }
+
     /// Report variables that were assigned but never used
     fn report_unused_variables(&mut self) {
-        for (name, span) in &self.assigned_variables {
+        // Report in source order; the map's order changes from run to run
+        let mut assigned: Vec<_> = self.assigned_variables.iter().collect();
+        assigned.sort_by_key(|(_, span)| span.start);
+        for (name, span) in assigned {
             // Skip variables starting with underscore (Python convention for unused)
             if name.starts_with('_') {
                 continue;
@@ -91,7 +259,7 @@ impl ControlFlowAnalyzer {
                     name: name.clone(),
                     line: span.line,
                     column: span.column,
-                    span: span.clone(),
+                    span: *span,
                 });
             }
         }
@@ -99,7 +267,9 @@ impl ControlFlowAnalyzer {
 
     /// Report functions that were defined but never called
     fn report_unused_functions(&mut self) {
-        for (name, span) in &self.defined_functions {
+        let mut defined: Vec<_> = self.defined_functions.iter().collect();
+        defined.sort_by_key(|(_, span)| span.start);
+        for (name, span) in defined {
             // Skip functions starting with underscore (Python convention for unused)
             if name.starts_with('_') {
                 continue;
@@ -109,6 +279,9 @@ impl ControlFlowAnalyzer {
             if name == "main" {
                 continue; // main is the entry point
             }
+            if name.starts_with("test_") {
+                continue; // discovered and called by `silk test`
+            }
             
             // Check if function was ever called
             if !self.called_functions.contains(name) {
@@ -116,16 +289,114 @@ impl ControlFlowAnalyzer {
                     name: name.clone(),
                     line: span.line,
                     column: span.column,
-                    span: span.clone(),
+                    span: *span,
+                });
+            }
+        }
+    }
+
+    /// Report functions that can never return because they call themselves
+    /// on every path
+    fn report_infinite_recursion(&mut self, program: &Program) {
+        let graph = CallGraph::build(program);
+        for function in graph.functions() {
+            if graph.always_recurses(&function.name) {
+                self.errors.push(SemanticError::InfiniteRecursion {
+                    function_name: function.name.clone(),
+                    line: function.span.line,
+                    column: function.span.column,
+                    span: function.span,
                 });
             }
         }
     }
 
+    /// Report imports whose names are never used
+    ///
+    /// The fix span covers the whole statement when none of its names are
+    /// used, otherwise just the unused name.
+    fn report_unused_imports(&mut self) {
+        let is_unused = |binding: &ImportBinding| {
+            !binding.reexport
+                && !self.exported_names.contains(&binding.name)
+                && !self.used_variables.contains(&binding.name)
+                && !self.called_functions.contains(&binding.name)
+        };
+
+        for binding in &self.imports {
+            if !is_unused(binding) {
+                continue;
+            }
+            let whole_statement = self
+                .imports
+                .iter()
+                .filter(|other| other.statement == binding.statement)
+                .all(is_unused);
+            self.errors.push(SemanticError::UnusedImport {
+                name: binding.name.clone(),
+                line: binding.span.line,
+                column: binding.span.column,
+                span: binding.span,
+                fix_span: if whole_statement {
+                    binding.statement
+                } else {
+                    binding.span
+                },
+            });
+        }
+    }
+
+    /// Collect the string entries of module-level `__all__` assignments
+    fn collect_exported_names(&mut self, program: &Program) {
+        for stmt in &program.statements {
+            let value = match &stmt.kind {
+                StatementKind::Assign { targets, value, .. }
+                    if targets.iter().any(Self::is_dunder_all) =>
+                {
+                    value
+                }
+                StatementKind::AugAssign { target, value, .. } if Self::is_dunder_all(target) => {
+                    value
+                }
+                _ => continue,
+            };
+            if let ExpressionKind::List { elements } | ExpressionKind::Tuple { elements } =
+                &value.kind
+            {
+                for element in elements {
+                    if let ExpressionKind::String(name) = &element.kind {
+                        self.exported_names.insert(name.clone());
+                    }
+                }
+            }
+        }
+    }
+
+    fn is_dunder_all(expr: &Expression) -> bool {
+        matches!(&expr.kind, ExpressionKind::Identifier(name) if name == "__all__")
+    }
+
+    /// Track the names bound by an import statement
+    fn track_import(&mut self, stmt: &Statement, names: &[silk_ast::Alias]) {
+        for alias in names {
+            if alias.name == "*" {
+                continue;
+            }
+            let name = alias.bound_name();
+            self.mark_initialized(name);
+            self.imports.push(ImportBinding {
+                name: name.to_string(),
+                span: alias.span,
+                statement: stmt.span,
+                reexport: alias.is_reexport(),
+            });
+        }
+    }
+
     /// Track a function definition
     fn track_function_definition(&mut self, name: &str, span: &Span) {
         // Only track the first definition (ignore redefinitions)
-        self.defined_functions.entry(name.to_string()).or_insert(span.clone());
+        self.defined_functions.entry(name.to_string()).or_insert(*span);
     }
 
     /// Track a function call
@@ -174,10 +445,16 @@ impl ControlFlowAnalyzer {
     /// Track all function/method calls in an expression tree (for chained calls)
     /// This recursively finds all calls without checking for variable initialization
     fn track_all_calls_in_expression(&mut self, expr: &Expression) {
+        ensure_sufficient_stack(|| self.track_calls_in_expression(expr))
+    }
+
+    fn track_calls_in_expression(&mut self, expr: &Expression) {
         match &expr.kind {
             // Direct function call: func()
             ExpressionKind::Identifier(name) => {
                 self.track_function_call(name);
+                // Calling a variable (e.g. `cls(...)`) also reads it
+                self.track_usage(name);
             }
             // Method call: obj.method()
             ExpressionKind::Attribute { value, attr } => {
@@ -250,27 +527,144 @@ impl ControlFlowAnalyzer {
     fn track_assignment(&mut self, name: &str, span: &Span) {
         // Only track the first assignment location
         if !self.assigned_variables.contains_key(name) {
-            self.assigned_variables.insert(name.to_string(), span.clone());
+            self.assigned_variables.insert(name.to_string(), *span);
+        }
+    }
+
+    /// Record a use of every name in a type annotation, e.g. `Optional`
+    /// and `int` in `Optional[int]`
+    fn track_annotation_usage(&mut self, annotation: &silk_ast::Type) {
+        use silk_ast::TypeKind;
+        match &annotation.kind {
+            TypeKind::Name(name) => {
+                self.used_variables.insert(name.clone());
+            }
+            TypeKind::Generic { base, args } => {
+                self.track_annotation_usage(base);
+                for arg in args {
+                    self.track_annotation_usage(arg);
+                }
+            }
+            TypeKind::Tuple { elements: types } | TypeKind::Union { types } => {
+                for ty in types {
+                    self.track_annotation_usage(ty);
+                }
+            }
+            TypeKind::Optional { inner } => self.track_annotation_usage(inner),
+            TypeKind::Callable {
+                params,
+                return_type,
+            } => {
+                for param in params {
+                    self.track_annotation_usage(param);
+                }
+                self.track_annotation_usage(return_type);
+            }
+            TypeKind::Literal { .. } | TypeKind::Any | TypeKind::None => {}
         }
     }
 
     /// Track that a variable was used (read)
     fn track_usage(&mut self, name: &str) {
         self.used_variables.insert(name.to_string());
+
+        // A read marks the parameter of the innermost function declaring it
+        for frame in self.parameter_frames.iter_mut().rev() {
+            if let Some(param) = frame.params.iter_mut().find(|(param, ..)| param == name) {
+                param.2 = true;
+                break;
+            }
+        }
+    }
+
+    /// Report the parameters of a function that its body never read
+    fn report_unused_parameters(&mut self, frame: ParameterFrame) {
+        if !frame.report {
+            return;
+        }
+        for (name, span, used) in frame.params {
+            // Skip parameters starting with underscore (Python convention for unused)
+            if used || name.starts_with('_') {
+                continue;
+            }
+            self.errors.push(SemanticError::UnusedParameter {
+                name,
+                function_name: frame.function_name.clone(),
+                line: span.line,
+                column: span.column,
+                span,
+            });
+        }
+    }
+
+    /// Check if unread parameters of a function are expected: dunder
+    /// methods, abstract or overriding methods, and stubs whose body is
+    /// only `pass`, `...`, a docstring or `raise NotImplementedError`
+    fn ignores_parameters(name: &str, body: &[Statement], decorator_list: &[Expression]) -> bool {
+        let is_dunder = name.starts_with("__") && name.ends_with("__");
+        let is_abstract = decorator_list.iter().any(|decorator| {
+            let name = match &decorator.kind {
+                ExpressionKind::Identifier(name) => name,
+                ExpressionKind::Attribute { attr, .. } => attr,
+                _ => return false,
+            };
+            name == "abstractmethod" || name == "override"
+        });
+        let is_stub = body.iter().all(|stmt| match &stmt.kind {
+            StatementKind::Pass => true,
+            StatementKind::Expr(expr) => matches!(
+                expr.kind,
+                ExpressionKind::Ellipsis | ExpressionKind::String(_)
+            ),
+            StatementKind::Raise { exc: Some(exc), .. } => {
+                let exc = match &exc.kind {
+                    ExpressionKind::Call { func, .. } => func,
+                    _ => exc,
+                };
+                matches!(
+                    &exc.kind,
+                    ExpressionKind::Identifier(name) if name == "NotImplementedError"
+                )
+            }
+            _ => false,
+        });
+        is_dunder || is_abstract || is_stub
     }
 
     /// Check if a variable is initialized, report error if not
     fn check_initialized(&mut self, name: &str, span: &Span) {
-        if !self.is_initialized(name) {
+        if !self.is_initialized(name) && !builtins::is_builtin(name) {
             self.errors.push(SemanticError::UninitializedVariable {
                 name: name.to_string(),
                 line: span.line,
                 column: span.column,
-                span: span.clone(),
+                span: *span,
             });
         }
     }
 
+    /// Check a `del` target and unbind the names it deletes
+    ///
+    /// `del x` reads `x` and unbinds it, so later reads may fail until it is
+    /// assigned again; after a branch that deletes it, it is possibly
+    /// unbound. Deleting an attribute or an item (`del a.b`, `del a[i]`)
+    /// only reads the object and the index.
+    fn delete_target(&mut self, target: &Expression) {
+        match &target.kind {
+            ExpressionKind::Identifier(name) => {
+                self.check_initialized(name, &target.span);
+                self.track_usage(name);
+                self.current_scope_mut().remove(name);
+            }
+            ExpressionKind::Tuple { elements } | ExpressionKind::List { elements } => {
+                for element in elements {
+                    self.delete_target(element);
+                }
+            }
+            _ => self.check_expression(target),
+        }
+    }
+
     /// Extract variable name from an expression (for assignments)
     fn extract_variable_name(expr: &Expression) -> Option<String> {
         match &expr.kind {
@@ -287,33 +681,12 @@ impl ControlFlowAnalyzer {
         }
     }
 
-    /// Track usage without checking initialization (for function calls to built-ins)
-    fn track_expression_usage(&mut self, expr: &Expression) {
-        match &expr.kind {
-            ExpressionKind::Identifier(name) => {
-                self.track_usage(name);
-            }
-            ExpressionKind::Attribute { value, .. } => {
-                self.track_expression_usage(value);
-            }
-            ExpressionKind::Subscript { value, index } => {
-                self.track_expression_usage(value);
-                self.track_expression_usage(index);
-            }
-            // For other expressions, just track nested identifiers
-            ExpressionKind::BinaryOp { left, right, .. } => {
-                self.track_expression_usage(left);
-                self.track_expression_usage(right);
-            }
-            ExpressionKind::UnaryOp { operand, .. } => {
-                self.track_expression_usage(operand);
-            }
-            _ => {}
-        }
-    }
-
     /// Check an expression for uninitialized variable usage
     fn check_expression(&mut self, expr: &Expression) {
+        ensure_sufficient_stack(|| self.check_expression_kind(expr))
+    }
+
+    fn check_expression_kind(&mut self, expr: &Expression) {
         match &expr.kind {
             ExpressionKind::Identifier(name) => {
                 self.check_initialized(name, &expr.span);
@@ -327,12 +700,16 @@ impl ControlFlowAnalyzer {
                 self.check_expression(operand);
             }
             ExpressionKind::Compare {
-                left, comparators, ..
+                left, ops, comparators,
             } => {
                 self.check_expression(left);
                 for comp in comparators {
                     self.check_expression(comp);
                 }
+                let operands = std::iter::once(left.as_ref()).chain(comparators);
+                for ((left, op), right) in operands.clone().zip(ops).zip(operands.skip(1)) {
+                    self.check_comparison(left, *op, right, &expr.span);
+                }
             }
             ExpressionKind::Call { func, args, keywords } => {
                 // Track function/method calls recursively to handle chained calls
@@ -372,6 +749,7 @@ impl ControlFlowAnalyzer {
                 orelse,
             } => {
                 self.check_expression(test);
+                self.check_constant_condition(test);
                 self.check_expression(body);
                 self.check_expression(orelse);
             }
@@ -386,6 +764,7 @@ impl ControlFlowAnalyzer {
                 // Lambda parameters are initialized within the lambda body scope
                 // Push new scope for lambda
                 self.push_scope();
+                let comprehension_depth = std::mem::take(&mut self.comprehension_depth);
                 
                 // Mark lambda parameters as initialized
                 for param in params {
@@ -394,6 +773,7 @@ impl ControlFlowAnalyzer {
                 
                 // Check lambda body with parameters marked as initialized
                 self.check_expression(body);
+                self.comprehension_depth = comprehension_depth;
                 
                 // Pop lambda scope
                 self.pop_scope();
@@ -406,10 +786,45 @@ impl ControlFlowAnalyzer {
                 // Walrus operator: check value, then mark target as initialized
                 self.check_expression(value);
                 if let Some(name) = Self::extract_variable_name(target) {
-                    self.mark_initialized(&name);
+                    if self.features.is_enabled(Feature::ComprehensionAssignments)
+                        && self.comprehension_depth > 0
+                    {
+                        // Target binds in the scope enclosing the comprehension
+                        let index = self.scope_stack.len() - 1 - self.comprehension_depth;
+                        self.scope_stack[index].insert(name.clone());
+                    } else {
+                        self.mark_initialized(&name);
+                    }
                     self.track_assignment(&name, &target.span);
                 }
             }
+            // F-string expressions are kept as source text; any name they
+            // mention, or that a field nested in the format spec mentions,
+            // counts as read
+            ExpressionKind::FString { parts } => {
+                for part in parts {
+                    if let FStringPart::Expression {
+                        code, format_spec, ..
+                    } = part
+                    {
+                        let nested = format_spec
+                            .iter()
+                            .flat_map(|spec| spec.split('{').skip(1))
+                            .map(|field| field.split('}').next().unwrap_or(""));
+                        let names = std::iter::once(code.as_str())
+                            .chain(nested)
+                            .flat_map(|code| {
+                                code.split(|c: char| !(c.is_alphanumeric() || c == '_'))
+                            })
+                            .filter(|word| {
+                                word.starts_with(|c: char| c.is_alphabetic() || c == '_')
+                            });
+                        for name in names {
+                            self.track_usage(name);
+                        }
+                    }
+                }
+            }
             // Literals don't need checking
             ExpressionKind::Integer(_)
             | ExpressionKind::Float(_)
@@ -417,7 +832,6 @@ impl ControlFlowAnalyzer {
             | ExpressionKind::RawString(_)
             | ExpressionKind::ByteString(_)
             | ExpressionKind::ByteRawString(_)
-            | ExpressionKind::FString { .. }
             | ExpressionKind::Boolean(_)
             | ExpressionKind::None
             | ExpressionKind::NotImplemented
@@ -439,6 +853,7 @@ impl ControlFlowAnalyzer {
             ExpressionKind::DictComp { key, value, generators } => {
                 // For dict comprehensions, check both key and value
                 self.push_scope();
+                self.comprehension_depth += 1;
                 
                 // Process generators in order (each can reference previous)
                 for generator in generators {
@@ -460,6 +875,7 @@ impl ControlFlowAnalyzer {
                 self.check_expression(key);
                 self.check_expression(value);
                 
+                self.comprehension_depth -= 1;
                 self.pop_scope();
             }
             ExpressionKind::GeneratorExp { element, generators } => {
@@ -473,6 +889,7 @@ impl ControlFlowAnalyzer {
     fn check_comprehension(&mut self, element: &silk_ast::Expression, generators: &[silk_ast::Comprehension]) {
         // Create new scope for comprehension variables
         self.push_scope();
+        self.comprehension_depth += 1;
         
         // Process generators in order (each can reference previous)
         for generator in generators {
@@ -494,47 +911,66 @@ impl ControlFlowAnalyzer {
         self.check_expression(element);
         
         // Pop scope - comprehension variables don't leak
+        self.comprehension_depth -= 1;
         self.pop_scope();
     }
 
     /// Check if a while loop condition is always true (infinite loop)
-    /// Detects patterns like `while True:` or `while 1:`
+    /// Detects patterns like `while True:`, `while 1:` or `while "a" < "b":`
     fn is_infinite_loop_condition(test: &silk_ast::Expression) -> bool {
-        use silk_ast::ExpressionKind;
-        
-        match &test.kind {
-            // while True:
-            ExpressionKind::Boolean(true) => true,
-            // while 1: (or any non-zero integer)
-            ExpressionKind::Integer(n) if *n != 0 => true,
-            // while 1.0: (or any non-zero float)
-            ExpressionKind::Float(f) if *f != 0.0 => true,
-            _ => false,
+        const_eval::truthiness(test) == Some(true)
+    }
+
+    /// Report a condition whose value is known without running the program
+    fn check_constant_condition(&mut self, test: &Expression) {
+        if let Some(value) = const_eval::truthiness(test) {
+            self.errors.push(SemanticError::ConstantCondition {
+                value,
+                line: test.span.line,
+                column: test.span.column,
+                span: test.span,
+            });
         }
     }
 
-    /// Analyze a block of statements
-    /// Returns true if the block always terminates (return/break/continue/raise on all paths)
-    fn analyze_block(&mut self, statements: &[Statement]) -> bool {
-        for statement in statements {
-            self.analyze_statement(statement);
+    /// Report one comparison of a chain that is always true or always false
+    fn check_comparison(
+        &mut self,
+        left: &Expression,
+        op: silk_ast::CompareOperator,
+        right: &Expression,
+        span: &Span,
+    ) {
+        if let Some((left_type, right_type, value)) =
+            conditions::incompatible_comparison(left, op, right)
+        {
+            self.errors.push(SemanticError::IncompatibleComparison {
+                left_type: left_type.to_string(),
+                right_type: right_type.to_string(),
+                value,
+                line: span.line,
+                column: span.column,
+                span: *span,
+            });
         }
-        // Block terminates if we ended unreachable
-        !self.is_reachable
     }
 
     // ========== STATEMENT ANALYSIS ==========
 
     /// Analyze a single statement
     fn analyze_statement(&mut self, stmt: &Statement) {
+        ensure_sufficient_stack(|| self.analyze_statement_kind(stmt))
+    }
+
+    fn analyze_statement_kind(&mut self, stmt: &Statement) {
         // Check if this statement is unreachable
         if !self.is_reachable {
             // Only report the first unreachable statement in a block
             if !self.unreachable_reported {
                 let statement_type = match &stmt.kind {
                     StatementKind::Return { .. } => "return",
-                    StatementKind::Break { .. } => "break",
-                    StatementKind::Continue { .. } => "continue",
+                    StatementKind::Break => "break",
+                    StatementKind::Continue => "continue",
                     StatementKind::Raise { .. } => "raise",
                     StatementKind::Pass => "pass",
                     StatementKind::Expr { .. } => "expression",
@@ -574,10 +1010,13 @@ impl ControlFlowAnalyzer {
             StatementKind::Assign {
                 targets,
                 value,
-                type_annotation: _,
+                type_annotation,
             } => {
                 // Check value expression for uninitialized variables
                 self.check_expression(value);
+                if let Some(annotation) = type_annotation {
+                    self.track_annotation_usage(annotation);
+                }
                 
                 // Mark all target variables as initialized and track assignment
                 for target in targets {
@@ -591,17 +1030,26 @@ impl ControlFlowAnalyzer {
             StatementKind::AnnAssign {
                 target,
                 value,
-                annotation: _,
+                annotation,
                 ..
             } => {
                 // Check value if present
                 if let Some(val) = value {
                     self.check_expression(val);
                 }
+                self.track_annotation_usage(annotation);
                 
                 // Mark target as initialized and track assignment
                 if let Some(name) = Self::extract_variable_name(target) {
-                    self.mark_initialized(&name);
+                    if self.features.is_enabled(Feature::ComprehensionAssignments)
+                        && self.comprehension_depth > 0
+                    {
+                        // Target binds in the scope enclosing the comprehension
+                        let index = self.scope_stack.len() - 1 - self.comprehension_depth;
+                        self.scope_stack[index].insert(name.clone());
+                    } else {
+                        self.mark_initialized(&name);
+                    }
                     self.track_assignment(&name, &target.span);
                 }
             }
@@ -617,6 +1065,9 @@ impl ControlFlowAnalyzer {
             StatementKind::FunctionDef { name, body, params, returns, decorator_list, .. } => {
                 // Track function definition (for unused function detection)
                 self.track_function_definition(name, &stmt.span);
+                // The name is bound once the def runs (`@name.setter` reads it)
+                self.mark_initialized(name);
+                let is_method = std::mem::replace(&mut self.in_class_body, false);
                 
                 // Track decorator usage - decorators are applied to the function
                 for decorator in decorator_list {
@@ -635,15 +1086,17 @@ impl ControlFlowAnalyzer {
                 
                 // Check default parameter expressions BEFORE entering function scope
                 // Default expressions are evaluated in the outer scope, not the function scope
-                for param in &params.args {
+                for param in params.positional().chain(&params.kwonlyargs) {
                     if let Some(default_expr) = &param.default {
                         self.check_expression(default_expr);
                     }
                 }
-                for param in &params.kwonlyargs {
-                    if let Some(default_expr) = &param.default {
-                        self.check_expression(default_expr);
-                    }
+                // Annotations are evaluated in the outer scope too
+                for annotation in params.all().filter_map(|param| param.annotation.as_ref()) {
+                    self.track_annotation_usage(annotation);
+                }
+                if let Some(annotation) = returns {
+                    self.track_annotation_usage(annotation);
                 }
                 
                 self.current_function_returns = false;
@@ -654,23 +1107,22 @@ impl ControlFlowAnalyzer {
                 // Push new scope for function (inherits outer scope visibility)
                 self.push_scope();
                 
-                // Mark all function parameters as initialized and track as assigned
-                for param in &params.args {
-                    self.mark_initialized(&param.name);
-                    self.track_assignment(&param.name, &param.span);
-                }
-                if let Some(vararg) = &params.vararg {
-                    self.mark_initialized(&vararg.name);
-                    self.track_assignment(&vararg.name, &vararg.span);
-                }
-                for param in &params.kwonlyargs {
+                // Mark all function parameters as initialized and track their reads
+                // (a method's implicit self/cls receiver is never reported as unused)
+                let has_receiver =
+                    is_method && MethodKind::from_decorators(name, decorator_list).receiver().is_some();
+                let mut frame = ParameterFrame {
+                    function_name: name.clone(),
+                    params: Vec::new(),
+                    report: !Self::ignores_parameters(name, body, decorator_list),
+                };
+                for (i, param) in params.all().enumerate() {
                     self.mark_initialized(&param.name);
-                    self.track_assignment(&param.name, &param.span);
-                }
-                if let Some(kwarg) = &params.kwarg {
-                    self.mark_initialized(&kwarg.name);
-                    self.track_assignment(&kwarg.name, &kwarg.span);
+                    if !(has_receiver && i == 0) {
+                        frame.params.push((param.name.clone(), param.span, false));
+                    }
                 }
+                self.parameter_frames.push(frame);
 
                 // Analyze function body
                 for stmt in body {
@@ -699,6 +1151,10 @@ impl ControlFlowAnalyzer {
                     }
                 }
 
+                if let Some(frame) = self.parameter_frames.pop() {
+                    self.report_unused_parameters(frame);
+                }
+
                 self.current_function_returns = previous_in_function;
                 self.in_loop = previous_in_loop;
                 self.is_reachable = previous_reachable; // Restore reachability
@@ -706,19 +1162,25 @@ impl ControlFlowAnalyzer {
                 
                 // Pop function scope
                 self.pop_scope();
+                self.in_class_body = is_method;
             }
 
             // Class definition
-            StatementKind::ClassDef { body, decorator_list, .. } => {
+            StatementKind::ClassDef { name, body, decorator_list, .. } => {
                 // Track decorator usage - decorators are applied to the class
                 for decorator in decorator_list {
                     self.track_decorator_usage(decorator);
                 }
                 
                 // Analyze class body
+                let previous_in_class_body = std::mem::replace(&mut self.in_class_body, true);
                 for stmt in body {
                     self.analyze_statement(stmt);
                 }
+                self.in_class_body = previous_in_class_body;
+
+                // The class name is bound once its body has run
+                self.mark_initialized(name);
             }
 
             // Control flow statements
@@ -729,6 +1191,20 @@ impl ControlFlowAnalyzer {
             } => {
                 // Check test expression first (this may include walrus operator that initializes variables)
                 self.check_expression(test);
+                self.check_constant_condition(test);
+
+                // An `elif` chain is checked once, from its first `if`
+                if !std::mem::take(&mut self.in_elif) {
+                    for duplicate in conditions::duplicate_branches(stmt) {
+                        self.errors.push(SemanticError::DuplicateBranch {
+                            kind: duplicate.kind.to_string(),
+                            first_line: duplicate.first_line,
+                            line: duplicate.span.line,
+                            column: duplicate.span.column,
+                            span: duplicate.span,
+                        });
+                    }
+                }
                 
                 let previous_reachable = self.is_reachable;
                 let previous_unreachable_reported = self.unreachable_reported;
@@ -752,9 +1228,14 @@ impl ControlFlowAnalyzer {
                     self.is_reachable = previous_reachable;
                     self.restore_scope_stack(previous_scope_stack.clone());
                     self.unreachable_reported = false; // Reset for else block
+                    self.in_elif = matches!(
+                        orelse.as_slice(),
+                        [Statement { kind: StatementKind::If { .. }, .. }]
+                    );
                     for stmt in orelse {
                         self.analyze_statement(stmt);
                     }
+                    self.in_elif = false;
                     (self.is_reachable, self.clone_scope_stack())
                 };
                 
@@ -786,8 +1267,11 @@ impl ControlFlowAnalyzer {
                 body,
                 orelse,
             } => {
-                // Check test expression
+                // Check test expression (`while True:` is the usual endless loop)
                 self.check_expression(test);
+                if !Self::is_infinite_loop_condition(test) {
+                    self.check_constant_condition(test);
+                }
                 
                 let previous_in_loop = self.in_loop;
                 let previous_reachable = self.is_reachable;
@@ -870,6 +1354,7 @@ impl ControlFlowAnalyzer {
                 handlers,
                 orelse,
                 finalbody,
+                ..
             } => {
                 let previous_reachable = self.is_reachable;
                 let previous_unreachable_reported = self.unreachable_reported;
@@ -1008,12 +1493,63 @@ impl ControlFlowAnalyzer {
             }
 
             // Pattern matching
-            StatementKind::Match { subject: _, cases } => {
-                // Analyze each case
+            StatementKind::Match { subject, cases } => {
+                self.check_expression(subject);
+
+                let previous_reachable = self.is_reachable;
+                let previous_unreachable_reported = self.unreachable_reported;
+                let previous_scope_stack = self.clone_scope_stack();
+
+                // Each case starts from the state before the match; a name is
+                // initialized after it only if every case that falls through binds it
+                let mut fallthrough_scopes: Vec<HashSet<String>> = Vec::new();
                 for case in cases {
+                    self.is_reachable = previous_reachable;
+                    self.restore_scope_stack(previous_scope_stack.clone());
+                    self.unreachable_reported = false;
+
+                    for expr in case.pattern.expressions() {
+                        self.check_expression(expr);
+                    }
+                    for (name, span) in case.pattern.bound_names() {
+                        self.mark_initialized(name);
+                        self.track_assignment(name, &span);
+                    }
+                    if let Some(guard) = &case.guard {
+                        self.check_expression(guard);
+                    }
                     for stmt in &case.body {
                         self.analyze_statement(stmt);
                     }
+                    if self.is_reachable {
+                        fallthrough_scopes.push(self.current_scope().clone());
+                    }
+                }
+
+                // Without an unguarded case that always matches, no case may
+                // match at all
+                let subject_value = const_eval::evaluate(subject);
+                let exhaustive = cases.iter().any(|case| {
+                    case.guard.is_none()
+                        && (case.pattern.is_irrefutable()
+                            || subject_value.as_ref().is_some_and(|value| {
+                                const_eval::pattern_matches(&case.pattern, value) == Some(true)
+                            }))
+                });
+                if !exhaustive {
+                    fallthrough_scopes.push(previous_scope_stack.last().unwrap().clone());
+                }
+
+                self.restore_scope_stack(previous_scope_stack);
+                self.is_reachable = previous_reachable && !fallthrough_scopes.is_empty();
+                self.unreachable_reported = previous_unreachable_reported;
+                if let Some((first, rest)) = fallthrough_scopes.split_first() {
+                    let merged: HashSet<String> = first
+                        .iter()
+                        .filter(|name| rest.iter().all(|scope| scope.contains(*name)))
+                        .cloned()
+                        .collect();
+                    *self.current_scope_mut() = merged;
                 }
             }
 
@@ -1028,7 +1564,7 @@ impl ControlFlowAnalyzer {
                 self.current_function_returns = true;
             }
 
-            StatementKind::Break { .. } => {
+            StatementKind::Break => {
                 if !self.in_loop {
                     self.errors.push(SemanticError::BreakOutsideLoop {
                         line: stmt.span.line,
@@ -1040,7 +1576,7 @@ impl ControlFlowAnalyzer {
                 self.is_reachable = false; // Code after break is unreachable
             }
 
-            StatementKind::Continue { .. } => {
+            StatementKind::Continue => {
                 // Mark code after continue as unreachable
                 self.is_reachable = false;
                 // TODO: Detect continue outside loop
@@ -1051,20 +1587,40 @@ impl ControlFlowAnalyzer {
                 self.is_reachable = false;
             }
 
-            StatementKind::Assert { .. } => {
-                // No control flow impact
+            StatementKind::Assert { test, msg } => {
+                self.check_expression(test);
+                if let Some(msg) = msg {
+                    self.check_expression(msg);
+                }
+                // `assert (x, "msg")` tests a non-empty tuple, which always
+                // passes; other constant asserts, like `assert 1 + 1 == 2` in
+                // a test, are written on purpose
+                if matches!(test.kind, ExpressionKind::Tuple { .. })
+                    && const_eval::truthiness(test) == Some(true)
+                {
+                    self.check_constant_condition(test);
+                }
             }
 
-            StatementKind::Delete { .. } => {
-                // No control flow impact
+            StatementKind::Delete { targets } => {
+                for target in targets {
+                    self.delete_target(target);
+                }
             }
 
             StatementKind::Pass => {
                 // No control flow impact
             }
 
-            StatementKind::Import { .. } | StatementKind::ImportFrom { .. } => {
-                // No control flow impact
+            StatementKind::Import { names } => {
+                self.track_import(stmt, names);
+            }
+
+            StatementKind::ImportFrom { module, names, .. } => {
+                // Future imports are compiler directives, not bindings
+                if module.as_deref() != Some("__future__") {
+                    self.track_import(stmt, names);
+                }
             }
 
             StatementKind::Global { names } => {
@@ -1119,10 +1675,7 @@ mod tests {
     #[test]
     fn test_analyze_empty_program_succeeds() {
         let mut analyzer = ControlFlowAnalyzer::new();
-        let program = Program {
-            statements: Vec::new(),
-            span: silk_lexer::Span::new(0, 0, 1, 1),
-        };
+        let program = Program::new(Vec::new(), silk_lexer::Span::new(0, 0, 1, 1));
 
         let result = analyzer.analyze(&program);
         assert!(result.is_ok());
@@ -1141,10 +1694,10 @@ mod tests {
             span: silk_lexer::Span::new(0, 1, 1, 1),
         });
 
-        let result = analyzer.analyze(&Program {
-            statements: Vec::new(),
-            span: silk_lexer::Span::new(0, 0, 1, 1),
-        });
+        let result = analyzer.analyze(&Program::new(
+            Vec::new(),
+            silk_lexer::Span::new(0, 0, 1, 1),
+        ));
         assert!(result.is_err());
         
         let errors = result.unwrap_err();
@@ -1167,12 +1720,12 @@ result = foo()
 class MyClass:
     pass
 
-if True:
+if x > 5:
     pass
 else:
-    pass
+    print(x)
 
-while False:
+while y < 0:
     break
     
 pass
@@ -1182,6 +1735,7 @@ print(y)
 print(result)
 
 import os
+print(os.name)
 
 global g
 "#;
@@ -0,0 +1,359 @@
+//! Docstring lints
+//!
+//! - `missing-docstring`: public module-level functions and public methods
+//!   of public module-level classes need a docstring. Allowed by default.
+//! - `undocumented-parameter`, `unknown-documented-parameter` and
+//!   `undocumented-return`: a docstring with sections must list every
+//!   parameter (except the method receiver) and nothing else, and document
+//!   the return value of a function that returns one.
+//!
+//! Only docstrings with a parameter, return or raises section are checked
+//! for consistency; a one-line summary documents nothing in particular.
+//! Both Google style (`Args:` followed by indented `name (type): text`
+//! entries) and NumPy style (`Parameters` underlined with dashes, followed
+//! by `name : type` entries) are understood.
+
+use crate::call_graph::{contains_yield, for_each_nested_block};
+use crate::classes::MethodKind;
+use crate::SemanticError;
+use silk_ast::{
+    ensure_sufficient_stack, Expression, ExpressionKind, FunctionParams, Program, Statement,
+    StatementKind, TypeKind,
+};
+
+/// Sections of a docstring that matter for the lints
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct Docstring {
+    /// Names listed in the parameter sections, if there are any
+    pub params: Option<Vec<String>>,
+    /// Whether there is a `Returns` or `Yields` section
+    pub returns: bool,
+    /// Whether there is a `Raises` section
+    pub raises: bool,
+}
+
+impl Docstring {
+    /// Whether the docstring has sections, and so should be complete
+    fn is_structured(&self) -> bool {
+        self.params.is_some() || self.returns || self.raises
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Section {
+    Params,
+    Returns,
+    Raises,
+}
+
+fn section_kind(header: &str) -> Option<Section> {
+    match header.trim().to_lowercase().as_str() {
+        "args" | "arguments" | "params" | "parameters" | "keyword args" | "keyword arguments"
+        | "other parameters" => Some(Section::Params),
+        "returns" | "return" | "yields" | "yield" => Some(Section::Returns),
+        "raises" | "raise" => Some(Section::Raises),
+        _ => None,
+    }
+}
+
+fn indentation(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+fn is_underline(line: &str) -> bool {
+    let line = line.trim();
+    line.len() >= 3 && line.chars().all(|c| c == '-')
+}
+
+/// Parameter names of a section entry: `x (int): ...`, `*args: ...` or,
+/// in NumPy style, `x, y : int`
+fn entry_names(entry: &str) -> Vec<String> {
+    let end = entry.find([':', '(']).unwrap_or(entry.len());
+    entry[..end]
+        .split(',')
+        .map(|name| name.trim().trim_start_matches('*').to_string())
+        .filter(|name| !name.is_empty())
+        .collect()
+}
+
+/// Find the parameter, return and raises sections of a docstring
+pub(crate) fn parse_docstring(text: &str) -> Docstring {
+    let lines: Vec<&str> = text.lines().collect();
+    let mut docstring = Docstring::default();
+    let mut index = 0;
+
+    while index < lines.len() {
+        let header = lines[index];
+        let numpy = lines.get(index + 1).is_some_and(|next| is_underline(next));
+        let section = if numpy {
+            section_kind(header)
+        } else {
+            header.trim().strip_suffix(':').and_then(section_kind)
+        };
+        index += 1;
+        let Some(section) = section else {
+            continue;
+        };
+        if numpy {
+            index += 1; // underline
+        }
+
+        // Google entries are indented below the header; NumPy entries are
+        // level with it and the section ends at the next underlined header
+        let header_indent = indentation(header);
+        let mut entry_indent = None;
+        let mut names = Vec::new();
+        while let Some(line) = lines.get(index) {
+            if line.trim().is_empty() {
+                index += 1;
+                continue;
+            }
+            let indent = indentation(line);
+            let ends = if numpy {
+                indent < header_indent || lines.get(index + 1).is_some_and(|l| is_underline(l))
+            } else {
+                indent <= header_indent
+            };
+            if ends {
+                break;
+            }
+            if *entry_indent.get_or_insert(indent) == indent {
+                names.extend(entry_names(line.trim()));
+            }
+            index += 1;
+        }
+
+        match section {
+            Section::Params => docstring.params.get_or_insert_with(Vec::new).extend(names),
+            Section::Returns => docstring.returns = true,
+            Section::Raises => docstring.raises = true,
+        }
+    }
+    docstring
+}
+
+/// Docstring of a function or class body: a string literal as its first
+/// statement
+pub fn docstring_of(body: &[Statement]) -> Option<(&str, &Expression)> {
+    let StatementKind::Expr(expr) = &body.first()?.kind else {
+        return None;
+    };
+    match &expr.kind {
+        ExpressionKind::String(text) | ExpressionKind::RawString(text) => Some((text, expr)),
+        _ => None,
+    }
+}
+
+/// Run the docstring lints on every function of a program
+pub(crate) fn check_docstrings(program: &Program) -> Vec<SemanticError> {
+    let mut errors = Vec::new();
+    check_block(&program.statements, Context::Module, &mut errors);
+    errors
+}
+
+/// Where a block of definitions is
+#[derive(Clone, Copy, PartialEq)]
+enum Context {
+    Module,
+    /// Body of a class; `public` when the class is a public module member
+    Class {
+        public: bool,
+    },
+    /// Anywhere inside a function
+    Function,
+}
+
+fn check_block(body: &[Statement], context: Context, errors: &mut Vec<SemanticError>) {
+    ensure_sufficient_stack(|| check_statements(body, context, errors))
+}
+
+fn check_statements(body: &[Statement], context: Context, errors: &mut Vec<SemanticError>) {
+    for stmt in body {
+        match &stmt.kind {
+            StatementKind::FunctionDef {
+                name,
+                params,
+                body,
+                decorator_list,
+                returns,
+                ..
+            } => {
+                let method_kind = match context {
+                    Context::Class { .. } => {
+                        Some(MethodKind::from_decorators(name, decorator_list))
+                    }
+                    _ => None,
+                };
+                let public = !name.starts_with('_')
+                    && match context {
+                        Context::Module => true,
+                        Context::Class { public } => {
+                            public
+                                && !matches!(
+                                    method_kind,
+                                    Some(MethodKind::PropertySetter | MethodKind::PropertyDeleter)
+                                )
+                        }
+                        Context::Function => false,
+                    };
+
+                match docstring_of(body) {
+                    Some((text, expr)) => {
+                        let receiver = method_kind.and_then(MethodKind::receiver);
+                        let function = Function {
+                            name,
+                            params,
+                            body,
+                            returns_value: returns_value(body, returns.as_ref()),
+                            receiver,
+                        };
+                        check_consistency(&function, &parse_docstring(text), expr, errors);
+                    }
+                    None if public => errors.push(SemanticError::MissingDocstring {
+                        function_name: name.clone(),
+                        line: stmt.span.line,
+                        column: stmt.span.column,
+                        span: stmt.span,
+                    }),
+                    None => {}
+                }
+                check_block(body, Context::Function, errors);
+            }
+            StatementKind::ClassDef { name, body, .. } => {
+                let public = context == Context::Module && !name.starts_with('_');
+                check_block(body, Context::Class { public }, errors);
+            }
+            _ => for_each_nested_block(stmt, &mut |block| check_block(block, context, errors)),
+        }
+    }
+}
+
+/// The parts of a function definition the consistency checks need
+struct Function<'a> {
+    name: &'a str,
+    params: &'a FunctionParams,
+    body: &'a [Statement],
+    returns_value: bool,
+    /// Receiver parameter of a method (`self`, `cls`), which is never
+    /// documented
+    receiver: Option<&'static str>,
+}
+
+fn check_consistency(
+    function: &Function,
+    docstring: &Docstring,
+    expr: &Expression,
+    errors: &mut Vec<SemanticError>,
+) {
+    if !docstring.is_structured() {
+        return;
+    }
+    let documented = docstring.params.as_deref().unwrap_or_default();
+
+    let mut params: Vec<_> = function.params.all().collect();
+    if function.receiver.is_some() && function.params.positional().next().is_some() {
+        params.remove(0);
+    }
+    for param in &params {
+        if !param.name.starts_with('_') && !documented.contains(&param.name) {
+            errors.push(SemanticError::UndocumentedParameter {
+                name: param.name.clone(),
+                function_name: function.name.to_string(),
+                line: param.span.line,
+                column: param.span.column,
+                span: param.span,
+            });
+        }
+    }
+
+    for name in documented {
+        if !params.iter().any(|param| &param.name == name) {
+            errors.push(SemanticError::UnknownDocumentedParameter {
+                name: name.clone(),
+                function_name: function.name.to_string(),
+                line: expr.span.line,
+                column: expr.span.column,
+                span: expr.span,
+            });
+        }
+    }
+
+    if function.returns_value && !docstring.returns && !is_stub(function.body) {
+        errors.push(SemanticError::UndocumentedReturn {
+            function_name: function.name.to_string(),
+            line: expr.span.line,
+            column: expr.span.column,
+            span: expr.span,
+        });
+    }
+}
+
+/// Whether a function gives back a value: it has a return annotation other
+/// than `None`, returns something other than `None`, or yields
+fn returns_value(body: &[Statement], annotation: Option<&silk_ast::Type>) -> bool {
+    match annotation {
+        Some(ty) => {
+            !matches!(&ty.kind, TypeKind::None)
+                && !matches!(&ty.kind, TypeKind::Name(name) if name == "None")
+        }
+        None => returns_something(body) || contains_yield(body),
+    }
+}
+
+fn returns_something(body: &[Statement]) -> bool {
+    body.iter().any(|stmt| {
+        let mut found = matches!(
+            &stmt.kind,
+            StatementKind::Return { value: Some(value) }
+                if !matches!(value.kind, ExpressionKind::None)
+        );
+        for_each_nested_block(stmt, &mut |block| found |= returns_something(block));
+        found
+    })
+}
+
+/// A body that is only a docstring and `...`, `pass` or `raise`: there is
+/// nothing to return yet
+fn is_stub(body: &[Statement]) -> bool {
+    body.iter().skip(1).all(|stmt| match &stmt.kind {
+        StatementKind::Pass | StatementKind::Raise { .. } => true,
+        StatementKind::Expr(expr) => matches!(expr.kind, ExpressionKind::Ellipsis),
+        _ => false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(params: &[&str]) -> Option<Vec<String>> {
+        Some(params.iter().map(|name| name.to_string()).collect())
+    }
+
+    #[test]
+    fn test_google_style() {
+        let docstring = parse_docstring(
+            "Add two numbers.\n\n    Args:\n        a (int): First.\n            More about a.\n        *rest: Others.\n\n    Returns:\n        int: The sum.\n    ",
+        );
+        assert_eq!(docstring.params, names(&["a", "rest"]));
+        assert!(docstring.returns);
+        assert!(!docstring.raises);
+    }
+
+    #[test]
+    fn test_numpy_style() {
+        let docstring = parse_docstring(
+            "Scale values.\n\n    Parameters\n    ----------\n    x, y : float\n        Coordinates.\n    factor : int\n\n    Raises\n    ------\n    ValueError\n    ",
+        );
+        assert_eq!(docstring.params, names(&["x", "y", "factor"]));
+        assert!(!docstring.returns);
+        assert!(docstring.raises);
+    }
+
+    #[test]
+    fn test_plain_docstring_has_no_sections() {
+        let docstring = parse_docstring("Do the thing.\n\nArgs are described elsewhere.");
+        assert_eq!(docstring, Docstring::default());
+        assert!(!docstring.is_structured());
+    }
+}
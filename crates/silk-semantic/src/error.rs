@@ -9,10 +9,12 @@ pub type SemanticResult<T> = Result<T, SemanticError>;
 /// Errors that can occur during semantic analysis
 #[derive(Debug, Error, Clone, PartialEq)]
 pub enum SemanticError {
-    /// Variable used before being defined
-    #[error("Undefined variable '{name}' at line {line}, column {column}")]
+    /// Variable used before being defined; `suggestion` is a defined name
+    /// with a similar spelling
+    #[error("Undefined variable '{name}' at line {line}, column {column}{}", did_you_mean(.suggestion))]
     UndefinedVariable {
         name: String,
+        suggestion: Option<String>,
         line: usize,
         column: usize,
         span: Span,
@@ -122,6 +124,62 @@ pub enum SemanticError {
         span: Span,
     },
 
+    /// Division or modulo by an operand that is always zero
+    #[error("Division by zero at line {line}, column {column}: the right operand of '{operator}' is always zero")]
+    DivisionByZero {
+        operator: String,
+        line: usize,
+        column: usize,
+        span: Span,
+    },
+
+    /// Operation on constant integers whose result does not fit in an `int`
+    #[error("Integer overflow at line {line}, column {column}: the result of '{operator}' does not fit in a 64-bit int")]
+    IntegerOverflow {
+        operator: String,
+        line: usize,
+        column: usize,
+        span: Span,
+    },
+
+    /// Shift by a constant count that is negative or at least the int width
+    #[error("Shift count {count} at line {line}, column {column} is out of range: it must be between 0 and 63")]
+    ShiftOutOfRange {
+        count: i64,
+        line: usize,
+        column: usize,
+        span: Span,
+    },
+
+    /// String formatting with a literal template that fails when it runs
+    #[error("Invalid string formatting at line {line}, column {column}: {message}")]
+    InvalidFormat {
+        message: String,
+        line: usize,
+        column: usize,
+        span: Span,
+    },
+
+    /// Literal pattern passed to a function of the `re` module that fails
+    /// to compile
+    #[error("Invalid regular expression at line {line}, column {column}: {message}")]
+    InvalidRegex {
+        message: String,
+        line: usize,
+        column: usize,
+        span: Span,
+    },
+
+    /// Dict key or set element of a type that cannot be hashed
+    #[error("Unhashable type '{type_name}' used as a {context} at line {line}, column {column}")]
+    UnhashableType {
+        type_name: String,
+        context: &'static str,
+        line: usize,
+        column: usize,
+        span: Span,
+    },
+
     /// Argument count mismatch
     #[error("Argument count mismatch at line {line}, column {column}: function '{function_name}' expects {expected} argument(s) but got {actual}")]
     ArgumentCountMismatch {
@@ -133,6 +191,30 @@ pub enum SemanticError {
         span: Span,
     },
 
+    /// Attribute that no class in the receiver's MRO defines; `suggestion`
+    /// is a member with a similar spelling
+    #[error("Class '{class_name}' has no attribute '{attr}' at line {line}, column {column}{}", did_you_mean(.suggestion))]
+    UnknownAttribute {
+        class_name: String,
+        attr: String,
+        suggestion: Option<String>,
+        line: usize,
+        column: usize,
+        span: Span,
+    },
+
+    /// Member that a built-in module with stubs does not define, used as
+    /// `module.name` or imported with `from module import name`
+    #[error("Module '{module}' has no member '{name}' at line {line}, column {column}{}", did_you_mean(.suggestion))]
+    UnknownModuleMember {
+        module: String,
+        name: String,
+        suggestion: Option<String>,
+        line: usize,
+        column: usize,
+        span: Span,
+    },
+
     /// Break statement outside of loop
     #[error("'break' statement outside of loop at line {line}, column {column}")]
     BreakOutsideLoop {
@@ -177,6 +259,15 @@ pub enum SemanticError {
         span: Span,
     },
 
+    /// `break`, `continue` or `return` that would leave an `except*` handler
+    #[error("'{statement}' cannot appear in an 'except*' block at line {line}, column {column}")]
+    JumpOutOfExceptStar {
+        statement: String,
+        line: usize,
+        column: usize,
+        span: Span,
+    },
+
     /// Function missing return statement on some paths
     #[error("Function '{function_name}' is missing a return statement on some execution paths (line {line}, column {column})")]
     MissingReturn {
@@ -220,4 +311,593 @@ pub enum SemanticError {
         column: usize,
         span: Span,
     },
+
+    /// Imported name never used; `fix_span` covers the text to delete
+    /// (the whole statement when none of its names are used)
+    #[error("Unused import '{name}' at line {line}, column {column}")]
+    UnusedImport {
+        name: String,
+        line: usize,
+        column: usize,
+        span: Span,
+        fix_span: Span,
+    },
+
+    /// Function that calls itself on every path, so it can never return
+    #[error("Function '{function_name}' calls itself on every path at line {line}, column {column}: the recursion has no base case")]
+    InfiniteRecursion {
+        function_name: String,
+        line: usize,
+        column: usize,
+        span: Span,
+    },
+
+    /// Function parameter that is never read in the function body
+    #[error("Unused parameter '{name}' of function '{function_name}' at line {line}, column {column}")]
+    UnusedParameter {
+        name: String,
+        function_name: String,
+        line: usize,
+        column: usize,
+        span: Span,
+    },
+
+    /// Variable that hides a builtin or a name from an enclosing scope;
+    /// `shadowed` describes what is hidden
+    #[error("Variable '{name}' at line {line}, column {column} shadows {shadowed}")]
+    ShadowedName {
+        name: String,
+        shadowed: String,
+        line: usize,
+        column: usize,
+        span: Span,
+    },
+
+    /// Assignment that replaces a function or class defined in the same scope
+    #[error("Assignment to '{name}' at line {line}, column {column} replaces the {kind} defined at line {first_line}")]
+    ReassignedDefinition {
+        name: String,
+        kind: String,
+        first_line: usize,
+        line: usize,
+        column: usize,
+        span: Span,
+    },
+
+    /// Condition whose value is known without running the program
+    #[error("Condition at line {line}, column {column} is always {value}")]
+    ConstantCondition {
+        value: bool,
+        line: usize,
+        column: usize,
+        span: Span,
+    },
+
+    /// Comparison of a variable with itself
+    #[error("Comparison of '{name}' with itself at line {line}, column {column} is always {value}")]
+    SelfComparison {
+        name: String,
+        value: bool,
+        line: usize,
+        column: usize,
+        span: Span,
+    },
+
+    /// Equality check between literals of types that are never equal
+    #[error("Comparison between '{left_type}' and '{right_type}' literals at line {line}, column {column} is always {value}")]
+    IncompatibleComparison {
+        left_type: String,
+        right_type: String,
+        value: bool,
+        line: usize,
+        column: usize,
+        span: Span,
+    },
+
+    /// `elif`/`else` branch with the same condition or body as an earlier
+    /// branch of the chain; `kind` is `condition` or `body`
+    #[error("Branch at line {line}, column {column} has the same {kind} as the branch at line {first_line}")]
+    DuplicateBranch {
+        kind: String,
+        first_line: usize,
+        line: usize,
+        column: usize,
+        span: Span,
+    },
+
+    /// Public function or method without a docstring (allowed by default)
+    #[error("Public function '{function_name}' at line {line}, column {column} has no docstring")]
+    MissingDocstring {
+        function_name: String,
+        line: usize,
+        column: usize,
+        span: Span,
+    },
+
+    /// Parameter missing from the parameter section of a docstring
+    #[error("Parameter '{name}' of function '{function_name}' at line {line}, column {column} is not documented")]
+    UndocumentedParameter {
+        name: String,
+        function_name: String,
+        line: usize,
+        column: usize,
+        span: Span,
+    },
+
+    /// Docstring entry for a parameter the function does not have
+    #[error("Docstring of function '{function_name}' at line {line}, column {column} documents '{name}', which is not a parameter")]
+    UnknownDocumentedParameter {
+        name: String,
+        function_name: String,
+        line: usize,
+        column: usize,
+        span: Span,
+    },
+
+    /// Docstring without a `Returns` section for a function that returns
+    /// a value
+    #[error("Docstring of function '{function_name}' at line {line}, column {column} does not document the return value")]
+    UndocumentedReturn {
+        function_name: String,
+        line: usize,
+        column: usize,
+        span: Span,
+    },
+
+    /// Function body or run of statements repeating earlier code (allowed
+    /// by default); `kind` is `function body` or `statements`
+    #[error("Duplicated {kind} at line {line}, column {column}: {nodes} nodes repeat the code at line {first_line}, column {first_column}")]
+    DuplicateCode {
+        kind: String,
+        nodes: usize,
+        first_line: usize,
+        first_column: usize,
+        line: usize,
+        column: usize,
+        span: Span,
+    },
+
+    /// Function with a metric above its limit (allowed by default); `metric`
+    /// is `cyclomatic complexity`, `nesting depth`, `parameter count` or
+    /// `line count`
+    #[error("Function '{function_name}' at line {line}, column {column} has a {metric} of {value} (limit {limit})")]
+    ComplexFunction {
+        function_name: String,
+        metric: String,
+        value: usize,
+        limit: usize,
+        line: usize,
+        column: usize,
+        span: Span,
+    },
+
+    /// Call with possible side effects in code run on import (allowed by
+    /// default)
+    #[error("Call to '{callee}' at line {line}, column {column} runs when the module is imported")]
+    ImportSideEffect {
+        callee: String,
+        line: usize,
+        column: usize,
+        span: Span,
+    },
+
+    // ========== CLASS ANALYSIS ERRORS ==========
+
+    /// Method does not take the conventional receiver (`self`/`cls`) first
+    #[error("{method_kind} '{method}' must take '{expected}' as its first parameter at line {line}, column {column}")]
+    InvalidMethodReceiver {
+        method: String,
+        method_kind: String,
+        expected: String,
+        line: usize,
+        column: usize,
+        span: Span,
+    },
+
+    /// Static method declares a `self`/`cls` parameter it will never receive
+    #[error("Static method '{method}' does not receive '{param}' at line {line}, column {column}")]
+    StaticMethodReceiver {
+        method: String,
+        param: String,
+        line: usize,
+        column: usize,
+        span: Span,
+    },
+
+    /// Base classes admit no consistent C3 method resolution order
+    #[error("Cannot create a consistent method resolution order (MRO) for class '{class_name}' at line {line}, column {column}")]
+    InconsistentMro {
+        class_name: String,
+        line: usize,
+        column: usize,
+        span: Span,
+    },
+
+    /// Zero-argument `super()` used outside of a class body
+    #[error("'super()' used outside of a class method at line {line}, column {column}")]
+    SuperOutsideClass {
+        line: usize,
+        column: usize,
+        span: Span,
+    },
+
+    /// Property accessor with the wrong number of parameters
+    #[error("{method_kind} '{name}' must take {expected} required parameter(s) and no *args or **kwargs, but takes {actual} required at line {line}, column {column}")]
+    InvalidPropertySignature {
+        name: String,
+        method_kind: String,
+        expected: usize,
+        actual: usize,
+        line: usize,
+        column: usize,
+        span: Span,
+    },
+
+    /// Positional-only parameter passed by keyword
+    #[error("Positional-only parameter '{param}' of '{function_name}' passed as a keyword argument at line {line}, column {column}")]
+    PositionalOnlyArgument {
+        function_name: String,
+        param: String,
+        line: usize,
+        column: usize,
+        span: Span,
+    },
+
+    /// Keyword-only parameter reached by a positional argument
+    #[error("Keyword-only parameter '{param}' of '{function_name}' must be passed by keyword at line {line}, column {column}")]
+    KeywordOnlyArgument {
+        function_name: String,
+        param: String,
+        line: usize,
+        column: usize,
+        span: Span,
+    },
+
+    /// Keyword argument that matches no parameter
+    #[error("Function '{function_name}' got an unexpected keyword argument '{keyword}' at line {line}, column {column}")]
+    UnexpectedKeywordArgument {
+        function_name: String,
+        keyword: String,
+        line: usize,
+        column: usize,
+        span: Span,
+    },
+
+    /// Parameter given both positionally and by keyword
+    #[error("Function '{function_name}' got multiple values for argument '{param}' at line {line}, column {column}")]
+    DuplicateArgument {
+        function_name: String,
+        param: String,
+        line: usize,
+        column: usize,
+        span: Span,
+    },
+
+    /// Parameter without a default after one with a default
+    #[error("Parameter '{param}' without a default follows a parameter with a default at line {line}, column {column}")]
+    NonDefaultAfterDefault {
+        param: String,
+        line: usize,
+        column: usize,
+        span: Span,
+    },
+
+    /// Function marked `@pure` whose body has a side effect
+    #[error("Function '{function_name}' at line {line}, column {column} is marked @pure but {reason} at line {reason_line}, column {reason_column}")]
+    ImpureFunction {
+        function_name: String,
+        reason: String,
+        reason_line: usize,
+        reason_column: usize,
+        line: usize,
+        column: usize,
+        span: Span,
+    },
+
+    /// Mutable default value, shared by every call of the function
+    #[error("Mutable default value for parameter '{param}' at line {line}, column {column}: the same {kind} is shared by every call (use None and create it inside the function)")]
+    MutableDefault {
+        param: String,
+        kind: String,
+        line: usize,
+        column: usize,
+        span: Span,
+    },
+
+    /// Defined name that could be mistaken for another; `reason` says why
+    #[error("Confusable identifier '{name}' at line {line}, column {column}: {reason}")]
+    ConfusableIdentifier {
+        name: String,
+        reason: String,
+        line: usize,
+        column: usize,
+        span: Span,
+    },
+
+    /// Required parameter that received no argument
+    #[error("Function '{function_name}' is missing required argument '{param}' at line {line}, column {column}")]
+    MissingArgument {
+        function_name: String,
+        param: String,
+        line: usize,
+        column: usize,
+        span: Span,
+    },
+
+    /// Attribute access, subscript or call on a value that may be None
+    #[error("'{target}' may be None at line {line}, column {column}: {operation} on type '{value_type}' ({suggestion})")]
+    OptionalAccess {
+        target: String,
+        operation: String,
+        value_type: String,
+        suggestion: String,
+        line: usize,
+        column: usize,
+        span: Span,
+    },
+}
+
+/// ` (did you mean 'name'?)`, or nothing without a suggestion
+fn did_you_mean(suggestion: &Option<String>) -> String {
+    suggestion
+        .as_ref()
+        .map_or_else(String::new, |name| format!(" (did you mean '{}'?)", name))
+}
+
+impl SemanticError {
+    /// Stable kebab-case identifier of this error kind (e.g. `undefined-variable`)
+    pub fn code(&self) -> &'static str {
+        match self {
+            SemanticError::UndefinedVariable { .. } => "undefined-variable",
+            SemanticError::RedefinedVariable { .. } => "redefined-variable",
+            SemanticError::UndefinedFunction { .. } => "undefined-function",
+            SemanticError::UndefinedClass { .. } => "undefined-class",
+            SemanticError::InvalidScope { .. } => "invalid-scope",
+            SemanticError::TypeMismatch { .. } => "type-mismatch",
+            SemanticError::AssignmentTypeMismatch { .. } => "assignment-type-mismatch",
+            SemanticError::ArgumentTypeMismatch { .. } => "argument-type-mismatch",
+            SemanticError::ReturnTypeMismatch { .. } => "return-type-mismatch",
+            SemanticError::InvalidBinaryOperation { .. } => "invalid-binary-operation",
+            SemanticError::InvalidUnaryOperation { .. } => "invalid-unary-operation",
+            SemanticError::InvalidSubscript { .. } => "invalid-subscript",
+            SemanticError::DivisionByZero { .. } => "division-by-zero",
+            SemanticError::IntegerOverflow { .. } => "integer-overflow",
+            SemanticError::ShiftOutOfRange { .. } => "shift-out-of-range",
+            SemanticError::InvalidFormat { .. } => "invalid-format",
+            SemanticError::InvalidRegex { .. } => "invalid-regex",
+            SemanticError::UnhashableType { .. } => "unhashable-type",
+            SemanticError::ArgumentCountMismatch { .. } => "argument-count-mismatch",
+            SemanticError::BreakOutsideLoop { .. } => "break-outside-loop",
+            SemanticError::ContinueOutsideLoop { .. } => "continue-outside-loop",
+            SemanticError::ReturnOutsideFunction { .. } => "return-outside-function",
+            SemanticError::UnreachableCode { .. } => "unreachable-code",
+            SemanticError::UninitializedVariable { .. } => "uninitialized-variable",
+            SemanticError::MissingReturn { .. } => "missing-return",
+            SemanticError::InfiniteLoop { .. } => "infinite-loop",
+            SemanticError::DeadCode { .. } => "dead-code",
+            SemanticError::UnusedVariable { .. } => "unused-variable",
+            SemanticError::UnusedFunction { .. } => "unused-function",
+            SemanticError::UnusedImport { .. } => "unused-import",
+            SemanticError::InfiniteRecursion { .. } => "infinite-recursion",
+            SemanticError::JumpOutOfExceptStar { .. } => "jump-out-of-except-star",
+            SemanticError::UnusedParameter { .. } => "unused-parameter",
+            SemanticError::ShadowedName { .. } => "shadowed-name",
+            SemanticError::ReassignedDefinition { .. } => "reassigned-definition",
+            SemanticError::ConstantCondition { .. } => "constant-condition",
+            SemanticError::SelfComparison { .. } => "self-comparison",
+            SemanticError::IncompatibleComparison { .. } => "incompatible-comparison",
+            SemanticError::DuplicateBranch { .. } => "duplicate-branch",
+            SemanticError::MissingDocstring { .. } => "missing-docstring",
+            SemanticError::DuplicateCode { .. } => "duplicate-code",
+            SemanticError::ComplexFunction { .. } => "complex-function",
+            SemanticError::ImportSideEffect { .. } => "import-side-effect",
+            SemanticError::UndocumentedParameter { .. } => "undocumented-parameter",
+            SemanticError::UnknownDocumentedParameter { .. } => "unknown-documented-parameter",
+            SemanticError::UndocumentedReturn { .. } => "undocumented-return",
+            SemanticError::InvalidMethodReceiver { .. } => "invalid-method-receiver",
+            SemanticError::StaticMethodReceiver { .. } => "static-method-receiver",
+            SemanticError::InconsistentMro { .. } => "inconsistent-mro",
+            SemanticError::SuperOutsideClass { .. } => "super-outside-class",
+            SemanticError::InvalidPropertySignature { .. } => "invalid-property-signature",
+            SemanticError::PositionalOnlyArgument { .. } => "positional-only-argument",
+            SemanticError::KeywordOnlyArgument { .. } => "keyword-only-argument",
+            SemanticError::UnexpectedKeywordArgument { .. } => "unexpected-keyword-argument",
+            SemanticError::DuplicateArgument { .. } => "duplicate-argument",
+            SemanticError::MissingArgument { .. } => "missing-argument",
+            SemanticError::NonDefaultAfterDefault { .. } => "non-default-after-default",
+            SemanticError::ImpureFunction { .. } => "impure-function",
+            SemanticError::MutableDefault { .. } => "mutable-default",
+            SemanticError::ConfusableIdentifier { .. } => "confusable-identifier",
+            SemanticError::OptionalAccess { .. } => "optional-access",
+            SemanticError::UnknownAttribute { .. } => "unknown-attribute",
+            SemanticError::UnknownModuleMember { .. } => "unknown-module-member",
+        }
+    }
+
+    /// Stable error code, explained by `silk explain` (e.g. `E0200`)
+    ///
+    /// Codes are grouped by kind: names and scopes from E0200, types and
+    /// operations from E0300, control flow from E0400, lints from E0500,
+    /// and classes and calls from E0600. A code is never reused.
+    pub fn error_code(&self) -> &'static str {
+        match self {
+            SemanticError::UndefinedVariable { .. } => "E0200",
+            SemanticError::RedefinedVariable { .. } => "E0201",
+            SemanticError::UndefinedFunction { .. } => "E0202",
+            SemanticError::UndefinedClass { .. } => "E0203",
+            SemanticError::InvalidScope { .. } => "E0204",
+            SemanticError::TypeMismatch { .. } => "E0300",
+            SemanticError::AssignmentTypeMismatch { .. } => "E0301",
+            SemanticError::ArgumentTypeMismatch { .. } => "E0302",
+            SemanticError::ReturnTypeMismatch { .. } => "E0303",
+            SemanticError::InvalidBinaryOperation { .. } => "E0304",
+            SemanticError::InvalidUnaryOperation { .. } => "E0305",
+            SemanticError::InvalidSubscript { .. } => "E0306",
+            SemanticError::DivisionByZero { .. } => "E0307",
+            SemanticError::IntegerOverflow { .. } => "E0308",
+            SemanticError::ShiftOutOfRange { .. } => "E0309",
+            SemanticError::InvalidFormat { .. } => "E0313",
+            SemanticError::InvalidRegex { .. } => "E0314",
+            SemanticError::UnhashableType { .. } => "E0315",
+            SemanticError::ArgumentCountMismatch { .. } => "E0310",
+            SemanticError::OptionalAccess { .. } => "E0311",
+            SemanticError::UnknownAttribute { .. } => "E0312",
+            SemanticError::UnknownModuleMember { .. } => "E0205",
+            SemanticError::BreakOutsideLoop { .. } => "E0400",
+            SemanticError::ContinueOutsideLoop { .. } => "E0401",
+            SemanticError::ReturnOutsideFunction { .. } => "E0402",
+            SemanticError::UnreachableCode { .. } => "E0403",
+            SemanticError::UninitializedVariable { .. } => "E0404",
+            SemanticError::MissingReturn { .. } => "E0405",
+            SemanticError::InfiniteLoop { .. } => "E0406",
+            SemanticError::DeadCode { .. } => "E0407",
+            SemanticError::InfiniteRecursion { .. } => "E0408",
+            SemanticError::JumpOutOfExceptStar { .. } => "E0409",
+            SemanticError::UnusedVariable { .. } => "E0500",
+            SemanticError::UnusedFunction { .. } => "E0501",
+            SemanticError::UnusedImport { .. } => "E0502",
+            SemanticError::UnusedParameter { .. } => "E0503",
+            SemanticError::ShadowedName { .. } => "E0504",
+            SemanticError::ReassignedDefinition { .. } => "E0505",
+            SemanticError::ConstantCondition { .. } => "E0506",
+            SemanticError::SelfComparison { .. } => "E0507",
+            SemanticError::IncompatibleComparison { .. } => "E0508",
+            SemanticError::DuplicateBranch { .. } => "E0509",
+            SemanticError::MissingDocstring { .. } => "E0510",
+            SemanticError::DuplicateCode { .. } => "E0518",
+            SemanticError::ComplexFunction { .. } => "E0519",
+            SemanticError::ImportSideEffect { .. } => "E0520",
+            SemanticError::UndocumentedParameter { .. } => "E0511",
+            SemanticError::UnknownDocumentedParameter { .. } => "E0512",
+            SemanticError::UndocumentedReturn { .. } => "E0513",
+            SemanticError::MutableDefault { .. } => "E0514",
+            SemanticError::ConfusableIdentifier { .. } => "E0515",
+            SemanticError::InvalidMethodReceiver { .. } => "E0600",
+            SemanticError::StaticMethodReceiver { .. } => "E0601",
+            SemanticError::InconsistentMro { .. } => "E0602",
+            SemanticError::SuperOutsideClass { .. } => "E0603",
+            SemanticError::InvalidPropertySignature { .. } => "E0604",
+            SemanticError::PositionalOnlyArgument { .. } => "E0605",
+            SemanticError::KeywordOnlyArgument { .. } => "E0606",
+            SemanticError::UnexpectedKeywordArgument { .. } => "E0607",
+            SemanticError::DuplicateArgument { .. } => "E0608",
+            SemanticError::MissingArgument { .. } => "E0609",
+            SemanticError::NonDefaultAfterDefault { .. } => "E0610",
+            SemanticError::ImpureFunction { .. } => "E0611",
+        }
+    }
+
+    /// Location of the error, if it has one
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            SemanticError::UndefinedVariable { span, .. } => Some(*span),
+            SemanticError::RedefinedVariable { span, .. } => Some(*span),
+            SemanticError::UndefinedFunction { span, .. } => Some(*span),
+            SemanticError::UndefinedClass { span, .. } => Some(*span),
+            SemanticError::TypeMismatch { span, .. } => Some(*span),
+            SemanticError::AssignmentTypeMismatch { span, .. } => Some(*span),
+            SemanticError::ArgumentTypeMismatch { span, .. } => Some(*span),
+            SemanticError::ReturnTypeMismatch { span, .. } => Some(*span),
+            SemanticError::InvalidBinaryOperation { span, .. } => Some(*span),
+            SemanticError::InvalidUnaryOperation { span, .. } => Some(*span),
+            SemanticError::InvalidSubscript { span, .. } => Some(*span),
+            SemanticError::DivisionByZero { span, .. } => Some(*span),
+            SemanticError::IntegerOverflow { span, .. } => Some(*span),
+            SemanticError::ShiftOutOfRange { span, .. } => Some(*span),
+            SemanticError::InvalidFormat { span, .. } => Some(*span),
+            SemanticError::InvalidRegex { span, .. } => Some(*span),
+            SemanticError::UnhashableType { span, .. } => Some(*span),
+            SemanticError::ArgumentCountMismatch { span, .. } => Some(*span),
+            SemanticError::BreakOutsideLoop { span, .. } => Some(*span),
+            SemanticError::ContinueOutsideLoop { span, .. } => Some(*span),
+            SemanticError::ReturnOutsideFunction { span, .. } => Some(*span),
+            SemanticError::UnreachableCode { span, .. } => Some(*span),
+            SemanticError::UninitializedVariable { span, .. } => Some(*span),
+            SemanticError::MissingReturn { span, .. } => Some(*span),
+            SemanticError::InfiniteLoop { span, .. } => Some(*span),
+            SemanticError::DeadCode { span, .. } => Some(*span),
+            SemanticError::UnusedVariable { span, .. } => Some(*span),
+            SemanticError::UnusedFunction { span, .. } => Some(*span),
+            SemanticError::UnusedImport { span, .. } => Some(*span),
+            SemanticError::InfiniteRecursion { span, .. } => Some(*span),
+            SemanticError::JumpOutOfExceptStar { span, .. } => Some(*span),
+            SemanticError::UnusedParameter { span, .. } => Some(*span),
+            SemanticError::ShadowedName { span, .. } => Some(*span),
+            SemanticError::ReassignedDefinition { span, .. } => Some(*span),
+            SemanticError::ConstantCondition { span, .. } => Some(*span),
+            SemanticError::SelfComparison { span, .. } => Some(*span),
+            SemanticError::IncompatibleComparison { span, .. } => Some(*span),
+            SemanticError::DuplicateBranch { span, .. } => Some(*span),
+            SemanticError::MissingDocstring { span, .. } => Some(*span),
+            SemanticError::DuplicateCode { span, .. } => Some(*span),
+            SemanticError::ComplexFunction { span, .. } => Some(*span),
+            SemanticError::ImportSideEffect { span, .. } => Some(*span),
+            SemanticError::UndocumentedParameter { span, .. } => Some(*span),
+            SemanticError::UnknownDocumentedParameter { span, .. } => Some(*span),
+            SemanticError::UndocumentedReturn { span, .. } => Some(*span),
+            SemanticError::InvalidMethodReceiver { span, .. } => Some(*span),
+            SemanticError::StaticMethodReceiver { span, .. } => Some(*span),
+            SemanticError::InconsistentMro { span, .. } => Some(*span),
+            SemanticError::SuperOutsideClass { span, .. } => Some(*span),
+            SemanticError::InvalidPropertySignature { span, .. } => Some(*span),
+            SemanticError::PositionalOnlyArgument { span, .. } => Some(*span),
+            SemanticError::KeywordOnlyArgument { span, .. } => Some(*span),
+            SemanticError::UnexpectedKeywordArgument { span, .. } => Some(*span),
+            SemanticError::DuplicateArgument { span, .. } => Some(*span),
+            SemanticError::MissingArgument { span, .. } => Some(*span),
+            SemanticError::NonDefaultAfterDefault { span, .. } => Some(*span),
+            SemanticError::ImpureFunction { span, .. } => Some(*span),
+            SemanticError::MutableDefault { span, .. } => Some(*span),
+            SemanticError::ConfusableIdentifier { span, .. } => Some(*span),
+            SemanticError::OptionalAccess { span, .. } => Some(*span),
+            SemanticError::UnknownAttribute { span, .. } => Some(*span),
+            SemanticError::UnknownModuleMember { span, .. } => Some(*span),
+            SemanticError::InvalidScope { .. } => None,
+        }
+    }
+
+    /// Check if this is a lint about suspicious but valid code
+    /// (unused or unreachable code) rather than a hard error
+    pub fn is_warning(&self) -> bool {
+        matches!(
+            self,
+            SemanticError::UnreachableCode { .. }
+                | SemanticError::DeadCode { .. }
+                | SemanticError::UnusedVariable { .. }
+                | SemanticError::UnusedFunction { .. }
+                | SemanticError::UnusedImport { .. }
+                | SemanticError::InfiniteRecursion { .. }
+                | SemanticError::UnusedParameter { .. }
+                | SemanticError::ShadowedName { .. }
+                | SemanticError::ReassignedDefinition { .. }
+                | SemanticError::ConstantCondition { .. }
+                | SemanticError::SelfComparison { .. }
+                | SemanticError::IncompatibleComparison { .. }
+                | SemanticError::DuplicateBranch { .. }
+                | SemanticError::MissingDocstring { .. }
+                | SemanticError::DuplicateCode { .. }
+                | SemanticError::ComplexFunction { .. }
+                | SemanticError::ImportSideEffect { .. }
+                | SemanticError::UndocumentedParameter { .. }
+                | SemanticError::UnknownDocumentedParameter { .. }
+                | SemanticError::UndocumentedReturn { .. }
+                | SemanticError::MutableDefault { .. }
+                | SemanticError::ConfusableIdentifier { .. }
+                | SemanticError::InvalidMethodReceiver { .. }
+                | SemanticError::StaticMethodReceiver { .. }
+                | SemanticError::OptionalAccess { .. }
+        )
+    }
+
+    /// Check if this lint is only reported when enabled (with
+    /// `enable_lint` on the analyzers, or a `[lints]` level in `silk.toml`)
+    pub fn is_allowed_by_default(&self) -> bool {
+        matches!(
+            self,
+            SemanticError::MissingDocstring { .. }
+                | SemanticError::DuplicateCode { .. }
+                | SemanticError::ComplexFunction { .. }
+                | SemanticError::ImportSideEffect { .. }
+        )
+    }
 }
@@ -0,0 +1,383 @@
+//! String formatting with a literal template
+//!
+//! Checks `"..." % args` and `"...".format(...)` when the template is a
+//! string literal, the way Python does when the line runs: the template is
+//! parsed, each placeholder is paired with its argument, and the type of the
+//! argument is checked against the conversion. Arguments whose type is not
+//! known are accepted. Messages are Python's, except for missing keys.
+
+use crate::types::Type;
+
+/// Arguments of a `%` operation
+pub(crate) enum PercentArgs {
+    /// Values of a tuple; a value that is not a tuple is a tuple of one
+    Positional(Vec<Type>),
+    /// A mapping, with the keys of a dict display whose keys are all strings
+    Mapping(Option<Vec<String>>),
+    /// A value whose type is not known, which may be a tuple or a mapping
+    Unknown,
+}
+
+/// Arguments of a `str.format` call
+pub(crate) struct FormatArgs<'a> {
+    pub positional: Vec<Type>,
+    pub keywords: Vec<(&'a str, Type)>,
+    /// A `**mapping` argument may supply any keyword
+    pub any_keyword: bool,
+}
+
+/// Check `template % args`, returning the error Python would raise
+pub(crate) fn check_percent(template: &str, args: &PercentArgs) -> Result<(), String> {
+    let chars: Vec<char> = template.chars().collect();
+    // A mapping is also a single value for placeholders without a key
+    let positional: Option<&[Type]> = match args {
+        PercentArgs::Positional(types) => Some(types),
+        PercentArgs::Mapping(_) => Some(&[Type::Unknown]),
+        PercentArgs::Unknown => None,
+    };
+    let mut next_arg = 0;
+    let mut take_arg = || -> Result<Option<&Type>, String> {
+        let Some(types) = positional else {
+            return Ok(None);
+        };
+        next_arg += 1;
+        types
+            .get(next_arg - 1)
+            .map(Some)
+            .ok_or_else(|| "not enough arguments for format string".to_string())
+    };
+
+    let mut i = 0;
+    while i < chars.len() {
+        if chars[i] != '%' {
+            i += 1;
+            continue;
+        }
+        i += 1;
+        let mut key = None;
+        if chars.get(i) == Some(&'(') {
+            // Keys may contain balanced parentheses
+            let mut depth = 1;
+            let start = i + 1;
+            while depth > 0 {
+                i += 1;
+                match chars.get(i) {
+                    Some('(') => depth += 1,
+                    Some(')') => depth -= 1,
+                    Some(_) => {}
+                    None => return Err("incomplete format key".to_string()),
+                }
+            }
+            key = Some(chars[start..i].iter().collect::<String>());
+            i += 1;
+        }
+        while matches!(chars.get(i), Some('-' | '+' | ' ' | '#' | '0')) {
+            i += 1;
+        }
+        for part in 0..2 {
+            if part == 1 {
+                if chars.get(i) != Some(&'.') {
+                    break;
+                }
+                i += 1;
+            }
+            if chars.get(i) == Some(&'*') {
+                i += 1;
+                if let Some(ty) = take_arg()? {
+                    if name_of(ty).is_some() && !matches!(ty, Type::Int | Type::Bool) {
+                        return Err("* wants int".to_string());
+                    }
+                }
+            } else {
+                while chars.get(i).is_some_and(char::is_ascii_digit) {
+                    i += 1;
+                }
+            }
+        }
+        if matches!(chars.get(i), Some('h' | 'l' | 'L')) {
+            i += 1;
+        }
+        let Some(&conversion) = chars.get(i) else {
+            return Err("incomplete format".to_string());
+        };
+        i += 1;
+        if conversion == '%' {
+            continue;
+        }
+        if !"diouxXeEfFgGcrsa".contains(conversion) {
+            return Err(format!(
+                "unsupported format character '{}' ({:#x}) at index {}",
+                conversion,
+                conversion as u32,
+                i - 1
+            ));
+        }
+        match (key, args) {
+            (Some(_), PercentArgs::Positional(_)) => {
+                return Err("format requires a mapping".to_string());
+            }
+            (Some(key), PercentArgs::Mapping(Some(keys))) if !keys.contains(&key) => {
+                return Err(format!("the format mapping has no key '{}'", key));
+            }
+            (Some(_), _) => {}
+            (None, _) => {
+                if let Some(ty) = take_arg()? {
+                    check_percent_type(conversion, ty)?;
+                }
+            }
+        }
+    }
+
+    match args {
+        PercentArgs::Positional(types) if next_arg < types.len() => {
+            Err("not all arguments converted during string formatting".to_string())
+        }
+        _ => Ok(()),
+    }
+}
+
+fn check_percent_type(conversion: char, ty: &Type) -> Result<(), String> {
+    let Some(name) = name_of(ty) else {
+        return Ok(());
+    };
+    let integer = matches!(ty, Type::Int | Type::Bool);
+    let real = integer || *ty == Type::Float;
+    match conversion {
+        'd' | 'i' | 'u' if !real => Err(format!(
+            "%{} format: a real number is required, not {}",
+            conversion, name
+        )),
+        'o' | 'x' | 'X' if !integer => Err(format!(
+            "%{} format: an integer is required, not {}",
+            conversion, name
+        )),
+        'e' | 'E' | 'f' | 'F' | 'g' | 'G' if !real => {
+            Err(format!("must be real number, not {}", name))
+        }
+        'c' if !integer && *ty != Type::Str => Err("%c requires int or char".to_string()),
+        _ => Ok(()),
+    }
+}
+
+/// Check `template.format(args)`, returning the error Python would raise
+pub(crate) fn check_format(template: &str, args: &FormatArgs) -> Result<(), String> {
+    let mut fields = Vec::new();
+    let chars: Vec<char> = template.chars().collect();
+    parse_template(&chars, &mut Numbering::Unset, 0, &mut fields)?;
+
+    for field in fields {
+        let ty = match &field.name {
+            FieldName::Index(index) => args.positional.get(*index).ok_or_else(|| {
+                format!(
+                    "Replacement index {} out of range for positional args tuple",
+                    index
+                )
+            })?,
+            FieldName::Keyword(name) => {
+                match args.keywords.iter().find(|(keyword, _)| keyword == name) {
+                    Some((_, ty)) => ty,
+                    None if args.any_keyword => continue,
+                    None => {
+                        return Err(format!(
+                            "no argument named '{}' for the format string",
+                            name
+                        ))
+                    }
+                }
+            }
+        };
+        // `{0.real}` and `{0[1]}` format a value of another type
+        if field.accessors {
+            continue;
+        }
+        let ty = if field.converted { &Type::Str } else { ty };
+        if let Some(spec) = &field.spec {
+            check_spec_type(spec, ty)?;
+        }
+    }
+    Ok(())
+}
+
+/// Automatic (`{}`) or manual (`{0}`) numbering of positional fields
+enum Numbering {
+    Unset,
+    Automatic(usize),
+    Manual,
+}
+
+enum FieldName {
+    Index(usize),
+    Keyword(String),
+}
+
+/// A replacement field of a `str.format` template
+struct Field {
+    name: FieldName,
+    /// Attribute or index lookups follow the argument name
+    accessors: bool,
+    /// `!r`, `!s` or `!a` turns the value into a string first
+    converted: bool,
+    /// Format spec, unless it contains nested fields
+    spec: Option<String>,
+}
+
+fn parse_template(
+    chars: &[char],
+    numbering: &mut Numbering,
+    depth: usize,
+    fields: &mut Vec<Field>,
+) -> Result<(), String> {
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '{' if chars.get(i + 1) == Some(&'{') => i += 2,
+            '}' if chars.get(i + 1) == Some(&'}') => i += 2,
+            '}' => return Err("Single '}' encountered in format string".to_string()),
+            '{' if i + 1 == chars.len() => {
+                return Err("Single '{' encountered in format string".to_string())
+            }
+            '{' => {
+                let mut nesting = 1;
+                let mut end = i + 1;
+                while end < chars.len() {
+                    match chars[end] {
+                        '{' => nesting += 1,
+                        '}' => nesting -= 1,
+                        _ => {}
+                    }
+                    if nesting == 0 {
+                        break;
+                    }
+                    end += 1;
+                }
+                if end == chars.len() {
+                    return Err("expected '}' before end of string".to_string());
+                }
+                if depth > 1 {
+                    return Err("Max string recursion exceeded".to_string());
+                }
+                parse_field(&chars[i + 1..end], numbering, depth, fields)?;
+                i = end + 1;
+            }
+            _ => i += 1,
+        }
+    }
+    Ok(())
+}
+
+fn parse_field(
+    text: &[char],
+    numbering: &mut Numbering,
+    depth: usize,
+    fields: &mut Vec<Field>,
+) -> Result<(), String> {
+    let name_end = text
+        .iter()
+        .position(|c| matches!(c, '!' | ':'))
+        .unwrap_or(text.len());
+    let field_name = &text[..name_end];
+    let arg_end = field_name
+        .iter()
+        .position(|c| matches!(c, '.' | '['))
+        .unwrap_or(field_name.len());
+    let arg_name: String = field_name[..arg_end].iter().collect();
+
+    let name =
+        if arg_name.is_empty() {
+            match numbering {
+                Numbering::Manual => return Err(
+                    "cannot switch from manual field specification to automatic field numbering"
+                        .to_string(),
+                ),
+                Numbering::Unset => {
+                    *numbering = Numbering::Automatic(1);
+                    FieldName::Index(0)
+                }
+                Numbering::Automatic(next) => {
+                    *next += 1;
+                    FieldName::Index(*next - 1)
+                }
+            }
+        } else if let Ok(index) = arg_name.parse::<usize>() {
+            if let Numbering::Automatic(_) = numbering {
+                return Err(
+                    "cannot switch from automatic field numbering to manual field specification"
+                        .to_string(),
+                );
+            }
+            *numbering = Numbering::Manual;
+            FieldName::Index(index)
+        } else {
+            FieldName::Keyword(arg_name)
+        };
+
+    let mut rest = &text[name_end..];
+    let mut converted = false;
+    if rest.first() == Some(&'!') {
+        match rest.get(1) {
+            Some('r' | 's' | 'a') => {}
+            Some(other) => return Err(format!("Unknown conversion specifier {}", other)),
+            None => return Err("end of string while looking for conversion specifier".to_string()),
+        }
+        if !matches!(rest.get(2), None | Some(':')) {
+            return Err("expected ':' after conversion specifier".to_string());
+        }
+        converted = true;
+        rest = &rest[2..];
+    }
+    let spec = rest.get(1..).unwrap_or_default();
+
+    fields.push(Field {
+        name,
+        accessors: arg_end < field_name.len(),
+        converted,
+        spec: (!spec.contains(&'{')).then(|| spec.iter().collect()),
+    });
+    // Nested fields of the spec are numbered after the field itself
+    parse_template(spec, numbering, depth + 1, fields)
+}
+
+/// Check a format spec without nested fields against the value's type
+fn check_spec_type(spec: &str, ty: &Type) -> Result<(), String> {
+    let Some(name) = name_of(ty) else {
+        return Ok(());
+    };
+    if spec.is_empty() {
+        return Ok(());
+    }
+    let allowed = match ty {
+        Type::Int | Type::Bool => "bcdoxXneEfFgG%",
+        Type::Float => "neEfFgG%",
+        Type::Str => "s",
+        _ => {
+            return Err(format!(
+                "unsupported format string passed to {}.__format__",
+                name
+            ))
+        }
+    };
+    match spec.chars().last() {
+        Some(code) if "bcdeEfFgGnosxX%".contains(code) && !allowed.contains(code) => Err(format!(
+            "Unknown format code '{}' for object of type '{}'",
+            code, name
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Python's name for the type of a value, when the type is known exactly
+fn name_of(ty: &Type) -> Option<&'static str> {
+    Some(match ty {
+        Type::Int => "int",
+        Type::Float => "float",
+        Type::Bool => "bool",
+        Type::Str => "str",
+        Type::Bytes => "bytes",
+        Type::None => "NoneType",
+        Type::List(_) => "list",
+        Type::Dict { .. } => "dict",
+        Type::Set(_) => "set",
+        Type::Tuple(_) => "tuple",
+        _ => return None,
+    })
+}
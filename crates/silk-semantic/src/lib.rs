@@ -1,3 +1,6 @@
+// SemanticError variants carry names and spans by value
+#![allow(clippy::result_large_err)]
+
 //! Semantic Analysis for Silk
 //!
 //! This crate implements semantic analysis including:
@@ -6,18 +9,44 @@
 //! - Name binding analysis
 //! - Type checking and inference
 //! - Control flow analysis
+//! - Call graph construction
 //! - Semantic validation
 
 pub mod analyzer;
+pub mod builtins;
+pub mod call_graph;
+pub mod classes;
+mod clones;
+mod conditions;
+mod confusables;
+mod const_eval;
+mod constants;
 pub mod control_flow;
+mod docstrings;
 pub mod error;
+mod formatting;
+pub mod metrics;
+mod narrowing;
+pub mod purity;
 pub mod scope;
+mod side_effects;
+pub mod signature;
+pub mod stdlib;
 pub mod symbol_table;
+pub mod typed;
 pub mod types;
 
 pub use analyzer::SemanticAnalyzer;
+pub use call_graph::{CallEdge, CallGraph, FunctionNode};
+pub use classes::{c3_linearization, short_name, ClassInfo, MethodInfo, MethodKind};
+pub use clones::DEFAULT_DUPLICATE_MIN_NODES;
 pub use control_flow::ControlFlowAnalyzer;
+pub use docstrings::docstring_of;
 pub use error::{SemanticError, SemanticResult};
+pub use metrics::{FunctionMetrics, MetricLimits};
+pub use purity::{Impurity, Purity};
 pub use scope::{Scope, ScopeKind};
-pub use symbol_table::{Symbol, SymbolKind, SymbolTable};
+pub use signature::{BindError, Binding, Param, ParamKind, Signature};
+pub use symbol_table::{Symbol, SymbolId, SymbolKind, SymbolTable};
+pub use typed::TypedProgram;
 pub use types::Type;
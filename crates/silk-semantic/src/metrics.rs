@@ -0,0 +1,213 @@
+//! Size and complexity metrics of functions
+//!
+//! Backs `silk analyze --metrics` and the `complex-function` lint. For each
+//! function and method, named by its qualified path as in the call graph:
+//! - cyclomatic complexity: one plus the number of decisions, counting
+//!   `if`/`elif`, loops, `except` clauses, `match` cases, conditional
+//!   expressions, `and`/`or` operators and the `for` and `if` clauses of
+//!   comprehensions. This is the number of independent paths through the
+//!   function's control flow graph, counted on the syntax tree.
+//! - nesting depth: the deepest chain of compound statements in the body,
+//!   an `elif` counting on the level of its `if`
+//! - parameters: every declared parameter except the method receiver
+//! - lines: from the `def` line to the last line with code
+//!
+//! Nested functions and classes are measured on their own and do not add
+//! to the function around them; lambdas do.
+
+use crate::call_graph::for_each_nested_block;
+use crate::classes::MethodKind;
+use silk_ast::{ExpressionKind, Node, NodeIndex, Program, Statement, StatementKind};
+use silk_lexer::Span;
+
+/// Metrics of one function or method
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionMetrics {
+    /// Qualified name (`outer.inner`, `Class.method`)
+    pub name: String,
+    /// Location of the definition
+    pub span: Span,
+    pub complexity: usize,
+    pub nesting: usize,
+    pub parameters: usize,
+    pub lines: usize,
+}
+
+/// Largest value of each metric before `complex-function` reports it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MetricLimits {
+    pub complexity: usize,
+    pub nesting: usize,
+    pub parameters: usize,
+    pub lines: usize,
+}
+
+impl Default for MetricLimits {
+    fn default() -> Self {
+        Self {
+            complexity: 10,
+            nesting: 4,
+            parameters: 6,
+            lines: 60,
+        }
+    }
+}
+
+impl FunctionMetrics {
+    /// Each metric above its limit: (metric name, value, limit)
+    pub fn exceeded(&self, limits: &MetricLimits) -> Vec<(&'static str, usize, usize)> {
+        [
+            ("cyclomatic complexity", self.complexity, limits.complexity),
+            ("nesting depth", self.nesting, limits.nesting),
+            ("parameter count", self.parameters, limits.parameters),
+            ("line count", self.lines, limits.lines),
+        ]
+        .into_iter()
+        .filter(|(_, value, limit)| value > limit)
+        .collect()
+    }
+}
+
+/// Metrics of every function and method of a program, in source order
+pub fn function_metrics(program: &Program) -> Vec<FunctionMetrics> {
+    let index = NodeIndex::new(program);
+    let mut metrics = Vec::new();
+    visit_block(&program.statements, None, false, &index, &mut metrics);
+    metrics
+}
+
+/// Measure the functions defined in a block; `prefix` is the qualified
+/// name of the enclosing function or class
+fn visit_block(
+    body: &[Statement],
+    prefix: Option<&str>,
+    in_class: bool,
+    index: &NodeIndex,
+    metrics: &mut Vec<FunctionMetrics>,
+) {
+    for statement in body {
+        let qualify = |name: &str| match prefix {
+            Some(prefix) => format!("{}.{}", prefix, name),
+            None => name.to_string(),
+        };
+        match &statement.kind {
+            StatementKind::FunctionDef {
+                name,
+                params,
+                body,
+                decorator_list,
+                ..
+            } => {
+                let receiver = in_class
+                    && MethodKind::from_decorators(name, decorator_list) != MethodKind::Static
+                    && params.positional().next().is_some();
+                let name = qualify(name);
+                metrics.push(FunctionMetrics {
+                    name: name.clone(),
+                    span: statement.span,
+                    complexity: 1 + decisions(statement, index),
+                    nesting: nesting(body),
+                    parameters: params.all().count() - usize::from(receiver),
+                    lines: last_line(statement, index) + 1 - statement.span.line,
+                });
+                visit_block(body, Some(&name), false, index, metrics);
+            }
+            StatementKind::ClassDef { name, body, .. } => {
+                visit_block(body, Some(&qualify(name)), true, index, metrics);
+            }
+            _ => for_each_nested_block(statement, &mut |block| {
+                visit_block(block, prefix, in_class, index, metrics)
+            }),
+        }
+    }
+}
+
+/// Nodes inside a definition, outside the functions and classes nested in it
+pub(crate) fn own_nodes<'a>(definition: &Statement, index: &'a NodeIndex) -> Vec<Node<'a>> {
+    let inside = |span: Span, outer: Span| outer.start <= span.start && span.end <= outer.end;
+    let nodes: Vec<Node> = index
+        .nodes_within(definition.span)
+        .filter(|node| {
+            !node
+                .as_statement()
+                .is_some_and(|statement| std::ptr::eq(statement, definition))
+        })
+        .collect();
+    let nested: Vec<Span> = nodes
+        .iter()
+        .filter_map(|node| node.as_statement())
+        .filter(|statement| {
+            matches!(
+                statement.kind,
+                StatementKind::FunctionDef { .. } | StatementKind::ClassDef { .. }
+            )
+        })
+        .map(|statement| statement.span)
+        .collect();
+    nodes
+        .into_iter()
+        .filter(|node| !nested.iter().any(|&outer| inside(node.span(), outer)))
+        .collect()
+}
+
+fn decisions(definition: &Statement, index: &NodeIndex) -> usize {
+    own_nodes(definition, index)
+        .iter()
+        .map(|node| match node {
+            Node::Statement(statement) => match &statement.kind {
+                StatementKind::If { .. }
+                | StatementKind::While { .. }
+                | StatementKind::For { .. } => 1,
+                StatementKind::Try { handlers, .. } => handlers.len(),
+                StatementKind::Match { cases, .. } => cases.len(),
+                _ => 0,
+            },
+            Node::Expression(expression) => match &expression.kind {
+                ExpressionKind::LogicalOp { .. } | ExpressionKind::IfExp { .. } => 1,
+                ExpressionKind::ListComp { generators, .. }
+                | ExpressionKind::SetComp { generators, .. }
+                | ExpressionKind::GeneratorExp { generators, .. }
+                | ExpressionKind::DictComp { generators, .. } => generators
+                    .iter()
+                    .map(|generator| 1 + generator.ifs.len())
+                    .sum(),
+                _ => 0,
+            },
+        })
+        .sum()
+}
+
+/// Deepest chain of compound statements in a block
+fn nesting(body: &[Statement]) -> usize {
+    body.iter().map(depth).max().unwrap_or(0)
+}
+
+/// Nesting of a statement: 0 for a simple one, 1 plus the deepest of its
+/// blocks for a compound one
+fn depth(statement: &Statement) -> usize {
+    // An `else` block that holds only an `if` is an `elif`, on the level
+    // of the `if` it continues
+    if let StatementKind::If { body, orelse, .. } = &statement.kind {
+        if let [elif @ Statement {
+            kind: StatementKind::If { .. },
+            ..
+        }] = orelse.as_slice()
+        {
+            return (nesting(body) + 1).max(depth(elif));
+        }
+    }
+    let mut deepest = None;
+    for_each_nested_block(statement, &mut |block| {
+        deepest = deepest.max(Some(nesting(block)));
+    });
+    deepest.map_or(0, |depth| depth + 1)
+}
+
+/// Last line on which a node of the definition starts
+fn last_line(definition: &Statement, index: &NodeIndex) -> usize {
+    index
+        .nodes_within(definition.span)
+        .map(|node| node.span().line)
+        .max()
+        .unwrap_or(definition.span.line)
+}
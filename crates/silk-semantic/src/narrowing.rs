@@ -0,0 +1,161 @@
+//! Flow-sensitive type narrowing
+//!
+//! Conditions such as `isinstance(x, int)`, `x is None` and plain `x` refine
+//! the type of `x` inside the branch they guard:
+//!
+//! ```text
+//! def f(x: int | None) -> int:
+//!     if x is None:
+//!         return 0
+//!     return x + 1      # x: int
+//! ```
+//!
+//! A branch that always exits (`return`, `raise`, `break`, `continue`)
+//! narrows the code after the `if` with the opposite condition. A narrowed
+//! type is tied to the variable's binding, so assigning to the variable
+//! drops it.
+
+use crate::types::Type;
+use silk_ast::{Expression, ExpressionKind, Statement, StatementKind};
+use silk_lexer::Span;
+use std::collections::HashMap;
+
+/// A variable's refined type
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct NarrowedVar {
+    pub name: String,
+    pub ty: Type,
+    /// Definition of the symbol being narrowed
+    pub binding: Span,
+}
+
+/// Types a condition refines, in the branch where it holds (`then`) and
+/// where it does not (`orelse`)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct Narrowing {
+    pub then: Vec<NarrowedVar>,
+    pub orelse: Vec<NarrowedVar>,
+}
+
+impl Narrowing {
+    /// `not condition`
+    pub fn negate(self) -> Self {
+        Self {
+            then: self.orelse,
+            orelse: self.then,
+        }
+    }
+
+    /// `a and b`: both hold in `then`; nothing is known when it fails
+    pub fn and(mut self, other: Self) -> Self {
+        self.then.extend(other.then);
+        Self {
+            then: self.then,
+            orelse: Vec::new(),
+        }
+    }
+
+    /// `a or b`: both fail in `orelse`; nothing is known when it holds
+    pub fn or(mut self, other: Self) -> Self {
+        self.orelse.extend(other.orelse);
+        Self {
+            then: Vec::new(),
+            orelse: self.orelse,
+        }
+    }
+}
+
+/// Narrowed variable types, one frame per enclosing branch
+#[derive(Debug)]
+pub(crate) struct NarrowedTypes {
+    frames: Vec<HashMap<String, (Type, Span)>>,
+}
+
+impl NarrowedTypes {
+    pub fn new() -> Self {
+        Self {
+            frames: vec![HashMap::new()],
+        }
+    }
+
+    /// Innermost narrowed type of `name`, if the variable is still bound
+    /// at `binding`
+    pub fn get(&self, name: &str, binding: Span) -> Option<&Type> {
+        self.frames
+            .iter()
+            .rev()
+            .find_map(|frame| frame.get(name))
+            .filter(|(_, span)| *span == binding)
+            .map(|(ty, _)| ty)
+    }
+
+    /// Enter a branch where `vars` hold
+    pub fn push(&mut self, vars: &[NarrowedVar]) {
+        self.frames.push(HashMap::new());
+        self.refine(vars);
+    }
+
+    /// Leave the innermost branch
+    pub fn pop(&mut self) {
+        if self.frames.len() > 1 {
+            self.frames.pop();
+        }
+    }
+
+    /// Narrow for the rest of the current branch
+    pub fn refine(&mut self, vars: &[NarrowedVar]) {
+        if let Some(frame) = self.frames.last_mut() {
+            for var in vars {
+                frame.insert(var.name.clone(), (var.ty.clone(), var.binding));
+            }
+        }
+    }
+}
+
+/// Variable a condition operand refers to: `x`, or `x` in `(x := value)`
+pub(crate) fn narrowed_name(expr: &Expression) -> Option<&str> {
+    match &expr.kind {
+        ExpressionKind::Identifier(name) => Some(name),
+        ExpressionKind::NamedExpr { target, .. } => narrowed_name(target),
+        _ => None,
+    }
+}
+
+/// Members of `declared` that `is_member` accepts, or `fallback` when none do
+pub(crate) fn filter_members(
+    declared: &Type,
+    fallback: Type,
+    mut is_member: impl FnMut(&Type) -> bool,
+) -> Type {
+    let kept: Vec<Type> = declared
+        .members()
+        .iter()
+        .filter(|member| is_member(member))
+        .cloned()
+        .collect();
+    if kept.is_empty() {
+        fallback
+    } else {
+        Type::union(kept)
+    }
+}
+
+/// Check if a block always leaves the enclosing branch
+pub(crate) fn always_exits(mut body: &[Statement]) -> bool {
+    // An `elif` ladder nests in `orelse`, so follow it in a loop
+    loop {
+        let Some(stmt) = body.last() else {
+            return false;
+        };
+        match &stmt.kind {
+            StatementKind::Return { .. }
+            | StatementKind::Raise { .. }
+            | StatementKind::Break
+            | StatementKind::Continue => return true,
+            StatementKind::If {
+                body: then, orelse, ..
+            } if always_exits(then) => body = orelse,
+            _ => return false,
+        }
+    }
+}
@@ -0,0 +1,507 @@
+//! Purity of functions
+//!
+//! A function is pure when calling it has no effect besides returning a
+//! value: it does no I/O and changes nothing its caller can see. Purity is
+//! inferred from the body without running it. A function is impure when
+//! its body, outside the functions, classes and lambdas nested in it:
+//! - calls anything not known to be pure. Pure are the pure built-ins,
+//!   the pure functions of standard modules, methods of string literals,
+//!   methods of lists, dicts and sets the function created itself, and
+//!   module-level functions inferred pure.
+//! - assigns to or deletes an attribute or item of anything it did not
+//!   create itself
+//! - declares a name `global` or `nonlocal`, imports a module or enters a
+//!   `with` block
+//!
+//! Module-level functions are inferred together: each is assumed pure
+//! until something in its body shows otherwise, so functions that only
+//! call each other are pure. A module-level function with a decorator
+//! other than `@pure` is called through the decorator, so calling it is
+//! not pure even when its body is.
+//!
+//! The analyzer records the result on function symbols, folds constant
+//! calls of pure functions, and checks functions marked `@pure`.
+
+use crate::classes::add_target_names;
+use crate::conditions::dotted_name;
+use crate::metrics::own_nodes;
+use crate::{builtins, stdlib};
+use silk_ast::{
+    ensure_sufficient_stack, Expression, ExpressionKind, Node, NodeIndex, Program, Statement,
+    StatementKind,
+};
+use silk_lexer::Span;
+use std::collections::{HashMap, HashSet};
+
+/// Why a function is not pure
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Impurity {
+    /// What the function does, e.g. `calls 'print'`
+    pub reason: String,
+    /// Where it does it
+    pub span: Span,
+}
+
+/// Inferred purity of the functions of a program
+#[derive(Debug, Clone, Default)]
+pub struct Purity {
+    /// Why each impure function is impure, by the span of its definition
+    impure: HashMap<Span, Impurity>,
+    /// Functions marked `@pure`: name and span of the definition
+    marked: Vec<(String, Span)>,
+    /// Module-level names that calls are resolved against
+    names: ModuleNames,
+}
+
+impl Purity {
+    /// Infer the purity of every function and method of a program
+    pub fn infer(program: &Program) -> Self {
+        let index = NodeIndex::new(program);
+        let mut names = ModuleNames::default();
+        let mut functions = Vec::new();
+        names.collect(&program.statements, &mut functions);
+
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for (name, _) in &functions {
+            *counts.entry(name.as_str()).or_default() += 1;
+        }
+        names.pure_functions = functions
+            .iter()
+            .filter(|(name, definition)| counts[name.as_str()] == 1 && !is_wrapped(definition))
+            .map(|(name, _)| name.clone())
+            .collect();
+        // Drop the functions shown impure until the rest are consistent
+        loop {
+            let impure: Vec<&String> = functions
+                .iter()
+                .filter(|(name, definition)| {
+                    names.pure_functions.contains(name)
+                        && impurity(definition, &names, &index).is_some()
+                })
+                .map(|(name, _)| name)
+                .collect();
+            if impure.is_empty() {
+                break;
+            }
+            for name in impure {
+                names.pure_functions.remove(name);
+            }
+        }
+
+        let mut purity = Self {
+            names,
+            ..Self::default()
+        };
+        for definition in index.nodes().filter_map(|node| node.as_statement()) {
+            let StatementKind::FunctionDef {
+                name,
+                decorator_list,
+                ..
+            } = &definition.kind
+            else {
+                continue;
+            };
+            if decorator_list.iter().any(is_pure_decorator) {
+                purity.marked.push((name.clone(), definition.span));
+            }
+            if let Some(impurity) = impurity(definition, &purity.names, &index) {
+                purity.impure.insert(definition.span, impurity);
+            }
+        }
+        purity
+    }
+
+    /// Whether the body of the function defined at `definition` is pure
+    pub fn is_pure(&self, definition: Span) -> bool {
+        !self.impure.contains_key(&definition)
+    }
+
+    /// Why the function defined at `definition` is not pure
+    pub fn impurity(&self, definition: Span) -> Option<&Impurity> {
+        self.impure.get(&definition)
+    }
+
+    /// Whether calling the module-level function `name` is pure
+    pub fn is_pure_function(&self, name: &str) -> bool {
+        self.names.pure_functions.contains(name)
+    }
+
+    /// Functions marked `@pure` whose body is not pure: name, span of the
+    /// definition and why
+    pub fn violations(&self) -> impl Iterator<Item = (&str, Span, &Impurity)> + '_ {
+        self.marked
+            .iter()
+            .filter_map(|(name, span)| Some((name.as_str(), *span, self.impure.get(span)?)))
+    }
+
+    /// Whether a call of `callee` in module-level code is known to be pure
+    pub(crate) fn is_pure_call(&self, callee: &Expression) -> bool {
+        self.names.is_pure_call(callee, &Locals::default())
+    }
+}
+
+/// Module-level names that decide what a callee refers to
+#[derive(Debug, Clone, Default)]
+struct ModuleNames {
+    /// Functions and classes defined in the module, which hide built-ins
+    defined: HashSet<String>,
+    /// Dotted path each imported name refers to (`sqrt` → `math.sqrt`)
+    imports: HashMap<String, String>,
+    /// Module-level functions calls of which are pure
+    pure_functions: HashSet<String>,
+}
+
+impl ModuleNames {
+    /// Collect the names bound in a module-level block, and its functions
+    fn collect<'a>(&mut self, body: &'a [Statement], functions: &mut Vec<(String, &'a Statement)>) {
+        ensure_sufficient_stack(|| self.collect_block(body, functions))
+    }
+
+    fn collect_block<'a>(
+        &mut self,
+        body: &'a [Statement],
+        functions: &mut Vec<(String, &'a Statement)>,
+    ) {
+        for statement in body {
+            match &statement.kind {
+                StatementKind::FunctionDef { name, .. } => {
+                    self.defined.insert(name.clone());
+                    functions.push((name.clone(), statement));
+                }
+                StatementKind::ClassDef { name, .. } => {
+                    self.defined.insert(name.clone());
+                }
+                StatementKind::Import { names } => {
+                    for alias in names {
+                        let path = match alias.asname {
+                            Some(_) => alias.name.clone(),
+                            None => alias.bound_name().to_string(),
+                        };
+                        self.imports.insert(alias.bound_name().to_string(), path);
+                    }
+                }
+                StatementKind::ImportFrom {
+                    module: Some(module),
+                    names,
+                    level: 0,
+                } => {
+                    for alias in names {
+                        let path = format!("{}.{}", module, alias.name);
+                        self.imports.insert(alias.bound_name().to_string(), path);
+                    }
+                }
+                // A function or built-in replaced by assignment is no
+                // longer known
+                StatementKind::Assign { targets, .. } => {
+                    for target in targets {
+                        add_target_names(target, &mut self.defined);
+                    }
+                }
+                StatementKind::AnnAssign { target, .. } => {
+                    add_target_names(target, &mut self.defined);
+                }
+                _ => crate::call_graph::for_each_nested_block(statement, &mut |block| {
+                    self.collect(block, functions)
+                }),
+            }
+        }
+    }
+
+    /// Whether calling `callee` is known to have no side effects
+    fn is_pure_call(&self, callee: &Expression, locals: &Locals) -> bool {
+        if let ExpressionKind::Attribute { value, .. } = &callee.kind {
+            let fresh = matches!(&value.kind, ExpressionKind::Identifier(name) if locals.fresh.contains(name));
+            if fresh || is_string_literal(value) {
+                return true;
+            }
+        }
+        let Some(name) = dotted_name(callee) else {
+            return false;
+        };
+        let (head, rest) = match name.split_once('.') {
+            Some((head, rest)) => (head, Some(rest)),
+            None => (name.as_str(), None),
+        };
+        if locals.names.contains(head) {
+            return false;
+        }
+        if let Some(import) = self.imports.get(head) {
+            let path = match rest {
+                Some(rest) => format!("{}.{}", import, rest),
+                None => import.clone(),
+            };
+            return path
+                .rsplit_once('.')
+                .is_some_and(|(module, member)| stdlib::is_pure_member(module, member));
+        }
+        if rest.is_some() {
+            return false;
+        }
+        if self.defined.contains(head) {
+            return self.pure_functions.contains(head);
+        }
+        builtins::is_pure_builtin(head)
+    }
+}
+
+/// Names bound inside a function
+#[derive(Debug, Default)]
+struct Locals {
+    /// Parameters and every name the body binds
+    names: HashSet<String>,
+    /// Names only ever bound to a list, dict or set display or
+    /// comprehension, so their value was created by the function
+    fresh: HashSet<String>,
+}
+
+impl Locals {
+    fn new(definition: &Statement, nodes: &[Node], index: &NodeIndex) -> Self {
+        let mut locals = Self::default();
+        let mut shared = HashSet::new();
+        let StatementKind::FunctionDef { params, body, .. } = &definition.kind else {
+            return locals;
+        };
+        for param in params.all() {
+            locals.names.insert(param.name.clone());
+            shared.insert(param.name.clone());
+        }
+
+        let mut bind = |names: HashSet<String>, fresh: bool| {
+            for name in names {
+                if fresh {
+                    locals.fresh.insert(name.clone());
+                } else {
+                    shared.insert(name.clone());
+                }
+                locals.names.insert(name);
+            }
+        };
+        let targets = |targets: &[&Expression]| {
+            let mut names = HashSet::new();
+            for target in targets {
+                add_target_names(target, &mut names);
+            }
+            names
+        };
+        let patterns = |patterns: &[&silk_ast::Pattern]| -> HashSet<String> {
+            patterns
+                .iter()
+                .flat_map(|pattern| pattern.bound_names())
+                .map(|(name, _)| name.to_string())
+                .collect()
+        };
+        for node in nodes {
+            match node {
+                Node::Statement(statement) => match &statement.kind {
+                    StatementKind::Assign {
+                        targets: to, value, ..
+                    } => {
+                        let simple = to
+                            .iter()
+                            .all(|target| matches!(target.kind, ExpressionKind::Identifier(_)));
+                        bind(
+                            targets(&to.iter().collect::<Vec<_>>()),
+                            simple && is_fresh(value),
+                        );
+                    }
+                    StatementKind::AnnAssign { target, value, .. } => {
+                        let fresh = matches!(target.kind, ExpressionKind::Identifier(_))
+                            && value.as_ref().is_some_and(is_fresh);
+                        bind(targets(&[target]), fresh);
+                    }
+                    StatementKind::For { target, .. } => bind(patterns(&[target]), false),
+                    StatementKind::With { items, .. } => {
+                        let vars: Vec<&Expression> = items
+                            .iter()
+                            .filter_map(|item| item.optional_vars.as_ref())
+                            .collect();
+                        bind(targets(&vars), false);
+                    }
+                    StatementKind::Try { handlers, .. } => {
+                        let names = handlers.iter().filter_map(|handler| handler.name.clone());
+                        bind(names.collect(), false);
+                    }
+                    StatementKind::Match { cases, .. } => {
+                        let cases: Vec<_> = cases.iter().map(|case| &case.pattern).collect();
+                        bind(patterns(&cases), false);
+                    }
+                    _ => {}
+                },
+                Node::Expression(expression) => match &expression.kind {
+                    ExpressionKind::NamedExpr { target, .. } => bind(targets(&[target]), false),
+                    ExpressionKind::ListComp { generators, .. }
+                    | ExpressionKind::SetComp { generators, .. }
+                    | ExpressionKind::GeneratorExp { generators, .. }
+                    | ExpressionKind::DictComp { generators, .. } => {
+                        let targets: Vec<_> = generators.iter().map(|g| &g.target).collect();
+                        bind(patterns(&targets), false);
+                    }
+                    _ => {}
+                },
+            }
+        }
+        // Functions and classes defined in the body are left out of its
+        // nodes, but bind names all the same
+        for node in index.nodes_within(block_span(body).unwrap_or(definition.span)) {
+            if let Some(
+                StatementKind::FunctionDef { name, .. } | StatementKind::ClassDef { name, .. },
+            ) = node.as_statement().map(|statement| &statement.kind)
+            {
+                bind(HashSet::from([name.clone()]), false);
+            }
+        }
+
+        locals.fresh.retain(|name| !shared.contains(name));
+        locals
+    }
+}
+
+/// The first thing in the body of `definition` that makes it impure
+fn impurity(definition: &Statement, names: &ModuleNames, index: &NodeIndex) -> Option<Impurity> {
+    let StatementKind::FunctionDef { body, .. } = &definition.kind else {
+        return None;
+    };
+    // Decorators, defaults and annotations run when the function is
+    // defined, and lambda bodies when the lambda is called
+    let body_span = block_span(body)?;
+    let nodes: Vec<Node> = own_nodes(definition, index)
+        .into_iter()
+        .filter(|node| contains(body_span, node.span()))
+        .collect();
+    let lambdas: Vec<Span> = nodes
+        .iter()
+        .filter_map(|node| match &node.as_expression()?.kind {
+            ExpressionKind::Lambda { body, .. } => Some(body.span),
+            _ => None,
+        })
+        .collect();
+    let nodes: Vec<Node> = nodes
+        .into_iter()
+        .filter(|node| !lambdas.iter().any(|&lambda| contains(lambda, node.span())))
+        .collect();
+
+    let locals = Locals::new(definition, &nodes, index);
+    nodes.iter().find_map(|node| {
+        let reason = match node {
+            Node::Statement(statement) => statement_impurity(statement, &locals)?,
+            Node::Expression(expression) => match &expression.kind {
+                ExpressionKind::Call { func, .. } if !names.is_pure_call(func, &locals) => {
+                    format!("calls '{}'", func)
+                }
+                _ => return None,
+            },
+        };
+        Some(Impurity {
+            reason,
+            span: node.span(),
+        })
+    })
+}
+
+/// What a statement does that a pure function may not
+fn statement_impurity(statement: &Statement, locals: &Locals) -> Option<String> {
+    match &statement.kind {
+        StatementKind::Global { names } => Some(format!("declares '{}' global", names.first()?)),
+        StatementKind::Nonlocal { names } => {
+            Some(format!("declares '{}' nonlocal", names.first()?))
+        }
+        StatementKind::Import { names } => Some(format!("imports '{}'", names.first()?.name)),
+        StatementKind::ImportFrom { module, .. } => Some(format!(
+            "imports from '{}'",
+            module.as_deref().unwrap_or(".")
+        )),
+        StatementKind::With { .. } => Some("enters a 'with' block".to_string()),
+        StatementKind::Assign { targets, .. } => targets
+            .iter()
+            .find_map(|target| shared_target(target, locals))
+            .map(|target| format!("assigns to '{}'", target)),
+        StatementKind::AugAssign { target, .. } | StatementKind::AnnAssign { target, .. } => {
+            shared_target(target, locals).map(|target| format!("assigns to '{}'", target))
+        }
+        StatementKind::Delete { targets } => targets
+            .iter()
+            .find_map(|target| shared_target(target, locals))
+            .map(|target| format!("deletes '{}'", target)),
+        _ => None,
+    }
+}
+
+/// An attribute or item in `target` of a value the function did not create
+fn shared_target<'a>(target: &'a Expression, locals: &Locals) -> Option<&'a Expression> {
+    match &target.kind {
+        ExpressionKind::Tuple { elements } | ExpressionKind::List { elements } => elements
+            .iter()
+            .find_map(|element| shared_target(element, locals)),
+        ExpressionKind::Attribute { value, .. } | ExpressionKind::Subscript { value, .. } => {
+            match &root(value).kind {
+                ExpressionKind::Identifier(name) if locals.fresh.contains(name) => None,
+                _ => Some(target),
+            }
+        }
+        _ => None,
+    }
+}
+
+/// The object an attribute or subscript chain starts from
+fn root(expression: &Expression) -> &Expression {
+    match &expression.kind {
+        ExpressionKind::Attribute { value, .. } | ExpressionKind::Subscript { value, .. } => {
+            root(value)
+        }
+        _ => expression,
+    }
+}
+
+/// A new list, dict or set
+fn is_fresh(value: &Expression) -> bool {
+    matches!(
+        value.kind,
+        ExpressionKind::List { .. }
+            | ExpressionKind::Dict { .. }
+            | ExpressionKind::Set { .. }
+            | ExpressionKind::ListComp { .. }
+            | ExpressionKind::DictComp { .. }
+            | ExpressionKind::SetComp { .. }
+    )
+}
+
+fn is_string_literal(expression: &Expression) -> bool {
+    matches!(
+        expression.kind,
+        ExpressionKind::String(_)
+            | ExpressionKind::RawString(_)
+            | ExpressionKind::ByteString(_)
+            | ExpressionKind::ByteRawString(_)
+            | ExpressionKind::FString { .. }
+    )
+}
+
+/// `@pure`
+fn is_pure_decorator(decorator: &Expression) -> bool {
+    matches!(&decorator.kind, ExpressionKind::Identifier(name) if name == "pure")
+}
+
+/// Whether a definition has a decorator that may replace the function
+fn is_wrapped(definition: &Statement) -> bool {
+    match &definition.kind {
+        StatementKind::FunctionDef { decorator_list, .. } => {
+            !decorator_list.iter().all(is_pure_decorator)
+        }
+        _ => false,
+    }
+}
+
+fn contains(outer: Span, inner: Span) -> bool {
+    outer.start <= inner.start && inner.end <= outer.end
+}
+
+/// Span from the start of a block to its end
+pub(crate) fn block_span(body: &[Statement]) -> Option<Span> {
+    let (first, last) = (body.first()?, body.last()?);
+    Some(Span::new(
+        first.span.start,
+        last.span.end,
+        first.span.line,
+        first.span.column,
+    ))
+}
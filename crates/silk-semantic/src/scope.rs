@@ -1,6 +1,7 @@
 //! Scope management for semantic analysis
 
 use crate::symbol_table::Symbol;
+use silk_lexer::Span;
 use std::collections::HashMap;
 
 /// Types of scopes in Silk
@@ -14,6 +15,8 @@ pub enum ScopeKind {
     Class,
     /// Local scope (e.g., inside if, while, for blocks)
     Local,
+    /// Comprehension scope (list/set/dict comprehensions and generator expressions)
+    Comprehension,
 }
 
 /// Represents a single scope containing symbols
@@ -25,6 +28,9 @@ pub struct Scope {
     symbols: HashMap<String, Symbol>,
     /// Index of parent scope (None for global scope)
     parent: Option<usize>,
+    /// Source range the scope covers (None for the global scope, which
+    /// covers the whole program)
+    span: Option<Span>,
 }
 
 impl Scope {
@@ -34,6 +40,15 @@ impl Scope {
             kind,
             symbols: HashMap::new(),
             parent,
+            span: None,
+        }
+    }
+
+    /// Create a new scope covering a range of the source
+    pub fn with_span(kind: ScopeKind, parent: Option<usize>, span: Span) -> Self {
+        Self {
+            span: Some(span),
+            ..Self::new(kind, parent)
         }
     }
 
@@ -57,6 +72,17 @@ impl Scope {
         self.parent
     }
 
+    /// Get the source range this scope covers
+    pub fn span(&self) -> Option<Span> {
+        self.span
+    }
+
+    /// Whether the scope covers a character offset of the source
+    pub fn contains(&self, offset: usize) -> bool {
+        self.span
+            .is_none_or(|span| span.start <= offset && offset < span.end)
+    }
+
     /// Get all symbols in this scope
     pub fn symbols(&self) -> &HashMap<String, Symbol> {
         &self.symbols
@@ -0,0 +1,84 @@
+//! Calls run on import (`import-side-effect`, allowed by default)
+//!
+//! Importing a module runs its top-level code, so a module that does more
+//! there than define functions and classes and bind constants does work,
+//! or I/O, for every importer. Every call that runs on import is reported
+//! unless the callee is known to be pure: a pure built-in function, an
+//! exception class, a pure function of a standard module (`math.sqrt`,
+//! `os.path.join`), a method of a string literal (`", ".join(names)`) or a
+//! function of the module inferred pure (see [`Purity`]).
+//!
+//! Class bodies, decorators, default values and base classes run on import
+//! and are checked; function and lambda bodies run only when called, and
+//! the body of `if __name__ == "__main__":` only when the module is run as
+//! a program, so they are not.
+
+use crate::purity::{block_span, Purity};
+use silk_ast::{CompareOperator, Expression, ExpressionKind, NodeIndex, Program, StatementKind};
+use silk_lexer::Span;
+
+/// A call that runs when the module is imported
+pub(crate) struct SideEffect {
+    /// The called expression, as written
+    pub callee: String,
+    pub span: Span,
+}
+
+/// Calls with possible side effects in the code run on import
+pub(crate) fn import_side_effects(program: &Program) -> Vec<SideEffect> {
+    let purity = Purity::infer(program);
+
+    let index = NodeIndex::new(program);
+    let mut skipped = Vec::new();
+    for node in index.nodes() {
+        match node.as_statement().map(|statement| &statement.kind) {
+            Some(StatementKind::FunctionDef { body, .. }) => skipped.extend(block_span(body)),
+            Some(StatementKind::If { test, body, .. }) if is_main_guard(test) => {
+                skipped.extend(block_span(body))
+            }
+            _ => {}
+        }
+        if let Some(ExpressionKind::Lambda { body, .. }) =
+            node.as_expression().map(|expression| &expression.kind)
+        {
+            skipped.push(body.span);
+        }
+    }
+
+    index
+        .nodes()
+        .filter_map(|node| node.as_expression())
+        .filter(|expression| {
+            !skipped
+                .iter()
+                .any(|span| span.start <= expression.span.start && expression.span.end <= span.end)
+        })
+        .filter_map(|expression| match &expression.kind {
+            ExpressionKind::Call { func, .. } if !purity.is_pure_call(func) => Some(SideEffect {
+                callee: func.to_string(),
+                span: expression.span,
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+/// `__name__ == "__main__"`, in either order
+fn is_main_guard(test: &Expression) -> bool {
+    let ExpressionKind::Compare {
+        left,
+        ops,
+        comparators,
+    } = &test.kind
+    else {
+        return false;
+    };
+    let name = |e: &Expression| matches!(&e.kind, ExpressionKind::Identifier(n) if n == "__name__");
+    let main = |e: &Expression| matches!(&e.kind, ExpressionKind::String(s) if s == "__main__");
+    match (ops.as_slice(), comparators.as_slice()) {
+        ([CompareOperator::Eq], [right]) => {
+            (name(left) && main(right)) || (main(left) && name(right))
+        }
+        _ => false,
+    }
+}
@@ -0,0 +1,348 @@
+//! Function signatures and call argument binding
+//!
+//! A signature records every parameter of a function together with how it
+//! may be passed:
+//! - Positional-only parameters (before `/`) cannot be passed by keyword
+//! - Keyword-only parameters (after `*` or `*args`) cannot be passed by position
+//! - `*args` and `**kwargs` absorb extra positional and keyword arguments
+//!
+//! Binding a call maps each argument to the parameter that receives it,
+//! following the same rules Python uses.
+
+use crate::types::Type;
+use silk_ast::FunctionParams;
+use std::fmt;
+use std::ops::Range;
+
+/// How a parameter may be passed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParamKind {
+    /// Declared before `/`
+    PositionalOnly,
+    /// Regular parameter, passed by position or keyword
+    PositionalOrKeyword,
+    /// `*args`
+    VarPositional,
+    /// Declared after `*` or `*args`
+    KeywordOnly,
+    /// `**kwargs`
+    VarKeyword,
+}
+
+/// A single function parameter
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Param {
+    pub name: String,
+    pub kind: ParamKind,
+    /// Declared type (Unknown if not annotated)
+    pub ty: Type,
+    pub has_default: bool,
+}
+
+impl Param {
+    /// Whether every call must supply this parameter
+    pub fn is_required(&self) -> bool {
+        !self.has_default
+            && matches!(
+                self.kind,
+                ParamKind::PositionalOnly | ParamKind::PositionalOrKeyword | ParamKind::KeywordOnly
+            )
+    }
+
+    fn is_positional(&self) -> bool {
+        matches!(
+            self.kind,
+            ParamKind::PositionalOnly | ParamKind::PositionalOrKeyword
+        )
+    }
+}
+
+/// Why a call's arguments do not fit a signature
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BindError {
+    /// More positional arguments than positional parameters; `keyword_only`
+    /// names the first keyword-only parameter, if any
+    TooManyPositional {
+        expected: usize,
+        given: usize,
+        keyword_only: Option<String>,
+    },
+    /// A positional-only parameter was passed by keyword
+    PositionalOnlyAsKeyword(String),
+    /// A keyword that matches no parameter
+    UnexpectedKeyword(String),
+    /// A parameter received both a positional and a keyword argument
+    MultipleValues(String),
+    /// Required parameters that received no argument
+    Missing(Vec<String>),
+}
+
+/// Parameter index receiving each argument of a call
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Binding {
+    /// One entry per positional argument
+    pub positional: Vec<usize>,
+    /// One entry per keyword argument (`None` for `**mapping` unpacking)
+    pub keywords: Vec<Option<usize>>,
+}
+
+/// The parameters of a function, in declaration order
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Signature {
+    pub params: Vec<Param>,
+}
+
+impl Signature {
+    /// Build a signature, resolving annotations with `resolve`
+    pub fn from_params(
+        params: &FunctionParams,
+        mut resolve: impl FnMut(&silk_ast::Type) -> Type,
+    ) -> Self {
+        let mut signature = Signature::default();
+        let mut push = |arg: &silk_ast::FunctionArg, kind| {
+            signature.params.push(Param {
+                name: arg.name.clone(),
+                kind,
+                ty: arg.annotation.as_ref().map_or(Type::Unknown, &mut resolve),
+                has_default: arg.default.is_some(),
+            })
+        };
+
+        for arg in &params.posonlyargs {
+            push(arg, ParamKind::PositionalOnly);
+        }
+        for arg in &params.args {
+            push(arg, ParamKind::PositionalOrKeyword);
+        }
+        if let Some(arg) = &params.vararg {
+            push(arg, ParamKind::VarPositional);
+        }
+        for arg in &params.kwonlyargs {
+            push(arg, ParamKind::KeywordOnly);
+        }
+        if let Some(arg) = &params.kwarg {
+            push(arg, ParamKind::VarKeyword);
+        }
+
+        signature
+    }
+
+    /// Drop the implicit receiver (`self`/`cls`), the first positional parameter
+    pub fn without_receiver(mut self) -> Self {
+        if self.params.first().is_some_and(Param::is_positional) {
+            self.params.remove(0);
+        }
+        self
+    }
+
+    /// Number of parameters, including `*args` and `**kwargs`
+    pub fn len(&self) -> usize {
+        self.params.len()
+    }
+
+    /// Whether the function takes no parameters
+    pub fn is_empty(&self) -> bool {
+        self.params.is_empty()
+    }
+
+    /// Look up a parameter by name
+    pub fn param(&self, name: &str) -> Option<&Param> {
+        self.params.iter().find(|param| param.name == name)
+    }
+
+    /// Number of required positional parameters
+    pub fn required_positional(&self) -> usize {
+        self.params
+            .iter()
+            .filter(|param| param.is_positional() && param.is_required())
+            .count()
+    }
+
+    /// Bind `positional` positional arguments and the given keyword names
+    /// (`None` for a `**mapping` unpacking) to parameters
+    ///
+    /// A `**mapping` may supply any keyword, so missing parameters are not
+    /// reported when one is present.
+    pub fn bind(&self, positional: usize, keywords: &[Option<&str>]) -> Result<Binding, BindError> {
+        let mut filled = vec![false; self.params.len()];
+        let find = |kind: ParamKind| self.params.iter().position(|param| param.kind == kind);
+
+        // Positional arguments fill positional parameters, then *args
+        let slots: Vec<usize> = (0..self.params.len())
+            .filter(|&i| self.params[i].is_positional())
+            .collect();
+        let mut binding = Binding {
+            positional: Vec::with_capacity(positional),
+            keywords: Vec::with_capacity(keywords.len()),
+        };
+        for index in 0..positional {
+            match slots.get(index).copied().or(find(ParamKind::VarPositional)) {
+                Some(slot) => {
+                    filled[slot] = true;
+                    binding.positional.push(slot);
+                }
+                None => {
+                    return Err(BindError::TooManyPositional {
+                        expected: slots.len(),
+                        given: positional,
+                        keyword_only: self
+                            .params
+                            .iter()
+                            .find(|param| param.kind == ParamKind::KeywordOnly)
+                            .map(|param| param.name.clone()),
+                    })
+                }
+            }
+        }
+
+        // Keywords fill named parameters, then **kwargs
+        for keyword in keywords {
+            let Some(name) = keyword else {
+                binding.keywords.push(None);
+                continue;
+            };
+            let named = self.params.iter().position(|param| {
+                param.name == *name
+                    && matches!(
+                        param.kind,
+                        ParamKind::PositionalOrKeyword | ParamKind::KeywordOnly
+                    )
+            });
+            let slot = match named {
+                Some(slot) if filled[slot] => {
+                    return Err(BindError::MultipleValues(name.to_string()))
+                }
+                Some(slot) => slot,
+                None => match find(ParamKind::VarKeyword) {
+                    Some(slot) => slot,
+                    None => {
+                        let positional_only = self.params.iter().any(|param| {
+                            param.name == *name && param.kind == ParamKind::PositionalOnly
+                        });
+                        return Err(if positional_only {
+                            BindError::PositionalOnlyAsKeyword(name.to_string())
+                        } else {
+                            BindError::UnexpectedKeyword(name.to_string())
+                        });
+                    }
+                },
+            };
+            filled[slot] = true;
+            binding.keywords.push(Some(slot));
+        }
+
+        // Every required parameter must have received an argument
+        if !keywords.iter().any(Option::is_none) {
+            let missing: Vec<String> = self
+                .params
+                .iter()
+                .zip(&filled)
+                .filter(|(param, filled)| param.is_required() && !**filled)
+                .map(|(param, _)| param.name.clone())
+                .collect();
+            if !missing.is_empty() {
+                return Err(BindError::Missing(missing));
+            }
+        }
+
+        Ok(binding)
+    }
+}
+
+/// `name: type = ...`, with `*`/`**` for `*args` and `**kwargs`; unknown
+/// types and default values are left out
+impl fmt::Display for Param {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            ParamKind::VarPositional => write!(f, "*")?,
+            ParamKind::VarKeyword => write!(f, "**")?,
+            _ => {}
+        }
+        write!(f, "{}", self.name)?;
+        if self.ty != Type::Unknown {
+            write!(f, ": {}", self.ty)?;
+        }
+        if self.has_default {
+            write!(f, " = ...")?;
+        }
+        Ok(())
+    }
+}
+
+/// The parameter list as written, e.g. `(a: int, /, b = ..., *, c)`
+impl fmt::Display for Signature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let parts: Vec<String> = self.parts().into_iter().map(|(_, text)| text).collect();
+        write!(f, "({})", parts.join(", "))
+    }
+}
+
+impl Signature {
+    /// Byte range of each parameter in the `Display` form, in parameter
+    /// order, for highlighting one of them
+    pub fn param_ranges(&self) -> Vec<Range<usize>> {
+        let mut ranges = Vec::with_capacity(self.params.len());
+        let mut offset = 1; // `(`
+        for (index, text) in self.parts() {
+            if index.is_some() {
+                ranges.push(offset..offset + text.len());
+            }
+            offset += text.len() + ", ".len();
+        }
+        ranges
+    }
+
+    /// Index of the parameter that receives an argument: the `position`th
+    /// positional argument, or the keyword argument `keyword`
+    ///
+    /// Unlike `bind`, this looks at one argument on its own, so it works on
+    /// calls that are still being written.
+    pub fn param_for_argument(&self, position: usize, keyword: Option<&str>) -> Option<usize> {
+        let find = |kind: ParamKind| self.params.iter().position(|param| param.kind == kind);
+        match keyword {
+            Some(name) => self
+                .params
+                .iter()
+                .position(|param| {
+                    param.name == name
+                        && matches!(
+                            param.kind,
+                            ParamKind::PositionalOrKeyword | ParamKind::KeywordOnly
+                        )
+                })
+                .or_else(|| find(ParamKind::VarKeyword)),
+            None => (0..self.params.len())
+                .filter(|&i| self.params[i].is_positional())
+                .nth(position)
+                .or_else(|| find(ParamKind::VarPositional)),
+        }
+    }
+
+    /// Each parameter as written, with the index of the parameter, and the
+    /// `/` and bare `*` markers between them (without an index)
+    fn parts(&self) -> Vec<(Option<usize>, String)> {
+        let mut parts = Vec::new();
+        let mut previous = None;
+        for (i, param) in self.params.iter().enumerate() {
+            if previous == Some(ParamKind::PositionalOnly)
+                && param.kind != ParamKind::PositionalOnly
+            {
+                parts.push((None, "/".to_string()));
+            }
+            if param.kind == ParamKind::KeywordOnly
+                && !matches!(
+                    previous,
+                    Some(ParamKind::KeywordOnly | ParamKind::VarPositional)
+                )
+            {
+                parts.push((None, "*".to_string()));
+            }
+            parts.push((Some(i), param.to_string()));
+            previous = Some(param.kind);
+        }
+        if previous == Some(ParamKind::PositionalOnly) {
+            parts.push((None, "/".to_string()));
+        }
+        parts
+    }
+}
@@ -0,0 +1,485 @@
+//! Type stubs of the built-in modules
+//!
+//! `import math` binds a `Type::Module`, so `math.sqrt(x)` is checked
+//! against the stub signature of `sqrt` and `math.sqroot` is reported as an
+//! unknown member. `from math import sqrt` binds `sqrt` with its stub type.
+//! Modules without stubs (and third-party modules) stay unknown and are not
+//! checked.
+
+use crate::signature::{Param, ParamKind, Signature};
+use crate::types::Type;
+
+/// Modules with stubs
+pub const MODULES: &[&str] = &[
+    "json", "math", "os", "os.path", "random", "re", "sys", "time",
+];
+
+/// Members of `math`
+const MATH: &[&str] = &[
+    "acos",
+    "asin",
+    "atan",
+    "atan2",
+    "ceil",
+    "comb",
+    "copysign",
+    "cos",
+    "cosh",
+    "degrees",
+    "e",
+    "exp",
+    "fabs",
+    "factorial",
+    "floor",
+    "fmod",
+    "gcd",
+    "hypot",
+    "inf",
+    "isclose",
+    "isfinite",
+    "isinf",
+    "isnan",
+    "isqrt",
+    "log",
+    "log10",
+    "log2",
+    "nan",
+    "pi",
+    "pow",
+    "radians",
+    "sin",
+    "sinh",
+    "sqrt",
+    "tan",
+    "tanh",
+    "tau",
+    "trunc",
+];
+
+/// Members of `json`
+const JSON: &[&str] = &["JSONDecodeError", "dump", "dumps", "load", "loads"];
+
+/// Members of `os`
+const OS: &[&str] = &[
+    "environ", "getcwd", "getenv", "linesep", "listdir", "makedirs", "mkdir", "name", "path",
+    "remove", "sep",
+];
+
+/// Members of `os.path`
+const OS_PATH: &[&str] = &[
+    "abspath", "basename", "dirname", "exists", "getsize", "isdir", "isfile", "join", "splitext",
+];
+
+/// Members of `random`
+const RANDOM: &[&str] = &[
+    "choice",
+    "randint",
+    "random",
+    "randrange",
+    "sample",
+    "seed",
+    "shuffle",
+    "uniform",
+];
+
+/// Members of `re`
+const RE: &[&str] = &[
+    "A",
+    "ASCII",
+    "DOTALL",
+    "I",
+    "IGNORECASE",
+    "M",
+    "MULTILINE",
+    "S",
+    "VERBOSE",
+    "X",
+    "compile",
+    "error",
+    "escape",
+    "findall",
+    "finditer",
+    "fullmatch",
+    "match",
+    "search",
+    "split",
+    "sub",
+    "subn",
+];
+
+/// Functions of `re` whose first argument is a pattern
+pub const RE_PATTERN_FUNCTIONS: &[&str] = &[
+    "compile",
+    "findall",
+    "finditer",
+    "fullmatch",
+    "match",
+    "search",
+    "split",
+    "sub",
+    "subn",
+];
+
+/// Members of `sys`
+const SYS: &[&str] = &[
+    "argv",
+    "executable",
+    "exit",
+    "maxsize",
+    "path",
+    "platform",
+    "stderr",
+    "stdin",
+    "stdout",
+    "version",
+];
+
+/// Members of `time`
+const TIME: &[&str] = &[
+    "monotonic",
+    "perf_counter",
+    "perf_counter_ns",
+    "sleep",
+    "time",
+    "time_ns",
+];
+
+/// Check if a module has stubs
+pub fn is_module(name: &str) -> bool {
+    member_names(name).is_some()
+}
+
+/// Names a module with stubs defines
+pub fn member_names(module: &str) -> Option<&'static [&'static str]> {
+    match module {
+        "json" => Some(JSON),
+        "math" => Some(MATH),
+        "os" => Some(OS),
+        "os.path" => Some(OS_PATH),
+        "random" => Some(RANDOM),
+        "re" => Some(RE),
+        "sys" => Some(SYS),
+        "time" => Some(TIME),
+        _ => None,
+    }
+}
+
+/// Check if a member of a module with stubs is a pure function, in the
+/// sense of `builtins::PURE_BUILTIN_FUNCTIONS`; functions that do I/O,
+/// read the clock or the environment, or use the random state are not
+pub fn is_pure_member(module: &str, name: &str) -> bool {
+    match module {
+        "math" => true,
+        "json" => matches!(name, "dumps" | "loads"),
+        "os.path" => matches!(name, "basename" | "dirname" | "join" | "splitext"),
+        "re" => matches!(
+            name,
+            "compile"
+                | "escape"
+                | "findall"
+                | "finditer"
+                | "fullmatch"
+                | "match"
+                | "search"
+                | "split"
+                | "sub"
+                | "subn"
+        ),
+        _ => false,
+    }
+}
+
+/// Type of a member of a module with stubs
+pub fn member_type(module: &str, name: &str) -> Option<Type> {
+    match module {
+        "json" => json(name),
+        "math" => math(name),
+        "os" => os(name),
+        "os.path" => os_path(name),
+        "random" => random(name),
+        "re" => re(name),
+        "sys" => sys(name),
+        "time" => time(name),
+        _ => None,
+    }
+}
+
+fn math(name: &str) -> Option<Type> {
+    use ParamKind::*;
+
+    let float = |name| param(name, PositionalOnly, Type::Float, false);
+    let int = |name| param(name, PositionalOnly, Type::Int, false);
+    Some(match name {
+        "e" | "inf" | "nan" | "pi" | "tau" => Type::Float,
+        "acos" | "asin" | "atan" | "cos" | "cosh" | "degrees" | "exp" | "fabs" | "log10"
+        | "log2" | "radians" | "sin" | "sinh" | "sqrt" | "tan" | "tanh" => {
+            function([float("x")], Type::Float)
+        }
+        "ceil" | "floor" | "trunc" => function([float("x")], Type::Int),
+        "isfinite" | "isinf" | "isnan" => function([float("x")], Type::Bool),
+        "atan2" => function([float("y"), float("x")], Type::Float),
+        "copysign" | "fmod" | "pow" => function([float("x"), float("y")], Type::Float),
+        "log" => function(
+            [float("x"), param("base", PositionalOnly, Type::Float, true)],
+            Type::Float,
+        ),
+        "hypot" => function(
+            [param("coordinates", VarPositional, Type::Float, false)],
+            Type::Float,
+        ),
+        "isclose" => function(
+            [
+                float("a"),
+                float("b"),
+                param("rel_tol", KeywordOnly, Type::Float, true),
+                param("abs_tol", KeywordOnly, Type::Float, true),
+            ],
+            Type::Bool,
+        ),
+        "factorial" | "isqrt" => function([int("n")], Type::Int),
+        "comb" => function([int("n"), int("k")], Type::Int),
+        "gcd" => function(
+            [param("integers", VarPositional, Type::Int, false)],
+            Type::Int,
+        ),
+        _ => return None,
+    })
+}
+
+fn json(name: &str) -> Option<Type> {
+    use ParamKind::*;
+
+    // Options shared by `dump` and `dumps`
+    let options = || {
+        [
+            param("indent", KeywordOnly, Type::optional(Type::Int), true),
+            param("sort_keys", KeywordOnly, Type::Bool, true),
+            param("ensure_ascii", KeywordOnly, Type::Bool, true),
+        ]
+    };
+    let obj = param("obj", PositionalOrKeyword, Type::Any, false);
+    let fp = param("fp", PositionalOrKeyword, Type::Any, false);
+    Some(match name {
+        // Classes are known by name only
+        "JSONDecodeError" => Type::Unknown,
+        "dumps" => function([obj].into_iter().chain(options()), Type::Str),
+        "dump" => function([obj, fp].into_iter().chain(options()), Type::None),
+        "loads" => function(
+            [param(
+                "s",
+                PositionalOrKeyword,
+                Type::union([Type::Str, Type::Bytes]),
+                false,
+            )],
+            Type::Any,
+        ),
+        "load" => function([fp], Type::Any),
+        _ => return None,
+    })
+}
+
+fn os(name: &str) -> Option<Type> {
+    use ParamKind::*;
+
+    let path = || param("path", PositionalOrKeyword, Type::Str, false);
+    let str_list = || Type::List(Box::new(Type::Str));
+    Some(match name {
+        // Submodule, also reachable with `import os.path`
+        "path" => Type::Module("os.path".to_string()),
+        "environ" => Type::Dict {
+            key_type: Box::new(Type::Str),
+            value_type: Box::new(Type::Str),
+        },
+        "linesep" | "name" | "sep" => Type::Str,
+        "getcwd" => function([], Type::Str),
+        // `str`, or `None` without a default; the default decides which
+        "getenv" => function(
+            [
+                param("key", PositionalOrKeyword, Type::Str, false),
+                param("default", PositionalOrKeyword, Type::Any, true),
+            ],
+            Type::Any,
+        ),
+        "listdir" => function(
+            [param("path", PositionalOrKeyword, Type::Str, true)],
+            str_list(),
+        ),
+        "remove" => function([path()], Type::None),
+        "mkdir" => function(
+            [path(), param("mode", PositionalOrKeyword, Type::Int, true)],
+            Type::None,
+        ),
+        "makedirs" => function(
+            [
+                param("name", PositionalOrKeyword, Type::Str, false),
+                param("mode", PositionalOrKeyword, Type::Int, true),
+                param("exist_ok", PositionalOrKeyword, Type::Bool, true),
+            ],
+            Type::None,
+        ),
+        _ => return None,
+    })
+}
+
+fn os_path(name: &str) -> Option<Type> {
+    use ParamKind::*;
+
+    let path = || param("path", PositionalOnly, Type::Str, false);
+    Some(match name {
+        "abspath" | "basename" | "dirname" => function([path()], Type::Str),
+        "exists" | "isdir" | "isfile" => function([path()], Type::Bool),
+        "getsize" => function([path()], Type::Int),
+        "splitext" => function([path()], Type::Tuple(vec![Type::Str, Type::Str])),
+        "join" => function(
+            [
+                param("a", PositionalOnly, Type::Str, false),
+                param("paths", VarPositional, Type::Str, false),
+            ],
+            Type::Str,
+        ),
+        _ => return None,
+    })
+}
+
+fn random(name: &str) -> Option<Type> {
+    use ParamKind::*;
+
+    let int = |name| param(name, PositionalOrKeyword, Type::Int, false);
+    let float = |name| param(name, PositionalOrKeyword, Type::Float, false);
+    let any = |name| param(name, PositionalOrKeyword, Type::Any, false);
+    Some(match name {
+        "seed" => function(
+            [param("a", PositionalOrKeyword, Type::Any, true)],
+            Type::None,
+        ),
+        "random" => function([], Type::Float),
+        "uniform" => function([float("a"), float("b")], Type::Float),
+        "randint" => function([int("a"), int("b")], Type::Int),
+        "randrange" => function(
+            [
+                int("start"),
+                param("stop", PositionalOrKeyword, Type::optional(Type::Int), true),
+                param("step", PositionalOrKeyword, Type::Int, true),
+            ],
+            Type::Int,
+        ),
+        "choice" => function([any("seq")], Type::Any),
+        "sample" => function(
+            [any("population"), int("k")],
+            Type::List(Box::new(Type::Any)),
+        ),
+        "shuffle" => function([any("x")], Type::None),
+        _ => return None,
+    })
+}
+
+fn re(name: &str) -> Option<Type> {
+    use ParamKind::*;
+
+    let pattern = || {
+        param(
+            "pattern",
+            PositionalOrKeyword,
+            Type::union([Type::Str, Type::Bytes]),
+            false,
+        )
+    };
+    let string = || param("string", PositionalOrKeyword, Type::Str, false);
+    let int = |name| param(name, PositionalOrKeyword, Type::Int, true);
+    // Pattern and match objects are not typed yet
+    let list = || Type::List(Box::new(Type::Any));
+    Some(match name {
+        "A" | "ASCII" | "DOTALL" | "I" | "IGNORECASE" | "M" | "MULTILINE" | "S" | "VERBOSE"
+        | "X" => Type::Int,
+        "error" => Type::Unknown,
+        "compile" => function([pattern(), int("flags")], Type::Any),
+        "escape" => function([pattern()], Type::Str),
+        "fullmatch" | "match" | "search" | "finditer" => {
+            function([pattern(), string(), int("flags")], Type::Any)
+        }
+        "findall" => function([pattern(), string(), int("flags")], list()),
+        "split" => function([pattern(), string(), int("maxsplit"), int("flags")], list()),
+        "sub" | "subn" => function(
+            [
+                pattern(),
+                param("repl", PositionalOrKeyword, Type::Any, false),
+                string(),
+                int("count"),
+                int("flags"),
+            ],
+            if name == "sub" {
+                Type::Str
+            } else {
+                Type::Tuple(vec![Type::Str, Type::Int])
+            },
+        ),
+        _ => return None,
+    })
+}
+
+/// Check a pattern the way the `re` module compiles it, on the syntax of
+/// the `regex` crate: look-around and backreferences are not supported
+///
+/// The error names the character position of the problem.
+pub fn check_regex(pattern: &str) -> Result<(), String> {
+    let error = match regex_syntax::Parser::new().parse(pattern) {
+        Ok(_) => return Ok(()),
+        Err(error) => error,
+    };
+    let (message, offset) = match &error {
+        regex_syntax::Error::Parse(error) => (error.kind().to_string(), error.span().start.offset),
+        regex_syntax::Error::Translate(error) => {
+            (error.kind().to_string(), error.span().start.offset)
+        }
+        _ => (error.to_string(), 0),
+    };
+    let position = pattern[..offset].chars().count();
+    Err(format!("{} at position {}", message, position))
+}
+
+fn sys(name: &str) -> Option<Type> {
+    let str_list = || Type::List(Box::new(Type::Str));
+    Some(match name {
+        // `argv[0]` is the script; the arguments after `--` follow it
+        "argv" | "path" => str_list(),
+        "executable" | "platform" | "version" => Type::Str,
+        "maxsize" => Type::Int,
+        // File objects are not typed yet
+        "stderr" | "stdin" | "stdout" => Type::Any,
+        "exit" => function(
+            [param("status", ParamKind::PositionalOnly, Type::Any, true)],
+            Type::None,
+        ),
+        _ => return None,
+    })
+}
+
+fn time(name: &str) -> Option<Type> {
+    Some(match name {
+        "monotonic" | "perf_counter" | "time" => function([], Type::Float),
+        "perf_counter_ns" | "time_ns" => function([], Type::Int),
+        "sleep" => function(
+            [param("secs", ParamKind::PositionalOnly, Type::Float, false)],
+            Type::None,
+        ),
+        _ => return None,
+    })
+}
+
+fn param(name: &str, kind: ParamKind, ty: Type, has_default: bool) -> Param {
+    Param {
+        name: name.to_string(),
+        kind,
+        ty,
+        has_default,
+    }
+}
+
+fn function(params: impl IntoIterator<Item = Param>, return_type: Type) -> Type {
+    Type::Function {
+        params: Some(Signature {
+            params: params.into_iter().collect(),
+        }),
+        return_type: Box::new(return_type),
+    }
+}
@@ -4,6 +4,7 @@ use crate::error::{SemanticError, SemanticResult};
 use crate::scope::{Scope, ScopeKind};
 use crate::types::Type;
 use silk_lexer::Span;
+use std::collections::HashMap;
 
 /// Kind of symbol
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -31,6 +32,9 @@ pub struct Symbol {
     pub span: Span,
     /// Type of the symbol
     pub ty: Type,
+    /// Whether calling the function has no side effects, as inferred by
+    /// `Purity`; false for other symbols
+    pub pure: bool,
 }
 
 impl Symbol {
@@ -41,6 +45,7 @@ impl Symbol {
             kind,
             span,
             ty: Type::Unknown,
+            pure: false,
         }
     }
 
@@ -51,11 +56,26 @@ impl Symbol {
             kind,
             span,
             ty,
+            pure: false,
         }
     }
 }
 
+/// Identifies a symbol across the whole program: its name and the index
+/// of the scope that defines it
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SymbolId {
+    /// Index of the defining scope
+    pub scope: usize,
+    /// Name of the symbol
+    pub name: String,
+}
+
 /// Symbol table for managing scopes and symbols
+///
+/// Scopes are kept after they are exited, so once analysis is done the
+/// table can answer position queries for tooling: which symbol a span
+/// refers to, where a symbol is used, and what is visible at an offset.
 #[derive(Debug)]
 pub struct SymbolTable {
     /// All scopes (using indices instead of references)
@@ -64,6 +84,8 @@ pub struct SymbolTable {
     current_scope: usize,
     /// Loop depth counter for break/continue validation
     loop_depth: usize,
+    /// Symbol each definition or reference span resolved to
+    occurrences: HashMap<Span, SymbolId>,
 }
 
 impl SymbolTable {
@@ -74,6 +96,7 @@ impl SymbolTable {
             scopes: vec![global_scope],
             current_scope: 0,
             loop_depth: 0,
+            occurrences: HashMap::new(),
         }
     }
 
@@ -85,6 +108,13 @@ impl SymbolTable {
         self.current_scope = self.scopes.len() - 1;
     }
 
+    /// Enter a new scope covering a range of the source
+    pub fn enter_scope_at(&mut self, kind: ScopeKind, span: Span) {
+        let parent = self.current_scope;
+        self.scopes.push(Scope::with_span(kind, Some(parent), span));
+        self.current_scope = self.scopes.len() - 1;
+    }
+
     /// Exit the current scope
     pub fn exit_scope(&mut self) -> SemanticResult<()> {
         if self.current_scope == 0 {
@@ -103,17 +133,22 @@ impl SymbolTable {
     /// Define a symbol in the current scope
     pub fn define_symbol(&mut self, symbol: Symbol) -> SemanticResult<()> {
         let name = symbol.name.clone();
-        let span = symbol.span.clone();
+        let span = symbol.span;
 
         // Check if symbol already exists in current scope
         if let Some(existing) = self.scopes[self.current_scope].lookup_local(&name) {
             // Allow variable/parameter redefinition (Python allows reassignment)
-            // But don't allow function/class/module redefinition
+            // and module rebinding (conditional imports like
+            // `try: import json` / `except ImportError: json = None`)
+            // But don't allow function/class redefinition; assigning over a
+            // function or class is left to the `reassigned-definition` lint
             match existing.kind {
-                SymbolKind::Variable | SymbolKind::Parameter => {
+                SymbolKind::Variable | SymbolKind::Parameter | SymbolKind::Module => {
                     // Allow redefinition for variables
                 }
-                SymbolKind::Function | SymbolKind::Class | SymbolKind::Module => {
+                SymbolKind::Function | SymbolKind::Class
+                    if matches!(symbol.kind, SymbolKind::Function | SymbolKind::Class) =>
+                {
                     return Err(SemanticError::RedefinedVariable {
                         name,
                         line: span.line,
@@ -122,13 +157,35 @@ impl SymbolTable {
                         span,
                     });
                 }
+                SymbolKind::Function | SymbolKind::Class => {}
             }
         }
 
+        // The first definition seen at a span wins: expressions may be
+        // re-walked in fresh scopes during type inference
+        self.occurrences.entry(span).or_insert(SymbolId {
+            scope: self.current_scope,
+            name: name.clone(),
+        });
         self.scopes[self.current_scope].define(name, symbol);
         Ok(())
     }
 
+    /// Define a symbol in the nearest enclosing scope that is not a comprehension
+    pub fn define_symbol_in_enclosing_scope(&mut self, symbol: Symbol) -> SemanticResult<()> {
+        let saved = self.current_scope;
+        while self.scopes[self.current_scope].kind == ScopeKind::Comprehension {
+            match self.scopes[self.current_scope].parent() {
+                Some(parent) => self.current_scope = parent,
+                None => break,
+            }
+        }
+
+        let result = self.define_symbol(symbol);
+        self.current_scope = saved;
+        result
+    }
+
     /// Resolve a symbol by searching current scope and parent scopes
     pub fn resolve_symbol(&self, name: &str) -> Option<&Symbol> {
         let mut current = self.current_scope;
@@ -147,6 +204,74 @@ impl SymbolTable {
         }
     }
 
+    /// Resolve a name read at `span` and remember what it refers to
+    ///
+    /// Returns whether the name resolved to a symbol.
+    pub fn record_reference(&mut self, name: &str, span: Span) -> bool {
+        let mut current = self.current_scope;
+
+        loop {
+            if self.scopes[current].lookup_local(name).is_some() {
+                self.occurrences.entry(span).or_insert(SymbolId {
+                    scope: current,
+                    name: name.to_string(),
+                });
+                return true;
+            }
+
+            match self.scopes[current].parent() {
+                Some(parent) => current = parent,
+                None => return false,
+            }
+        }
+    }
+
+    /// Look up a symbol in the current scope only
+    pub fn lookup_local(&self, name: &str) -> Option<&Symbol> {
+        self.scopes[self.current_scope].lookup_local(name)
+    }
+
+    /// Resolve a symbol in the scopes enclosing the current one
+    ///
+    /// Class scopes are skipped: names in a class body are not visible
+    /// from its methods.
+    pub fn resolve_in_enclosing_scopes(&self, name: &str) -> Option<&Symbol> {
+        let mut current = self.scopes[self.current_scope].parent()?;
+
+        loop {
+            let scope = &self.scopes[current];
+            if scope.kind != ScopeKind::Class {
+                if let Some(symbol) = scope.lookup_local(name) {
+                    return Some(symbol);
+                }
+            }
+
+            match scope.parent() {
+                Some(parent) => current = parent,
+                None => return None,
+            }
+        }
+    }
+
+    /// Names visible from the current scope, sorted and without duplicates
+    ///
+    /// Class bodies only count when they are the current scope, as in
+    /// `visible_symbols`.
+    pub fn visible_names(&self) -> Vec<&str> {
+        let mut names = Vec::new();
+        let mut current = Some(self.current_scope);
+        while let Some(index) = current {
+            let scope = &self.scopes[index];
+            if index == self.current_scope || scope.kind != ScopeKind::Class {
+                names.extend(scope.symbols().keys().map(String::as_str));
+            }
+            current = scope.parent();
+        }
+        names.sort_unstable();
+        names.dedup();
+        names
+    }
+
     /// Resolve a symbol mutably by searching current scope and parent scopes
     pub fn resolve_symbol_mut(&mut self, name: &str) -> Option<&mut Symbol> {
         let mut current = self.current_scope;
@@ -205,6 +330,81 @@ impl SymbolTable {
     }
 }
 
+/// Queries for tooling, meant to be used once analysis is done
+impl SymbolTable {
+    /// All scopes, indexed by the `scope` of a `SymbolId`; the global
+    /// scope is first
+    pub fn scopes(&self) -> &[Scope] {
+        &self.scopes
+    }
+
+    /// Symbol with the given id
+    pub fn symbol(&self, id: &SymbolId) -> Option<&Symbol> {
+        self.scopes.get(id.scope)?.lookup_local(&id.name)
+    }
+
+    /// Id of the symbol defined or referenced at exactly `span`
+    pub fn symbol_id_at(&self, span: Span) -> Option<&SymbolId> {
+        self.occurrences.get(&span)
+    }
+
+    /// Symbol defined or referenced at exactly `span`
+    pub fn symbol_at(&self, span: Span) -> Option<&Symbol> {
+        self.symbol(self.symbol_id_at(span)?)
+    }
+
+    /// Spans of every definition of and reference to a symbol, in source
+    /// order
+    pub fn references(&self, id: &SymbolId) -> Vec<Span> {
+        let mut spans: Vec<Span> = self
+            .occurrences
+            .iter()
+            .filter(|(_, occurrence)| *occurrence == id)
+            .map(|(span, _)| *span)
+            .collect();
+        spans.sort_by_key(|span| (span.start, span.end));
+        spans
+    }
+
+    /// Index of the innermost scope covering a character offset (as in `Span`)
+    pub fn scope_at(&self, offset: usize) -> usize {
+        self.scopes
+            .iter()
+            .enumerate()
+            .filter(|(_, scope)| scope.contains(offset))
+            .filter_map(|(index, scope)| Some((index, scope.span()?)))
+            .min_by_key(|(index, span)| (span.end - span.start, *index))
+            .map_or(0, |(index, _)| index)
+    }
+
+    /// Symbols visible at a character offset, sorted by name
+    ///
+    /// Inner definitions hide outer ones with the same name. Class bodies
+    /// are only visible from the class body itself, not from its methods.
+    /// Every symbol of a scope counts, including ones defined after the
+    /// offset.
+    pub fn visible_symbols(&self, offset: usize) -> Vec<&Symbol> {
+        let mut visible: HashMap<&str, &Symbol> = HashMap::new();
+        let mut current = Some(self.scope_at(offset));
+        let mut innermost = true;
+
+        while let Some(index) = current {
+            let scope = &self.scopes[index];
+            if innermost || scope.kind != ScopeKind::Class {
+                for (name, symbol) in scope.symbols() {
+                    visible.entry(name.as_str()).or_insert(symbol);
+                }
+            }
+            innermost = false;
+            current = scope.parent();
+        }
+
+        let mut symbols: Vec<&Symbol> = visible.into_values().collect();
+        symbols.sort_by(|a, b| a.name.cmp(&b.name));
+        symbols
+    }
+}
+
 impl Default for SymbolTable {
     fn default() -> Self {
         Self::new()
@@ -0,0 +1,109 @@
+//! Programs annotated with the types found by semantic analysis
+//!
+//! `SemanticAnalyzer::analyze_typed` records the type of every expression
+//! it checks, keyed by the expression's span, so later stages (code
+//! generation, editor hover, optimization passes) can look types up instead
+//! of inferring them again. Types are recorded where the expression
+//! appears, so a variable read inside `if x is not None:` has its narrowed
+//! type. Names bound by assignment are recorded with the type they are
+//! bound to.
+
+use crate::{SemanticError, Type};
+use silk_ast::{Expression, NodeIndex, Program};
+use silk_lexer::Span;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Expression types collected during analysis
+pub(crate) type ExpressionTypes = HashMap<Span, Type>;
+
+/// A program together with the resolved type of each expression
+///
+/// The program is shared rather than owned: analysis only reads it, and
+/// the parsed program it comes from is usually kept around as well.
+#[derive(Debug, Clone)]
+pub struct TypedProgram {
+    program: Arc<Program>,
+    types: ExpressionTypes,
+    errors: Vec<SemanticError>,
+}
+
+impl TypedProgram {
+    pub(crate) fn new(
+        program: Arc<Program>,
+        types: ExpressionTypes,
+        errors: Vec<SemanticError>,
+    ) -> Self {
+        Self {
+            program,
+            types,
+            errors,
+        }
+    }
+
+    /// The analyzed program
+    pub fn program(&self) -> &Program {
+        &self.program
+    }
+
+    /// The analyzed program, shared with whoever else holds it
+    pub fn shared_program(&self) -> &Arc<Program> {
+        &self.program
+    }
+
+    /// Position index over the analyzed program, for finding the
+    /// statement or expression at an offset
+    pub fn node_index(&self) -> NodeIndex<'_> {
+        NodeIndex::new(&self.program)
+    }
+
+    /// Errors and warnings found by the analysis
+    pub fn errors(&self) -> &[SemanticError] {
+        &self.errors
+    }
+
+    /// Whether the analysis found any errors (warnings do not count)
+    pub fn has_errors(&self) -> bool {
+        self.errors.iter().any(|err| !err.is_warning())
+    }
+
+    /// Type of an expression of the program
+    pub fn type_of(&self, expr: &Expression) -> Option<&Type> {
+        self.type_at(expr.span)
+    }
+
+    /// Type of the expression with exactly this span
+    pub fn type_at(&self, span: Span) -> Option<&Type> {
+        self.types.get(&span)
+    }
+
+    /// Innermost expression containing a character offset (as in `Span`),
+    /// with its type
+    ///
+    /// On `obj.name.upper()`, an offset inside `name` gives `obj.name`.
+    pub fn type_at_offset(&self, offset: usize) -> Option<(Span, &Type)> {
+        self.types
+            .iter()
+            .filter(|(span, _)| span.start <= offset && offset < span.end)
+            .min_by_key(|(span, _)| (span.end - span.start, span.start))
+            .map(|(span, ty)| (*span, ty))
+    }
+
+    /// Every typed expression, in source order; an expression comes before
+    /// the expressions nested in it
+    pub fn expression_types(&self) -> Vec<(Span, &Type)> {
+        let mut types: Vec<_> = self.types.iter().map(|(span, ty)| (*span, ty)).collect();
+        types.sort_by_key(|(span, _)| (span.start, std::cmp::Reverse(span.end)));
+        types
+    }
+
+    /// Number of typed expressions
+    pub fn len(&self) -> usize {
+        self.types.len()
+    }
+
+    /// Whether no expression was typed
+    pub fn is_empty(&self) -> bool {
+        self.types.is_empty()
+    }
+}
@@ -1,5 +1,6 @@
 //! Type system for Silk semantic analysis
 
+use crate::signature::Signature;
 use std::fmt;
 
 /// Represents a type in the Silk type system
@@ -11,6 +12,8 @@ pub enum Type {
     Float,
     /// String type (str)
     Str,
+    /// Byte string type (bytes)
+    Bytes,
     /// Boolean type (bool)
     Bool,
     /// None type
@@ -21,8 +24,8 @@ pub enum Type {
     Unknown,
     /// Function type with parameter and return types
     Function {
-        /// Parameter types (name, type) - None means no parameters stored yet
-        params: Option<Vec<(String, Type)>>,
+        /// Parameters and their types - None means no parameters stored yet
+        params: Option<Signature>,
         /// Return type of the function
         return_type: Box<Type>,
     },
@@ -39,9 +42,60 @@ pub enum Type {
     Set(Box<Type>),
     /// Tuple type with element types (heterogeneous)
     Tuple(Vec<Type>),
+    /// Instance of a user-defined class, by its qualified name
+    Instance(String),
+    /// One of several types (`int | None`); build with `Type::union`
+    Union(Vec<Type>),
+    /// A built-in module whose members are known (`import math`)
+    Module(String),
 }
 
 impl Type {
+    /// Union of `types`, flattening nested unions and removing duplicates
+    ///
+    /// A single remaining type is returned as is, and `Any`/`Unknown`
+    /// absorb the other members.
+    pub fn union(types: impl IntoIterator<Item = Type>) -> Type {
+        let mut members: Vec<Type> = Vec::new();
+        for ty in types {
+            match ty {
+                Type::Union(inner) => {
+                    for member in inner {
+                        if !members.contains(&member) {
+                            members.push(member);
+                        }
+                    }
+                }
+                Type::Any | Type::Unknown => return ty,
+                ty if !members.contains(&ty) => members.push(ty),
+                _ => {}
+            }
+        }
+        match members.len() {
+            0 => Type::Unknown,
+            1 => members.remove(0),
+            _ => Type::Union(members),
+        }
+    }
+
+    /// `T | None`
+    pub fn optional(ty: Type) -> Type {
+        Type::union([ty, Type::None])
+    }
+
+    /// Members of a union, or the type itself
+    pub fn members(&self) -> &[Type] {
+        match self {
+            Type::Union(members) => members,
+            ty => std::slice::from_ref(ty),
+        }
+    }
+
+    /// Check if a value of this type may be None
+    pub fn includes_none(&self) -> bool {
+        self.members().contains(&Type::None)
+    }
+
     /// Check if this type is compatible with another type for assignment
     ///
     /// This is used for type checking in assignments, function calls, etc.
@@ -62,6 +116,17 @@ impl Type {
             return true;
         }
 
+        // A union value fits if every member fits; a union target accepts
+        // any of its members
+        if let Type::Union(members) = self {
+            return members
+                .iter()
+                .all(|member| member.is_compatible_with(other));
+        }
+        if let Type::Union(members) = other {
+            return members.iter().any(|member| self.is_compatible_with(member));
+        }
+
         // Special case: int can be assigned to float (widening conversion)
         if matches!(self, Type::Int) && matches!(other, Type::Float) {
             return true;
@@ -126,6 +191,7 @@ impl Type {
             Type::Int => "int",
             Type::Float => "float",
             Type::Str => "str",
+            Type::Bytes => "bytes",
             Type::Bool => "bool",
             Type::None => "None",
             Type::Any => "Any",
@@ -135,15 +201,20 @@ impl Type {
             Type::Dict { .. } => "dict",
             Type::Set(_) => "set",
             Type::Tuple(_) => "tuple",
+            Type::Instance(_) => "object",
+            Type::Union(_) => "union",
+            Type::Module(_) => "module",
         }
     }
 
     /// Parse a type from a string (for type annotations)
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Option<Type> {
         match s {
             "int" => Some(Type::Int),
             "float" => Some(Type::Float),
             "str" => Some(Type::Str),
+            "bytes" => Some(Type::Bytes),
             "bool" => Some(Type::Bool),
             "None" => Some(Type::None),
             "Any" => Some(Type::Any),
@@ -163,7 +234,7 @@ impl Type {
     pub fn is_builtin(&self) -> bool {
         matches!(
             self,
-            Type::Int | Type::Float | Type::Str | Type::Bool | Type::None | Type::Any
+            Type::Int | Type::Float | Type::Str | Type::Bytes | Type::Bool | Type::None | Type::Any
         )
     }
 
@@ -176,24 +247,44 @@ impl Type {
 
     /// Check if this type can be used in comparison operations (< > <= >=)
     ///
-    /// Returns true for Int, Float, Str, and Unknown
+    /// Returns true for Int, Float, Str, Bytes, and Unknown
     pub fn is_comparable(&self) -> bool {
-        matches!(self, Type::Int | Type::Float | Type::Str | Type::Unknown)
+        matches!(
+            self,
+            Type::Int | Type::Float | Type::Str | Type::Bytes | Type::Unknown
+        )
     }
 
     /// Check if this type can be indexed/subscripted
     ///
-    /// Returns true for List, Dict, Tuple, Str, and Unknown
+    /// Returns true for List, Dict, Tuple, Str, Bytes, and Unknown
     pub fn is_indexable(&self) -> bool {
         matches!(
             self,
-            Type::List(_) | Type::Dict { .. } | Type::Tuple(_) | Type::Str | Type::Unknown
+            Type::List(_)
+                | Type::Dict { .. }
+                | Type::Tuple(_)
+                | Type::Str
+                | Type::Bytes
+                | Type::Unknown
         )
     }
 
+    /// Check if a value of this type may be called
+    ///
+    /// Returns true for functions, class instances (which may define
+    /// `__call__`), Any and Unknown, and for a union with any such member
+    pub fn is_callable(&self) -> bool {
+        match self {
+            Type::Function { .. } | Type::Instance(_) | Type::Any | Type::Unknown => true,
+            Type::Union(members) => members.iter().any(Type::is_callable),
+            _ => false,
+        }
+    }
+
     /// Check if this type can be iterated over (for loops)
     ///
-    /// Returns true for List, Dict, Set, Tuple, Str, and Unknown
+    /// Returns true for List, Dict, Set, Tuple, Str, Bytes, and Unknown
     pub fn is_iterable(&self) -> bool {
         matches!(
             self,
@@ -202,18 +293,19 @@ impl Type {
                 | Type::Set(_)
                 | Type::Tuple(_)
                 | Type::Str
+                | Type::Bytes
                 | Type::Unknown
         )
     }
 
     /// Get the index type for this container type
     ///
-    /// For lists/tuples/strings: returns int
+    /// For lists/tuples/strings/bytes: returns int
     /// For dicts: returns the key type
     /// For others: returns Unknown
     pub fn expected_index_type(&self) -> Type {
         match self {
-            Type::List(_) | Type::Tuple(_) | Type::Str => Type::Int,
+            Type::List(_) | Type::Tuple(_) | Type::Str | Type::Bytes => Type::Int,
             Type::Dict { key_type, .. } => (**key_type).clone(),
             _ => Type::Unknown,
         }
@@ -225,16 +317,41 @@ impl Type {
     /// For dicts: returns value type
     /// For tuples: returns Unknown (varies by index)
     /// For strings: returns str
+    /// For bytes: returns int (a slice of bytes is bytes)
     pub fn index_result_type(&self) -> Type {
         match self {
             Type::List(elem_type) => (**elem_type).clone(),
             Type::Dict { value_type, .. } => (**value_type).clone(),
             Type::Tuple(_) => Type::Unknown, // Could be any element type
             Type::Str => Type::Str,
+            Type::Bytes => Type::Int,
             _ => Type::Unknown,
         }
     }
 
+    /// Return type of calling the built-in method `name` on a value of
+    /// this type, or None if the analyzer does not know the method
+    ///
+    /// Only methods of `bytes` are known so far.
+    pub fn method_return_type(&self, name: &str) -> Option<Type> {
+        match (self, name) {
+            (Type::Bytes, "decode" | "hex") => Some(Type::Str),
+            (
+                Type::Bytes,
+                "lower" | "upper" | "strip" | "lstrip" | "rstrip" | "replace" | "join"
+                | "removeprefix" | "removesuffix",
+            ) => Some(Type::Bytes),
+            (
+                Type::Bytes,
+                "startswith" | "endswith" | "isalnum" | "isalpha" | "isascii" | "isdigit"
+                | "isspace",
+            ) => Some(Type::Bool),
+            (Type::Bytes, "count" | "find" | "index" | "rfind" | "rindex") => Some(Type::Int),
+            (Type::Bytes, "split" | "splitlines") => Some(Type::List(Box::new(Type::Bytes))),
+            _ => None,
+        }
+    }
+
     /// Check if two types can be used together in a binary operation
     ///
     /// This is a stricter check than is_compatible_with, used for operations
@@ -299,6 +416,17 @@ impl fmt::Display for Type {
                     )
                 }
             }
+            Type::Instance(class_name) => write!(f, "{}", crate::classes::short_name(class_name)),
+            Type::Module(name) => write!(f, "module '{}'", name),
+            Type::Union(members) => write!(
+                f,
+                "{}",
+                members
+                    .iter()
+                    .map(|t| t.to_string())
+                    .collect::<Vec<_>>()
+                    .join(" | ")
+            ),
             _ => write!(f, "{}", self.as_str()),
         }
     }
@@ -347,6 +475,7 @@ mod tests {
         assert_eq!(Type::Int.as_str(), "int");
         assert_eq!(Type::Float.as_str(), "float");
         assert_eq!(Type::Str.as_str(), "str");
+        assert_eq!(Type::Bytes.as_str(), "bytes");
         assert_eq!(Type::Bool.as_str(), "bool");
         assert_eq!(Type::None.as_str(), "None");
         assert_eq!(Type::Any.as_str(), "Any");
@@ -358,6 +487,7 @@ mod tests {
         assert_eq!(Type::from_str("int"), Some(Type::Int));
         assert_eq!(Type::from_str("float"), Some(Type::Float));
         assert_eq!(Type::from_str("str"), Some(Type::Str));
+        assert_eq!(Type::from_str("bytes"), Some(Type::Bytes));
         assert_eq!(Type::from_str("bool"), Some(Type::Bool));
         assert_eq!(Type::from_str("None"), Some(Type::None));
         assert_eq!(Type::from_str("Any"), Some(Type::Any));
@@ -369,16 +499,52 @@ mod tests {
         assert!(Type::Int.is_builtin());
         assert!(Type::Float.is_builtin());
         assert!(Type::Str.is_builtin());
+        assert!(Type::Bytes.is_builtin());
         assert!(Type::Bool.is_builtin());
         assert!(Type::None.is_builtin());
         assert!(Type::Any.is_builtin());
         assert!(!Type::Unknown.is_builtin());
     }
 
+    #[test]
+    fn test_type_is_callable() {
+        assert!(Type::Unknown.is_callable());
+        assert!(Type::Instance("Handler".to_string()).is_callable());
+        assert!(Type::union(vec![Type::None, Type::Any]).is_callable());
+        assert!(!Type::Str.is_callable());
+        assert!(!Type::List(Box::new(Type::Int)).is_callable());
+    }
+
     #[test]
     fn test_type_display() {
         assert_eq!(format!("{}", Type::Int), "int");
         assert_eq!(format!("{}", Type::Float), "float");
         assert_eq!(format!("{}", Type::Str), "str");
+        assert_eq!(format!("{}", Type::optional(Type::Int)), "int | None");
+    }
+
+    #[test]
+    fn test_union_flattens_and_deduplicates() {
+        let inner = Type::union([Type::Int, Type::Str]);
+        assert_eq!(
+            Type::union([inner, Type::Int, Type::None]),
+            Type::Union(vec![Type::Int, Type::Str, Type::None])
+        );
+        assert_eq!(Type::union([Type::Int, Type::Int]), Type::Int);
+        assert_eq!(Type::union([Type::Int, Type::Unknown]), Type::Unknown);
+    }
+
+    #[test]
+    fn test_union_compatibility() {
+        let optional_int = Type::optional(Type::Int);
+        // Each member fits the union
+        assert!(Type::Int.is_compatible_with(&optional_int));
+        assert!(Type::None.is_compatible_with(&optional_int));
+        assert!(!Type::Str.is_compatible_with(&optional_int));
+        // A union fits only if every member does
+        assert!(!optional_int.is_compatible_with(&Type::Int));
+        assert!(optional_int.is_compatible_with(&Type::optional(Type::Float)));
+        assert!(optional_int.includes_none());
+        assert!(!Type::Int.includes_none());
     }
 }
@@ -47,7 +47,7 @@ y: int = get_list()["bad"]
 
     assert!(result.is_err(), "Expected subscript error");
     let errors = result.err().unwrap();
-    assert!(errors.len() >= 1);
+    assert!(!errors.is_empty());
     assert!(
         errors
             .iter()
@@ -105,7 +105,7 @@ z: int = get_int() + get_str()
 
     assert!(result.is_err());
     let errors = result.err().unwrap();
-    assert!(errors.len() >= 1);
+    assert!(!errors.is_empty());
     assert!(
         errors
             .iter()
@@ -207,7 +207,7 @@ y: int = data[0]
 
     assert!(result.is_err());
     let errors = result.err().unwrap();
-    assert!(errors.len() >= 1);
+    assert!(!errors.is_empty());
     assert!(
         errors
             .iter()
@@ -237,7 +237,7 @@ z: str = process("bad")
     // We expect at least 2 errors: return type mismatch and argument type mismatch
     // Note: The return error may not be caught if function body analysis is not complete
     assert!(
-        errors.len() >= 1,
+        !errors.is_empty(),
         "Expected at least 1 error, got: {:?}",
         errors
     );
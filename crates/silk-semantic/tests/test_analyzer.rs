@@ -271,15 +271,8 @@ except Exception as e:
     print(e)
     "#;
     let result = analyze(source);
-    // Will have error for 'Exception' and 'print'
-    assert!(result.is_err());
-    let errors = result.unwrap_err();
-    // Check that 'e' is not in the errors
-    for error in &errors {
-        if let SemanticError::UndefinedVariable { name, .. } = error {
-            assert_ne!(name, "e", "e should be defined by except handler");
-        }
-    }
+    // 'Exception' and 'print' are builtins and 'e' is defined by the handler
+    assert!(result.is_ok(), "Unexpected errors: {:?}", result);
 }
 
 #[test]
@@ -404,3 +404,45 @@ y: int = x + "text"
         .iter()
         .any(|e| e.to_string().contains("Invalid operation")));
 }
+
+// ========== MATRIX MULTIPLICATION ==========
+
+#[test]
+fn test_matmult_on_builtin_types_is_invalid() {
+    let source = r#"
+x: int = 2
+y = x @ 3
+"#;
+    let program = Parser::parse(source).expect("Failed to parse");
+
+    let mut analyzer = SemanticAnalyzer::new_without_control_flow();
+    let errors = analyzer.analyze(&program).unwrap_err();
+
+    assert!(errors
+        .iter()
+        .any(|e| e.to_string().contains("Invalid operation") && e.to_string().contains("@")));
+}
+
+#[test]
+fn test_matmult_on_instances_is_allowed() {
+    let source = r#"
+class Matrix:
+    def __matmul__(self, other):
+        return self
+
+a = Matrix()
+b = Matrix()
+c = a @ b
+a @= b
+"#;
+    let program = Parser::parse(source).expect("Failed to parse");
+
+    let mut analyzer = SemanticAnalyzer::new_without_control_flow();
+    let result = analyzer.analyze(&program);
+
+    assert!(
+        result.is_ok(),
+        "Expected no errors, got: {:?}",
+        result.err()
+    );
+}
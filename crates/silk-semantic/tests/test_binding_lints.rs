@@ -0,0 +1,303 @@
+//! Tests for unused parameter, shadowing and definition reassignment lints
+
+use silk_parser::Parser;
+use silk_semantic::{SemanticAnalyzer, SemanticError};
+
+fn analyze(source: &str) -> Vec<SemanticError> {
+    let program = Parser::parse(source).expect("Parser should succeed");
+    let mut analyzer = SemanticAnalyzer::new();
+    analyzer.analyze(&program).err().unwrap_or_default()
+}
+
+/// (function, parameter) pairs reported as unused
+fn unused_parameters(source: &str) -> Vec<(String, String)> {
+    analyze(source)
+        .into_iter()
+        .filter_map(|err| match err {
+            SemanticError::UnusedParameter {
+                name,
+                function_name,
+                ..
+            } => Some((function_name, name)),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Messages of shadowing warnings
+fn shadowed(source: &str) -> Vec<String> {
+    analyze(source)
+        .into_iter()
+        .filter(|err| matches!(err, SemanticError::ShadowedName { .. }))
+        .map(|err| err.to_string())
+        .collect()
+}
+
+/// Messages of reassigned definition warnings
+fn reassigned(source: &str) -> Vec<String> {
+    analyze(source)
+        .into_iter()
+        .filter(|err| matches!(err, SemanticError::ReassignedDefinition { .. }))
+        .map(|err| err.to_string())
+        .collect()
+}
+
+fn pair(function: &str, param: &str) -> (String, String) {
+    (function.to_string(), param.to_string())
+}
+
+// ========== UNUSED PARAMETERS ==========
+
+#[test]
+fn test_unused_parameter_span_and_code() {
+    let errors = analyze("def f(a, b):\n    return a\n\nprint(f(1, 2))\n");
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].code(), "unused-parameter");
+    assert!(errors[0].is_warning());
+    let span = errors[0].span().unwrap();
+    assert_eq!((span.line, span.column), (1, 10));
+}
+
+#[test]
+fn test_every_parameter_kind_is_checked() {
+    let source = "def f(a, /, b, *args, c, **kwargs):\n    return 0\n\nprint(f)\n";
+    assert_eq!(
+        unused_parameters(source),
+        [
+            pair("f", "a"),
+            pair("f", "b"),
+            pair("f", "args"),
+            pair("f", "c"),
+            pair("f", "kwargs")
+        ]
+    );
+}
+
+#[test]
+fn test_read_of_same_name_in_other_function_is_not_a_use() {
+    let source = r#"
+def f(x):
+    return 1
+
+def g(x):
+    return x
+
+print(f(1), g(2))
+"#;
+    assert_eq!(unused_parameters(source), [pair("f", "x")]);
+}
+
+#[test]
+fn test_parameter_read_by_nested_function() {
+    let source = r#"
+def outer(x):
+    def inner():
+        return x
+    return inner
+
+print(outer(1))
+"#;
+    assert!(unused_parameters(source).is_empty());
+}
+
+#[test]
+fn test_parameter_read_in_fstring() {
+    let source = "def greet(name):\n    return f\"Hello {name}!\"\n\nprint(greet(\"a\"))\n";
+    assert!(unused_parameters(source).is_empty());
+    // A field nested in the format spec reads its names too
+    let source =
+        "def pad(text, width):\n    return f\"{text!r:>{width}}\"\n\nprint(pad(\"a\", 3))\n";
+    assert!(unused_parameters(source).is_empty());
+}
+
+#[test]
+fn test_underscore_and_receiver_parameters_are_ignored() {
+    let source = r#"
+class Greeter:
+    def greet(self, _name):
+        return 1
+
+    @classmethod
+    def make(cls, _size):
+        return 2
+
+print(Greeter().greet(1), Greeter.make(2))
+"#;
+    assert!(unused_parameters(source).is_empty());
+}
+
+#[test]
+fn test_stubs_dunders_and_abstract_methods_are_ignored() {
+    let source = r#"
+class Shape:
+    def area(self, scale):
+        raise NotImplementedError
+
+    def describe(self, verbose):
+        """Describe the shape"""
+        ...
+
+    def __exit__(self, exc_type, exc, tb):
+        return None
+
+    @abstractmethod
+    def draw(self, canvas):
+        return None
+
+def todo(x):
+    pass
+
+print(Shape(), todo(1))
+"#;
+    assert!(unused_parameters(source).is_empty());
+}
+
+// ========== SHADOWING ==========
+
+#[test]
+fn test_shadowed_builtin() {
+    let errors = analyze("list = [1]\nprint(list)\n");
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].code(), "shadowed-name");
+    assert!(errors[0].is_warning());
+    assert_eq!(
+        errors[0].to_string(),
+        "Variable 'list' at line 1, column 1 shadows the builtin 'list'"
+    );
+}
+
+#[test]
+fn test_shadowed_outer_name() {
+    let source = r#"
+total = 0
+
+def add(x):
+    total = x + 1
+    return total
+
+print(add(1), total)
+"#;
+    assert_eq!(
+        shadowed(source),
+        ["Variable 'total' at line 5, column 5 shadows the outer 'total' from line 2"]
+    );
+}
+
+#[test]
+fn test_loop_with_and_walrus_targets_can_shadow() {
+    let source = r#"
+def f(items):
+    for id in items:
+        print(id)
+    with open("x") as input:
+        print(input)
+    if (max := 3) > 2:
+        print(max)
+    return 0
+
+print(f([]))
+"#;
+    assert_eq!(shadowed(source).len(), 3);
+}
+
+#[test]
+fn test_global_and_nonlocal_do_not_shadow() {
+    let source = r#"
+count = 0
+
+def bump():
+    global count
+    count = count + 1
+    return count
+
+def outer():
+    value = 1
+    def inner():
+        nonlocal value
+        value = 2
+        return value
+    return inner
+
+print(bump(), outer())
+"#;
+    assert!(shadowed(source).is_empty());
+}
+
+#[test]
+fn test_rebinding_and_class_attributes_do_not_shadow() {
+    let source = r#"
+def f():
+    x = 1
+    x = 2
+    return x
+
+class Config:
+    type = "default"
+
+print(f(), Config())
+"#;
+    assert!(shadowed(source).is_empty());
+}
+
+#[test]
+fn test_parameters_do_not_shadow() {
+    let source = "x = 1\n\ndef f(x, list):\n    return [x, list]\n\nprint(f(1, 2), x)\n";
+    assert!(shadowed(source).is_empty());
+}
+
+// ========== REASSIGNED DEFINITIONS ==========
+
+#[test]
+fn test_reassigned_function() {
+    let errors = analyze("def helper():\n    return 1\n\nhelper = 5\nprint(helper)\n");
+    let warnings: Vec<_> = errors
+        .iter()
+        .filter(|err| err.code() == "reassigned-definition")
+        .collect();
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].is_warning());
+    assert_eq!(
+        warnings[0].to_string(),
+        "Assignment to 'helper' at line 4, column 1 replaces the function defined at line 1"
+    );
+    assert_eq!(warnings[0].span().unwrap().line, 4);
+}
+
+#[test]
+fn test_reassigned_class() {
+    let source = "class Point:\n    pass\n\nPoint = None\nprint(Point)\n";
+    assert_eq!(
+        reassigned(source),
+        ["Assignment to 'Point' at line 4, column 1 replaces the class defined at line 1"]
+    );
+}
+
+#[test]
+fn test_wrapping_a_definition_is_not_a_reassignment() {
+    let source = r#"
+def wrap(f):
+    return f
+
+def greet():
+    return 1
+
+greet = wrap(greet)
+print(greet())
+"#;
+    assert!(reassigned(source).is_empty());
+}
+
+#[test]
+fn test_assignment_before_definition_is_not_a_reassignment() {
+    let source = "handler = None\n\ndef handler():\n    return 1\n\nprint(handler)\n";
+    assert!(reassigned(source).is_empty());
+}
+
+#[test]
+fn test_redefining_a_function_is_still_an_error() {
+    let source = "def f():\n    return 1\n\ndef f():\n    return 2\n";
+    let errors = analyze(source);
+    assert!(errors
+        .iter()
+        .any(|err| matches!(err, SemanticError::RedefinedVariable { .. })));
+}
@@ -0,0 +1,80 @@
+//! Tests for the bytes type: literals, indexing, concatenation and methods
+
+use silk_parser::Parser;
+use silk_semantic::{SemanticAnalyzer, SemanticError, Type};
+
+fn analyze(source: &str) -> (SemanticAnalyzer, Vec<SemanticError>) {
+    let program = Parser::parse(source).expect("Parser should succeed");
+    let mut analyzer = SemanticAnalyzer::new_without_control_flow();
+    let errors = analyzer.analyze(&program).err().unwrap_or_default();
+    (analyzer, errors)
+}
+
+/// Type of the variable `name` after analyzing `source` without errors
+fn type_of(source: &str, name: &str) -> Type {
+    let (analyzer, errors) = analyze(source);
+    assert!(errors.is_empty(), "unexpected errors: {:?}", errors);
+    analyzer
+        .symbol_table()
+        .resolve_symbol(name)
+        .unwrap()
+        .ty
+        .clone()
+}
+
+#[test]
+fn test_byte_string_literals_are_bytes() {
+    assert_eq!(type_of("data = b'abc'", "data"), Type::Bytes);
+    assert_eq!(type_of("data = rb'\\d'", "data"), Type::Bytes);
+    assert_eq!(type_of("data = bytes(3)", "data"), Type::Bytes);
+    assert_eq!(type_of("data: bytes = b''", "data"), Type::Bytes);
+    assert_eq!(Type::Bytes.to_string(), "bytes");
+}
+
+#[test]
+fn test_indexing_gives_int_and_slicing_gives_bytes() {
+    let source = "data = b'abc'\nfirst = data[0]\nrest = data[1:]\n";
+    assert_eq!(type_of(source, "first"), Type::Int);
+    assert_eq!(type_of(source, "rest"), Type::Bytes);
+    let source = "values = [byte for byte in b'abc']";
+    assert_eq!(type_of(source, "values"), Type::List(Box::new(Type::Int)));
+}
+
+#[test]
+fn test_bytes_index_must_be_int() {
+    let (_, errors) = analyze("data = b'abc'\nfirst = data['a']\n");
+    assert!(matches!(
+        errors.as_slice(),
+        [SemanticError::InvalidSubscript { collection_type, index_type, .. }]
+            if collection_type == "bytes" && index_type == "str"
+    ));
+}
+
+#[test]
+fn test_bytes_concatenate_only_with_bytes() {
+    assert_eq!(type_of("data = b'ab' + b'cd'", "data"), Type::Bytes);
+    let (_, errors) = analyze("data = b'ab' + 'cd'");
+    assert!(matches!(
+        errors.as_slice(),
+        [SemanticError::InvalidBinaryOperation { operator, left_type, right_type, .. }]
+            if operator == "+" && left_type == "bytes" && right_type == "str"
+    ));
+}
+
+#[test]
+fn test_bytes_methods() {
+    let source = r#"data = b"caf\xc3\xa9"
+text = data.decode("utf-8")
+digits = data.hex()
+loud = data.upper()
+parts = data.split(b",")
+found = data.startswith(b"c")
+"#;
+    assert_eq!(type_of(source, "text"), Type::Str);
+    assert_eq!(type_of(source, "digits"), Type::Str);
+    assert_eq!(type_of(source, "loud"), Type::Bytes);
+    assert_eq!(type_of(source, "parts"), Type::List(Box::new(Type::Bytes)));
+    assert_eq!(type_of(source, "found"), Type::Bool);
+    // Methods the analyzer does not know stay unknown
+    assert_eq!(type_of("data = b'a'.fromhex('61')", "data"), Type::Unknown);
+}
@@ -0,0 +1,277 @@
+//! Tests for the call graph and infinite recursion detection
+
+use silk_parser::Parser;
+use silk_semantic::{CallGraph, SemanticAnalyzer, SemanticError};
+
+fn graph(source: &str) -> CallGraph {
+    let program = Parser::parse(source).expect("Parser should succeed");
+    CallGraph::build(&program)
+}
+
+/// (caller, callee) pairs in source order
+fn edges(source: &str) -> Vec<(String, String)> {
+    graph(source)
+        .edges()
+        .iter()
+        .map(|edge| (edge.caller.clone(), edge.callee.clone()))
+        .collect()
+}
+
+fn edge(caller: &str, callee: &str) -> (String, String) {
+    (caller.to_string(), callee.to_string())
+}
+
+/// Functions reported as recursing on every path
+fn infinite_recursion(source: &str) -> Vec<String> {
+    let program = Parser::parse(source).expect("Parser should succeed");
+    let mut analyzer = SemanticAnalyzer::new();
+    analyzer
+        .analyze(&program)
+        .err()
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|err| match err {
+            SemanticError::InfiniteRecursion { function_name, .. } => Some(function_name),
+            _ => None,
+        })
+        .collect()
+}
+
+// ========== CALL GRAPH ==========
+
+#[test]
+fn test_function_calls() {
+    let source = r#"
+def helper():
+    return 1
+
+def main():
+    helper()
+    print(helper())
+
+main()
+"#;
+    assert_eq!(
+        edges(source),
+        vec![
+            edge("main", "helper"),
+            edge("main", "helper"),
+            edge("<module>", "main")
+        ]
+    );
+}
+
+#[test]
+fn test_nested_functions_are_qualified() {
+    let source = r#"
+def outer():
+    def inner():
+        return 1
+    return inner()
+"#;
+    let graph = graph(source);
+    let names: Vec<&str> = graph.functions().iter().map(|f| f.name.as_str()).collect();
+    assert_eq!(names, vec!["outer", "outer.inner"]);
+    assert_eq!(
+        graph
+            .callees("outer")
+            .map(|e| e.callee.as_str())
+            .collect::<Vec<_>>(),
+        vec!["outer.inner"]
+    );
+}
+
+#[test]
+fn test_method_calls_through_receiver() {
+    let source = r#"
+class Point:
+    def __init__(self, x: int):
+        self.x = x
+
+    def moved(self, dx: int):
+        return Point.create(self.shift(dx))
+
+    def shift(self, dx: int) -> int:
+        return self.x + dx
+
+    @staticmethod
+    def create(x: int):
+        return Point(x)
+"#;
+    assert_eq!(
+        edges(source),
+        vec![
+            edge("Point.moved", "Point.create"),
+            edge("Point.moved", "Point.shift"),
+            edge("Point.create", "Point.__init__"),
+        ]
+    );
+}
+
+#[test]
+fn test_methods_do_not_see_class_scope() {
+    // `helper()` inside a method is the module-level function, not the method
+    let source = r#"
+def helper():
+    return 1
+
+class A:
+    def helper(self):
+        return helper()
+"#;
+    assert_eq!(edges(source), vec![edge("A.helper", "helper")]);
+}
+
+#[test]
+fn test_parameters_shadow_functions() {
+    let source = r#"
+def f():
+    return 1
+
+def apply(f):
+    return f()
+"#;
+    assert!(edges(source).is_empty());
+}
+
+#[test]
+fn test_is_recursive() {
+    let source = r#"
+def even(n: int) -> bool:
+    if n == 0:
+        return True
+    return odd(n - 1)
+
+def odd(n: int) -> bool:
+    if n == 0:
+        return False
+    return even(n - 1)
+
+def leaf():
+    return even(2)
+"#;
+    let graph = graph(source);
+    assert!(graph.is_recursive("even"));
+    assert!(graph.is_recursive("odd"));
+    assert!(!graph.is_recursive("leaf"));
+    assert_eq!(graph.callers("even").count(), 2);
+}
+
+#[test]
+fn test_edge_spans_point_at_calls() {
+    let graph = graph("def f():\n    pass\n\nx = 1\ny = f()\n");
+    let span = graph.edges()[0].span;
+    assert_eq!((span.line, span.column), (5, 5));
+    assert_eq!(graph.function("f").unwrap().span.line, 1);
+}
+
+// ========== INFINITE RECURSION ==========
+
+#[test]
+fn test_unconditional_recursion_reported() {
+    let source = r#"
+def countdown(n: int) -> int:
+    print(n)
+    return countdown(n - 1)
+
+countdown(3)
+"#;
+    assert_eq!(infinite_recursion(source), vec!["countdown"]);
+}
+
+#[test]
+fn test_base_case_not_reported() {
+    let source = r#"
+def fact(n: int) -> int:
+    if n <= 1:
+        return 1
+    return n * fact(n - 1)
+
+fact(3)
+"#;
+    assert!(infinite_recursion(source).is_empty());
+}
+
+#[test]
+fn test_recursion_in_every_branch_reported() {
+    let source = r#"
+def walk(n: int) -> int:
+    if n > 0:
+        walk(n - 1)
+    else:
+        walk(n + 1)
+
+walk(3)
+"#;
+    assert_eq!(infinite_recursion(source), vec!["walk"]);
+}
+
+#[test]
+fn test_conditional_calls_not_reported() {
+    let source = r#"
+def a(n: int) -> int:
+    return n > 0 and a(n - 1)
+
+def b(n: int) -> int:
+    return b(n - 1) if n else 0
+
+def c(items: list[int]) -> int:
+    for item in items:
+        c([item])
+    return 0
+
+def d(n: int) -> int:
+    try:
+        return d(n - 1)
+    except RecursionError:
+        return 0
+
+a(1)
+b(1)
+c([])
+d(1)
+"#;
+    assert!(infinite_recursion(source).is_empty());
+}
+
+#[test]
+fn test_recursive_method_and_property() {
+    let source = r#"
+class Temperature:
+    @property
+    def celsius(self) -> float:
+        return self.celsius
+
+    @celsius.setter
+    def celsius(self, value: float):
+        self.celsius = value
+
+    def fahrenheit(self) -> float:
+        return self.celsius * 9 / 5 + 32
+
+    def run(self):
+        self.run()
+
+t = Temperature()
+t.fahrenheit()
+t.run()
+"#;
+    assert_eq!(
+        infinite_recursion(source),
+        vec![
+            "Temperature.celsius",
+            "Temperature.celsius.setter",
+            "Temperature.run"
+        ]
+    );
+}
+
+#[test]
+fn test_infinite_recursion_is_warning() {
+    let program = Parser::parse("def f():\n    f()\n\nf()\n").unwrap();
+    let mut analyzer = SemanticAnalyzer::new();
+    let errors = analyzer.analyze(&program).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].code(), "infinite-recursion");
+    assert!(errors[0].is_warning());
+}
@@ -228,7 +228,7 @@ result = factorial(5)
 }
 
 #[test]
-fn test_method_call_returns_unknown() {
+fn test_method_call_returns_declared_type() {
     let source = r#"
 class MyClass:
     def method(self) -> int:
@@ -242,9 +242,9 @@ result = obj.method()
     let mut analyzer = SemanticAnalyzer::new_without_control_flow();
     analyzer.analyze(&program).unwrap();
 
-    // Method calls not yet supported - returns Unknown
+    // Method calls resolve through the class to the declared return type
     let symbol = analyzer.symbol_table().resolve_symbol("result").unwrap();
-    assert_eq!(symbol.ty, Type::Unknown);
+    assert_eq!(symbol.ty, Type::Int);
 }
 
 #[test]
@@ -114,7 +114,7 @@ y: int = x["a"]
     assert!(result.is_err(), "Expected InvalidSubscript error");
     let errors = result.err().unwrap();
     // Might have multiple errors if assignment type checking also fails
-    assert!(errors.len() >= 1, "Expected at least one error");
+    assert!(!errors.is_empty(), "Expected at least one error");
     assert!(
         errors.iter().any(
             |e| matches!(e, SemanticError::InvalidSubscript { collection_type, index_type, .. }
@@ -164,7 +164,7 @@ y: int = x[0]
         result
     );
     let errors = result.err().unwrap();
-    assert!(errors.len() >= 1, "Expected at least one error");
+    assert!(!errors.is_empty(), "Expected at least one error");
     assert!(
         errors.iter().any(
             |e| matches!(e, SemanticError::InvalidSubscript { collection_type, index_type, .. }
@@ -215,7 +215,7 @@ y: int = x[0]
         result
     );
     let errors = result.err().unwrap();
-    assert!(errors.len() >= 1, "Expected at least one error");
+    assert!(!errors.is_empty(), "Expected at least one error");
     assert!(
         errors.iter().any(
             |e| matches!(e, SemanticError::InvalidSubscript { collection_type, .. }
@@ -282,7 +282,7 @@ y: int = x["a"][0]
 
     assert!(result.is_err(), "Expected InvalidSubscript error");
     let errors = result.err().unwrap();
-    assert!(errors.len() >= 1);
+    assert!(!errors.is_empty());
     assert!(
         errors
             .iter()
@@ -0,0 +1,319 @@
+//! Tests for constant condition, self-comparison, incompatible comparison
+//! and duplicate branch lints
+
+use silk_parser::Parser;
+use silk_semantic::{ControlFlowAnalyzer, SemanticAnalyzer, SemanticError};
+
+/// Messages of the condition lints, in report order
+fn lints(source: &str) -> Vec<String> {
+    let program = Parser::parse(source).expect("Parser should succeed");
+    let mut analyzer = SemanticAnalyzer::new();
+    analyzer
+        .analyze(&program)
+        .err()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|err| {
+            matches!(
+                err,
+                SemanticError::ConstantCondition { .. }
+                    | SemanticError::SelfComparison { .. }
+                    | SemanticError::IncompatibleComparison { .. }
+                    | SemanticError::DuplicateBranch { .. }
+            )
+        })
+        .map(|err| err.to_string())
+        .collect()
+}
+
+// ========== CONSTANT CONDITIONS ==========
+
+#[test]
+fn test_constant_if_conditions() {
+    let source = r#"
+if 0:
+    print(1)
+if not True:
+    print(2)
+if []:
+    print(3)
+if "a" == "a":
+    print(4)
+if 2 > 1 and None:
+    print(5)
+"#;
+    assert_eq!(
+        lints(source),
+        [
+            "Condition at line 2, column 4 is always false",
+            "Condition at line 4, column 4 is always false",
+            "Condition at line 6, column 4 is always false",
+            "Condition at line 8, column 4 is always true",
+            "Condition at line 10, column 4 is always false",
+        ]
+    );
+}
+
+#[test]
+fn test_while_true_is_not_reported() {
+    let source = r#"
+while True:
+    break
+while 1:
+    break
+while False:
+    print(1)
+"#;
+    assert_eq!(
+        lints(source),
+        ["Condition at line 6, column 7 is always false"]
+    );
+}
+
+#[test]
+fn test_conditions_folded_from_strings_and_lengths() {
+    let source = r#"
+x = 1
+if "ab" * 2 == "abab":
+    print(1)
+if len([x, x]) > 2 or "c" in ("a", "b"):
+    print(2)
+while len("ab") - 2:
+    print(3)
+while 1 == 1:
+    break
+"#;
+    assert_eq!(
+        lints(source),
+        [
+            "Condition at line 3, column 4 is always true",
+            "Condition at line 5, column 4 is always false",
+            "Condition at line 7, column 7 is always false",
+        ]
+    );
+}
+
+#[test]
+fn test_assert_that_always_passes() {
+    let source = r#"
+x = 1
+assert (x > 0, "x must be positive")
+assert ()
+assert 1 + 1 == 2
+assert False
+assert x > 0
+"#;
+    assert_eq!(
+        lints(source),
+        ["Condition at line 3, column 8 is always true"]
+    );
+}
+
+#[test]
+fn test_constant_conditional_expression() {
+    assert_eq!(
+        lints("x = 1 if -1 else 2\nprint(x)\n"),
+        ["Condition at line 1, column 10 is always true"]
+    );
+}
+
+#[test]
+fn test_variable_conditions_are_not_constant() {
+    let source = r#"
+x = 1
+if x:
+    print(x)
+if x > 0 and True:
+    print(x)
+"#;
+    assert!(lints(source).is_empty());
+}
+
+#[test]
+fn test_constant_condition_is_a_warning() {
+    let program = Parser::parse("if False:\n    print(1)\n").unwrap();
+    let errors = ControlFlowAnalyzer::new().analyze(&program).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].code(), "constant-condition");
+    assert!(errors[0].is_warning());
+}
+
+// ========== SELF-COMPARISON ==========
+
+#[test]
+fn test_self_comparison() {
+    let source = r#"
+x = 1
+if x == x:
+    print(x)
+print(x != x, x < x)
+"#;
+    assert_eq!(
+        lints(source),
+        [
+            "Comparison of 'x' with itself at line 3, column 4 is always true",
+            "Comparison of 'x' with itself at line 5, column 7 is always false",
+            "Comparison of 'x' with itself at line 5, column 15 is always false",
+        ]
+    );
+}
+
+#[test]
+fn test_self_comparison_of_attributes() {
+    let source = r#"
+class P:
+    @property
+    def x(self) -> int:
+        return 1
+
+p = P()
+print(p.x >= p.x)
+"#;
+    assert_eq!(
+        lints(source),
+        ["Comparison of 'p.x' with itself at line 8, column 7 is always true"]
+    );
+}
+
+#[test]
+fn test_different_operands_and_calls_are_not_self_comparisons() {
+    let source = r#"
+def f():
+    return 1
+
+x = 1
+y = 2
+print(x == y, f() == f(), x in x)
+"#;
+    assert!(lints(source).is_empty());
+}
+
+#[test]
+fn test_self_comparison_needs_a_known_type() {
+    // `x != x` is the NaN check for floats; untyped values may be floats
+    let source = r#"
+ratio = 0.5
+flag = True
+name = "a"
+
+def check(value):
+    return value == value
+
+class P:
+    pass
+
+p = P()
+print(ratio != ratio, ratio == ratio, p.x == p.x, flag == flag, name != name)
+"#;
+    assert_eq!(
+        lints(source),
+        [
+            "Comparison of 'flag' with itself at line 13, column 51 is always true",
+            "Comparison of 'name' with itself at line 13, column 65 is always false",
+        ]
+    );
+}
+
+// ========== INCOMPATIBLE COMPARISONS ==========
+
+#[test]
+fn test_incompatible_literal_comparison() {
+    let errors = lints("print(\"a\" == 1, None != 0, [1] == (1,))\n");
+    assert_eq!(
+        errors,
+        [
+            "Comparison between 'str' and 'int' literals at line 1, column 7 is always false",
+            "Comparison between 'None' and 'int' literals at line 1, column 17 is always true",
+            "Comparison between 'list' and 'tuple' literals at line 1, column 28 is always false",
+        ]
+    );
+}
+
+#[test]
+fn test_numeric_literals_are_compatible() {
+    assert!(lints("print(1 == 1.0, True == 1, -2 != 2.5, \"a\" < \"b\")\n").is_empty());
+}
+
+// ========== DUPLICATE BRANCHES ==========
+
+#[test]
+fn test_duplicate_elif_condition() {
+    let source = r#"
+def classify(x):
+    if x == 1:
+        return "one"
+    elif x == 2:
+        return "two"
+    elif x == 1:
+        return "uno"
+    return "many"
+
+print(classify(1))
+"#;
+    assert_eq!(
+        lints(source),
+        ["Branch at line 7, column 5 has the same condition as the branch at line 3"]
+    );
+}
+
+#[test]
+fn test_duplicate_branch_bodies() {
+    let source = r#"
+def sign(x):
+    if x > 0:
+        result = 1
+        return result
+    elif x < 0:
+        result = -1
+        return result
+    else:
+        result = 1
+        return result
+
+print(sign(1))
+"#;
+    assert_eq!(
+        lints(source),
+        ["Branch at line 10, column 9 has the same body as the branch at line 3"]
+    );
+}
+
+#[test]
+fn test_each_duplicate_is_reported_once() {
+    let source = r#"
+x = 1
+if x == 1:
+    print(x)
+elif x == 2:
+    print(x)
+elif x == 3:
+    print(x)
+"#;
+    assert_eq!(
+        lints(source),
+        [
+            "Branch at line 5, column 1 has the same body as the branch at line 3",
+            "Branch at line 7, column 1 has the same body as the branch at line 3",
+        ]
+    );
+}
+
+#[test]
+fn test_trivial_and_distinct_bodies_are_not_duplicates() {
+    let source = r#"
+def f(x):
+    for i in x:
+        if i == 1:
+            continue
+        elif i == 2:
+            print(i)
+        elif i == 3:
+            continue
+        else:
+            print(i + 1)
+    return 0
+
+print(f([]))
+"#;
+    assert!(lints(source).is_empty());
+}
@@ -42,7 +42,7 @@ def foo(cond):
     let result = analyze_control_flow(source);
     assert!(result.is_err(), "x is not initialized in else branch, should error");
     let errors = result.unwrap_err();
-    assert!(errors.len() >= 1, "Should have error for uninitialized x");
+    assert!(!errors.is_empty(), "Should have error for uninitialized x");
 }
 
 #[test]
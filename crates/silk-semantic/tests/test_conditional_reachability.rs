@@ -2,8 +2,8 @@ use silk_parser::Parser;
 use silk_semantic::{ControlFlowAnalyzer, SemanticError};
 
 /// Helper to parse source and run control flow analysis
-/// Filters out UnusedFunction and UnusedVariable errors since we're testing
-/// reachability analysis, not whether test functions are called.
+/// Filters out UnusedFunction, UnusedVariable and DuplicateBranch errors since
+/// we're testing reachability analysis, not whether test functions are called.
 fn analyze_control_flow(source: &str) -> Result<(), Vec<SemanticError>> {
     let program = Parser::parse(source).expect("Parser failed");
     
@@ -18,7 +18,8 @@ fn analyze_control_flow(source: &str) -> Result<(), Vec<SemanticError>> {
                 .into_iter()
                 .filter(|e| !matches!(e,
                     SemanticError::UnusedFunction { .. } |
-                    SemanticError::UnusedVariable { .. }
+                    SemanticError::UnusedVariable { .. } |
+                    SemanticError::DuplicateBranch { .. }
                 ))
                 .collect();
             
@@ -133,7 +134,7 @@ def nested(x, y):
     
     let errors = result.unwrap_err();
     // Should detect unreachable in the nested if block
-    assert!(errors.len() >= 1, "Should have at least 1 error");
+    assert!(!errors.is_empty(), "Should have at least 1 error");
     assert!(is_unreachable_code_error(&errors[0]), "Should be UnreachableCode error");
 }
 
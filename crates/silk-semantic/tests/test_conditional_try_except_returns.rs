@@ -2,7 +2,6 @@
 /// 
 /// These tests verify that control flow analysis correctly determines whether
 /// functions return on all paths when try/except blocks are inside conditionals.
-
 use silk_parser::Parser;
 use silk_semantic::{ControlFlowAnalyzer, SemanticError};
 
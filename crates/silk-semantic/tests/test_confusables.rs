@@ -0,0 +1,105 @@
+//! Tests for the confusable identifier lint
+
+use silk_parser::Parser;
+use silk_semantic::{SemanticAnalyzer, SemanticError};
+
+/// (name, reason) pairs reported as confusable
+fn confusable(source: &str) -> Vec<(String, String)> {
+    let program = Parser::parse(source).expect("Parser should succeed");
+    let mut analyzer = SemanticAnalyzer::new();
+    analyzer
+        .analyze(&program)
+        .err()
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|err| match err {
+            SemanticError::ConfusableIdentifier { name, reason, .. } => Some((name, reason)),
+            _ => None,
+        })
+        .collect()
+}
+
+fn pair(name: &str, reason: &str) -> (String, String) {
+    (name.to_string(), reason.to_string())
+}
+
+#[test]
+fn test_mixed_scripts() {
+    // Cyrillic 'а' in a Latin word
+    assert_eq!(
+        confusable("p\u{430}ypal = 1\n"),
+        vec![pair(
+            "p\u{430}ypal",
+            "it mixes the Latin and Cyrillic scripts"
+        )]
+    );
+}
+
+#[test]
+fn test_single_script_names_are_fine() {
+    assert!(confusable("caf\u{e9} = 1\nмосква = 2\nαβγ = 3\n变量 = 4\n").is_empty());
+    // Japanese mixes Han and Kana, which counts as one writing system
+    assert!(confusable("日本語のテキスト = 1\n").is_empty());
+}
+
+#[test]
+fn test_lookalike_of_another_name() {
+    // All-Cyrillic 'рау' next to Latin 'pay', in either order
+    let source = "pay = 1\n\u{440}\u{430}\u{443} = 2\n";
+    assert_eq!(
+        confusable(source),
+        vec![pair(
+            "\u{440}\u{430}\u{443}",
+            "it looks like 'pay' defined at line 1"
+        )]
+    );
+    let source = "\u{440}\u{430}\u{443} = 1\ndef f():\n    pay = 2\n    return pay\n";
+    assert_eq!(
+        confusable(source),
+        vec![pair("pay", "it looks like 'рау' defined at line 1")]
+    );
+}
+
+#[test]
+fn test_lookalike_of_a_builtin() {
+    // All-Cyrillic 'һех'
+    assert_eq!(
+        confusable("\u{4bb}\u{435}\u{445} = 1\n"),
+        vec![pair(
+            "\u{4bb}\u{435}\u{445}",
+            "it looks like the builtin 'hex'"
+        )]
+    );
+}
+
+#[test]
+fn test_invisible_joiner() {
+    assert_eq!(
+        confusable("a\u{200d}b = 1\nab = 2\n"),
+        vec![pair(
+            "a\u{200d}b",
+            "it contains the invisible character U+200D"
+        )]
+    );
+}
+
+#[test]
+fn test_similar_ascii_names_are_fine() {
+    assert!(confusable("l = 1\nI = 2\nrn = 3\nm = 4\n").is_empty());
+}
+
+#[test]
+fn test_compatibility_forms_are_the_same_name() {
+    // The ligature is normalized to 'file', so this is one variable that
+    // is defined and then used
+    let source = "\u{fb01}le = 1\nprint(file)\n";
+    assert!(confusable(source).is_empty());
+    let program = Parser::parse(source).unwrap();
+    assert!(SemanticAnalyzer::new().analyze(&program).is_ok());
+}
+
+#[test]
+fn test_reported_once_per_name() {
+    let source = "p\u{430}y = 1\ndef f():\n    p\u{430}y = 2\n    return p\u{430}y\n";
+    assert_eq!(confusable(source).len(), 1);
+}
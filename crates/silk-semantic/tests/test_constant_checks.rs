@@ -0,0 +1,110 @@
+//! Tests for division by zero, shift range and integer overflow checks on
+//! constant operands
+
+use silk_parser::Parser;
+use silk_semantic::{SemanticAnalyzer, SemanticError};
+
+fn analyze(source: &str) -> Vec<SemanticError> {
+    let program = Parser::parse(source).expect("Parser should succeed");
+    let mut analyzer = SemanticAnalyzer::new_without_control_flow();
+    analyzer.analyze(&program).err().unwrap_or_default()
+}
+
+fn messages(source: &str) -> Vec<String> {
+    analyze(source).iter().map(|err| err.to_string()).collect()
+}
+
+// ========== DIVISION BY ZERO ==========
+
+#[test]
+fn test_division_by_literal_zero() {
+    let errors = analyze("x = 10\ny = x / 0\n");
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].code(), "division-by-zero");
+    assert!(!errors[0].is_warning());
+    assert_eq!(
+        errors[0].to_string(),
+        "Division by zero at line 2, column 5: the right operand of '/' is always zero"
+    );
+}
+
+#[test]
+fn test_modulo_floor_division_and_float_zero() {
+    let source = "x = 10\nprint(x % 0, x // 0.0, x / (2 - 2))\n";
+    let operators: Vec<_> = analyze(source)
+        .into_iter()
+        .map(|err| match err {
+            SemanticError::DivisionByZero { operator, .. } => operator,
+            other => panic!("Expected DivisionByZero, got {:?}", other),
+        })
+        .collect();
+    assert_eq!(operators, ["%", "//", "/"]);
+}
+
+#[test]
+fn test_nonzero_divisors_are_accepted() {
+    assert!(analyze("x = 10\nprint(x / 2, x % (3 - 1), 1 // -1, x / 0.5)\n").is_empty());
+}
+
+// ========== SHIFTS ==========
+
+#[test]
+fn test_out_of_range_shifts() {
+    let source = "x = 1\nprint(x << 64, x >> -1)\n";
+    assert_eq!(
+        messages(source),
+        [
+            "Shift count 64 at line 2, column 7 is out of range: it must be between 0 and 63",
+            "Shift count -1 at line 2, column 16 is out of range: it must be between 0 and 63",
+        ]
+    );
+}
+
+#[test]
+fn test_in_range_shifts_are_accepted() {
+    assert!(analyze("x = 1\nprint(x << 63, x >> 0, 1 << 62)\n").is_empty());
+}
+
+// ========== OVERFLOW ==========
+
+#[test]
+fn test_constant_overflow() {
+    let source = "big = 9223372036854775807 + 1\nprint(big, 2 ** 64, 3 << 62)\n";
+    let errors = analyze(source);
+    assert_eq!(errors.len(), 3);
+    assert!(errors.iter().all(|err| err.code() == "integer-overflow"));
+    assert_eq!(
+        errors[0].to_string(),
+        "Integer overflow at line 1, column 7: the result of '+' does not fit in a 64-bit int"
+    );
+}
+
+#[test]
+fn test_overflow_is_reported_at_the_innermost_operation() {
+    let errors = analyze("x = (9223372036854775807 * 2) + 1\n");
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        &errors[0],
+        SemanticError::IntegerOverflow { operator, .. } if operator == "*"
+    ));
+}
+
+#[test]
+fn test_negating_the_minimum_int_overflows() {
+    let errors = analyze("x = -(-9223372036854775807 - 1)\n");
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].code(), "integer-overflow");
+}
+
+#[test]
+fn test_values_that_fit_are_accepted() {
+    let source =
+        "print(9223372036854775806 + 1, 2 ** 62, -7 // 2, -7 % 3, 2 ** -1, 1.5e308 * 10)\n";
+    assert!(analyze(source).is_empty());
+}
+
+#[test]
+fn test_operands_that_are_not_constant_are_not_folded() {
+    let source = "x = 9223372036854775807\nprint(x + 1, x * x)\n";
+    assert!(analyze(source).is_empty());
+}
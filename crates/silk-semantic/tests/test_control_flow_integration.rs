@@ -2,7 +2,6 @@
 /// 
 /// These tests verify that control flow analysis is properly integrated
 /// with the semantic analyzer and that errors from both systems are properly merged.
-
 use silk_parser::Parser;
 use silk_semantic::{SemanticAnalyzer, SemanticError};
 
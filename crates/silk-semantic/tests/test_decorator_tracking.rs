@@ -16,8 +16,9 @@ fn analyze_ignoring_warnings(source: &str) -> Result<(), Vec<SemanticError>> {
             let serious_errors: Vec<_> = errors.into_iter()
                 .filter(|e| {
                     match e {
-                        // Filter out all unused variable warnings
+                        // Filter out all unused variable and parameter warnings
                         SemanticError::UnusedVariable { .. } => false,
+                        SemanticError::UnusedParameter { .. } => false,
                         // Filter out unused function warnings for:
                         // - "decorator" and "inner_decorator" (nested functions in decorator factories)
                         // - "method" (class methods not tracked through attribute access - known limitation)
@@ -264,3 +265,52 @@ result = calculate()
     assert!(result.is_ok(), "Decorator with expression argument should work: {:?}", result.err());
 }
 
+
+#[test]
+fn test_any_callable_expression_as_decorator() {
+    // PEP 614: the decorator is checked by its type, not its form
+    let source = r#"
+def register(func):
+    return func
+
+def trace(func):
+    return func
+
+handlers = {"register": register}
+debug = True
+
+@handlers["register"] if debug else trace
+def handle():
+    return 1
+
+result = handle()
+"#;
+    let result = analyze_ignoring_warnings(source);
+    assert!(result.is_ok(), "Callable decorator expression should pass: {:?}", result.err());
+}
+
+#[test]
+fn test_non_callable_decorator() {
+    let source = r#"
+names = ["first", "second"]
+
+@names[0]
+def handle():
+    return 1
+
+result = handle()
+"#;
+    match analyze_ignoring_warnings(source) {
+        Err(errors) => {
+            assert_eq!(errors.len(), 1, "{:?}", errors);
+            match &errors[0] {
+                SemanticError::TypeMismatch { message, line, .. } => {
+                    assert_eq!(message, "a decorator must be callable, not 'str'");
+                    assert_eq!(*line, 4);
+                }
+                other => panic!("Expected a type mismatch, got {:?}", other),
+            }
+        }
+        Ok(()) => panic!("A string decorator should be reported"),
+    }
+}
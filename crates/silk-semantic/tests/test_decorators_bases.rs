@@ -86,7 +86,8 @@ def my_function():
 fn test_function_decorator_attribute() {
     let source = r#"
 class Module:
-    pass
+    def decorator(self, func):
+        return func
 
 module = Module()
 
@@ -95,8 +96,6 @@ def my_function():
     pass
     "#;
     let result = analyze(source);
-    // Note: This will pass because we validate 'module' exists, not that it has a 'decorator' attribute
-    // Attribute checking is beyond current scope
     assert!(
         result.is_ok(),
         "Decorator with attribute should validate object: {:?}",
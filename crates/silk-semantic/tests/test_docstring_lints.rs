@@ -0,0 +1,247 @@
+//! Tests for the docstring lints: missing docstrings, and parameter and
+//! return value consistency
+
+use silk_parser::Parser;
+use silk_semantic::{ControlFlowAnalyzer, SemanticError};
+
+/// Messages of the docstring lints, with `missing-docstring` enabled
+fn lints(source: &str) -> Vec<String> {
+    let program = Parser::parse(source).expect("Parser should succeed");
+    let mut analyzer = ControlFlowAnalyzer::new();
+    analyzer.enable_lint("missing-docstring");
+    analyzer
+        .analyze(&program)
+        .err()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|err| {
+            matches!(
+                err,
+                SemanticError::MissingDocstring { .. }
+                    | SemanticError::UndocumentedParameter { .. }
+                    | SemanticError::UnknownDocumentedParameter { .. }
+                    | SemanticError::UndocumentedReturn { .. }
+            )
+        })
+        .map(|err| err.to_string())
+        .collect()
+}
+
+// ========== MISSING DOCSTRINGS ==========
+
+#[test]
+fn test_missing_docstring_is_allowed_by_default() {
+    let program = Parser::parse("def area(r):\n    return r\n\nprint(area(1))\n").unwrap();
+    assert!(ControlFlowAnalyzer::new().analyze(&program).is_ok());
+
+    let mut analyzer = ControlFlowAnalyzer::new();
+    analyzer.enable_lint("missing-docstring");
+    let errors = analyzer.analyze(&program).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].code(), "missing-docstring");
+    assert!(errors[0].is_warning());
+    assert!(errors[0].is_allowed_by_default());
+    assert_eq!(
+        errors[0].to_string(),
+        "Public function 'area' at line 1, column 1 has no docstring"
+    );
+}
+
+#[test]
+fn test_public_functions_and_methods_need_docstrings() {
+    let source = r#"
+def public():
+    return 1
+
+def _private():
+    return 2
+
+class Shape:
+    def area(self):
+        return 0
+
+    def __repr__(self):
+        return "Shape"
+
+    @property
+    def size(self):
+        """Size of the shape"""
+        return 1
+
+    @size.setter
+    def size(self, value):
+        print(value)
+
+class _Hidden:
+    def method(self):
+        return 3
+
+def outer():
+    """Outer"""
+    def inner():
+        return 4
+    return inner
+
+print(public(), _private(), Shape(), _Hidden(), outer())
+"#;
+    assert_eq!(
+        lints(source),
+        [
+            "Public function 'public' at line 2, column 1 has no docstring",
+            "Public function 'area' at line 9, column 5 has no docstring",
+        ]
+    );
+}
+
+// ========== PARAMETERS ==========
+
+#[test]
+fn test_google_style_parameters() {
+    let source = r#"
+def scale(value, factor, offset=0):
+    """Scale a value.
+
+    Args:
+        value (float): The value to scale.
+        factor: How much to scale by.
+            Must be positive.
+        amount: Not a parameter.
+    """
+    print(value * factor + offset)
+
+scale(1, 2)
+"#;
+    assert_eq!(
+        lints(source),
+        [
+            "Parameter 'offset' of function 'scale' at line 2, column 26 is not documented",
+            "Docstring of function 'scale' at line 3, column 5 documents 'amount', which is not a parameter",
+        ]
+    );
+}
+
+#[test]
+fn test_numpy_style_parameters() {
+    let source = r#"
+def move(x, y, *args, **kwargs):
+    """Move a point.
+
+    Parameters
+    ----------
+    x, y : float
+        Coordinates.
+    *args
+        Extra values.
+    """
+    print(x, y, args, kwargs)
+
+move(1, 2)
+"#;
+    assert_eq!(
+        lints(source),
+        ["Parameter 'kwargs' of function 'move' at line 2, column 23 is not documented"]
+    );
+}
+
+#[test]
+fn test_receiver_and_private_parameters_need_no_docs() {
+    let source = r#"
+class Account:
+    """A bank account"""
+
+    def deposit(self, amount, _audit=None):
+        """Add money.
+
+        Args:
+            amount: How much to add.
+        """
+        print(amount, _audit)
+
+    @classmethod
+    def open(cls, owner):
+        """Open an account.
+
+        Args:
+            owner: Who owns it.
+
+        Returns:
+            The new account.
+        """
+        return cls()
+
+print(Account().deposit(1), Account.open("me"))
+"#;
+    assert!(lints(source).is_empty());
+}
+
+#[test]
+fn test_plain_docstrings_are_not_checked() {
+    let source = r#"
+def add(a, b):
+    """Add two numbers"""
+    return a + b
+
+print(add(1, 2))
+"#;
+    assert!(lints(source).is_empty());
+}
+
+// ========== RETURN VALUES ==========
+
+#[test]
+fn test_undocumented_return_value() {
+    let source = r#"
+def double(n):
+    """Double a number.
+
+    Args:
+        n: The number.
+    """
+    if n > 0:
+        return n * 2
+    return 0
+
+print(double(1))
+"#;
+    assert_eq!(
+        lints(source),
+        ["Docstring of function 'double' at line 3, column 5 does not document the return value"]
+    );
+}
+
+#[test]
+fn test_return_annotation_and_yields() {
+    let source = r#"
+def name(n) -> str:
+    """Name a number.
+
+    Args:
+        n: The number.
+    """
+    print(n)
+    raise ValueError()
+
+def nothing(n) -> None:
+    """Print a number.
+
+    Args:
+        n: The number.
+    """
+    print(n)
+    return None
+
+def stub(n) -> int:
+    """Not written yet.
+
+    Args:
+        n: The number.
+    """
+    ...
+
+print(name(1), nothing(2), stub(3))
+"#;
+    assert_eq!(
+        lints(source),
+        ["Docstring of function 'name' at line 3, column 5 does not document the return value"]
+    );
+}
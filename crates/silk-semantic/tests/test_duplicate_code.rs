@@ -0,0 +1,105 @@
+//! Tests for the `duplicate-code` lint
+
+use silk_parser::Parser;
+use silk_semantic::{ControlFlowAnalyzer, SemanticError};
+
+/// Messages of `duplicate-code`, reporting copies of at least `min_nodes`
+fn duplicates(source: &str, min_nodes: usize) -> Vec<String> {
+    let program = Parser::parse(source).expect("Parser should succeed");
+    let mut analyzer = ControlFlowAnalyzer::new();
+    analyzer.enable_lint("duplicate-code");
+    analyzer.set_duplicate_min_nodes(min_nodes);
+    analyzer
+        .analyze(&program)
+        .err()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|err| matches!(err, SemanticError::DuplicateCode { .. }))
+        .map(|err| err.to_string())
+        .collect()
+}
+
+const FUNCTIONS: &str = "\
+def room(width, depth):
+    area = width * depth
+    print('area:', area)
+    return area
+
+def garden(w, d):
+    area = width * depth
+    print('area:',   area)  # same code
+    return area
+
+print(room(1, 2), garden(3, 4))
+";
+
+#[test]
+fn test_duplicate_code_is_allowed_by_default() {
+    let program = Parser::parse(FUNCTIONS).unwrap();
+    let errors = ControlFlowAnalyzer::new()
+        .analyze(&program)
+        .err()
+        .unwrap_or_default();
+    assert!(!errors
+        .iter()
+        .any(|err| matches!(err, SemanticError::DuplicateCode { .. })));
+}
+
+#[test]
+fn test_duplicated_function_bodies() {
+    let errors = duplicates(FUNCTIONS, 10);
+    assert_eq!(
+        errors,
+        ["Duplicated function body at line 6, column 1: 12 nodes repeat the code at line 1, column 1"]
+    );
+
+    // Smaller than the threshold
+    assert!(duplicates(FUNCTIONS, 13).is_empty());
+    // Bodies that differ are not copies
+    let changed = FUNCTIONS.replace("'area:',   area", "'size:', area");
+    assert!(duplicates(&changed, 10).is_empty());
+}
+
+#[test]
+fn test_repeated_statements() {
+    let source = "\
+total = 0
+for item in items:
+    total += item.price * item.count
+    print(item.name, total)
+log('done')
+for item in items:
+    total += item.price * item.count
+    print(item.name, total)
+log('done')
+print(total)
+";
+    // Reported once, as far as the copies keep matching
+    assert_eq!(
+        duplicates(source, 10),
+        ["Duplicated statements at line 6, column 1: 19 nodes repeat the code at line 2, column 1"]
+    );
+
+    // Runs inside blocks are compared too, against code anywhere
+    let source = "\
+x = compute(a, b) + offset
+print(x * 2, x / 2)
+if ready:
+    x = compute(a, b) + offset
+    print(x * 2, x / 2)
+";
+    assert_eq!(
+        duplicates(source, 10),
+        ["Duplicated statements at line 4, column 5: 17 nodes repeat the code at line 1, column 1"]
+    );
+}
+
+#[test]
+fn test_code_inside_a_copy_is_reported_once() {
+    let method = "    def area(self):\n        w = self.width * self.scale\n        h = self.height * self.scale\n        return w * h\n";
+    let source = format!("class A:\n{0}\nclass B:\n{0}\n", method);
+    assert_eq!(
+        duplicates(&source, 10),
+        ["Duplicated statements at line 8, column 5: 19 nodes repeat the code at line 2, column 5"]
+    );
+}
@@ -0,0 +1,89 @@
+//! Tests for the rules of `except*` handlers
+
+use silk_parser::Parser;
+use silk_semantic::{SemanticAnalyzer, SemanticError};
+
+/// (statement, line) of each jump out of an `except*` handler
+fn jumps(source: &str) -> Vec<(String, usize)> {
+    let program = Parser::parse(source).expect("Parser should succeed");
+    SemanticAnalyzer::new()
+        .analyze(&program)
+        .err()
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|err| match err {
+            SemanticError::JumpOutOfExceptStar {
+                statement, line, ..
+            } => Some((statement, line)),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn test_jumps_out_of_except_star() {
+    let source = r#"
+def parse(values: list[str]) -> int:
+    for value in values:
+        try:
+            return int(value)
+        except* ValueError:
+            if value:
+                continue
+            break
+        except* TypeError:
+            return 0
+    return 0
+"#;
+    assert_eq!(
+        jumps(source),
+        vec![
+            ("continue".to_string(), 8),
+            ("break".to_string(), 9),
+            ("return".to_string(), 11)
+        ]
+    );
+}
+
+#[test]
+fn test_jumps_inside_nested_loops_and_functions_are_fine() {
+    let source = r#"
+def handle(groups: list[int]) -> int:
+    try:
+        return len(groups)
+    except* ValueError:
+        for group in groups:
+            if group:
+                break
+            continue
+        def fallback() -> int:
+            return 0
+        fallback()
+    return 0
+"#;
+    assert!(jumps(source).is_empty());
+}
+
+#[test]
+fn test_plain_except_allows_jumps() {
+    let source = r#"
+def parse(value: str) -> int:
+    try:
+        return int(value)
+    except ValueError:
+        return 0
+"#;
+    assert!(jumps(source).is_empty());
+}
+
+#[test]
+fn test_exception_group_builtins() {
+    let source = r#"
+try:
+    raise ExceptionGroup("errors", [ValueError("bad")])
+except* BaseExceptionGroup:
+    pass
+"#;
+    let program = Parser::parse(source).unwrap();
+    assert!(SemanticAnalyzer::new().analyze(&program).is_ok());
+}
@@ -0,0 +1,84 @@
+/// Tests for semantics behind unstable feature gates
+use silk_ast::{Feature, FeatureSet};
+use silk_parser::Parser;
+use silk_semantic::{SemanticAnalyzer, SemanticError};
+
+fn analyze_with(source: &str, features: FeatureSet) -> Vec<SemanticError> {
+    let program = Parser::parse_with_features(source, features.clone()).expect("Parse failed");
+    let mut analyzer = SemanticAnalyzer::new();
+    analyzer.set_features(features);
+    match analyzer.analyze(&program) {
+        Ok(()) => Vec::new(),
+        Err(errors) => errors,
+    }
+}
+
+fn comprehension_assignments() -> FeatureSet {
+    let mut features = FeatureSet::new();
+    features.enable(Feature::ComprehensionAssignments);
+    features
+}
+
+#[test]
+fn test_walrus_target_stays_in_comprehension_by_default() {
+    let source = r#"
+items = [1, 2, 3]
+result = [y for x in items if (y := x * 2) > 2]
+print(result, y)
+"#;
+    let errors = analyze_with(source, FeatureSet::new());
+    assert!(
+        errors
+            .iter()
+            .any(|e| matches!(e, SemanticError::UndefinedVariable { name, .. } if name == "y")),
+        "Expected y to be undefined outside the comprehension: {:?}",
+        errors
+    );
+}
+
+#[test]
+fn test_walrus_target_binds_in_enclosing_scope_when_enabled() {
+    let source = r#"
+items = [1, 2, 3]
+result = [y for x in items if (y := x * 2) > 2]
+print(result, y)
+"#;
+    let errors = analyze_with(source, comprehension_assignments());
+    assert!(errors.is_empty(), "Expected no errors: {:?}", errors);
+}
+
+#[test]
+fn test_walrus_in_iterable_binds_in_function_scope() {
+    let source = r#"
+def main():
+    doubled = [x * 2 for x in (items := [1, 2, 3])]
+    return doubled + items
+"#;
+    let errors = analyze_with(source, comprehension_assignments());
+    assert!(errors.is_empty(), "Expected no errors: {:?}", errors);
+}
+
+#[test]
+fn test_comprehension_variables_still_do_not_leak() {
+    let source = r#"
+items = [1, 2, 3]
+result = [y for x in items if (y := x * 2) > 2]
+print(result, x)
+"#;
+    let errors = analyze_with(source, comprehension_assignments());
+    assert!(errors
+        .iter()
+        .any(|e| matches!(e, SemanticError::UndefinedVariable { name, .. } if name == "x")));
+}
+
+#[test]
+fn test_pragma_features_apply_to_the_analysis() {
+    let source = r#"# feature: comprehension-assignments
+items = [1, 2, 3]
+result = [y for x in items if (y := x * 2) > 2]
+print(result, y)
+"#;
+    // Only the program enables the feature, not the analyzer
+    let errors = analyze_with(source, FeatureSet::new());
+    assert!(errors.is_empty(), "Expected no errors: {:?}", errors);
+}
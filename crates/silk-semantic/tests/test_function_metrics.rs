@@ -0,0 +1,143 @@
+//! Tests for function metrics and the `complex-function` lint
+
+use silk_parser::Parser;
+use silk_semantic::metrics::function_metrics;
+use silk_semantic::{ControlFlowAnalyzer, FunctionMetrics, MetricLimits, SemanticError};
+
+fn metrics(source: &str) -> Vec<FunctionMetrics> {
+    let program = Parser::parse(source).expect("Parser should succeed");
+    function_metrics(&program)
+}
+
+/// Messages of `complex-function` under `limits`
+fn complex_functions(source: &str, limits: MetricLimits) -> Vec<String> {
+    let program = Parser::parse(source).expect("Parser should succeed");
+    let mut analyzer = ControlFlowAnalyzer::new();
+    analyzer.enable_lint("complex-function");
+    analyzer.set_metric_limits(limits);
+    analyzer
+        .analyze(&program)
+        .err()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|err| matches!(err, SemanticError::ComplexFunction { .. }))
+        .map(|err| err.to_string())
+        .collect()
+}
+
+const CLASSIFY: &str = "\
+def classify(values, limit):
+    result = []
+    for value in values:
+        if value > limit and value % 2 == 0:
+            result.append('big even')
+        elif value < 0 or value is None:
+            result.append('small')
+        else:
+            while value > 10:
+                value = value // 2
+    try:
+        total = sum(v for v in values if v > 0)
+    except TypeError:
+        total = 0
+    except ValueError:
+        total = -1
+    return result if result else total
+";
+
+#[test]
+fn test_cyclomatic_complexity_counts_decisions() {
+    let found = metrics(CLASSIFY);
+    assert_eq!(found.len(), 1);
+    // for, if, elif, while, and, or, 2 handlers, generator for + if,
+    // conditional expression
+    assert_eq!(found[0].complexity, 12);
+    assert_eq!(found[0].nesting, 3);
+    assert_eq!(found[0].parameters, 2);
+    assert_eq!(found[0].lines, 17);
+}
+
+#[test]
+fn test_straight_line_function_has_complexity_one() {
+    let found = metrics("def f():\n    x = 1\n    return x\n");
+    assert_eq!(found[0].complexity, 1);
+    assert_eq!(found[0].nesting, 0);
+    assert_eq!(found[0].parameters, 0);
+    assert_eq!(found[0].lines, 3);
+}
+
+#[test]
+fn test_nested_functions_are_measured_separately() {
+    let source = "\
+def outer(a):
+    def inner(b):
+        if b:
+            return 1
+        return 2
+    return inner(a)
+
+class Shape:
+    def area(self, scale):
+        return scale if scale else 1
+
+    @staticmethod
+    def unit(size):
+        return size
+
+    @classmethod
+    def make(cls, *args, **kwargs):
+        return cls()
+";
+    let found: Vec<(String, usize, usize)> = metrics(source)
+        .into_iter()
+        .map(|m| (m.name, m.complexity, m.parameters))
+        .collect();
+    assert_eq!(
+        found,
+        vec![
+            ("outer".to_string(), 1, 1),
+            ("outer.inner".to_string(), 2, 1),
+            ("Shape.area".to_string(), 2, 1),
+            ("Shape.unit".to_string(), 1, 1),
+            ("Shape.make".to_string(), 1, 2),
+        ]
+    );
+}
+
+#[test]
+fn test_complex_function_is_allowed_by_default() {
+    let program = Parser::parse(CLASSIFY).unwrap();
+    let errors = ControlFlowAnalyzer::new()
+        .analyze(&program)
+        .err()
+        .unwrap_or_default();
+    assert!(!errors
+        .iter()
+        .any(|err| matches!(err, SemanticError::ComplexFunction { .. })));
+}
+
+#[test]
+fn test_complex_function_reports_each_metric_over_its_limit() {
+    assert_eq!(
+        complex_functions(CLASSIFY, MetricLimits::default()),
+        vec![
+            "Function 'classify' at line 1, column 1 has a cyclomatic complexity of 12 (limit 10)"
+                .to_string()
+        ]
+    );
+    let strict = MetricLimits {
+        complexity: 20,
+        nesting: 2,
+        parameters: 1,
+        lines: 60,
+    };
+    assert_eq!(
+        complex_functions(CLASSIFY, strict),
+        vec![
+            "Function 'classify' at line 1, column 1 has a nesting depth of 3 (limit 2)"
+                .to_string(),
+            "Function 'classify' at line 1, column 1 has a parameter count of 2 (limit 1)"
+                .to_string(),
+        ]
+    );
+}
@@ -0,0 +1,94 @@
+//! Tests for the hashability of dict keys and set elements
+
+use silk_parser::Parser;
+use silk_semantic::{SemanticAnalyzer, SemanticError};
+
+/// Helper to parse and analyze source code, returning every error
+fn analyze(source: &str) -> Vec<SemanticError> {
+    let program = Parser::parse(source).expect("Parser should succeed");
+    let mut analyzer = SemanticAnalyzer::new_without_control_flow();
+    analyzer.analyze(&program).err().unwrap_or_default()
+}
+
+fn codes(source: &str) -> Vec<&'static str> {
+    analyze(source).iter().map(|err| err.code()).collect()
+}
+
+#[test]
+fn test_mutable_dict_keys_are_reported() {
+    let errors = analyze("d = {[1]: 'a'}\nprint(d)\n");
+    assert_eq!(errors.len(), 1);
+    assert_eq!(
+        errors[0].to_string(),
+        "Unhashable type 'list[int]' used as a dict key at line 1, column 6"
+    );
+    assert_eq!(errors[0].error_code(), "E0315");
+
+    assert_eq!(
+        codes("d = {{1: 2}: 3, {4}: 5}\nprint(d)\n"),
+        ["unhashable-type", "unhashable-type"]
+    );
+    assert_eq!(
+        codes("d = {k: 1 for k in [[1], [2]]}\nprint(d)\n"),
+        ["unhashable-type"]
+    );
+    assert_eq!(
+        codes("d: dict[str, int] = {}\nkey = [1]\nprint(d[key])\n"),
+        ["invalid-subscript"]
+    );
+}
+
+#[test]
+fn test_mutable_set_elements_are_reported() {
+    let errors = analyze("s = {1, (2, [3])}\nprint(s)\n");
+    assert_eq!(errors.len(), 1);
+    assert_eq!(
+        errors[0].to_string(),
+        "Unhashable type 'tuple[int, list[int]]' used as a set element at line 1, column 9"
+    );
+    assert_eq!(
+        codes("s = {[x] for x in range(3)}\nprint(s)\n"),
+        ["unhashable-type"]
+    );
+}
+
+#[test]
+fn test_hashable_keys_are_accepted() {
+    let source = "d = {1: 'a', 'b': 2, (1, 'c'): 3, None: 4, 2.5: 5}\n\
+                  s = {x * 2 for x in range(3)}\n\
+                  t = {k: [k] for k in 'abc'}\n\
+                  print(d, s, t)\n";
+    assert!(analyze(source).is_empty(), "{:?}", analyze(source));
+
+    // Values that may be hashable are not reported
+    assert!(analyze("def f(key):\n    return {key: 1}\nprint(f(1))\n").is_empty());
+    assert!(analyze("x = 1 if input() else [1]\nprint({x})\n").is_empty());
+}
+
+#[test]
+fn test_classes_defining_eq_without_hash() {
+    let source = "class Point:\n    def __eq__(self, other):\n        return True\n\
+                  class Hashed(Point):\n    def __hash__(self):\n        return 0\n\
+                  class Plain:\n    pass\n";
+    assert_eq!(
+        codes(&format!(
+            "{}print({{Point(), Hashed(), Plain()}})\n",
+            source
+        )),
+        ["unhashable-type"]
+    );
+    // Subclasses inherit the missing hash
+    assert_eq!(
+        codes(&format!(
+            "{}class Sub(Point):\n    pass\nprint({{Sub(): 1}})\n",
+            source
+        )),
+        ["unhashable-type"]
+    );
+    // Bases that cannot be resolved may define `__hash__`
+    assert!(analyze("from lib import Base\nclass C(Base):\n    pass\nprint({C()})\n").is_empty());
+    assert_eq!(
+        codes("from lib import Base\nclass C(Base):\n    def __eq__(self, other):\n        return True\nprint({C()})\n"),
+        ["unhashable-type"]
+    );
+}
@@ -0,0 +1,128 @@
+//! Tests for the `import-side-effect` lint
+
+use silk_parser::Parser;
+use silk_semantic::{ControlFlowAnalyzer, SemanticError};
+
+/// Callees reported by `import-side-effect`, with their lines
+fn side_effects(source: &str) -> Vec<(String, usize)> {
+    let program = Parser::parse(source).expect("Parser should succeed");
+    let mut analyzer = ControlFlowAnalyzer::new();
+    analyzer.enable_lint("import-side-effect");
+    analyzer
+        .analyze(&program)
+        .err()
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|err| match err {
+            SemanticError::ImportSideEffect { callee, line, .. } => Some((callee, line)),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn test_import_side_effect_is_allowed_by_default() {
+    let program = Parser::parse("print('loading')\n").unwrap();
+    let errors = ControlFlowAnalyzer::new()
+        .analyze(&program)
+        .err()
+        .unwrap_or_default();
+    assert!(!errors
+        .iter()
+        .any(|err| matches!(err, SemanticError::ImportSideEffect { .. })));
+}
+
+#[test]
+fn test_top_level_calls_are_reported() {
+    let source = "\
+def setup():
+    print('setup')
+
+items = []
+items.append(1)
+setup()
+print('loaded')
+";
+    assert_eq!(
+        side_effects(source),
+        vec![
+            ("items.append".to_string(), 5),
+            ("setup".to_string(), 6),
+            ("print".to_string(), 7),
+        ]
+    );
+}
+
+#[test]
+fn test_pure_calls_are_not_reported() {
+    let source = "\
+import math
+import os.path
+from re import compile as regex
+
+SIZES = sorted([3, 1, 2])
+LIMIT = max(len(SIZES), int('4'))
+ROOT = math.sqrt(16)
+PATH = os.path.join('a', 'b')
+WORD = regex('[a-z]+')
+NAMES = ', '.join(['a', 'b'])
+ERROR = ValueError('bad value')
+";
+    assert_eq!(side_effects(source), vec![]);
+}
+
+#[test]
+fn test_impure_and_shadowed_callees_are_reported() {
+    let source = "\
+import random
+import os.path
+
+def len(value):
+    print(value)
+    return 0
+
+SEED = random.random()
+FOUND = os.path.exists('config')
+SIZE = len([1])
+";
+    assert_eq!(
+        side_effects(source),
+        vec![
+            ("random.random".to_string(), 8),
+            ("os.path.exists".to_string(), 9),
+            ("len".to_string(), 10),
+        ]
+    );
+}
+
+#[test]
+fn test_code_run_on_import_includes_classes_and_definitions() {
+    let source = "\
+def register(function):
+    return function
+
+class Config:
+    values = load()
+
+    def read(self, path=default_path()):
+        return open(path)
+
+@route('/')
+def index(handler=lambda: print('called')):
+    return print('index')
+
+if __name__ == '__main__':
+    print('running')
+else:
+    print('imported')
+";
+    assert_eq!(
+        side_effects(source),
+        vec![
+            ("load".to_string(), 5),
+            ("default_path".to_string(), 7),
+            ("route".to_string(), 10),
+            ("print".to_string(), 17),
+        ]
+    );
+}
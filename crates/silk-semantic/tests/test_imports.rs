@@ -0,0 +1,190 @@
+//! Tests for import bindings, re-exports and the unused-import lint
+
+use silk_parser::Parser;
+use silk_semantic::{SemanticAnalyzer, SemanticError, SymbolKind};
+
+/// Helper to run the full analysis (including control flow lints)
+fn analyze(source: &str) -> Vec<SemanticError> {
+    let program = Parser::parse(source).expect("Parser should succeed");
+    let mut analyzer = SemanticAnalyzer::new();
+    analyzer.analyze(&program).err().unwrap_or_default()
+}
+
+/// Names reported as unused imports, in report order
+fn unused_imports(source: &str) -> Vec<String> {
+    analyze(source)
+        .into_iter()
+        .filter_map(|err| match err {
+            SemanticError::UnusedImport { name, .. } => Some(name),
+            _ => None,
+        })
+        .collect()
+}
+
+// ========== BINDINGS ==========
+
+#[test]
+fn test_import_as_binds_alias_only() {
+    let program = Parser::parse("import numpy as np\nprint(np)").unwrap();
+    let mut analyzer = SemanticAnalyzer::new_without_control_flow();
+    analyzer.analyze(&program).unwrap();
+
+    let symbol = analyzer.symbol_table().resolve_symbol("np").unwrap();
+    assert_eq!(symbol.kind, SymbolKind::Module);
+    assert!(analyzer.symbol_table().resolve_symbol("numpy").is_none());
+}
+
+#[test]
+fn test_dotted_import_binds_top_level_package() {
+    let errors = analyze("import os.path\nprint(os.path.join)");
+    assert!(errors.is_empty(), "Unexpected errors: {:?}", errors);
+}
+
+#[test]
+fn test_star_import_binds_nothing() {
+    let errors = analyze("from os import *");
+    assert!(errors.is_empty(), "Unexpected errors: {:?}", errors);
+}
+
+#[test]
+fn test_conditional_import_fallback() {
+    let source = r#"
+try:
+    import json
+except ImportError:
+    json = None
+print(json)
+"#;
+    let errors = analyze(source);
+    assert!(errors.is_empty(), "Unexpected errors: {:?}", errors);
+}
+
+#[test]
+fn test_conditional_import_alternatives() {
+    let source = r#"
+try:
+    import ujson as json
+except ImportError:
+    import json
+print(json)
+"#;
+    let errors = analyze(source);
+    assert!(errors.is_empty(), "Unexpected errors: {:?}", errors);
+}
+
+// ========== UNUSED IMPORTS ==========
+
+#[test]
+fn test_unused_import_reported() {
+    let errors = analyze("import os");
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].code(), "unused-import");
+    assert!(errors[0].is_warning());
+}
+
+#[test]
+fn test_used_imports_not_reported() {
+    let source = r#"
+import math
+from os import path
+import numpy as np
+print(math.pi, np)
+path.join("a", "b")
+"#;
+    assert!(unused_imports(source).is_empty());
+}
+
+#[test]
+fn test_unused_alias_reported_by_bound_name() {
+    assert_eq!(unused_imports("from x import y as z"), vec!["z"]);
+}
+
+#[test]
+fn test_import_used_inside_function() {
+    let source = r#"
+import sys
+
+def main():
+    sys.exit(0)
+"#;
+    assert!(unused_imports(source).is_empty());
+}
+
+#[test]
+fn test_import_used_only_in_annotations() {
+    let source = r#"
+from typing import Callable, Dict, List, Optional, Tuple
+from shapes import Point, Polygon
+
+def first(x: Optional[int], points: List[Point]) -> Dict[str, Tuple[int, int]]:
+    return {}
+
+handler: Callable = first
+corners: list[Polygon] = []
+print(handler, corners)
+"#;
+    assert!(
+        unused_imports(source).is_empty(),
+        "{:?}",
+        unused_imports(source)
+    );
+    assert_eq!(
+        unused_imports("from typing import Optional, Any\nx: Optional[int] = None\nprint(x)"),
+        ["Any"]
+    );
+}
+
+#[test]
+fn test_fix_span_covers_statement_when_all_unused() {
+    let source = "import os, sys";
+    let errors = analyze(source);
+    assert_eq!(errors.len(), 2);
+    for err in &errors {
+        let SemanticError::UnusedImport { fix_span, .. } = err else {
+            panic!("Expected UnusedImport, got {:?}", err);
+        };
+        assert_eq!((fix_span.start, fix_span.end), (0, source.len()));
+    }
+}
+
+#[test]
+fn test_fix_span_covers_name_when_partially_used() {
+    let source = "from m import a, b as c\nprint(a)";
+    let errors = analyze(source);
+    assert_eq!(errors.len(), 1);
+    let SemanticError::UnusedImport {
+        name,
+        span,
+        fix_span,
+        ..
+    } = &errors[0]
+    else {
+        panic!("Expected UnusedImport, got {:?}", errors[0]);
+    };
+    assert_eq!(name, "c");
+    assert_eq!(&source[fix_span.start..fix_span.end], "b as c");
+    assert_eq!(span, fix_span);
+}
+
+// ========== RE-EXPORTS ==========
+
+#[test]
+fn test_redundant_alias_is_reexport() {
+    let source = "import os as os\nfrom m import f as f";
+    assert!(unused_imports(source).is_empty());
+}
+
+#[test]
+fn test_names_in_dunder_all_are_exported() {
+    let source = r#"
+from m import a, b, c
+__all__ = ["a"]
+__all__ += ("b",)
+"#;
+    assert_eq!(unused_imports(source), vec!["c"]);
+}
+
+#[test]
+fn test_future_import_never_unused() {
+    assert!(unused_imports("from __future__ import annotations").is_empty());
+}
@@ -0,0 +1,333 @@
+//! Tests for inheritance, C3 method resolution order, and super()
+
+use silk_parser::Parser;
+use silk_semantic::{c3_linearization, SemanticAnalyzer, SemanticError, Type};
+
+/// Helper to parse and analyze source code
+fn analyze(source: &str) -> Result<(), Vec<SemanticError>> {
+    let program = Parser::parse(source).expect("Parser should succeed");
+
+    let mut analyzer = SemanticAnalyzer::new_without_control_flow();
+    analyzer.analyze(&program)
+}
+
+/// Helper returning the analyzer so symbols and classes can be inspected
+fn analyzer_for(source: &str) -> SemanticAnalyzer {
+    let program = Parser::parse(source).expect("Parser should succeed");
+
+    let mut analyzer = SemanticAnalyzer::new_without_control_flow();
+    let result = analyzer.analyze(&program);
+    assert!(result.is_ok(), "Analysis should succeed: {:?}", result);
+    analyzer
+}
+
+fn names(list: &[&str]) -> Vec<String> {
+    list.iter().map(|s| s.to_string()).collect()
+}
+
+// ========== C3 LINEARIZATION ==========
+
+#[test]
+fn test_c3_linearization_single_base() {
+    let mro = c3_linearization("B", &names(&["A"]), vec![names(&["A"])]);
+    assert_eq!(mro, Some(names(&["B", "A"])));
+}
+
+#[test]
+fn test_c3_linearization_conflict() {
+    // class C(A, B) where B already inherits from A
+    let mro = c3_linearization(
+        "C",
+        &names(&["A", "B"]),
+        vec![names(&["A"]), names(&["B", "A"])],
+    );
+    assert_eq!(mro, None);
+}
+
+#[test]
+fn test_single_inheritance_mro() {
+    let source = r#"
+class Animal:
+    pass
+
+class Dog(Animal):
+    pass
+    "#;
+    let analyzer = analyzer_for(source);
+    let info = analyzer.class_info("Dog").unwrap();
+    assert_eq!(info.bases, names(&["Animal"]));
+    assert_eq!(info.mro, names(&["Dog", "Animal"]));
+}
+
+#[test]
+fn test_diamond_mro() {
+    let source = r#"
+class A:
+    pass
+
+class B(A):
+    pass
+
+class C(A):
+    pass
+
+class D(B, C):
+    pass
+    "#;
+    let analyzer = analyzer_for(source);
+    assert_eq!(
+        analyzer.class_info("D").unwrap().mro,
+        names(&["D", "B", "C", "A"])
+    );
+}
+
+#[test]
+fn test_object_base_is_implicit() {
+    let source = r#"
+class A(object):
+    pass
+    "#;
+    let analyzer = analyzer_for(source);
+    assert_eq!(analyzer.class_info("A").unwrap().mro, names(&["A"]));
+}
+
+#[test]
+fn test_inconsistent_mro() {
+    let source = r#"
+class A:
+    pass
+
+class B(A):
+    pass
+
+class C(A, B):
+    pass
+    "#;
+    let errors = analyze(source).unwrap_err();
+    assert!(matches!(
+        &errors[0],
+        SemanticError::InconsistentMro { class_name, .. } if class_name == "C"
+    ));
+}
+
+#[test]
+fn test_circular_inheritance() {
+    let source = r#"
+class A(B):
+    pass
+
+class B(A):
+    pass
+    "#;
+    let errors = analyze(source).unwrap_err();
+    assert!(errors
+        .iter()
+        .any(|e| matches!(e, SemanticError::InconsistentMro { .. })));
+}
+
+// ========== MEMBER RESOLUTION ==========
+
+#[test]
+fn test_inherited_method_call_type() {
+    let source = r#"
+class Animal:
+    def legs(self) -> int:
+        return 4
+
+class Dog(Animal):
+    pass
+
+d = Dog()
+n = d.legs()
+    "#;
+    let analyzer = analyzer_for(source);
+    let symbol = analyzer.symbol_table().resolve_symbol("n").unwrap();
+    assert_eq!(symbol.ty, Type::Int);
+}
+
+#[test]
+fn test_override_follows_mro() {
+    let source = r#"
+class A:
+    def name(self) -> int:
+        return 1
+
+class B(A):
+    pass
+
+class C(A):
+    def name(self) -> str:
+        return "c"
+
+class D(B, C):
+    pass
+
+x = D().name()
+    "#;
+    let analyzer = analyzer_for(source);
+    let symbol = analyzer.symbol_table().resolve_symbol("x").unwrap();
+    assert_eq!(symbol.ty, Type::Str);
+}
+
+#[test]
+fn test_inherited_method_argument_count() {
+    let source = r#"
+class Base:
+    def add(self, n: int) -> int:
+        return n
+
+class Child(Base):
+    pass
+
+x = Child().add()
+    "#;
+    let errors = analyze(source).unwrap_err();
+    assert!(matches!(
+        &errors[0],
+        SemanticError::ArgumentCountMismatch { function_name, expected: 1, actual: 0, .. }
+            if function_name == "Child.add"
+    ));
+}
+
+#[test]
+fn test_local_class_does_not_share_members_with_module_class() {
+    let source = r#"
+class Node:
+    def size(self) -> int:
+        return 1
+
+def make() -> int:
+    class Node:
+        def width(self) -> int:
+            return 2
+
+    class Leaf(Node):
+        pass
+
+    return Leaf().width() + Node().width()
+
+class Tree:
+    class Node:
+        pass
+
+n = Node().size()
+    "#;
+    let analyzer = analyzer_for(source);
+    assert!(analyzer.class_info("Node").unwrap().has_member("size"));
+    let local = analyzer.class_info("make.<locals>.Node").unwrap();
+    assert_eq!(local.name, "Node");
+    assert!(local.has_member("width"));
+    assert_eq!(
+        analyzer.class_info("make.<locals>.Leaf").unwrap().mro,
+        names(&["make.<locals>.Leaf", "make.<locals>.Node"])
+    );
+    assert!(analyzer.class_info("Tree.Node").is_some());
+
+    let source = r#"
+class Node:
+    def size(self) -> int:
+        return 1
+
+def make() -> int:
+    class Node:
+        def width(self) -> int:
+            return 2
+
+    node = Node()
+    return node.size()
+    "#;
+    let errors = analyze(source).unwrap_err();
+    assert!(matches!(
+        &errors[..],
+        [SemanticError::UnknownAttribute { class_name, attr, .. }]
+            if class_name == "Node" && attr == "size"
+    ));
+}
+
+// ========== SUPER ==========
+
+#[test]
+fn test_super_method_call_type() {
+    let source = r#"
+class Base:
+    def size(self) -> int:
+        return 1
+
+class Child(Base):
+    def size(self) -> int:
+        return super().size() + 1
+
+    def label(self) -> str:
+        return "child"
+    "#;
+    assert!(analyze(source).is_ok(), "{:?}", analyze(source));
+}
+
+#[test]
+fn test_super_skips_current_class() {
+    let source = r#"
+class Base:
+    def value(self) -> str:
+        return "base"
+
+class Child(Base):
+    def value(self) -> int:
+        return 1
+
+    def check(self) -> int:
+        return super().value()
+    "#;
+    let errors = analyze(source).unwrap_err();
+    assert!(matches!(
+        &errors[0],
+        SemanticError::ReturnTypeMismatch { actual_type, .. } if actual_type == "str"
+    ));
+}
+
+#[test]
+fn test_super_outside_class() {
+    let source = r#"
+def f():
+    return super()
+    "#;
+    let errors = analyze(source).unwrap_err();
+    assert!(matches!(
+        &errors[0],
+        SemanticError::SuperOutsideClass { .. }
+    ));
+}
+
+// ========== SUBTYPING ==========
+
+#[test]
+fn test_subclass_assignable_to_base_annotation() {
+    let source = r#"
+class Animal:
+    pass
+
+class Dog(Animal):
+    pass
+
+a: Animal = Dog()
+    "#;
+    assert!(analyze(source).is_ok(), "{:?}", analyze(source));
+}
+
+#[test]
+fn test_base_not_assignable_to_subclass_annotation() {
+    let source = r#"
+class Animal:
+    pass
+
+class Dog(Animal):
+    pass
+
+d: Dog = Animal()
+    "#;
+    let errors = analyze(source).unwrap_err();
+    assert!(matches!(
+        &errors[0],
+        SemanticError::AssignmentTypeMismatch { expected_type, value_type, .. }
+            if expected_type == "Dog" && value_type == "Animal"
+    ));
+}
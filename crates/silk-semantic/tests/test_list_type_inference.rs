@@ -190,3 +190,17 @@ fn test_nested_empty_lists() {
     );
     assert_eq!(symbol.ty.to_string(), "list[list[<unknown>]]");
 }
+
+#[test]
+fn test_list_slice_is_a_list() {
+    let source = "x = [1, 2, 3]\ny = x[1:]\nz = x[0]";
+    let program = Parser::parse(source).unwrap();
+
+    let mut analyzer = SemanticAnalyzer::new_without_control_flow();
+    analyzer.analyze(&program).unwrap();
+
+    let symbol = analyzer.symbol_table().resolve_symbol("y").unwrap();
+    assert_eq!(symbol.ty, Type::List(Box::new(Type::Int)));
+    let symbol = analyzer.symbol_table().resolve_symbol("z").unwrap();
+    assert_eq!(symbol.ty, Type::Int);
+}
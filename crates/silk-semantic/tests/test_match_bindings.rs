@@ -0,0 +1,173 @@
+//! Tests for the names that `case` patterns look up and bind
+
+use silk_parser::Parser;
+use silk_semantic::{ControlFlowAnalyzer, SemanticAnalyzer, SemanticError};
+
+/// Names reported as undefined by the semantic analyzer
+fn undefined(source: &str) -> Vec<String> {
+    let program = Parser::parse(source).expect("Parser should succeed");
+    SemanticAnalyzer::new()
+        .analyze(&program)
+        .err()
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|err| match err {
+            SemanticError::UndefinedVariable { name, .. } => Some(name),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Names reported as possibly uninitialized by control flow analysis
+fn uninitialized(source: &str) -> Vec<String> {
+    let program = Parser::parse(source).expect("Parser should succeed");
+    ControlFlowAnalyzer::new()
+        .analyze(&program)
+        .err()
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|err| match err {
+            SemanticError::UninitializedVariable { name, .. } => Some(name),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn test_captured_names_are_defined_in_the_case() {
+    let source = r#"
+class Point:
+    pass
+
+def describe(command):
+    match command:
+        case Point(x=0, y=y) if y > 0:
+            return y
+        case [first, *rest]:
+            return first + len(rest)
+        case {"id": 1 | 2 as ident, **extra}:
+            return ident + len(extra)
+        case _:
+            return 0
+"#;
+    assert_eq!(undefined(source), Vec::<String>::new());
+}
+
+#[test]
+fn test_class_and_value_patterns_are_looked_up() {
+    let source = r#"
+def describe(command):
+    match command:
+        case Missing():
+            return 1
+        case Color.RED:
+            return 2
+    return 0
+"#;
+    assert_eq!(undefined(source), vec!["Missing", "Color"]);
+}
+
+#[test]
+fn test_name_bound_by_every_case_is_initialized_after_the_match() {
+    let source = r#"
+def size(shape):
+    match shape:
+        case (width, height):
+            area = width * height
+        case [side]:
+            area = side * side
+        case _:
+            area = 0
+    return area
+"#;
+    assert_eq!(uninitialized(source), Vec::<String>::new());
+
+    // A capture in an or-pattern binds in every alternative
+    let source = r#"
+def first(items):
+    match items:
+        case [head, *_] | (head,):
+            pass
+        case other:
+            head = other
+    return head
+"#;
+    assert_eq!(uninitialized(source), Vec::<String>::new());
+}
+
+#[test]
+fn test_name_bound_by_some_cases_may_be_uninitialized() {
+    let source = r#"
+def size(shape):
+    match shape:
+        case (width, height):
+            area = width * height
+        case _:
+            pass
+    return area
+"#;
+    assert_eq!(uninitialized(source), vec!["area"]);
+
+    // Without an irrefutable case, no case may run at all
+    let source = r#"
+def size(shape):
+    match shape:
+        case (width, height):
+            area = width * height
+        case [side]:
+            area = side * side
+    return area
+"#;
+    assert_eq!(uninitialized(source), vec!["area"]);
+
+    // Captures of one case are not bound in the next
+    let source = r#"
+def size(shape):
+    match shape:
+        case [side]:
+            pass
+        case _:
+            return side
+"#;
+    assert_eq!(uninitialized(source), vec!["side"]);
+}
+
+#[test]
+fn test_cases_that_do_not_fall_through_are_not_merged() {
+    let source = r#"
+def size(shape):
+    match shape:
+        case (width, height):
+            area = width * height
+        case _:
+            return 0
+    return area
+"#;
+    assert_eq!(uninitialized(source), Vec::<String>::new());
+
+    // A guarded wildcard may not match
+    let source = r#"
+def size(shape, strict):
+    match shape:
+        case (width, height):
+            area = width * height
+        case _ if strict:
+            return 0
+    return area
+"#;
+    assert_eq!(uninitialized(source), vec!["area"]);
+}
+
+#[test]
+fn test_case_matching_a_constant_subject_always_runs() {
+    let source = r#"
+def mode():
+    match "fast":
+        case "slow":
+            speed = 1
+        case "fast" | "quick":
+            speed = 2
+    return speed
+"#;
+    assert_eq!(uninitialized(source), Vec::<String>::new());
+}
@@ -0,0 +1,375 @@
+//! Tests for @property, @staticmethod, and @classmethod semantics
+
+use silk_parser::Parser;
+use silk_semantic::{MethodKind, SemanticAnalyzer, SemanticError, Type};
+
+/// Helper to parse and analyze source code
+fn analyze(source: &str) -> Result<(), Vec<SemanticError>> {
+    let program = Parser::parse(source).expect("Parser should succeed");
+
+    let mut analyzer = SemanticAnalyzer::new_without_control_flow();
+    analyzer.analyze(&program)
+}
+
+/// Helper returning the analyzer so symbols and classes can be inspected
+fn analyzer_for(source: &str) -> SemanticAnalyzer {
+    let program = Parser::parse(source).expect("Parser should succeed");
+
+    let mut analyzer = SemanticAnalyzer::new_without_control_flow();
+    analyzer.analyze(&program).expect("Analysis should succeed");
+    analyzer
+}
+
+// ========== METHOD KINDS ==========
+
+#[test]
+fn test_decorators_are_builtin() {
+    let source = r#"
+class Point:
+    @property
+    def x(self):
+        return 1
+
+    @staticmethod
+    def origin():
+        return 0
+
+    @classmethod
+    def make(cls):
+        return cls()
+    "#;
+    assert!(analyze(source).is_ok(), "{:?}", analyze(source));
+}
+
+#[test]
+fn test_method_kinds_collected() {
+    let source = r#"
+class Point:
+    def move(self, dx):
+        pass
+
+    @property
+    def x(self):
+        return 1
+
+    @x.setter
+    def x(self, value):
+        pass
+
+    @staticmethod
+    def origin():
+        return 0
+
+    @classmethod
+    def make(cls):
+        return cls()
+    "#;
+    let analyzer = analyzer_for(source);
+    let info = analyzer
+        .class_info("Point")
+        .expect("Point should be collected");
+
+    assert_eq!(info.method("move").unwrap().kind, MethodKind::Instance);
+    assert_eq!(info.method("x").unwrap().kind, MethodKind::Property);
+    assert_eq!(info.method("origin").unwrap().kind, MethodKind::Static);
+    assert_eq!(info.method("make").unwrap().kind, MethodKind::Class);
+    assert!(info.property("x").is_some());
+    assert!(info.property("move").is_none());
+
+    // The receiver is not part of the call signature
+    assert_eq!(info.method("move").unwrap().params.len(), 1);
+}
+
+// ========== RECEIVER CONVENTIONS ==========
+
+#[test]
+fn test_instance_method_without_self() {
+    let source = r#"
+class Point:
+    def move(dx):
+        pass
+    "#;
+    let errors = analyze(source).unwrap_err();
+    assert!(matches!(
+        &errors[0],
+        SemanticError::InvalidMethodReceiver { method, expected, .. }
+            if method == "move" && expected == "self"
+    ));
+    assert!(errors[0].is_warning());
+}
+
+#[test]
+fn test_instance_method_with_no_parameters() {
+    let source = r#"
+class Point:
+    def reset():
+        pass
+    "#;
+    let errors = analyze(source).unwrap_err();
+    assert!(matches!(
+        &errors[0],
+        SemanticError::InvalidMethodReceiver { method, .. } if method == "reset"
+    ));
+}
+
+#[test]
+fn test_classmethod_requires_cls() {
+    let source = r#"
+class Point:
+    @classmethod
+    def make(self):
+        return 1
+    "#;
+    let errors = analyze(source).unwrap_err();
+    assert!(matches!(
+        &errors[0],
+        SemanticError::InvalidMethodReceiver { method_kind, expected, .. }
+            if method_kind == "Class method" && expected == "cls"
+    ));
+}
+
+#[test]
+fn test_staticmethod_with_self() {
+    let source = r#"
+class Point:
+    @staticmethod
+    def origin(self):
+        return 0
+    "#;
+    let errors = analyze(source).unwrap_err();
+    assert!(matches!(
+        &errors[0],
+        SemanticError::StaticMethodReceiver { method, param, .. }
+            if method == "origin" && param == "self"
+    ));
+    assert!(errors[0].is_warning());
+}
+
+#[test]
+fn test_staticmethod_with_regular_parameters() {
+    let source = r#"
+class Math:
+    @staticmethod
+    def add(a, b):
+        return a + b
+    "#;
+    assert!(analyze(source).is_ok());
+}
+
+#[test]
+fn test_new_is_implicit_classmethod() {
+    let source = r#"
+class Point:
+    def __new__(cls):
+        return cls
+    "#;
+    assert!(analyze(source).is_ok());
+}
+
+#[test]
+fn test_property_getter_with_extra_parameter() {
+    let source = r#"
+class Point:
+    @property
+    def x(self, scale):
+        return scale
+    "#;
+    let errors = analyze(source).unwrap_err();
+    assert!(matches!(
+        &errors[0],
+        SemanticError::InvalidPropertySignature { name, expected: 1, actual: 2, .. } if name == "x"
+    ));
+
+    // Parameters with a default are not counted, as in the check
+    let source = r#"
+class Point:
+    @property
+    def x(self, scale, offset=0):
+        return scale + offset
+    "#;
+    let errors = analyze(source).unwrap_err();
+    assert!(matches!(
+        &errors[0],
+        SemanticError::InvalidPropertySignature {
+            expected: 1,
+            actual: 2,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn test_property_setter_requires_value() {
+    let source = r#"
+class Point:
+    @property
+    def x(self):
+        return 1
+
+    @x.setter
+    def x(self):
+        pass
+    "#;
+    let errors = analyze(source).unwrap_err();
+    assert!(matches!(
+        &errors[0],
+        SemanticError::InvalidPropertySignature { method_kind, expected: 2, .. }
+            if method_kind == "Property setter"
+    ));
+}
+
+// ========== TYPES THROUGH METHODS ==========
+
+#[test]
+fn test_class_call_returns_instance() {
+    let source = r#"
+class Point:
+    pass
+
+p = Point()
+    "#;
+    let analyzer = analyzer_for(source);
+    let symbol = analyzer.symbol_table().resolve_symbol("p").unwrap();
+    assert_eq!(symbol.ty, Type::Instance("Point".to_string()));
+}
+
+#[test]
+fn test_property_access_uses_getter_type() {
+    let source = r#"
+class Circle:
+    @property
+    def area(self) -> float:
+        return 3.0
+
+c = Circle()
+a = c.area
+    "#;
+    let analyzer = analyzer_for(source);
+    let symbol = analyzer.symbol_table().resolve_symbol("a").unwrap();
+    assert_eq!(symbol.ty, Type::Float);
+}
+
+#[test]
+fn test_property_type_mismatch() {
+    let source = r#"
+class Circle:
+    @property
+    def name(self) -> str:
+        return "circle"
+
+c = Circle()
+n: int = c.name
+    "#;
+    let errors = analyze(source).unwrap_err();
+    assert!(matches!(
+        &errors[0],
+        SemanticError::AssignmentTypeMismatch { value_type, .. } if value_type == "str"
+    ));
+}
+
+#[test]
+fn test_staticmethod_call_on_class() {
+    let source = r#"
+class Math:
+    @staticmethod
+    def double(x: int) -> int:
+        return x * 2
+
+y = Math.double(4)
+    "#;
+    let analyzer = analyzer_for(source);
+    let symbol = analyzer.symbol_table().resolve_symbol("y").unwrap();
+    assert_eq!(symbol.ty, Type::Int);
+}
+
+#[test]
+fn test_staticmethod_does_not_receive_self() {
+    let source = r#"
+class Math:
+    @staticmethod
+    def double(x: int) -> int:
+        return x * 2
+
+m = Math()
+y = m.double()
+    "#;
+    let errors = analyze(source).unwrap_err();
+    assert!(matches!(
+        &errors[0],
+        SemanticError::ArgumentCountMismatch {
+            expected: 1,
+            actual: 0,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn test_instance_method_argument_count_excludes_self() {
+    let source = r#"
+class Counter:
+    def add(self, n: int) -> int:
+        return n
+
+c = Counter()
+x = c.add(1, 2)
+    "#;
+    let errors = analyze(source).unwrap_err();
+    assert!(matches!(
+        &errors[0],
+        SemanticError::ArgumentCountMismatch { function_name, expected: 1, actual: 2, .. }
+            if function_name == "Counter.add"
+    ));
+}
+
+#[test]
+fn test_classmethod_call_returns_declared_type() {
+    let source = r#"
+class Config:
+    @classmethod
+    def default_port(cls) -> int:
+        return 8080
+
+port = Config.default_port()
+    "#;
+    let analyzer = analyzer_for(source);
+    let symbol = analyzer.symbol_table().resolve_symbol("port").unwrap();
+    assert_eq!(symbol.ty, Type::Int);
+}
+
+#[test]
+fn test_self_is_typed_as_instance() {
+    let source = r#"
+class Circle:
+    @property
+    def radius(self) -> float:
+        return 1.0
+
+    def diameter(self) -> float:
+        return self.radius * 2
+    "#;
+    assert!(analyze(source).is_ok());
+}
+
+#[test]
+fn test_receiver_not_reported_unused() {
+    let source = r#"
+class Point:
+    @staticmethod
+    def origin():
+        return 0
+
+    @classmethod
+    def make(cls):
+        return 1
+
+    def zero(self):
+        return 0
+
+print(Point.origin(), Point.make(), Point().zero())
+    "#;
+    let program = Parser::parse(source).unwrap();
+    let mut analyzer = SemanticAnalyzer::new();
+    let result = analyzer.analyze(&program);
+    assert!(result.is_ok(), "{:?}", result);
+}
@@ -168,6 +168,16 @@ x = path
     assert!(result.is_ok());
 }
 
+#[test]
+fn test_resolve_module_attributes() {
+    let source = r#"
+if __name__ == "__main__":
+    x = (__file__, __doc__)
+    "#;
+    let result = analyze(source);
+    assert!(result.is_ok());
+}
+
 // ========== SCOPE RESOLUTION ==========
 
 #[test]
@@ -252,7 +262,14 @@ def func():
     return x
     "#;
     let result = analyze(source);
-    assert!(result.is_ok(), "Local variable can shadow global");
+    // Allowed, but reported as a shadowing warning
+    let errors = result.unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert!(
+        matches!(&errors[0], SemanticError::ShadowedName { name, .. } if name == "x"),
+        "Local variable can shadow global"
+    );
+    assert!(errors[0].is_warning());
 }
 
 #[test]
@@ -1,7 +1,6 @@
 /// Tests for Step 15.5: Nested Scope Variable Visibility
 ///
 /// Verifies that inner functions can properly access variables from outer scopes (closures).
-
 use silk_parser::Parser;
 use silk_semantic::{ControlFlowAnalyzer, SemanticError};
 
@@ -206,7 +205,7 @@ def outer():
     // y is unused but that's a different concern (UnusedVariable warning)
     // The important thing is no UninitializedVariable error
     match result {
-        Ok(()) => assert!(true),
+        Ok(()) => {}
         Err(errors) => {
             // Filter out UnusedVariable errors - we're testing scope visibility
             let init_errors: Vec<_> = errors.iter()
@@ -0,0 +1,158 @@
+//! Tests for None-safety: access to values whose type includes None
+
+use silk_parser::Parser;
+use silk_semantic::{SemanticAnalyzer, SemanticError};
+
+/// Helper to parse and analyze source code, returning every error
+fn analyze(source: &str) -> Vec<SemanticError> {
+    let program = Parser::parse(source).expect("Parser should succeed");
+    let mut analyzer = SemanticAnalyzer::new_without_control_flow();
+    analyzer.analyze(&program).err().unwrap_or_default()
+}
+
+/// (target, operation) of every optional-access error
+fn optional_accesses(source: &str) -> Vec<(String, String)> {
+    analyze(source)
+        .into_iter()
+        .filter_map(|err| match err {
+            SemanticError::OptionalAccess {
+                target, operation, ..
+            } => Some((target, operation)),
+            _ => None,
+        })
+        .collect()
+}
+
+const USER: &str = r#"
+class User:
+    def greet(self) -> str:
+        return "hi"
+
+def find(name: str) -> User | None:
+    return None
+"#;
+
+// ========== REPORTED ACCESSES ==========
+
+#[test]
+fn test_attribute_access_on_optional() {
+    let source = format!("{}\ndef f(u: User | None):\n    u.greet()\n", USER);
+    let errors = analyze(&source);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].code(), "optional-access");
+    assert!(errors[0].is_warning());
+    assert_eq!(
+        errors[0].to_string(),
+        "'u' may be None at line 10, column 5: attribute access on type 'User | None' \
+         (add an `if u is not None` check)"
+    );
+}
+
+#[test]
+fn test_subscript_on_optional() {
+    let source = r#"
+def f(items: Optional[list[int]]) -> int:
+    return items[0]
+"#;
+    // Only the None member is reported, not an invalid subscript
+    let errors = analyze(source);
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        &errors[0],
+        SemanticError::OptionalAccess { operation, .. } if operation == "subscript"
+    ));
+}
+
+#[test]
+fn test_call_on_optional() {
+    let source = r#"
+def f(n: int | None):
+    n()
+"#;
+    assert_eq!(
+        optional_accesses(source),
+        vec![("n".to_string(), "call".to_string())]
+    );
+}
+
+#[test]
+fn test_optional_call_result() {
+    let source = format!("{}\nfind(\"a\").greet()\n", USER);
+    let errors = analyze(&source);
+    assert_eq!(errors.len(), 1);
+    let SemanticError::OptionalAccess {
+        target, suggestion, ..
+    } = &errors[0]
+    else {
+        panic!("Expected OptionalAccess, got {:?}", errors[0]);
+    };
+    assert_eq!(target, "find()");
+    assert!(suggestion.contains("assign it to a variable"));
+}
+
+#[test]
+fn test_method_call_reported_once() {
+    let source = r#"
+def f(items: list[int] | None):
+    items.append(1)
+"#;
+    assert_eq!(optional_accesses(source).len(), 1);
+}
+
+// ========== GUARDED ACCESSES ==========
+
+#[test]
+fn test_guarded_by_if_not_none() {
+    let source = format!(
+        "{}\ndef f(u: User | None) -> str:\n    if u is not None:\n        return u.greet()\n    return \"\"\n",
+        USER
+    );
+    assert!(optional_accesses(&source).is_empty());
+}
+
+#[test]
+fn test_guarded_by_early_return() {
+    let source = r#"
+def f(items: list[int] | None) -> int:
+    if not items:
+        return 0
+    return items[0]
+"#;
+    assert!(optional_accesses(source).is_empty());
+}
+
+#[test]
+fn test_guarded_within_boolean_operators() {
+    let source = format!(
+        "{}\ndef f(u: User | None):\n    a = u is not None and u.greet()\n    b = u is None or u.greet()\n    print(a, b)\n",
+        USER
+    );
+    assert!(optional_accesses(&source).is_empty());
+}
+
+#[test]
+fn test_guarded_by_walrus() {
+    let source = format!(
+        "{}\nif (u := find(\"a\")) is not None:\n    u.greet()\n",
+        USER
+    );
+    assert!(optional_accesses(&source).is_empty());
+}
+
+#[test]
+fn test_guarded_by_comprehension_filter() {
+    let source = r#"
+def f(groups: list[list[int] | None]) -> list[int]:
+    return [g[0] for g in groups if g is not None]
+"#;
+    assert!(optional_accesses(source).is_empty());
+}
+
+#[test]
+fn test_unannotated_values_not_reported() {
+    let source = r#"
+def f(x):
+    return x.value
+"#;
+    assert!(optional_accesses(source).is_empty());
+}
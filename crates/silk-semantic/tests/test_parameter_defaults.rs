@@ -103,3 +103,88 @@ def f(a=x+y):
 "#;
     assert!(analyze(source).is_ok()); // Should succeed - both x and y are in outer scope
 }
+
+// ========== DEFAULT PLACEMENT ==========
+
+#[test]
+fn test_non_default_after_default_is_error() {
+    let source = r#"
+def f(a, b=1, c):
+    pass
+"#;
+    let result = analyze(source);
+    assert!(result.is_err());
+    let errors = result.unwrap_err();
+    assert!(errors.contains("NonDefaultAfterDefault"));
+    assert!(errors.contains("\"c\""));
+}
+
+#[test]
+fn test_non_default_after_positional_only_default_is_error() {
+    let source = r#"
+def f(a=1, /, b):
+    pass
+"#;
+    assert!(analyze(source)
+        .unwrap_err()
+        .contains("NonDefaultAfterDefault"));
+}
+
+#[test]
+fn test_keyword_only_without_default_after_default_is_ok() {
+    let source = r#"
+def f(a=1, *, b, c=2, d):
+    pass
+"#;
+    assert!(analyze(source).is_ok());
+}
+
+// ========== MUTABLE DEFAULTS ==========
+
+#[test]
+fn test_mutable_default_values_are_flagged() {
+    let source = r#"
+def f(a=[], b={}, c=set(), *, d=[x for x in range(3)]):
+    pass
+"#;
+    let program = Parser::parse(source).unwrap();
+    let mut analyzer = SemanticAnalyzer::new_without_control_flow();
+    let errors = analyzer.analyze(&program).unwrap_err();
+
+    let kinds: Vec<_> = errors
+        .iter()
+        .map(|e| match e {
+            silk_semantic::SemanticError::MutableDefault { param, kind, .. } => {
+                assert!(e.is_warning());
+                assert!(e.to_string().contains("use None"));
+                (param.as_str(), kind.as_str())
+            }
+            other => panic!("Unexpected error: {:?}", other),
+        })
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![("a", "list"), ("b", "dict"), ("c", "set"), ("d", "list")]
+    );
+}
+
+#[test]
+fn test_immutable_default_values_are_ok() {
+    let source = r#"
+def f(a=None, b=(), c="text", d=0, e=-1.5):
+    pass
+"#;
+    assert!(analyze(source).is_ok());
+}
+
+#[test]
+fn test_user_defined_list_function_is_not_flagged() {
+    let source = r#"
+def list():
+    return 0
+
+def f(a=list()):
+    pass
+"#;
+    assert!(analyze(source).is_ok());
+}
@@ -0,0 +1,296 @@
+/// Tests for positional-only (`/`) and keyword-only (`*`) parameters and
+/// call-site argument binding
+use silk_parser::Parser;
+use silk_semantic::{BindError, SemanticAnalyzer, SemanticError, Signature};
+
+fn analyze(source: &str) -> Vec<SemanticError> {
+    let program = Parser::parse(source).expect("Failed to parse");
+    let mut analyzer = SemanticAnalyzer::new_without_control_flow();
+    analyzer.analyze(&program).err().unwrap_or_default()
+}
+
+fn signature(source: &str) -> Signature {
+    let program = Parser::parse(source).expect("Failed to parse");
+    match &program.statements[0].kind {
+        silk_ast::StatementKind::FunctionDef { params, .. } => {
+            Signature::from_params(params, |_| silk_semantic::Type::Unknown)
+        }
+        other => panic!("Expected function definition, got {:?}", other),
+    }
+}
+
+// ========== VALID CALLS ==========
+
+#[test]
+fn test_valid_calls_with_all_parameter_kinds() {
+    let errors = analyze(
+        r#"
+def f(a, b=1, /, c=2, *, d, e=5):
+    return a
+
+f(1, d=4)
+f(1, 2, 3, d=4)
+f(1, 2, c=3, d=4, e=5)
+f(1, e=5, d=4)
+"#,
+    );
+    assert!(errors.is_empty(), "Expected no errors, got: {:?}", errors);
+}
+
+#[test]
+fn test_defaults_and_keywords_for_regular_parameters() {
+    let errors = analyze(
+        r#"
+def greet(name, greeting="Hello"):
+    return greeting
+
+greet("Ada")
+greet(name="Ada")
+greet("Ada", greeting="Hi")
+"#,
+    );
+    assert!(errors.is_empty(), "Expected no errors, got: {:?}", errors);
+}
+
+#[test]
+fn test_varargs_and_kwargs_absorb_extra_arguments() {
+    let errors = analyze(
+        r#"
+def log(*parts, **options):
+    return parts
+
+log(1, 2, 3, sep=",", end="")
+"#,
+    );
+    assert!(errors.is_empty(), "Expected no errors, got: {:?}", errors);
+}
+
+#[test]
+fn test_kwargs_unpacking_may_supply_missing_arguments() {
+    let errors = analyze(
+        r#"
+def f(a, *, b):
+    return a
+
+options = {}
+f(1, **options)
+"#,
+    );
+    assert!(errors.is_empty(), "Expected no errors, got: {:?}", errors);
+}
+
+// ========== CALL-SITE VIOLATIONS ==========
+
+#[test]
+fn test_positional_only_passed_by_keyword() {
+    let errors = analyze(
+        r#"
+def f(a, /, b):
+    return a
+
+f(a=1, b=2)
+"#,
+    );
+    assert!(
+        matches!(
+            errors.as_slice(),
+            [SemanticError::PositionalOnlyArgument { function_name, param, line: 5, .. }]
+                if function_name == "f" && param == "a"
+        ),
+        "got: {:?}",
+        errors
+    );
+}
+
+#[test]
+fn test_keyword_only_passed_positionally() {
+    let errors = analyze(
+        r#"
+def f(a, *, b):
+    return a
+
+f(1, 2)
+"#,
+    );
+    assert!(
+        matches!(
+            errors.as_slice(),
+            [SemanticError::KeywordOnlyArgument { param, column: 6, .. }] if param == "b"
+        ),
+        "got: {:?}",
+        errors
+    );
+}
+
+#[test]
+fn test_missing_keyword_only_argument() {
+    let errors = analyze(
+        r#"
+def f(a, *, b):
+    return a
+
+f(1)
+"#,
+    );
+    assert!(
+        matches!(
+            errors.as_slice(),
+            [SemanticError::MissingArgument { param, .. }] if param == "b"
+        ),
+        "got: {:?}",
+        errors
+    );
+}
+
+#[test]
+fn test_unexpected_and_duplicate_keywords() {
+    let errors = analyze(
+        r#"
+def f(a, b=2):
+    return a
+
+f(1, c=3)
+f(1, a=2)
+"#,
+    );
+    assert_eq!(errors.len(), 2, "got: {:?}", errors);
+    assert!(matches!(
+        &errors[0],
+        SemanticError::UnexpectedKeywordArgument { keyword, .. } if keyword == "c"
+    ));
+    assert!(matches!(
+        &errors[1],
+        SemanticError::DuplicateArgument { param, .. } if param == "a"
+    ));
+}
+
+#[test]
+fn test_keyword_argument_types_are_checked() {
+    let errors = analyze(
+        r#"
+def f(a: int, *, scale: float):
+    return a
+
+f(1, scale="big")
+"#,
+    );
+    assert!(
+        matches!(
+            errors.as_slice(),
+            [SemanticError::ArgumentTypeMismatch { param_name, .. }] if param_name == "scale"
+        ),
+        "got: {:?}",
+        errors
+    );
+}
+
+#[test]
+fn test_method_keyword_only_parameters() {
+    let errors = analyze(
+        r#"
+class Shape:
+    def scale(self, factor, *, around=0):
+        return self
+
+s = Shape()
+s.scale(2, around=1)
+s.scale(2, 1)
+"#,
+    );
+    assert!(
+        matches!(
+            errors.as_slice(),
+            [SemanticError::KeywordOnlyArgument { function_name, param, .. }]
+                if function_name == "Shape.scale" && param == "around"
+        ),
+        "got: {:?}",
+        errors
+    );
+}
+
+// ========== SIGNATURE BINDING ==========
+
+#[test]
+fn test_signature_binding_maps_arguments_to_parameters() {
+    let sig = signature("def f(a, /, b, *rest, c, **extra):\n    pass");
+    let binding = sig.bind(4, &[Some("c"), Some("z")]).unwrap();
+    // a, b, then *rest twice; c by name, z into **extra
+    assert_eq!(binding.positional, vec![0, 1, 2, 2]);
+    assert_eq!(binding.keywords, vec![Some(3), Some(4)]);
+}
+
+#[test]
+fn test_signature_binding_errors() {
+    let sig = signature("def f(a, /, b=1, *, c):\n    pass");
+    assert_eq!(
+        sig.bind(3, &[]),
+        Err(BindError::TooManyPositional {
+            expected: 2,
+            given: 3,
+            keyword_only: Some("c".to_string()),
+        })
+    );
+    assert_eq!(
+        sig.bind(1, &[Some("a"), Some("c")]),
+        Err(BindError::PositionalOnlyAsKeyword("a".to_string()))
+    );
+    assert_eq!(
+        sig.bind(0, &[]),
+        Err(BindError::Missing(vec!["a".to_string(), "c".to_string()]))
+    );
+    assert_eq!(sig.required_positional(), 1);
+}
+
+#[test]
+fn test_signature_display() {
+    use silk_semantic::{Param, ParamKind, Signature, Type};
+
+    let param = |name: &str, kind, ty, has_default| Param {
+        name: name.to_string(),
+        kind,
+        ty,
+        has_default,
+    };
+    let signature = Signature {
+        params: vec![
+            param("a", ParamKind::PositionalOnly, Type::Int, false),
+            param("b", ParamKind::PositionalOrKeyword, Type::Unknown, true),
+            param("c", ParamKind::KeywordOnly, Type::Str, false),
+            param("kwargs", ParamKind::VarKeyword, Type::Unknown, false),
+        ],
+    };
+    assert_eq!(
+        signature.to_string(),
+        "(a: int, /, b = ..., *, c: str, **kwargs)"
+    );
+    assert_eq!(Signature::default().to_string(), "()");
+}
+
+#[test]
+fn test_parameter_for_argument() {
+    use silk_semantic::{Param, ParamKind, Signature, Type};
+
+    let param = |name: &str, kind| Param {
+        name: name.to_string(),
+        kind,
+        ty: Type::Unknown,
+        has_default: false,
+    };
+    let signature = Signature {
+        params: vec![
+            param("a", ParamKind::PositionalOnly),
+            param("b", ParamKind::PositionalOrKeyword),
+            param("args", ParamKind::VarPositional),
+            param("c", ParamKind::KeywordOnly),
+        ],
+    };
+    assert_eq!(signature.param_for_argument(1, None), Some(1));
+    assert_eq!(signature.param_for_argument(5, None), Some(2));
+    assert_eq!(signature.param_for_argument(0, Some("c")), Some(3));
+    assert_eq!(signature.param_for_argument(0, Some("a")), None);
+
+    let text = signature.to_string();
+    let ranges = signature.param_ranges();
+    let params: Vec<&str> = ranges.iter().map(|range| &text[range.clone()]).collect();
+    assert_eq!(params, vec!["a", "b", "*args", "c"]);
+}
@@ -0,0 +1,231 @@
+//! Tests for purity inference, folding calls of pure functions and `@pure`
+
+use silk_parser::Parser;
+use silk_semantic::{Purity, SemanticAnalyzer, SemanticError};
+
+/// Span of the module-level function `name`
+fn definition(program: &silk_ast::Program, name: &str) -> silk_lexer::Span {
+    program
+        .statements
+        .iter()
+        .find(|statement| {
+            matches!(&statement.kind, silk_ast::StatementKind::FunctionDef { name: n, .. } if n == name)
+        })
+        .expect("function should be defined")
+        .span
+}
+
+/// Why each named module-level function is impure (`None` when pure)
+fn reasons(source: &str, names: &[&str]) -> Vec<Option<String>> {
+    let program = Parser::parse(source).expect("Parser should succeed");
+    let purity = Purity::infer(&program);
+    names
+        .iter()
+        .map(|name| {
+            purity
+                .impurity(definition(&program, name))
+                .map(|impurity| impurity.reason.clone())
+        })
+        .collect()
+}
+
+fn analyze(source: &str) -> Vec<SemanticError> {
+    let program = Parser::parse(source).expect("Parser should succeed");
+    SemanticAnalyzer::new()
+        .analyze(&program)
+        .err()
+        .unwrap_or_default()
+}
+
+#[test]
+fn test_side_effects_make_functions_impure() {
+    let source = "\
+import math
+
+counter = 0
+
+def area(width, height):
+    return math.sqrt(width * height) + abs(width)
+
+def log(message):
+    print(message)
+
+def bump():
+    global counter
+    counter += 1
+
+def rename(point):
+    point.name = 'origin'
+
+def read(path):
+    with open(path) as file:
+        return file.read()
+";
+    assert_eq!(
+        reasons(source, &["area", "log", "bump", "rename", "read"]),
+        vec![
+            None,
+            Some("calls 'print'".to_string()),
+            Some("declares 'counter' global".to_string()),
+            Some("assigns to 'point.name'".to_string()),
+            Some("enters a 'with' block".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_purity_follows_calls() {
+    let source = "\
+def double(x):
+    return x * 2
+
+def quadruple(x):
+    return double(double(x))
+
+def shout(x):
+    log(x)
+    return x
+
+def log(x):
+    print(x)
+
+def even(n):
+    return n == 0 or odd(n - 1)
+
+def odd(n):
+    return n != 0 and even(n - 1)
+";
+    assert_eq!(
+        reasons(source, &["quadruple", "shout", "even", "odd"]),
+        vec![None, Some("calls 'log'".to_string()), None, None]
+    );
+}
+
+#[test]
+fn test_values_created_by_the_function_may_change() {
+    let source = "\
+def squares(n):
+    result = []
+    for i in range(n):
+        result.append(i * i)
+    table = {}
+    table['count'] = n
+    return result
+
+def extend(items):
+    items.append(1)
+
+def undecorated(x):
+    return x
+
+@staticmethod
+def wrapped(x):
+    return x
+";
+    assert_eq!(
+        reasons(source, &["squares", "extend"]),
+        vec![None, Some("calls 'items.append'".to_string())]
+    );
+
+    let program = Parser::parse(source).unwrap();
+    let purity = Purity::infer(&program);
+    assert!(purity.is_pure_function("undecorated"));
+    // The decorator may replace the function with anything
+    assert!(!purity.is_pure_function("wrapped"));
+}
+
+#[test]
+fn test_function_symbols_record_purity() {
+    let source = "\
+def add(a, b):
+    return a + b
+
+def show(a):
+    print(a)
+
+show(add(1, 2))
+";
+    let program = Parser::parse(source).unwrap();
+    let mut analyzer = SemanticAnalyzer::new();
+    analyzer.analyze(&program).unwrap();
+    let table = analyzer.symbol_table();
+    assert!(table.resolve_symbol("add").unwrap().pure);
+    assert!(!table.resolve_symbol("show").unwrap().pure);
+}
+
+#[test]
+fn test_constant_folding_through_pure_calls() {
+    let source = "\
+def zero():
+    return 0
+
+def offset(x):
+    return x - 3
+
+ratio = 1 / zero()
+other = 10 % offset(3)
+";
+    let errors = analyze(source);
+    assert_eq!(
+        errors
+            .iter()
+            .filter(|err| matches!(err, SemanticError::DivisionByZero { .. }))
+            .count(),
+        2,
+        "{:?}",
+        errors
+    );
+
+    // Impure or reassigned functions are not folded
+    let source = "\
+def zero():
+    print('zero')
+    return 0
+
+def nothing():
+    return 0
+
+nothing = abs
+
+ratio = 1 / zero()
+other = 1 / nothing(1)
+";
+    let errors = analyze(source);
+    assert!(
+        !errors
+            .iter()
+            .any(|err| matches!(err, SemanticError::DivisionByZero { .. })),
+        "{:?}",
+        errors
+    );
+}
+
+#[test]
+fn test_pure_decorator_is_checked() {
+    let source = "\
+@pure
+def area(width, height):
+    return width * height
+
+@pure
+def noisy(x):
+    print(x)
+    return x
+";
+    let errors = analyze(source);
+    assert_eq!(errors.len(), 1, "{:?}", errors);
+    match &errors[0] {
+        SemanticError::ImpureFunction {
+            function_name,
+            reason,
+            line,
+            reason_line,
+            ..
+        } => {
+            assert_eq!(function_name, "noisy");
+            assert_eq!(reason, "calls 'print'");
+            assert_eq!((*line, *reason_line), (5, 7));
+        }
+        other => panic!("Expected ImpureFunction, got {:?}", other),
+    }
+}
@@ -0,0 +1,197 @@
+//! Tests for the type stubs of built-in modules
+
+use silk_parser::Parser;
+use silk_semantic::{stdlib, SemanticAnalyzer, SemanticError, SymbolKind, Type};
+
+/// Helper to parse and analyze source code, returning every error
+fn analyze(source: &str) -> Vec<SemanticError> {
+    let program = Parser::parse(source).expect("Parser should succeed");
+    let mut analyzer = SemanticAnalyzer::new_without_control_flow();
+    analyzer.analyze(&program).err().unwrap_or_default()
+}
+
+fn codes(source: &str) -> Vec<&'static str> {
+    analyze(source).iter().map(|err| err.code()).collect()
+}
+
+#[test]
+fn test_module_binding_is_typed() {
+    let program = Parser::parse("import math as m\nimport numpy\nprint(m, numpy)\n").unwrap();
+    let mut analyzer = SemanticAnalyzer::new_without_control_flow();
+    analyzer.analyze(&program).unwrap();
+
+    let symbol = analyzer.symbol_table().resolve_symbol("m").unwrap();
+    assert_eq!(symbol.kind, SymbolKind::Module);
+    assert_eq!(symbol.ty, Type::Module("math".to_string()));
+    assert_eq!(symbol.ty.to_string(), "module 'math'");
+
+    // Modules without stubs are not typed
+    let symbol = analyzer.symbol_table().resolve_symbol("numpy").unwrap();
+    assert_eq!(symbol.ty, Type::Unknown);
+}
+
+#[test]
+fn test_member_types() {
+    assert!(analyze("import math\nx: float = math.sqrt(2) + math.pi\nprint(x)\n").is_empty());
+    assert!(analyze("import math\nn: int = math.floor(2.5)\nprint(n)\n").is_empty());
+    assert_eq!(
+        codes("import math\nn: int = math.sqrt(2)\nprint(n)\n"),
+        ["assignment-type-mismatch"]
+    );
+    assert_eq!(
+        codes("from math import pi\nname: str = pi\nprint(name)\n"),
+        ["assignment-type-mismatch"]
+    );
+}
+
+#[test]
+fn test_calls_are_checked_against_stub_signatures() {
+    assert!(analyze("import math\nprint(math.isclose(1.0, 1.1, rel_tol=0.2))\n").is_empty());
+    assert!(analyze("from math import hypot\nprint(hypot(3, 4, 5))\n").is_empty());
+    assert_eq!(
+        codes("import math\nprint(math.sqrt(\"4\"))\n"),
+        ["argument-type-mismatch"]
+    );
+    assert_eq!(
+        codes("from math import sqrt\nprint(sqrt())\n"),
+        ["argument-count-mismatch"]
+    );
+    // `x` is positional-only
+    assert_eq!(codes("import math\nprint(math.sqrt(x=4))\n").len(), 1);
+}
+
+#[test]
+fn test_unknown_members_are_reported() {
+    let errors = analyze("import math\nprint(math.sqroot(2))\n");
+    assert_eq!(errors.len(), 1);
+    assert_eq!(
+        errors[0].to_string(),
+        "Module 'math' has no member 'sqroot' at line 2, column 7 (did you mean 'sqrt'?)"
+    );
+    assert_eq!(errors[0].error_code(), "E0205");
+
+    assert_eq!(
+        codes("from math import flor\nprint(flor)\n"),
+        ["unknown-module-member"]
+    );
+    // Modules without stubs and relative imports are not checked
+    assert!(analyze("import numpy\nprint(numpy.anything)\n").is_empty());
+    assert!(analyze("from .math import anything\nprint(anything)\n").is_empty());
+}
+
+#[test]
+fn test_json_stubs() {
+    let source = "import json\n\
+                  text: str = json.dumps({'a': [1]}, indent=2, sort_keys=True)\n\
+                  try:\n    data = json.loads(text)\nexcept json.JSONDecodeError:\n    data = None\n\
+                  print(data)\n";
+    assert!(analyze(source).is_empty(), "{:?}", analyze(source));
+    assert_eq!(
+        codes("import json\nprint(json.loads(1))\n"),
+        ["argument-type-mismatch"]
+    );
+    // Options are keyword-only
+    assert_eq!(
+        codes("import json\nprint(json.dumps([1], 2))\n"),
+        ["keyword-only-argument"]
+    );
+}
+
+#[test]
+fn test_random_and_time_stubs() {
+    let source = "import random\nimport time\n\
+                  random.seed(42)\n\
+                  roll: int = random.randint(1, 6)\n\
+                  start: float = time.perf_counter()\n\
+                  time.sleep(0.5)\n\
+                  print(roll, random.choice([1, 2]), time.time() - start)\n";
+    assert!(analyze(source).is_empty(), "{:?}", analyze(source));
+    assert_eq!(
+        codes("import random\nprint(random.randint(1))\n"),
+        ["argument-count-mismatch"]
+    );
+    assert_eq!(
+        codes("import time\nprint(time.sleep('1'))\n"),
+        ["argument-type-mismatch"]
+    );
+}
+
+#[test]
+fn test_every_listed_member_has_a_type() {
+    for module in stdlib::MODULES {
+        for name in stdlib::member_names(module).unwrap() {
+            assert!(
+                stdlib::member_type(module, name).is_some(),
+                "{}.{}",
+                module,
+                name
+            );
+        }
+    }
+}
+
+#[test]
+fn test_re_stubs() {
+    let source = "import re\n\
+                  words: list[str] = re.findall(r'\\w+', 'a b')\n\
+                  text: str = re.sub('[aeiou]', '', 'silk', count=1)\n\
+                  print(words, text, re.compile('x', re.IGNORECASE))\n";
+    assert!(analyze(source).is_empty(), "{:?}", analyze(source));
+    assert_eq!(
+        codes("import re\nprint(re.search('a'))\n"),
+        ["argument-count-mismatch"]
+    );
+}
+
+#[test]
+fn test_literal_regex_patterns_are_compiled() {
+    let errors = analyze("import re\nprint(re.compile(r'(\\d+'))\n");
+    assert_eq!(errors.len(), 1);
+    assert_eq!(
+        errors[0].to_string(),
+        "Invalid regular expression at line 2, column 18: unclosed group at position 0"
+    );
+    assert_eq!(errors[0].error_code(), "E0314");
+
+    // The syntax is that of the `regex` crate
+    assert_eq!(
+        codes("import re\nprint(re.match(r'(a)\\1', 'aa'))\n"),
+        ["invalid-regex"]
+    );
+    assert_eq!(
+        codes("import re\nprint(re.split(pattern='[a-', string='x'))\n"),
+        ["invalid-regex"]
+    );
+    // Positions count characters, not bytes
+    let errors = analyze("import re\nprint(re.compile('é('))\n");
+    assert!(errors[0].to_string().ends_with("at position 1"));
+
+    // Patterns with flags, and patterns that are not literals, are not checked
+    assert!(analyze("import re\nprint(re.compile('(', re.VERBOSE))\n").is_empty());
+    assert!(analyze("import re\np = '('\nprint(re.compile(p))\n").is_empty());
+}
+
+#[test]
+fn test_sys_and_os_stubs() {
+    let source = "import sys\nimport os.path\n\
+                  args: list[str] = sys.argv[1:]\n\
+                  home: str = os.environ['HOME']\n\
+                  config = os.path.join(home, '.silk', 'config.toml')\n\
+                  if not os.path.exists(config):\n    sys.exit(1)\n\
+                  print(args, os.getenv('USER', 'nobody'))\n";
+    assert!(analyze(source).is_empty(), "{:?}", analyze(source));
+
+    // Submodules are checked through the parent module and on their own
+    assert_eq!(
+        codes("import os\nprint(os.path.exist('x'))\n"),
+        ["unknown-module-member"]
+    );
+    assert_eq!(
+        codes("from os import path\nprint(path.join('a', 1))\n"),
+        ["argument-type-mismatch"]
+    );
+    assert_eq!(
+        codes("import os.path as p\nn: int = p.basename('a')\nprint(n)\n"),
+        ["assignment-type-mismatch"]
+    );
+}
@@ -0,0 +1,237 @@
+//! Tests for checking `%` formatting and `str.format` calls against their
+//! literal templates
+
+use silk_parser::Parser;
+use silk_semantic::{SemanticAnalyzer, SemanticError};
+
+/// Messages of the formatting errors in `source`
+fn format_errors(source: &str) -> Vec<String> {
+    let program = Parser::parse(source).expect("Parser should succeed");
+    SemanticAnalyzer::new_without_control_flow()
+        .analyze(&program)
+        .err()
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|err| match err {
+            SemanticError::InvalidFormat { message, .. } => Some(message),
+            _ => None,
+        })
+        .collect()
+}
+
+/// The one formatting error of `source`
+fn format_error(source: &str) -> String {
+    let errors = format_errors(source);
+    assert_eq!(errors.len(), 1, "{:?}", errors);
+    errors.into_iter().next().unwrap()
+}
+
+// ========== % FORMATTING ==========
+
+#[test]
+fn test_percent_argument_count() {
+    let prefix = "name = 'Ada'\n";
+    assert_eq!(
+        format_error(&format!("{}print('%s is %d' % (name,))\n", prefix)),
+        "not enough arguments for format string"
+    );
+    assert_eq!(
+        format_error(&format!("{}print('%s' % (name, 1))\n", prefix)),
+        "not all arguments converted during string formatting"
+    );
+    assert_eq!(
+        format_error("print('%*d' % (5,))\n"),
+        "not enough arguments for format string"
+    );
+    assert_eq!(
+        format_error("print('done' % 1)\n"),
+        "not all arguments converted during string formatting"
+    );
+}
+
+#[test]
+fn test_percent_conversion_types() {
+    assert_eq!(
+        format_error("name = 'Ada'\nprint('%d' % name)\n"),
+        "%d format: a real number is required, not str"
+    );
+    assert_eq!(
+        format_error("print('%x' % (1.5,))\n"),
+        "%x format: an integer is required, not float"
+    );
+    assert_eq!(
+        format_error("print('%.2f' % None)\n"),
+        "must be real number, not NoneType"
+    );
+    assert_eq!(
+        format_error("print('%c' % [1])\n"),
+        "%c requires int or char"
+    );
+}
+
+#[test]
+fn test_percent_template_errors() {
+    assert_eq!(
+        format_error("print('%y' % 1)\n"),
+        "unsupported format character 'y' (0x79) at index 1"
+    );
+    assert_eq!(format_error("print('100%' % 1)\n"), "incomplete format");
+    assert_eq!(
+        format_error("print('%(name' % {})\n"),
+        "incomplete format key"
+    );
+}
+
+#[test]
+fn test_percent_mappings() {
+    assert_eq!(
+        format_error("print('%(name)s' % ('Ada',))\n"),
+        "format requires a mapping"
+    );
+    assert_eq!(
+        format_error("print('%(name)s' % {'nam': 'Ada'})\n"),
+        "the format mapping has no key 'name'"
+    );
+    let source = "print('%(name)s is %(age)d' % {'name': 'Ada', 'age': 36})\n";
+    assert!(format_errors(source).is_empty());
+}
+
+#[test]
+fn test_valid_percent_formatting() {
+    let source = r#"
+def report(name: str, count: int, ratio: float, values: list[int], options):
+    print("%s: %5d items, %.1f%% (%r) %c" % (name, count, ratio * 100, values, name))
+    print("%-*s|%x|%o" % (10, name, count, True))
+    print("%s" % values)
+    print("%s %s" % options)
+    print("%d" % count)
+    print(f"{count}%d" % count)
+    print(count % 3)
+"#;
+    assert!(format_errors(source).is_empty());
+}
+
+// ========== str.format ==========
+
+#[test]
+fn test_format_arguments() {
+    assert_eq!(
+        format_error("print('{} and {}'.format(1))\n"),
+        "Replacement index 1 out of range for positional args tuple"
+    );
+    assert_eq!(
+        format_error("print('{name}'.format(nam=1))\n"),
+        "no argument named 'name' for the format string"
+    );
+    assert_eq!(
+        format_error("print('{:{}}'.format(1))\n"),
+        "Replacement index 1 out of range for positional args tuple"
+    );
+}
+
+#[test]
+fn test_format_numbering_and_syntax() {
+    assert_eq!(
+        format_error("print('{0} {}'.format(1, 2))\n"),
+        "cannot switch from manual field specification to automatic field numbering"
+    );
+    assert_eq!(
+        format_error("print('{} {0}'.format(1, 2))\n"),
+        "cannot switch from automatic field numbering to manual field specification"
+    );
+    assert_eq!(
+        format_error("print('a}'.format())\n"),
+        "Single '}' encountered in format string"
+    );
+    assert_eq!(
+        format_error("print('{0'.format(1))\n"),
+        "expected '}' before end of string"
+    );
+    assert_eq!(
+        format_error("print('{!x}'.format(1))\n"),
+        "Unknown conversion specifier x"
+    );
+}
+
+#[test]
+fn test_format_codes() {
+    assert_eq!(
+        format_error("print('{:d}'.format('x'))\n"),
+        "Unknown format code 'd' for object of type 'str'"
+    );
+    assert_eq!(
+        format_error("print('{total:d}'.format(total=1.5))\n"),
+        "Unknown format code 'd' for object of type 'float'"
+    );
+    assert_eq!(
+        format_error("print('{:>8}'.format([1]))\n"),
+        "unsupported format string passed to list.__format__"
+    );
+}
+
+#[test]
+fn test_valid_format_calls() {
+    let source = r#"
+def report(name: str, count: int, ratio: float, extra: dict[str, int], thing):
+    print("{} has {:>5d} items ({:.1%})".format(name, count, ratio))
+    print("{0}, {0!r:>10}, {1:x}, {1:.2f}".format(name, count))
+    print("{name} {ratio:{width}.2f} {{literal}}".format(name=name, ratio=ratio, width=count))
+    print("{0.real} {0[0]}".format(count))
+    print("{missing}".format(**extra))
+    print("{:d} {:s}".format(thing, thing))
+    print("{:%Y-%m-%d}".format(thing))
+"#;
+    assert!(format_errors(source).is_empty());
+}
+
+#[test]
+fn test_invalid_format_is_an_error() {
+    let program = Parser::parse("print('{} {}'.format(1))\n").unwrap();
+    let errors = SemanticAnalyzer::new_without_control_flow()
+        .analyze(&program)
+        .unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].code(), "invalid-format");
+    assert!(!errors[0].is_warning());
+    assert_eq!(
+        errors[0].to_string(),
+        "Invalid string formatting at line 1, column 7: Replacement index 1 out of range \
+         for positional args tuple"
+    );
+}
+
+// ========== ASSIGNMENTS ==========
+
+/// Codes of every error in `source`
+fn error_codes(source: &str) -> Vec<&'static str> {
+    let program = Parser::parse(source).expect("Parser should succeed");
+    SemanticAnalyzer::new_without_control_flow()
+        .analyze(&program)
+        .err()
+        .unwrap_or_default()
+        .iter()
+        .map(|err| err.code())
+        .collect()
+}
+
+#[test]
+fn test_percent_formatting_in_assignments() {
+    let source = r#"
+count = 3
+ratio = 0.5
+label = "%d items" % 3
+summary = "%s: %d (%.0f%%)" % (label, count, ratio)
+items: str = "%s" % [1, 2]
+length = len("%d" % count)
+"#;
+    assert!(error_codes(source).is_empty(), "{:?}", error_codes(source));
+
+    // An invalid template is a formatting error, not an operand type error
+    assert_eq!(error_codes("label = '%d %d' % 3\n"), ["invalid-format"]);
+    assert_eq!(error_codes("label = '%d' % 'three'\n"), ["invalid-format"]);
+    // Other operands are still checked
+    assert_eq!(
+        error_codes("remainder = 3 % 'three'\n"),
+        ["invalid-binary-operation"]
+    );
+}
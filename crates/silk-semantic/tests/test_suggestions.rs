@@ -0,0 +1,188 @@
+//! Tests for "did you mean" suggestions on undefined names and attributes
+
+use silk_parser::Parser;
+use silk_semantic::{SemanticAnalyzer, SemanticError};
+
+/// Helper to parse and analyze source code, returning every error
+fn analyze(source: &str) -> Vec<SemanticError> {
+    let program = Parser::parse(source).expect("Parser should succeed");
+    let mut analyzer = SemanticAnalyzer::new_without_control_flow();
+    analyzer.analyze(&program).err().unwrap_or_default()
+}
+
+/// (class, attribute, suggestion) of every unknown-attribute error
+fn unknown_attributes(source: &str) -> Vec<(String, String, Option<String>)> {
+    analyze(source)
+        .into_iter()
+        .filter_map(|err| match err {
+            SemanticError::UnknownAttribute {
+                class_name,
+                attr,
+                suggestion,
+                ..
+            } => Some((class_name, attr, suggestion)),
+            _ => None,
+        })
+        .collect()
+}
+
+const POINT: &str = r#"
+class Shape:
+    sides = 0
+
+    def area(self) -> float:
+        return 0.0
+
+class Point(Shape):
+    origin: int
+
+    def __init__(self, x: int):
+        self.x = x
+        if x > 0:
+            self.positive = True
+
+    def length(self) -> int:
+        return self.x
+
+p = Point(1)
+"#;
+
+// ========== UNDEFINED NAMES ==========
+
+#[test]
+fn test_undefined_name_suggests_similar_variable() {
+    let errors = analyze("counter = 1\nprint(countr)\n");
+    assert_eq!(errors.len(), 1);
+    assert_eq!(
+        errors[0].to_string(),
+        "Undefined variable 'countr' at line 2, column 7 (did you mean 'counter'?)"
+    );
+}
+
+#[test]
+fn test_undefined_name_suggests_builtin() {
+    let errors = analyze("prnt(1)\n");
+    assert!(
+        matches!(&errors[..], [SemanticError::UndefinedVariable { suggestion: Some(s), .. }] if s == "print"),
+        "{:?}",
+        errors
+    );
+}
+
+#[test]
+fn test_undefined_name_without_similar_name() {
+    let errors = analyze("value = 1\nprint(zebra)\n");
+    assert!(
+        matches!(
+            &errors[..],
+            [SemanticError::UndefinedVariable {
+                suggestion: None,
+                ..
+            }]
+        ),
+        "{:?}",
+        errors
+    );
+    assert_eq!(
+        errors[0].to_string(),
+        "Undefined variable 'zebra' at line 2, column 7"
+    );
+}
+
+#[test]
+fn test_suggestion_only_uses_visible_names() {
+    let source = "def f():\n    total = 1\n    return total\n\nprint(totl)\n";
+    let errors = analyze(source);
+    assert!(
+        matches!(
+            &errors[..],
+            [SemanticError::UndefinedVariable {
+                suggestion: None,
+                ..
+            }]
+        ),
+        "{:?}",
+        errors
+    );
+}
+
+// ========== UNKNOWN ATTRIBUTES ==========
+
+#[test]
+fn test_known_members_are_accepted() {
+    let source = format!(
+        "{}\nprint(p.x, p.positive, p.origin, p.sides, p.area(), p.length(), Point.sides, p.__class__)\n",
+        POINT
+    );
+    assert!(analyze(&source).is_empty(), "{:?}", analyze(&source));
+}
+
+#[test]
+fn test_unknown_method_suggests_similar_member() {
+    let source = format!("{}\np.lenght()\n", POINT);
+    assert_eq!(
+        unknown_attributes(&source),
+        vec![(
+            "Point".to_string(),
+            "lenght".to_string(),
+            Some("length".to_string())
+        )]
+    );
+}
+
+#[test]
+fn test_unknown_attribute_message() {
+    let source = format!("{}\nprint(p.aera)\n", POINT);
+    let errors = analyze(&source);
+    assert_eq!(errors.len(), 1, "{:?}", errors);
+    assert_eq!(
+        errors[0].to_string(),
+        "Class 'Point' has no attribute 'aera' at line 21, column 7 (did you mean 'area'?)"
+    );
+}
+
+#[test]
+fn test_unknown_attribute_on_self_and_class() {
+    let source = r#"
+class Counter:
+    def __init__(self):
+        self.count = 0
+
+    def bump(self):
+        self.cout += 1
+
+print(Counter.missing)
+"#;
+    assert_eq!(
+        unknown_attributes(source),
+        vec![
+            (
+                "Counter".to_string(),
+                "cout".to_string(),
+                Some("count".to_string())
+            ),
+            ("Counter".to_string(), "missing".to_string(), None),
+        ]
+    );
+}
+
+#[test]
+fn test_dynamic_classes_are_not_checked() {
+    let source = r#"
+import json
+
+class Proxy:
+    def __getattr__(self, name):
+        return name
+
+class Derived(json.JSONEncoder):
+    pass
+
+class Unknown(Base):
+    pass
+
+def f(proxy: Proxy, derived: Derived, unknown: Unknown):
+    print(proxy.anything, derived.anything, unknown.anything)
+"#;
+    assert!(unknown_attributes(source).is_empty());
+}
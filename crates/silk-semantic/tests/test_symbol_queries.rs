@@ -0,0 +1,207 @@
+//! Tests for symbol table queries after analysis: symbol at a span,
+//! references of a symbol and symbols visible at a position
+
+use silk_lexer::Span;
+use silk_parser::Parser;
+use silk_semantic::{ScopeKind, SemanticAnalyzer, SymbolKind, SymbolTable};
+
+fn analyze(source: &str) -> SemanticAnalyzer {
+    let program = Parser::parse(source).expect("Parser should succeed");
+    let mut analyzer = SemanticAnalyzer::new_without_control_flow();
+    let _ = analyzer.analyze(&program);
+    analyzer
+}
+
+/// Span of the `nth` occurrence of `text` in `source`
+fn span_of(source: &str, text: &str, nth: usize) -> Span {
+    let (start, _) = source.match_indices(text).nth(nth).expect("text not found");
+    let line = source[..start].matches('\n').count() + 1;
+    let column = start - source[..start].rfind('\n').map_or(0, |i| i + 1) + 1;
+    Span::new(start, start + text.len(), line, column)
+}
+
+/// Source text of every reference of the symbol used at `span`
+fn reference_lines(table: &SymbolTable, source: &str, span: Span) -> Vec<usize> {
+    let id = table.symbol_id_at(span).expect("no symbol at span");
+    table
+        .references(id)
+        .iter()
+        .map(|span| {
+            assert!(source[span.start..].starts_with(&id.name));
+            span.line
+        })
+        .collect()
+}
+
+fn visible_names(table: &SymbolTable, offset: usize) -> Vec<String> {
+    table
+        .visible_symbols(offset)
+        .into_iter()
+        .map(|symbol| symbol.name.clone())
+        .collect()
+}
+
+// ========== SYMBOL AT SPAN ==========
+
+#[test]
+fn test_reference_resolves_to_its_definition() {
+    let source = "count = 1\nprint(count + 1)\n";
+    let analyzer = analyze(source);
+    let table = analyzer.symbol_table();
+    let symbol = table.symbol_at(span_of(source, "count", 1)).unwrap();
+    assert_eq!(symbol.name, "count");
+    assert_eq!(symbol.kind, SymbolKind::Variable);
+    assert_eq!(symbol.span, span_of(source, "count", 0));
+}
+
+#[test]
+fn test_local_reference_resolves_to_the_parameter() {
+    let source = r#"
+value = 1
+
+def f(value):
+    return value
+
+print(f(value))
+"#;
+    let analyzer = analyze(source);
+    let table = analyzer.symbol_table();
+    let local = table.symbol_at(span_of(source, "value", 2)).unwrap();
+    assert_eq!(local.kind, SymbolKind::Parameter);
+    let global = table.symbol_id_at(span_of(source, "value", 3)).unwrap();
+    assert_eq!(global.scope, 0);
+}
+
+#[test]
+fn test_unresolved_names_have_no_symbol() {
+    let source = "print(missing)\n";
+    let analyzer = analyze(source);
+    let table = analyzer.symbol_table();
+    assert!(table.symbol_at(span_of(source, "missing", 0)).is_none());
+    assert!(table.symbol_at(span_of(source, "print", 0)).is_none());
+}
+
+// ========== REFERENCES ==========
+
+#[test]
+fn test_references_include_definitions_and_reads() {
+    let source = r#"
+total = 0
+total += 1
+
+def show():
+    return total
+
+total = total * 2
+"#;
+    let analyzer = analyze(source);
+    let table = analyzer.symbol_table();
+    assert_eq!(
+        reference_lines(table, source, span_of(source, "total", 0)),
+        [2, 3, 6, 8, 8]
+    );
+}
+
+#[test]
+fn test_references_stay_within_their_scope() {
+    let source = r#"
+def first(x):
+    return x
+
+def second(x):
+    return x + 1
+
+print(first(1), second(2))
+"#;
+    let analyzer = analyze(source);
+    let table = analyzer.symbol_table();
+    assert_eq!(
+        reference_lines(table, source, span_of(source, "x", 1)),
+        [2, 3]
+    );
+    assert_eq!(
+        reference_lines(table, source, span_of(source, "x", 3)),
+        [5, 6]
+    );
+}
+
+#[test]
+fn test_comprehension_variables_are_tracked() {
+    let source = "items = [1, 2]\nsquares = [n * n for n in items]\n";
+    let analyzer = analyze(source);
+    let table = analyzer.symbol_table();
+    let id = table.symbol_id_at(span_of(source, "n", 2)).unwrap();
+    assert_eq!(table.scopes()[id.scope].kind, ScopeKind::Comprehension);
+    assert_eq!(table.references(id).len(), 3);
+}
+
+// ========== VISIBLE SYMBOLS ==========
+
+#[test]
+fn test_visible_symbols_in_function_body() {
+    let source = r#"
+limit = 10
+
+def clamp(n):
+    result = n
+    return result
+
+done = True
+"#;
+    let analyzer = analyze(source);
+    let table = analyzer.symbol_table();
+    let inside = source.find("return result").unwrap();
+    assert_eq!(
+        visible_names(table, inside),
+        ["clamp", "done", "limit", "n", "result"]
+    );
+    let after = source.find("done").unwrap();
+    assert_eq!(visible_names(table, after), ["clamp", "done", "limit"]);
+}
+
+#[test]
+fn test_class_body_is_not_visible_from_methods() {
+    let source = r#"
+class Counter:
+    step = 1
+
+    def bump(self, amount):
+        return amount
+
+print(Counter)
+"#;
+    let analyzer = analyze(source);
+    let table = analyzer.symbol_table();
+    let in_method = source.find("return amount").unwrap();
+    assert_eq!(
+        visible_names(table, in_method),
+        ["Counter", "amount", "self"]
+    );
+    let in_class = source.find("step").unwrap();
+    assert_eq!(visible_names(table, in_class), ["Counter", "bump", "step"]);
+}
+
+#[test]
+fn test_inner_definitions_hide_outer_ones() {
+    let source = "x = 1\nf = lambda x: x\n";
+    let analyzer = analyze(source);
+    let table = analyzer.symbol_table();
+    let body = source.rfind('x').unwrap();
+    let visible = table.visible_symbols(body);
+    let x = visible.iter().find(|symbol| symbol.name == "x").unwrap();
+    assert_eq!(x.kind, SymbolKind::Parameter);
+}
+
+#[test]
+fn test_scope_spans_end_with_the_block() {
+    let source = "def f():\n    if True:\n        y = 1\n    return 0\nz = 2\n";
+    let analyzer = analyze(source);
+    let table = analyzer.symbol_table();
+    let function = table.scope_at(source.find("return").unwrap());
+    let span = table.scopes()[function].span().unwrap();
+    assert_eq!(
+        &source[span.start..span.end],
+        "def f():\n    if True:\n        y = 1\n    return 0\n"
+    );
+    assert_eq!(table.scope_at(source.find('z').unwrap()), 0);
+}
@@ -297,3 +297,36 @@ fn test_class_scope() {
     table.exit_scope().unwrap();
     assert!(table.resolve_symbol("method").is_none());
 }
+
+// ========== POSITION QUERY TESTS ==========
+
+#[test]
+fn test_definitions_and_references_are_queryable() {
+    let mut table = SymbolTable::new();
+    let definition = Span::new(0, 1, 1, 1);
+    let reference = Span::new(10, 11, 2, 7);
+    let symbol = Symbol::new("x".to_string(), SymbolKind::Variable, definition);
+    table.define_symbol(symbol).unwrap();
+    assert!(table.record_reference("x", reference));
+    assert!(!table.record_reference("y", Span::new(20, 21, 3, 1)));
+
+    let id = table.symbol_id_at(reference).unwrap().clone();
+    assert_eq!(id.scope, 0);
+    assert_eq!(table.symbol_at(reference).unwrap().span, definition);
+    assert_eq!(table.references(&id), [definition, reference]);
+}
+
+#[test]
+fn test_scope_ranges_are_kept_after_exit() {
+    let mut table = SymbolTable::new();
+    table.enter_scope_at(ScopeKind::Function, Span::new(10, 50, 2, 1));
+    let local = Symbol::new("local".to_string(), SymbolKind::Variable, make_span(3, 5));
+    table.define_symbol(local).unwrap();
+    table.exit_scope().unwrap();
+
+    assert_eq!(table.scopes().len(), 2);
+    assert_eq!(table.scope_at(20), 1);
+    assert_eq!(table.scope_at(50), 0);
+    assert_eq!(table.visible_symbols(20).len(), 1);
+    assert!(table.visible_symbols(5).is_empty());
+}
@@ -2,7 +2,6 @@
 /// 
 /// These tests verify that control flow analysis correctly determines reachability
 /// after try/except blocks when returns are involved.
-
 use silk_parser::Parser;
 use silk_semantic::{ControlFlowAnalyzer, SemanticError};
 
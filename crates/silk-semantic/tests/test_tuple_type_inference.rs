@@ -182,3 +182,45 @@ fn test_tuple_bool_and_none() {
         Type::Tuple(vec![Type::Bool, Type::Bool, Type::None])
     );
 }
+
+#[test]
+fn test_tuple_slice_is_a_tuple() {
+    let source = "t = (1, \"a\", 2.5, True)\nhead = t[:2]\ntail = t[1:]\nlast = t[-1:]\nodd = t[::2]\nback = t[::-1]\nnone = t[3:1]";
+    let program = Parser::parse(source).unwrap();
+
+    let mut analyzer = SemanticAnalyzer::new_without_control_flow();
+    analyzer.analyze(&program).unwrap();
+
+    let ty = |name: &str| {
+        analyzer
+            .symbol_table()
+            .resolve_symbol(name)
+            .unwrap()
+            .ty
+            .clone()
+    };
+    assert_eq!(ty("head"), Type::Tuple(vec![Type::Int, Type::Str]));
+    assert_eq!(
+        ty("tail"),
+        Type::Tuple(vec![Type::Str, Type::Float, Type::Bool])
+    );
+    assert_eq!(ty("last"), Type::Tuple(vec![Type::Bool]));
+    assert_eq!(ty("odd"), Type::Tuple(vec![Type::Int, Type::Float]));
+    assert_eq!(
+        ty("back"),
+        Type::Tuple(vec![Type::Bool, Type::Float, Type::Str, Type::Int])
+    );
+    assert_eq!(ty("none"), Type::Tuple(vec![]));
+}
+
+#[test]
+fn test_tuple_slice_with_unknown_bounds() {
+    let source = "n = 1\nt = (1, \"a\")\nrest = t[n:]";
+    let program = Parser::parse(source).unwrap();
+
+    let mut analyzer = SemanticAnalyzer::new_without_control_flow();
+    analyzer.analyze(&program).unwrap();
+
+    let symbol = analyzer.symbol_table().resolve_symbol("rest").unwrap();
+    assert_eq!(symbol.ty, Type::Unknown);
+}
@@ -0,0 +1,241 @@
+//! Tests for flow-sensitive type narrowing (isinstance, None checks)
+
+use silk_parser::Parser;
+use silk_semantic::{SemanticAnalyzer, SemanticError};
+
+/// Helper to parse and analyze source code
+fn analyze(source: &str) -> Result<(), Vec<SemanticError>> {
+    let program = Parser::parse(source).expect("Parser should succeed");
+    let mut analyzer = SemanticAnalyzer::new_without_control_flow();
+    analyzer.analyze(&program)
+}
+
+fn assert_ok(source: &str) {
+    let result = analyze(source);
+    assert!(
+        result.is_ok(),
+        "Expected no errors, got: {:?}",
+        result.err()
+    );
+}
+
+// ========== OPTIONAL TYPES ==========
+
+#[test]
+fn test_optional_annotation_requires_narrowing() {
+    let source = r#"
+def f(x: int | None) -> int:
+    return x + 1
+"#;
+    let errors = analyze(source).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        errors[0],
+        SemanticError::InvalidBinaryOperation { .. }
+    ));
+}
+
+#[test]
+fn test_optional_generic_annotation() {
+    let source = r#"
+def f(x: Optional[int]) -> int:
+    return x
+"#;
+    let errors = analyze(source).unwrap_err();
+    assert!(
+        matches!(&errors[0], SemanticError::ReturnTypeMismatch { actual_type, .. } if actual_type == "int | None")
+    );
+}
+
+// ========== NONE CHECKS ==========
+
+#[test]
+fn test_is_not_none_narrows_then_branch() {
+    assert_ok(
+        r#"
+def f(x: int | None) -> int:
+    if x is not None:
+        return x + 1
+    return 0
+"#,
+    );
+}
+
+#[test]
+fn test_is_none_narrows_else_branch() {
+    assert_ok(
+        r#"
+def f(x: int | None) -> int:
+    if x is None:
+        return 0
+    else:
+        return x
+"#,
+    );
+}
+
+#[test]
+fn test_early_return_narrows_rest_of_block() {
+    assert_ok(
+        r#"
+def f(x: int | None) -> int:
+    if x is None:
+        return 0
+    y = x * 2
+    return y
+"#,
+    );
+}
+
+#[test]
+fn test_assert_narrows_rest_of_block() {
+    assert_ok(
+        r#"
+def f(x: int | None, y: int | str) -> int:
+    assert x is not None
+    assert isinstance(y, int), "y is not an int"
+    return x + y
+"#,
+    );
+
+    // The narrowing ends with the block
+    let source = r#"
+def f(x: int | None, flag: bool) -> int:
+    if flag:
+        assert x is not None
+    return x + 1
+"#;
+    let errors = analyze(source).unwrap_err();
+    assert_eq!(errors.len(), 1, "{:?}", errors);
+    assert!(matches!(
+        errors[0],
+        SemanticError::InvalidBinaryOperation { .. }
+    ));
+}
+
+#[test]
+fn test_equality_with_none_and_truthiness() {
+    assert_ok(
+        r#"
+def f(x: int | None, y: int | None) -> int:
+    if x == None:
+        return 0
+    if y:
+        return x + y
+    return x
+"#,
+    );
+}
+
+#[test]
+fn test_and_combines_narrowings() {
+    assert_ok(
+        r#"
+def f(x: int | None, y: int | None) -> int:
+    if x is not None and y is not None:
+        return x + y
+    return 0
+"#,
+    );
+}
+
+#[test]
+fn test_or_narrows_after_early_exit() {
+    assert_ok(
+        r#"
+def f(x: int | None, y: int | None) -> int:
+    if x is None or y is None:
+        return 0
+    return x + y
+"#,
+    );
+}
+
+#[test]
+fn test_conditional_expression_narrows() {
+    assert_ok(
+        r#"
+def f(x: int | None) -> int:
+    return x + 1 if x is not None else 0
+"#,
+    );
+}
+
+// ========== ISINSTANCE ==========
+
+#[test]
+fn test_isinstance_narrows_both_branches() {
+    assert_ok(
+        r#"
+def f(v: int | str) -> int:
+    if isinstance(v, int):
+        return v
+    else:
+        return len(v)
+"#,
+    );
+}
+
+#[test]
+fn test_not_isinstance_with_tuple() {
+    assert_ok(
+        r#"
+def f(v: int | float | str) -> str:
+    if not isinstance(v, (int, float)):
+        return v
+    return str(v)
+"#,
+    );
+}
+
+#[test]
+fn test_isinstance_user_class() {
+    assert_ok(
+        r#"
+class Animal:
+    pass
+
+class Dog(Animal):
+    def bark(self) -> str:
+        return "woof"
+
+def speak(a: Animal | None) -> str:
+    if isinstance(a, Dog):
+        return a.bark()
+    return ""
+"#,
+    );
+}
+
+// ========== INVALIDATION ==========
+
+#[test]
+fn test_narrowing_ends_with_branch() {
+    let source = r#"
+def f(x: int | None) -> int:
+    if x is not None:
+        y = x + 1
+    return x
+"#;
+    let errors = analyze(source).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        errors[0],
+        SemanticError::ReturnTypeMismatch { .. }
+    ));
+}
+
+#[test]
+fn test_assignment_drops_narrowing() {
+    let source = r#"
+def f(x: int | None) -> int:
+    if x is None:
+        return 0
+    x = None
+    return x
+"#;
+    let errors = analyze(source).unwrap_err();
+    assert!(
+        matches!(&errors[0], SemanticError::ReturnTypeMismatch { actual_type, .. } if actual_type == "None")
+    );
+}
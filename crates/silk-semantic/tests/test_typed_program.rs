@@ -0,0 +1,173 @@
+//! Tests for typed program output: expression types recorded by
+//! `SemanticAnalyzer::analyze_typed`
+
+use silk_parser::Parser;
+use silk_semantic::{SemanticAnalyzer, Type, TypedProgram};
+
+fn analyze_typed(source: &str) -> TypedProgram {
+    let program = Parser::parse(source).expect("Parser should succeed");
+    SemanticAnalyzer::new().analyze_typed(program)
+}
+
+/// Type of the expression spelled exactly `text`, at its `nth` occurrence
+fn type_of_text(typed: &TypedProgram, source: &str, text: &str, nth: usize) -> Option<Type> {
+    let (start, _) = source.match_indices(text).nth(nth)?;
+    typed
+        .expression_types()
+        .into_iter()
+        .find(|(span, _)| span.start == start && span.end == start + text.len())
+        .map(|(_, ty)| ty.clone())
+}
+
+#[test]
+fn test_literal_and_operator_types() {
+    let source = "x = 1 + 2.5\ny = \"a\" + \"b\"\nz = [1, 2]\n";
+    let typed = analyze_typed(source);
+    assert_eq!(type_of_text(&typed, source, "1", 0), Some(Type::Int));
+    assert_eq!(
+        type_of_text(&typed, source, "1 + 2.5", 0),
+        Some(Type::Float)
+    );
+    assert_eq!(
+        type_of_text(&typed, source, "\"a\" + \"b\"", 0),
+        Some(Type::Str)
+    );
+    assert_eq!(
+        type_of_text(&typed, source, "[1, 2]", 0),
+        Some(Type::List(Box::new(Type::Int)))
+    );
+}
+
+#[test]
+fn test_assignment_targets_and_reads_are_typed() {
+    let source = "count: float = 1\nname = \"silk\"\nprint(name, count)\n";
+    let typed = analyze_typed(source);
+    assert_eq!(type_of_text(&typed, source, "count", 0), Some(Type::Float));
+    assert_eq!(type_of_text(&typed, source, "count", 1), Some(Type::Float));
+    assert_eq!(type_of_text(&typed, source, "name", 0), Some(Type::Str));
+    assert_eq!(type_of_text(&typed, source, "name", 1), Some(Type::Str));
+}
+
+#[test]
+fn test_call_results_use_the_return_type() {
+    let source = r#"
+def label(count: int) -> str:
+    return str(count)
+
+text = label(3)
+"#;
+    let typed = analyze_typed(source);
+    assert_eq!(type_of_text(&typed, source, "label(3)", 0), Some(Type::Str));
+    assert_eq!(type_of_text(&typed, source, "count", 1), Some(Type::Int));
+}
+
+#[test]
+fn test_narrowed_types_are_recorded_where_used() {
+    let source = r#"
+def f(value: int | None) -> int:
+    if value is not None:
+        return value
+    return 0
+"#;
+    let typed = analyze_typed(source);
+    assert_eq!(
+        type_of_text(&typed, source, "value", 1),
+        Some(Type::union([Type::Int, Type::None]))
+    );
+    assert_eq!(type_of_text(&typed, source, "value", 2), Some(Type::Int));
+}
+
+#[test]
+fn test_type_at_offset_finds_the_innermost_expression() {
+    let source = "total = 1 + 2 * 3.0\n";
+    let typed = analyze_typed(source);
+    let (span, ty) = typed.type_at_offset(source.find('2').unwrap()).unwrap();
+    assert_eq!(&source[span.start..span.end], "2");
+    assert_eq!(ty, &Type::Int);
+
+    let (span, ty) = typed.type_at_offset(source.find('*').unwrap()).unwrap();
+    assert_eq!(&source[span.start..span.end], "2 * 3.0");
+    assert_eq!(ty, &Type::Float);
+
+    assert!(typed.type_at_offset(source.len()).is_none());
+}
+
+#[test]
+fn test_type_of_program_expressions() {
+    let source = "flag = not 1\n";
+    let typed = analyze_typed(source);
+    let silk_ast::StatementKind::Assign { targets, value, .. } =
+        &typed.program().statements[0].kind
+    else {
+        panic!("Expected an assignment");
+    };
+    assert_eq!(typed.type_of(value), Some(&Type::Bool));
+    assert_eq!(typed.type_of(&targets[0]), Some(&Type::Bool));
+}
+
+#[test]
+fn test_expression_types_are_in_source_order() {
+    let typed = analyze_typed("a = 1\nb = a + 2\n");
+    let starts: Vec<_> = typed
+        .expression_types()
+        .iter()
+        .map(|(span, _)| span.start)
+        .collect();
+    let mut sorted = starts.clone();
+    sorted.sort();
+    assert_eq!(starts, sorted);
+    assert_eq!(typed.len(), 6);
+}
+
+#[test]
+fn test_typed_program_keeps_errors() {
+    let source = "x: int = \"text\"\nprint(x)\n";
+    let typed = analyze_typed(source);
+    assert!(typed.has_errors());
+    assert_eq!(typed.errors().len(), 1);
+    assert_eq!(typed.errors()[0].code(), "assignment-type-mismatch");
+    assert_eq!(type_of_text(&typed, source, "x", 0), Some(Type::Int));
+    assert_eq!(type_of_text(&typed, source, "\"text\"", 0), Some(Type::Str));
+}
+
+#[test]
+fn test_typed_analysis_reports_the_same_diagnostics() {
+    let source = "def f(a):\n    return 1\n\nx = f(1) + \"a\"\n";
+    let program = Parser::parse(source).unwrap();
+    let plain = SemanticAnalyzer::new().analyze(&program).unwrap_err();
+    let typed = SemanticAnalyzer::new().analyze_typed(program);
+    assert_eq!(typed.errors(), plain.as_slice());
+    assert!(!typed.is_empty());
+}
+
+#[test]
+fn test_warnings_alone_are_not_errors() {
+    let typed = analyze_typed("list = [1]\nprint(list)\n");
+    assert_eq!(typed.errors().len(), 1);
+    assert!(!typed.has_errors());
+}
+
+#[test]
+fn test_node_index_finds_nodes_by_position() {
+    use silk_ast::{ExpressionKind, Node, StatementKind};
+
+    let source = "def area(side: float) -> float:\n    return side * side\n";
+    let typed = analyze_typed(source);
+    let index = typed.node_index();
+
+    let offset = source.find("side * side").unwrap();
+    let nodes = index.nodes_at(offset);
+    assert_eq!(nodes.len(), 4);
+    assert!(matches!(
+        nodes[0],
+        Node::Statement(stmt) if matches!(stmt.kind, StatementKind::FunctionDef { .. })
+    ));
+    let expr = index.expression_at(offset).unwrap();
+    assert_eq!(expr.kind, ExpressionKind::Identifier("side".to_string()));
+    assert_eq!(typed.type_of(expr), Some(&Type::Float));
+
+    let ret = index.statement_at(offset).unwrap();
+    assert!(matches!(ret.kind, StatementKind::Return { .. }));
+    assert_eq!(index.statement(ret.span), Some(ret));
+    assert!(index.node_at(source.len()).is_none());
+}
@@ -1,10 +1,9 @@
-use silk_lexer::Lexer;
 use silk_parser::Parser;
 use silk_semantic::{ControlFlowAnalyzer, SemanticError};
 
 /// Helper to parse source and run control flow analysis
-/// Filters out UnusedFunction and UnusedVariable errors since we're testing
-/// unreachable code detection, not whether test functions are called.
+/// Filters out UnusedFunction, UnusedVariable and ConstantCondition errors since
+/// we're testing unreachable code detection, not whether test functions are called.
 fn analyze_control_flow(source: &str) -> Result<(), Vec<SemanticError>> {
     let program = Parser::parse(source).expect("Parser failed");
     
@@ -19,7 +18,8 @@ fn analyze_control_flow(source: &str) -> Result<(), Vec<SemanticError>> {
                 .into_iter()
                 .filter(|e| !matches!(e,
                     SemanticError::UnusedFunction { .. } |
-                    SemanticError::UnusedVariable { .. }
+                    SemanticError::UnusedVariable { .. } |
+                    SemanticError::ConstantCondition { .. }
                 ))
                 .collect();
             
@@ -194,7 +194,7 @@ def executor(func):
 
 result = executor(callback)
 "#;
-    let result = analyze(source);
+    let _result = analyze(source);
     // Note: callback is referenced as an identifier (not called), so it's tracked as "used" variable
     // but not as "called" function. This is acceptable - we're detecting truly unused functions
     // In practice, this should not warn since callback is used in some way
@@ -1,7 +1,6 @@
 /// Tests for Step 15: Unused Variable Detection
 ///
 /// Verifies that the semantic analyzer can detect variables that are assigned but never used.
-
 use silk_parser::Parser;
 use silk_semantic::{ControlFlowAnalyzer, SemanticError};
 
@@ -67,10 +66,11 @@ def foo(x, y):
     // Should detect 'y' as unused parameter
     assert_eq!(errors.len(), 1);
     match &errors[0] {
-        SemanticError::UnusedVariable { name, .. } => {
+        SemanticError::UnusedParameter { name, function_name, .. } => {
             assert_eq!(name, "y");
+            assert_eq!(function_name, "foo");
         }
-        _ => panic!("Expected UnusedVariable error, got {:?}", errors[0]),
+        _ => panic!("Expected UnusedParameter error, got {:?}", errors[0]),
     }
 }
 
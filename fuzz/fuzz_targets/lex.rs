@@ -0,0 +1,23 @@
+//! Fuzz the lexer: any UTF-8 input must lex without panicking, recovery must
+//! account for every error, and lossless tokens must reproduce the input
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use silk_lexer::{render_lossless, Lexer, TokenKind};
+
+fuzz_target!(|source: &str| {
+    let (tokens, errors) = Lexer::new(source).tokenize_lossy();
+    assert_eq!(
+        tokens.last().map(|token| &token.kind),
+        Some(&TokenKind::Eof)
+    );
+    let error_tokens = tokens
+        .iter()
+        .filter(|token| token.kind == TokenKind::Error)
+        .count();
+    assert_eq!(error_tokens, errors.len());
+
+    if let Ok(tokens) = Lexer::new(source).tokenize_lossless() {
+        assert_eq!(render_lossless(&tokens), source);
+    }
+});